@@ -0,0 +1,313 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! Defines a struct that describes how to rotate, scale, mirror, shear and move objects in
+//! two-dimensional space.
+
+use crate::Coordinate; //The transformed coordinates are rounded to coordinates.
+use crate::OverflowError; //If the transformed coordinates don't fit in a coordinate.
+use crate::Point2D; //To transform points.
+
+/// An affine transformation, which maps every point to a new point with a linear function.
+///
+/// The transformation is stored as a 2 by 3 matrix. A point `(x, y)` is transformed into:
+/// * `x' = xx * x + xy * y + dx`
+/// * `y' = yx * x + yy * y + dy`
+///
+/// # Rounding
+/// The matrix is stored with 64-bit floating point numbers, so that rotations can be represented.
+/// The transformed coordinates are computed in 64-bit floating point, in the order of the formulas
+/// above, and then rounded to the nearest `Coordinate`. Halves are rounded up, towards positive
+/// infinity, by computing `floor(value + 0.5)`. The GPU implementations compute in the same order,
+/// but the GPU may fuse a multiplication and an addition into a single operation that rounds only
+/// once. If a transformed coordinate lies very close to halfway between two coordinates, the GPU
+/// may then round it to the other one.
+///
+/// # Overflow
+/// If a rounded coordinate is outside of the range of `Coordinate`, or is not a number at all,
+/// transforming gives an `OverflowError`.
+///
+/// # Examples
+/// ```
+/// use apex::{AffineTransform, Point2D};
+/// let quarter_turn = AffineTransform::rotation(&Point2D { x: 10, y: 10 }, std::f64::consts::FRAC_PI_2);
+/// assert_eq!(quarter_turn.apply(&Point2D { x: 20, y: 10 }), Ok(Point2D { x: 10, y: 20 }));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AffineTransform {
+	/// How much the X coordinate of the original point contributes to the new X coordinate.
+	pub xx: f64,
+
+	/// How much the Y coordinate of the original point contributes to the new X coordinate.
+	pub xy: f64,
+
+	/// How far to move in the X direction, after the linear part of the transformation.
+	pub dx: f64,
+
+	/// How much the X coordinate of the original point contributes to the new Y coordinate.
+	pub yx: f64,
+
+	/// How much the Y coordinate of the original point contributes to the new Y coordinate.
+	pub yy: f64,
+
+	/// How far to move in the Y direction, after the linear part of the transformation.
+	pub dy: f64,
+}
+
+impl AffineTransform {
+	/// Create a transformation that keeps every point where it is.
+	pub fn identity() -> AffineTransform {
+		AffineTransform { xx: 1.0, xy: 0.0, dx: 0.0, yx: 0.0, yy: 1.0, dy: 0.0 }
+	}
+
+	/// Create a transformation that rotates around a pivot point.
+	///
+	/// # Arguments
+	/// * `pivot` - The point to rotate around. This point stays in place.
+	/// * `angle` - How far to rotate, in radians. Positive angles rotate counter-clockwise, from the
+	/// positive X axis towards the positive Y axis.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{AffineTransform, Point2D};
+	/// let half_turn = AffineTransform::rotation(&Point2D { x: 0, y: 0 }, std::f64::consts::PI);
+	/// assert_eq!(half_turn.apply(&Point2D { x: 30, y: -40 }), Ok(Point2D { x: -30, y: 40 }));
+	/// ```
+	pub fn rotation(pivot: &Point2D, angle: f64) -> AffineTransform {
+		let (sin, cos) = angle.sin_cos();
+		AffineTransform { xx: cos, xy: -sin, dx: 0.0, yx: sin, yy: cos, dy: 0.0 }.around(pivot)
+	}
+
+	/// Create a transformation that scales relative to a pivot point.
+	///
+	/// The X and Y directions can be scaled by different factors. Negative factors mirror the
+	/// object in that direction.
+	///
+	/// # Arguments
+	/// * `pivot` - The point to scale relative to. This point stays in place.
+	/// * `factor_x` - How much to scale in the X direction.
+	/// * `factor_y` - How much to scale in the Y direction.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{AffineTransform, Point2D};
+	/// let stretch = AffineTransform::scaling(&Point2D { x: 100, y: 100 }, 2.0, 0.5);
+	/// assert_eq!(stretch.apply(&Point2D { x: 150, y: 150 }), Ok(Point2D { x: 200, y: 125 }));
+	/// ```
+	pub fn scaling(pivot: &Point2D, factor_x: f64, factor_y: f64) -> AffineTransform {
+		AffineTransform { xx: factor_x, xy: 0.0, dx: 0.0, yx: 0.0, yy: factor_y, dy: 0.0 }.around(pivot)
+	}
+
+	/// Create a transformation that mirrors across a line.
+	///
+	/// The line is given by two points on it. If those points are the same, this mirrors through
+	/// that point instead, which is the same as rotating around it by half a turn.
+	///
+	/// # Arguments
+	/// * `axis_start` - A point on the line to mirror across.
+	/// * `axis_end` - Another point on the line to mirror across.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{AffineTransform, Point2D};
+	/// let flip = AffineTransform::mirror(&Point2D { x: 0, y: 10 }, &Point2D { x: 1, y: 10 }); //Across the horizontal line at Y=10.
+	/// assert_eq!(flip.apply(&Point2D { x: 5, y: 15 }), Ok(Point2D { x: 5, y: 5 }));
+	/// ```
+	pub fn mirror(axis_start: &Point2D, axis_end: &Point2D) -> AffineTransform {
+		let direction_x = axis_end.x as f64 - axis_start.x as f64;
+		let direction_y = axis_end.y as f64 - axis_start.y as f64;
+		let length_squared = direction_x * direction_x + direction_y * direction_y;
+		if length_squared == 0.0 {
+			return AffineTransform::scaling(axis_start, -1.0, -1.0);
+		}
+		let cos = (direction_x * direction_x - direction_y * direction_y) / length_squared; //Cosine and sine of twice the angle of the line.
+		let sin = 2.0 * direction_x * direction_y / length_squared;
+		AffineTransform { xx: cos, xy: sin, dx: 0.0, yx: sin, yy: -cos, dy: 0.0 }.around(axis_start)
+	}
+
+	/// Combine this transformation with another one that is applied afterwards.
+	///
+	/// The combination is computed without rounding in between, so it may give slightly different
+	/// results than applying the two transformations one after another.
+	///
+	/// # Arguments
+	/// * `next` - The transformation to apply after this one.
+	///
+	/// # Result
+	/// A transformation that does both at once.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{AffineTransform, Point2D};
+	/// let origin = Point2D { x: 0, y: 0 };
+	/// let both = AffineTransform::scaling(&origin, 3.0, 3.0).then(&AffineTransform::rotation(&origin, std::f64::consts::FRAC_PI_2));
+	/// assert_eq!(both.apply(&Point2D { x: 10, y: 0 }), Ok(Point2D { x: 0, y: 30 }));
+	/// ```
+	pub fn then(&self, next: &AffineTransform) -> AffineTransform {
+		AffineTransform {
+			xx: next.xx * self.xx + next.xy * self.yx,
+			xy: next.xx * self.xy + next.xy * self.yy,
+			dx: next.xx * self.dx + next.xy * self.dy + next.dx,
+			yx: next.yx * self.xx + next.yy * self.yx,
+			yy: next.yx * self.xy + next.yy * self.yy,
+			dy: next.yx * self.dx + next.yy * self.dy + next.dy,
+		}
+	}
+
+	/// Transform a single point.
+	///
+	/// # Arguments
+	/// * `point` - The point to transform.
+	///
+	/// # Result
+	/// The transformed point, rounded to the nearest coordinates, or an error if that is out of the
+	/// range of `Coordinate`. The transformation is computed with `f64`, so with the `coordinate64`
+	/// feature, coordinates beyond 2^53 are rounded to a multiple of a power of two.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{AffineTransform, Coordinate, OverflowError, Point2D};
+	/// let double = AffineTransform::scaling(&Point2D { x: 0, y: 0 }, 2.0, 2.0);
+	/// assert_eq!(double.apply(&Point2D { x: 3, y: -4 }), Ok(Point2D { x: 6, y: -8 }));
+	/// assert_eq!(double.apply(&Point2D { x: Coordinate::MAX, y: 0 }), Err(OverflowError));
+	/// ```
+	pub fn apply(&self, point: &Point2D) -> Result<Point2D, OverflowError> {
+		let x = point.x as f64;
+		let y = point.y as f64;
+		Ok(Point2D {
+			x: round(self.xx * x + self.xy * y + self.dx)?,
+			y: round(self.yx * x + self.yy * y + self.dy)?,
+		})
+	}
+
+	/// Make a linear transformation that works relative to the origin work relative to a pivot
+	/// point instead.
+	///
+	/// # Arguments
+	/// * `pivot` - The point that must stay in place.
+	fn around(mut self, pivot: &Point2D) -> AffineTransform {
+		let x = pivot.x as f64;
+		let y = pivot.y as f64;
+		self.dx = x - (self.xx * x + self.xy * y);
+		self.dy = y - (self.yx * x + self.yy * y);
+		self
+	}
+}
+
+/// Round a transformed coordinate to the nearest `Coordinate`, with halves rounded up.
+///
+/// # Arguments
+/// * `value` - The transformed coordinate.
+///
+/// # Result
+/// The rounded coordinate, or an error if it is out of the range of `Coordinate`.
+pub(crate) fn round(value: f64) -> Result<Coordinate, OverflowError> {
+	let rounded = (value + 0.5).floor();
+	if rounded >= Coordinate::MIN as f64 && rounded < -(Coordinate::MIN as f64) { //Also false for NaN. The maximum itself may not be representable as f64, but the negated minimum is.
+		Ok(rounded as Coordinate)
+	} else {
+		Err(OverflowError)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+
+	/// Test that the identity transformation keeps points in place, even at the edges of the range
+	/// in which the operations are exact.
+	#[test_case(Point2D { x: 0, y: 0 } ; "origin")]
+	#[test_case(Point2D { x: -123, y: 456 } ; "mixed")]
+	#[test_case(Point2D { x: -crate::MAX_EXACT_COORDINATE - 1, y: crate::MAX_EXACT_COORDINATE } ; "extremes")]
+	fn identity(point: Point2D) {
+		assert_eq!(AffineTransform::identity().apply(&point), Ok(point));
+	}
+
+	/// Test rotating by multiples of a quarter turn, which must be exact despite the rounding errors
+	/// in the sine and cosine.
+	#[test_case(1, Point2D { x: 90, y: 160 } ; "quarter")]
+	#[test_case(2, Point2D { x: 60, y: 90 } ; "half")]
+	#[test_case(3, Point2D { x: 130, y: 60 } ; "three quarters")]
+	#[test_case(4, Point2D { x: 160, y: 130 } ; "full")]
+	#[test_case(-1, Point2D { x: 130, y: 60 } ; "clockwise")]
+	fn rotation_quarters(quarters: i32, expected: Point2D) {
+		let pivot = Point2D { x: 110, y: 110 };
+		let transform = AffineTransform::rotation(&pivot, quarters as f64 * std::f64::consts::FRAC_PI_2);
+		assert_eq!(transform.apply(&Point2D { x: 160, y: 130 }), Ok(expected));
+		assert_eq!(transform.apply(&pivot), Ok(pivot), "The pivot stays in place.");
+	}
+
+	/// Test rotating by an angle that doesn't give whole coordinates.
+	#[test]
+	fn rotation_rounded() {
+		let transform = AffineTransform::rotation(&Point2D { x: 0, y: 0 }, std::f64::consts::FRAC_PI_4);
+		assert_eq!(transform.apply(&Point2D { x: 100, y: 0 }), Ok(Point2D { x: 71, y: 71 }), "100 / sqrt(2) = 70.71 rounds to 71.");
+	}
+
+	/// Test that halves are rounded towards positive infinity, for both signs.
+	#[test_case(Point2D { x: 1, y: 3 }, Point2D { x: 1, y: 2 } ; "positive")]
+	#[test_case(Point2D { x: -1, y: -3 }, Point2D { x: 0, y: -1 } ; "negative")]
+	fn rounding_halves(point: Point2D, expected: Point2D) {
+		let half = AffineTransform::scaling(&Point2D { x: 0, y: 0 }, 0.5, 0.5);
+		assert_eq!(half.apply(&point), Ok(expected));
+	}
+
+	/// Test scaling relative to a pivot point.
+	#[test_case(2.0, 2.0, Point2D { x: 30, y: -10 } ; "uniform")]
+	#[test_case(3.0, 1.0, Point2D { x: 40, y: 0 } ; "non-uniform")]
+	#[test_case(-1.0, 1.0, Point2D { x: 0, y: 0 } ; "mirrored")]
+	#[test_case(0.0, 0.0, Point2D { x: 10, y: 10 } ; "collapsed")]
+	fn scaling(factor_x: f64, factor_y: f64, expected: Point2D) {
+		let transform = AffineTransform::scaling(&Point2D { x: 10, y: 10 }, factor_x, factor_y);
+		assert_eq!(transform.apply(&Point2D { x: 20, y: 0 }), Ok(expected));
+	}
+
+	/// Test mirroring across lines in various directions.
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 0, y: 5 }, Point2D { x: -20, y: 10 } ; "vertical")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 5, y: 0 }, Point2D { x: 20, y: -10 } ; "horizontal")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 5, y: 5 }, Point2D { x: 10, y: 20 } ; "diagonal")]
+	#[test_case(Point2D { x: 3, y: 3 }, Point2D { x: -7, y: -7 }, Point2D { x: 10, y: 20 } ; "diagonal reversed")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 0, y: 0 }, Point2D { x: -20, y: -10 } ; "through a point")]
+	fn mirror(axis_start: Point2D, axis_end: Point2D, expected: Point2D) {
+		let transform = AffineTransform::mirror(&axis_start, &axis_end);
+		assert_eq!(transform.apply(&Point2D { x: 20, y: 10 }), Ok(expected));
+		assert_eq!(transform.apply(&expected), Ok(Point2D { x: 20, y: 10 }), "Mirroring twice gives the original point.");
+	}
+
+	/// Test combining transformations.
+	#[test]
+	fn then() {
+		let origin = Point2D { x: 0, y: 0 };
+		let shift = AffineTransform { dx: 5.0, dy: -5.0, ..AffineTransform::identity() };
+		let rotate = AffineTransform::rotation(&origin, std::f64::consts::FRAC_PI_2);
+		let point = Point2D { x: 10, y: 0 };
+		assert_eq!(shift.then(&rotate).apply(&point), Ok(Point2D { x: 5, y: 15 }), "First move to 15,-5, then rotate.");
+		assert_eq!(rotate.then(&shift).apply(&point), Ok(Point2D { x: 5, y: 5 }), "First rotate to 0,10, then move.");
+	}
+
+	/// Test detecting coordinates that go out of range.
+	#[test_case(AffineTransform::scaling(&Point2D { x: 0, y: 0 }, 2.0, 1.0), Point2D { x: Coordinate::MAX / 2 + 1, y: 0 } ; "scaled too far")]
+	#[test_case(AffineTransform::scaling(&Point2D { x: 0, y: 0 }, -1.0, 1.0), Point2D { x: Coordinate::MIN, y: 0 } ; "negated minimum")]
+	#[test_case(AffineTransform { dy: 0.5, ..AffineTransform::identity() }, Point2D { x: 0, y: Coordinate::MAX } ; "rounded up out of range")]
+	#[test_case(AffineTransform { xx: f64::NAN, ..AffineTransform::identity() }, Point2D { x: 1, y: 1 } ; "not a number")]
+	#[test_case(AffineTransform { dx: f64::INFINITY, ..AffineTransform::identity() }, Point2D { x: 1, y: 1 } ; "infinite")]
+	fn overflow(transform: AffineTransform, point: Point2D) {
+		assert_eq!(transform.apply(&point), Err(OverflowError));
+	}
+
+	/// Test that coordinates at the edges of the range are not mistaken for overflow.
+	#[test]
+	#[cfg(not(feature = "coordinate64"))] //64-bit coordinates at the edges of the range can't be represented exactly as f64.
+	fn extremes() {
+		let mirror = AffineTransform::scaling(&Point2D { x: 0, y: 0 }, -1.0, -1.0);
+		assert_eq!(mirror.apply(&Point2D { x: Coordinate::MAX, y: -Coordinate::MAX }), Ok(Point2D { x: -Coordinate::MAX, y: Coordinate::MAX }));
+		let shift = AffineTransform { dx: -0.5, ..AffineTransform::identity() };
+		assert_eq!(shift.apply(&Point2D { x: Coordinate::MIN, y: 0 }), Ok(Point2D { x: Coordinate::MIN, y: 0 }), "The half is rounded up, back into range.");
+	}
+}
\ No newline at end of file
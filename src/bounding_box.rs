@@ -0,0 +1,256 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! Defines a struct that describes the axis-aligned rectangle around a shape.
+
+use crate::Coordinate; //The extent of the box is stored in coordinates.
+use crate::Point2D; //The corners of the box.
+
+/// An axis-aligned rectangle, which is the smallest one that contains a certain shape.
+///
+/// The box is closed. Points on its boundary are inside of it, and boxes that only touch each other
+/// intersect. A box around a single point has no width or height, but still contains that point.
+///
+/// A box can also be empty, if the shape it was computed for has no vertices at all. Empty boxes
+/// have their minimum above their maximum. They don't contain or intersect anything, and don't
+/// affect the union with other boxes.
+///
+/// # Examples
+/// ```
+/// use apex::{BoundingBox, Point2D};
+/// let a = BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 10, y: 10 } };
+/// let b = BoundingBox { min: Point2D { x: 10, y: 5 }, max: Point2D { x: 20, y: 20 } };
+/// assert!(a.intersects(&b)); //They touch along the line X=10.
+/// assert_eq!(a.union(&b), BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 20, y: 20 } });
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoundingBox {
+	/// The corner of the box with the lowest X and Y coordinates.
+	pub min: Point2D,
+
+	/// The corner of the box with the highest X and Y coordinates.
+	pub max: Point2D,
+}
+
+impl BoundingBox {
+	/// Create an empty box, which contains nothing.
+	///
+	/// This is the box around a shape without any vertices. Including points in it grows it to
+	/// contain those points.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{BoundingBox, Point2D};
+	/// let mut bounds = BoundingBox::empty();
+	/// assert!(bounds.is_empty());
+	/// bounds.include(&Point2D { x: 3, y: 4 });
+	/// assert_eq!(bounds, BoundingBox { min: Point2D { x: 3, y: 4 }, max: Point2D { x: 3, y: 4 } });
+	/// ```
+	pub const fn empty() -> Self {
+		BoundingBox {
+			min: Point2D { x: Coordinate::MAX, y: Coordinate::MAX },
+			max: Point2D { x: Coordinate::MIN, y: Coordinate::MIN },
+		}
+	}
+
+	/// Test whether this box is empty, containing nothing at all.
+	pub fn is_empty(&self) -> bool {
+		self.min.x > self.max.x || self.min.y > self.max.y
+	}
+
+	/// Grow this box so that it contains a certain point.
+	///
+	/// # Arguments
+	/// * `point` - The point that must be inside the box afterwards.
+	pub fn include(&mut self, point: &Point2D) {
+		self.min.x = self.min.x.min(point.x);
+		self.min.y = self.min.y.min(point.y);
+		self.max.x = self.max.x.max(point.x);
+		self.max.y = self.max.y.max(point.y);
+	}
+
+	/// Get the smallest box that contains both this box and another one.
+	///
+	/// # Arguments
+	/// * `other` - The box to combine this box with.
+	///
+	/// # Result
+	/// A box that contains both boxes.
+	pub fn union(&self, other: &BoundingBox) -> BoundingBox {
+		BoundingBox {
+			min: Point2D { x: self.min.x.min(other.min.x), y: self.min.y.min(other.min.y) },
+			max: Point2D { x: self.max.x.max(other.max.x), y: self.max.y.max(other.max.y) },
+		}
+	}
+
+	/// Test whether this box overlaps with another box.
+	///
+	/// Boxes that only touch each other along an edge or at a corner also intersect.
+	///
+	/// # Arguments
+	/// * `other` - The box to test against.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{BoundingBox, Point2D};
+	/// let a = BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 10, y: 10 } };
+	/// assert!(a.intersects(&BoundingBox { min: Point2D { x: 10, y: 10 }, max: Point2D { x: 20, y: 20 } }));
+	/// assert!(!a.intersects(&BoundingBox { min: Point2D { x: 11, y: 0 }, max: Point2D { x: 20, y: 20 } }));
+	/// ```
+	pub fn intersects(&self, other: &BoundingBox) -> bool {
+		self.min.x <= other.max.x && other.min.x <= self.max.x && self.min.y <= other.max.y && other.min.y <= self.max.y
+			&& !self.is_empty() && !other.is_empty()
+	}
+
+	/// Test whether another box lies completely inside of this box.
+	///
+	/// The other box may touch the boundary of this box. Empty boxes have no position, so they are
+	/// not inside of any box.
+	///
+	/// # Arguments
+	/// * `other` - The box that may be inside of this box.
+	pub fn contains(&self, other: &BoundingBox) -> bool {
+		self.min.x <= other.min.x && self.min.y <= other.min.y && other.max.x <= self.max.x && other.max.y <= self.max.y
+			&& !other.is_empty()
+	}
+
+	/// Test whether a point lies inside of this box, or on its boundary.
+	///
+	/// # Arguments
+	/// * `point` - The point that may be inside of this box.
+	pub fn contains_point(&self, point: &Point2D) -> bool {
+		self.min.x <= point.x && point.x <= self.max.x && self.min.y <= point.y && point.y <= self.max.y
+	}
+
+	/// Get the centre of this box, rounded down to whole coordinates.
+	///
+	/// The centre of an empty box is meaningless.
+	pub fn centre(&self) -> Point2D {
+		Point2D {
+			x: (self.min.x as i128 + self.max.x as i128).div_euclid(2) as Coordinate,
+			y: (self.min.y as i128 + self.max.y as i128).div_euclid(2) as Coordinate,
+		}
+	}
+
+	/// Get the squared distance from a point to the nearest point of this box.
+	///
+	/// Points inside the box have a distance of 0. The distance is squared, so that it can be
+	/// computed exactly. The distance to an empty box is the highest possible distance.
+	///
+	/// # Arguments
+	/// * `point` - The point to measure the distance from.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{BoundingBox, Point2D};
+	/// let bounds = BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 10, y: 10 } };
+	/// assert_eq!(bounds.distance_squared(&Point2D { x: 5, y: 5 }), 0);
+	/// assert_eq!(bounds.distance_squared(&Point2D { x: 13, y: -4 }), 3 * 3 + 4 * 4);
+	/// ```
+	pub fn distance_squared(&self, point: &Point2D) -> u128 {
+		if self.is_empty() {
+			return u128::MAX;
+		}
+		let dx = gap(point.x, self.min.x, self.max.x);
+		let dy = gap(point.y, self.min.y, self.max.y);
+		(dx * dx).saturating_add(dy * dy) //Each square fits, but with 64-bit coordinates their sum may not.
+	}
+}
+
+/// Get the distance from a coordinate to a range of coordinates, along one dimension.
+///
+/// # Arguments
+/// * `coordinate` - The coordinate to measure the distance from.
+/// * `min` - The start of the range.
+/// * `max` - The end of the range.
+fn gap(coordinate: Coordinate, min: Coordinate, max: Coordinate) -> u128 {
+	if coordinate < min {
+		(min as i128 - coordinate as i128) as u128
+	} else if coordinate > max {
+		(coordinate as i128 - max as i128) as u128
+	} else {
+		0
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+
+	/// A box from 0 to 10 in both dimensions, to test with.
+	fn ten() -> BoundingBox {
+		BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 10, y: 10 } }
+	}
+
+	/// Test that the empty box doesn't contain or intersect anything.
+	#[test]
+	fn empty() {
+		let empty = BoundingBox::empty();
+		assert!(empty.is_empty());
+		assert!(!empty.intersects(&ten()), "An empty box doesn't intersect anything.");
+		assert!(!ten().intersects(&empty), "Nothing intersects an empty box.");
+		assert!(!ten().contains(&empty), "An empty box has no position, so it's not inside anything.");
+		assert!(!empty.contains_point(&Point2D { x: 0, y: 0 }));
+		assert_eq!(empty.union(&ten()), ten(), "The empty box doesn't affect the union.");
+		assert_eq!(empty.distance_squared(&Point2D { x: 0, y: 0 }), u128::MAX);
+	}
+
+	/// Test growing a box by including points.
+	#[test]
+	fn include() {
+		let mut bounds = BoundingBox::empty();
+		for point in [Point2D { x: 5, y: -3 }, Point2D { x: -2, y: 8 }, Point2D { x: 1, y: 1 }] {
+			bounds.include(&point);
+		}
+		assert_eq!(bounds, BoundingBox { min: Point2D { x: -2, y: -3 }, max: Point2D { x: 5, y: 8 } });
+		assert!(!bounds.is_empty(), "A box around points is not empty, even if it would have no area.");
+	}
+
+	/// Test whether boxes intersect each other.
+	#[test_case(BoundingBox { min: Point2D { x: 2, y: 2 }, max: Point2D { x: 8, y: 8 } }, true ; "inside")]
+	#[test_case(BoundingBox { min: Point2D { x: -5, y: -5 }, max: Point2D { x: 15, y: 15 } }, true ; "around")]
+	#[test_case(BoundingBox { min: Point2D { x: 5, y: -5 }, max: Point2D { x: 15, y: 5 } }, true ; "overlapping_corner")]
+	#[test_case(BoundingBox { min: Point2D { x: 10, y: 10 }, max: Point2D { x: 20, y: 20 } }, true ; "touching_corner")]
+	#[test_case(BoundingBox { min: Point2D { x: 11, y: 0 }, max: Point2D { x: 20, y: 10 } }, false ; "right")]
+	#[test_case(BoundingBox { min: Point2D { x: 0, y: -10 }, max: Point2D { x: 10, y: -1 } }, false ; "below")]
+	fn intersects(other: BoundingBox, expected: bool) {
+		assert_eq!(ten().intersects(&other), expected);
+		assert_eq!(other.intersects(&ten()), expected, "Intersection is symmetric.");
+	}
+
+	/// Test whether boxes are inside other boxes.
+	#[test]
+	fn contains() {
+		assert!(ten().contains(&ten()), "A box touching the boundary from the inside is contained.");
+		assert!(ten().contains(&BoundingBox { min: Point2D { x: 3, y: 3 }, max: Point2D { x: 3, y: 3 } }), "A box around a single point can be contained.");
+		assert!(!ten().contains(&BoundingBox { min: Point2D { x: 3, y: 3 }, max: Point2D { x: 11, y: 3 } }), "This box sticks out.");
+		assert!(ten().contains_point(&Point2D { x: 10, y: 0 }), "Points on the boundary are inside.");
+		assert!(!ten().contains_point(&Point2D { x: 10, y: 11 }));
+	}
+
+	/// Test finding the centre of boxes, also near the edges of the range of coordinates.
+	#[test]
+	fn centre() {
+		assert_eq!(ten().centre(), Point2D { x: 5, y: 5 });
+		assert_eq!(BoundingBox { min: Point2D { x: -3, y: 0 }, max: Point2D { x: 0, y: 1 } }.centre(), Point2D { x: -2, y: 0 }, "The centre is rounded down.");
+		let everything = BoundingBox { min: Point2D { x: Coordinate::MIN, y: Coordinate::MIN }, max: Point2D { x: Coordinate::MAX, y: Coordinate::MAX } };
+		assert_eq!(everything.centre(), Point2D { x: -1, y: -1 }, "The sum of the corners doesn't fit in a coordinate, but the centre must.");
+	}
+
+	/// Test the distance from points to a box.
+	#[test_case(Point2D { x: 5, y: 5 }, 0 ; "inside")]
+	#[test_case(Point2D { x: 10, y: 3 }, 0 ; "boundary")]
+	#[test_case(Point2D { x: -3, y: 5 }, 9 ; "left")]
+	#[test_case(Point2D { x: 13, y: 14 }, 9 + 16 ; "corner")]
+	#[test_case(Point2D { x: Coordinate::MIN, y: Coordinate::MIN }, 2 * (-(Coordinate::MIN as i128)) as u128 * (-(Coordinate::MIN as i128)) as u128 ; "far")]
+	fn distance_squared(point: Point2D, expected: u128) {
+		assert_eq!(ten().distance_squared(&point), expected);
+	}
+}
\ No newline at end of file
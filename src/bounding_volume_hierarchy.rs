@@ -0,0 +1,406 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! Defines a tree of bounding boxes, to quickly find which items are near a certain place.
+
+use std::cmp::Reverse; //To pop the nearest nodes first from a max-heap.
+use std::collections::BinaryHeap; //To visit the nodes in order of distance, when searching for the nearest items.
+
+use crate::BoundingBox; //The nodes of the tree are bounding boxes.
+use crate::Point2D; //To search for the items nearest to a point.
+use crate::operations::bounding_volume_hierarchy; //To build the tree.
+
+/// A binary tree of bounding boxes around a list of items, to quickly find the items in a region.
+///
+/// Each leaf of the tree is the bounding box of one item. Each internal node is the bounding box
+/// around its two children. A query only needs to visit the branches of the tree whose boxes are
+/// near the region it searches, skipping most of the items.
+///
+/// The tree is built in bulk, by sorting the items along a space-filling curve and splitting that
+/// sorted list recursively. The boxes of the items can be changed afterwards without rebuilding the
+/// tree, by updating the boxes along the path to the root. This keeps the tree valid, but if the
+/// items move far apart from their neighbours on the curve, the queries become slower.
+///
+/// The items are identified by their index in the list of boxes that the tree was built from.
+/// Items with an empty bounding box are never found by any query.
+///
+/// # Examples
+/// ```
+/// use apex::{BoundingBox, BoundingVolumeHierarchy, Point2D};
+/// let boxes = [
+/// 	BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 10, y: 10 } },
+/// 	BoundingBox { min: Point2D { x: 100, y: 0 }, max: Point2D { x: 110, y: 10 } },
+/// 	BoundingBox { min: Point2D { x: 5, y: 5 }, max: Point2D { x: 20, y: 20 } },
+/// ];
+/// let hierarchy = BoundingVolumeHierarchy::new(&boxes);
+/// assert_eq!(hierarchy.intersecting(&BoundingBox { min: Point2D { x: 8, y: 8 }, max: Point2D { x: 9, y: 9 } }), vec![0, 2]);
+/// assert_eq!(hierarchy.nearest(&Point2D { x: 90, y: 0 }, 1), vec![1]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundingVolumeHierarchy {
+	/// The bounding box of every node in the tree.
+	///
+	/// The internal nodes come first, with the root at index 0. They are followed by the leaves, in
+	/// the order in which the items were sorted. If there is only one item, its leaf is the root.
+	boxes: Vec<BoundingBox>,
+
+	/// The two children of every internal node, as indices into the nodes.
+	children: Vec<[usize; 2]>,
+
+	/// The parent of every node, as an index into the nodes. The root is its own parent.
+	parents: Vec<usize>,
+
+	/// For every leaf, the item that it holds.
+	leaf_items: Vec<usize>,
+
+	/// For every item, the node of the leaf that holds it.
+	item_leaves: Vec<usize>,
+}
+
+impl BoundingVolumeHierarchy {
+	/// Build a tree around the bounding boxes of a list of items.
+	///
+	/// This chooses automatically whether to build the tree on a single thread, on multiple threads
+	/// or on the GPU. The cost model estimates which is fastest, from the number of items.
+	///
+	/// # Arguments
+	/// * `boxes` - The bounding box of each item.
+	pub fn new(boxes: &[BoundingBox]) -> Self {
+		bounding_volume_hierarchy::build(boxes)
+	}
+
+	/// Assemble a tree from its structure, and compute the boxes of its nodes.
+	///
+	/// # Arguments
+	/// * `boxes` - The bounding box of each item.
+	/// * `children` - The two children of each internal node. The internal nodes are numbered from
+	/// 0, and the leaves from the number of internal nodes onwards, in sorted order.
+	/// * `leaf_items` - The item that each leaf holds.
+	pub(crate) fn from_parts(boxes: &[BoundingBox], children: Vec<[usize; 2]>, leaf_items: Vec<usize>) -> Self {
+		let num_nodes = children.len() + leaf_items.len();
+		let mut parents = vec![0; num_nodes];
+		for (node, node_children) in children.iter().enumerate() {
+			for child in node_children {
+				parents[*child] = node;
+			}
+		}
+		let mut item_leaves = vec![0; leaf_items.len()];
+		for (leaf, item) in leaf_items.iter().enumerate() {
+			item_leaves[*item] = children.len() + leaf;
+		}
+		let mut result = BoundingVolumeHierarchy {
+			boxes: vec![BoundingBox::empty(); num_nodes],
+			children,
+			parents,
+			leaf_items,
+			item_leaves,
+		};
+		result.refit(boxes);
+		result
+	}
+
+	/// Get the number of items in the tree.
+	pub fn len(&self) -> usize {
+		self.leaf_items.len()
+	}
+
+	/// Test whether the tree has no items at all.
+	pub fn is_empty(&self) -> bool {
+		self.leaf_items.is_empty()
+	}
+
+	/// Get the bounding box around all items in the tree.
+	///
+	/// # Result
+	/// The box around all items, or an empty box if there are no items.
+	pub fn bounds(&self) -> BoundingBox {
+		self.boxes.first().copied().unwrap_or(BoundingBox::empty())
+	}
+
+	/// Get the bounding box of one item, as it is known to the tree.
+	///
+	/// # Arguments
+	/// * `item` - The index of the item.
+	pub fn item_box(&self, item: usize) -> BoundingBox {
+		self.boxes[self.item_leaves[item]]
+	}
+
+	/// Find all items whose bounding boxes intersect a certain region.
+	///
+	/// Items that only touch the region along its boundary are also found.
+	///
+	/// # Arguments
+	/// * `region` - The region to search in.
+	///
+	/// # Result
+	/// The indices of the items that intersect the region, in ascending order.
+	pub fn intersecting(&self, region: &BoundingBox) -> Vec<usize> {
+		self.search(|bounds| bounds.intersects(region), |bounds| bounds.intersects(region))
+	}
+
+	/// Find all items whose bounding boxes lie completely inside of a certain region.
+	///
+	/// # Arguments
+	/// * `region` - The region to search in.
+	///
+	/// # Result
+	/// The indices of the items that are inside the region, in ascending order.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{BoundingBox, BoundingVolumeHierarchy, Point2D};
+	/// let hierarchy = BoundingVolumeHierarchy::new(&[
+	/// 	BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 10, y: 10 } },
+	/// 	BoundingBox { min: Point2D { x: 5, y: 5 }, max: Point2D { x: 20, y: 20 } },
+	/// ]);
+	/// assert_eq!(hierarchy.within(&BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 15, y: 15 } }), vec![0]);
+	/// ```
+	pub fn within(&self, region: &BoundingBox) -> Vec<usize> {
+		self.search(|bounds| bounds.intersects(region), |bounds| region.contains(bounds))
+	}
+
+	/// Find the items whose bounding boxes are nearest to a point.
+	///
+	/// The distance to an item is the distance to the nearest point of its bounding box. Items whose
+	/// box contains the point have a distance of 0. Items at the same distance are ordered by their
+	/// index.
+	///
+	/// # Arguments
+	/// * `point` - The point to search around.
+	/// * `count` - How many items to find. If the tree has fewer items, all of them are found,
+	/// except those with empty bounding boxes.
+	///
+	/// # Result
+	/// The indices of the nearest items, from nearest to farthest.
+	pub fn nearest(&self, point: &Point2D, count: usize) -> Vec<usize> {
+		let mut result = Vec::with_capacity(count.min(self.len()));
+		if self.is_empty() || count == 0 {
+			return result;
+		}
+		//Visit the nodes in order of their distance, so the leaves are found from nearest to farthest.
+		//At equal distances, internal nodes go first, so that all leaves at that distance are queued before the first of them is reported.
+		let mut queue = BinaryHeap::new();
+		queue.push(Reverse((self.boxes[0].distance_squared(point), false, 0)));
+		while let Some(Reverse((distance, is_item, index))) = queue.pop() {
+			if distance == u128::MAX {
+				break; //Only empty boxes are left.
+			}
+			if is_item {
+				result.push(index);
+				if result.len() == count {
+					break;
+				}
+			} else if self.is_leaf(index) {
+				queue.push(Reverse((distance, true, self.leaf_items[index - self.children.len()])));
+			} else {
+				for child in self.children[index] {
+					queue.push(Reverse((self.boxes[child].distance_squared(point), false, child)));
+				}
+			}
+		}
+		result
+	}
+
+	/// Change the bounding box of one item.
+	///
+	/// The boxes of the nodes above it are updated to match, without changing the structure of the
+	/// tree.
+	///
+	/// # Arguments
+	/// * `item` - The index of the item to change.
+	/// * `bounds` - The new bounding box of the item.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{BoundingBox, BoundingVolumeHierarchy, Point2D};
+	/// let mut hierarchy = BoundingVolumeHierarchy::new(&[
+	/// 	BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 10, y: 10 } },
+	/// 	BoundingBox { min: Point2D { x: 5, y: 5 }, max: Point2D { x: 20, y: 20 } },
+	/// ]);
+	/// hierarchy.update(1, BoundingBox { min: Point2D { x: 50, y: 50 }, max: Point2D { x: 60, y: 60 } });
+	/// assert_eq!(hierarchy.intersecting(&BoundingBox { min: Point2D { x: 55, y: 55 }, max: Point2D { x: 55, y: 55 } }), vec![1]);
+	/// ```
+	pub fn update(&mut self, item: usize, bounds: BoundingBox) {
+		let mut node = self.item_leaves[item];
+		self.boxes[node] = bounds;
+		while node != 0 {
+			node = self.parents[node];
+			let [left, right] = self.children[node];
+			self.boxes[node] = self.boxes[left].union(&self.boxes[right]);
+		}
+	}
+
+	/// Change the bounding boxes of all items at once.
+	///
+	/// The boxes of all internal nodes are recomputed, without changing the structure of the tree.
+	/// This is faster than updating the items one by one, if many of them changed.
+	///
+	/// # Arguments
+	/// * `boxes` - The new bounding box of each item.
+	pub fn refit(&mut self, boxes: &[BoundingBox]) {
+		assert_eq!(boxes.len(), self.len(), "The tree must get a new box for each of its items.");
+		let first_leaf = self.children.len();
+		for (leaf, item) in self.leaf_items.iter().enumerate() {
+			self.boxes[first_leaf + leaf] = boxes[*item];
+		}
+		//List the internal nodes from the root downwards, so that in reverse, children come before their parents.
+		let mut order = Vec::with_capacity(self.children.len());
+		if !self.children.is_empty() {
+			order.push(0);
+		}
+		let mut next = 0;
+		while next < order.len() {
+			for child in self.children[order[next]] {
+				if !self.is_leaf(child) {
+					order.push(child);
+				}
+			}
+			next += 1;
+		}
+		for node in order.into_iter().rev() {
+			let [left, right] = self.children[node];
+			self.boxes[node] = self.boxes[left].union(&self.boxes[right]);
+		}
+	}
+
+	/// Test whether a node is a leaf of the tree.
+	///
+	/// # Arguments
+	/// * `node` - The index of the node.
+	fn is_leaf(&self, node: usize) -> bool {
+		node >= self.children.len()
+	}
+
+	/// Find all items that satisfy a condition, skipping the branches that can't contain any.
+	///
+	/// # Arguments
+	/// * `may_contain` - Whether a node with a certain box could contain items that satisfy the
+	/// condition.
+	/// * `matches` - Whether an item with a certain box satisfies the condition.
+	///
+	/// # Result
+	/// The indices of the items that satisfy the condition, in ascending order.
+	fn search(&self, may_contain: impl Fn(&BoundingBox) -> bool, matches: impl Fn(&BoundingBox) -> bool) -> Vec<usize> {
+		let mut result = Vec::new();
+		if self.is_empty() {
+			return result;
+		}
+		let mut stack = vec![0];
+		while let Some(node) = stack.pop() {
+			if self.is_leaf(node) {
+				if matches(&self.boxes[node]) {
+					result.push(self.leaf_items[node - self.children.len()]);
+				}
+			} else if may_contain(&self.boxes[node]) {
+				stack.extend(self.children[node]);
+			}
+		}
+		result.sort_unstable();
+		result
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Create a box around a single point.
+	fn point_box(x: i32, y: i32) -> BoundingBox {
+		BoundingBox { min: Point2D { x: x as _, y: y as _ }, max: Point2D { x: x as _, y: y as _ } }
+	}
+
+	/// Create a grid of small boxes, to search in.
+	fn grid() -> Vec<BoundingBox> {
+		(0..400).map(|i| BoundingBox { min: Point2D { x: (i % 20) * 10, y: (i / 20) * 10 }, max: Point2D { x: (i % 20) * 10 + 5, y: (i / 20) * 10 + 5 } }).collect()
+	}
+
+	/// Find the items that intersect a region by testing all of them, to compare the tree against.
+	fn brute_force_intersecting(boxes: &[BoundingBox], region: &BoundingBox) -> Vec<usize> {
+		(0..boxes.len()).filter(|item| boxes[*item].intersects(region)).collect()
+	}
+
+	/// Test that a tree without items finds nothing.
+	#[test]
+	fn empty() {
+		let hierarchy = BoundingVolumeHierarchy::new(&[]);
+		assert!(hierarchy.is_empty());
+		assert!(hierarchy.bounds().is_empty());
+		assert_eq!(hierarchy.intersecting(&point_box(0, 0)), Vec::<usize>::new());
+		assert_eq!(hierarchy.nearest(&Point2D { x: 0, y: 0 }, 3), Vec::<usize>::new());
+	}
+
+	/// Test a tree with a single item, whose leaf is the root.
+	#[test]
+	fn single() {
+		let mut hierarchy = BoundingVolumeHierarchy::new(&[point_box(3, 4)]);
+		assert_eq!(hierarchy.len(), 1);
+		assert_eq!(hierarchy.intersecting(&point_box(3, 4)), vec![0]);
+		assert_eq!(hierarchy.nearest(&Point2D { x: 100, y: 100 }, 5), vec![0]);
+		hierarchy.update(0, point_box(50, 50));
+		assert_eq!(hierarchy.bounds(), point_box(50, 50));
+	}
+
+	/// Test that intersection queries find the same items as testing every item.
+	#[test]
+	fn intersecting_matches_brute_force() {
+		let boxes = grid();
+		let hierarchy = BoundingVolumeHierarchy::new(&boxes);
+		assert_eq!(hierarchy.bounds(), BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 195, y: 195 } });
+		for region in [point_box(0, 0), point_box(7, 7), BoundingBox { min: Point2D { x: 33, y: 48 }, max: Point2D { x: 91, y: 120 } }, BoundingBox { min: Point2D { x: -100, y: -100 }, max: Point2D { x: 1000, y: 1000 } }] {
+			assert_eq!(hierarchy.intersecting(&region), brute_force_intersecting(&boxes, &region));
+		}
+	}
+
+	/// Test finding the items that are completely inside a region.
+	#[test]
+	fn within() {
+		let hierarchy = BoundingVolumeHierarchy::new(&grid());
+		let region = BoundingBox { min: Point2D { x: 10, y: 0 }, max: Point2D { x: 32, y: 15 } }; //Cuts through the boxes at X=30.
+		assert_eq!(hierarchy.within(&region), vec![1, 2, 21, 22]);
+	}
+
+	/// Test finding the nearest items, from nearest to farthest.
+	#[test]
+	fn nearest() {
+		let boxes = [point_box(0, 0), point_box(10, 0), point_box(3, 0), BoundingBox::empty(), point_box(-3, 0), point_box(100, 100)];
+		let hierarchy = BoundingVolumeHierarchy::new(&boxes);
+		assert_eq!(hierarchy.nearest(&Point2D { x: 1, y: 0 }, 1), vec![0]);
+		assert_eq!(hierarchy.nearest(&Point2D { x: 1, y: 0 }, 3), vec![0, 2, 4], "Items 2 and 4 are equally far, so they are ordered by index.");
+		assert_eq!(hierarchy.nearest(&Point2D { x: 1, y: 0 }, 10), vec![0, 2, 4, 1, 5], "The item with an empty box is never found.");
+	}
+
+	/// Test that the nearest items match sorting all items by their distance.
+	#[test]
+	fn nearest_matches_brute_force() {
+		let boxes = grid();
+		let hierarchy = BoundingVolumeHierarchy::new(&boxes);
+		let point = Point2D { x: 77, y: 123 };
+		let mut expected: Vec<usize> = (0..boxes.len()).collect();
+		expected.sort_by_key(|item| (boxes[*item].distance_squared(&point), *item));
+		assert_eq!(hierarchy.nearest(&point, 25), expected[..25]);
+	}
+
+	/// Test that updating items keeps the queries correct, without rebuilding the tree.
+	#[test]
+	fn update_and_refit() {
+		let mut boxes = grid();
+		let mut hierarchy = BoundingVolumeHierarchy::new(&boxes);
+		boxes[5] = point_box(1000, 1000);
+		hierarchy.update(5, boxes[5]);
+		assert_eq!(hierarchy.intersecting(&point_box(1000, 1000)), vec![5]);
+		assert_eq!(hierarchy.item_box(5), point_box(1000, 1000));
+
+		for bounds in boxes.iter_mut() {
+			*bounds = BoundingBox { min: Point2D { x: bounds.min.x - 500, y: bounds.min.y }, max: Point2D { x: bounds.max.x - 500, y: bounds.max.y } };
+		}
+		hierarchy.refit(&boxes);
+		let region = BoundingBox { min: Point2D { x: -480, y: 20 }, max: Point2D { x: -400, y: 60 } };
+		assert_eq!(hierarchy.intersecting(&region), brute_force_intersecting(&boxes, &region));
+		assert_eq!(hierarchy.bounds(), BoundingBox { min: Point2D { x: -500, y: 0 }, max: Point2D { x: 500, y: 1000 } });
+	}
+}
\ No newline at end of file
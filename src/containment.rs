@@ -0,0 +1,25 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! Defines an enum for where a point is relative to a shape.
+
+/// These are the possible positions of a point relative to a shape.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Containment {
+	/// The point is inside the shape, not on its boundary.
+	INSIDE,
+
+	/// The point is outside of the shape, not on its boundary.
+	OUTSIDE,
+
+	/// The point is exactly on the boundary of the shape.
+	///
+	/// This includes points on edges that don't separate the inside from the outside, for instance
+	/// edges between two overlapping parts of the shape.
+	BOUNDARY,
+}
\ No newline at end of file
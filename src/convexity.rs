@@ -9,7 +9,7 @@
 //! Defines an enum for different types of convexity.
 
 /// These are the possible states of convexity that a shape can have.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Convexity {
 	/// The convexity has not yet been computed. This should never be returned as a result of a
 	/// public function.
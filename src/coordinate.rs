@@ -16,7 +16,15 @@
 ///
 /// The type is 32-bits to allow for single-width entries in graphics card integer processors.
 /// Anything else kills performance.
+///
+/// With the `coordinate64` feature, the type is 64-bits instead, for workspaces that are larger or
+/// need a finer resolution. The GPU implementations would then need 128-bit integers, which
+/// graphics cards lack, so in this mode the operations that have a GPU implementation run on the
+/// host instead.
+#[cfg(not(feature = "coordinate64"))]
 pub type Coordinate = i32;
+#[cfg(feature = "coordinate64")]
+pub type Coordinate = i64;
 
 /// The type used to store the size of areas in 2D space.
 ///
@@ -31,13 +39,33 @@ pub type Coordinate = i32;
 /// The available integer types would require this to use `i128`. However this size is so rarely
 /// used and would incur such a performance penalty that it's better to use `i64`. This type can at
 /// most represent only half of the total coordinate space in 2D, but it's much more performant.
+///
+/// With the `coordinate64` feature, this is `i128`, which likewise covers half of the coordinate
+/// space of the 64-bit coordinates. The operations only compute areas exactly for shapes within
+/// `MAX_EXACT_COORDINATE` though.
+#[cfg(not(feature = "coordinate64"))]
 pub type Area = i64;
+#[cfg(feature = "coordinate64")]
+pub type Area = i128;
+
+/// The greatest magnitude of coordinates for which every operation is exact.
+///
+/// The geometric predicates of the operations, such as the orientation of three points or the
+/// intersection point of two line segments, multiply differences of coordinates in 128-bit
+/// integers. With 32-bit coordinates, these never overflow, so the whole coordinate range is exact.
+/// With the `coordinate64` feature, coordinates need to stay within this magnitude for the
+/// intersection points to be computed exactly. Beyond it, the results are only approximate.
+#[cfg(not(feature = "coordinate64"))]
+pub const MAX_EXACT_COORDINATE: Coordinate = Coordinate::MAX;
+#[cfg(feature = "coordinate64")]
+pub const MAX_EXACT_COORDINATE: Coordinate = 1 << 40;
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
+    #[cfg(not(feature = "coordinate64"))]
     /// Test whether the possible range of `Coordinate` is as expected.
     fn coordinate_range() {
         let x: Coordinate = 0x7FFFFFFF;
@@ -47,6 +75,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "coordinate64"))]
     /// Test whether the possible range of `Area` is as expected.
     fn area_range() {
         let area: Area = 0x7FFFFFFFFFFFFFFF;
@@ -54,4 +83,24 @@ mod tests {
         let result = area.checked_add(1);
         assert_eq!(result, None, "It needs to overflow then.");
     }
+
+    #[test]
+    #[cfg(feature = "coordinate64")]
+    /// Test whether the possible range of `Coordinate` is as expected with 64-bit coordinates.
+    fn coordinate_range_64() {
+        let x: Coordinate = 0x7FFFFFFFFFFFFFFF;
+        assert_eq!(x, 0x7FFFFFFFFFFFFFFF, "We need to be able to store at least this coordinate.");
+        let result = x.checked_add(1);
+        assert_eq!(result, None, "It needs to overflow then.");
+    }
+
+    #[test]
+    #[cfg(feature = "coordinate64")]
+    /// Test whether the possible range of `Area` is as expected with 128-bit areas.
+    fn area_range_64() {
+        let area: Area = 0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF;
+        assert_eq!(area, 0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF, "We need to be able to store at least this area.");
+        let result = area.checked_add(1);
+        assert_eq!(result, None, "It needs to overflow then.");
+    }
 }
\ No newline at end of file
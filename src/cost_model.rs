@@ -0,0 +1,832 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! This module estimates how long operations take on each device, so that Apex can choose where to
+//! compute each operation.
+//!
+//! For every operation, the time it takes on each device is modelled as a fixed overhead (for
+//! instance to start threads or launch a kernel) plus a certain time per item that is processed.
+//! Copying data between the host and the GPU is modelled in the same way. When an operation is
+//! performed, the time it would take on each device is estimated, including the time needed to
+//! copy the data to that device if it's not there yet, and the fastest device is chosen.
+//!
+//! The parameters of this model depend heavily on the hardware. They can be measured with small
+//! benchmarks by calling `calibrate`, which takes a fraction of a second in an optimised build and
+//! a few seconds in a debug build. Since that is much longer than most operations take, Apex never
+//! calibrates on its own. Until `calibrate` is called, or a cost model is installed with `install`,
+//! a rough estimate is used. Applications that perform many operations should calibrate once at
+//! startup, or install a cost model that was measured in an earlier run.
+
+use std::hint::black_box;
+use std::sync::RwLock;
+use std::time::Instant;
+
+use crate::AffineTransform; //To benchmark transformations.
+use crate::BoundingBox; //To benchmark building spatial indices.
+use crate::Coordinate; //To construct polygons for the benchmarks.
+use crate::FillRule; //To benchmark boolean operations and containment.
+use crate::MultiPolygon; //To benchmark triangulation.
+use crate::OverflowPolicy; //To benchmark translation.
+use crate::Point2D; //To construct polygons for the benchmarks.
+use crate::Polygon; //To benchmark operations on polygons.
+use crate::detail::gpu; //To check which types the GPU supports.
+use crate::detail::gpu::DeviceTypes; //The GPU implementations can only be chosen if the GPU supports the types they need.
+use crate::detail::sync_status::SyncStatus; //Where the data currently is determines the cost of copying it.
+use crate::operations::{area, boolean, bounding_box, bounding_volume_hierarchy, containment, convex_hull, convexity, offset, self_intersection, simplify, transform, translate, triangulate}; //The operations to benchmark.
+use crate::operations::boolean::BooleanOperation; //To benchmark boolean operations.
+use crate::operations::offset::JoinType; //To benchmark offsetting.
+use crate::operations::simplify::SimplificationMethod; //To benchmark simplification.
+
+/// The cost model that is currently used, if it has been calibrated or installed.
+///
+/// While it is being calibrated, the previous cost model is used, also by the benchmarks
+/// themselves.
+static MODEL: RwLock<Option<CostModel>> = RwLock::new(None);
+
+/// How many times each benchmark is repeated. The fastest run is used, to filter out noise.
+const REPEATS: usize = 5;
+
+/// The fraction of the time of a thread that may be spent on scheduling it, rather than doing work.
+///
+/// This determines how small the chunks of work can become when an operation is multi-threaded.
+const MAX_TASK_OVERHEAD_FRACTION: f64 = 0.1;
+
+/// The number of vertices in the small polygon to benchmark boolean operations and offsetting with.
+///
+/// These operations take much longer per vertex than the other operations, so they are benchmarked
+/// with smaller polygons to keep the calibration fast.
+const BOOLEAN_SMALL: usize = 100;
+
+/// The number of vertices in the large polygon to benchmark boolean operations and offsetting with.
+const BOOLEAN_LARGE: usize = 4000;
+
+/// Whether the GPU implementations can be chosen at all.
+///
+/// With 64-bit coordinates, the GPU implementations always process the coordinates on the host. With
+/// 32-bit coordinates, it depends on the types that the GPU supports, which is checked when choosing
+/// a backend.
+const GPU_AVAILABLE: bool = cfg!(feature = "gpu") && !cfg!(feature = "coordinate64");
+
+/// The devices and ways of computing that an operation can be performed with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+	/// On the host, on a single thread.
+	ST,
+
+	/// On the host, on multiple threads.
+	MT,
+
+	/// On the GPU.
+	GPU,
+}
+
+/// The operations that the cost model has measurements for.
+#[allow(non_camel_case_types)] //The variants are capitalised like those of the other enums, with underscores between words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+	/// Calculating the area of a polygon, for each vertex.
+	AREA,
+
+	/// Performing a boolean operation between two shapes, for each vertex of both shapes.
+	BOOLEAN,
+
+	/// Finding the bounding box of a shape, for each vertex.
+	BOUNDING_BOX,
+
+	/// Finding whether points are inside a polygon, for each combination of a point and a vertex.
+	CONTAINMENT,
+
+	/// Finding the convex hull of a polygon or a set of points, for each point.
+	CONVEX_HULL,
+
+	/// Determining the convexity of a polygon, for each vertex.
+	CONVEXITY,
+
+	/// Offsetting a polygon, for each vertex.
+	OFFSET,
+
+	/// Finding where the boundary of a shape intersects itself, for each edge.
+	SELF_INTERSECTION,
+
+	/// Reducing the number of vertices of a shape, for each vertex.
+	SIMPLIFY,
+
+	/// Building a spatial index over a list of shapes, for each shape.
+	SPATIAL_INDEX,
+
+	/// Transforming a polygon with an affine transformation, for each vertex.
+	TRANSFORM,
+
+	/// Translating a polygon, for each vertex.
+	TRANSLATE,
+
+	/// Dividing a multi-polygon into triangles, for each vertex.
+	TRIANGULATE,
+}
+
+/// How long it takes to process a certain number of items.
+///
+/// The time is modelled as a fixed overhead, plus a fixed time per item.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Throughput {
+	/// The time it takes to start processing, regardless of the number of items, in seconds.
+	pub overhead: f64,
+
+	/// The time it takes to process each item, in seconds.
+	pub per_item: f64,
+}
+
+impl Throughput {
+	/// Estimate the time it takes to process a number of items.
+	///
+	/// # Arguments
+	/// * `num_items` - The number of items to process.
+	///
+	/// # Result
+	/// The estimated time, in seconds.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::cost_model::Throughput;
+	/// let throughput = Throughput { overhead: 0.5, per_item: 0.25 };
+	/// assert_eq!(throughput.time(10), 3.0);
+	/// ```
+	pub fn time(&self, num_items: usize) -> f64 {
+		self.overhead + self.per_item * num_items as f64
+	}
+
+	/// Derive the throughput from the time it took to process two different numbers of items.
+	///
+	/// # Arguments
+	/// * `small_items` - The smaller number of items that was processed.
+	/// * `small_time` - The time it took to process the smaller number of items, in seconds.
+	/// * `large_items` - The larger number of items that was processed.
+	/// * `large_time` - The time it took to process the larger number of items, in seconds.
+	fn fit(small_items: usize, small_time: f64, large_items: usize, large_time: f64) -> Throughput {
+		let per_item = ((large_time - small_time) / (large_items - small_items) as f64).max(f64::MIN_POSITIVE); //Noise could make this negative, but processing is never free.
+		let overhead = (small_time - per_item * small_items as f64).max(0.0);
+		Throughput { overhead, per_item }
+	}
+}
+
+/// How long an operation takes on each of the backends.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OperationCost {
+	/// The time it takes on the host, on a single thread.
+	pub st: Throughput,
+
+	/// The time it takes on the host, on multiple threads.
+	pub mt: Throughput,
+
+	/// The time it takes on the GPU, excluding copying the data to the GPU.
+	///
+	/// If the operation has no GPU implementation, or Apex was compiled without a GPU runtime, this
+	/// is `None`.
+	pub gpu: Option<Throughput>,
+}
+
+/// Estimates of how long each operation takes on each of the backends.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CostModel {
+	/// How long it takes to calculate the area of a polygon.
+	pub area: OperationCost,
+
+	/// How long it takes to perform a boolean operation between two polygons.
+	pub boolean: OperationCost,
+
+	/// How long it takes to find the bounding box of a polygon.
+	pub bounding_box: OperationCost,
+
+	/// How long it takes to find whether points are inside a polygon.
+	pub containment: OperationCost,
+
+	/// How long it takes to find the convex hull of a polygon.
+	pub convex_hull: OperationCost,
+
+	/// How long it takes to determine the convexity of a polygon.
+	pub convexity: OperationCost,
+
+	/// How long it takes to offset a polygon.
+	pub offset: OperationCost,
+
+	/// How long it takes to find where the boundary of a shape intersects itself.
+	pub self_intersection: OperationCost,
+
+	/// How long it takes to reduce the number of vertices of a shape.
+	pub simplify: OperationCost,
+
+	/// How long it takes to build a spatial index over a list of shapes.
+	pub spatial_index: OperationCost,
+
+	/// How long it takes to transform a polygon with an affine transformation.
+	pub transform: OperationCost,
+
+	/// How long it takes to translate a polygon.
+	pub translate: OperationCost,
+
+	/// How long it takes to divide a multi-polygon into triangles.
+	pub triangulate: OperationCost,
+
+	/// How long it takes to copy vertices from the host to the GPU.
+	pub upload: Throughput,
+
+	/// How long it takes to copy vertices from the GPU to the host.
+	pub download: Throughput,
+
+	/// The time it takes to schedule a piece of work on a thread of the thread pool, in seconds.
+	pub task_overhead: f64,
+}
+
+impl CostModel {
+	/// Get a rough estimate of the costs, without measuring anything.
+	///
+	/// This is used until the cost model is calibrated. It is based on typical hardware, but may be
+	/// far off for the hardware that Apex actually runs on.
+	pub const fn estimate() -> CostModel {
+		let gpu = if GPU_AVAILABLE { Some(Throughput { overhead: 2e-5, per_item: 5e-11 }) } else { None };
+		CostModel {
+			area: OperationCost {
+				st: Throughput { overhead: 5e-8, per_item: 1.5e-9 },
+				mt: Throughput { overhead: 2e-5, per_item: 4e-10 },
+				gpu,
+			},
+			boolean: OperationCost {
+				st: Throughput { overhead: 1e-6, per_item: 3e-7 },
+				mt: Throughput { overhead: 3e-5, per_item: 1.5e-7 },
+				gpu: if GPU_AVAILABLE { Some(Throughput { overhead: 1e-4, per_item: 1.2e-7 }) } else { None },
+			},
+			bounding_box: OperationCost {
+				st: Throughput { overhead: 5e-8, per_item: 1.5e-9 },
+				mt: Throughput { overhead: 2e-5, per_item: 4e-10 },
+				gpu,
+			},
+			containment: OperationCost {
+				st: Throughput { overhead: 5e-8, per_item: 2e-9 },
+				mt: Throughput { overhead: 2e-5, per_item: 5e-10 },
+				gpu,
+			},
+			convex_hull: OperationCost {
+				st: Throughput { overhead: 5e-8, per_item: 3e-8 },
+				mt: Throughput { overhead: 3e-5, per_item: 6e-9 },
+				gpu: if GPU_AVAILABLE { Some(Throughput { overhead: 1e-4, per_item: 2e-9 }) } else { None },
+			},
+			convexity: OperationCost {
+				st: Throughput { overhead: 5e-8, per_item: 1e-8 },
+				mt: Throughput { overhead: 2e-5, per_item: 2.5e-9 },
+				gpu: None,
+			},
+			offset: OperationCost {
+				st: Throughput { overhead: 1e-6, per_item: 4e-7 },
+				mt: Throughput { overhead: 3e-5, per_item: 2e-7 },
+				gpu: None,
+			},
+			self_intersection: OperationCost {
+				st: Throughput { overhead: 1e-6, per_item: 2e-7 },
+				mt: Throughput { overhead: 3e-5, per_item: 6e-8 },
+				gpu: None,
+			},
+			simplify: OperationCost {
+				st: Throughput { overhead: 1e-6, per_item: 1e-7 },
+				mt: Throughput { overhead: 3e-5, per_item: 3e-8 },
+				gpu: None,
+			},
+			spatial_index: OperationCost {
+				st: Throughput { overhead: 1e-7, per_item: 8e-8 },
+				mt: Throughput { overhead: 3e-5, per_item: 2.5e-8 },
+				gpu: if GPU_AVAILABLE { Some(Throughput { overhead: 2e-4, per_item: 1e-8 }) } else { None },
+			},
+			transform: OperationCost {
+				st: Throughput { overhead: 1e-7, per_item: 4e-9 },
+				mt: Throughput { overhead: 2e-5, per_item: 1e-9 },
+				gpu: if GPU_AVAILABLE { Some(Throughput { overhead: 6e-5, per_item: 1e-10 }) } else { None },
+			},
+			translate: OperationCost {
+				st: Throughput { overhead: 5e-8, per_item: 1e-9 },
+				mt: Throughput { overhead: 2e-5, per_item: 2.5e-10 },
+				gpu,
+			},
+			triangulate: OperationCost {
+				st: Throughput { overhead: 1e-6, per_item: 1e-7 },
+				mt: Throughput { overhead: 3e-5, per_item: 5e-8 },
+				gpu: None,
+			},
+			upload: Throughput { overhead: 2e-5, per_item: 1e-9 },
+			download: Throughput { overhead: 2e-5, per_item: 1e-9 },
+			task_overhead: 1e-6,
+		}
+	}
+
+	/// Measure the costs on the hardware that Apex is running on.
+	///
+	/// This runs a small benchmark for every operation on every backend. It takes about 0.1 to 0.4
+	/// seconds in an optimised build and about 2 to 4 seconds in a debug build, plus the time to
+	/// initialise the GPU, if any.
+	pub fn measure() -> CostModel {
+		let mut model = CostModel::estimate();
+		model.task_overhead = measure_task_overhead();
+		model.area = measure_operation(|polygon| { black_box(area::area_polygon_st(polygon)); }, |polygon| { black_box(area::area_polygon_mt(polygon)); });
+		model.boolean = measure_operation_sized(BOOLEAN_SMALL, BOOLEAN_LARGE, |polygon| { black_box(boolean::boolean_polygon_st(polygon, polygon, BooleanOperation::UNION, FillRule::NON_ZERO)); }, |polygon| { black_box(boolean::boolean_polygon_mt(polygon, polygon, BooleanOperation::UNION, FillRule::NON_ZERO)); });
+		model.bounding_box = measure_operation(|polygon| { black_box(bounding_box::bounding_box_polygon_st(polygon)); }, |polygon| { black_box(bounding_box::bounding_box_polygon_mt(polygon)); });
+		model.containment = measure_operation(|polygon| { black_box(containment::containment_polygon_st(polygon, black_box(&Point2D { x: 0, y: 1 }), FillRule::NON_ZERO)); }, |polygon| { black_box(containment::containment_polygon_mt(polygon, black_box(&Point2D { x: 0, y: 1 }), FillRule::NON_ZERO)); });
+		model.convex_hull = measure_operation(|polygon| { black_box(convex_hull::convex_hull_polygon_st(polygon)); }, |polygon| { black_box(convex_hull::convex_hull_polygon_mt(polygon)); });
+		model.convexity = measure_operation(|polygon| { black_box(convexity::convexity_polygon_st(polygon)); }, |polygon| { black_box(convexity::convexity_polygon_mt(polygon)); });
+		model.offset = measure_operation_sized(BOOLEAN_SMALL, BOOLEAN_LARGE, |polygon| { black_box(offset::offset_polygon_st(polygon, black_box(1), JoinType::MITER { limit: 2.0 })); }, |polygon| { black_box(offset::offset_polygon_mt(polygon, black_box(1), JoinType::MITER { limit: 2.0 })); });
+		model.self_intersection = measure_operation_sized(BOOLEAN_SMALL, BOOLEAN_LARGE, |polygon| { black_box(self_intersection::crossings_polygon_st(polygon)); }, |polygon| { black_box(self_intersection::crossings_polygon_mt(polygon)); });
+		model.simplify = measure_operation_sized(BOOLEAN_SMALL, BOOLEAN_LARGE, |polygon| { black_box(simplify::simplify_polygon_st(polygon, SimplificationMethod::DOUGLAS_PEUCKER { tolerance: 1.0 }, false)); }, |polygon| { black_box(simplify::simplify_polygon_mt(polygon, SimplificationMethod::DOUGLAS_PEUCKER { tolerance: 1.0 }, false)); });
+		model.spatial_index = measure_operation_sized(BOOLEAN_SMALL, BOOLEAN_LARGE, |polygon| { black_box(bounding_volume_hierarchy::build_st(&vertex_boxes(polygon))); }, |polygon| { black_box(bounding_volume_hierarchy::build_mt(&vertex_boxes(polygon))); });
+		model.transform = measure_operation(|polygon| { black_box(transform::transform_polygon_st(polygon, black_box(&AffineTransform::identity()))).ok(); }, |polygon| { black_box(transform::transform_polygon_mt(polygon, black_box(&AffineTransform::identity()))).ok(); });
+		model.translate = measure_operation(|polygon| { black_box(translate::translate_polygon_st(polygon, black_box(1), black_box(1), OverflowPolicy::WRAPPING)).ok(); }, |polygon| { black_box(translate::translate_polygon_mt(polygon, black_box(1), black_box(1), OverflowPolicy::WRAPPING)).ok(); });
+		model.triangulate = measure_operation_sized(BOOLEAN_SMALL, BOOLEAN_LARGE, |polygon| { black_box(triangulate::triangulate_multipolygon_st(&MultiPolygon::from_iter([Polygon::from_iter(polygon.iter().copied())]))); }, |polygon| { black_box(triangulate::triangulate_multipolygon_mt(&MultiPolygon::from_iter([Polygon::from_iter(polygon.iter().copied())]))); });
+		#[cfg(all(feature = "gpu", not(feature = "coordinate64")))]
+		if gpu::supports_coordinates() {
+			gpu_benchmarks::measure(&mut model);
+		}
+		model
+	}
+
+	/// Get the costs of a certain operation.
+	///
+	/// # Arguments
+	/// * `operation` - The operation to get the costs of.
+	///
+	/// # Result
+	/// How long that operation takes on each backend.
+	pub fn operation(&self, operation: Operation) -> &OperationCost {
+		match operation {
+			Operation::AREA => &self.area,
+			Operation::BOOLEAN => &self.boolean,
+			Operation::BOUNDING_BOX => &self.bounding_box,
+			Operation::CONTAINMENT => &self.containment,
+			Operation::CONVEX_HULL => &self.convex_hull,
+			Operation::CONVEXITY => &self.convexity,
+			Operation::OFFSET => &self.offset,
+			Operation::SELF_INTERSECTION => &self.self_intersection,
+			Operation::SIMPLIFY => &self.simplify,
+			Operation::SPATIAL_INDEX => &self.spatial_index,
+			Operation::TRANSFORM => &self.transform,
+			Operation::TRANSLATE => &self.translate,
+			Operation::TRIANGULATE => &self.triangulate,
+		}
+	}
+
+	/// Choose the backend that would perform an operation the fastest.
+	///
+	/// This includes the time to copy the data to the device where it is needed, if it's not there
+	/// yet. The GPU is only chosen if it supports the types that the operation needs.
+	///
+	/// # Arguments
+	/// * `operation` - The operation to perform.
+	/// * `num_items` - The number of items to perform the operation on.
+	/// * `sync_status` - Which copy of the data is currently up-to-date.
+	///
+	/// # Result
+	/// The backend that is estimated to be the fastest.
+	pub(crate) fn choose(&self, operation: Operation, num_items: usize, sync_status: SyncStatus) -> Backend {
+		self.choose_with(operation, num_items, sync_status, gpu::device_types)
+	}
+
+	/// Choose the backend that would perform an operation the fastest, on a GPU that supports
+	/// certain types.
+	///
+	/// # Arguments
+	/// * `operation` - The operation to perform.
+	/// * `num_items` - The number of items to perform the operation on.
+	/// * `sync_status` - Which copy of the data is currently up-to-date.
+	/// * `device_types` - Finds the types that the GPU supports. This is only called if the operation
+	/// has a GPU implementation, since it may need to initialise the GPU.
+	///
+	/// # Result
+	/// The backend that is estimated to be the fastest.
+	fn choose_with(&self, operation: Operation, num_items: usize, sync_status: SyncStatus, device_types: impl FnOnce() -> DeviceTypes) -> Backend {
+		let cost = self.operation(operation);
+		let host_transfer = if sync_status == SyncStatus::GPU { self.download.time(num_items) } else { 0.0 };
+		let mut fastest = (Backend::ST, cost.st.time(num_items) + host_transfer);
+		let mt_time = cost.mt.time(num_items) + host_transfer;
+		if mt_time < fastest.1 {
+			fastest = (Backend::MT, mt_time);
+		}
+		if let Some(gpu) = cost.gpu && gpu_supports(operation, device_types()) {
+			let gpu_transfer = if sync_status == SyncStatus::HOST { self.upload.time(num_items) } else { 0.0 };
+			if gpu.time(num_items) + gpu_transfer < fastest.1 {
+				fastest = (Backend::GPU, gpu.time(num_items) + gpu_transfer);
+			}
+		}
+		fastest.0
+	}
+
+	/// Choose how many items each thread should process at least, when performing an operation on
+	/// multiple threads.
+	///
+	/// The work is divided evenly over the threads, unless that makes the chunks so small that the
+	/// time to schedule them becomes significant.
+	///
+	/// # Arguments
+	/// * `operation` - The operation to perform.
+	/// * `num_items` - The number of items to perform the operation on.
+	///
+	/// # Result
+	/// The minimum number of items to process in each thread.
+	pub(crate) fn chunk_size(&self, operation: Operation, num_items: usize) -> usize {
+		let min_chunk_size = (self.task_overhead / (MAX_TASK_OVERHEAD_FRACTION * self.operation(operation).st.per_item)).round();
+		let min_chunk_size = min_chunk_size.clamp(1.0, usize::MAX as f64) as usize;
+		min_chunk_size.max(num_items / rayon::current_num_threads())
+	}
+}
+
+/// Measure the costs on the hardware that Apex is running on, and use them from now on.
+///
+/// Until this is called, Apex uses the estimated cost model. This takes as long as
+/// `CostModel::measure`, so it is best done once at startup. Calibrating again may be useful if the
+/// load on the system changed, for instance.
+///
+/// # Result
+/// The new cost model.
+///
+/// # Examples
+/// ```
+/// let model = apex::cost_model::calibrate();
+/// assert!(model.translate.st.per_item > 0.0);
+/// assert_eq!(apex::cost_model::current(), model);
+/// ```
+pub fn calibrate() -> CostModel {
+	let model = CostModel::measure();
+	*MODEL.write().unwrap() = Some(model.clone());
+	model
+}
+
+/// Use a certain cost model from now on.
+///
+/// This can be used to re-use measurements from an earlier run, instead of calibrating again, or to
+/// make the choice of backends predictable.
+///
+/// # Arguments
+/// * `model` - The cost model to use.
+///
+/// # Examples
+/// ```
+/// use apex::cost_model::CostModel;
+/// apex::cost_model::install(CostModel::estimate());
+/// assert_eq!(apex::cost_model::current(), CostModel::estimate());
+/// ```
+pub fn install(model: CostModel) {
+	*MODEL.write().unwrap() = Some(model);
+}
+
+/// Get the cost model that is currently used.
+///
+/// If it was not calibrated or installed yet, this is the estimated cost model.
+///
+/// # Result
+/// The cost model that is currently used.
+pub fn current() -> CostModel {
+	with_model(CostModel::clone)
+}
+
+/// Choose the backend that would perform an operation the fastest, according to the current cost
+/// model.
+///
+/// # Arguments
+/// * `operation` - The operation to perform.
+/// * `num_items` - The number of items to perform the operation on.
+/// * `sync_status` - Which copy of the data is currently up-to-date.
+///
+/// # Result
+/// The backend that is estimated to be the fastest.
+pub(crate) fn choose(operation: Operation, num_items: usize, sync_status: SyncStatus) -> Backend {
+	with_model(|model| model.choose(operation, num_items, sync_status))
+}
+
+/// Choose how many items each thread should process at least, when performing an operation on
+/// multiple threads, according to the current cost model.
+///
+/// # Arguments
+/// * `operation` - The operation to perform.
+/// * `num_items` - The number of items to perform the operation on.
+///
+/// # Result
+/// The minimum number of items to process in each thread.
+pub(crate) fn chunk_size(operation: Operation, num_items: usize) -> usize {
+	with_model(|model| model.chunk_size(operation, num_items))
+}
+
+/// Whether a GPU can perform an operation, if it supports certain types.
+///
+/// # Arguments
+/// * `operation` - The operation to perform on the GPU.
+/// * `device_types` - The types that the GPU supports.
+///
+/// # Result
+/// `true` if the GPU implementation of the operation can run on the GPU, or `false` if it can't.
+fn gpu_supports(operation: Operation, device_types: DeviceTypes) -> bool {
+	match operation {
+		Operation::TRANSFORM => device_types.supports_coordinates() && device_types.float64, //The matrix of the transformation is applied in 64-bit floating point.
+		_ => device_types.supports_coordinates(), //All GPU implementations compute with the coordinates.
+	}
+}
+
+/// Run a function with the current cost model.
+///
+/// If the cost model was not calibrated or installed yet, the estimated cost model is used.
+///
+/// # Arguments
+/// * `function` - The function to run with the current cost model.
+///
+/// # Result
+/// The result of the function.
+fn with_model<T>(function: impl FnOnce(&CostModel) -> T) -> T {
+	match MODEL.read().unwrap().as_ref() {
+		Some(model) => function(model),
+		None => function(&CostModel::estimate()),
+	}
+}
+
+/// Measure how long it takes to schedule a piece of work on a thread of the thread pool.
+///
+/// # Result
+/// The time per piece of work, in seconds.
+fn measure_task_overhead() -> f64 {
+	use rayon::prelude::*;
+
+	const NUM_TASKS: usize = 10000;
+	let time = fastest_of(|| (0..NUM_TASKS).into_par_iter().with_max_len(1).for_each(|task| { black_box(task); }));
+	time * rayon::current_num_threads() as f64 / NUM_TASKS as f64
+}
+
+/// Measure how long an operation takes on the host, single-threaded and multi-threaded.
+///
+/// # Arguments
+/// * `st` - The single-threaded implementation of the operation.
+/// * `mt` - The multi-threaded implementation of the operation.
+///
+/// # Result
+/// The costs of the operation, without a GPU implementation.
+fn measure_operation(st: impl FnMut(&mut Polygon), mt: impl FnMut(&mut Polygon)) -> OperationCost {
+	const SMALL: usize = 1000;
+	const LARGE: usize = 80000; //Large enough to be divided over multiple threads.
+	measure_operation_sized(SMALL, LARGE, st, mt)
+}
+
+/// Measure how long an operation takes on the host, with polygons of specific sizes.
+///
+/// This is for operations that are too slow to benchmark with the usual sizes.
+///
+/// # Arguments
+/// * `small_size` - The number of vertices in the small benchmark polygon.
+/// * `large_size` - The number of vertices in the large benchmark polygon.
+/// * `st` - The single-threaded implementation of the operation.
+/// * `mt` - The multi-threaded implementation of the operation.
+///
+/// # Result
+/// The costs of the operation, without a GPU implementation.
+fn measure_operation_sized(small_size: usize, large_size: usize, mut st: impl FnMut(&mut Polygon), mut mt: impl FnMut(&mut Polygon)) -> OperationCost {
+	let mut small = benchmark_polygon(small_size);
+	let mut large = benchmark_polygon(large_size);
+	OperationCost {
+		st: Throughput::fit(small_size, fastest_of(|| st(&mut small)), large_size, fastest_of(|| st(&mut large))),
+		mt: Throughput::fit(small_size, fastest_of(|| mt(&mut small)), large_size, fastest_of(|| mt(&mut large))),
+		gpu: None,
+	}
+}
+
+/// Create a polygon to run the benchmarks on.
+///
+/// The polygon is convex, which is the most common case for operations whose speed depends on the
+/// shape.
+///
+/// # Arguments
+/// * `num_vertices` - The number of vertices in the polygon. This must be at most 92681, or the
+/// coordinates will overflow.
+fn benchmark_polygon(num_vertices: usize) -> Polygon {
+	let half = num_vertices as Coordinate / 2;
+	Polygon::from_iter((-half..num_vertices as Coordinate - half).map(|i| Point2D { x: i, y: i * i })) //A parabola, closed by a straight edge.
+}
+
+/// Get a bounding box around each vertex of a polygon, to benchmark building spatial indices with.
+///
+/// # Arguments
+/// * `polygon` - The polygon whose vertices to put in boxes.
+fn vertex_boxes(polygon: &Polygon) -> Vec<BoundingBox> {
+	polygon.iter().map(|vertex| BoundingBox { min: *vertex, max: *vertex }).collect()
+}
+
+/// Measure how long a function takes, taking the fastest of several runs.
+///
+/// # Arguments
+/// * `function` - The function to measure.
+///
+/// # Result
+/// The time of the fastest run, in seconds.
+fn fastest_of(mut function: impl FnMut()) -> f64 {
+	(0..REPEATS).map(|_| {
+		let start = Instant::now();
+		function();
+		start.elapsed().as_secs_f64()
+	}).fold(f64::INFINITY, f64::min)
+}
+
+/// The benchmarks of the GPU implementations.
+#[cfg(all(feature = "gpu", not(feature = "coordinate64")))]
+mod gpu_benchmarks {
+	use super::*;
+
+	/// The number of vertices in the small polygon to benchmark with.
+	const SMALL: usize = 1000;
+
+	/// The number of vertices in the large polygon to benchmark with.
+	///
+	/// This is larger than for the host, because the GPU needs a lot of work before the time per
+	/// item becomes noticeable next to the overhead.
+	const LARGE: usize = 1_000_000;
+
+	/// The number of boxes in the large spatial index to benchmark with.
+	///
+	/// Building a spatial index sorts the boxes and builds the tree on the host, which takes too long
+	/// to benchmark with the usual large size.
+	const INDEX_LARGE: usize = 100_000;
+
+	/// The number of points to test against each polygon, to benchmark finding whether points are
+	/// inside polygons.
+	const CONTAINMENT_POINTS: usize = 16;
+
+	/// Measure the costs of the GPU implementations and of copying data to and from the GPU.
+	///
+	/// # Arguments
+	/// * `model` - The cost model to store the measurements in.
+	pub(super) fn measure(model: &mut CostModel) {
+		let mut small = Polygon::from_iter((0..SMALL as i32).map(|i| Point2D { x: i, y: i % 2 }));
+		let mut large = Polygon::from_iter((0..LARGE as i32).map(|i| Point2D { x: i, y: i % 2 }));
+		gpu::wait(); //Make sure that the GPU is initialised before measuring anything.
+
+		model.upload = Throughput::fit(
+			SMALL, fastest_of(|| { black_box(gpu::upload(small.host_vertices())); gpu::wait(); }),
+			LARGE, fastest_of(|| { black_box(gpu::upload(large.host_vertices())); gpu::wait(); }),
+		);
+		let small_handle = gpu::upload(small.host_vertices());
+		let large_handle = gpu::upload(large.host_vertices());
+		model.download = Throughput::fit(
+			SMALL, fastest_of(|| { black_box(gpu::download(&small_handle)); }),
+			LARGE, fastest_of(|| { black_box(gpu::download(&large_handle)); }),
+		);
+
+		model.area.gpu = Some(Throughput::fit(
+			SMALL, fastest_of(|| { black_box(area::area_polygon_gpu(&small)); }),
+			LARGE, fastest_of(|| { black_box(area::area_polygon_gpu(&large)); }),
+		));
+		let boolean_small = benchmark_polygon(BOOLEAN_SMALL);
+		let boolean_large = benchmark_polygon(BOOLEAN_LARGE);
+		model.boolean.gpu = Some(Throughput::fit(
+			BOOLEAN_SMALL, fastest_of(|| { black_box(boolean::boolean_polygon_gpu(&boolean_small, &boolean_small, BooleanOperation::UNION, FillRule::NON_ZERO)); }),
+			BOOLEAN_LARGE, fastest_of(|| { black_box(boolean::boolean_polygon_gpu(&boolean_large, &boolean_large, BooleanOperation::UNION, FillRule::NON_ZERO)); }),
+		));
+		model.bounding_box.gpu = Some(Throughput::fit(
+			SMALL, fastest_of(|| { black_box(bounding_box::bounding_box_polygon_gpu(&small)); }),
+			LARGE, fastest_of(|| { black_box(bounding_box::bounding_box_polygon_gpu(&large)); }),
+		));
+		let small_boxes = vertex_boxes(&small);
+		let index_boxes = vertex_boxes(&Polygon::from_iter((0..INDEX_LARGE as i32).map(|i| Point2D { x: i, y: i % 2 })));
+		model.spatial_index.gpu = Some(Throughput::fit(
+			SMALL, fastest_of(|| { black_box(bounding_volume_hierarchy::build_gpu(&small_boxes)); }),
+			INDEX_LARGE, fastest_of(|| { black_box(bounding_volume_hierarchy::build_gpu(&index_boxes)); }),
+		));
+		let points: Vec<Point2D> = (0..CONTAINMENT_POINTS as i32).map(|i| Point2D { x: i * 7, y: 1 }).collect();
+		model.containment.gpu = Some(Throughput::fit(
+			SMALL * CONTAINMENT_POINTS, fastest_of(|| { black_box(containment::containment_polygon_batch_gpu(&small, &points, FillRule::NON_ZERO)); }),
+			LARGE * CONTAINMENT_POINTS, fastest_of(|| { black_box(containment::containment_polygon_batch_gpu(&large, &points, FillRule::NON_ZERO)); }),
+		));
+		model.convex_hull.gpu = Some(Throughput::fit(
+			SMALL, fastest_of(|| { black_box(convex_hull::convex_hull_polygon_gpu(&small)); }),
+			LARGE, fastest_of(|| { black_box(convex_hull::convex_hull_polygon_gpu(&large)); }),
+		));
+		if gpu::supports_float64() {
+			model.transform.gpu = Some(Throughput::fit(
+				SMALL, fastest_of(|| { black_box(transform::transform_polygon_gpu(&mut small, black_box(&AffineTransform::identity()))).ok(); gpu::wait(); }),
+				LARGE, fastest_of(|| { black_box(transform::transform_polygon_gpu(&mut large, black_box(&AffineTransform::identity()))).ok(); gpu::wait(); }),
+			));
+		}
+		model.translate.gpu = Some(Throughput::fit(
+			SMALL, fastest_of(|| { black_box(translate::translate_polygon_gpu(&mut small, black_box(1), black_box(1), OverflowPolicy::WRAPPING)).ok(); gpu::wait(); }),
+			LARGE, fastest_of(|| { black_box(translate::translate_polygon_gpu(&mut large, black_box(1), black_box(1), OverflowPolicy::WRAPPING)).ok(); gpu::wait(); }),
+		));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+
+	/// Test estimating the time to process a number of items.
+	#[test_case(0,  1.0  ; "none")]
+	#[test_case(1,  1.25 ; "one")]
+	#[test_case(10, 3.5  ; "ten")]
+	fn throughput_time(num_items: usize, expected: f64) {
+		let throughput = Throughput { overhead: 1.0, per_item: 0.25 };
+		assert_eq!(throughput.time(num_items), expected);
+	}
+
+	/// Test deriving the throughput from two measurements.
+	#[test]
+	fn throughput_fit() {
+		let throughput = Throughput::fit(10, 3.0, 30, 7.0);
+		assert_eq!(throughput, Throughput { overhead: 1.0, per_item: 0.2 });
+	}
+
+	/// Test that noisy measurements don't result in negative times.
+	#[test]
+	fn throughput_fit_noise() {
+		let throughput = Throughput::fit(10, 3.0, 30, 2.0); //Processing more items was faster.
+		assert!(throughput.per_item > 0.0, "Processing items is never free.");
+		assert!(throughput.overhead >= 0.0, "The overhead can't be negative.");
+	}
+
+	/// Test that small amounts of work are done on a single thread, where there is no overhead.
+	#[test]
+	fn choose_small() {
+		let model = CostModel::estimate();
+		assert_eq!(model.choose(Operation::TRANSLATE, 10, SyncStatus::HOST), Backend::ST);
+		assert_eq!(model.choose(Operation::AREA, 10, SyncStatus::SYNCED), Backend::ST);
+	}
+
+	/// Test that large amounts of work are done on multiple threads, if the data is on the host.
+	#[test]
+	fn choose_large() {
+		let model = CostModel { upload: Throughput { overhead: 1.0, per_item: 1.0 }, ..CostModel::estimate() }; //Make copying to the GPU very slow.
+		assert_eq!(model.choose(Operation::TRANSLATE, 10_000_000, SyncStatus::HOST), Backend::MT);
+		assert_eq!(model.choose(Operation::CONVEXITY, 10_000_000, SyncStatus::HOST), Backend::MT);
+	}
+
+	/// Test that the GPU is never chosen for operations without a GPU implementation.
+	#[test]
+	fn choose_no_gpu_implementation() {
+		let model = CostModel::estimate();
+		assert_ne!(model.choose(Operation::CONVEXITY, 10_000_000, SyncStatus::GPU), Backend::GPU, "There is no GPU implementation for convexity.");
+	}
+
+	/// Test that data on the GPU stays there for operations with a GPU implementation.
+	#[cfg(all(feature = "gpu", not(feature = "coordinate64")))]
+	#[test]
+	fn choose_gpu_resident() {
+		let model = CostModel::estimate();
+		assert_eq!(model.choose(Operation::TRANSLATE, 10, SyncStatus::GPU), Backend::GPU, "Copying the data back is more expensive than translating it on the GPU.");
+	}
+
+	/// Test that the GPU is never chosen with 64-bit coordinates, even for data that is on the GPU.
+	#[cfg(feature = "coordinate64")]
+	#[test]
+	fn choose_no_gpu_64_bit() {
+		let model = CostModel::estimate();
+		assert_ne!(model.choose(Operation::TRANSLATE, 10, SyncStatus::GPU), Backend::GPU, "No GPU supports the 128-bit products of 64-bit coordinates.");
+		assert_ne!(model.choose(Operation::AREA, 10_000_000, SyncStatus::HOST), Backend::GPU, "No GPU supports the 128-bit products of 64-bit coordinates.");
+	}
+
+	/// Test that the GPU is never chosen if it doesn't support 64-bit integers, even for data that is
+	/// on the GPU.
+	#[test]
+	fn choose_gpu_unsupported() {
+		let instant = Throughput { overhead: 0.0, per_item: 0.0 };
+		let model = CostModel { translate: OperationCost { gpu: Some(instant), ..CostModel::estimate().translate }, ..CostModel::estimate() }; //Pretend that the GPU is infinitely fast.
+		let unsupported = || DeviceTypes { int64: false, float64: false };
+		assert_ne!(model.choose_with(Operation::TRANSLATE, 10, SyncStatus::GPU, unsupported), Backend::GPU, "The GPU can't compute with the coordinates.");
+		assert_ne!(model.choose_with(Operation::TRANSLATE, 10_000_000, SyncStatus::HOST, unsupported), Backend::GPU, "The GPU can't compute with the coordinates.");
+		let supported = || DeviceTypes { int64: true, float64: true };
+		assert_eq!(model.choose_with(Operation::TRANSLATE, 10, SyncStatus::GPU, supported) == Backend::GPU, !cfg!(feature = "coordinate64"), "With 32-bit coordinates, the fastest backend is chosen.");
+	}
+
+	/// Test that transformations are never done on a GPU that doesn't support 64-bit floating point.
+	#[test]
+	fn choose_transform_no_float64() {
+		let instant = Throughput { overhead: 0.0, per_item: 0.0 };
+		let model = CostModel { transform: OperationCost { gpu: Some(instant), ..CostModel::estimate().transform }, ..CostModel::estimate() }; //Pretend that the GPU is infinitely fast.
+		let no_float64 = || DeviceTypes { int64: true, float64: false };
+		assert_ne!(model.choose_with(Operation::TRANSFORM, 10, SyncStatus::GPU, no_float64), Backend::GPU, "The matrix of the transformation needs 64-bit floating point.");
+	}
+
+	/// Test that the chunks for multi-threading divide the work evenly, unless they become too small.
+	#[test]
+	fn chunk_size() {
+		let model = CostModel { task_overhead: 1e-6, ..CostModel::estimate() };
+		let min_chunk_size = model.chunk_size(Operation::TRANSLATE, 0);
+		assert_eq!(min_chunk_size, 10000, "Scheduling a chunk may take at most 10% of the time to process it, which is 1ns per vertex.");
+		let num_items = min_chunk_size * rayon::current_num_threads() * 10;
+		assert_eq!(model.chunk_size(Operation::TRANSLATE, num_items), num_items / rayon::current_num_threads(), "With enough work, it is divided evenly.");
+	}
+
+	/// Test measuring the costs on this hardware.
+	#[test]
+	fn measure() {
+		let model = CostModel::measure();
+		for operation in [Operation::AREA, Operation::BOOLEAN, Operation::BOUNDING_BOX, Operation::CONTAINMENT, Operation::CONVEX_HULL, Operation::CONVEXITY, Operation::OFFSET, Operation::SELF_INTERSECTION, Operation::SIMPLIFY, Operation::SPATIAL_INDEX, Operation::TRANSFORM, Operation::TRANSLATE, Operation::TRIANGULATE] {
+			let cost = model.operation(operation);
+			for throughput in [cost.st, cost.mt] {
+				assert!(throughput.overhead.is_finite() && throughput.overhead >= 0.0, "The overhead must be a valid time.");
+				assert!(throughput.per_item.is_finite() && throughput.per_item > 0.0, "The time per item must be a valid time.");
+			}
+		}
+		assert!(model.task_overhead.is_finite() && model.task_overhead >= 0.0, "The task overhead must be a valid time.");
+	}
+}
\ No newline at end of file
@@ -0,0 +1,36 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! Defines a struct that describes where the boundary of a shape intersects itself.
+
+use crate::Point2D; //The position of the crossing.
+
+/// A place where two edges of a shape meet, other than at the vertex between consecutive edges.
+///
+/// The edges are identified by the index of their first vertex. Edge `i` goes from vertex `i` to the
+/// next vertex of the same contour. For multi-polygons, the indices count the vertices of all
+/// contours together, in the order that they are stored in.
+///
+/// Crossings can be ordered. They are ordered by their first edge, then by their second edge.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Crossing {
+	/// The edge with the lowest index of the two.
+	pub first_edge: usize,
+
+	/// The edge with the highest index of the two.
+	pub second_edge: usize,
+
+	/// Where the edges meet.
+	///
+	/// If the edges cross or touch each other in a single point, this is that point, rounded to the
+	/// nearest coordinates, with halves rounded up as `predicates::RationalPoint::rounded` does. If
+	/// they overlap along a stretch, this is the lowest point of the overlap in lexicographic order,
+	/// except for consecutive edges, which already meet at their shared vertex. For those, this is
+	/// the other end of the overlap.
+	pub point: Point2D,
+}
\ No newline at end of file
@@ -0,0 +1,225 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! Provides access to the GPU that Apex uses for its GPU implementations.
+//!
+//! Which GPU runtime is used is selected at compile time, through the Cargo features of this
+//! library. The `cuda` feature selects CUDA, the `wgpu` feature selects WebGPU (Vulkan, Metal or
+//! DirectX), and the `cpu` feature selects the CubeCL CPU runtime. The CPU runtime is not a GPU,
+//! but it executes the same kernels, which makes it possible to test the GPU implementations on
+//! machines without a GPU. If multiple runtimes are enabled, CUDA is preferred over WebGPU, which
+//! is preferred over the CPU runtime.
+//!
+//! If none of these features are enabled, the GPU implementations are not compiled at all.
+//!
+//! Not every GPU supports every data type. The kernels store coordinates as 32-bit integers, but
+//! compute with them in 64-bit integers, so that products of coordinates can't overflow. Many GPUs
+//! lack 64-bit integers, or only offer them as an optional feature, like WebGPU does. Which types
+//! are supported is therefore queried from the device when Apex runs, and the GPU implementations
+//! process the coordinates on the host instead if the device can't compute with them. With 64-bit
+//! coordinates, the products would need 128-bit integers, which no GPU offers, so then the GPU is
+//! never used.
+
+#[cfg(feature = "gpu")]
+use std::sync::OnceLock; //To query the supported types only once.
+
+#[cfg(feature = "gpu")]
+use cubecl::ir::{ElemType, FloatKind, IntKind, UIntKind}; //To query which types the GPU supports.
+#[cfg(feature = "gpu")]
+use cubecl::prelude::{ComputeClient, CubeCount, CubeDim, CubeElement, Runtime}; //To get a compute client and launch dimensions.
+use cubecl::server::Handle; //To refer to memory on the GPU.
+
+#[cfg(feature = "gpu")]
+use crate::Coordinate; //Vertices are stored on the GPU as flat arrays of coordinates.
+use crate::Point2D; //To copy vertices to and from the GPU.
+
+#[cfg(all(feature = "gpu", not(any(feature = "cuda", feature = "wgpu", feature = "cpu"))))]
+compile_error!("The \"gpu\" feature only enables shared code. Enable one of the runtime features instead: \"cuda\", \"wgpu\" or \"cpu\".");
+
+/// The CubeCL runtime that the GPU implementations are executed with.
+#[cfg(feature = "cuda")]
+pub(crate) type GpuRuntime = cubecl::cuda::CudaRuntime;
+
+/// The CubeCL runtime that the GPU implementations are executed with.
+#[cfg(all(feature = "wgpu", not(feature = "cuda")))]
+pub(crate) type GpuRuntime = cubecl::wgpu::WgpuRuntime;
+
+/// The CubeCL runtime that the GPU implementations are executed with.
+#[cfg(all(feature = "cpu", not(any(feature = "cuda", feature = "wgpu"))))]
+pub(crate) type GpuRuntime = cubecl::cpu::CpuRuntime;
+
+/// The number of work units in each cube that the kernels of Apex are launched with.
+#[cfg(feature = "gpu")]
+const CUBE_SIZE: u32 = 256;
+
+/// The maximum number of cubes that can be launched along a single axis.
+///
+/// This is the lowest limit imposed by any of the supported runtimes.
+#[cfg(feature = "gpu")]
+const MAX_CUBES_PER_AXIS: u32 = 65535;
+
+/// The data types that the GPU supports, beyond the 32-bit types that every GPU supports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct DeviceTypes {
+	/// Whether the GPU can compute with signed and unsigned 64-bit integers.
+	pub(crate) int64: bool,
+
+	/// Whether the GPU can compute with 64-bit floating point numbers.
+	pub(crate) float64: bool,
+}
+
+impl DeviceTypes {
+	/// Whether a GPU with these types can compute with the coordinates of Apex.
+	///
+	/// # Result
+	/// `true` if the GPU implementations can process the coordinates on this GPU, or `false` if they
+	/// need to be processed on the host instead.
+	pub(crate) fn supports_coordinates(&self) -> bool {
+		!cfg!(feature = "coordinate64") && self.int64
+	}
+}
+
+/// Get a client to communicate with the default GPU device.
+///
+/// The runtime caches the client for each device, so this is cheap to call repeatedly.
+#[cfg(feature = "gpu")]
+pub(crate) fn client() -> ComputeClient<<GpuRuntime as Runtime>::Server> {
+	GpuRuntime::client(&Default::default())
+}
+
+/// Find which data types the default GPU device supports.
+///
+/// This is only queried once, the first time it is needed, since it requires initialising the GPU.
+///
+/// # Result
+/// The data types that the GPU supports.
+#[cfg(feature = "gpu")]
+pub(crate) fn device_types() -> DeviceTypes {
+	static DEVICE_TYPES: OnceLock<DeviceTypes> = OnceLock::new();
+	*DEVICE_TYPES.get_or_init(|| {
+		let client = client();
+		let properties = client.properties();
+		DeviceTypes {
+			int64: properties.supports_type(ElemType::Int(IntKind::I64)) && properties.supports_type(ElemType::UInt(UIntKind::U64)),
+			float64: properties.supports_type(ElemType::Float(FloatKind::F64)),
+		}
+	})
+}
+
+/// Find which data types the default GPU device supports.
+///
+/// Without a GPU runtime, there is no GPU to support anything.
+#[cfg(not(feature = "gpu"))]
+pub(crate) fn device_types() -> DeviceTypes {
+	DeviceTypes { int64: false, float64: false }
+}
+
+/// Whether the GPU implementations can compute with the coordinates on the default GPU device.
+///
+/// If not, they need to process the coordinates on the host instead.
+///
+/// # Result
+/// `true` if the GPU can compute with the coordinates, or `false` if it can't.
+#[cfg(feature = "gpu")]
+pub(crate) fn supports_coordinates() -> bool {
+	device_types().supports_coordinates()
+}
+
+/// Whether the default GPU device can compute with 64-bit floating point numbers.
+///
+/// Transforming coordinates needs these, to compute the same way as on the host.
+///
+/// # Result
+/// `true` if the GPU supports 64-bit floating point numbers, or `false` if it doesn't.
+#[cfg(feature = "gpu")]
+pub(crate) fn supports_float64() -> bool {
+	device_types().float64
+}
+
+/// Compute the launch dimensions for a kernel that processes a certain number of work items.
+///
+/// The kernels in Apex use `ABSOLUTE_POS` to find which work item they need to process, and check
+/// whether that position is in range. This spreads the cubes over multiple axes if there are too
+/// many cubes for a single axis.
+///
+/// # Arguments
+/// * `work_items` - The number of work items that need to be processed, each by one work unit.
+///
+/// # Result
+/// The number of cubes to launch and the dimensions of each cube.
+#[cfg(feature = "gpu")]
+pub(crate) fn launch_dimensions(work_items: usize) -> (CubeCount, CubeDim) {
+	let num_cubes = work_items.div_ceil(CUBE_SIZE as usize).max(1) as u32;
+	let cubes_x = num_cubes.min(MAX_CUBES_PER_AXIS);
+	let cubes_y = num_cubes.div_ceil(cubes_x);
+	(CubeCount::Static(cubes_x, cubes_y, 1), CubeDim::new_1d(CUBE_SIZE))
+}
+
+/// Wait until the GPU has finished all work that was submitted to it.
+///
+/// Kernels are launched asynchronously. Reading memory from the GPU waits for them automatically,
+/// but to measure how long the kernels take, it is necessary to wait for them explicitly.
+#[cfg(all(feature = "gpu", not(feature = "coordinate64")))] //With 64-bit coordinates, the GPU implementations are not benchmarked.
+pub(crate) fn wait() {
+	cubecl::future::block_on(client().sync());
+}
+
+/// Copy a list of vertices to the GPU.
+///
+/// On the GPU, the vertices are stored as a flat array of coordinates, with the X and Y coordinates
+/// of each vertex interleaved.
+///
+/// # Arguments
+/// * `vertices` - The vertices to copy to the GPU.
+///
+/// # Result
+/// A handle to the memory on the GPU that contains the vertices.
+#[cfg(feature = "gpu")]
+pub(crate) fn upload(vertices: &[Point2D]) -> Handle {
+	let coordinates: Vec<Coordinate> = vertices.iter().flat_map(|vertex| [vertex.x, vertex.y]).collect();
+	upload_coordinates(&coordinates)
+}
+
+/// Copy a flat array of coordinates to the GPU, as they are.
+///
+/// This is the layout in which vertices are stored on the GPU, so coordinates that are already
+/// interleaved can be copied without converting them first.
+///
+/// # Arguments
+/// * `coordinates` - The coordinates to copy to the GPU, with the X and Y coordinates of each vertex
+/// interleaved.
+///
+/// # Result
+/// A handle to the memory on the GPU that contains the coordinates.
+#[cfg(feature = "gpu")]
+pub(crate) fn upload_coordinates(coordinates: &[Coordinate]) -> Handle {
+	client().create(Coordinate::as_bytes(coordinates))
+}
+
+/// Copy a list of vertices from the GPU back to the host.
+///
+/// # Arguments
+/// * `handle` - A handle to the memory on the GPU that contains the vertices, as a flat array of
+/// coordinates with the X and Y coordinates of each vertex interleaved.
+///
+/// # Result
+/// The vertices that were stored on the GPU.
+#[cfg(feature = "gpu")]
+pub(crate) fn download(handle: &Handle) -> Vec<Point2D> {
+	let bytes = client().read_one(handle.clone());
+	Coordinate::from_bytes(&bytes).chunks_exact(2).map(|pair| Point2D { x: pair[0], y: pair[1] }).collect()
+}
+
+/// Copy a list of vertices from the GPU back to the host.
+///
+/// Without a GPU runtime, nothing can be copied to the GPU, so there is never anything to copy
+/// back.
+#[cfg(not(feature = "gpu"))]
+pub(crate) fn download(_handle: &Handle) -> Vec<Point2D> {
+	unreachable!("Without a GPU runtime, the GPU copy can never be leading.")
+}
\ No newline at end of file
@@ -11,4 +11,6 @@
 //! The files in this module are separated from the rest, to make it easier to find what you need in
 //! the source code of this library.
 
-pub(crate) mod sync_status;
\ No newline at end of file
+pub(crate) mod gpu;
+pub(crate) mod sync_status;
+pub(crate) mod vertex_buffer;
\ No newline at end of file
@@ -0,0 +1,175 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! Provides a list of vertices that can be kept on the host, the GPU, or both.
+
+use std::sync::OnceLock; //For thread-safe interior mutability to keep CPU and GPU in sync.
+use cubecl::server::Handle; //To refer to the copy of the vertices on the GPU.
+
+use crate::Coordinate; //To count the vertices on the GPU.
+use crate::Point2D; //The vertices in the buffer.
+use crate::detail::gpu; //To copy the vertices between the host and the GPU.
+use crate::detail::sync_status::SyncStatus; //To report which copy is up-to-date.
+
+/// A list of vertices, of which there is a copy on the host, on the GPU, or both.
+///
+/// The geometric types of Apex store their vertices in this buffer. Whenever the vertices are
+/// needed on a device where they are outdated, they are copied there. Whenever the vertices are
+/// modified on one device, the copy on the other device is released.
+///
+/// Reading the vertices only requires a shared reference, even if they need to be copied first.
+/// If multiple threads need to copy them at the same time, only one of them copies them, and the
+/// others wait for it. After that, reading them requires no synchronisation at all.
+pub(crate) struct VertexBuffer {
+	/// The copy of the vertices that is in the host CPU's RAM.
+	///
+	/// If the copy on the GPU was modified, this copy is outdated, and is left empty until it is
+	/// needed again.
+	host: OnceLock<Vec<Point2D>>,
+
+	/// The copy of the vertices that is on the GPU, as a flat array of coordinates with the X and Y
+	/// coordinates of each vertex interleaved.
+	///
+	/// Before the first time that the vertices get synced to the GPU, and whenever the copy on the
+	/// host was modified, this is left empty.
+	///
+	/// Together with the copy on the host, this tracks the up-to-date-ness of the vertex data. The
+	/// copies that are not empty are up-to-date. At least one of them is always up-to-date.
+	gpu: OnceLock<Handle>,
+}
+
+impl VertexBuffer {
+	/// Create a buffer of vertices that are on the host.
+	///
+	/// # Arguments
+	/// * `vertices` - The vertices to store in the buffer.
+	pub(crate) fn new(vertices: Vec<Point2D>) -> Self {
+		VertexBuffer {
+			host: OnceLock::from(vertices),
+			gpu: OnceLock::new(),
+		}
+	}
+
+	/// Create a buffer of vertices that are only on the GPU.
+	///
+	/// # Arguments
+	/// * `handle` - The vertices on the GPU, as a flat array of coordinates with the X and Y
+	/// coordinates of each vertex interleaved.
+	#[cfg(feature = "gpu")]
+	pub(crate) fn from_gpu(handle: Handle) -> Self {
+		VertexBuffer {
+			host: OnceLock::new(),
+			gpu: OnceLock::from(handle),
+		}
+	}
+
+	/// Get the number of vertices in the buffer.
+	///
+	/// This doesn't copy the vertices to the host if they are only on the GPU.
+	pub(crate) fn len(&self) -> usize {
+		match self.host.get() {
+			Some(vertices) => vertices.len(),
+			None => { //Don't copy the vertices to the host just to count them.
+				let handle = self.gpu.get().expect("The host copy is outdated, so the GPU copy must exist.");
+				handle.size() as usize / (2 * size_of::<Coordinate>())
+			}
+		}
+	}
+
+	/// Get which copy of the vertices is currently the most up-to-date.
+	pub(crate) fn sync_status(&self) -> SyncStatus {
+		match (self.host.get().is_some(), self.gpu.get().is_some()) {
+			(true, false) => SyncStatus::HOST,
+			(false, true) => SyncStatus::GPU,
+			(true, true) => SyncStatus::SYNCED,
+			(false, false) => unreachable!("At least one of the copies must be up-to-date."),
+		}
+	}
+
+	/// Obtain the vertices on the host.
+	///
+	/// If the latest version of the vertices is in the GPU rather than the host, it will be copied
+	/// to the host's RAM. If the latest version of the vertices is on the CPU (or they are in
+	/// sync), it will simply give a reference to those.
+	pub(crate) fn host(&self) -> &Vec<Point2D> {
+		self.host.get_or_init(|| gpu::download(self.gpu.get().expect("The host copy is outdated, so the GPU copy must exist.")))
+	}
+
+	/// Obtain the vertices on the host, allowing their modification.
+	///
+	/// If the latest version of the vertices is in the GPU rather than the host, it will be copied
+	/// to the host's RAM first.
+	///
+	/// Since the vertices may be modified, the host becomes the leading copy. The copy on the GPU is
+	/// released. The next time the vertices are needed on the GPU, they will be copied there again.
+	pub(crate) fn host_mut(&mut self) -> &mut Vec<Point2D> {
+		self.host();
+		self.gpu.take();
+		self.host.get_mut().expect("After syncing, the host copy must exist.")
+	}
+
+	/// Obtain the vertices on the GPU.
+	///
+	/// If the latest version of the vertices is in the host rather than the GPU, it will be copied
+	/// to the GPU first. If the latest version of the vertices is in the GPU (or they are in sync),
+	/// it will simply give a reference to those.
+	#[cfg(feature = "gpu")]
+	pub(crate) fn gpu(&self) -> &Handle {
+		self.gpu.get_or_init(|| gpu::upload(self.host.get().expect("The GPU copy is outdated, so the host copy must exist.")))
+	}
+
+	/// Obtain the vertices on the GPU, allowing their modification.
+	///
+	/// If the latest version of the vertices is in the host rather than the GPU, it will be copied
+	/// to the GPU first.
+	///
+	/// Since the vertices may be modified, the GPU becomes the leading copy. The copy on the host is
+	/// released. The next time the vertices are needed on the host, they will be copied back.
+	#[cfg(feature = "gpu")]
+	pub(crate) fn gpu_mut(&mut self) -> &mut Handle {
+		self.gpu();
+		self.host.take();
+		self.gpu.get_mut().expect("After syncing, the GPU copy must exist.")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Test that new buffers start out with their vertices on the host.
+	#[test]
+	fn new() {
+		let buffer = VertexBuffer::new(vec![Point2D { x: 1, y: 2 }, Point2D { x: 3, y: 4 }]);
+		assert_eq!(buffer.sync_status(), SyncStatus::HOST, "New buffers only exist on the host.");
+		assert_eq!(buffer.len(), 2);
+	}
+
+	/// Test that modifying the vertices on the host keeps the host leading.
+	#[test]
+	fn host_mut() {
+		let mut buffer = VertexBuffer::new(vec![]);
+		buffer.host_mut().push(Point2D { x: 1, y: 2 });
+		assert_eq!(*buffer.host(), vec![Point2D { x: 1, y: 2 }]);
+		assert_eq!(buffer.sync_status(), SyncStatus::HOST, "The vertices were never needed on the GPU.");
+	}
+
+	/// Test copying the vertices to the GPU and back.
+	#[cfg(feature = "gpu")]
+	#[test]
+	fn round_trip() {
+		let mut buffer = VertexBuffer::new(vec![Point2D { x: 1, y: 2 }, Point2D { x: 3, y: 4 }]);
+		buffer.gpu();
+		assert_eq!(buffer.sync_status(), SyncStatus::SYNCED, "After copying to the GPU, both copies are up-to-date.");
+		buffer.gpu_mut();
+		assert_eq!(buffer.sync_status(), SyncStatus::GPU, "The GPU copy may have been modified, so it is leading.");
+		assert_eq!(buffer.len(), 2, "The length is taken from the GPU copy.");
+		assert_eq!(*buffer.host(), vec![Point2D { x: 1, y: 2 }, Point2D { x: 3, y: 4 }], "The vertices must be copied back unchanged.");
+		assert_eq!(buffer.sync_status(), SyncStatus::SYNCED, "After copying back to the host, both copies are up-to-date.");
+	}
+}
\ No newline at end of file
@@ -0,0 +1,78 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! Defines an enum for the rules that decide which parts of a self-intersecting or overlapping
+//! shape are filled.
+
+/// The rules that decide which regions of a shape are inside the shape.
+///
+/// Each rule is based on the winding number of the shape around a point: how many times the
+/// contours of the shape go around the point counter-clockwise, minus how many times they go around
+/// it clockwise. For simple shapes, every rule gives the same result. They only differ for regions
+/// where contours overlap or where a contour intersects itself.
+#[allow(non_camel_case_types)] //The variants are capitalised like those of the other enums, with underscores between words.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FillRule {
+	/// Regions with an odd winding number are filled. Overlapping regions alternate between filled
+	/// and empty.
+	EVEN_ODD,
+
+	/// Regions with a winding number other than zero are filled, regardless of the direction of the
+	/// contours around it.
+	NON_ZERO,
+
+	/// Regions with a positive winding number are filled. Clockwise contours only cut away from
+	/// counter-clockwise contours around them.
+	POSITIVE,
+
+	/// Regions with a negative winding number are filled. This is the mirror image of `POSITIVE`.
+	NEGATIVE,
+}
+
+impl FillRule {
+	/// Check whether a region with a certain winding number is filled according to this rule.
+	///
+	/// # Arguments
+	/// * `winding_number` - The winding number of the shape around the region.
+	///
+	/// # Result
+	/// Whether the region is inside the shape.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::FillRule;
+	/// assert!(!FillRule::EVEN_ODD.is_filled(2));
+	/// assert!(FillRule::NON_ZERO.is_filled(2));
+	/// assert!(!FillRule::POSITIVE.is_filled(-1));
+	/// ```
+	pub fn is_filled(&self, winding_number: i32) -> bool {
+		match self {
+			FillRule::EVEN_ODD => winding_number % 2 != 0,
+			FillRule::NON_ZERO => winding_number != 0,
+			FillRule::POSITIVE => winding_number > 0,
+			FillRule::NEGATIVE => winding_number < 0,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+
+	/// Test which winding numbers each rule fills.
+	#[test_case(FillRule::EVEN_ODD, [false, true, false, true, false] ; "even-odd")]
+	#[test_case(FillRule::NON_ZERO, [true, true, false, true, true] ; "non-zero")]
+	#[test_case(FillRule::POSITIVE, [false, false, false, true, true] ; "positive")]
+	#[test_case(FillRule::NEGATIVE, [true, true, false, false, false] ; "negative")]
+	fn is_filled(rule: FillRule, expected: [bool; 5]) {
+		for (winding_number, filled) in (-2..=2).zip(expected) {
+			assert_eq!(rule.is_filled(winding_number), filled, "Winding number {winding_number}.");
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,634 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! Reads and writes polygons in the compact binary format of this library.
+//!
+//! The format is meant to store large numbers of polygons, such as caches of sliced layers. It is
+//! written and read one polygon at a time, so huge files never need to be in memory at once. The
+//! coordinates are stored exactly, without a scale.
+//!
+//! A file starts with a header of the 4 bytes `APEX`, followed by the version of the format as a
+//! little-endian 16-bit integer. After that, each polygon is stored in a record:
+//! * The number of vertices plus one, as a variable-length integer.
+//! * For each vertex, the difference of its X and Y coordinates from those of the previous vertex,
+//! as zigzag-encoded variable-length integers. The first vertex is relative to the origin.
+//! * A CRC-32 checksum of the record, as a little-endian 32-bit integer.
+//!
+//! The file ends with a record that holds a 0, followed by the number of polygons in the file as a
+//! variable-length integer, and a checksum. Variable-length integers are stored in LEB128: 7 bits
+//! per byte, least significant first, with the highest bit set in all bytes except the last.
+//! Zigzag encoding maps 0, -1, 1, -2, ... to 0, 1, 2, 3, ... so that small differences take few
+//! bytes regardless of their sign.
+//!
+//! The reader reports corruption as errors: a wrong header, records that don't match their
+//! checksums, and files that end before their last record. Since the differences are stored with
+//! more bits than coordinates have, files can be written with 64-bit coordinates and read with
+//! 32-bit coordinates, as long as the coordinates fit.
+
+use std::error::Error; //The errors of the reader can be propagated with other errors.
+use std::fmt; //To describe the errors as text.
+use std::io::ErrorKind; //To recognise files that end too early.
+use std::io::Read; //To read the polygons from any source.
+use std::io::Write; //To write the polygons to any destination.
+
+use crate::Coordinate; //To decode the coordinates directly into interleaved arrays.
+use crate::Point2D; //The vertices that are read and written.
+use crate::Polygon; //The polygons that are read and written.
+
+/// The bytes that every file in this format starts with.
+const MAGIC: [u8; 4] = *b"APEX";
+
+/// The version of the format that is written, and the newest version that can be read.
+pub const VERSION: u16 = 1;
+
+/// The number of vertices to reserve memory for at most before reading them.
+///
+/// Corrupt vertex counts are only detected by the checksum after the vertices are read, so they
+/// shouldn't cause huge allocations up front.
+const MAX_RESERVED_VERTICES: usize = 1 << 16;
+
+/// The table for computing CRC-32 checksums one byte at a time.
+const CRC_TABLE: [u32; 256] = crc_table();
+
+/// The reasons that polygons can't be read or written.
+#[allow(non_camel_case_types)] //The variants are capitalised like those of the other enums, with underscores between words.
+#[derive(Debug)]
+pub enum BinaryError {
+	/// Reading from the source or writing to the destination failed.
+	IO(std::io::Error),
+
+	/// The data doesn't start with the header of this format.
+	NOT_APEX,
+
+	/// The data was written in a newer version of the format, which this version of the library
+	/// can't read. This holds the version.
+	UNSUPPORTED_VERSION(u16),
+
+	/// The data ends before the record that marks the end of the file.
+	TRUNCATED,
+
+	/// A record doesn't match its checksum, or can't be decoded. This holds the index of the
+	/// record, where the record that marks the end of the file comes after all polygons.
+	CORRUPT(usize),
+
+	/// A coordinate is outside of the range of `Coordinate`. The data may have been written with
+	/// 64-bit coordinates.
+	OVERFLOW,
+}
+
+impl fmt::Display for BinaryError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			BinaryError::IO(error) => write!(f, "Reading or writing the polygons failed: {}", error),
+			BinaryError::NOT_APEX => write!(f, "The data is not in the binary format of Apex."),
+			BinaryError::UNSUPPORTED_VERSION(version) => write!(f, "Version {} of the binary format is not supported.", version),
+			BinaryError::TRUNCATED => write!(f, "The data ends before the end of the file is marked."),
+			BinaryError::CORRUPT(record) => write!(f, "Record {} of the data is corrupt.", record),
+			BinaryError::OVERFLOW => write!(f, "A coordinate in the data went out of the range of the coordinate type."),
+		}
+	}
+}
+
+impl Error for BinaryError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			BinaryError::IO(error) => Some(error),
+			_ => None,
+		}
+	}
+}
+
+impl From<std::io::Error> for BinaryError {
+	fn from(error: std::io::Error) -> BinaryError {
+		BinaryError::IO(error)
+	}
+}
+
+/// Writes polygons to a destination, one at a time.
+///
+/// The header is written when the writer is created. The file is only complete after `finish` is
+/// called, which marks the end of the file. Each polygon is written with a few small writes, so
+/// for files it is best to wrap the file in a `BufWriter`.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// use apex::io::binary::{BinaryReader, BinaryWriter};
+/// let square = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 1000, y: 0 }, Point2D { x: 1000, y: 1000 }, Point2D { x: 0, y: 1000 }]);
+/// let mut writer = BinaryWriter::new(Vec::new()).unwrap();
+/// writer.write_polygon(&square).unwrap();
+/// let data = writer.finish().unwrap();
+/// assert_eq!(data.len(), 6 + 16 + 6, "The header, the square with its checksum, and the end.");
+///
+/// let polygons: Vec<Polygon> = BinaryReader::new(data.as_slice()).unwrap().collect::<Result<_, _>>().unwrap();
+/// assert_eq!(polygons, vec![square]);
+/// ```
+pub struct BinaryWriter<W: Write> {
+	/// The destination to write to.
+	destination: W,
+
+	/// The number of polygons that were written.
+	num_polygons: usize,
+
+	/// The record that is being encoded, kept to reuse its memory.
+	record: Vec<u8>,
+}
+
+impl<W: Write> BinaryWriter<W> {
+	/// Start writing polygons to a destination, by writing the header.
+	///
+	/// # Arguments
+	/// * `destination` - Where to write the polygons to.
+	///
+	/// # Result
+	/// The writer, or the error that occurred while writing the header.
+	pub fn new(mut destination: W) -> Result<Self, BinaryError> {
+		destination.write_all(&MAGIC)?;
+		destination.write_all(&VERSION.to_le_bytes())?;
+		Ok(BinaryWriter { destination, num_polygons: 0, record: Vec::new() })
+	}
+
+	/// Write a polygon.
+	///
+	/// If the vertices of the polygon are on the GPU, they are copied to the host to write them.
+	///
+	/// # Arguments
+	/// * `polygon` - The polygon to write.
+	pub fn write_polygon(&mut self, polygon: &Polygon) -> Result<(), BinaryError> {
+		self.record.clear();
+		write_varint(&mut self.record, polygon.len() as u128 + 1);
+		let mut previous = (0i128, 0i128);
+		for vertex in polygon.iter() {
+			let (x, y) = (vertex.x as i128, vertex.y as i128);
+			write_varint(&mut self.record, zigzag(x - previous.0));
+			write_varint(&mut self.record, zigzag(y - previous.1));
+			previous = (x, y);
+		}
+		self.write_record()?;
+		self.num_polygons += 1;
+		Ok(())
+	}
+
+	/// Mark the end of the file and flush the destination.
+	///
+	/// # Result
+	/// The destination, to continue using it, or the error that occurred while writing.
+	pub fn finish(mut self) -> Result<W, BinaryError> {
+		self.record.clear();
+		write_varint(&mut self.record, 0);
+		write_varint(&mut self.record, self.num_polygons as u128);
+		self.write_record()?;
+		self.destination.flush()?;
+		Ok(self.destination)
+	}
+
+	/// Write the encoded record, followed by its checksum.
+	fn write_record(&mut self) -> Result<(), BinaryError> {
+		let checksum = !self.record.iter().fold(!0, |crc, byte| crc_update(crc, *byte));
+		self.destination.write_all(&self.record)?;
+		self.destination.write_all(&checksum.to_le_bytes())?;
+		Ok(())
+	}
+}
+
+/// Reads polygons from a source, one at a time.
+///
+/// The header is read and checked when the reader is created. The reader is also an iterator over
+/// the polygons, which ends at the end of the file, or after the first error. The source is read
+/// one byte at a time, so for files it is best to wrap the file in a `BufReader`.
+///
+/// # Examples
+/// ```
+/// use apex::{Coordinate, Point2D, Polygon};
+/// use apex::io::binary::{BinaryReader, BinaryWriter};
+/// let mut writer = BinaryWriter::new(Vec::new()).unwrap();
+/// writer.write_polygon(&Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 0, y: 10 }])).unwrap();
+/// writer.write_polygon(&Polygon::from_iter([Point2D { x: 5, y: 5 }, Point2D { x: 6, y: 5 }, Point2D { x: 5, y: 6 }])).unwrap();
+/// let data = writer.finish().unwrap();
+///
+/// let mut reader = BinaryReader::new(data.as_slice()).unwrap();
+/// let mut coordinates: Vec<Coordinate> = Vec::new(); //All polygons in one interleaved array.
+/// while let Some(num_vertices) = reader.read_coordinates(&mut coordinates).unwrap() {
+/// 	assert_eq!(num_vertices, 3);
+/// }
+/// assert_eq!(coordinates, vec![0, 0, 10, 0, 0, 10, 5, 5, 6, 5, 5, 6]);
+/// ```
+pub struct BinaryReader<R: Read> {
+	/// The source to read from.
+	source: R,
+
+	/// The number of polygons that were read.
+	num_polygons: usize,
+
+	/// Whether the end of the file was reached, or an error occurred, after which nothing more is
+	/// read.
+	finished: bool,
+
+	/// The checksum of the part of the current record that was read so far.
+	crc: u32,
+}
+
+impl<R: Read> BinaryReader<R> {
+	/// Start reading polygons from a source, by reading and checking the header.
+	///
+	/// # Arguments
+	/// * `source` - Where to read the polygons from.
+	///
+	/// # Result
+	/// The reader, or the reason why the header can't be read.
+	pub fn new(mut source: R) -> Result<Self, BinaryError> {
+		let mut header = [0u8; 6];
+		source.read_exact(&mut header).map_err(|error| if error.kind() == ErrorKind::UnexpectedEof { BinaryError::NOT_APEX } else { BinaryError::IO(error) })?;
+		if header[..4] != MAGIC {
+			return Err(BinaryError::NOT_APEX);
+		}
+		let version = u16::from_le_bytes([header[4], header[5]]);
+		if version == 0 || version > VERSION {
+			return Err(BinaryError::UNSUPPORTED_VERSION(version));
+		}
+		Ok(BinaryReader { source, num_polygons: 0, finished: false, crc: !0 })
+	}
+
+	/// Read the next polygon.
+	///
+	/// # Result
+	/// The polygon, or `None` at the end of the file, or the reason why the polygon can't be read.
+	pub fn read_polygon(&mut self) -> Result<Option<Polygon>, BinaryError> {
+		let Some(num_vertices) = self.start_record()? else {
+			return Ok(None);
+		};
+		let mut vertices = Vec::with_capacity(num_vertices.min(MAX_RESERVED_VERTICES));
+		self.read_vertices(num_vertices, |x, y| vertices.push(Point2D { x, y }))?;
+		Ok(Some(Polygon::from_iter(vertices)))
+	}
+
+	/// Read the coordinates of the next polygon into an interleaved array.
+	///
+	/// The X and Y coordinates of each vertex are appended to the array, interleaved, which is the
+	/// layout in which vertices are copied to the GPU. This way, many polygons can be read into the
+	/// same array without converting them. If the polygon can't be read, the array is left as it
+	/// was.
+	///
+	/// # Arguments
+	/// * `coordinates` - The array to append the coordinates to.
+	///
+	/// # Result
+	/// The number of vertices of the polygon, or `None` at the end of the file, or the reason why the
+	/// polygon can't be read.
+	pub fn read_coordinates(&mut self, coordinates: &mut Vec<Coordinate>) -> Result<Option<usize>, BinaryError> {
+		let Some(num_vertices) = self.start_record()? else {
+			return Ok(None);
+		};
+		let original_length = coordinates.len();
+		coordinates.reserve(num_vertices.min(MAX_RESERVED_VERTICES) * 2);
+		let result = self.read_vertices(num_vertices, |x, y| coordinates.extend([x, y]));
+		if result.is_err() {
+			coordinates.truncate(original_length);
+		}
+		result.map(|_| Some(num_vertices))
+	}
+
+	/// Read the next polygon, decoding its coordinates into a buffer that is copied to the GPU as it
+	/// is.
+	///
+	/// This skips the copy of the vertices on the host. The polygon only exists on the GPU.
+	///
+	/// # Result
+	/// The polygon, or `None` at the end of the file, or the reason why the polygon can't be read.
+	#[cfg(feature = "gpu")]
+	pub fn read_polygon_gpu(&mut self) -> Result<Option<Polygon>, BinaryError> {
+		let mut coordinates = Vec::new();
+		Ok(match self.read_coordinates(&mut coordinates)? {
+			Some(0) => Some(Polygon::new()), //Nothing to copy to the GPU.
+			Some(_) => Some(Polygon::from_gpu_coordinates(&coordinates)),
+			None => None,
+		})
+	}
+
+	/// Start reading the next record.
+	///
+	/// If this is the record that marks the end of the file, it is read completely and checked.
+	/// Otherwise, only the number of vertices is read, and the vertices must be read next.
+	///
+	/// # Result
+	/// The number of vertices of the polygon, or `None` at the end of the file, or the reason why the
+	/// record can't be read.
+	fn start_record(&mut self) -> Result<Option<usize>, BinaryError> {
+		if self.finished {
+			return Ok(None);
+		}
+		self.finished = true; //Until the record is read successfully.
+		self.crc = !0;
+		let num_vertices = self.varint()?;
+		if num_vertices == 0 {
+			let num_polygons = self.varint()?;
+			self.check_crc()?;
+			if num_polygons != self.num_polygons as u128 {
+				return Err(BinaryError::CORRUPT(self.num_polygons));
+			}
+			return Ok(None);
+		}
+		usize::try_from(num_vertices - 1).map(Some).map_err(|_| BinaryError::CORRUPT(self.num_polygons))
+	}
+
+	/// Read the vertices of the current record and check its checksum.
+	///
+	/// # Arguments
+	/// * `num_vertices` - The number of vertices in the record.
+	/// * `vertex` - Called with the X and Y coordinates of each vertex, in order.
+	fn read_vertices<F>(&mut self, num_vertices: usize, mut vertex: F) -> Result<(), BinaryError>
+			where F: FnMut(Coordinate, Coordinate) {
+		let mut previous = (0i128, 0i128);
+		for _ in 0..num_vertices {
+			let x = previous.0.checked_add(unzigzag(self.varint()?)).ok_or(BinaryError::OVERFLOW)?;
+			let y = previous.1.checked_add(unzigzag(self.varint()?)).ok_or(BinaryError::OVERFLOW)?;
+			vertex(Coordinate::try_from(x).map_err(|_| BinaryError::OVERFLOW)?, Coordinate::try_from(y).map_err(|_| BinaryError::OVERFLOW)?);
+			previous = (x, y);
+		}
+		self.check_crc()?;
+		self.num_polygons += 1;
+		self.finished = false;
+		Ok(())
+	}
+
+	/// Read a single byte of the current record, adding it to the checksum.
+	fn byte(&mut self) -> Result<u8, BinaryError> {
+		let mut byte = [0u8];
+		self.source.read_exact(&mut byte).map_err(|error| if error.kind() == ErrorKind::UnexpectedEof { BinaryError::TRUNCATED } else { BinaryError::IO(error) })?;
+		self.crc = crc_update(self.crc, byte[0]);
+		Ok(byte[0])
+	}
+
+	/// Read a variable-length integer of the current record.
+	fn varint(&mut self) -> Result<u128, BinaryError> {
+		let mut result = 0u128;
+		let mut shift = 0;
+		loop {
+			let byte = self.byte()?;
+			if shift > 126 || (shift == 126 && byte > 0b11) { //The integer doesn't fit in 128 bits.
+				return Err(BinaryError::CORRUPT(self.num_polygons));
+			}
+			result |= ((byte & 0x7F) as u128) << shift;
+			if byte & 0x80 == 0 {
+				return Ok(result);
+			}
+			shift += 7;
+		}
+	}
+
+	/// Read the checksum at the end of the current record, and compare it to the checksum of the
+	/// bytes of the record.
+	fn check_crc(&mut self) -> Result<(), BinaryError> {
+		let expected = !self.crc;
+		let mut checksum = [0u8; 4];
+		self.source.read_exact(&mut checksum).map_err(|error| if error.kind() == ErrorKind::UnexpectedEof { BinaryError::TRUNCATED } else { BinaryError::IO(error) })?;
+		if u32::from_le_bytes(checksum) != expected {
+			return Err(BinaryError::CORRUPT(self.num_polygons));
+		}
+		Ok(())
+	}
+}
+
+impl<R: Read> Iterator for BinaryReader<R> {
+	type Item = Result<Polygon, BinaryError>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.read_polygon().transpose()
+	}
+}
+
+/// Append a variable-length integer to a record.
+///
+/// # Arguments
+/// * `record` - The record to append the integer to.
+/// * `value` - The integer to append.
+fn write_varint(record: &mut Vec<u8>, mut value: u128) {
+	while value >= 0x80 {
+		record.push((value & 0x7F) as u8 | 0x80);
+		value >>= 7;
+	}
+	record.push(value as u8);
+}
+
+/// Map a signed integer to an unsigned one, so that integers close to zero become small.
+///
+/// # Arguments
+/// * `value` - The signed integer.
+fn zigzag(value: i128) -> u128 {
+	((value << 1) ^ (value >> 127)) as u128
+}
+
+/// Map an unsigned integer back to the signed integer that `zigzag` mapped to it.
+///
+/// # Arguments
+/// * `value` - The unsigned integer.
+fn unzigzag(value: u128) -> i128 {
+	(value >> 1) as i128 ^ -((value & 1) as i128)
+}
+
+/// Add a byte to a CRC-32 checksum.
+///
+/// # Arguments
+/// * `crc` - The checksum of the bytes before, in its inverted form. Start with all bits set, and
+/// invert the result after the last byte.
+/// * `byte` - The byte to add.
+fn crc_update(crc: u32, byte: u8) -> u32 {
+	CRC_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+}
+
+/// Compute the table for CRC-32 checksums, with the polynomial used by zip and PNG.
+const fn crc_table() -> [u32; 256] {
+	let mut table = [0u32; 256];
+	let mut index = 0;
+	while index < 256 {
+		let mut crc = index as u32;
+		let mut bit = 0;
+		while bit < 8 {
+			crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+			bit += 1;
+		}
+		table[index] = crc;
+		index += 1;
+	}
+	table
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+
+	/// Write polygons to a byte array.
+	fn write(polygons: &[Polygon]) -> Vec<u8> {
+		let mut writer = BinaryWriter::new(Vec::new()).unwrap();
+		for polygon in polygons {
+			writer.write_polygon(polygon).unwrap();
+		}
+		writer.finish().unwrap()
+	}
+
+	/// Read all polygons from a byte array.
+	fn read(data: &[u8]) -> Result<Vec<Polygon>, BinaryError> {
+		BinaryReader::new(data)?.collect()
+	}
+
+	/// Test that polygons are read back as they were written.
+	#[test]
+	fn round_trip() {
+		let polygons = vec![
+			crate::test::data::polygon::pentagram_1000(),
+			Polygon::new(),
+			Polygon::from_iter([Point2D { x: Coordinate::MIN, y: Coordinate::MAX }, Point2D { x: Coordinate::MAX, y: Coordinate::MIN }, Point2D { x: -1, y: 0 }]),
+			crate::test::data::polygon::square_1000(),
+		];
+		assert_eq!(read(&write(&polygons)).unwrap(), polygons);
+	}
+
+	/// Test the exact encoding of a small polygon.
+	#[test]
+	fn encoding() {
+		let data = write(&[Polygon::from_iter([Point2D { x: 1, y: -1 }, Point2D { x: 65, y: -1 }])]);
+		assert_eq!(&data[..6], b"APEX\x01\x00", "The header with version 1.");
+		assert_eq!(&data[6..12], &[3, 2, 1, 0x80, 0x01, 0], "2 vertices plus one, then the zigzagged differences 1, -1, 64 and 0.");
+		let checksum = !data[6..12].iter().fold(!0, |crc, byte| crc_update(crc, *byte));
+		assert_eq!(&data[12..16], &checksum.to_le_bytes());
+		assert_eq!(&data[16..18], &[0, 1], "The end of the file, with the number of polygons.");
+		assert_eq!(data.len(), 22);
+	}
+
+	/// Test the CRC-32 checksum against the check value of the standard.
+	#[test]
+	fn crc() {
+		assert_eq!(!b"123456789".iter().fold(!0, |crc, byte| crc_update(crc, *byte)), 0xCBF4_3926);
+	}
+
+	/// Test the variable-length integers at the extremes of their range.
+	#[test_case(0 ; "zero")]
+	#[test_case(127 ; "largest single byte")]
+	#[test_case(128 ; "smallest two bytes")]
+	#[test_case(u128::MAX ; "maximum")]
+	fn varints(value: u128) {
+		let mut record = Vec::new();
+		write_varint(&mut record, value);
+		let mut data = MAGIC.to_vec();
+		data.extend(VERSION.to_le_bytes());
+		data.extend(&record);
+		let mut reader = BinaryReader::new(data.as_slice()).unwrap();
+		assert_eq!(reader.varint().unwrap(), value);
+	}
+
+	/// Test that zigzag encoding maps small signed integers to small unsigned integers and back.
+	#[test_case(0, 0 ; "zero")]
+	#[test_case(-1, 1 ; "minus one")]
+	#[test_case(1, 2 ; "one")]
+	#[test_case(i128::MIN, u128::MAX ; "minimum")]
+	#[test_case(i128::MAX, u128::MAX - 1 ; "maximum")]
+	fn zigzags(signed: i128, unsigned: u128) {
+		assert_eq!(zigzag(signed), unsigned);
+		assert_eq!(unzigzag(unsigned), signed);
+	}
+
+	/// Test reading many polygons into one interleaved array.
+	#[test]
+	fn interleaved() {
+		let data = write(&[crate::test::data::polygon::triangle_1000(), crate::test::data::polygon::square_1000()]);
+		let mut reader = BinaryReader::new(data.as_slice()).unwrap();
+		let mut coordinates = vec![7];
+		assert_eq!(reader.read_coordinates(&mut coordinates).unwrap(), Some(3));
+		assert_eq!(reader.read_coordinates(&mut coordinates).unwrap(), Some(4));
+		assert_eq!(reader.read_coordinates(&mut coordinates).unwrap(), None);
+		assert_eq!(reader.read_coordinates(&mut coordinates).unwrap(), None, "After the end, there is nothing more to read.");
+		assert_eq!(coordinates, vec![7, 24, 24, 1024, 24, 524, 1024, 0, 0, 1000, 0, 1000, 1000, 0, 1000]);
+	}
+
+	/// Test that data in other formats is rejected.
+	#[test_case(b"" ; "empty")]
+	#[test_case(b"APE" ; "short")]
+	#[test_case(b"<svg>" ; "other format")]
+	fn not_apex(data: &[u8]) {
+		assert!(matches!(BinaryReader::new(data), Err(BinaryError::NOT_APEX)));
+	}
+
+	/// Test that data from newer versions is rejected.
+	#[test]
+	fn newer_version() {
+		assert!(matches!(BinaryReader::new(b"APEX\x02\x00".as_slice()), Err(BinaryError::UNSUPPORTED_VERSION(2))));
+	}
+
+	/// Test that data that ends too early is reported, wherever it ends.
+	#[test]
+	fn truncated() {
+		let data = write(&[crate::test::data::polygon::square_1000(), crate::test::data::polygon::triangle_1000()]);
+		for length in 6..data.len() {
+			assert!(matches!(read(&data[..length]), Err(BinaryError::TRUNCATED)), "The data was truncated to {} bytes.", length);
+		}
+	}
+
+	/// Test that corrupting any byte of a record is detected by the checksums.
+	#[test]
+	fn corrupt() {
+		let data = write(&[crate::test::data::polygon::square_1000(), crate::test::data::polygon::triangle_1000()]);
+		for position in 6..data.len() {
+			let mut corrupted = data.clone();
+			corrupted[position] ^= 0x10;
+			assert!(matches!(read(&corrupted), Err(BinaryError::CORRUPT(_)) | Err(BinaryError::TRUNCATED)), "Byte {} was corrupted.", position);
+		}
+	}
+
+	/// Test that the polygon count in the record that marks the end of the file is checked.
+	#[test]
+	fn missing_polygon() {
+		let mut data = write(&[crate::test::data::polygon::square_1000()]);
+		let end = data.len() - 6;
+		data.truncate(end);
+		let mut record = Vec::new();
+		write_varint(&mut record, 0);
+		write_varint(&mut record, 2); //Claim that there were 2 polygons.
+		let checksum = !record.iter().fold(!0, |crc, byte| crc_update(crc, *byte));
+		data.extend(record);
+		data.extend(checksum.to_le_bytes());
+		assert!(matches!(read(&data), Err(BinaryError::CORRUPT(1))));
+	}
+
+	/// Test that coordinates that don't fit in the coordinate type are reported.
+	#[test]
+	fn overflow() {
+		let mut record = Vec::new();
+		write_varint(&mut record, 2);
+		write_varint(&mut record, zigzag(Coordinate::MAX as i128 + 1));
+		write_varint(&mut record, 0);
+		let checksum = !record.iter().fold(!0, |crc, byte| crc_update(crc, *byte));
+		let mut data = b"APEX\x01\x00".to_vec();
+		data.extend(record);
+		data.extend(checksum.to_le_bytes());
+		assert!(matches!(read(&data), Err(BinaryError::OVERFLOW)));
+	}
+
+	/// Test that reading stops after the first error.
+	#[test]
+	fn stops_after_error() {
+		let data = write(&[crate::test::data::polygon::square_1000()]);
+		let mut reader = BinaryReader::new(&data[..data.len() - 1]).unwrap();
+		assert!(reader.next().unwrap().is_ok());
+		assert!(matches!(reader.next(), Some(Err(BinaryError::TRUNCATED))));
+		assert!(reader.next().is_none());
+	}
+
+	/// Test decoding polygons directly into the buffer that is copied to the GPU.
+	#[cfg(all(feature = "gpu", not(feature = "coordinate64")))]
+	#[test]
+	fn read_polygon_gpu() {
+		let data = write(&[crate::test::data::polygon::square_1000(), Polygon::new()]);
+		let mut reader = BinaryReader::new(data.as_slice()).unwrap();
+		let square = reader.read_polygon_gpu().unwrap().unwrap();
+		assert_eq!(square.sync_status(), crate::detail::sync_status::SyncStatus::GPU, "The vertices were never on the host.");
+		assert_eq!(square, crate::test::data::polygon::square_1000());
+		assert_eq!(reader.read_polygon_gpu().unwrap().unwrap().len(), 0);
+		assert!(reader.read_polygon_gpu().unwrap().is_none());
+	}
+}
\ No newline at end of file
@@ -0,0 +1,119 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! This module contains the readers and writers that exchange the geometric objects of this
+//! library with other software, through file formats.
+//!
+//! Each file format has its own module. The formats mostly store coordinates as decimal numbers,
+//! which the readers quantise to `Coordinate` with a configurable scale. Malformed input is reported
+//! with an error that is specific to the format, rather than by panicking.
+
+use crate::MultiPolygon; //The polygons in the formats are read as multi-polygons.
+use crate::Point2D; //The vertices of the geometry.
+use crate::Polygon; //Line strings are read as polygons.
+use crate::operations::area; //To find the orientation of rings and contours.
+use crate::operations::containment; //To find which outer contour surrounds each hole.
+
+pub mod binary;
+pub mod svg;
+pub mod wkb;
+pub mod wkt;
+
+/// A geometric object read from a format that can hold different kinds of objects.
+///
+/// Polygons in these formats consist of an exterior ring and any number of interior rings, which
+/// are holes. They are read as the contours of a multi-polygon. The exterior rings are made to wind
+/// counter-clockwise and the interior rings clockwise, so that the holes are negative.
+#[allow(non_camel_case_types)] //The variants are capitalised like those of the other enums, with underscores between words.
+#[derive(Debug)]
+pub enum Geometry {
+	/// An open chain of line segments. Its vertices are stored in a polygon, in order, even though
+	/// the polygon closes the chain.
+	LINE_STRING(Polygon),
+
+	/// A single polygon with its holes, as the contours of a multi-polygon.
+	POLYGON(MultiPolygon),
+
+	/// Any number of polygons with their holes, all as the contours of one multi-polygon.
+	MULTI_POLYGON(MultiPolygon),
+}
+
+/// Add the rings of a polygon in one of the formats to a multi-polygon, as contours.
+///
+/// Rings in the formats repeat their first vertex at the end, which is removed. The first ring is
+/// the exterior ring, which is reversed if it winds clockwise. The other rings are holes, which are
+/// reversed if they wind counter-clockwise. Reversing a ring keeps its first vertex in place.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to add the rings to.
+/// * `rings` - The rings of the polygon, with their exterior ring first.
+pub(crate) fn push_rings(multi_polygon: &mut MultiPolygon, rings: Vec<Vec<Point2D>>) {
+	for (index, mut ring) in rings.into_iter().enumerate() {
+		if ring.len() > 1 && ring.first() == ring.last() {
+			ring.pop();
+		}
+		if ring.is_empty() {
+			continue;
+		}
+		let doubled_area = area::doubled_area_st(&ring);
+		if (index == 0 && doubled_area < 0) || (index > 0 && doubled_area > 0) {
+			ring[1..].reverse();
+		}
+		multi_polygon.push_contour(ring);
+	}
+}
+
+/// Group the contours of a multi-polygon into polygons with holes, as the formats store them.
+///
+/// Each contour that is not a hole starts a polygon. Each hole is added to the smallest of those
+/// that surrounds it. Holes that are not surrounded by any contour can't be stored as holes, so they
+/// become polygons of their own. Empty contours are left out.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to group the contours of.
+///
+/// # Result
+/// For each polygon, its rings, with the exterior ring first.
+pub(crate) fn group_contours(multi_polygon: &MultiPolygon) -> Vec<Vec<&[Point2D]>> {
+	let mut result: Vec<Vec<&[Point2D]>> = Vec::new();
+	let mut outer_areas = Vec::new(); //For each polygon in the result, twice the area of its exterior ring.
+	let mut holes = Vec::new();
+	for contour in multi_polygon.contours().filter(|contour| !contour.is_empty()) {
+		let doubled_area = area::doubled_area_st(contour);
+		if doubled_area >= 0 {
+			result.push(vec![contour]);
+			outer_areas.push(doubled_area);
+		} else {
+			holes.push(contour);
+		}
+	}
+	for hole in holes {
+		let surrounding = (0..outer_areas.len())
+			.filter(|index| surrounds(result[*index][0], hole))
+			.min_by_key(|index| outer_areas[*index]);
+		match surrounding {
+			Some(index) => result[index].push(hole),
+			None => result.push(vec![hole]),
+		}
+	}
+	result
+}
+
+/// Test whether a hole is inside of an outer contour.
+///
+/// The first vertex of the hole that is not on the boundary of the outer contour decides. Holes
+/// that lie entirely on the boundary are considered to be inside.
+///
+/// # Arguments
+/// * `outer` - The vertices of the outer contour.
+/// * `hole` - The vertices of the hole.
+fn surrounds(outer: &[Point2D], hole: &[Point2D]) -> bool {
+	hole.iter()
+		.find_map(|vertex| containment::winding_number_st(outer, vertex))
+		.is_none_or(|winding_number| winding_number != 0)
+}
\ No newline at end of file
@@ -0,0 +1,1214 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! Reads and writes polygons as Scalable Vector Graphics documents.
+//!
+//! The reader understands the shapes that consist of straight edges and curves: `<polygon>`,
+//! `<polyline>`, `<rect>` and `<path>`. Curves in paths and the rounded corners of rectangles are
+//! flattened into straight edges, within a tolerance. Shapes can be nested in groups, and the
+//! `transform` attributes of the shapes and their groups are applied. Elements that are never
+//! rendered by themselves, like the contents of `<defs>` and `<clipPath>`, are skipped.
+//!
+//! Coordinates in the document are in user units. These are multiplied by a scale and then rounded
+//! to the nearest `Coordinate`, with halves rounded up. The viewport of the document and styling
+//! such as fill rules are ignored. SVG documents have their Y axis pointing down, while this
+//! library makes no assumption about the direction of the axes, so the Y coordinates are kept as
+//! they are.
+//!
+//! The writer creates documents to inspect shapes in a browser or to attach to bug reports. Each
+//! shape gets its own style, and its vertices can be annotated with markers and indices. The
+//! annotations are written with elements that the reader skips, so written documents can be read
+//! back.
+
+use std::error::Error; //The errors of the reader can be propagated with other errors.
+use std::f64::consts::PI; //To flatten arcs.
+use std::fmt; //To describe the errors as text.
+
+use crate::AffineTransform; //To apply the transform attributes.
+use crate::Coordinate; //To convert coordinates to user units when writing.
+use crate::FillRule; //To write which regions of the shapes are filled.
+use crate::MultiPolygon; //To read all shapes in a document as one multi-polygon.
+use crate::OverflowError; //Coordinates may not fit after scaling.
+use crate::Point2D; //The vertices that are read.
+use crate::Polygon; //To read the shapes as polygons.
+use crate::affine_transform; //To round the coordinates the same way as transformations do.
+
+/// The most edges that a single curve is flattened into, regardless of the tolerance.
+///
+/// This prevents huge allocations for tiny tolerances or huge scales.
+const MAX_CURVE_EDGES: usize = 1 << 16;
+
+/// Elements whose contents are not rendered by themselves, so their shapes are skipped.
+const NOT_RENDERED: [&str; 11] = ["clipPath", "defs", "desc", "marker", "mask", "metadata", "pattern", "script", "style", "symbol", "title"];
+
+/// The reasons that an SVG document can't be read.
+#[allow(non_camel_case_types)] //The variants are capitalised like those of the other enums, with underscores between words.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SvgError {
+	/// The document is not well-formed XML. This holds the byte position in the document where the
+	/// problem was found.
+	MALFORMED(usize),
+
+	/// An attribute of a shape could not be parsed. This holds the names of the element and of the
+	/// attribute.
+	INVALID_ATTRIBUTE(String, String),
+
+	/// A coordinate is outside of the range of `Coordinate` after scaling.
+	OVERFLOW,
+}
+
+impl fmt::Display for SvgError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			SvgError::MALFORMED(position) => write!(f, "The SVG document is not well-formed at position {}.", position),
+			SvgError::INVALID_ATTRIBUTE(element, attribute) => write!(f, "The {} attribute of a <{}> element is invalid.", attribute, element),
+			SvgError::OVERFLOW => write!(f, "A coordinate in the SVG document went out of the range of the coordinate type."),
+		}
+	}
+}
+
+impl Error for SvgError {}
+
+impl From<OverflowError> for SvgError {
+	fn from(_: OverflowError) -> SvgError {
+		SvgError::OVERFLOW
+	}
+}
+
+/// Read every shape in an SVG document as a separate polygon.
+///
+/// Paths with multiple subpaths give a polygon for each subpath. The shapes are returned in the
+/// order in which they appear in the document.
+///
+/// # Arguments
+/// * `svg` - An SVG document, the contents of an SVG file, as text mark-up.
+/// * `scale` - How many coordinates make up one user unit of the document.
+/// * `tolerance` - How far the edges of flattened curves may deviate from the curves, in
+/// coordinates, after scaling.
+///
+/// # Result
+/// The polygons in the document, or the reason why the document can't be read.
+///
+/// # Examples
+/// ```
+/// use apex::Point2D;
+/// use apex::io::svg::read_polygons;
+/// let svg = r#"<svg xmlns="http://www.w3.org/2000/svg">
+/// 	<g transform="translate(10, 0)">
+/// 		<rect width="4" height="3" />
+/// 	</g>
+/// </svg>"#;
+/// let polygons = read_polygons(svg, 100.0, 1.0).unwrap();
+/// assert_eq!(polygons.len(), 1);
+/// assert_eq!(polygons[0].iter().copied().collect::<Vec<Point2D>>(), vec![
+/// 	Point2D { x: 1000, y: 0 },
+/// 	Point2D { x: 1400, y: 0 },
+/// 	Point2D { x: 1400, y: 300 },
+/// 	Point2D { x: 1000, y: 300 },
+/// ]);
+/// ```
+pub fn read_polygons(svg: &str, scale: f64, tolerance: f64) -> Result<Vec<Polygon>, SvgError> {
+	let mut result = Vec::new();
+	let document = AffineTransform::scaling(&Point2D { x: 0, y: 0 }, scale, scale);
+	let mut open_elements: Vec<(&str, AffineTransform, bool)> = Vec::new(); //For each element that is not closed yet, its name, its transformation and whether it is rendered.
+	let mut position = 0;
+	while let Some(tag) = next_tag(svg, &mut position)? {
+		match tag {
+			Tag::START(name, attributes, closed) => {
+				let (parent, rendered) = open_elements.last().map_or((document, true), |(_, transform, rendered)| (*transform, *rendered));
+				let transform = match attribute(&attributes, "transform") {
+					Some(value) => parse_transform(value).ok_or_else(|| invalid(name, "transform"))?.then(&parent),
+					None => parent,
+				};
+				let rendered = rendered && !NOT_RENDERED.contains(&name);
+				if rendered {
+					result.extend(read_shape(name, &attributes, &transform, tolerance)?);
+				}
+				if !closed {
+					open_elements.push((name, transform, rendered));
+				}
+			},
+			Tag::END(name, start) => {
+				if open_elements.pop().map(|(open_name, _, _)| open_name) != Some(name) {
+					return Err(SvgError::MALFORMED(start));
+				}
+			},
+		}
+	}
+	if !open_elements.is_empty() {
+		return Err(SvgError::MALFORMED(svg.len())); //Some elements are never closed.
+	}
+	Ok(result)
+}
+
+/// Read all shapes in an SVG document as the contours of one multi-polygon.
+///
+/// Each polygon that `read_polygons` would give becomes a contour. Whether a contour is a hole is
+/// determined by its orientation, like for any multi-polygon.
+///
+/// # Arguments
+/// * `svg` - An SVG document, the contents of an SVG file, as text mark-up.
+/// * `scale` - How many coordinates make up one user unit of the document.
+/// * `tolerance` - How far the edges of flattened curves may deviate from the curves, in
+/// coordinates, after scaling.
+///
+/// # Result
+/// The multi-polygon, or the reason why the document can't be read.
+///
+/// # Examples
+/// ```
+/// use apex::io::svg::read_multi_polygon;
+/// let svg = r#"<svg xmlns="http://www.w3.org/2000/svg">
+/// 	<path d="M 0 0 H 10 V 10 H 0 Z M 2 2 V 8 H 8 V 2 Z" />
+/// </svg>"#;
+/// let square_with_hole = read_multi_polygon(svg, 1.0, 0.5).unwrap();
+/// assert_eq!(square_with_hole.num_contours(), 2);
+/// assert!(square_with_hole.is_hole(1));
+/// ```
+pub fn read_multi_polygon(svg: &str, scale: f64, tolerance: f64) -> Result<MultiPolygon, SvgError> {
+	Ok(read_polygons(svg, scale, tolerance)?.into_iter().collect())
+}
+
+/// A piece of mark-up in an XML document that affects the structure of the document.
+#[allow(non_camel_case_types)] //The variants are capitalised like those of the other enums, with underscores between words.
+enum Tag<'a> {
+	/// The start of an element, with its name and its attributes. The flag is whether the element
+	/// is closed immediately as well.
+	START(&'a str, Vec<(&'a str, &'a str)>, bool),
+
+	/// The end of an element, with its name and the byte position where the tag starts.
+	END(&'a str, usize),
+}
+
+/// Find the next start or end tag in an XML document.
+///
+/// Text, comments, character data, processing instructions and document type declarations are
+/// skipped.
+///
+/// # Arguments
+/// * `svg` - The XML document.
+/// * `position` - The byte position to start searching from. This is moved past the tag that is
+/// found.
+///
+/// # Result
+/// The tag, or `None` if there are no more tags in the document, or an error if the mark-up is
+/// malformed.
+fn next_tag<'a>(svg: &'a str, position: &mut usize) -> Result<Option<Tag<'a>>, SvgError> {
+	let bytes = svg.as_bytes();
+	loop {
+		let start = match svg[*position..].find('<') {
+			Some(offset) => *position + offset,
+			None => return Ok(None),
+		};
+		let rest = &svg[start..];
+		let skip_until = |terminator: &str| rest.find(terminator).map(|end| start + end + terminator.len()).ok_or(SvgError::MALFORMED(start));
+		if rest.starts_with("<!--") {
+			*position = skip_until("-->")?;
+		} else if rest.starts_with("<![CDATA[") {
+			*position = skip_until("]]>")?;
+		} else if rest.starts_with("<?") {
+			*position = skip_until("?>")?;
+		} else if rest.starts_with("<!") {
+			//A document type declaration, which may contain an internal subset between brackets.
+			let mut depth = 0;
+			let mut end = start + 2;
+			while end < bytes.len() && (bytes[end] != b'>' || depth > 0) {
+				match bytes[end] {
+					b'[' => depth += 1,
+					b']' => depth -= 1,
+					_ => {},
+				}
+				end += 1;
+			}
+			if end >= bytes.len() {
+				return Err(SvgError::MALFORMED(start));
+			}
+			*position = end + 1;
+		} else if let Some(name) = rest.strip_prefix("</") {
+			let end = name.find('>').ok_or(SvgError::MALFORMED(start))?;
+			*position = start + 2 + end + 1;
+			return Ok(Some(Tag::END(name[..end].trim_end(), start)));
+		} else {
+			return start_tag(svg, start, position).map(Some);
+		}
+	}
+}
+
+/// Parse a start tag of an element, with its attributes.
+///
+/// # Arguments
+/// * `svg` - The XML document.
+/// * `start` - The byte position of the `<` that starts the tag.
+/// * `position` - The output, the byte position just after the tag.
+fn start_tag<'a>(svg: &'a str, start: usize, position: &mut usize) -> Result<Tag<'a>, SvgError> {
+	let bytes = svg.as_bytes();
+	let is_name_end = |byte: u8| byte.is_ascii_whitespace() || byte == b'/' || byte == b'>' || byte == b'=';
+	let mut cursor = start + 1;
+	while cursor < bytes.len() && !is_name_end(bytes[cursor]) {
+		cursor += 1;
+	}
+	if cursor == start + 1 {
+		return Err(SvgError::MALFORMED(start)); //Elements need a name.
+	}
+	let name = &svg[start + 1..cursor];
+	let mut attributes = Vec::new();
+	loop {
+		while cursor < bytes.len() && bytes[cursor].is_ascii_whitespace() {
+			cursor += 1;
+		}
+		if cursor >= bytes.len() {
+			return Err(SvgError::MALFORMED(start)); //The tag never ends.
+		}
+		if bytes[cursor] == b'>' {
+			*position = cursor + 1;
+			return Ok(Tag::START(name, attributes, false));
+		}
+		if bytes[cursor] == b'/' {
+			if bytes.get(cursor + 1) != Some(&b'>') {
+				return Err(SvgError::MALFORMED(cursor));
+			}
+			*position = cursor + 2;
+			return Ok(Tag::START(name, attributes, true));
+		}
+		let attribute_start = cursor;
+		while cursor < bytes.len() && !is_name_end(bytes[cursor]) {
+			cursor += 1;
+		}
+		let attribute_name = &svg[attribute_start..cursor];
+		while cursor < bytes.len() && bytes[cursor].is_ascii_whitespace() {
+			cursor += 1;
+		}
+		if attribute_name.is_empty() || bytes.get(cursor) != Some(&b'=') {
+			return Err(SvgError::MALFORMED(cursor));
+		}
+		cursor += 1;
+		while cursor < bytes.len() && bytes[cursor].is_ascii_whitespace() {
+			cursor += 1;
+		}
+		let quote = match bytes.get(cursor) {
+			Some(quote @ (b'"' | b'\'')) => *quote as char,
+			_ => return Err(SvgError::MALFORMED(cursor)),
+		};
+		let value_end = cursor + 1 + svg[cursor + 1..].find(quote).ok_or(SvgError::MALFORMED(cursor))?;
+		attributes.push((attribute_name, &svg[cursor + 1..value_end]));
+		cursor = value_end + 1;
+	}
+}
+
+/// Get the value of an attribute, if the element has it.
+fn attribute<'a>(attributes: &[(&str, &'a str)], name: &str) -> Option<&'a str> {
+	attributes.iter().find(|(attribute_name, _)| *attribute_name == name).map(|(_, value)| *value)
+}
+
+/// Create the error for an attribute that can't be parsed.
+fn invalid(element: &str, attribute: &str) -> SvgError {
+	SvgError::INVALID_ATTRIBUTE(element.to_string(), attribute.to_string())
+}
+
+/// Read the polygons of one element.
+///
+/// # Arguments
+/// * `name` - The name of the element.
+/// * `attributes` - The attributes of the element.
+/// * `transform` - The transformation from user units to coordinates, including the scale.
+/// * `tolerance` - How far flattened curves may deviate from the curves, in coordinates.
+///
+/// # Result
+/// The polygons of the element, which is empty if the element is not a shape.
+fn read_shape(name: &str, attributes: &[(&str, &str)], transform: &AffineTransform, tolerance: f64) -> Result<Vec<Polygon>, SvgError> {
+	match name {
+		"polygon" | "polyline" => {
+			let mut numbers = Numbers::new(attribute(attributes, "points").unwrap_or(""));
+			let mut points = Vec::new();
+			while let Some(x) = numbers.number() {
+				let y = numbers.number().ok_or_else(|| invalid(name, "points"))?;
+				points.push(apply(transform, (x, y)));
+			}
+			if !numbers.is_at_end() {
+				return Err(invalid(name, "points"));
+			}
+			if points.is_empty() {
+				return Ok(Vec::new());
+			}
+			Ok(vec![to_polygon(&points, false)?])
+		},
+		"rect" => {
+			let length = |attribute_name: &str| match attribute(attributes, attribute_name) {
+				Some(value) => parse_length(value).map(Some).ok_or_else(|| invalid(name, attribute_name)),
+				None => Ok(None),
+			};
+			let x = length("x")?.unwrap_or(0.0);
+			let y = length("y")?.unwrap_or(0.0);
+			let width = length("width")?.unwrap_or(0.0);
+			let height = length("height")?.unwrap_or(0.0);
+			if width <= 0.0 || height <= 0.0 {
+				return Ok(Vec::new()); //Rectangles without size are not rendered.
+			}
+			let (radius_x, radius_y) = match (length("rx")?, length("ry")?) {
+				(Some(radius_x), Some(radius_y)) => (radius_x, radius_y),
+				(Some(radius), None) | (None, Some(radius)) => (radius, radius),
+				(None, None) => (0.0, 0.0),
+			};
+			let radius_x = radius_x.clamp(0.0, width / 2.0);
+			let radius_y = radius_y.clamp(0.0, height / 2.0);
+			let data = if radius_x > 0.0 && radius_y > 0.0 {
+				format!("M {} {} H {} A {rx} {ry} 0 0 1 {} {} V {} A {rx} {ry} 0 0 1 {} {} H {} A {rx} {ry} 0 0 1 {} {} V {} A {rx} {ry} 0 0 1 {} {} Z",
+					x + radius_x, y, x + width - radius_x,
+					x + width, y + radius_y, y + height - radius_y,
+					x + width - radius_x, y + height, x + radius_x,
+					x, y + height - radius_y, y + radius_y,
+					x + radius_x, y,
+					rx = radius_x, ry = radius_y)
+			} else {
+				format!("M {} {} H {} V {} H {} Z", x, y, x + width, y + height, x)
+			};
+			read_path(&data, transform, tolerance).ok_or_else(|| invalid(name, "rx"))?
+		},
+		"path" => read_path(attribute(attributes, "d").unwrap_or(""), transform, tolerance).ok_or_else(|| invalid(name, "d"))?,
+		_ => Ok(Vec::new()),
+	}
+}
+
+/// Read the subpaths of path data as polygons.
+///
+/// Subpaths that consist of a single point are skipped. Consecutive vertices that round to the
+/// same coordinates are merged, and so is a last vertex that coincides with the first.
+///
+/// # Arguments
+/// * `data` - The path data, as in the `d` attribute of a `<path>` element.
+/// * `transform` - The transformation from user units to coordinates, including the scale.
+/// * `tolerance` - How far flattened curves may deviate from the curves, in coordinates.
+///
+/// # Result
+/// The polygons, or an error if the coordinates don't fit. The outer option is `None` if the path
+/// data is invalid.
+fn read_path(data: &str, transform: &AffineTransform, tolerance: f64) -> Option<Result<Vec<Polygon>, SvgError>> {
+	let mut contours: Vec<Vec<(f64, f64)>> = Vec::new();
+	let mut contour: Vec<(f64, f64)> = Vec::new();
+	let mut current = (0.0, 0.0); //The current point, in user units.
+	let mut subpath_start = (0.0, 0.0);
+	let mut previous_control: Option<(u8, (f64, f64))> = None; //The last control point of the previous curve, for smooth curves, with the type of curve.
+	let mut numbers = Numbers::new(data);
+	let mut command: Option<u8> = None;
+	loop {
+		numbers.skip_separators();
+		match numbers.peek() {
+			None => break,
+			Some(letter) if letter.is_ascii_alphabetic() => {
+				command = Some(letter);
+				numbers.position += 1;
+			},
+			Some(_) if matches!(command, None | Some(b'Z' | b'z')) => return None, //Numbers without a command.
+			Some(_) => {}, //Repeat the previous command.
+		}
+		let letter = command?;
+		if contours.is_empty() && contour.is_empty() && !matches!(letter, b'M' | b'm') {
+			return None; //Paths must start with a move.
+		}
+		let relative = letter.is_ascii_lowercase();
+		let offset = if relative { current } else { (0.0, 0.0) };
+		let point = |numbers: &mut Numbers| -> Option<(f64, f64)> {
+			Some((numbers.number()? + offset.0, numbers.number()? + offset.1))
+		};
+		if contour.is_empty() && !matches!(letter, b'M' | b'm' | b'Z' | b'z') {
+			contour.push(apply(transform, current)); //Drawing after closing a subpath starts at the start of that subpath.
+		}
+		let mut control = None;
+		match letter.to_ascii_uppercase() {
+			b'M' => {
+				let target = point(&mut numbers)?;
+				contours.push(std::mem::take(&mut contour));
+				contour.push(apply(transform, target));
+				current = target;
+				subpath_start = target;
+				command = Some(if relative { b'l' } else { b'L' }); //Further coordinate pairs are lines.
+			},
+			b'L' => {
+				current = point(&mut numbers)?;
+				contour.push(apply(transform, current));
+			},
+			b'H' => {
+				current = (numbers.number()? + offset.0, current.1);
+				contour.push(apply(transform, current));
+			},
+			b'V' => {
+				current = (current.0, numbers.number()? + offset.1);
+				contour.push(apply(transform, current));
+			},
+			b'C' | b'S' => {
+				let first = if letter.eq_ignore_ascii_case(&b'C') {
+					point(&mut numbers)?
+				} else {
+					smooth_control(previous_control, b'C', current)
+				};
+				let second = point(&mut numbers)?;
+				let target = point(&mut numbers)?;
+				let controls = [current, first, second, target].map(|control| apply(transform, control));
+				flatten_cubic(&controls, tolerance, &mut contour);
+				control = Some((b'C', second));
+				current = target;
+			},
+			b'Q' | b'T' => {
+				let middle = if letter.eq_ignore_ascii_case(&b'Q') {
+					point(&mut numbers)?
+				} else {
+					smooth_control(previous_control, b'Q', current)
+				};
+				let target = point(&mut numbers)?;
+				let controls = [current, middle, target].map(|control| apply(transform, control));
+				flatten_quadratic(&controls, tolerance, &mut contour);
+				control = Some((b'Q', middle));
+				current = target;
+			},
+			b'A' => {
+				let radius_x = numbers.number()?;
+				let radius_y = numbers.number()?;
+				let rotation = numbers.number()?;
+				let large_arc = numbers.flag()?;
+				let sweep = numbers.flag()?;
+				let target = point(&mut numbers)?;
+				flatten_arc(current, (radius_x, radius_y), rotation, large_arc, sweep, target, transform, tolerance, &mut contour);
+				current = target;
+			},
+			b'Z' => {
+				contours.push(std::mem::take(&mut contour));
+				current = subpath_start;
+			},
+			_ => return None, //Unknown command.
+		}
+		previous_control = control;
+	}
+	contours.push(contour);
+	let polygons = contours.iter().filter(|contour| contour.len() > 1).map(|contour| to_polygon(contour, true));
+	Some(polygons.filter(|polygon| polygon.as_ref().map_or(true, |polygon| polygon.len() > 1)).collect())
+}
+
+/// Get the first control point of a smooth curve, which is the reflection of the last control
+/// point of the previous curve.
+///
+/// # Arguments
+/// * `previous_control` - The last control point of the previous curve, with its type of curve.
+/// * `curve` - The type of the smooth curve.
+/// * `current` - The current point, where the smooth curve starts.
+fn smooth_control(previous_control: Option<(u8, (f64, f64))>, curve: u8, current: (f64, f64)) -> (f64, f64) {
+	match previous_control {
+		Some((previous_curve, control)) if previous_curve == curve => (current.0 * 2.0 - control.0, current.1 * 2.0 - control.1),
+		_ => current, //If the previous command was not the same type of curve, the control point is on the current point.
+	}
+}
+
+/// Flatten a cubic Bézier curve into straight edges.
+///
+/// The curve is divided at equal parameter intervals. The number of edges is chosen such that the
+/// edges can't deviate from the curve by more than the tolerance. That is bounded by an eighth of
+/// the largest second derivative of the curve, divided by the square of the number of edges.
+///
+/// # Arguments
+/// * `controls` - The control points of the curve, already transformed to coordinates.
+/// * `tolerance` - How far the edges may deviate from the curve, in coordinates.
+/// * `contour` - The contour to add the vertices to. The start of the curve should already be in
+/// it.
+fn flatten_cubic(controls: &[(f64, f64); 4], tolerance: f64, contour: &mut Vec<(f64, f64)>) {
+	let [p0, p1, p2, p3] = *controls;
+	let second_difference = |a: (f64, f64), b: (f64, f64), c: (f64, f64)| (a.0 - 2.0 * b.0 + c.0).hypot(a.1 - 2.0 * b.1 + c.1);
+	let largest = second_difference(p0, p1, p2).max(second_difference(p1, p2, p3));
+	let num_edges = num_edges((largest * 3.0 / (4.0 * tolerance)).sqrt());
+	for i in 1..=num_edges {
+		let t = i as f64 / num_edges as f64;
+		let u = 1.0 - t;
+		let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+		contour.push((a * p0.0 + b * p1.0 + c * p2.0 + d * p3.0, a * p0.1 + b * p1.1 + c * p2.1 + d * p3.1));
+	}
+}
+
+/// Flatten a quadratic Bézier curve into straight edges.
+///
+/// # Arguments
+/// * `controls` - The control points of the curve, already transformed to coordinates.
+/// * `tolerance` - How far the edges may deviate from the curve, in coordinates.
+/// * `contour` - The contour to add the vertices to. The start of the curve should already be in
+/// it.
+fn flatten_quadratic(controls: &[(f64, f64); 3], tolerance: f64, contour: &mut Vec<(f64, f64)>) {
+	let [p0, p1, p2] = *controls;
+	let second_difference = (p0.0 - 2.0 * p1.0 + p2.0).hypot(p0.1 - 2.0 * p1.1 + p2.1);
+	let num_edges = num_edges((second_difference / (4.0 * tolerance)).sqrt());
+	for i in 1..=num_edges {
+		let t = i as f64 / num_edges as f64;
+		let u = 1.0 - t;
+		let (a, b, c) = (u * u, 2.0 * u * t, t * t);
+		contour.push((a * p0.0 + b * p1.0 + c * p2.0, a * p0.1 + b * p1.1 + c * p2.1));
+	}
+}
+
+/// Flatten an elliptical arc into straight edges.
+///
+/// The arc is given like in SVG path data, by its endpoints and the shape of the ellipse. It is
+/// converted to the centre of the ellipse and a range of angles, as described in the appendix of
+/// the SVG specification. If the radii are too small to reach the end point, they are scaled up.
+///
+/// # Arguments
+/// * `start` - The start of the arc, in user units.
+/// * `radii` - The radii of the ellipse, in user units.
+/// * `rotation` - How far the ellipse is rotated, in degrees.
+/// * `large_arc` - Whether to take the larger of the two arcs between the endpoints.
+/// * `sweep` - Whether to go in the direction of increasing angles.
+/// * `end` - The end of the arc, in user units.
+/// * `transform` - The transformation from user units to coordinates.
+/// * `tolerance` - How far the edges may deviate from the arc, in coordinates.
+/// * `contour` - The contour to add the vertices to, in coordinates.
+#[allow(clippy::too_many_arguments)] //The arc is given by all of the parameters of the SVG command.
+fn flatten_arc(start: (f64, f64), radii: (f64, f64), rotation: f64, large_arc: bool, sweep: bool, end: (f64, f64), transform: &AffineTransform, tolerance: f64, contour: &mut Vec<(f64, f64)>) {
+	if start == end {
+		return; //An arc without endpoints is omitted.
+	}
+	let (mut radius_x, mut radius_y) = (radii.0.abs(), radii.1.abs());
+	if radius_x == 0.0 || radius_y == 0.0 {
+		contour.push(apply(transform, end)); //Degenerates into a straight line.
+		return;
+	}
+	let (sin, cos) = rotation.to_radians().sin_cos();
+	let half_x = (start.0 - end.0) / 2.0;
+	let half_y = (start.1 - end.1) / 2.0;
+	let rotated_x = cos * half_x + sin * half_y;
+	let rotated_y = -sin * half_x + cos * half_y;
+	let lambda = (rotated_x / radius_x).powi(2) + (rotated_y / radius_y).powi(2);
+	if lambda > 1.0 {
+		radius_x *= lambda.sqrt();
+		radius_y *= lambda.sqrt();
+	}
+	let numerator = (radius_x * radius_y).powi(2) - (radius_x * rotated_y).powi(2) - (radius_y * rotated_x).powi(2);
+	let denominator = (radius_x * rotated_y).powi(2) + (radius_y * rotated_x).powi(2);
+	let factor = (numerator / denominator).max(0.0).sqrt() * if large_arc == sweep { -1.0 } else { 1.0 };
+	let centre_rotated = (factor * radius_x * rotated_y / radius_y, -factor * radius_y * rotated_x / radius_x);
+	let centre = (
+		cos * centre_rotated.0 - sin * centre_rotated.1 + (start.0 + end.0) / 2.0,
+		sin * centre_rotated.0 + cos * centre_rotated.1 + (start.1 + end.1) / 2.0,
+	);
+	let start_angle = ((rotated_y - centre_rotated.1) / radius_y).atan2((rotated_x - centre_rotated.0) / radius_x);
+	let end_angle = ((-rotated_y - centre_rotated.1) / radius_y).atan2((-rotated_x - centre_rotated.0) / radius_x);
+	let mut sweep_angle = end_angle - start_angle;
+	if sweep && sweep_angle < 0.0 {
+		sweep_angle += 2.0 * PI;
+	} else if !sweep && sweep_angle > 0.0 {
+		sweep_angle -= 2.0 * PI;
+	}
+
+	//The largest radius after transforming is the largest radius times the largest singular value of the transformation.
+	let sum_squares = transform.xx.powi(2) + transform.xy.powi(2) + transform.yx.powi(2) + transform.yy.powi(2);
+	let determinant = transform.xx * transform.yy - transform.xy * transform.yx;
+	let stretch = ((sum_squares + (sum_squares.powi(2) - 4.0 * determinant.powi(2)).max(0.0).sqrt()) / 2.0).sqrt();
+	let radius = radius_x.max(radius_y) * stretch;
+	//An edge spanning an angle of 2 * acos(1 - tolerance / radius) deviates exactly the tolerance from the circle.
+	let max_step = if tolerance < radius { 2.0 * (1.0 - tolerance / radius).acos() } else { PI / 2.0 };
+	let num_edges = num_edges(sweep_angle.abs() / max_step);
+	for i in 1..num_edges {
+		let angle = start_angle + sweep_angle * i as f64 / num_edges as f64;
+		let (angle_sin, angle_cos) = angle.sin_cos();
+		let point = (
+			centre.0 + radius_x * cos * angle_cos - radius_y * sin * angle_sin,
+			centre.1 + radius_x * sin * angle_cos + radius_y * cos * angle_sin,
+		);
+		contour.push(apply(transform, point));
+	}
+	contour.push(apply(transform, end)); //Exactly at the end point, without rounding errors.
+}
+
+/// Get how many edges to flatten a curve into, from the ideal, fractional number of edges.
+///
+/// This is at least one edge and at most `MAX_CURVE_EDGES`, also if the number is not finite.
+fn num_edges(ideal: f64) -> usize {
+	if ideal.is_nan() {
+		return MAX_CURVE_EDGES;
+	}
+	(ideal.ceil() as usize).clamp(1, MAX_CURVE_EDGES)
+}
+
+/// Transform a point in user units.
+fn apply(transform: &AffineTransform, point: (f64, f64)) -> (f64, f64) {
+	(
+		transform.xx * point.0 + transform.xy * point.1 + transform.dx,
+		transform.yx * point.0 + transform.yy * point.1 + transform.dy,
+	)
+}
+
+/// Round the vertices of a contour to coordinates, to create a polygon.
+///
+/// # Arguments
+/// * `contour` - The vertices, transformed to coordinates but not rounded yet.
+/// * `merge_duplicates` - Whether to merge consecutive vertices that round to the same
+/// coordinates, including a last vertex that coincides with the first.
+fn to_polygon(contour: &[(f64, f64)], merge_duplicates: bool) -> Result<Polygon, SvgError> {
+	let mut polygon = Polygon::with_capacity(contour.len());
+	for (x, y) in contour {
+		let vertex = Point2D { x: affine_transform::round(*x)?, y: affine_transform::round(*y)? };
+		if !merge_duplicates || polygon.len() == 0 || *polygon.vertex(polygon.len() - 1) != vertex {
+			polygon.push(vertex);
+		}
+	}
+	if merge_duplicates && polygon.len() > 1 && polygon.vertex(0) == polygon.vertex(polygon.len() - 1) {
+		polygon.pop();
+	}
+	Ok(polygon)
+}
+
+/// Parse a length attribute, which is a number in user units, optionally with the `px` unit.
+fn parse_length(value: &str) -> Option<f64> {
+	let value = value.trim();
+	let mut numbers = Numbers::new(value.strip_suffix("px").unwrap_or(value));
+	let length = numbers.number()?;
+	if numbers.is_at_end() { Some(length) } else { None }
+}
+
+/// Parse a `transform` attribute.
+///
+/// The attribute is a list of transformations. These are applied in reverse order, so the last
+/// transformation in the list is applied first.
+///
+/// # Result
+/// The combined transformation, or `None` if the attribute is invalid.
+fn parse_transform(value: &str) -> Option<AffineTransform> {
+	let mut result = AffineTransform::identity();
+	let mut rest = value;
+	loop {
+		rest = rest.trim_start_matches(|character: char| character.is_ascii_whitespace() || character == ',');
+		if rest.is_empty() {
+			return Some(result);
+		}
+		let open = rest.find('(')?;
+		let close = rest.find(')')?;
+		if close < open {
+			return None;
+		}
+		let name = rest[..open].trim_end();
+		let mut numbers = Numbers::new(&rest[open + 1..close]);
+		let mut arguments = Vec::new();
+		while let Some(number) = numbers.number() {
+			arguments.push(number);
+		}
+		if !numbers.is_at_end() {
+			return None;
+		}
+		let transform = match (name, arguments.as_slice()) {
+			("matrix", [a, b, c, d, e, f]) => AffineTransform { xx: *a, xy: *c, dx: *e, yx: *b, yy: *d, dy: *f },
+			("translate", [x]) => AffineTransform { dx: *x, ..AffineTransform::identity() },
+			("translate", [x, y]) => AffineTransform { dx: *x, dy: *y, ..AffineTransform::identity() },
+			("scale", [factor]) => AffineTransform { xx: *factor, yy: *factor, ..AffineTransform::identity() },
+			("scale", [x, y]) => AffineTransform { xx: *x, yy: *y, ..AffineTransform::identity() },
+			("rotate", [angle]) => rotation(*angle, 0.0, 0.0),
+			("rotate", [angle, x, y]) => rotation(*angle, *x, *y),
+			("skewX", [angle]) => AffineTransform { xy: angle.to_radians().tan(), ..AffineTransform::identity() },
+			("skewY", [angle]) => AffineTransform { yx: angle.to_radians().tan(), ..AffineTransform::identity() },
+			_ => return None,
+		};
+		result = transform.then(&result);
+		rest = &rest[close + 1..];
+	}
+}
+
+/// Create a rotation around a pivot point, like the `rotate` transformation in SVG.
+///
+/// # Arguments
+/// * `angle` - How far to rotate, in degrees.
+/// * `x` - The X coordinate of the pivot point.
+/// * `y` - The Y coordinate of the pivot point.
+fn rotation(angle: f64, x: f64, y: f64) -> AffineTransform {
+	let (sin, cos) = angle.to_radians().sin_cos();
+	AffineTransform { xx: cos, xy: -sin, dx: x - (cos * x - sin * y), yx: sin, yy: cos, dy: y - (sin * x + cos * y) }
+}
+
+/// Reads numbers from a list of numbers, as found in SVG attributes.
+///
+/// The numbers may be separated by whitespace and at most one comma. Separators may be left out if
+/// the numbers can be told apart otherwise, like in `10-5.5.5`, which holds the numbers 10, -5.5
+/// and 0.5.
+struct Numbers<'a> {
+	/// The text to read from.
+	text: &'a [u8],
+
+	/// The byte position in the text to read the next number from.
+	position: usize,
+}
+
+impl<'a> Numbers<'a> {
+	/// Start reading numbers from the start of a text.
+	fn new(text: &'a str) -> Numbers<'a> {
+		Numbers { text: text.as_bytes(), position: 0 }
+	}
+
+	/// Get the next character, without consuming it.
+	fn peek(&self) -> Option<u8> {
+		self.text.get(self.position).copied()
+	}
+
+	/// Skip over whitespace and a comma.
+	fn skip_separators(&mut self) {
+		let mut had_comma = false;
+		while let Some(character) = self.peek() {
+			if character == b',' && !had_comma {
+				had_comma = true;
+			} else if !character.is_ascii_whitespace() {
+				break;
+			}
+			self.position += 1;
+		}
+	}
+
+	/// Check whether all of the text has been read, apart from separators.
+	fn is_at_end(&mut self) -> bool {
+		self.skip_separators();
+		self.position >= self.text.len()
+	}
+
+	/// Read the next number.
+	///
+	/// # Result
+	/// The number, or `None` if the text doesn't continue with a number. In that case, only the
+	/// separators are consumed.
+	fn number(&mut self) -> Option<f64> {
+		self.skip_separators();
+		let start = self.position;
+		let mut end = start;
+		let digits = |end: &mut usize| {
+			let from = *end;
+			while self.text.get(*end).is_some_and(u8::is_ascii_digit) {
+				*end += 1;
+			}
+			*end > from
+		};
+		if matches!(self.text.get(end), Some(b'+' | b'-')) {
+			end += 1;
+		}
+		let mut has_digits = digits(&mut end);
+		if self.text.get(end) == Some(&b'.') {
+			end += 1;
+			has_digits |= digits(&mut end);
+		}
+		if !has_digits {
+			return None;
+		}
+		if matches!(self.text.get(end), Some(b'e' | b'E')) {
+			let mut exponent_end = end + 1;
+			if matches!(self.text.get(exponent_end), Some(b'+' | b'-')) {
+				exponent_end += 1;
+			}
+			if digits(&mut exponent_end) {
+				end = exponent_end;
+			}
+		}
+		let number = std::str::from_utf8(&self.text[start..end]).ok()?.parse().ok()?;
+		self.position = end;
+		Some(number)
+	}
+
+	/// Read a flag of an arc command, which is a single `0` or `1`.
+	fn flag(&mut self) -> Option<bool> {
+		self.skip_separators();
+		let flag = match self.peek()? {
+			b'0' => false,
+			b'1' => true,
+			_ => return None,
+		};
+		self.position += 1;
+		Some(flag)
+	}
+}
+
+/// How a shape is drawn in an SVG document.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgStyle {
+	/// The colour to fill the shape with, as a CSS colour like `#4060c0`, or `none`.
+	pub fill: String,
+
+	/// How opaque the fill is, from 0 for transparent to 1 for opaque.
+	pub fill_opacity: f64,
+
+	/// Which regions of the shape are filled.
+	///
+	/// SVG only has the even-odd and non-zero rules, so the positive and negative rules are drawn
+	/// with the non-zero rule. For shapes whose holes wind opposite to their outer contours, that
+	/// gives the same result.
+	pub fill_rule: FillRule,
+
+	/// The colour of the outline of the shape, as a CSS colour, or `none`. The vertex markers and
+	/// indices are drawn in this colour too, or in black if there is no outline.
+	pub stroke: String,
+
+	/// The width of the outline, in pixels on the screen, regardless of how far the document is
+	/// zoomed in.
+	pub stroke_width: f64,
+}
+
+impl Default for SvgStyle {
+	/// A translucent blue fill with a dark blue outline.
+	fn default() -> SvgStyle {
+		SvgStyle {
+			fill: "#4060c0".to_string(),
+			fill_opacity: 0.5,
+			fill_rule: FillRule::NON_ZERO,
+			stroke: "#203060".to_string(),
+			stroke_width: 1.0,
+		}
+	}
+}
+
+/// The options for writing an SVG document.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SvgWriteOptions {
+	/// How many coordinates make up one user unit of the document. Read the document back with the
+	/// same scale to get the same coordinates.
+	pub scale: f64,
+
+	/// Whether to mark every vertex with a dot.
+	pub vertex_markers: bool,
+
+	/// Whether to label every vertex with its index in the shape. The vertices of a multi-polygon
+	/// are numbered through all of its contours.
+	pub vertex_indices: bool,
+
+	/// Whether to mark the seam of every contour, which is its first vertex, with a circle and a line
+	/// in the direction of the contour.
+	pub seam_markers: bool,
+}
+
+impl Default for SvgWriteOptions {
+	/// One coordinate per user unit, without annotations.
+	fn default() -> SvgWriteOptions {
+		SvgWriteOptions { scale: 1.0, vertex_markers: false, vertex_indices: false, seam_markers: false }
+	}
+}
+
+/// An SVG document to write shapes to.
+///
+/// Shapes are added to the document one by one, each with its own style. The document is written
+/// by formatting it, for instance with `to_string`. Its view box fits around all of the shapes, with
+/// a margin of 5% of their size.
+///
+/// Each shape is written as a `<path>` element with a subpath for each contour. The annotations are
+/// written as `<circle>`, `<line>` and `<text>` elements, which the reader skips. Reading a written
+/// document with the same scale gives the same contours, as long as every contour has at least two
+/// vertices and no consecutive duplicate vertices, since the reader merges those.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// use apex::io::svg::{read_polygons, SvgDocument, SvgStyle, SvgWriteOptions};
+/// let triangle = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 1000, y: 0 }, Point2D { x: 500, y: 800 }]);
+/// let mut document = SvgDocument::new(SvgWriteOptions { vertex_markers: true, ..SvgWriteOptions::default() });
+/// document.add_polygon(&triangle, &SvgStyle::default());
+/// let svg = document.to_string();
+/// assert!(svg.contains("viewBox=\"-50 -50 1100 900\""));
+/// assert_eq!(read_polygons(&svg, 1.0, 1.0).unwrap(), vec![triangle]);
+/// ```
+pub struct SvgDocument {
+	/// The options for writing the document.
+	options: SvgWriteOptions,
+
+	/// The shapes in the document, as the vertices of their contours, with their styles.
+	shapes: Vec<(Vec<Vec<Point2D>>, SvgStyle)>,
+}
+
+impl SvgDocument {
+	/// Create an empty document.
+	///
+	/// # Arguments
+	/// * `options` - How to write the document.
+	pub fn new(options: SvgWriteOptions) -> SvgDocument {
+		SvgDocument { options, shapes: Vec::new() }
+	}
+
+	/// Add a polygon to the document, drawn on top of the shapes that were added before.
+	///
+	/// # Arguments
+	/// * `polygon` - The polygon to add.
+	/// * `style` - How to draw the polygon.
+	pub fn add_polygon(&mut self, polygon: &Polygon, style: &SvgStyle) {
+		self.shapes.push((vec![polygon.iter().copied().collect()], style.clone()));
+	}
+
+	/// Add a multi-polygon to the document, drawn on top of the shapes that were added before.
+	///
+	/// # Arguments
+	/// * `multi_polygon` - The multi-polygon to add.
+	/// * `style` - How to draw the multi-polygon.
+	pub fn add_multi_polygon(&mut self, multi_polygon: &MultiPolygon, style: &SvgStyle) {
+		self.shapes.push((multi_polygon.contours().map(|contour| contour.to_vec()).collect(), style.clone()));
+	}
+}
+
+impl fmt::Display for SvgDocument {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let unit = |coordinate: Coordinate| coordinate as f64 / self.options.scale;
+		let mut left = f64::INFINITY;
+		let mut top = f64::INFINITY;
+		let mut right = f64::NEG_INFINITY;
+		let mut bottom = f64::NEG_INFINITY;
+		for vertex in self.shapes.iter().flat_map(|(contours, _)| contours.iter().flatten()) {
+			left = left.min(unit(vertex.x));
+			top = top.min(unit(vertex.y));
+			right = right.max(unit(vertex.x));
+			bottom = bottom.max(unit(vertex.y));
+		}
+		if left > right {
+			(left, top, right, bottom) = (0.0, 0.0, 0.0, 0.0); //There are no vertices. Show the origin.
+		}
+		let size = (right - left).max(bottom - top);
+		let margin = if size > 0.0 { size * 0.05 } else { 1.0 };
+		let marker_radius = margin / 10.0;
+
+		writeln!(f, "<?xml version=\"1.0\" encoding=\"utf-8\" ?>")?;
+		writeln!(f, "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">", left - margin, top - margin, right - left + margin * 2.0, bottom - top + margin * 2.0)?;
+		for (contours, style) in &self.shapes {
+			writeln!(f, "\t<g>")?;
+			write!(f, "\t\t<path d=\"")?;
+			let mut separator = "";
+			for contour in contours.iter().filter(|contour| !contour.is_empty()) {
+				write!(f, "{}M {},{}", separator, unit(contour[0].x), unit(contour[0].y))?;
+				if contour.len() > 1 {
+					write!(f, " L")?;
+					for vertex in &contour[1..] {
+						write!(f, " {},{}", unit(vertex.x), unit(vertex.y))?;
+					}
+				}
+				write!(f, " Z")?;
+				separator = " ";
+			}
+			let fill_rule = if style.fill_rule == FillRule::EVEN_ODD { "evenodd" } else { "nonzero" };
+			writeln!(f, "\" fill=\"{}\" fill-opacity=\"{}\" fill-rule=\"{}\" stroke=\"{}\" stroke-width=\"{}\" vector-effect=\"non-scaling-stroke\" />", escape(&style.fill), style.fill_opacity, fill_rule, escape(&style.stroke), style.stroke_width)?;
+
+			let annotation_colour = if style.stroke == "none" { "black".to_string() } else { escape(&style.stroke) };
+			if self.options.vertex_markers {
+				writeln!(f, "\t\t<g fill=\"{}\">", annotation_colour)?;
+				for vertex in contours.iter().flatten() {
+					writeln!(f, "\t\t\t<circle cx=\"{}\" cy=\"{}\" r=\"{}\" />", unit(vertex.x), unit(vertex.y), marker_radius)?;
+				}
+				writeln!(f, "\t\t</g>")?;
+			}
+			if self.options.vertex_indices {
+				writeln!(f, "\t\t<g fill=\"{}\" font-family=\"sans-serif\" font-size=\"{}\">", annotation_colour, marker_radius * 3.0)?;
+				for (index, vertex) in contours.iter().flatten().enumerate() {
+					writeln!(f, "\t\t\t<text x=\"{}\" y=\"{}\">{}</text>", unit(vertex.x) + marker_radius, unit(vertex.y) - marker_radius, index)?;
+				}
+				writeln!(f, "\t\t</g>")?;
+			}
+			if self.options.seam_markers {
+				writeln!(f, "\t\t<g fill=\"none\" stroke=\"#e02020\" stroke-width=\"2\" vector-effect=\"non-scaling-stroke\">")?;
+				for contour in contours.iter().filter(|contour| !contour.is_empty()) {
+					let (x, y) = (unit(contour[0].x), unit(contour[0].y));
+					writeln!(f, "\t\t\t<circle cx=\"{}\" cy=\"{}\" r=\"{}\" />", x, y, marker_radius * 2.0)?;
+					let next = contour.iter().find(|vertex| **vertex != contour[0]); //Skip duplicates of the seam to find the direction.
+					if let Some(next) = next {
+						let (direction_x, direction_y) = (unit(next.x) - x, unit(next.y) - y);
+						let length = direction_x.hypot(direction_y) / (marker_radius * 6.0);
+						writeln!(f, "\t\t\t<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" />", x, y, x + direction_x / length, y + direction_y / length)?;
+					}
+				}
+				writeln!(f, "\t\t</g>")?;
+			}
+			writeln!(f, "\t</g>")?;
+		}
+		write!(f, "</svg>")
+	}
+}
+
+/// Escape the characters that have a special meaning in XML attributes and text.
+fn escape(text: &str) -> String {
+	text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+	use crate::Shape2D;
+
+	/// Wrap shapes in an SVG document.
+	fn document(shapes: &str) -> String {
+		format!("<?xml version=\"1.0\" encoding=\"utf-8\" ?>\n<svg xmlns=\"http://www.w3.org/2000/svg\">\n{}\n</svg>", shapes)
+	}
+
+	/// Get the vertices of a polygon.
+	fn vertices(polygon: &Polygon) -> Vec<Point2D> {
+		polygon.iter().copied().collect()
+	}
+
+	/// Test reading the test data, which is the same as what the test loader reads.
+	#[test]
+	fn test_data() {
+		let polygons = read_polygons(include_str!("../test/data/polygon/pentagram_1000.svg"), 1.0, 1.0).unwrap();
+		assert_eq!(polygons, vec![crate::test::data::polygon::pentagram_1000()]);
+		let multi_polygon = read_multi_polygon(include_str!("../test/data/multi_polygon/square_with_hole_1000.svg"), 1.0, 1.0).unwrap();
+		assert_eq!(multi_polygon.num_contours(), 2);
+		assert_eq!(multi_polygon.area(), 1000000 - 250000);
+	}
+
+	/// Test the elements with straight edges, which are read exactly.
+	#[test_case("<polygon points=\"0,0 10,0 10,10\" />", vec![Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 10, y: 10 }] ; "polygon")]
+	#[test_case("<polyline points=\"0 0, 10 0 , 10 10\" />", vec![Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 10, y: 10 }] ; "polyline")]
+	#[test_case("<polygon points=\"1e1-5 .5.5\" />", vec![Point2D { x: 10, y: -5 }, Point2D { x: 1, y: 1 }] ; "compact numbers")]
+	#[test_case("<rect x=\"1\" y=\"2\" width=\"3px\" height=\"4\" />", vec![Point2D { x: 1, y: 2 }, Point2D { x: 4, y: 2 }, Point2D { x: 4, y: 6 }, Point2D { x: 1, y: 6 }] ; "rect")]
+	#[test_case("<path d=\"M 1 1 L 5 1 5 5 Z\" />", vec![Point2D { x: 1, y: 1 }, Point2D { x: 5, y: 1 }, Point2D { x: 5, y: 5 }] ; "path with implicit lines")]
+	#[test_case("<path d=\"m1,1 h4 v4 h-4 z\" />", vec![Point2D { x: 1, y: 1 }, Point2D { x: 5, y: 1 }, Point2D { x: 5, y: 5 }, Point2D { x: 1, y: 5 }] ; "relative path")]
+	#[test_case("<path d=\"M 1 1 H 5 V 5 L 1 1 Z\" />", vec![Point2D { x: 1, y: 1 }, Point2D { x: 5, y: 1 }, Point2D { x: 5, y: 5 }] ; "explicitly closed path")]
+	fn straight(shape: &str, expected: Vec<Point2D>) {
+		let polygons = read_polygons(&document(shape), 1.0, 1.0).unwrap();
+		assert_eq!(polygons.len(), 1);
+		assert_eq!(vertices(&polygons[0]), expected);
+	}
+
+	/// Test that the scale is applied to all coordinates, and that the result is rounded with halves
+	/// rounded up.
+	#[test]
+	fn scale() {
+		let polygons = read_polygons(&document("<polygon points=\"0.25,-0.25 1.5,0 0,1\" />"), 10.0, 1.0).unwrap();
+		assert_eq!(vertices(&polygons[0]), vec![Point2D { x: 3, y: -2 }, Point2D { x: 15, y: 0 }, Point2D { x: 0, y: 10 }]);
+	}
+
+	/// Test paths with multiple subpaths, including drawing after closing a subpath.
+	#[test]
+	fn subpaths() {
+		let polygons = read_polygons(&document("<path d=\"M0 0 H10 V10 Z M20 20 h10 v10 z l-5 0 0 -5 z\" />"), 1.0, 1.0).unwrap();
+		assert_eq!(polygons.len(), 3);
+		assert_eq!(vertices(&polygons[1]), vec![Point2D { x: 20, y: 20 }, Point2D { x: 30, y: 20 }, Point2D { x: 30, y: 30 }]);
+		assert_eq!(vertices(&polygons[2]), vec![Point2D { x: 20, y: 20 }, Point2D { x: 15, y: 20 }, Point2D { x: 15, y: 15 }], "After closing, drawing continues from the start of the subpath.");
+	}
+
+	/// Test that curves are flattened within the tolerance, by comparing the area of circles made
+	/// of curves with the area of the circle.
+	///
+	/// The cubic curves approximate the circle only up to 0.03% of the radius.
+	#[test_case("<path d=\"M 100 0 A 100 100 0 0 1 -100 0 A 100 100 0 0 1 100 0 Z\" />" ; "arcs")]
+	#[test_case("<path d=\"M 100 0 a 100 100 0 1 0 0 0.001 Z\" />" ; "large arc")]
+	#[test_case("<rect x=\"-100\" y=\"-100\" width=\"200\" height=\"200\" rx=\"100\" />" ; "rounded rect")]
+	#[test_case("<path d=\"M 100 0 C 100 55.2285 55.2285 100 0 100 S -100 55.2285 -100 0 S -55.2285 -100 0 -100 S 100 -55.2285 100 0 Z\" />" ; "cubic curves")]
+	fn circles(shape: &str) {
+		let scale = 100.0; //Radius of 10000 coordinates.
+		let tolerance = 5.0;
+		let polygons = read_polygons(&document(shape), scale, tolerance).unwrap();
+		assert_eq!(polygons.len(), 1);
+		let radius = 10000.0;
+		for vertex in polygons[0].iter() {
+			let distance = (vertex.x as f64).hypot(vertex.y as f64);
+			assert!((distance - radius).abs() <= 4.0, "The vertices must be on the circle, up to rounding and the cubic approximation, but one is at distance {}.", distance);
+		}
+		let area = polygons[0].area().abs() as f64;
+		let circle = PI * radius * radius;
+		assert!(area <= circle && area >= circle - 2.0 * PI * radius * (tolerance + 4.0), "The edges must be within the tolerance of the circle, but the area is {} instead of {}.", area, circle);
+		assert!(polygons[0].len() < 1000, "The curves must not be flattened into more edges than necessary.");
+	}
+
+	/// Test quadratic curves, including smooth ones that reflect the previous control point.
+	#[test]
+	fn quadratic() {
+		let polygons = read_polygons(&document("<path d=\"M 0 0 Q 5 10 10 0 T 20 0 Z\" />"), 1.0, 0.04).unwrap();
+		let vertices = vertices(&polygons[0]);
+		assert!(vertices.contains(&Point2D { x: 5, y: 5 }), "The top of the first curve is halfway to its control point.");
+		assert!(vertices.contains(&Point2D { x: 15, y: -5 }), "The second curve mirrors the first.");
+		assert!(vertices.contains(&Point2D { x: 20, y: 0 }), "The curve ends exactly at its end point.");
+	}
+
+	/// Test that transforms of nested groups and shapes are combined.
+	#[test_case("<g transform=\"translate(10 20)\"><polygon points=\"1,0 0,1 0,0\" /></g>", vec![Point2D { x: 11, y: 20 }, Point2D { x: 10, y: 21 }, Point2D { x: 10, y: 20 }] ; "translate")]
+	#[test_case("<g transform=\"translate(10)\"><g transform=\"scale(2, 3)\"><polygon points=\"1,0 0,1 0,0\" /></g></g>", vec![Point2D { x: 12, y: 0 }, Point2D { x: 10, y: 3 }, Point2D { x: 10, y: 0 }] ; "nested")]
+	#[test_case("<polygon transform=\"translate(10) scale(2)\" points=\"1,0 0,1 0,0\" />", vec![Point2D { x: 12, y: 0 }, Point2D { x: 10, y: 2 }, Point2D { x: 10, y: 0 }] ; "list applied from right to left")]
+	#[test_case("<polygon transform=\"rotate(90, 5, 5)\" points=\"10,5 5,10 5,5\" />", vec![Point2D { x: 5, y: 10 }, Point2D { x: 0, y: 5 }, Point2D { x: 5, y: 5 }] ; "rotate around pivot")]
+	#[test_case("<polygon transform=\"matrix(1 2 3 4 5 6)\" points=\"1,0 0,1 0,0\" />", vec![Point2D { x: 6, y: 8 }, Point2D { x: 8, y: 10 }, Point2D { x: 5, y: 6 }] ; "matrix")]
+	#[test_case("<polygon transform=\"skewX(45)\" points=\"0,10 0,0 10,0\" />", vec![Point2D { x: 10, y: 10 }, Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }] ; "skew")]
+	fn transforms(shapes: &str, expected: Vec<Point2D>) {
+		let polygons = read_polygons(&document(shapes), 1.0, 1.0).unwrap();
+		assert_eq!(vertices(&polygons[0]), expected);
+	}
+
+	/// Test that elements which are not rendered by themselves, and other mark-up, are skipped.
+	#[test]
+	fn skipped() {
+		let svg = document("<!-- <polygon points=\"0 0 1 1 2 0\" /> -->\n<defs><rect width=\"5\" height=\"5\" /></defs>\n<title>Shapes</title>\n<![CDATA[<rect width=\"1\" height=\"1\" />]]>\n<rect width=\"0\" height=\"5\" />\n<circle r=\"5\" />\n<polygon points=\"0,0 1,0 1,1\" />");
+		let polygons = read_polygons(&format!("<!DOCTYPE svg [ <!ENTITY x \"y\"> ]>{}", svg), 1.0, 1.0).unwrap();
+		assert_eq!(polygons.len(), 1, "Only the last polygon is rendered.");
+	}
+
+	/// Test that problems in the document are reported as errors.
+	#[test_case("<svg><polygon points=\"0 0 1 1\"></svg>", SvgError::MALFORMED(31) ; "wrong end tag")]
+	#[test_case("<svg><polygon points=\"0 0 1 1\" />", SvgError::MALFORMED(33) ; "unclosed element")]
+	#[test_case("<svg><polygon points=\"0 0 1 1 /></svg>", SvgError::MALFORMED(21) ; "unclosed attribute")]
+	#[test_case("<svg><polygon points=0 /></svg>", SvgError::MALFORMED(21) ; "unquoted attribute")]
+	#[test_case("<svg><polygon points=\"0 0 1\" /></svg>", SvgError::INVALID_ATTRIBUTE("polygon".to_string(), "points".to_string()) ; "odd number of coordinates")]
+	#[test_case("<svg><polygon points=\"0 0 1 x\" /></svg>", SvgError::INVALID_ATTRIBUTE("polygon".to_string(), "points".to_string()) ; "not a number")]
+	#[test_case("<svg><path d=\"M 0 0 L 1\" /></svg>", SvgError::INVALID_ATTRIBUTE("path".to_string(), "d".to_string()) ; "incomplete path")]
+	#[test_case("<svg><path d=\"0 0 L 1 1\" /></svg>", SvgError::INVALID_ATTRIBUTE("path".to_string(), "d".to_string()) ; "path without command")]
+	#[test_case("<svg><path d=\"L 1 1 2 0\" /></svg>", SvgError::INVALID_ATTRIBUTE("path".to_string(), "d".to_string()) ; "path without move")]
+	#[test_case("<svg><path d=\"M 0 0 X 1 1\" /></svg>", SvgError::INVALID_ATTRIBUTE("path".to_string(), "d".to_string()) ; "unknown command")]
+	#[test_case("<svg><rect width=\"5mm\" height=\"5\" /></svg>", SvgError::INVALID_ATTRIBUTE("rect".to_string(), "width".to_string()) ; "unsupported unit")]
+	#[test_case("<svg><g transform=\"spin(5)\"></g></svg>", SvgError::INVALID_ATTRIBUTE("g".to_string(), "transform".to_string()) ; "unknown transform")]
+	#[test_case("<svg><polygon points=\"0 0 1e300 1\" /></svg>", SvgError::OVERFLOW ; "overflow")]
+	fn errors(svg: &str, expected: SvgError) {
+		assert_eq!(read_polygons(svg, 1.0, 1.0), Err(expected));
+	}
+
+	/// Test that written documents are read back as the same shapes, with every annotation.
+	#[test_case(1.0 ; "unit scale")]
+	#[test_case(1000.0 ; "fine scale")]
+	#[test_case(0.001 ; "coarse scale")]
+	fn round_trip(scale: f64) {
+		let options = SvgWriteOptions { scale, vertex_markers: true, vertex_indices: true, seam_markers: true };
+		let mut document = SvgDocument::new(options);
+		let pentagram = crate::test::data::polygon::pentagram_1000();
+		let odd = Polygon::from_iter([Point2D { x: -1234567, y: 1 }, Point2D { x: 7654321, y: -3 }, Point2D { x: 5, y: 999999 }]);
+		let square_with_hole = crate::test::data::multi_polygon::square_with_hole_1000();
+		document.add_polygon(&pentagram, &SvgStyle::default());
+		document.add_polygon(&odd, &SvgStyle { fill_rule: FillRule::EVEN_ODD, stroke: "none".to_string(), ..SvgStyle::default() });
+		document.add_multi_polygon(&square_with_hole, &SvgStyle::default());
+		let polygons = read_polygons(&document.to_string(), scale, 1.0).unwrap();
+		let mut expected = vec![pentagram, odd];
+		expected.extend(square_with_hole.contours().map(|contour| Polygon::from_iter(contour.iter().copied())));
+		assert_eq!(polygons, expected);
+	}
+
+	/// Test that the view box fits around all shapes, in user units.
+	#[test]
+	fn view_box() {
+		let mut document = SvgDocument::new(SvgWriteOptions { scale: 10.0, ..SvgWriteOptions::default() });
+		document.add_polygon(&Polygon::from_iter([Point2D { x: -100, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 50 }]), &SvgStyle::default());
+		document.add_polygon(&Polygon::from_iter([Point2D { x: 0, y: 100 }, Point2D { x: 10, y: 100 }, Point2D { x: 10, y: 200 }]), &SvgStyle::default());
+		assert!(document.to_string().contains("viewBox=\"-11 -1 22 22\""), "The shapes span 20 by 20 units, with a margin of 1 unit.");
+	}
+
+	/// Test writing a document without any shapes.
+	#[test]
+	fn empty() {
+		let svg = SvgDocument::new(SvgWriteOptions::default()).to_string();
+		assert!(svg.contains("viewBox=\"-1 -1 2 2\""), "Without shapes, the view box shows the origin.");
+		assert_eq!(read_polygons(&svg, 1.0, 1.0), Ok(Vec::new()));
+	}
+
+	/// Test that the annotations are only written if they are enabled.
+	#[test_case(false, false, false, 0, 0, 0 ; "none")]
+	#[test_case(true, false, false, 4, 0, 0 ; "vertex markers")]
+	#[test_case(false, true, false, 0, 4, 0 ; "vertex indices")]
+	#[test_case(false, false, true, 1, 0, 1 ; "seam markers")]
+	#[test_case(true, true, true, 5, 4, 1 ; "all")]
+	fn annotations(vertex_markers: bool, vertex_indices: bool, seam_markers: bool, circles: usize, texts: usize, lines: usize) {
+		let mut document = SvgDocument::new(SvgWriteOptions { scale: 1.0, vertex_markers, vertex_indices, seam_markers });
+		document.add_polygon(&crate::test::data::polygon::square_1000(), &SvgStyle::default());
+		let svg = document.to_string();
+		assert_eq!(svg.matches("<circle").count(), circles);
+		assert_eq!(svg.matches("<text").count(), texts);
+		assert_eq!(svg.matches("<line").count(), lines);
+		assert!(svg.contains("<text x=\"1005\" y=\"995\">2</text>") == vertex_indices, "The indices are next to their vertices.");
+	}
+
+	/// Test that styles are written with their special characters escaped.
+	#[test]
+	fn styles() {
+		let mut document = SvgDocument::new(SvgWriteOptions::default());
+		let style = SvgStyle { fill: "url(#a&b)".to_string(), fill_opacity: 0.25, fill_rule: FillRule::EVEN_ODD, stroke: "\"red\"".to_string(), stroke_width: 3.0 };
+		document.add_polygon(&crate::test::data::polygon::triangle_1000(), &style);
+		let svg = document.to_string();
+		assert!(svg.contains("fill=\"url(#a&amp;b)\" fill-opacity=\"0.25\" fill-rule=\"evenodd\" stroke=\"&quot;red&quot;\" stroke-width=\"3\""));
+		assert_eq!(read_polygons(&svg, 1.0, 1.0).unwrap(), vec![crate::test::data::polygon::triangle_1000()]);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,498 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! Reads and writes geometry as Well-Known Binary.
+//!
+//! The reader understands line strings, polygons and multi-polygons, in either byte order. Z and M
+//! ordinates are accepted, both in the ISO encoding and in the extended encoding of PostGIS, but
+//! they are ignored, since the geometry of this library is two-dimensional. Spatial reference IDs
+//! of the extended encoding are skipped as well. The writer writes two-dimensional geometry in
+//! little-endian byte order, without a spatial reference ID.
+//!
+//! Coordinates are multiplied by a scale and then rounded to the nearest `Coordinate`, with halves
+//! rounded up. The writer divides them by the same scale again.
+
+use std::error::Error; //The errors of the reader can be propagated with other errors.
+use std::fmt; //To describe the errors as text.
+
+use crate::MultiPolygon; //To read and write polygons with holes.
+use crate::OverflowError; //Coordinates may not fit after scaling.
+use crate::Point2D; //The vertices that are read and written.
+use crate::Polygon; //To read and write line strings, and polygons without holes.
+use crate::affine_transform; //To round the coordinates the same way as transformations do.
+use crate::io; //To convert between rings and contours.
+use crate::io::Geometry; //The geometry that is read.
+
+/// The type code of a line string.
+const LINE_STRING: u32 = 2;
+
+/// The type code of a polygon.
+const POLYGON: u32 = 3;
+
+/// The type code of a multi-polygon.
+const MULTI_POLYGON: u32 = 6;
+
+/// The flag in the type codes of the extended encoding for geometry with Z ordinates.
+const EXTENDED_Z: u32 = 0x8000_0000;
+
+/// The flag in the type codes of the extended encoding for geometry with M ordinates.
+const EXTENDED_M: u32 = 0x4000_0000;
+
+/// The flag in the type codes of the extended encoding for geometry with a spatial reference ID.
+const EXTENDED_SRID: u32 = 0x2000_0000;
+
+/// The reasons that Well-Known Binary can't be read.
+#[allow(non_camel_case_types)] //The variants are capitalised like those of the other enums, with underscores between words.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WkbError {
+	/// The data is truncated or otherwise doesn't follow the encoding of Well-Known Binary. This
+	/// holds the byte position in the data where the problem was found.
+	MALFORMED(usize),
+
+	/// The data holds a type of geometry that can't be read, such as a point. This holds the type
+	/// code, as it is in the data.
+	UNSUPPORTED(u32),
+
+	/// A coordinate is outside of the range of `Coordinate` after scaling.
+	OVERFLOW,
+}
+
+impl fmt::Display for WkbError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			WkbError::MALFORMED(position) => write!(f, "The Well-Known Binary is malformed at position {}.", position),
+			WkbError::UNSUPPORTED(code) => write!(f, "The geometry type with code {} is not supported.", code),
+			WkbError::OVERFLOW => write!(f, "A coordinate in the Well-Known Binary went out of the range of the coordinate type."),
+		}
+	}
+}
+
+impl Error for WkbError {}
+
+impl From<OverflowError> for WkbError {
+	fn from(_: OverflowError) -> WkbError {
+		WkbError::OVERFLOW
+	}
+}
+
+/// Read a geometry from Well-Known Binary.
+///
+/// The exterior rings of polygons are made to wind counter-clockwise and their interior rings
+/// clockwise, so that the rings of a polygon become the outer contour and holes of a multi-polygon.
+/// The last vertex of each ring, which repeats the first, is removed.
+///
+/// # Arguments
+/// * `wkb` - The Well-Known Binary of a single geometry.
+/// * `scale` - How many coordinates make up one unit in the data.
+///
+/// # Result
+/// The geometry, or the reason why the data can't be read.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// use apex::io::Geometry;
+/// use apex::io::wkb::{read, write_line_string};
+/// let line = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 150, y: -50 }]);
+/// let wkb = write_line_string(&line, 100.0);
+/// assert_eq!(wkb.len(), 1 + 4 + 4 + 2 * 16);
+/// match read(&wkb, 100.0).unwrap() {
+/// 	Geometry::LINE_STRING(read_back) => assert_eq!(read_back, line),
+/// 	_ => panic!("This is a line string."),
+/// }
+/// ```
+pub fn read(wkb: &[u8], scale: f64) -> Result<Geometry, WkbError> {
+	let mut bytes = Bytes { bytes: wkb, position: 0 };
+	let header = bytes.header()?;
+	let geometry = match header.geometry_type {
+		LINE_STRING => Geometry::LINE_STRING(Polygon::from_iter(read_points(&mut bytes, &header, scale)?)),
+		POLYGON => {
+			let mut multi_polygon = MultiPolygon::new();
+			io::push_rings(&mut multi_polygon, read_rings(&mut bytes, &header, scale)?);
+			Geometry::POLYGON(multi_polygon)
+		}
+		MULTI_POLYGON => {
+			let mut multi_polygon = MultiPolygon::new();
+			let num_polygons = bytes.count(header.little_endian, 1 + 4 + 4)?;
+			for _ in 0..num_polygons {
+				let polygon_header = bytes.header()?;
+				if polygon_header.geometry_type != POLYGON {
+					return Err(WkbError::UNSUPPORTED(polygon_header.code));
+				}
+				io::push_rings(&mut multi_polygon, read_rings(&mut bytes, &polygon_header, scale)?);
+			}
+			Geometry::MULTI_POLYGON(multi_polygon)
+		}
+		_ => return Err(WkbError::UNSUPPORTED(header.code)),
+	};
+	if bytes.position < wkb.len() {
+		return Err(WkbError::MALFORMED(bytes.position));
+	}
+	Ok(geometry)
+}
+
+/// Read a polygon or multi-polygon from Well-Known Binary, as a multi-polygon.
+///
+/// This reads the geometry like `read`, but only accepts polygons and multi-polygons.
+///
+/// # Arguments
+/// * `wkb` - The Well-Known Binary of a polygon or multi-polygon.
+/// * `scale` - How many coordinates make up one unit in the data.
+///
+/// # Result
+/// The contours of all polygons in the data, or the reason why the data can't be read.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, Point2D, Shape2D};
+/// use apex::io::wkb::{read_multi_polygon, write_multi_polygon};
+/// let mut square_with_hole = MultiPolygon::new();
+/// square_with_hole.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 10, y: 10 }, Point2D { x: 0, y: 10 }]);
+/// square_with_hole.push_contour([Point2D { x: 2, y: 2 }, Point2D { x: 2, y: 8 }, Point2D { x: 8, y: 8 }, Point2D { x: 8, y: 2 }]);
+/// let wkb = write_multi_polygon(&square_with_hole, 1.0);
+/// assert_eq!(read_multi_polygon(&wkb, 1.0).unwrap().area(), 10 * 10 - 6 * 6);
+/// ```
+pub fn read_multi_polygon(wkb: &[u8], scale: f64) -> Result<MultiPolygon, WkbError> {
+	match read(wkb, scale)? {
+		Geometry::POLYGON(multi_polygon) | Geometry::MULTI_POLYGON(multi_polygon) => Ok(multi_polygon),
+		Geometry::LINE_STRING(_) => Err(WkbError::UNSUPPORTED(LINE_STRING)),
+	}
+}
+
+/// Write the vertices of a polygon as a line string, an open chain of line segments.
+///
+/// # Arguments
+/// * `polygon` - The polygon with the vertices of the line string.
+/// * `scale` - How many coordinates make up one unit in the data.
+///
+/// # Result
+/// The Well-Known Binary of the line string.
+pub fn write_line_string(polygon: &Polygon, scale: f64) -> Vec<u8> {
+	let mut result = Vec::new();
+	write_header(&mut result, LINE_STRING);
+	let vertices: Vec<Point2D> = polygon.iter().copied().collect();
+	write_points(&mut result, &vertices, false, scale);
+	result
+}
+
+/// Write a polygon as a polygon with a single ring, or without rings if it has no vertices.
+///
+/// The ring winds the same way as the polygon. Polygons that wind clockwise are negative in this
+/// library, which Well-Known Binary can't express, so they are read back as counter-clockwise
+/// polygons.
+///
+/// # Arguments
+/// * `polygon` - The polygon to write.
+/// * `scale` - How many coordinates make up one unit in the data.
+///
+/// # Result
+/// The Well-Known Binary of the polygon.
+pub fn write_polygon(polygon: &Polygon, scale: f64) -> Vec<u8> {
+	let mut result = Vec::new();
+	write_header(&mut result, POLYGON);
+	let vertices: Vec<Point2D> = polygon.iter().copied().collect();
+	if vertices.is_empty() {
+		result.extend(0u32.to_le_bytes());
+	} else {
+		result.extend(1u32.to_le_bytes());
+		write_points(&mut result, &vertices, true, scale);
+	}
+	result
+}
+
+/// Write a multi-polygon as a multi-polygon.
+///
+/// Each contour that is not a hole becomes the exterior ring of a polygon. Each hole becomes an
+/// interior ring of the smallest polygon that surrounds it. The rings wind the same way as the
+/// contours, so the exterior rings are counter-clockwise and the interior rings clockwise. Holes
+/// that are not surrounded by any other contour become polygons of their own, which are read back
+/// as counter-clockwise contours.
+///
+/// Reading the data back with the same scale gives the same contours, but grouped by polygon: each
+/// outer contour followed by its holes.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to write.
+/// * `scale` - How many coordinates make up one unit in the data.
+///
+/// # Result
+/// The Well-Known Binary of the multi-polygon.
+pub fn write_multi_polygon(multi_polygon: &MultiPolygon, scale: f64) -> Vec<u8> {
+	let mut result = Vec::new();
+	write_header(&mut result, MULTI_POLYGON);
+	let polygons = io::group_contours(multi_polygon);
+	result.extend((polygons.len() as u32).to_le_bytes());
+	for rings in polygons {
+		write_header(&mut result, POLYGON);
+		result.extend((rings.len() as u32).to_le_bytes());
+		for ring in rings {
+			write_points(&mut result, ring, true, scale);
+		}
+	}
+	result
+}
+
+/// Read a counted list of points.
+///
+/// # Arguments
+/// * `bytes` - The data to read from.
+/// * `header` - The header of the geometry that the points belong to.
+/// * `scale` - How many coordinates make up one unit in the data.
+///
+/// # Result
+/// The points, or the reason why they can't be read.
+fn read_points(bytes: &mut Bytes, header: &Header, scale: f64) -> Result<Vec<Point2D>, WkbError> {
+	let num_points = bytes.count(header.little_endian, header.ordinates * 8)?;
+	let mut result = Vec::with_capacity(num_points);
+	for _ in 0..num_points {
+		let x = bytes.f64(header.little_endian)?;
+		let y = bytes.f64(header.little_endian)?;
+		bytes.take((header.ordinates - 2) * 8)?;
+		result.push(Point2D { x: affine_transform::round(x * scale)?, y: affine_transform::round(y * scale)? });
+	}
+	Ok(result)
+}
+
+/// Read the counted list of rings of a polygon.
+///
+/// # Arguments
+/// * `bytes` - The data to read from.
+/// * `header` - The header of the polygon.
+/// * `scale` - How many coordinates make up one unit in the data.
+///
+/// # Result
+/// The rings, with their exterior ring first, or the reason why they can't be read.
+fn read_rings(bytes: &mut Bytes, header: &Header, scale: f64) -> Result<Vec<Vec<Point2D>>, WkbError> {
+	let num_rings = bytes.count(header.little_endian, 4)?;
+	(0..num_rings).map(|_| read_points(bytes, header, scale)).collect()
+}
+
+/// Write the byte order and type code of a two-dimensional geometry.
+///
+/// # Arguments
+/// * `bytes` - The data to append the header to.
+/// * `geometry_type` - The type code of the geometry.
+fn write_header(bytes: &mut Vec<u8>, geometry_type: u32) {
+	bytes.push(1); //Little-endian.
+	bytes.extend(geometry_type.to_le_bytes());
+}
+
+/// Write a counted list of points.
+///
+/// # Arguments
+/// * `bytes` - The data to append the points to.
+/// * `points` - The points to write.
+/// * `close` - Whether to repeat the first point at the end, to close a ring.
+/// * `scale` - How many coordinates make up one unit in the data.
+fn write_points(bytes: &mut Vec<u8>, points: &[Point2D], close: bool, scale: f64) {
+	let closing_point = points.first().filter(|_| close);
+	bytes.extend(((points.len() + closing_point.iter().len()) as u32).to_le_bytes());
+	for point in points.iter().chain(closing_point) {
+		bytes.extend((point.x as f64 / scale).to_le_bytes());
+		bytes.extend((point.y as f64 / scale).to_le_bytes());
+	}
+}
+
+/// The header of a geometry, which describes how the rest of the geometry is encoded.
+struct Header {
+	/// Whether the numbers of the geometry are little-endian, rather than big-endian.
+	little_endian: bool,
+
+	/// The type of geometry, without the flags for Z and M ordinates.
+	geometry_type: u32,
+
+	/// How many numbers each point consists of.
+	ordinates: usize,
+
+	/// The type code as it was in the data, with the flags.
+	code: u32,
+}
+
+/// Reads numbers from Well-Known Binary data.
+struct Bytes<'a> {
+	/// The data to read.
+	bytes: &'a [u8],
+
+	/// The position of the next byte to read.
+	position: usize,
+}
+
+impl<'a> Bytes<'a> {
+	/// Consume a number of bytes, which must all be there.
+	///
+	/// # Arguments
+	/// * `length` - How many bytes to consume.
+	fn take(&mut self, length: usize) -> Result<&'a [u8], WkbError> {
+		if self.bytes.len() - self.position < length {
+			return Err(WkbError::MALFORMED(self.position));
+		}
+		self.position += length;
+		Ok(&self.bytes[self.position - length..self.position])
+	}
+
+	/// Consume a 32-bit unsigned integer.
+	///
+	/// # Arguments
+	/// * `little_endian` - Whether the integer is little-endian, rather than big-endian.
+	fn u32(&mut self, little_endian: bool) -> Result<u32, WkbError> {
+		let bytes = self.take(4)?.try_into().expect("Exactly 4 bytes were taken.");
+		Ok(if little_endian { u32::from_le_bytes(bytes) } else { u32::from_be_bytes(bytes) })
+	}
+
+	/// Consume a 64-bit floating point number.
+	///
+	/// # Arguments
+	/// * `little_endian` - Whether the number is little-endian, rather than big-endian.
+	fn f64(&mut self, little_endian: bool) -> Result<f64, WkbError> {
+		let bytes = self.take(8)?.try_into().expect("Exactly 8 bytes were taken.");
+		Ok(if little_endian { f64::from_le_bytes(bytes) } else { f64::from_be_bytes(bytes) })
+	}
+
+	/// Consume the number of elements of a list.
+	///
+	/// The count is checked against the remaining length of the data, so that corrupt counts can't
+	/// cause huge allocations.
+	///
+	/// # Arguments
+	/// * `little_endian` - Whether the count is little-endian, rather than big-endian.
+	/// * `element_size` - The least number of bytes that each element takes.
+	fn count(&mut self, little_endian: bool, element_size: usize) -> Result<usize, WkbError> {
+		let position = self.position;
+		let count = self.u32(little_endian)? as usize;
+		if count.saturating_mul(element_size) > self.bytes.len() - self.position {
+			return Err(WkbError::MALFORMED(position));
+		}
+		Ok(count)
+	}
+
+	/// Consume the byte order and type code at the start of a geometry, and the spatial reference
+	/// ID if there is one.
+	fn header(&mut self) -> Result<Header, WkbError> {
+		let position = self.position;
+		let little_endian = match self.take(1)?[0] {
+			0 => false,
+			1 => true,
+			_ => return Err(WkbError::MALFORMED(position)),
+		};
+		let code = self.u32(little_endian)?;
+		let iso_code = code & !(EXTENDED_Z | EXTENDED_M | EXTENDED_SRID);
+		let dimensions = iso_code / 1000; //0 for XY, 1 for XYZ, 2 for XYM and 3 for XYZM.
+		if dimensions > 3 {
+			return Err(WkbError::UNSUPPORTED(code));
+		}
+		let has_z = code & EXTENDED_Z != 0 || dimensions == 1 || dimensions == 3;
+		let has_m = code & EXTENDED_M != 0 || dimensions == 2 || dimensions == 3;
+		if code & EXTENDED_SRID != 0 {
+			self.u32(little_endian)?;
+		}
+		Ok(Header { little_endian, geometry_type: iso_code % 1000, ordinates: 2 + has_z as usize + has_m as usize, code })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+
+	/// Convert hexadecimal text, as databases usually show Well-Known Binary, to bytes.
+	fn hex(text: &str) -> Vec<u8> {
+		(0..text.len()).step_by(2).map(|index| u8::from_str_radix(&text[index..index + 2], 16).unwrap()).collect()
+	}
+
+	/// Get the contours of a multi-polygon, to compare them.
+	fn contours(multi_polygon: &MultiPolygon) -> Vec<Vec<Point2D>> {
+		multi_polygon.contours().map(|contour| contour.to_vec()).collect()
+	}
+
+	/// Test reading the line string from 1,2 to 3,4 in the different encodings.
+	#[test_case("010200000002000000000000000000F03F000000000000004000000000000008400000000000001040" ; "little endian")]
+	#[test_case("0000000002000000023FF0000000000000400000000000000040080000000000004010000000000000" ; "big endian")]
+	#[test_case("01EA03000002000000000000000000F03F0000000000000040000000000000F0BF00000000000008400000000000001040000000000000F0BF" ; "iso z")]
+	#[test_case("01D207000002000000000000000000F03F0000000000000040000000000000F0BF00000000000008400000000000001040000000000000F0BF" ; "iso m")]
+	#[test_case("01BA0B000002000000000000000000F03F0000000000000040000000000000F0BF000000000000F0BF00000000000008400000000000001040000000000000F0BF000000000000F0BF" ; "iso zm")]
+	#[test_case("010200008002000000000000000000F03F0000000000000040000000000000F0BF00000000000008400000000000001040000000000000F0BF" ; "extended z")]
+	#[test_case("0102000020E610000002000000000000000000F03F000000000000004000000000000008400000000000001040" ; "extended srid")]
+	fn encodings(wkb: &str) {
+		match read(&hex(wkb), 1.0).unwrap() {
+			Geometry::LINE_STRING(line) => assert_eq!(line.iter().copied().collect::<Vec<Point2D>>(), vec![Point2D { x: 1, y: 2 }, Point2D { x: 3, y: 4 }]),
+			other => panic!("This is a line string, not {:?}.", other),
+		}
+	}
+
+	/// Test that rings are oriented like the contours of a multi-polygon, keeping their first vertex,
+	/// and that their closing vertex is removed.
+	#[test]
+	fn orientation() {
+		let mut wkb = hex("01030000000100000005000000");
+		for coordinate in [0.0f64, 0.0, 0.0, 10.0, 10.0, 10.0, 10.0, 0.0, 0.0, 0.0] { //Clockwise.
+			wkb.extend(coordinate.to_le_bytes());
+		}
+		let multi_polygon = read_multi_polygon(&wkb, 1.0).unwrap();
+		assert_eq!(contours(&multi_polygon), vec![vec![Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 10, y: 10 }, Point2D { x: 0, y: 10 }]]);
+	}
+
+	/// Test that written data is read back as the same geometry.
+	#[test_case(1.0 ; "unit scale")]
+	#[test_case(1000.0 ; "fine scale")]
+	#[test_case(0.5 ; "coarse scale")]
+	fn round_trip(scale: f64) {
+		let pentagram = crate::test::data::polygon::pentagram_1000();
+		match read(&write_polygon(&pentagram, scale), scale).unwrap() {
+			Geometry::POLYGON(polygon) => assert_eq!(contours(&polygon), vec![pentagram.iter().copied().collect::<Vec<Point2D>>()]),
+			other => panic!("A polygon was written, not {:?}.", other),
+		}
+		match read(&write_line_string(&pentagram, scale), scale).unwrap() {
+			Geometry::LINE_STRING(line) => assert_eq!(line, pentagram),
+			other => panic!("A line string was written, not {:?}.", other),
+		}
+		let square_with_hole = crate::test::data::multi_polygon::square_with_hole_1000();
+		let wkb = write_multi_polygon(&square_with_hole, scale);
+		assert_eq!(wkb.len(), 5 + 4 + 5 + 4 + 2 * (4 + 5 * 16), "One polygon with two rings of 5 points each.");
+		assert_eq!(contours(&read_multi_polygon(&wkb, scale).unwrap()), contours(&square_with_hole));
+	}
+
+	/// Test writing and reading geometry without vertices.
+	#[test]
+	fn empty() {
+		assert_eq!(write_line_string(&Polygon::new(), 1.0), hex("010200000000000000"));
+		assert_eq!(write_polygon(&Polygon::new(), 1.0), hex("010300000000000000"));
+		assert_eq!(write_multi_polygon(&MultiPolygon::new(), 1.0), hex("010600000000000000"));
+		assert_eq!(read_multi_polygon(&hex("010600000000000000"), 1.0).unwrap().num_contours(), 0);
+	}
+
+	/// Test the errors for data that can't be read.
+	#[test_case("", WkbError::MALFORMED(0) ; "no data")]
+	#[test_case("0203000000", WkbError::MALFORMED(0) ; "invalid byte order")]
+	#[test_case("010100000000000000000000000000000000000000", WkbError::UNSUPPORTED(1) ; "point")]
+	#[test_case("01A40F0000", WkbError::UNSUPPORTED(4004) ; "unknown dimensions")]
+	#[test_case("0103000000010000000400000000000000", WkbError::MALFORMED(9) ; "truncated points")]
+	#[test_case("01030000000100", WkbError::MALFORMED(5) ; "truncated count")]
+	#[test_case("0103000000FFFFFFFF", WkbError::MALFORMED(5) ; "huge count")]
+	#[test_case("01060000000100000001020000000000000000", WkbError::UNSUPPORTED(2) ; "line string in multi-polygon")]
+	#[test_case("01030000000000000000", WkbError::MALFORMED(9) ; "trailing data")]
+	fn errors(wkb: &str, expected: WkbError) {
+		assert_eq!(read(&hex(wkb), 1.0).map(|_| ()), Err(expected));
+	}
+
+	/// Test that coordinates that don't fit after scaling are reported.
+	#[test]
+	fn overflow() {
+		let mut wkb = hex("010200000001000000");
+		wkb.extend(1e30f64.to_le_bytes());
+		wkb.extend(0.0f64.to_le_bytes());
+		assert_eq!(read(&wkb, 1.0).map(|_| ()), Err(WkbError::OVERFLOW));
+		wkb[9..17].copy_from_slice(&f64::NAN.to_le_bytes());
+		assert_eq!(read(&wkb, 1.0).map(|_| ()), Err(WkbError::OVERFLOW), "NaN coordinates, as used for empty points, can't be read either.");
+	}
+
+	/// Test that the line string reader rejects other types when a multi-polygon is expected.
+	#[test]
+	fn not_a_polygon() {
+		assert_eq!(read_multi_polygon(&write_line_string(&Polygon::new(), 1.0), 1.0).map(|_| ()), Err(WkbError::UNSUPPORTED(LINE_STRING)));
+	}
+}
\ No newline at end of file
@@ -0,0 +1,573 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! Reads and writes geometry as Well-Known Text.
+//!
+//! The reader understands `LINESTRING`, `POLYGON` and `MULTIPOLYGON`, including their `EMPTY` forms.
+//! Keywords are case-insensitive. Z and M ordinates are accepted, but ignored, since the geometry of
+//! this library is two-dimensional. An `SRID=...;` prefix, as in Extended Well-Known Text, is
+//! skipped as well.
+//!
+//! Coordinates in the text are multiplied by a scale and then rounded to the nearest `Coordinate`,
+//! with halves rounded up. The writer divides them by the same scale again.
+
+use std::error::Error; //The errors of the reader can be propagated with other errors.
+use std::fmt; //To describe the errors as text.
+use std::fmt::Write; //To write the text of the geometry.
+
+use crate::MultiPolygon; //To read and write polygons with holes.
+use crate::OverflowError; //Coordinates may not fit after scaling.
+use crate::Point2D; //The vertices that are read and written.
+use crate::Polygon; //To read and write line strings, and polygons without holes.
+use crate::affine_transform; //To round the coordinates the same way as transformations do.
+use crate::io; //For the geometry types and to convert between rings and contours.
+use crate::io::Geometry; //The geometry that is read.
+
+/// The reasons that Well-Known Text can't be read.
+#[allow(non_camel_case_types)] //The variants are capitalised like those of the other enums, with underscores between words.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WktError {
+	/// The text doesn't follow the grammar of Well-Known Text. This holds the byte position in the
+	/// text where the problem was found.
+	MALFORMED(usize),
+
+	/// The text holds a type of geometry that can't be read, such as a `POINT`. This holds the name
+	/// of the type.
+	UNSUPPORTED(String),
+
+	/// A coordinate is outside of the range of `Coordinate` after scaling.
+	OVERFLOW,
+}
+
+impl fmt::Display for WktError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			WktError::MALFORMED(position) => write!(f, "The Well-Known Text is malformed at position {}.", position),
+			WktError::UNSUPPORTED(geometry_type) => write!(f, "The geometry type {} is not supported.", geometry_type),
+			WktError::OVERFLOW => write!(f, "A coordinate in the Well-Known Text went out of the range of the coordinate type."),
+		}
+	}
+}
+
+impl Error for WktError {}
+
+impl From<OverflowError> for WktError {
+	fn from(_: OverflowError) -> WktError {
+		WktError::OVERFLOW
+	}
+}
+
+/// Read a geometry from Well-Known Text.
+///
+/// The exterior rings of polygons are made to wind counter-clockwise and their interior rings
+/// clockwise, so that the rings of a polygon become the outer contour and holes of a multi-polygon.
+/// The last vertex of each ring, which repeats the first, is removed.
+///
+/// # Arguments
+/// * `wkt` - The Well-Known Text of a single geometry.
+/// * `scale` - How many coordinates make up one unit in the text.
+///
+/// # Result
+/// The geometry, or the reason why the text can't be read.
+///
+/// # Examples
+/// ```
+/// use apex::Shape2D;
+/// use apex::io::Geometry;
+/// use apex::io::wkt::read;
+/// let geometry = read("POLYGON ((0 0, 0 10, 10 10, 10 0, 0 0), (2 2, 8 2, 8 8, 2 8, 2 2))", 100.0).unwrap();
+/// match geometry {
+/// 	Geometry::POLYGON(square_with_hole) => {
+/// 		assert_eq!(square_with_hole.num_contours(), 2);
+/// 		assert_eq!(square_with_hole.area(), 1000 * 1000 - 600 * 600); //The exterior ring was clockwise, but is made counter-clockwise.
+/// 	}
+/// 	_ => panic!("This is a polygon."),
+/// }
+/// ```
+pub fn read(wkt: &str, scale: f64) -> Result<Geometry, WktError> {
+	let mut tokens = Tokens::new(wkt);
+	if tokens.peek_word().eq_ignore_ascii_case("SRID") {
+		tokens.word();
+		tokens.expect('=')?;
+		tokens.number()?;
+		tokens.expect(';')?;
+	}
+	let geometry_type = tokens.word().to_ascii_uppercase();
+	let ordinates = match tokens.peek_word().to_ascii_uppercase().as_str() {
+		"Z" | "M" => 3,
+		"ZM" => 4,
+		_ => 2,
+	};
+	if ordinates > 2 {
+		tokens.word();
+	}
+	let geometry = match geometry_type.as_str() {
+		"LINESTRING" => Geometry::LINE_STRING(Polygon::from_iter(read_points(&mut tokens, ordinates, scale)?)),
+		"POLYGON" => {
+			let mut multi_polygon = MultiPolygon::new();
+			io::push_rings(&mut multi_polygon, read_rings(&mut tokens, ordinates, scale)?);
+			Geometry::POLYGON(multi_polygon)
+		}
+		"MULTIPOLYGON" => {
+			let mut multi_polygon = MultiPolygon::new();
+			if !tokens.empty() {
+				tokens.expect('(')?;
+				loop {
+					io::push_rings(&mut multi_polygon, read_rings(&mut tokens, ordinates, scale)?);
+					if !tokens.next_in_list()? {
+						break;
+					}
+				}
+			}
+			Geometry::MULTI_POLYGON(multi_polygon)
+		}
+		"" => return Err(WktError::MALFORMED(tokens.position)),
+		_ => return Err(WktError::UNSUPPORTED(geometry_type)),
+	};
+	tokens.skip_whitespace();
+	if tokens.position < wkt.len() {
+		return Err(WktError::MALFORMED(tokens.position));
+	}
+	Ok(geometry)
+}
+
+/// Read a polygon or multi-polygon from Well-Known Text, as a multi-polygon.
+///
+/// This reads the geometry like `read`, but only accepts polygons and multi-polygons.
+///
+/// # Arguments
+/// * `wkt` - The Well-Known Text of a `POLYGON` or `MULTIPOLYGON`.
+/// * `scale` - How many coordinates make up one unit in the text.
+///
+/// # Result
+/// The contours of all polygons in the text, or the reason why the text can't be read.
+///
+/// # Examples
+/// ```
+/// use apex::io::wkt::read_multi_polygon;
+/// let multi_polygon = read_multi_polygon("MULTIPOLYGON (((0 0, 1 0, 0 1, 0 0)), ((5 5, 6 5, 5 6, 5 5)))", 1.0).unwrap();
+/// assert_eq!(multi_polygon.num_contours(), 2);
+/// ```
+pub fn read_multi_polygon(wkt: &str, scale: f64) -> Result<MultiPolygon, WktError> {
+	match read(wkt, scale)? {
+		Geometry::POLYGON(multi_polygon) | Geometry::MULTI_POLYGON(multi_polygon) => Ok(multi_polygon),
+		Geometry::LINE_STRING(_) => Err(WktError::UNSUPPORTED("LINESTRING".to_string())),
+	}
+}
+
+/// Write the vertices of a polygon as a `LINESTRING`, an open chain of line segments.
+///
+/// # Arguments
+/// * `polygon` - The polygon with the vertices of the line string.
+/// * `scale` - How many coordinates make up one unit in the text.
+///
+/// # Result
+/// The Well-Known Text of the line string.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// use apex::io::wkt::write_line_string;
+/// let line = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 150, y: -50 }]);
+/// assert_eq!(write_line_string(&line, 100.0), "LINESTRING (0 0, 1.5 -0.5)");
+/// ```
+pub fn write_line_string(polygon: &Polygon, scale: f64) -> String {
+	let mut result = "LINESTRING".to_string();
+	let vertices: Vec<Point2D> = polygon.iter().copied().collect();
+	if vertices.is_empty() {
+		result.push_str(" EMPTY");
+	} else {
+		result.push(' ');
+		write_points(&mut result, &vertices, false, scale);
+	}
+	result
+}
+
+/// Write a polygon as a `POLYGON` with a single ring.
+///
+/// The ring winds the same way as the polygon. Polygons that wind clockwise are negative in this
+/// library, which Well-Known Text can't express, so they are read back as counter-clockwise
+/// polygons.
+///
+/// # Arguments
+/// * `polygon` - The polygon to write.
+/// * `scale` - How many coordinates make up one unit in the text.
+///
+/// # Result
+/// The Well-Known Text of the polygon.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// use apex::io::wkt::write_polygon;
+/// let triangle = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 0, y: 10 }]);
+/// assert_eq!(write_polygon(&triangle, 1.0), "POLYGON ((0 0, 10 0, 0 10, 0 0))");
+/// ```
+pub fn write_polygon(polygon: &Polygon, scale: f64) -> String {
+	let mut result = "POLYGON".to_string();
+	let vertices: Vec<Point2D> = polygon.iter().copied().collect();
+	if vertices.is_empty() {
+		result.push_str(" EMPTY");
+	} else {
+		result.push_str(" (");
+		write_points(&mut result, &vertices, true, scale);
+		result.push(')');
+	}
+	result
+}
+
+/// Write a multi-polygon as a `MULTIPOLYGON`.
+///
+/// Each contour that is not a hole becomes the exterior ring of a polygon. Each hole becomes an
+/// interior ring of the smallest polygon that surrounds it. The rings wind the same way as the
+/// contours, so the exterior rings are counter-clockwise and the interior rings clockwise. Holes
+/// that are not surrounded by any other contour become polygons of their own, which are read back
+/// as counter-clockwise contours.
+///
+/// Reading the text back with the same scale gives the same contours, but grouped by polygon: each
+/// outer contour followed by its holes.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to write.
+/// * `scale` - How many coordinates make up one unit in the text.
+///
+/// # Result
+/// The Well-Known Text of the multi-polygon.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, Point2D};
+/// use apex::io::wkt::write_multi_polygon;
+/// let mut multi_polygon = MultiPolygon::new();
+/// multi_polygon.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 0, y: 10 }]);
+/// multi_polygon.push_contour([Point2D { x: 1, y: 1 }, Point2D { x: 1, y: 2 }, Point2D { x: 2, y: 1 }]);
+/// assert_eq!(write_multi_polygon(&multi_polygon, 1.0), "MULTIPOLYGON (((0 0, 10 0, 0 10, 0 0), (1 1, 1 2, 2 1, 1 1)))");
+/// ```
+pub fn write_multi_polygon(multi_polygon: &MultiPolygon, scale: f64) -> String {
+	let mut result = "MULTIPOLYGON".to_string();
+	let polygons = io::group_contours(multi_polygon);
+	if polygons.is_empty() {
+		result.push_str(" EMPTY");
+		return result;
+	}
+	result.push_str(" (");
+	for (index, rings) in polygons.iter().enumerate() {
+		if index > 0 {
+			result.push_str(", ");
+		}
+		result.push('(');
+		for (ring_index, ring) in rings.iter().enumerate() {
+			if ring_index > 0 {
+				result.push_str(", ");
+			}
+			write_points(&mut result, ring, true, scale);
+		}
+		result.push(')');
+	}
+	result.push(')');
+	result
+}
+
+/// Read a parenthesised list of points, or `EMPTY`.
+///
+/// # Arguments
+/// * `tokens` - The text to read from.
+/// * `ordinates` - How many numbers each point consists of. Only the first two are used.
+/// * `scale` - How many coordinates make up one unit in the text.
+///
+/// # Result
+/// The points, or the reason why they can't be read.
+fn read_points(tokens: &mut Tokens, ordinates: usize, scale: f64) -> Result<Vec<Point2D>, WktError> {
+	let mut result = Vec::new();
+	if tokens.empty() {
+		return Ok(result);
+	}
+	tokens.expect('(')?;
+	loop {
+		let x = tokens.number()?;
+		let y = tokens.number()?;
+		for _ in 2..ordinates {
+			tokens.number()?;
+		}
+		result.push(Point2D { x: affine_transform::round(x * scale)?, y: affine_transform::round(y * scale)? });
+		if !tokens.next_in_list()? {
+			return Ok(result);
+		}
+	}
+}
+
+/// Read the parenthesised list of rings of a polygon, or `EMPTY`.
+///
+/// # Arguments
+/// * `tokens` - The text to read from.
+/// * `ordinates` - How many numbers each point consists of. Only the first two are used.
+/// * `scale` - How many coordinates make up one unit in the text.
+///
+/// # Result
+/// The rings, with their exterior ring first, or the reason why they can't be read.
+fn read_rings(tokens: &mut Tokens, ordinates: usize, scale: f64) -> Result<Vec<Vec<Point2D>>, WktError> {
+	let mut result = Vec::new();
+	if tokens.empty() {
+		return Ok(result);
+	}
+	tokens.expect('(')?;
+	loop {
+		result.push(read_points(tokens, ordinates, scale)?);
+		if !tokens.next_in_list()? {
+			return Ok(result);
+		}
+	}
+}
+
+/// Write a parenthesised list of points.
+///
+/// # Arguments
+/// * `text` - The text to append the points to.
+/// * `points` - The points to write.
+/// * `close` - Whether to repeat the first point at the end, to close a ring.
+/// * `scale` - How many coordinates make up one unit in the text.
+fn write_points(text: &mut String, points: &[Point2D], close: bool, scale: f64) {
+	text.push('(');
+	for (index, point) in points.iter().chain(points.first().filter(|_| close)).enumerate() {
+		if index > 0 {
+			text.push_str(", ");
+		}
+		write!(text, "{} {}", point.x as f64 / scale, point.y as f64 / scale).expect("Writing to a string can't fail.");
+	}
+	text.push(')');
+}
+
+/// Splits Well-Known Text into words, numbers and punctuation.
+struct Tokens<'a> {
+	/// The text to read.
+	text: &'a str,
+
+	/// The byte position of the next character to read.
+	position: usize,
+}
+
+impl<'a> Tokens<'a> {
+	/// Start reading a text from the beginning.
+	///
+	/// # Arguments
+	/// * `text` - The text to read.
+	fn new(text: &'a str) -> Tokens<'a> {
+		Tokens { text, position: 0 }
+	}
+
+	/// Skip any whitespace before the next token.
+	fn skip_whitespace(&mut self) {
+		let remainder = &self.text[self.position..];
+		self.position += remainder.len() - remainder.trim_start().len();
+	}
+
+	/// Get the next word, made of letters, without consuming it.
+	///
+	/// If the next token is not a word, this is empty.
+	fn peek_word(&mut self) -> &'a str {
+		self.skip_whitespace();
+		let remainder = &self.text[self.position..];
+		let length = remainder.find(|character: char| !character.is_ascii_alphabetic()).unwrap_or(remainder.len());
+		&remainder[..length]
+	}
+
+	/// Consume the next word, made of letters.
+	///
+	/// If the next token is not a word, this is empty and nothing is consumed.
+	fn word(&mut self) -> &'a str {
+		let word = self.peek_word();
+		self.position += word.len();
+		word
+	}
+
+	/// Consume the word `EMPTY` if it is next.
+	///
+	/// # Result
+	/// Whether the word was there.
+	fn empty(&mut self) -> bool {
+		if self.peek_word().eq_ignore_ascii_case("EMPTY") {
+			self.word();
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Consume a punctuation character, which must be next.
+	///
+	/// # Arguments
+	/// * `character` - The expected character.
+	fn expect(&mut self, character: char) -> Result<(), WktError> {
+		self.skip_whitespace();
+		if self.text[self.position..].starts_with(character) {
+			self.position += character.len_utf8();
+			Ok(())
+		} else {
+			Err(WktError::MALFORMED(self.position))
+		}
+	}
+
+	/// Consume the comma or closing parenthesis after an element of a list.
+	///
+	/// # Result
+	/// Whether another element follows, which is the case after a comma.
+	fn next_in_list(&mut self) -> Result<bool, WktError> {
+		if self.expect(',').is_ok() {
+			return Ok(true);
+		}
+		self.expect(')')?;
+		Ok(false)
+	}
+
+	/// Consume a number, which must be next.
+	fn number(&mut self) -> Result<f64, WktError> {
+		self.skip_whitespace();
+		let remainder = &self.text[self.position..];
+		let length = remainder.find(|character: char| !(character.is_ascii_digit() || "+-.eE".contains(character))).unwrap_or(remainder.len());
+		match remainder[..length].parse::<f64>() {
+			Ok(number) => {
+				self.position += length;
+				Ok(number)
+			}
+			Err(_) => Err(WktError::MALFORMED(self.position)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+
+	/// Get the contours of a multi-polygon, to compare them.
+	fn contours(multi_polygon: &MultiPolygon) -> Vec<Vec<Point2D>> {
+		multi_polygon.contours().map(|contour| contour.to_vec()).collect()
+	}
+
+	/// Test reading each type of geometry.
+	#[test]
+	fn geometry_types() {
+		match read("LINESTRING (1 2, 3 4, 5 6)", 1.0).unwrap() {
+			Geometry::LINE_STRING(line) => assert_eq!(line.iter().copied().collect::<Vec<Point2D>>(), vec![Point2D { x: 1, y: 2 }, Point2D { x: 3, y: 4 }, Point2D { x: 5, y: 6 }]),
+			other => panic!("This is a line string, not {:?}.", other),
+		}
+		match read("POLYGON ((0 0, 4 0, 0 4, 0 0))", 1.0).unwrap() {
+			Geometry::POLYGON(polygon) => assert_eq!(contours(&polygon), vec![vec![Point2D { x: 0, y: 0 }, Point2D { x: 4, y: 0 }, Point2D { x: 0, y: 4 }]], "The closing vertex is removed."),
+			other => panic!("This is a polygon, not {:?}.", other),
+		}
+		match read("MULTIPOLYGON (((0 0, 4 0, 0 4, 0 0)), ((10 0, 14 0, 10 4, 10 0), (11 1, 11 2, 12 1, 11 1)))", 1.0).unwrap() {
+			Geometry::MULTI_POLYGON(multi_polygon) => {
+				assert_eq!(multi_polygon.num_contours(), 3);
+				assert!(multi_polygon.is_hole(2));
+			}
+			other => panic!("This is a multi-polygon, not {:?}.", other),
+		}
+	}
+
+	/// Test that rings are oriented like the contours of a multi-polygon, keeping their first vertex.
+	#[test]
+	fn orientation() {
+		let multi_polygon = read_multi_polygon("POLYGON ((0 0, 0 10, 10 10, 10 0, 0 0), (2 2, 8 2, 8 8, 2 8, 2 2))", 1.0).unwrap();
+		assert_eq!(contours(&multi_polygon), vec![
+			vec![Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 10, y: 10 }, Point2D { x: 0, y: 10 }],
+			vec![Point2D { x: 2, y: 2 }, Point2D { x: 2, y: 8 }, Point2D { x: 8, y: 8 }, Point2D { x: 8, y: 2 }],
+		]);
+	}
+
+	/// Test variations in the text that don't change the geometry.
+	#[test_case("polygon((0 0,4 0,0 4,0 0))" ; "compact lower case")]
+	#[test_case("  POLYGON (\n\t(0 0, 4 0, 0 4, 0 0)\n)  " ; "extra whitespace")]
+	#[test_case("POLYGON Z ((0 0 1, 4 0 2, 0 4 3, 0 0 1))" ; "z")]
+	#[test_case("POLYGON M ((0 0 1, 4 0 2, 0 4 3, 0 0 1))" ; "m")]
+	#[test_case("POLYGON ZM ((0 0 1 5, 4 0 2 5, 0 4 3 5, 0 0 1 5))" ; "zm")]
+	#[test_case("SRID=4326;POLYGON ((0 0, 4 0, 0 4, 0 0))" ; "srid")]
+	#[test_case("POLYGON ((0 0, 4 0, 0 4))" ; "unclosed")]
+	#[test_case("POLYGON ((0.0 0e0, 4.0 -0, +0 0.4E1))" ; "number formats")]
+	fn variations(wkt: &str) {
+		let expected = vec![vec![Point2D { x: 0, y: 0 }, Point2D { x: 4, y: 0 }, Point2D { x: 0, y: 4 }]];
+		assert_eq!(contours(&read_multi_polygon(wkt, 1.0).unwrap()), expected);
+	}
+
+	/// Test that coordinates are scaled and rounded with halves rounded up.
+	#[test]
+	fn scale() {
+		let multi_polygon = read_multi_polygon("POLYGON ((0.0014 -0.0015, 0.0025 0, 0 0.0025))", 1000.0).unwrap();
+		assert_eq!(contours(&multi_polygon), vec![vec![Point2D { x: 1, y: -1 }, Point2D { x: 3, y: 0 }, Point2D { x: 0, y: 3 }]]);
+	}
+
+	/// Test reading the empty forms of the geometry types.
+	#[test]
+	fn empty() {
+		assert!(matches!(read("LINESTRING EMPTY", 1.0), Ok(Geometry::LINE_STRING(line)) if line.len() == 0));
+		assert_eq!(read_multi_polygon("POLYGON EMPTY", 1.0).unwrap().num_contours(), 0);
+		assert_eq!(read_multi_polygon("MULTIPOLYGON EMPTY", 1.0).unwrap().num_contours(), 0);
+		assert_eq!(read_multi_polygon("MULTIPOLYGON (EMPTY, ((0 0, 1 0, 0 1, 0 0)))", 1.0).unwrap().num_contours(), 1);
+	}
+
+	/// Test that written text is read back as the same geometry.
+	#[test_case(1.0 ; "unit scale")]
+	#[test_case(1000.0 ; "fine scale")]
+	#[test_case(0.5 ; "coarse scale")]
+	fn round_trip(scale: f64) {
+		let pentagram = crate::test::data::polygon::pentagram_1000();
+		match read(&write_polygon(&pentagram, scale), scale).unwrap() {
+			Geometry::POLYGON(polygon) => assert_eq!(contours(&polygon), vec![pentagram.iter().copied().collect::<Vec<Point2D>>()]),
+			other => panic!("A polygon was written, not {:?}.", other),
+		}
+		match read(&write_line_string(&pentagram, scale), scale).unwrap() {
+			Geometry::LINE_STRING(line) => assert_eq!(line, pentagram),
+			other => panic!("A line string was written, not {:?}.", other),
+		}
+		let square_with_hole = crate::test::data::multi_polygon::square_with_hole_1000();
+		let read_back = read_multi_polygon(&write_multi_polygon(&square_with_hole, scale), scale).unwrap();
+		assert_eq!(contours(&read_back), contours(&square_with_hole));
+	}
+
+	/// Test that holes are written in the smallest polygon that surrounds them.
+	#[test]
+	fn grouping() {
+		let mut multi_polygon = MultiPolygon::new();
+		multi_polygon.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+		multi_polygon.push_contour([Point2D { x: 45, y: 45 }, Point2D { x: 45, y: 55 }, Point2D { x: 55, y: 55 }, Point2D { x: 55, y: 45 }]); //Hole in the inner island.
+		multi_polygon.push_contour([Point2D { x: 10, y: 10 }, Point2D { x: 10, y: 90 }, Point2D { x: 90, y: 90 }, Point2D { x: 90, y: 10 }]); //Hole in the outer square.
+		multi_polygon.push_contour([Point2D { x: 200, y: 0 }, Point2D { x: 200, y: 10 }, Point2D { x: 210, y: 0 }]); //Hole without surrounding contour.
+		multi_polygon.push_contour([Point2D { x: 40, y: 40 }, Point2D { x: 60, y: 40 }, Point2D { x: 60, y: 60 }, Point2D { x: 40, y: 60 }]); //Island in the hole of the outer square.
+		assert_eq!(write_multi_polygon(&multi_polygon, 1.0), "MULTIPOLYGON (\
+			((0 0, 100 0, 100 100, 0 100, 0 0), (10 10, 10 90, 90 90, 90 10, 10 10)), \
+			((40 40, 60 40, 60 60, 40 60, 40 40), (45 45, 45 55, 55 55, 55 45, 45 45)), \
+			((200 0, 200 10, 210 0, 200 0)))");
+	}
+
+	/// Test writing geometry without vertices.
+	#[test]
+	fn write_empty() {
+		assert_eq!(write_line_string(&Polygon::new(), 1.0), "LINESTRING EMPTY");
+		assert_eq!(write_polygon(&Polygon::new(), 1.0), "POLYGON EMPTY");
+		assert_eq!(write_multi_polygon(&MultiPolygon::new(), 1.0), "MULTIPOLYGON EMPTY");
+	}
+
+	/// Test the errors for text that can't be read.
+	#[test_case("", WktError::MALFORMED(0) ; "empty text")]
+	#[test_case("POINT (1 2)", WktError::UNSUPPORTED("POINT".to_string()) ; "unsupported type")]
+	#[test_case("POLYGON (0 0, 1 0, 0 1)", WktError::MALFORMED(9) ; "missing ring")]
+	#[test_case("POLYGON ((0 0, 1 0, 0 1)", WktError::MALFORMED(24) ; "unclosed list")]
+	#[test_case("POLYGON ((0 0, 1, 0 1))", WktError::MALFORMED(16) ; "missing ordinate")]
+	#[test_case("POLYGON ((0 0, 1 x, 0 1))", WktError::MALFORMED(17) ; "not a number")]
+	#[test_case("POLYGON ((0 0, 1 0, 0 1)) extra", WktError::MALFORMED(26) ; "trailing text")]
+	#[test_case("POLYGON ((0 0, 1e10 0, 0 1))", WktError::OVERFLOW ; "overflow")]
+	fn errors(wkt: &str, expected: WktError) {
+		let scale = if cfg!(feature = "coordinate64") { 1e10 } else { 1.0 }; //With 64-bit coordinates, it takes a larger scale to overflow.
+		assert_eq!(read(wkt, scale).map(|_| ()), Err(expected));
+	}
+
+	/// Test that the line string reader rejects other types when a multi-polygon is expected.
+	#[test]
+	fn not_a_polygon() {
+		assert_eq!(read_multi_polygon("LINESTRING (0 0, 1 1)", 1.0).map(|_| ()), Err(WktError::UNSUPPORTED("LINESTRING".to_string())));
+	}
+}
\ No newline at end of file
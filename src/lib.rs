@@ -10,23 +10,62 @@
 
 #[macro_use] extern crate auto_ops;
 
+pub mod affine_transform;
+pub use affine_transform::*;
+
+pub mod bounding_box;
+pub use bounding_box::*;
+
+pub mod bounding_volume_hierarchy;
+pub use bounding_volume_hierarchy::*;
+
+pub mod containment;
+pub use containment::*;
+
 pub mod convexity;
 pub use convexity::*;
 
 pub mod coordinate;
 pub use coordinate::*;
 
+pub mod cost_model;
+
+pub mod crossing;
+pub use crossing::*;
+
+pub mod fill_rule;
+pub use fill_rule::*;
+
+pub mod io;
+
+pub mod multi_polygon;
+pub use multi_polygon::*;
+
 pub mod operations;
 
+pub mod overflow_error;
+pub use overflow_error::*;
+
+pub mod overflow_policy;
+pub use overflow_policy::*;
+
 pub mod point2d;
 pub use point2d::*;
 
 pub mod polygon;
 pub use polygon::*;
 
+pub mod polygon_batch;
+pub use polygon_batch::*;
+
+pub mod predicates;
+
 pub mod shape2d;
 pub use shape2d::*;
 
+pub mod spatial_index;
+pub use spatial_index::*;
+
 pub mod two_dimensional;
 pub use two_dimensional::*;
 
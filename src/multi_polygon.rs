@@ -0,0 +1,567 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! Defines the MultiPolygon struct.
+
+use std::fmt; //You can print multi-polygons as text.
+use std::iter::FromIterator; //Constructing multi-polygons from iterable lists of polygons.
+#[cfg(feature = "gpu")]
+use std::sync::OnceLock; //To lazily copy the contour starts to the GPU.
+#[cfg(feature = "gpu")]
+use cubecl::server::Handle; //To refer to the copy of the vertices on the GPU.
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer}; //To serialise multi-polygons as sequences of contours.
+
+use crate::AffineTransform; //To transform the multi-polygon.
+use crate::Area; //To return the multi-polygon's surface area.
+use crate::BoundingBox; //To return the bounding box of the multi-polygon.
+use crate::Containment; //To return whether points are inside the multi-polygon.
+use crate::Convexity; //To return the multi-polygon's convexity.
+use crate::Coordinate; //To translate the multi-polygon.
+use crate::Crossing; //To return where the boundary of the multi-polygon intersects itself.
+use crate::FillRule; //To decide which regions of the multi-polygon are inside.
+use crate::OverflowError; //If transforming the multi-polygon would move it out of range.
+use crate::OverflowPolicy; //To choose what happens if translating the multi-polygon moves it out of range.
+use crate::Point2D; //The vertices of the contours are Point2D.
+use crate::Polygon; //Each contour is a polygon.
+use crate::Shape2D; //This is a 2D shape.
+use crate::TwoDimensional; //This is a two-dimensional object.
+use crate::detail::sync_status; //To track whether the GPU or CPU copies are up-to-date.
+use crate::detail::vertex_buffer::VertexBuffer; //To store the vertices on the host and the GPU.
+use crate::operations::area; //To calculate the area of the multi-polygons.
+use crate::operations::bounding_box; //To find the bounding box of the multi-polygons.
+use crate::operations::containment; //To find whether points are inside the multi-polygons.
+use crate::operations::convex_hull; //To find the convex hull of the multi-polygons.
+use crate::operations::convexity; //To determine the convexity of the multi-polygons.
+use crate::operations::self_intersection; //To find where the multi-polygons intersect themselves.
+use crate::operations::transform; //To transform the multi-polygons.
+use crate::operations::translate; //To translate the multi-polygons.
+use crate::operations::triangulate; //To divide the multi-polygons into triangles.
+
+/// A plane figure consisting of any number of closed contours of straight line segments.
+///
+/// Each contour is a closed polygonal chain, like a `Polygon`. Contours that wind
+/// counter-clockwise are positive. They are the outer contours of the shape. Contours that wind
+/// clockwise are negative. They are holes in the shape. For instance, a doughnut shape consists of
+/// a counter-clockwise outer contour with a clockwise hole inside of it.
+///
+/// The contours are expected to be disjoint, and holes are expected to be inside of outer
+/// contours. The area of the multi-polygon is the sum of the signed areas of its contours.
+///
+/// # Basic usage
+/// A multi-polygon can be constructed from polygons, each of which becomes a contour:
+/// ```
+/// use apex::{MultiPolygon, Point2D, Polygon, Shape2D};
+/// let outer = Polygon::from_iter([ //Counter-clockwise.
+/// 	Point2D { x: 0, y: 0 },
+/// 	Point2D { x: 1000, y: 0 },
+/// 	Point2D { x: 1000, y: 1000 },
+/// 	Point2D { x: 0, y: 1000 },
+/// ]);
+/// let hole = Polygon::from_iter([ //Clockwise.
+/// 	Point2D { x: 250, y: 250 },
+/// 	Point2D { x: 250, y: 750 },
+/// 	Point2D { x: 750, y: 750 },
+/// 	Point2D { x: 750, y: 250 },
+/// ]);
+/// let doughnut = MultiPolygon::from_iter([outer, hole]);
+/// assert_eq!(doughnut.area(), 1000 * 1000 - 500 * 500);
+/// ```
+///
+/// # Host vs. GPU
+/// The vertices of all contours are stored together, in a single buffer. Like with `Polygon`, Apex
+/// decides for itself whether to process them on the host or on the GPU. When they are copied to
+/// the GPU, all contours are copied at once, so that a whole layer of shapes can be processed with
+/// a single upload.
+pub struct MultiPolygon {
+	/// The vertices of all contours, one contour after another.
+	///
+	/// There is a copy of these vertices on the host, on the GPU, or both. These vertices are not
+	/// publicly accessible, since access to the most up-to-date version may require a sync between
+	/// the CPU and the GPU.
+	vertices: VertexBuffer,
+
+	/// The index of the first vertex of each contour in the vertex buffer.
+	///
+	/// This has one more element than there are contours. The last element is the total number of
+	/// vertices. So the vertices of contour `i` are in the range from `contour_starts[i]` to
+	/// `contour_starts[i + 1]`. The contours never change on the GPU, so this is only kept on the
+	/// host.
+	contour_starts: Vec<usize>,
+
+	/// A copy of the contour starts on the GPU, for the kernels that need to know where each
+	/// contour ends.
+	///
+	/// This is only copied to the GPU when a kernel needs it, and is released when the contours
+	/// change.
+	#[cfg(feature = "gpu")]
+	gpu_contour_starts: OnceLock<Handle>,
+}
+
+impl MultiPolygon {
+	/// Create a new, empty multi-polygon, without any contours.
+	///
+	/// The multi-polygon will be degenerate, since it has no contours.
+	pub fn new() -> Self {
+		MultiPolygon {
+			vertices: VertexBuffer::new(vec!()),
+			contour_starts: vec![0],
+			#[cfg(feature = "gpu")]
+			gpu_contour_starts: OnceLock::new(),
+		}
+	}
+
+	/// Get the number of contours in this multi-polygon, including holes.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{MultiPolygon, Point2D};
+	/// let mut multi = MultiPolygon::new();
+	/// assert_eq!(multi.num_contours(), 0);
+	/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+	/// assert_eq!(multi.num_contours(), 1);
+	/// ```
+	pub fn num_contours(&self) -> usize {
+		self.contour_starts.len() - 1
+	}
+
+	/// Get the total number of vertices in all contours of this multi-polygon.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{MultiPolygon, Point2D};
+	/// let mut multi = MultiPolygon::new();
+	/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+	/// multi.push_contour([Point2D { x: 200, y: 0 }, Point2D { x: 300, y: 0 }, Point2D { x: 250, y: 100 }]);
+	/// assert_eq!(multi.len(), 6);
+	/// ```
+	pub fn len(&self) -> usize {
+		self.vertices.len()
+	}
+
+	/// Get the vertices of one of the contours.
+	///
+	/// # Arguments
+	/// * `index` - The index of the contour, in the order that they were added.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{MultiPolygon, Point2D};
+	/// let mut multi = MultiPolygon::new();
+	/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+	/// assert_eq!(multi.contour(0)[2], Point2D { x: 50, y: 100 });
+	/// ```
+	pub fn contour(&self, index: usize) -> &[Point2D] {
+		&self.host_vertices()[self.contour_starts[index]..self.contour_starts[index + 1]]
+	}
+
+	/// Get the vertices of one of the contours, allowing their modification.
+	///
+	/// The vertices can be moved, but no vertices can be added or removed this way.
+	///
+	/// # Arguments
+	/// * `index` - The index of the contour, in the order that they were added.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{MultiPolygon, Point2D};
+	/// let mut multi = MultiPolygon::new();
+	/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+	/// multi.contour_mut(0)[2].y = 200;
+	/// assert_eq!(multi.contour(0)[2], Point2D { x: 50, y: 200 });
+	/// ```
+	pub fn contour_mut(&mut self, index: usize) -> &mut [Point2D] {
+		let (start, end) = (self.contour_starts[index], self.contour_starts[index + 1]);
+		&mut self.host_vertices_mut()[start..end]
+	}
+
+	/// Create an iterator over the contours of this multi-polygon.
+	///
+	/// The contours are given in the order that they were added, each as a slice of vertices.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{MultiPolygon, Point2D};
+	/// let mut multi = MultiPolygon::new();
+	/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+	/// multi.push_contour([Point2D { x: 200, y: 0 }, Point2D { x: 300, y: 0 }, Point2D { x: 300, y: 100 }, Point2D { x: 200, y: 100 }]);
+	/// let sizes: Vec<usize> = multi.contours().map(|contour| contour.len()).collect();
+	/// assert_eq!(sizes, vec![3, 4]);
+	/// ```
+	pub fn contours(&self) -> impl Iterator<Item = &[Point2D]> {
+		let vertices = self.host_vertices();
+		self.contour_starts.windows(2).map(move |range| &vertices[range[0]..range[1]])
+	}
+
+	/// Test whether one of the contours is a hole.
+	///
+	/// Holes are contours with a negative area, which wind clockwise.
+	///
+	/// # Arguments
+	/// * `index` - The index of the contour, in the order that they were added.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{MultiPolygon, Point2D};
+	/// let mut multi = MultiPolygon::new();
+	/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]); //Counter-clockwise.
+	/// multi.push_contour([Point2D { x: 40, y: 10 }, Point2D { x: 50, y: 50 }, Point2D { x: 60, y: 10 }]); //Clockwise.
+	/// assert!(!multi.is_hole(0));
+	/// assert!(multi.is_hole(1));
+	/// ```
+	pub fn is_hole(&self, index: usize) -> bool {
+		area::doubled_area_st(self.contour(index)) < 0
+	}
+
+	/// Add a contour to this multi-polygon.
+	///
+	/// The contour becomes an outer contour if it winds counter-clockwise, or a hole if it winds
+	/// clockwise.
+	///
+	/// # Arguments
+	/// * `contour` - The vertices of the new contour.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{MultiPolygon, Point2D, Shape2D};
+	/// let mut multi = MultiPolygon::new();
+	/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+	/// assert_eq!(multi.area(), 5000);
+	/// ```
+	pub fn push_contour<I>(&mut self, contour: I)
+			where I: IntoIterator<Item = Point2D> {
+		let vertices = self.host_vertices_mut();
+		vertices.extend(contour);
+		let end = vertices.len();
+		self.contour_starts.push(end);
+		self.contours_changed();
+	}
+
+	/// Remove the last contour from this multi-polygon and return it as a polygon.
+	///
+	/// If there are no contours, return `None`.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{MultiPolygon, Point2D};
+	/// let mut multi = MultiPolygon::new();
+	/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+	/// let removed = multi.pop_contour().unwrap();
+	/// assert_eq!(removed.len(), 3);
+	/// assert_eq!(multi.num_contours(), 0);
+	/// assert!(multi.pop_contour().is_none());
+	/// ```
+	pub fn pop_contour(&mut self) -> Option<Polygon> {
+		if self.num_contours() == 0 {
+			return None;
+		}
+		self.contour_starts.pop();
+		let start = *self.contour_starts.last().expect("The start of the first contour is always there.");
+		let removed = self.host_vertices_mut().split_off(start);
+		self.contours_changed();
+		Some(Polygon::from_iter(removed))
+	}
+
+	/// Removes all contours from this multi-polygon, leaving it empty.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{MultiPolygon, Point2D};
+	/// let mut multi = MultiPolygon::new();
+	/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+	/// multi.clear();
+	/// assert_eq!(multi.num_contours(), 0);
+	/// assert_eq!(multi.len(), 0);
+	/// ```
+	pub fn clear(&mut self) {
+		self.host_vertices_mut().clear();
+		self.contour_starts.truncate(1);
+		self.contours_changed();
+	}
+
+	/// Get the index of the first vertex of each contour in the vertex buffer, followed by the
+	/// total number of vertices.
+	pub(crate) fn contour_starts(&self) -> &[usize] {
+		&self.contour_starts
+	}
+
+	/// Get a copy of the contour starts on the GPU, as 32-bit unsigned integers.
+	///
+	/// If they are not on the GPU yet, they are copied there first.
+	#[cfg(feature = "gpu")]
+	pub(crate) fn gpu_contour_starts(&self) -> &Handle {
+		use cubecl::prelude::CubeElement;
+		self.gpu_contour_starts.get_or_init(|| {
+			let starts: Vec<u32> = self.contour_starts.iter().map(|start| *start as u32).collect();
+			crate::detail::gpu::client().create(u32::as_bytes(&starts))
+		})
+	}
+
+	/// Get which copy of the vertices is currently the most up-to-date.
+	///
+	/// Operations use this to decide whether to compute on the host or on the GPU, since it
+	/// indicates whether the vertices would need to be copied first.
+	pub(crate) fn sync_status(&self) -> sync_status::SyncStatus {
+		self.vertices.sync_status()
+	}
+
+	/// Obtain the vertices of all contours on the host.
+	///
+	/// If the latest version of the vertices is in the GPU rather than the host, it will be copied
+	/// to the host's RAM. If the latest version of the vertices is on the CPU (or they are in
+	/// sync), it will simply give a reference to those.
+	pub(crate) fn host_vertices(&self) -> &Vec<Point2D> {
+		self.vertices.host()
+	}
+
+	/// Obtain the vertices of all contours on the host, allowing their modification.
+	///
+	/// If the latest version of the vertices is in the GPU rather than the host, it will be copied
+	/// to the host's RAM first. Since the vertices may be modified, the host becomes the leading
+	/// copy.
+	pub(crate) fn host_vertices_mut(&mut self) -> &mut Vec<Point2D> {
+		self.vertices.host_mut()
+	}
+
+	/// Obtain the vertices of all contours on the GPU.
+	///
+	/// If the latest version of the vertices is in the host rather than the GPU, it will be copied
+	/// to the GPU first. If the latest version of the vertices is in the GPU (or they are in sync),
+	/// it will simply give a reference to those.
+	///
+	/// On the GPU, the vertices are stored as a flat array of coordinates, with the X and Y
+	/// coordinates of each vertex interleaved.
+	#[cfg(feature = "gpu")]
+	pub(crate) fn gpu_vertices(&self) -> &Handle {
+		self.vertices.gpu()
+	}
+
+	/// Obtain the vertices of all contours on the GPU, allowing their modification.
+	///
+	/// If the latest version of the vertices is in the host rather than the GPU, it will be copied
+	/// to the GPU first. Since the vertices may be modified, the GPU becomes the leading copy.
+	#[cfg(feature = "gpu")]
+	pub(crate) fn gpu_vertices_mut(&mut self) -> &mut Handle {
+		self.vertices.gpu_mut()
+	}
+
+	/// Release the copy of the contour starts on the GPU, after the contours changed.
+	fn contours_changed(&mut self) {
+		#[cfg(feature = "gpu")]
+		self.gpu_contour_starts.take();
+	}
+}
+
+impl TwoDimensional for MultiPolygon {
+	fn translate_with_policy(&mut self, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+		translate::translate_multipolygon(self, dx, dy, policy)
+	}
+
+	fn transform(&mut self, transform: &AffineTransform) -> Result<(), OverflowError> {
+		transform::transform_multipolygon(self, transform)
+	}
+}
+
+impl Shape2D for MultiPolygon {
+	fn area(&self) -> Area {
+		area::area_multipolygon(self)
+	}
+
+	fn bounding_box(&self) -> BoundingBox {
+		bounding_box::bounding_box_multipolygon(self)
+	}
+
+	fn containment(&self, point: &Point2D, fill_rule: FillRule) -> Containment {
+		containment::containment_multipolygon(self, point, fill_rule)
+	}
+
+	fn convex_hull(&self) -> Polygon {
+		convex_hull::convex_hull_multipolygon(self)
+	}
+
+	fn convexity(&self) -> Convexity {
+		convexity::convexity_multipolygon(self)
+	}
+
+	fn crossings(&self) -> Vec<Crossing> {
+		self_intersection::crossings_multipolygon(self)
+	}
+
+	fn triangulate(&self) -> Vec<u32> {
+		triangulate::triangulate_multipolygon(self)
+	}
+}
+
+impl FromIterator<Polygon> for MultiPolygon {
+	/// Construct a new multi-polygon from a collection of polygons.
+	///
+	/// Each polygon becomes a contour of the multi-polygon. Counter-clockwise polygons become outer
+	/// contours, clockwise polygons become holes.
+	///
+	/// # Arguments
+	/// * `iter` - An object that can be converted into an iterator, yielding the polygons that
+	/// become the contours of the new multi-polygon.
+	fn from_iter<T>(iter: T) -> Self
+			where T: IntoIterator<Item = Polygon> {
+		let mut vertices = vec!();
+		let mut contour_starts = vec![0];
+		for polygon in iter {
+			vertices.extend_from_slice(polygon.host_vertices());
+			contour_starts.push(vertices.len());
+		}
+		MultiPolygon {
+			vertices: VertexBuffer::new(vertices),
+			contour_starts,
+			#[cfg(feature = "gpu")]
+			gpu_contour_starts: OnceLock::new(),
+		}
+	}
+}
+
+impl fmt::Debug for MultiPolygon {
+	/// A reference string representing this multi-polygon, for debugging the multi-polygon in a log
+	/// or CLI output.
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("MultiPolygon")
+			.field("contours", &self.contours().collect::<Vec<&[Point2D]>>())
+			.finish()
+	}
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for MultiPolygon {
+	/// Serialise the contours of this multi-polygon, as a sequence of sequences of points.
+	///
+	/// If the latest version of the vertices is on the GPU, they are copied to the host first.
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_seq(self.contours())
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for MultiPolygon {
+	/// Deserialise a multi-polygon from a sequence of contours, each a sequence of points.
+	///
+	/// The vertices of the new multi-polygon are on the host.
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<MultiPolygon, D::Error> {
+		let mut result = MultiPolygon::new();
+		for contour in Vec::<Vec<Point2D>>::deserialize(deserializer)? {
+			result.push_contour(contour);
+		}
+		Ok(result)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test::data::multi_polygon;
+	use crate::test::data::polygon;
+
+	/// Test creating a new, empty multi-polygon.
+	#[test]
+	fn new() {
+		let multi = MultiPolygon::new();
+		assert_eq!(multi.num_contours(), 0, "The new multi-polygon has no contours.");
+		assert_eq!(multi.len(), 0, "The new multi-polygon has no vertices.");
+		assert_eq!(multi.sync_status(), sync_status::SyncStatus::HOST, "New multi-polygons only exist on the host.");
+	}
+
+	/// Test creating a multi-polygon from polygons.
+	#[test]
+	fn from_iter() {
+		let multi = MultiPolygon::from_iter([polygon::square_1000(), polygon::triangle_1000()]);
+		assert_eq!(multi.num_contours(), 2, "Each polygon becomes a contour.");
+		assert_eq!(multi.len(), 7, "The square has 4 vertices, the triangle 3.");
+		assert_eq!(multi.contour(0), polygon::square_1000().host_vertices().as_slice(), "The first contour is the square.");
+		assert_eq!(multi.contour(1), polygon::triangle_1000().host_vertices().as_slice(), "The second contour is the triangle.");
+	}
+
+	/// Test adding and removing contours.
+	#[test]
+	fn push_pop_contour() {
+		let mut multi = MultiPolygon::new();
+		multi.push_contour(polygon::square_1000().iter().copied());
+		multi.push_contour([]); //An empty contour is allowed.
+		multi.push_contour(polygon::triangle_1000().iter().copied());
+		assert_eq!(multi.num_contours(), 3);
+		assert_eq!(multi.contour(1).len(), 0, "The second contour is empty.");
+
+		let triangle = multi.pop_contour().expect("There were 3 contours.");
+		assert_eq!(*triangle.host_vertices(), *polygon::triangle_1000().host_vertices(), "The last contour was the triangle.");
+		assert_eq!(multi.pop_contour().expect("There were 2 contours.").len(), 0, "The empty contour comes next.");
+		assert_eq!(multi.len(), 4, "Only the square is left.");
+	}
+
+	/// Test iterating over the contours.
+	#[test]
+	fn contours() {
+		let multi = multi_polygon::square_with_hole_1000();
+		let contours: Vec<&[Point2D]> = multi.contours().collect();
+		assert_eq!(contours.len(), 2, "There is an outer contour and a hole.");
+		assert_eq!(contours[0].len(), 4);
+		assert_eq!(contours[1].len(), 4);
+	}
+
+	/// Test distinguishing holes from outer contours.
+	#[test]
+	fn is_hole() {
+		let multi = multi_polygon::square_with_hole_1000();
+		assert!(!multi.is_hole(0), "The outer square is counter-clockwise.");
+		assert!(multi.is_hole(1), "The hole is clockwise.");
+	}
+
+	/// Test modifying the vertices of a contour.
+	#[test]
+	fn contour_mut() {
+		let mut multi = multi_polygon::two_squares_1000();
+		multi.contour_mut(1)[0] = Point2D { x: -10, y: -10 };
+		assert_eq!(multi.contour(1)[0], Point2D { x: -10, y: -10 }, "The vertex was modified.");
+		assert_eq!(multi.contour(0), polygon::square_1000().host_vertices().as_slice(), "The other contour was not modified.");
+	}
+
+	/// Test removing all contours.
+	#[test]
+	fn clear() {
+		let mut multi = multi_polygon::square_with_hole_1000();
+		multi.clear();
+		assert_eq!(multi.num_contours(), 0, "After clearing, there are no contours.");
+		assert_eq!(multi.len(), 0, "After clearing, there are no vertices.");
+	}
+
+	/// Test that multi-polygons can be shared between threads.
+	#[test]
+	fn send_sync() {
+		fn assert_send_sync<T: Send + Sync>() {}
+		assert_send_sync::<MultiPolygon>();
+	}
+
+	/// Test copying all contours to the GPU at once, and back.
+	#[cfg(feature = "gpu")]
+	#[test]
+	fn sync_gpu() {
+		let mut multi = multi_polygon::two_squares_1000();
+		multi.gpu_vertices_mut();
+		assert_eq!(multi.sync_status(), sync_status::SyncStatus::GPU, "The GPU copy may have been modified, so it is leading.");
+		assert_eq!(multi.len(), 8, "Both contours are on the GPU.");
+		assert_eq!(multi.contour(1), multi_polygon::two_squares_1000().contour(1), "The contours must be copied back unchanged.");
+		assert_eq!(multi.sync_status(), sync_status::SyncStatus::SYNCED, "After copying back to the host, both copies are up-to-date.");
+	}
+
+	/// Test serialising a multi-polygon and deserialising it again.
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serde_round_trip() {
+		let json = serde_json::to_string(&multi_polygon::square_with_hole_1000()).unwrap();
+		assert!(json.starts_with(r#"[[{"x":0,"y":0},{"x":1000,"y":0},"#), "The multi-polygon is serialised as a sequence of contours.");
+		let multi: MultiPolygon = serde_json::from_str(&json).unwrap();
+		assert_eq!(multi.contours().collect::<Vec<&[Point2D]>>(), multi_polygon::square_with_hole_1000().contours().collect::<Vec<&[Point2D]>>());
+		assert_eq!(multi.sync_status(), sync_status::SyncStatus::HOST, "Deserialised multi-polygons only exist on the host.");
+		assert_eq!(serde_json::from_str::<MultiPolygon>("[]").unwrap().num_contours(), 0);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,770 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! This module contains the implementations of operations to calculate the surface area of
+//! geometric objects.
+//!
+//! The area of a polygon is calculated with the shoelace formula. Each edge of the polygon
+//! contributes the cross product of its two endpoints, which is twice the signed area of the
+//! triangle between the edge and the coordinate origin. The sum of these is twice the signed area
+//! of the polygon. Counter-clockwise polygons get a positive area this way, clockwise polygons get a
+//! negative area.
+//!
+//! The cross products of two vertices don't fit in an `Area`, and neither do the intermediate sums
+//! of them, even if the final area does. So these are summed in 128-bit integers. At the end, the
+//! doubled area is halved, rounding towards zero. This way, reversing a polygon exactly negates its
+//! area. If the area doesn't fit in an `Area`, it is clamped to the range of `Area`.
+
+use cubecl::prelude::*; //For the GPU implementation.
+use rayon::prelude::*; //For multi-threaded implementations.
+
+use crate::Area; //To return the area of the polygon.
+use crate::Coordinate; //The GPU kernel reads coordinates.
+use crate::MultiPolygon; //Calculate the area of multi-polygons.
+use crate::Point2D; //To calculate the cross product of vertices.
+use crate::Polygon; //Calculate the area of polygons.
+use crate::PolygonBatch; //Calculate the areas of batches of polygons at once.
+use crate::cost_model::{self, Backend, Operation}; //To choose the fastest implementation.
+
+/// Calculate the signed surface area of a polygon.
+///
+/// This chooses automatically whether to calculate the area on a single thread, on multiple threads
+/// or on the GPU. The cost model estimates which is fastest, from the number of vertices in the
+/// polygon and from where the most up-to-date copy of the vertices is.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// //Create a right triangle, counter-clockwise.
+/// let poly = Polygon::from_iter([
+/// 	Point2D { x: 0, y: 0 },
+/// 	Point2D { x: 100, y: 0 },
+/// 	Point2D { x: 0, y: 100 }
+/// ]);
+/// assert_eq!(apex::operations::area::area_polygon(&poly), 5000);
+/// ```
+pub fn area_polygon(polygon: &Polygon) -> Area {
+	match cost_model::choose(Operation::AREA, polygon.len(), polygon.sync_status()) {
+		Backend::ST => area_polygon_st(polygon),
+		Backend::MT => area_polygon_mt(polygon),
+		#[cfg(feature = "gpu")]
+		Backend::GPU => area_polygon_gpu(polygon),
+		#[cfg(not(feature = "gpu"))]
+		Backend::GPU => unreachable!("Without a GPU runtime, the cost model never chooses the GPU."),
+	}
+}
+
+/// Calculate the signed surface area of a polygon.
+///
+/// This implementation is single-threaded and simply sums the contribution of every edge one by
+/// one.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// //Create a right triangle, counter-clockwise.
+/// let poly = Polygon::from_iter([
+/// 	Point2D { x: 0, y: 0 },
+/// 	Point2D { x: 100, y: 0 },
+/// 	Point2D { x: 0, y: 100 }
+/// ]);
+/// assert_eq!(apex::operations::area::area_polygon_st(&poly), 5000);
+/// ```
+pub fn area_polygon_st(polygon: &Polygon) -> Area {
+	halve(doubled_area_st(polygon.host_vertices()))
+}
+
+/// Calculate the signed surface area of a polygon.
+///
+/// This implementation is multi-threaded. Each thread sums the contribution of a range of edges,
+/// after which the partial sums are combined.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// //Create a right triangle, counter-clockwise.
+/// let poly = Polygon::from_iter([
+/// 	Point2D { x: 0, y: 0 },
+/// 	Point2D { x: 100, y: 0 },
+/// 	Point2D { x: 0, y: 100 }
+/// ]);
+/// assert_eq!(apex::operations::area::area_polygon_mt(&poly), 5000);
+/// ```
+pub fn area_polygon_mt(polygon: &Polygon) -> Area {
+	halve(doubled_area_mt(polygon.host_vertices()))
+}
+
+/// Calculate the signed surface area of a polygon.
+///
+/// This implementation computes the cross products of the vertices on the GPU. If the vertices are
+/// not on the GPU yet, they are copied there first, and kept there. The GPU doesn't have 128-bit
+/// integers, so the two products of each cross product are returned separately, and they are
+/// summed on the host.
+///
+/// If the GPU doesn't support 64-bit integers, the area is calculated on the host instead.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// //Create a right triangle, counter-clockwise.
+/// let poly = Polygon::from_iter([
+/// 	Point2D { x: 0, y: 0 },
+/// 	Point2D { x: 100, y: 0 },
+/// 	Point2D { x: 0, y: 100 }
+/// ]);
+/// assert_eq!(apex::operations::area::area_polygon_gpu(&poly), 5000);
+/// ```
+#[cfg(feature = "gpu")]
+pub fn area_polygon_gpu(polygon: &Polygon) -> Area {
+	use crate::detail::gpu;
+
+	if !gpu::supports_coordinates() {
+		return area_polygon(polygon);
+	}
+	let coordinates_handle = polygon.gpu_vertices();
+	let num_vertices = coordinates_handle.size() as usize / (2 * size_of::<Coordinate>());
+	if num_vertices == 0 {
+		return 0; //Can't launch a kernel without any work. An empty polygon has no area.
+	}
+	let client = gpu::client();
+	let products_handle = client.empty(num_vertices * 2 * size_of::<i64>());
+	let (cube_count, cube_dim) = gpu::launch_dimensions(num_vertices);
+	unsafe {
+		area_polygon_kernel::launch::<gpu::GpuRuntime>(
+			&client,
+			cube_count,
+			cube_dim,
+			ArrayArg::from_raw_parts::<Coordinate>(coordinates_handle, num_vertices * 2, 1),
+			ArrayArg::from_raw_parts::<i64>(&products_handle, num_vertices * 2, 1),
+		);
+	}
+	let bytes = client.read_one(products_handle);
+	let products = i64::from_bytes(&bytes);
+	let doubled_area: i128 = products.chunks(2).map(|pair| pair[0] as i128 - pair[1] as i128).sum();
+	halve(doubled_area)
+}
+
+/// Calculate the signed surface area of a multi-polygon.
+///
+/// This is the sum of the signed areas of its contours, so holes subtract from the area. This
+/// chooses automatically whether to calculate the area on a single thread, on multiple threads or
+/// on the GPU, in the same way as `area_polygon`.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, Point2D};
+/// let mut multi = MultiPolygon::new();
+/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// multi.push_contour([Point2D { x: 25, y: 25 }, Point2D { x: 25, y: 75 }, Point2D { x: 75, y: 75 }, Point2D { x: 75, y: 25 }]); //A hole.
+/// assert_eq!(apex::operations::area::area_multipolygon(&multi), 7500);
+/// ```
+pub fn area_multipolygon(multi_polygon: &MultiPolygon) -> Area {
+	match cost_model::choose(Operation::AREA, multi_polygon.len(), multi_polygon.sync_status()) {
+		Backend::ST => area_multipolygon_st(multi_polygon),
+		Backend::MT => area_multipolygon_mt(multi_polygon),
+		#[cfg(feature = "gpu")]
+		Backend::GPU => area_multipolygon_gpu(multi_polygon),
+		#[cfg(not(feature = "gpu"))]
+		Backend::GPU => unreachable!("Without a GPU runtime, the cost model never chooses the GPU."),
+	}
+}
+
+/// Calculate the signed surface area of a multi-polygon.
+///
+/// This implementation is single-threaded. It sums the contributions of the edges of all contours
+/// one by one.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, Point2D};
+/// let mut multi = MultiPolygon::new();
+/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// multi.push_contour([Point2D { x: 25, y: 25 }, Point2D { x: 25, y: 75 }, Point2D { x: 75, y: 75 }, Point2D { x: 75, y: 25 }]); //A hole.
+/// assert_eq!(apex::operations::area::area_multipolygon_st(&multi), 7500);
+/// ```
+pub fn area_multipolygon_st(multi_polygon: &MultiPolygon) -> Area {
+	halve(multi_polygon.contours().map(doubled_area_st).sum())
+}
+
+/// Calculate the signed surface area of a multi-polygon.
+///
+/// This implementation is multi-threaded. The edges of all contours are divided over the threads
+/// together, regardless of which contour they belong to. This way, the work is balanced even if
+/// some contours are much bigger than others.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, Point2D};
+/// let mut multi = MultiPolygon::new();
+/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// multi.push_contour([Point2D { x: 25, y: 25 }, Point2D { x: 25, y: 75 }, Point2D { x: 75, y: 75 }, Point2D { x: 75, y: 25 }]); //A hole.
+/// assert_eq!(apex::operations::area::area_multipolygon_mt(&multi), 7500);
+/// ```
+pub fn area_multipolygon_mt(multi_polygon: &MultiPolygon) -> Area {
+	halve(doubled_area_contours_mt(multi_polygon.host_vertices(), multi_polygon.contour_starts()))
+}
+
+/// Calculate the signed surface area of a multi-polygon.
+///
+/// This implementation computes the cross products of the vertices of all contours on the GPU, in
+/// a single kernel launch. If the vertices are not on the GPU yet, they are all copied there at
+/// once, and kept there. The products are summed on the host, like in `area_polygon_gpu`.
+///
+/// If the GPU doesn't support 64-bit integers, the area is calculated on the host instead.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, Point2D};
+/// let mut multi = MultiPolygon::new();
+/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// multi.push_contour([Point2D { x: 25, y: 25 }, Point2D { x: 25, y: 75 }, Point2D { x: 75, y: 75 }, Point2D { x: 75, y: 25 }]); //A hole.
+/// assert_eq!(apex::operations::area::area_multipolygon_gpu(&multi), 7500);
+/// ```
+#[cfg(feature = "gpu")]
+pub fn area_multipolygon_gpu(multi_polygon: &MultiPolygon) -> Area {
+	use crate::detail::gpu;
+
+	if !gpu::supports_coordinates() {
+		return area_multipolygon(multi_polygon);
+	}
+	let coordinates_handle = multi_polygon.gpu_vertices();
+	let num_vertices = coordinates_handle.size() as usize / (2 * size_of::<Coordinate>());
+	if num_vertices == 0 {
+		return 0; //Can't launch a kernel without any work. Without vertices, there is no area.
+	}
+	let num_contours = multi_polygon.num_contours();
+	let contour_starts_handle = multi_polygon.gpu_contour_starts();
+	let client = gpu::client();
+	let products_handle = client.empty(num_vertices * 2 * size_of::<i64>());
+	let (cube_count, cube_dim) = gpu::launch_dimensions(num_vertices);
+	unsafe {
+		area_contours_kernel::launch::<gpu::GpuRuntime>(
+			&client,
+			cube_count,
+			cube_dim,
+			ArrayArg::from_raw_parts::<Coordinate>(coordinates_handle, num_vertices * 2, 1),
+			ArrayArg::from_raw_parts::<u32>(contour_starts_handle, num_contours + 1, 1),
+			ArrayArg::from_raw_parts::<i64>(&products_handle, num_vertices * 2, 1),
+		);
+	}
+	let bytes = client.read_one(products_handle);
+	let products = i64::from_bytes(&bytes);
+	let doubled_area: i128 = products.chunks(2).map(|pair| pair[0] as i128 - pair[1] as i128).sum();
+	halve(doubled_area)
+}
+
+/// Calculate the signed surface area of every polygon in a batch.
+///
+/// This chooses automatically whether to calculate the areas on a single thread, on multiple
+/// threads or on the GPU, in the same way as `area_polygon`. The cost model considers the total
+/// number of vertices in the batch, since all polygons are processed together.
+///
+/// # Result
+/// The area of each polygon, in the order that they were added to the batch.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// assert_eq!(apex::operations::area::area_batch(&batch), vec![10000, 5000]);
+/// ```
+pub fn area_batch(batch: &PolygonBatch) -> Vec<Area> {
+	match cost_model::choose(Operation::AREA, batch.len(), batch.sync_status()) {
+		Backend::ST => area_batch_st(batch),
+		Backend::MT => area_batch_mt(batch),
+		#[cfg(feature = "gpu")]
+		Backend::GPU => area_batch_gpu(batch),
+		#[cfg(not(feature = "gpu"))]
+		Backend::GPU => unreachable!("Without a GPU runtime, the cost model never chooses the GPU."),
+	}
+}
+
+/// Calculate the signed surface area of every polygon in a batch.
+///
+/// This implementation is single-threaded. It calculates the areas of the polygons one by one.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// assert_eq!(apex::operations::area::area_batch_st(&batch), vec![10000, 5000]);
+/// ```
+pub fn area_batch_st(batch: &PolygonBatch) -> Vec<Area> {
+	batch.polygons().map(|polygon| halve(doubled_area_st(polygon))).collect()
+}
+
+/// Calculate the signed surface area of every polygon in a batch.
+///
+/// This implementation is multi-threaded, in a single pass over the vertices of all polygons. The
+/// vertices are divided over the threads regardless of which polygon they belong to, so the work is
+/// balanced even if the batch has many small polygons or a few big ones. Each thread produces the
+/// partial areas of the polygons that its vertices are in, which are then added together.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// assert_eq!(apex::operations::area::area_batch_mt(&batch), vec![10000, 5000]);
+/// ```
+pub fn area_batch_mt(batch: &PolygonBatch) -> Vec<Area> {
+	doubled_areas_contours_mt(batch.host_vertices(), batch.offsets()).into_iter().map(halve).collect()
+}
+
+/// Calculate the signed surface area of every polygon in a batch.
+///
+/// This implementation computes the cross products of the vertices of all polygons on the GPU, in
+/// a single kernel launch. If the vertices are not on the GPU yet, the whole batch is copied there
+/// at once, and kept there. The products are then summed per polygon on the host.
+///
+/// If the GPU doesn't support 64-bit integers, the area is calculated on the host instead.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// assert_eq!(apex::operations::area::area_batch_gpu(&batch), vec![10000, 5000]);
+/// ```
+#[cfg(feature = "gpu")]
+pub fn area_batch_gpu(batch: &PolygonBatch) -> Vec<Area> {
+	use crate::detail::gpu;
+
+	if !gpu::supports_coordinates() {
+		return area_batch(batch);
+	}
+	let coordinates_handle = batch.gpu_vertices();
+	let num_vertices = coordinates_handle.size() as usize / (2 * size_of::<Coordinate>());
+	if num_vertices == 0 {
+		return vec![0; batch.num_polygons()]; //Can't launch a kernel without any work. Without vertices, none of the polygons have area.
+	}
+	let offsets_handle = batch.gpu_offsets();
+	let client = gpu::client();
+	let products_handle = client.empty(num_vertices * 2 * size_of::<i64>());
+	let (cube_count, cube_dim) = gpu::launch_dimensions(num_vertices);
+	unsafe {
+		area_contours_kernel::launch::<gpu::GpuRuntime>(
+			&client,
+			cube_count,
+			cube_dim,
+			ArrayArg::from_raw_parts::<Coordinate>(coordinates_handle, num_vertices * 2, 1),
+			ArrayArg::from_raw_parts::<u32>(offsets_handle, batch.num_polygons() + 1, 1),
+			ArrayArg::from_raw_parts::<i64>(&products_handle, num_vertices * 2, 1),
+		);
+	}
+	let bytes = client.read_one(products_handle);
+	let products = i64::from_bytes(&bytes);
+	batch.offsets().windows(2).map(|range| {
+		let doubled_area: i128 = products[range[0] * 2..range[1] * 2].chunks(2).map(|pair| pair[0] as i128 - pair[1] as i128).sum();
+		halve(doubled_area)
+	}).collect()
+}
+
+/// GPU kernel to calculate the two products of the cross product of every edge of a polygon.
+///
+/// Every work unit processes one edge, from the vertex at its position to the next vertex.
+///
+/// # Arguments
+/// * `coordinates` - The vertices of the polygon, with the X and Y coordinates interleaved.
+/// * `products` - The output, two products for every edge. The first is the X coordinate of the
+/// start of the edge times the Y coordinate of the end. The second is the X coordinate of the end
+/// times the Y coordinate of the start.
+#[cube(launch)]
+fn area_polygon_kernel(coordinates: &Array<Coordinate>, products: &mut Array<i64>) {
+	let num_vertices = coordinates.len() / 2;
+	if ABSOLUTE_POS < num_vertices {
+		let next = (ABSOLUTE_POS + 1) % num_vertices;
+		let x = i64::cast_from(coordinates[ABSOLUTE_POS * 2]);
+		let y = i64::cast_from(coordinates[ABSOLUTE_POS * 2 + 1]);
+		let next_x = i64::cast_from(coordinates[next * 2]);
+		let next_y = i64::cast_from(coordinates[next * 2 + 1]);
+		products[ABSOLUTE_POS * 2] = x * next_y;
+		products[ABSOLUTE_POS * 2 + 1] = next_x * y;
+	}
+}
+
+/// GPU kernel to calculate the two products of the cross product of every edge of multiple
+/// contours.
+///
+/// Every work unit processes one edge, from the vertex at its position to the next vertex in the
+/// same contour. It finds the contour that its vertex belongs to with a binary search through the
+/// contour starts.
+///
+/// # Arguments
+/// * `coordinates` - The vertices of all contours, with the X and Y coordinates interleaved.
+/// * `contour_starts` - The index of the first vertex of each contour, followed by the total number
+/// of vertices.
+/// * `products` - The output, two products for every edge, like in `area_polygon_kernel`.
+#[cube(launch)]
+fn area_contours_kernel(coordinates: &Array<Coordinate>, contour_starts: &Array<u32>, products: &mut Array<i64>) {
+	let num_vertices = coordinates.len() / 2;
+	if ABSOLUTE_POS < num_vertices {
+		let mut low = 0;
+		let mut high = contour_starts.len() - 1;
+		while high - low > 1 { //Find the last contour that starts at or before this vertex.
+			let middle = (low + high) / 2;
+			if contour_starts[middle] <= ABSOLUTE_POS {
+				low = middle;
+			} else {
+				high = middle;
+			}
+		}
+		while contour_starts[low + 1] <= ABSOLUTE_POS { //Skip empty contours that start at the same vertex.
+			low += 1;
+		}
+		let mut next = ABSOLUTE_POS + 1;
+		if next == contour_starts[low + 1] {
+			next = contour_starts[low];
+		}
+		let x = i64::cast_from(coordinates[ABSOLUTE_POS * 2]);
+		let y = i64::cast_from(coordinates[ABSOLUTE_POS * 2 + 1]);
+		let next_x = i64::cast_from(coordinates[next * 2]);
+		let next_y = i64::cast_from(coordinates[next * 2 + 1]);
+		products[ABSOLUTE_POS * 2] = x * next_y;
+		products[ABSOLUTE_POS * 2 + 1] = next_x * y;
+	}
+}
+
+/// Calculate twice the signed area of a closed contour of vertices, single-threaded.
+///
+/// Doubling the area keeps the result exact, since the area itself may end in a half.
+pub(crate) fn doubled_area_st(vertices: &[Point2D]) -> i128 {
+	let mut doubled_area: i128 = 0;
+	for i in 0..vertices.len() {
+		doubled_area += edge_contribution(&vertices[i], &vertices[(i + 1) % vertices.len()]);
+	}
+	doubled_area
+}
+
+/// Calculate twice the signed area of a closed contour of vertices, multi-threaded.
+///
+/// Doubling the area keeps the result exact, since the area itself may end in a half.
+pub(crate) fn doubled_area_mt(vertices: &[Point2D]) -> i128 {
+	let chunk_size = cost_model::chunk_size(Operation::AREA, vertices.len());
+	(0..vertices.len()).into_par_iter().with_min_len(chunk_size).map(
+		|i| edge_contribution(&vertices[i], &vertices[(i + 1) % vertices.len()])
+	).sum()
+}
+
+/// Calculate twice the total signed area of multiple closed contours, multi-threaded.
+///
+/// The vertices are divided in chunks over the threads. Each chunk finds the contour that its first
+/// vertex is in, and then tracks which contour it is in while iterating over its vertices.
+///
+/// # Arguments
+/// * `vertices` - The vertices of all contours, one contour after another.
+/// * `contour_starts` - The index of the first vertex of each contour, followed by the total number
+/// of vertices.
+pub(crate) fn doubled_area_contours_mt(vertices: &[Point2D], contour_starts: &[usize]) -> i128 {
+	let chunk_size = cost_model::chunk_size(Operation::AREA, vertices.len()).max(1);
+	vertices.par_chunks(chunk_size).enumerate().map(|(chunk_index, chunk)| {
+		let first = chunk_index * chunk_size;
+		let mut contour = contour_starts.partition_point(|start| *start <= first) - 1;
+		let mut doubled_area: i128 = 0;
+		for i in first..(first + chunk.len()) {
+			while contour_starts[contour + 1] <= i { //Skip to the contour that this vertex is in, which may be after some empty contours.
+				contour += 1;
+			}
+			let next = if i + 1 == contour_starts[contour + 1] { contour_starts[contour] } else { i + 1 };
+			doubled_area += edge_contribution(&vertices[i], &vertices[next]);
+		}
+		doubled_area
+	}).sum()
+}
+
+/// Calculate twice the signed area of each of multiple closed contours, multi-threaded.
+///
+/// This divides the vertices over the threads in the same way as `doubled_area_contours_mt`. Each
+/// chunk produces the partial sums of the contours that it touches, which are added together
+/// afterwards. Only the contours at the boundaries between chunks get more than one partial sum.
+///
+/// # Arguments
+/// * `vertices` - The vertices of all contours, one contour after another.
+/// * `contour_starts` - The index of the first vertex of each contour, followed by the total number
+/// of vertices.
+///
+/// # Result
+/// The doubled area of each contour.
+pub(crate) fn doubled_areas_contours_mt(vertices: &[Point2D], contour_starts: &[usize]) -> Vec<i128> {
+	let chunk_size = cost_model::chunk_size(Operation::AREA, vertices.len()).max(1);
+	let partial_sums: Vec<(usize, Vec<i128>)> = vertices.par_chunks(chunk_size).enumerate().map(|(chunk_index, chunk)| {
+		let first = chunk_index * chunk_size;
+		let first_contour = contour_starts.partition_point(|start| *start <= first) - 1;
+		let mut contour = first_contour;
+		let mut sums = vec![0];
+		for i in first..(first + chunk.len()) {
+			while contour_starts[contour + 1] <= i { //Skip to the contour that this vertex is in, which may be after some empty contours.
+				contour += 1;
+				sums.push(0);
+			}
+			let next = if i + 1 == contour_starts[contour + 1] { contour_starts[contour] } else { i + 1 };
+			sums[contour - first_contour] += edge_contribution(&vertices[i], &vertices[next]);
+		}
+		(first_contour, sums)
+	}).collect();
+	let mut doubled_areas = vec![0; contour_starts.len() - 1];
+	for (first_contour, sums) in partial_sums {
+		for (offset, sum) in sums.into_iter().enumerate() {
+			doubled_areas[first_contour + offset] += sum;
+		}
+	}
+	doubled_areas
+}
+
+/// Calculate the contribution of one edge to the doubled area of a polygon.
+///
+/// This is the cross product of the two endpoints of the edge.
+fn edge_contribution(start: &Point2D, end: &Point2D) -> i128 {
+	start.x as i128 * end.y as i128 - end.x as i128 * start.y as i128
+}
+
+/// Halve a doubled area, rounding towards zero and clamping it to the range of `Area`.
+#[allow(clippy::unnecessary_cast)] //With 64-bit coordinates, the area is already an i128.
+fn halve(doubled_area: i128) -> Area {
+	(doubled_area / 2).clamp(Area::MIN as i128, Area::MAX as i128) as Area
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+
+	/// Test the area of an empty polygon.
+	///
+	/// It has no edges, so it has no area.
+	#[test]
+	fn area_polygon_empty() {
+		let poly = Polygon::new();
+		assert_eq!(area_polygon_st(&poly), 0, "An empty polygon has no area.");
+		assert_eq!(area_polygon_mt(&poly), 0, "An empty polygon has no area.");
+	}
+
+	/// Test the area of polygons that have too few vertices to enclose anything.
+	#[test_case(&[Point2D { x: 40, y: 70 }] ; "point")]
+	#[test_case(&[Point2D { x: 40, y: 70 }, Point2D { x: 1000, y: -50 }] ; "line")]
+	fn area_polygon_too_few_vertices(vertices: &[Point2D]) {
+		let poly = Polygon::from_iter(vertices.iter().copied());
+		assert_eq!(area_polygon_st(&poly), 0, "The polygon can't enclose any area with fewer than 3 vertices.");
+		assert_eq!(area_polygon_mt(&poly), 0, "The polygon can't enclose any area with fewer than 3 vertices.");
+	}
+
+	/// Test the area of some basic shapes, which are counter-clockwise, so positive.
+	#[test_case(crate::test::data::polygon::square_1000(), 1000 * 1000 ; "square")]
+	#[test_case(crate::test::data::polygon::triangle_1000(), 1000 * 1000 / 2 ; "triangle")]
+	fn area_polygon_positive(poly: Polygon, expected: Area) {
+		assert_eq!(area_polygon_st(&poly), expected);
+		assert_eq!(area_polygon_mt(&poly), expected);
+		assert_eq!(area_polygon(&poly), expected);
+	}
+
+	/// Test the area of a clockwise polygon, which must be negative.
+	#[test]
+	fn area_polygon_negative() {
+		let original = crate::test::data::polygon::square_1000();
+		let poly = Polygon::from_iter(original.host_vertices().iter().rev().copied());
+		assert_eq!(area_polygon_st(&poly), -1000 * 1000, "The square is reversed, so it is clockwise and its area is negative.");
+		assert_eq!(area_polygon_mt(&poly), -1000 * 1000, "The square is reversed, so it is clockwise and its area is negative.");
+	}
+
+	/// Test the area of a polygon whose doubled area is odd.
+	///
+	/// The area is rounded towards zero, so that both orientations give the same magnitude.
+	#[test]
+	fn area_polygon_rounding() {
+		let poly = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 3, y: 0 }, Point2D { x: 0, y: 3 }]); //Area is 4.5.
+		assert_eq!(area_polygon_st(&poly), 4, "Rounded towards zero.");
+		assert_eq!(area_polygon_mt(&poly), 4, "Rounded towards zero.");
+		let reversed = Polygon::from_iter([Point2D { x: 0, y: 3 }, Point2D { x: 3, y: 0 }, Point2D { x: 0, y: 0 }]); //Area is -4.5.
+		assert_eq!(area_polygon_st(&reversed), -4, "Rounded towards zero.");
+		assert_eq!(area_polygon_mt(&reversed), -4, "Rounded towards zero.");
+	}
+
+	/// Test the area of a self-intersecting polygon, where the two lobes have opposite orientations.
+	///
+	/// The two lobes cancel each other out.
+	#[test]
+	fn area_polygon_bowtie() {
+		let poly = Polygon::from_iter([
+			Point2D { x: 0, y: 0 },
+			Point2D { x: 1000, y: 1000 },
+			Point2D { x: 1000, y: 0 },
+			Point2D { x: 0, y: 1000 }
+		]);
+		assert_eq!(area_polygon_st(&poly), 0, "The lobes of the bowtie cancel each other out.");
+		assert_eq!(area_polygon_mt(&poly), 0, "The lobes of the bowtie cancel each other out.");
+	}
+
+	/// Test the area of a small polygon far away from the origin.
+	///
+	/// The cross products of the vertices are huge there, but they must cancel out exactly.
+	#[test]
+	fn area_polygon_far_away() {
+		let poly = Polygon::from_iter([
+			Point2D { x: Coordinate::MAX - 1000, y: Coordinate::MAX - 1000 },
+			Point2D { x: Coordinate::MAX, y: Coordinate::MAX - 1000 },
+			Point2D { x: Coordinate::MAX, y: Coordinate::MAX },
+			Point2D { x: Coordinate::MAX - 1000, y: Coordinate::MAX }
+		]);
+		assert_eq!(area_polygon_st(&poly), 1000 * 1000);
+		assert_eq!(area_polygon_mt(&poly), 1000 * 1000);
+	}
+
+	/// Test the area of the largest triangle that can be represented.
+	///
+	/// This is half of the coordinate space, which is just about the biggest area that fits.
+	#[test]
+	#[cfg(not(feature = "coordinate64"))] //With 64-bit coordinates, these areas are beyond the range where the operations are exact.
+	fn area_polygon_largest_triangle() {
+		let poly = Polygon::from_iter([
+			Point2D { x: Coordinate::MIN, y: Coordinate::MIN },
+			Point2D { x: Coordinate::MAX, y: Coordinate::MIN },
+			Point2D { x: Coordinate::MIN, y: Coordinate::MAX }
+		]);
+		let side = Coordinate::MAX as i128 - Coordinate::MIN as i128;
+		let expected = (side * side / 2) as Area;
+		assert_eq!(area_polygon_st(&poly), expected);
+		assert_eq!(area_polygon_mt(&poly), expected);
+		assert_eq!(area_polygon(&poly), expected);
+	}
+
+	/// Test the area of a square spanning the whole coordinate space, which doesn't fit in `Area`.
+	///
+	/// It gets clamped to the maximum area.
+	#[test]
+	#[cfg(not(feature = "coordinate64"))] //With 64-bit coordinates, these areas are beyond the range where the operations are exact.
+	fn area_polygon_too_large() {
+		let poly = Polygon::from_iter([
+			Point2D { x: Coordinate::MIN, y: Coordinate::MIN },
+			Point2D { x: Coordinate::MAX, y: Coordinate::MIN },
+			Point2D { x: Coordinate::MAX, y: Coordinate::MAX },
+			Point2D { x: Coordinate::MIN, y: Coordinate::MAX }
+		]);
+		assert_eq!(area_polygon_st(&poly), Area::MAX, "The area is too large to represent, so it gets clamped.");
+		assert_eq!(area_polygon_mt(&poly), Area::MAX, "The area is too large to represent, so it gets clamped.");
+	}
+
+	/// Test the area of a square that is too large for a 64-bit area, with 64-bit coordinates.
+	#[test]
+	#[cfg(feature = "coordinate64")]
+	fn area_polygon_beyond_64_bits() {
+		let poly = Polygon::from_iter([
+			Point2D { x: -crate::MAX_EXACT_COORDINATE, y: -crate::MAX_EXACT_COORDINATE },
+			Point2D { x: crate::MAX_EXACT_COORDINATE, y: -crate::MAX_EXACT_COORDINATE },
+			Point2D { x: crate::MAX_EXACT_COORDINATE, y: crate::MAX_EXACT_COORDINATE },
+			Point2D { x: -crate::MAX_EXACT_COORDINATE, y: crate::MAX_EXACT_COORDINATE }
+		]);
+		let expected = 4 * crate::MAX_EXACT_COORDINATE as Area * crate::MAX_EXACT_COORDINATE as Area;
+		assert!(expected > i64::MAX as Area, "This area must not fit in a 64-bit area.");
+		assert_eq!(area_polygon_st(&poly), expected);
+		assert_eq!(area_polygon_mt(&poly), expected);
+	}
+
+	/// Test the area of a polygon with many vertices, so that the multi-threaded implementation
+	/// divides it over multiple threads.
+	///
+	/// The polygon is a staircase of 1x1 steps, closed off by the diagonal along the tips of the
+	/// steps.
+	#[test]
+	fn area_polygon_many_vertices() {
+		let steps = 20000;
+		let mut poly = Polygon::with_capacity(steps as usize * 2 + 1);
+		for i in 0..steps {
+			poly.push(Point2D { x: i, y: i });
+			poly.push(Point2D { x: i + 1, y: i });
+		}
+		poly.push(Point2D { x: steps, y: steps });
+		//Between every step and the diagonal is a triangle of half a square.
+		let expected = steps as Area / 2;
+		assert_eq!(area_polygon_st(&poly), expected);
+		assert_eq!(area_polygon_mt(&poly), expected);
+		assert_eq!(area_polygon(&poly), expected);
+	}
+
+	/// Test that the GPU implementation computes the same area as the host.
+	#[cfg(feature = "gpu")]
+	#[test]
+	fn area_polygon_gpu_matches_host() {
+		for poly in [crate::test::data::polygon::square_1000(), crate::test::data::polygon::triangle_1000()] {
+			assert_eq!(area_polygon_gpu(&poly), area_polygon_st(&poly));
+		}
+	}
+
+	/// Test the area of multi-polygons, which is the sum of the areas of the contours.
+	#[test_case(MultiPolygon::new(), 0 ; "empty")]
+	#[test_case(crate::test::data::multi_polygon::square_with_hole_1000(), 1000 * 1000 - 500 * 500 ; "square_with_hole")]
+	#[test_case(crate::test::data::multi_polygon::two_squares_1000(), 2 * 1000 * 1000 ; "two_squares")]
+	#[test_case(MultiPolygon::from_iter([Polygon::new(), crate::test::data::polygon::triangle_1000(), Polygon::new()]), 1000 * 1000 / 2 ; "empty_contours")]
+	fn area_multipolygon_contours(multi: MultiPolygon, expected: Area) {
+		assert_eq!(area_multipolygon_st(&multi), expected);
+		assert_eq!(area_multipolygon_mt(&multi), expected);
+		assert_eq!(area_multipolygon(&multi), expected);
+	}
+
+	/// Test the area of a multi-polygon with many contours of different sizes, which get divided
+	/// over the threads in chunks that don't align with the contours.
+	#[test]
+	fn area_multipolygon_many_contours() {
+		let mut multi = MultiPolygon::new();
+		let mut expected_doubled = 0;
+		for size in 0..100 {
+			let steps = size * 10;
+			multi.push_contour((0..steps).flat_map(|step| [Point2D { x: step, y: step }, Point2D { x: step + 1, y: step }]).chain([Point2D { x: steps, y: steps }, Point2D { x: 0, y: steps }]));
+			expected_doubled += doubled_area_st(multi.contour(size as usize));
+		}
+		let expected = halve(expected_doubled);
+		assert_eq!(area_multipolygon_st(&multi), expected);
+		assert_eq!(area_multipolygon_mt(&multi), expected);
+	}
+
+	/// Test calculating the area of multi-polygons on the GPU.
+	#[cfg(feature = "gpu")]
+	#[test_case(crate::test::data::multi_polygon::square_with_hole_1000() ; "square_with_hole")]
+	#[test_case(MultiPolygon::from_iter([Polygon::new(), crate::test::data::polygon::triangle_1000(), Polygon::new()]) ; "empty_contours")]
+	fn area_multipolygon_gpu_matches_host(multi: MultiPolygon) {
+		assert_eq!(area_multipolygon_gpu(&multi), area_multipolygon_st(&multi));
+	}
+
+	/// Test the areas of the polygons in a batch, which are calculated separately.
+	#[test_case(PolygonBatch::new(), vec![] ; "empty")]
+	#[test_case(PolygonBatch::from_iter([crate::test::data::polygon::square_1000(), crate::test::data::polygon::triangle_1000()]), vec![1000 * 1000, 1000 * 1000 / 2] ; "square_triangle")]
+	#[test_case(PolygonBatch::from_iter([Polygon::new(), crate::test::data::polygon::triangle_1000(), Polygon::new()]), vec![0, 1000 * 1000 / 2, 0] ; "empty_polygons")]
+	fn area_batch_polygons(batch: PolygonBatch, expected: Vec<Area>) {
+		assert_eq!(area_batch_st(&batch), expected);
+		assert_eq!(area_batch_mt(&batch), expected);
+		assert_eq!(area_batch(&batch), expected);
+	}
+
+	/// Test the areas of a batch with many polygons of different sizes, which get divided over the
+	/// threads in chunks that don't align with the polygons.
+	#[test]
+	fn area_batch_many_polygons() {
+		let mut batch = PolygonBatch::new();
+		let mut expected = vec![];
+		for size in 0..100 {
+			let steps = size * 10;
+			batch.push_polygon((0..steps).flat_map(|step| [Point2D { x: step, y: step }, Point2D { x: step + 1, y: step }]).chain([Point2D { x: steps, y: steps }, Point2D { x: 0, y: steps }]));
+			expected.push(halve(doubled_area_st(batch.polygon(size as usize))));
+		}
+		assert_eq!(area_batch_st(&batch), expected);
+		assert_eq!(area_batch_mt(&batch), expected);
+	}
+
+	/// Test calculating the areas of a batch on the GPU.
+	#[cfg(feature = "gpu")]
+	#[test_case(PolygonBatch::from_iter([crate::test::data::polygon::square_1000(), crate::test::data::polygon::l_shape_1000()]) ; "square_l_shape")]
+	#[test_case(PolygonBatch::from_iter([Polygon::new(), crate::test::data::polygon::triangle_1000(), Polygon::new()]) ; "empty_polygons")]
+	fn area_batch_gpu_matches_host(batch: PolygonBatch) {
+		assert_eq!(area_batch_gpu(&batch), area_batch_st(&batch));
+	}
+}
\ No newline at end of file
@@ -0,0 +1,1450 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! This module contains the implementations of boolean operations between two shapes: union,
+//! intersection, difference and exclusive or.
+//!
+//! The two shapes are called the subject and the clip. They may consist of any number of contours,
+//! which may intersect themselves and each other. Which regions of each shape are filled is decided
+//! by a fill rule. The operations work in four steps:
+//! 1. The edges of both shapes are split wherever they intersect or touch another edge. This turns
+//! them into segments that only meet at their endpoints. Where edges cross, the edges are snap
+//! rounded: the pixels around the intersection points and around the endpoints of the edges are
+//! hot, and every edge that passes through a hot pixel is split at its centre. The pixel around a
+//! coordinate contains the points that round to it, with halves rounded up, as
+//! `RationalPoint::rounded` in the predicates module does. Snap rounding moves the edges slightly,
+//! but never makes them cross. They may still touch, where they are split once more, without moving
+//! them. Segments that coincide are merged, and segments that cancel each other out are removed.
+//! 2. A sweep line moves through the segments from left to right, to find the winding numbers of
+//! both shapes on either side of each segment.
+//! 3. The segments that separate a region inside the result from a region outside the result are
+//! kept. They are directed such that the inside of the result is on their left.
+//! 4. The kept segments are linked together into contours. Where multiple contours meet in one
+//! vertex, they are separated, taking the sharpest turn to the left.
+//!
+//! The result is a multi-polygon with counter-clockwise outer contours and clockwise holes, which
+//! don't overlap each other and don't intersect themselves, and without collinear vertices. The only
+//! inexactness is the rounding of intersection points to the grid of coordinates.
+
+use std::cmp; //To compute bounding boxes.
+use std::cmp::Ordering; //To sort segments along the sweep line.
+use cubecl::prelude::*; //For the GPU implementation.
+use rayon::prelude::*; //For multi-threaded implementations.
+
+use crate::Coordinate; //The sweep line is at a certain X coordinate.
+use crate::FillRule; //To choose which regions of the shapes are filled.
+use crate::MultiPolygon; //The operands and results of boolean operations.
+use crate::Point2D; //The endpoints of the segments.
+use crate::Polygon; //Boolean operations between polygons.
+use crate::PolygonBatch; //Boolean operations between batches of polygons at once.
+use crate::cost_model::{self, Backend, Operation}; //To choose the fastest implementation.
+use crate::detail::sync_status::SyncStatus; //To tell the cost model where the data is needed.
+use crate::operations::self_intersection; //To find where the segments meet.
+use crate::predicates::{self, direction, orientation}; //To find where the segments meet.
+
+/// The boolean operations that can be performed between two shapes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BooleanOperation {
+	/// The regions that are inside either of the shapes, or both.
+	UNION,
+
+	/// The regions that are inside both of the shapes.
+	INTERSECTION,
+
+	/// The regions that are inside the subject, but not inside the clip.
+	DIFFERENCE,
+
+	/// The regions that are inside exactly one of the shapes.
+	XOR,
+}
+
+impl BooleanOperation {
+	/// Check whether a region is inside the result of this operation.
+	///
+	/// # Arguments
+	/// * `in_subject` - Whether the region is inside the subject.
+	/// * `in_clip` - Whether the region is inside the clip.
+	fn contains(&self, in_subject: bool, in_clip: bool) -> bool {
+		match self {
+			BooleanOperation::UNION => in_subject || in_clip,
+			BooleanOperation::INTERSECTION => in_subject && in_clip,
+			BooleanOperation::DIFFERENCE => in_subject && !in_clip,
+			BooleanOperation::XOR => in_subject != in_clip,
+		}
+	}
+}
+
+/// A straight piece of the boundary of the shapes, between two vertices.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Segment {
+	/// The start of the segment. After normalising, this is the lowest endpoint in lexicographic
+	/// order.
+	start: Point2D,
+
+	/// The end of the segment.
+	end: Point2D,
+
+	/// How much the winding number of the subject and of the clip change when crossing the segment
+	/// from its right to its left side. This is the number of times the contours of each shape
+	/// pass along this segment from its start to its end, minus the number of times they pass
+	/// along it the other way.
+	winding: [i32; 2],
+}
+
+/// Perform a boolean operation between two polygons.
+///
+/// This chooses automatically whether to perform the operation on a single thread, on multiple
+/// threads or with help of the GPU. The cost model estimates which is fastest, from the number of
+/// vertices in the polygons.
+///
+/// # Arguments
+/// * `subject` - The first operand.
+/// * `clip` - The second operand. For a difference, this is the shape that is cut away.
+/// * `operation` - Which boolean operation to perform.
+/// * `fill_rule` - Which regions of each polygon are considered to be inside the polygon.
+///
+/// # Result
+/// The regions that result from the operation.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, Point2D, Polygon, Shape2D};
+/// use apex::operations::boolean::BooleanOperation;
+/// let square = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let shifted = Polygon::from_iter([Point2D { x: 50, y: 50 }, Point2D { x: 150, y: 50 }, Point2D { x: 150, y: 150 }, Point2D { x: 50, y: 150 }]);
+/// let union = apex::operations::boolean::boolean_polygon(&square, &shifted, BooleanOperation::UNION, FillRule::NON_ZERO);
+/// assert_eq!(union.area(), 17500);
+/// let intersection = apex::operations::boolean::boolean_polygon(&square, &shifted, BooleanOperation::INTERSECTION, FillRule::NON_ZERO);
+/// assert_eq!(intersection.area(), 2500);
+/// ```
+pub fn boolean_polygon(subject: &Polygon, clip: &Polygon, operation: BooleanOperation, fill_rule: FillRule) -> MultiPolygon {
+	boolean(polygon_segments(subject.host_vertices(), clip.host_vertices()), operation, fill_rule, choose_backend(subject.len() + clip.len()))
+}
+
+/// Perform a boolean operation between two polygons, on a single thread.
+///
+/// # Arguments
+/// * `subject` - The first operand.
+/// * `clip` - The second operand. For a difference, this is the shape that is cut away.
+/// * `operation` - Which boolean operation to perform.
+/// * `fill_rule` - Which regions of each polygon are considered to be inside the polygon.
+///
+/// # Result
+/// The regions that result from the operation.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, Point2D, Polygon, Shape2D};
+/// use apex::operations::boolean::BooleanOperation;
+/// let square = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let shifted = Polygon::from_iter([Point2D { x: 50, y: 50 }, Point2D { x: 150, y: 50 }, Point2D { x: 150, y: 150 }, Point2D { x: 50, y: 150 }]);
+/// let difference = apex::operations::boolean::boolean_polygon_st(&square, &shifted, BooleanOperation::DIFFERENCE, FillRule::NON_ZERO);
+/// assert_eq!(difference.area(), 7500);
+/// ```
+pub fn boolean_polygon_st(subject: &Polygon, clip: &Polygon, operation: BooleanOperation, fill_rule: FillRule) -> MultiPolygon {
+	boolean(polygon_segments(subject.host_vertices(), clip.host_vertices()), operation, fill_rule, Backend::ST)
+}
+
+/// Perform a boolean operation between two polygons, on multiple threads.
+///
+/// The search for intersections between the edges and the sorting of the segments are divided over
+/// the threads. The sweep line that determines the winding numbers runs on a single thread.
+///
+/// # Arguments
+/// * `subject` - The first operand.
+/// * `clip` - The second operand. For a difference, this is the shape that is cut away.
+/// * `operation` - Which boolean operation to perform.
+/// * `fill_rule` - Which regions of each polygon are considered to be inside the polygon.
+///
+/// # Result
+/// The regions that result from the operation.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, Point2D, Polygon, Shape2D};
+/// use apex::operations::boolean::BooleanOperation;
+/// let square = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let shifted = Polygon::from_iter([Point2D { x: 50, y: 50 }, Point2D { x: 150, y: 50 }, Point2D { x: 150, y: 150 }, Point2D { x: 50, y: 150 }]);
+/// let xor = apex::operations::boolean::boolean_polygon_mt(&square, &shifted, BooleanOperation::XOR, FillRule::NON_ZERO);
+/// assert_eq!(xor.area(), 15000);
+/// ```
+pub fn boolean_polygon_mt(subject: &Polygon, clip: &Polygon, operation: BooleanOperation, fill_rule: FillRule) -> MultiPolygon {
+	boolean(polygon_segments(subject.host_vertices(), clip.host_vertices()), operation, fill_rule, Backend::MT)
+}
+
+/// Perform a boolean operation between two polygons, with help of the GPU.
+///
+/// The GPU finds the pairs of edges whose bounding boxes overlap. The exact intersection tests of
+/// those pairs need 128-bit integers, so they are performed on the host, on multiple threads, as is
+/// the rest of the operation.
+///
+/// # Arguments
+/// * `subject` - The first operand.
+/// * `clip` - The second operand. For a difference, this is the shape that is cut away.
+/// * `operation` - Which boolean operation to perform.
+/// * `fill_rule` - Which regions of each polygon are considered to be inside the polygon.
+///
+/// # Result
+/// The regions that result from the operation.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, Point2D, Polygon, Shape2D};
+/// use apex::operations::boolean::BooleanOperation;
+/// let square = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let shifted = Polygon::from_iter([Point2D { x: 50, y: 50 }, Point2D { x: 150, y: 50 }, Point2D { x: 150, y: 150 }, Point2D { x: 50, y: 150 }]);
+/// let union = apex::operations::boolean::boolean_polygon_gpu(&square, &shifted, BooleanOperation::UNION, FillRule::NON_ZERO);
+/// assert_eq!(union.area(), 17500);
+/// ```
+#[cfg(feature = "gpu")]
+pub fn boolean_polygon_gpu(subject: &Polygon, clip: &Polygon, operation: BooleanOperation, fill_rule: FillRule) -> MultiPolygon {
+	if !crate::detail::gpu::supports_coordinates() {
+		return boolean_polygon(subject, clip, operation, fill_rule);
+	}
+	boolean(polygon_segments(subject.host_vertices(), clip.host_vertices()), operation, fill_rule, Backend::GPU)
+}
+
+/// Perform a boolean operation between two multi-polygons.
+///
+/// All contours of each multi-polygon together form one shape. This chooses automatically whether
+/// to perform the operation on a single thread, on multiple threads or with help of the GPU, in the
+/// same way as `boolean_polygon`.
+///
+/// # Arguments
+/// * `subject` - The first operand.
+/// * `clip` - The second operand. For a difference, this is the shape that is cut away.
+/// * `operation` - Which boolean operation to perform.
+/// * `fill_rule` - Which regions of each shape are considered to be inside the shape.
+///
+/// # Result
+/// The regions that result from the operation.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, MultiPolygon, Point2D, Shape2D};
+/// use apex::operations::boolean::BooleanOperation;
+/// let mut frame = MultiPolygon::new();
+/// frame.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// frame.push_contour([Point2D { x: 25, y: 25 }, Point2D { x: 25, y: 75 }, Point2D { x: 75, y: 75 }, Point2D { x: 75, y: 25 }]); //A hole.
+/// let mut plug = MultiPolygon::new();
+/// plug.push_contour([Point2D { x: 0, y: 40 }, Point2D { x: 100, y: 40 }, Point2D { x: 100, y: 60 }, Point2D { x: 0, y: 60 }]);
+/// let result = apex::operations::boolean::boolean_multipolygon(&frame, &plug, BooleanOperation::UNION, FillRule::NON_ZERO);
+/// assert_eq!(result.num_contours(), 3, "The plug splits the hole in two.");
+/// assert_eq!(result.area(), 10000 - 2 * 50 * 15);
+/// ```
+pub fn boolean_multipolygon(subject: &MultiPolygon, clip: &MultiPolygon, operation: BooleanOperation, fill_rule: FillRule) -> MultiPolygon {
+	boolean(multipolygon_segments(subject, clip), operation, fill_rule, choose_backend(subject.len() + clip.len()))
+}
+
+/// Perform a boolean operation between two multi-polygons, on a single thread.
+///
+/// # Arguments
+/// * `subject` - The first operand.
+/// * `clip` - The second operand. For a difference, this is the shape that is cut away.
+/// * `operation` - Which boolean operation to perform.
+/// * `fill_rule` - Which regions of each shape are considered to be inside the shape.
+///
+/// # Result
+/// The regions that result from the operation.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, MultiPolygon, Point2D, Shape2D};
+/// use apex::operations::boolean::BooleanOperation;
+/// let mut squares = MultiPolygon::new();
+/// squares.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// squares.push_contour([Point2D { x: 200, y: 0 }, Point2D { x: 300, y: 0 }, Point2D { x: 300, y: 100 }, Point2D { x: 200, y: 100 }]);
+/// let mut bar = MultiPolygon::new();
+/// bar.push_contour([Point2D { x: 50, y: 25 }, Point2D { x: 250, y: 25 }, Point2D { x: 250, y: 75 }, Point2D { x: 50, y: 75 }]);
+/// let result = apex::operations::boolean::boolean_multipolygon_st(&squares, &bar, BooleanOperation::INTERSECTION, FillRule::NON_ZERO);
+/// assert_eq!(result.num_contours(), 2);
+/// assert_eq!(result.area(), 2 * 50 * 50);
+/// ```
+pub fn boolean_multipolygon_st(subject: &MultiPolygon, clip: &MultiPolygon, operation: BooleanOperation, fill_rule: FillRule) -> MultiPolygon {
+	boolean(multipolygon_segments(subject, clip), operation, fill_rule, Backend::ST)
+}
+
+/// Perform a boolean operation between two multi-polygons, on multiple threads.
+///
+/// The search for intersections between the edges and the sorting of the segments are divided over
+/// the threads. The sweep line that determines the winding numbers runs on a single thread.
+///
+/// # Arguments
+/// * `subject` - The first operand.
+/// * `clip` - The second operand. For a difference, this is the shape that is cut away.
+/// * `operation` - Which boolean operation to perform.
+/// * `fill_rule` - Which regions of each shape are considered to be inside the shape.
+///
+/// # Result
+/// The regions that result from the operation.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, MultiPolygon, Point2D, Shape2D};
+/// use apex::operations::boolean::BooleanOperation;
+/// let mut squares = MultiPolygon::new();
+/// squares.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// squares.push_contour([Point2D { x: 200, y: 0 }, Point2D { x: 300, y: 0 }, Point2D { x: 300, y: 100 }, Point2D { x: 200, y: 100 }]);
+/// let mut bar = MultiPolygon::new();
+/// bar.push_contour([Point2D { x: 50, y: 25 }, Point2D { x: 250, y: 25 }, Point2D { x: 250, y: 75 }, Point2D { x: 50, y: 75 }]);
+/// let result = apex::operations::boolean::boolean_multipolygon_mt(&squares, &bar, BooleanOperation::UNION, FillRule::NON_ZERO);
+/// assert_eq!(result.num_contours(), 1);
+/// assert_eq!(result.area(), 2 * 100 * 100 + 100 * 50);
+/// ```
+pub fn boolean_multipolygon_mt(subject: &MultiPolygon, clip: &MultiPolygon, operation: BooleanOperation, fill_rule: FillRule) -> MultiPolygon {
+	boolean(multipolygon_segments(subject, clip), operation, fill_rule, Backend::MT)
+}
+
+/// Perform a boolean operation between two multi-polygons, with help of the GPU.
+///
+/// The GPU finds the pairs of edges whose bounding boxes overlap. The rest of the operation is
+/// performed on the host, on multiple threads, like in `boolean_polygon_gpu`.
+///
+/// # Arguments
+/// * `subject` - The first operand.
+/// * `clip` - The second operand. For a difference, this is the shape that is cut away.
+/// * `operation` - Which boolean operation to perform.
+/// * `fill_rule` - Which regions of each shape are considered to be inside the shape.
+///
+/// # Result
+/// The regions that result from the operation.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, MultiPolygon, Point2D, Shape2D};
+/// use apex::operations::boolean::BooleanOperation;
+/// let mut square = MultiPolygon::new();
+/// square.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let mut bar = MultiPolygon::new();
+/// bar.push_contour([Point2D { x: 50, y: 25 }, Point2D { x: 250, y: 25 }, Point2D { x: 250, y: 75 }, Point2D { x: 50, y: 75 }]);
+/// let result = apex::operations::boolean::boolean_multipolygon_gpu(&square, &bar, BooleanOperation::DIFFERENCE, FillRule::NON_ZERO);
+/// assert_eq!(result.area(), 100 * 100 - 50 * 50);
+/// ```
+#[cfg(feature = "gpu")]
+pub fn boolean_multipolygon_gpu(subject: &MultiPolygon, clip: &MultiPolygon, operation: BooleanOperation, fill_rule: FillRule) -> MultiPolygon {
+	if !crate::detail::gpu::supports_coordinates() {
+		return boolean_multipolygon(subject, clip, operation, fill_rule);
+	}
+	boolean(multipolygon_segments(subject, clip), operation, fill_rule, Backend::GPU)
+}
+
+/// Perform a boolean operation between the polygons of two batches, pair by pair.
+///
+/// The first polygon of the subjects is combined with the first polygon of the clips, the second
+/// with the second, and so on. Both batches must have the same number of polygons. This chooses
+/// automatically whether to perform the operations on a single thread or on multiple threads. The
+/// cost model considers the total number of vertices in both batches.
+///
+/// # Arguments
+/// * `subjects` - The first operand of each operation.
+/// * `clips` - The second operand of each operation. For a difference, these are the shapes that
+/// are cut away.
+/// * `operation` - Which boolean operation to perform.
+/// * `fill_rule` - Which regions of each polygon are considered to be inside the polygon.
+///
+/// # Result
+/// For each pair of polygons, the regions that result from the operation.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, Point2D, PolygonBatch, Shape2D};
+/// use apex::operations::boolean::BooleanOperation;
+/// let mut subjects = PolygonBatch::new();
+/// subjects.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// subjects.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let mut clips = PolygonBatch::new();
+/// clips.push_polygon([Point2D { x: 50, y: 50 }, Point2D { x: 150, y: 50 }, Point2D { x: 150, y: 150 }, Point2D { x: 50, y: 150 }]);
+/// clips.push_polygon([Point2D { x: 200, y: 0 }, Point2D { x: 300, y: 0 }, Point2D { x: 300, y: 100 }, Point2D { x: 200, y: 100 }]);
+/// let unions = apex::operations::boolean::boolean_batch(&subjects, &clips, BooleanOperation::UNION, FillRule::NON_ZERO);
+/// assert_eq!(unions[0].area(), 17500);
+/// assert_eq!(unions[1].num_contours(), 2, "These squares don't overlap.");
+/// ```
+pub fn boolean_batch(subjects: &PolygonBatch, clips: &PolygonBatch, operation: BooleanOperation, fill_rule: FillRule) -> Vec<MultiPolygon> {
+	match choose_backend(subjects.len() + clips.len()) {
+		Backend::ST => boolean_batch_st(subjects, clips, operation, fill_rule),
+		Backend::MT => boolean_batch_mt(subjects, clips, operation, fill_rule),
+		Backend::GPU => boolean_batch_mt(subjects, clips, operation, fill_rule), //There is no GPU implementation for batches. The GPU is only chosen for a lot of work, which suits multiple threads best.
+	}
+}
+
+/// Perform a boolean operation between the polygons of two batches, pair by pair, on a single
+/// thread.
+///
+/// # Arguments
+/// * `subjects` - The first operand of each operation.
+/// * `clips` - The second operand of each operation. For a difference, these are the shapes that
+/// are cut away.
+/// * `operation` - Which boolean operation to perform.
+/// * `fill_rule` - Which regions of each polygon are considered to be inside the polygon.
+///
+/// # Result
+/// For each pair of polygons, the regions that result from the operation.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, Point2D, PolygonBatch, Shape2D};
+/// use apex::operations::boolean::BooleanOperation;
+/// let mut subjects = PolygonBatch::new();
+/// subjects.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let mut clips = PolygonBatch::new();
+/// clips.push_polygon([Point2D { x: 50, y: 50 }, Point2D { x: 150, y: 50 }, Point2D { x: 150, y: 150 }, Point2D { x: 50, y: 150 }]);
+/// let differences = apex::operations::boolean::boolean_batch_st(&subjects, &clips, BooleanOperation::DIFFERENCE, FillRule::NON_ZERO);
+/// assert_eq!(differences[0].area(), 7500);
+/// ```
+pub fn boolean_batch_st(subjects: &PolygonBatch, clips: &PolygonBatch, operation: BooleanOperation, fill_rule: FillRule) -> Vec<MultiPolygon> {
+	assert_eq!(subjects.num_polygons(), clips.num_polygons(), "Every subject needs a clip to combine it with.");
+	subjects.polygons().zip(clips.polygons()).map(|(subject, clip)| boolean(polygon_segments(subject, clip), operation, fill_rule, Backend::ST)).collect()
+}
+
+/// Perform a boolean operation between the polygons of two batches, pair by pair, on multiple
+/// threads.
+///
+/// The pairs of polygons are divided over the threads, and each operation is performed on a single
+/// thread.
+///
+/// # Arguments
+/// * `subjects` - The first operand of each operation.
+/// * `clips` - The second operand of each operation. For a difference, these are the shapes that
+/// are cut away.
+/// * `operation` - Which boolean operation to perform.
+/// * `fill_rule` - Which regions of each polygon are considered to be inside the polygon.
+///
+/// # Result
+/// For each pair of polygons, the regions that result from the operation.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, Point2D, PolygonBatch, Shape2D};
+/// use apex::operations::boolean::BooleanOperation;
+/// let mut subjects = PolygonBatch::new();
+/// subjects.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let mut clips = PolygonBatch::new();
+/// clips.push_polygon([Point2D { x: 50, y: 50 }, Point2D { x: 150, y: 50 }, Point2D { x: 150, y: 150 }, Point2D { x: 50, y: 150 }]);
+/// let xors = apex::operations::boolean::boolean_batch_mt(&subjects, &clips, BooleanOperation::XOR, FillRule::NON_ZERO);
+/// assert_eq!(xors[0].area(), 15000);
+/// ```
+pub fn boolean_batch_mt(subjects: &PolygonBatch, clips: &PolygonBatch, operation: BooleanOperation, fill_rule: FillRule) -> Vec<MultiPolygon> {
+	assert_eq!(subjects.num_polygons(), clips.num_polygons(), "Every subject needs a clip to combine it with.");
+	let pairs: Vec<(&[Point2D], &[Point2D])> = subjects.polygons().zip(clips.polygons()).collect();
+	pairs.into_par_iter().map(|(subject, clip)| boolean(polygon_segments(subject, clip), operation, fill_rule, Backend::ST)).collect()
+}
+
+/// Make every polygon of a batch simple, on a single thread.
+///
+/// This is the union of each polygon with nothing, like `self_intersection::make_simple_polygon`.
+///
+/// # Arguments
+/// * `batch` - The polygons to make simple.
+/// * `fill_rule` - Which regions of each polygon are considered to be inside the polygon.
+pub(crate) fn make_simple_batch_st(batch: &PolygonBatch, fill_rule: FillRule) -> Vec<MultiPolygon> {
+	batch.polygons().map(|vertices| boolean(polygon_segments(vertices, &[]), BooleanOperation::UNION, fill_rule, Backend::ST)).collect()
+}
+
+/// Make every polygon of a batch simple, on multiple threads.
+///
+/// The polygons are divided over the threads, and each polygon is processed on a single thread.
+///
+/// # Arguments
+/// * `batch` - The polygons to make simple.
+/// * `fill_rule` - Which regions of each polygon are considered to be inside the polygon.
+pub(crate) fn make_simple_batch_mt(batch: &PolygonBatch, fill_rule: FillRule) -> Vec<MultiPolygon> {
+	let polygons: Vec<&[Point2D]> = batch.polygons().collect();
+	polygons.into_par_iter().map(|vertices| boolean(polygon_segments(vertices, &[]), BooleanOperation::UNION, fill_rule, Backend::ST)).collect()
+}
+
+/// Choose the backend to perform a boolean operation with.
+///
+/// All implementations need the vertices on the host, so for the cost model, the data is always on
+/// the host.
+///
+/// # Arguments
+/// * `num_vertices` - The total number of vertices in both operands.
+fn choose_backend(num_vertices: usize) -> Backend {
+	cost_model::choose(Operation::BOOLEAN, num_vertices, SyncStatus::HOST)
+}
+
+/// Collect the edges of two polygons as segments.
+///
+/// # Arguments
+/// * `subject` - The vertices of the polygon whose edges change the winding number of the subject.
+/// * `clip` - The vertices of the polygon whose edges change the winding number of the clip.
+fn polygon_segments(subject: &[Point2D], clip: &[Point2D]) -> Vec<Segment> {
+	let mut segments = Vec::with_capacity(subject.len() + clip.len());
+	contour_segments(subject, 0, &mut segments);
+	contour_segments(clip, 1, &mut segments);
+	segments
+}
+
+/// Collect the edges of all contours of two multi-polygons as segments.
+///
+/// # Arguments
+/// * `subject` - The multi-polygon whose edges change the winding number of the subject.
+/// * `clip` - The multi-polygon whose edges change the winding number of the clip.
+fn multipolygon_segments(subject: &MultiPolygon, clip: &MultiPolygon) -> Vec<Segment> {
+	let mut segments = Vec::with_capacity(subject.len() + clip.len());
+	for contour in subject.contours() {
+		contour_segments(contour, 0, &mut segments);
+	}
+	for contour in clip.contours() {
+		contour_segments(contour, 1, &mut segments);
+	}
+	segments
+}
+
+/// Add the edges of a closed contour to a list of segments.
+///
+/// Edges without length are left out.
+///
+/// # Arguments
+/// * `vertices` - The vertices of the contour.
+/// * `operand` - Which winding number the edges change: 0 for the subject, 1 for the clip.
+/// * `segments` - The list of segments to add the edges to.
+fn contour_segments(vertices: &[Point2D], operand: usize, segments: &mut Vec<Segment>) {
+	for i in 0..vertices.len() {
+		let start = vertices[i];
+		let end = vertices[(i + 1) % vertices.len()];
+		if start != end {
+			let mut winding = [0, 0];
+			winding[operand] = 1;
+			segments.push(Segment { start, end, winding });
+		}
+	}
+}
+
+/// Perform a boolean operation on the segments of two shapes.
+///
+/// # Arguments
+/// * `segments` - The edges of both shapes.
+/// * `operation` - Which boolean operation to perform.
+/// * `fill_rule` - Which regions of each shape are considered to be inside the shape.
+/// * `backend` - How to find the intersections between the segments.
+fn boolean(segments: Vec<Segment>, operation: BooleanOperation, fill_rule: FillRule, backend: Backend) -> MultiPolygon {
+	let mut segments = normalise(segments, backend);
+	let mut snap_rounded = false;
+	loop {
+		let (splits, crossings) = match backend {
+			Backend::ST => find_splits_st(&segments),
+			Backend::MT => find_splits_mt(&segments),
+			#[cfg(feature = "gpu")]
+			Backend::GPU => find_splits_gpu(&segments),
+			#[cfg(not(feature = "gpu"))]
+			Backend::GPU => unreachable!("Without a GPU runtime, the cost model never chooses the GPU."),
+		};
+		let splits = if crossings.is_empty() {
+			splits //Splitting where segments touch doesn't move them, so this never creates new crossings.
+		} else {
+			debug_assert!(!snap_rounded, "Snap rounded segments must not cross each other.");
+			snap_rounded = true;
+			snap_round(&segments, crossings, backend) //The hot pixels include all endpoints, so this splits the segments where they touch too.
+		};
+		if splits.is_empty() {
+			break;
+		}
+		segments = normalise(split(&segments, splits), backend);
+	}
+
+	let left_windings = left_windings(&segments);
+	let mut edges: Vec<(Point2D, Point2D)> = Vec::new();
+	for (segment, left) in segments.iter().zip(left_windings) {
+		let right = [left[0] - segment.winding[0], left[1] - segment.winding[1]];
+		let inside_left = operation.contains(fill_rule.is_filled(left[0]), fill_rule.is_filled(left[1]));
+		let inside_right = operation.contains(fill_rule.is_filled(right[0]), fill_rule.is_filled(right[1]));
+		if inside_left && !inside_right {
+			edges.push((segment.start, segment.end));
+		} else if inside_right && !inside_left {
+			edges.push((segment.end, segment.start)); //Reverse it, so that the inside is on the left.
+		}
+	}
+
+	let mut result = MultiPolygon::new();
+	for contour in link_contours(edges) {
+		result.push_contour(contour);
+	}
+	result
+}
+
+/// Bring segments into a canonical form.
+///
+/// Each segment is directed from its lowest endpoint to its highest endpoint in lexicographic
+/// order. Segments without length are removed. Segments with the same endpoints are merged, and
+/// segments that don't change any winding number are removed. The result is sorted.
+///
+/// # Arguments
+/// * `segments` - The segments to normalise.
+/// * `backend` - Whether to sort the segments on a single thread or on multiple threads.
+fn normalise(mut segments: Vec<Segment>, backend: Backend) -> Vec<Segment> {
+	segments.retain(|segment| segment.start != segment.end);
+	for segment in segments.iter_mut() {
+		if segment.start > segment.end {
+			std::mem::swap(&mut segment.start, &mut segment.end);
+			segment.winding = [-segment.winding[0], -segment.winding[1]];
+		}
+	}
+	let key = |segment: &Segment| (segment.start, segment.end);
+	match backend {
+		Backend::ST => segments.sort_unstable_by_key(key),
+		_ => segments.par_sort_unstable_by_key(key),
+	}
+
+	let mut merged: Vec<Segment> = Vec::with_capacity(segments.len());
+	for segment in segments {
+		match merged.last_mut() {
+			Some(last) if key(last) == key(&segment) => {
+				last.winding = [last.winding[0] + segment.winding[0], last.winding[1] + segment.winding[1]];
+			}
+			_ => merged.push(segment),
+		}
+	}
+	merged.retain(|segment| segment.winding != [0, 0]);
+	merged
+}
+
+/// Find where segments need to be split, on a single thread.
+///
+/// The pairs of segments that meet are found with the Bentley-Ottmann sweep of the
+/// self-intersection module, and then tested one by one.
+///
+/// # Arguments
+/// * `segments` - The normalised segments.
+///
+/// # Result
+/// For every split where segments touch, the index of the segment to split and the point to split
+/// it at. And the points where segments cross, rounded to the grid.
+fn find_splits_st(segments: &[Segment]) -> (Vec<(usize, Point2D)>, Vec<Point2D>) {
+	let mut splits = Vec::new();
+	let mut crossings = Vec::new();
+	for (first, second) in meeting_segments(segments, 1) {
+		splits_between(segments, first, second, &mut splits, &mut crossings);
+	}
+	(splits, crossings)
+}
+
+/// Find where segments need to be split, on multiple threads.
+///
+/// The sweep line searches vertical slabs of the plane in parallel, and the pairs of segments that
+/// meet are then divided over the threads.
+///
+/// # Arguments
+/// * `segments` - The normalised segments.
+///
+/// # Result
+/// For every split where segments touch, the index of the segment to split and the point to split
+/// it at. And the points where segments cross, rounded to the grid.
+fn find_splits_mt(segments: &[Segment]) -> (Vec<(usize, Point2D)>, Vec<Point2D>) {
+	meeting_segments(segments, num_slabs(segments.len())).into_par_iter().fold(|| (Vec::new(), Vec::new()), |(mut splits, mut crossings), (first, second)| {
+		splits_between(segments, first, second, &mut splits, &mut crossings);
+		(splits, crossings)
+	}).reduce(|| (Vec::new(), Vec::new()), concatenate_splits)
+}
+
+/// Choose how many slabs to divide the plane into, for the multi-threaded sweep line.
+fn num_slabs(num_segments: usize) -> usize {
+	cmp::max(1, num_segments / cost_model::chunk_size(Operation::BOOLEAN, num_segments))
+}
+
+/// Combine the splits and crossings found by two threads.
+fn concatenate_splits(mut first: (Vec<(usize, Point2D)>, Vec<Point2D>), second: (Vec<(usize, Point2D)>, Vec<Point2D>)) -> (Vec<(usize, Point2D)>, Vec<Point2D>) {
+	first.0.extend(second.0);
+	first.1.extend(second.1);
+	first
+}
+
+/// Find the pairs of segments that meet, including where they only touch.
+///
+/// # Arguments
+/// * `segments` - The normalised segments.
+/// * `num_slabs` - How many vertical slabs of the plane to search in parallel.
+fn meeting_segments(segments: &[Segment], num_slabs: usize) -> Vec<(usize, usize)> {
+	let ends: Vec<(Point2D, Point2D)> = segments.iter().map(|segment| (segment.start, segment.end)).collect();
+	self_intersection::meeting_segments(&ends, num_slabs)
+}
+
+/// Find where segments need to be split, with help of the GPU.
+///
+/// The GPU finds the pairs of segments whose bounding boxes overlap. Those are then tested exactly
+/// on the host, on multiple threads.
+///
+/// # Arguments
+/// * `segments` - The normalised segments.
+///
+/// # Result
+/// For every split where segments touch, the index of the segment to split and the point to split
+/// it at. And the points where segments cross, rounded to the grid.
+#[cfg(feature = "gpu")]
+fn find_splits_gpu(segments: &[Segment]) -> (Vec<(usize, Point2D)>, Vec<Point2D>) {
+	use crate::detail::gpu;
+
+	if segments.is_empty() {
+		return (Vec::new(), Vec::new()); //Can't launch a kernel without any work.
+	}
+	let boxes: Vec<Coordinate> = segments.iter().flat_map(|segment| [
+		segment.start.x,
+		segment.end.x,
+		cmp::min(segment.start.y, segment.end.y),
+		cmp::max(segment.start.y, segment.end.y),
+	]).collect();
+	let client = gpu::client();
+	let boxes_handle = client.create(Coordinate::as_bytes(&boxes));
+	let counts_handle = client.empty(segments.len() * size_of::<u32>());
+	let (cube_count, cube_dim) = gpu::launch_dimensions(segments.len());
+	unsafe {
+		count_overlapping_boxes_kernel::launch::<gpu::GpuRuntime>(
+			&client,
+			cube_count,
+			cube_dim,
+			ArrayArg::from_raw_parts::<Coordinate>(&boxes_handle, boxes.len(), 1),
+			ArrayArg::from_raw_parts::<u32>(&counts_handle, segments.len(), 1),
+		);
+	}
+	let counts = u32::from_bytes(&client.read_one(counts_handle)).to_vec();
+	let mut offsets = Vec::with_capacity(segments.len() + 1);
+	offsets.push(0u32);
+	for count in &counts {
+		offsets.push(offsets.last().unwrap() + count);
+	}
+	let num_pairs = *offsets.last().unwrap() as usize;
+	if num_pairs == 0 {
+		return (Vec::new(), Vec::new());
+	}
+
+	let offsets_handle = client.create(u32::as_bytes(&offsets));
+	let pairs_handle = client.empty(num_pairs * size_of::<u32>());
+	let (cube_count, cube_dim) = gpu::launch_dimensions(segments.len());
+	unsafe {
+		list_overlapping_boxes_kernel::launch::<gpu::GpuRuntime>(
+			&client,
+			cube_count,
+			cube_dim,
+			ArrayArg::from_raw_parts::<Coordinate>(&boxes_handle, boxes.len(), 1),
+			ArrayArg::from_raw_parts::<u32>(&offsets_handle, offsets.len(), 1),
+			ArrayArg::from_raw_parts::<u32>(&pairs_handle, num_pairs, 1),
+		);
+	}
+	let pairs = u32::from_bytes(&client.read_one(pairs_handle)).to_vec();
+	(0..segments.len()).into_par_iter().fold(|| (Vec::new(), Vec::new()), |(mut splits, mut crossings), i| {
+		for other in &pairs[offsets[i] as usize..offsets[i + 1] as usize] {
+			splits_between(segments, i, *other as usize, &mut splits, &mut crossings);
+		}
+		(splits, crossings)
+	}).reduce(|| (Vec::new(), Vec::new()), concatenate_splits)
+}
+
+/// GPU kernel to count, for every segment, the later segments whose bounding boxes overlap with its
+/// own.
+///
+/// The segments are sorted by their lowest X coordinate, so the search stops at the first later
+/// segment that starts to the right of this segment.
+///
+/// # Arguments
+/// * `boxes` - The bounding box of every segment, as its lowest X, highest X, lowest Y and highest
+/// Y coordinate.
+/// * `counts` - The output, the number of overlapping later segments for every segment.
+#[cube(launch)]
+fn count_overlapping_boxes_kernel(boxes: &Array<Coordinate>, counts: &mut Array<u32>) {
+	let num_segments = boxes.len() / 4;
+	if ABSOLUTE_POS < num_segments {
+		let max_x = boxes[ABSOLUTE_POS * 4 + 1];
+		let min_y = boxes[ABSOLUTE_POS * 4 + 2];
+		let max_y = boxes[ABSOLUTE_POS * 4 + 3];
+		let mut count = 0;
+		let mut other = ABSOLUTE_POS + 1;
+		while other < num_segments {
+			if boxes[other * 4] > max_x {
+				break; //This and all further segments are completely to the right.
+			}
+			if boxes[other * 4 + 2] <= max_y && boxes[other * 4 + 3] >= min_y {
+				count += 1;
+			}
+			other += 1;
+		}
+		counts[ABSOLUTE_POS] = count;
+	}
+}
+
+/// GPU kernel to list, for every segment, the later segments whose bounding boxes overlap with its
+/// own.
+///
+/// # Arguments
+/// * `boxes` - The bounding box of every segment, like in `count_overlapping_boxes_kernel`.
+/// * `offsets` - Where the list of each segment starts in the output, followed by the total length
+/// of the output.
+/// * `pairs` - The output, the indices of the overlapping later segments of each segment.
+#[cube(launch)]
+fn list_overlapping_boxes_kernel(boxes: &Array<Coordinate>, offsets: &Array<u32>, pairs: &mut Array<u32>) {
+	let num_segments = boxes.len() / 4;
+	if ABSOLUTE_POS < num_segments {
+		let max_x = boxes[ABSOLUTE_POS * 4 + 1];
+		let min_y = boxes[ABSOLUTE_POS * 4 + 2];
+		let max_y = boxes[ABSOLUTE_POS * 4 + 3];
+		let mut position = offsets[ABSOLUTE_POS];
+		let mut other = ABSOLUTE_POS + 1;
+		while other < num_segments {
+			if boxes[other * 4] > max_x {
+				break; //This and all further segments are completely to the right.
+			}
+			if boxes[other * 4 + 2] <= max_y && boxes[other * 4 + 3] >= min_y {
+				pairs[position] = other;
+				position += 1;
+			}
+			other += 1;
+		}
+	}
+}
+
+/// Find where two segments need to be split, so that they only meet at their endpoints.
+///
+/// If an endpoint of one segment lies on the other segment, that other segment is split there. If
+/// the segments cross, the intersection point is rounded to the grid, and left to snap rounding.
+///
+/// # Arguments
+/// * `segments` - The normalised segments.
+/// * `first` - The index of one of the segments.
+/// * `second` - The index of the other segment.
+/// * `splits` - The list to add the splits to.
+/// * `crossings` - The list to add the rounded intersection point to, if the segments cross.
+fn splits_between(segments: &[Segment], first: usize, second: usize, splits: &mut Vec<(usize, Point2D)>, crossings: &mut Vec<Point2D>) {
+	let a = &segments[first];
+	let b = &segments[second];
+	if cmp::max(a.start.y, a.end.y) < cmp::min(b.start.y, b.end.y) || cmp::max(b.start.y, b.end.y) < cmp::min(a.start.y, a.end.y) {
+		return; //Their bounding boxes don't overlap.
+	}
+	let side_b_start = orientation(&a.start, &a.end, &b.start);
+	let side_b_end = orientation(&a.start, &a.end, &b.end);
+	let side_a_start = orientation(&b.start, &b.end, &a.start);
+	let side_a_end = orientation(&b.start, &b.end, &a.end);
+	if side_b_start.signum() * side_b_end.signum() < 0 && side_a_start.signum() * side_a_end.signum() < 0 {
+		crossings.push(predicates::intersection(&a.start, &a.end, &b.start, &b.end).expect("Crossing segments must meet.").rounded());
+		return;
+	}
+	//Otherwise they can only touch where an endpoint of one is on the other, including when they are collinear.
+	for (side, point) in [(side_b_start, b.start), (side_b_end, b.end)] {
+		if side == 0 && is_strictly_between(a, &point) {
+			splits.push((first, point));
+		}
+	}
+	for (side, point) in [(side_a_start, a.start), (side_a_end, a.end)] {
+		if side == 0 && is_strictly_between(b, &point) {
+			splits.push((second, point));
+		}
+	}
+}
+
+/// Check whether a point on the line through a segment lies between the endpoints of the segment,
+/// excluding the endpoints themselves.
+///
+/// # Arguments
+/// * `segment` - A normalised segment.
+/// * `point` - A point that is collinear with the segment.
+fn is_strictly_between(segment: &Segment, point: &Point2D) -> bool {
+	segment.start < *point && *point < segment.end
+}
+
+/// Snap round the segments, so that they don't cross each other any more.
+///
+/// The pixels around the endpoints of the segments and around the rounded intersection points are
+/// hot. Every segment that passes through a hot pixel is split at its centre. Snap rounding moves
+/// the segments by less than a pixel, and guarantees that the pieces don't cross each other, though
+/// they may still touch.
+///
+/// To find which segments pass through which hot pixels, the sweep line finds the segments that
+/// meet the diagonals of the two-by-two square around each hot pixel. Every segment that passes
+/// through the pixel also passes through the square, and must meet one of its diagonals. These
+/// candidates are then tested exactly.
+///
+/// # Arguments
+/// * `segments` - The normalised segments.
+/// * `crossings` - The points where segments cross, rounded to the grid.
+/// * `backend` - Whether to search on a single thread or on multiple threads.
+///
+/// # Result
+/// For every split, the index of the segment to split and the point to split it at.
+fn snap_round(segments: &[Segment], crossings: Vec<Point2D>, backend: Backend) -> Vec<(usize, Point2D)> {
+	let mut hot_pixels = crossings;
+	hot_pixels.extend(segments.iter().flat_map(|segment| [segment.start, segment.end]));
+	match backend {
+		Backend::ST => hot_pixels.sort_unstable(),
+		_ => hot_pixels.par_sort_unstable(),
+	}
+	hot_pixels.dedup();
+
+	let mut ends: Vec<(Point2D, Point2D)> = segments.iter().map(|segment| (segment.start, segment.end)).collect();
+	for pixel in &hot_pixels {
+		//Saturate at the edge of the coordinate range. The square gets narrower there, but no segment can pass outside of it anyway.
+		let (left, right) = (pixel.x.saturating_sub(1), pixel.x.saturating_add(1));
+		let (bottom, top) = (pixel.y.saturating_sub(1), pixel.y.saturating_add(1));
+		ends.push((Point2D { x: left, y: bottom }, Point2D { x: right, y: top }));
+		ends.push((Point2D { x: left, y: top }, Point2D { x: right, y: bottom }));
+	}
+	let num_slabs = if backend == Backend::ST { 1 } else { num_slabs(ends.len()) };
+	let pairs = self_intersection::meeting_segments(&ends, num_slabs);
+	//The pairs are sorted by their first index, so the pairs of a segment with a diagonal come after those of a segment with another segment.
+	let is_split = |&(segment, diagonal): &(usize, usize)| {
+		let pixel = hot_pixels[(diagonal - segments.len()) / 2];
+		let segment = &segments[segment];
+		pixel != segment.start && pixel != segment.end && passes_through_pixel(segment, &pixel)
+	};
+	let as_split = |(segment, diagonal): (usize, usize)| (segment, hot_pixels[(diagonal - segments.len()) / 2]);
+	let mut splits: Vec<(usize, Point2D)> = match backend {
+		Backend::ST => pairs.into_iter().filter(|(first, second)| *first < segments.len() && *second >= segments.len()).filter(is_split).map(as_split).collect(),
+		_ => pairs.into_par_iter().filter(|(first, second)| *first < segments.len() && *second >= segments.len()).filter(is_split).map(as_split).collect(),
+	};
+	splits.dedup(); //A segment may meet both diagonals of the same pixel. Those pairs are adjacent.
+	splits
+}
+
+/// Check whether a segment passes through the pixel around a coordinate.
+///
+/// The pixel contains the points that round to the coordinate. Since halves are rounded up, it
+/// includes its left and bottom sides, but not its right and top sides.
+///
+/// # Arguments
+/// * `segment` - The segment to test.
+/// * `pixel` - The coordinate at the centre of the pixel.
+fn passes_through_pixel(segment: &Segment, pixel: &Point2D) -> bool {
+	//Along the segment, a parameter goes from 0 at the start to 1 at the end. Each side of the pixel bounds the parameter with a fraction, which may be excluded.
+	//With coordinates doubled, the sides of the pixel are at integer coordinates. The fractions have positive denominators.
+	let mut lowest = (0, 1, false); //Numerator, denominator, and whether it's excluded.
+	let mut highest = (1, 1, false);
+	for (start, end, centre) in [(segment.start.x, segment.end.x, pixel.x), (segment.start.y, segment.end.y, pixel.y)] {
+		let delta = 2 * (end as i128 - start as i128);
+		let low = 2 * (centre as i128 - start as i128) - 1; //The included side, relative to the start.
+		let high = low + 2; //The excluded side.
+		let (lower, upper) = match delta.cmp(&0) {
+			Ordering::Equal => {
+				if low > 0 || high <= 0 {
+					return false; //Parallel to these sides, and outside of them.
+				}
+				continue;
+			}
+			Ordering::Greater => ((low, delta, false), (high, delta, true)),
+			Ordering::Less => ((-high, -delta, true), (-low, -delta, false)),
+		};
+		match predicates::compare_products(lower.0, lowest.1, lowest.0, lower.1) {
+			Ordering::Greater => lowest = lower,
+			Ordering::Equal => lowest.2 |= lower.2,
+			Ordering::Less => {}
+		}
+		match predicates::compare_products(upper.0, highest.1, highest.0, upper.1) {
+			Ordering::Less => highest = upper,
+			Ordering::Equal => highest.2 |= upper.2,
+			Ordering::Greater => {}
+		}
+	}
+	match predicates::compare_products(lowest.0, highest.1, highest.0, lowest.1) {
+		Ordering::Less => true,
+		Ordering::Equal => !lowest.2 && !highest.2,
+		Ordering::Greater => false,
+	}
+}
+
+/// Split segments at the given points.
+///
+/// # Arguments
+/// * `segments` - The segments to split.
+/// * `splits` - For every split, the index of the segment to split and the point to split it at.
+///
+/// # Result
+/// The pieces of the segments. They are not normalised.
+fn split(segments: &[Segment], mut splits: Vec<(usize, Point2D)>) -> Vec<Segment> {
+	splits.sort_unstable_by_key(|(index, _)| *index);
+	let mut pieces = Vec::with_capacity(segments.len() + splits.len());
+	let mut next_split = 0;
+	for (index, segment) in segments.iter().enumerate() {
+		let first_split = next_split;
+		while next_split < splits.len() && splits[next_split].0 == index {
+			next_split += 1;
+		}
+		let points = &mut splits[first_split..next_split];
+		//Order the points along the segment. After rounding they may be slightly off the segment, so sort by their projection on it.
+		let segment_direction = direction(&segment.start, &segment.end);
+		points.sort_unstable_by_key(|(_, point)| {
+			let relative = direction(&segment.start, point);
+			relative.0 * segment_direction.0 + relative.1 * segment_direction.1
+		});
+		let mut start = segment.start;
+		for (_, point) in points.iter() {
+			pieces.push(Segment { start, end: *point, winding: segment.winding });
+			start = *point;
+		}
+		pieces.push(Segment { start, end: segment.end, winding: segment.winding });
+	}
+	pieces
+}
+
+/// Compute the winding numbers of both shapes on the left side of every segment.
+///
+/// A vertical sweep line moves from left to right through the segments. It keeps track of the
+/// non-vertical segments that it crosses, sorted from bottom to top. Since the segments don't cross
+/// each other, their order doesn't change while they are crossed by the sweep line. When a segment
+/// is added to the sweep line, the region above it is the region below the segment right above it,
+/// so its winding numbers follow from those of the segment above it. For vertical segments, the
+/// region left of it is found the same way, just before the sweep line reaches its X coordinate.
+///
+/// # Arguments
+/// * `segments` - The normalised segments, which only meet at their endpoints.
+///
+/// # Result
+/// For every segment, the winding numbers of the subject and the clip on its left side. For
+/// non-vertical segments, this is the region above the segment.
+fn left_windings(segments: &[Segment]) -> Vec<[i32; 2]> {
+	let mut left = vec![[0, 0]; segments.len()];
+	let below = |left: &Vec<[i32; 2]>, segment: Option<&usize>| match segment {
+		Some(index) => [left[*index][0] - segments[*index].winding[0], left[*index][1] - segments[*index].winding[1]],
+		None => [0, 0], //Nothing above, so this is the outside of both shapes.
+	};
+	let mut sweep_xs: Vec<Coordinate> = segments.iter().flat_map(|segment| [segment.start.x, segment.end.x]).collect();
+	sweep_xs.sort_unstable();
+	sweep_xs.dedup();
+
+	let mut active: Vec<usize> = Vec::new(); //The non-vertical segments that the sweep line crosses, from bottom to top.
+	let mut next = 0; //The first segment that the sweep line hasn't reached yet. The segments are sorted by their start.
+	for x in sweep_xs {
+		let first = next;
+		while next < segments.len() && segments[next].start.x == x {
+			next += 1;
+		}
+		for vertical in (first..next).filter(|index| segments[*index].end.x == x) {
+			let doubled_middle_y = segments[vertical].start.y as i128 + segments[vertical].end.y as i128;
+			let position = active.partition_point(|other| !is_above_left_of(&segments[*other], x, doubled_middle_y));
+			left[vertical] = below(&left, active.get(position));
+		}
+		active.retain(|index| segments[*index].end.x != x);
+		let mut starting: Vec<usize> = (first..next).filter(|index| segments[*index].end.x != x).collect();
+		starting.sort_unstable_by(|a, b| compare_right_of(&segments[*b], &segments[*a], x)); //From top to bottom, so that the segment above is always known.
+		for index in starting {
+			let position = active.partition_point(|other| compare_right_of(&segments[*other], &segments[index], x) == Ordering::Less);
+			left[index] = below(&left, active.get(position));
+			active.insert(position, index);
+		}
+	}
+	left
+}
+
+/// Compare the heights of two non-vertical segments just right of a certain X coordinate.
+///
+/// # Arguments
+/// * `a` - The first segment, which must span the X coordinate.
+/// * `b` - The second segment, which must span the X coordinate.
+/// * `x` - The X coordinate to compare them at, plus an infinitesimal amount.
+///
+/// # Result
+/// Whether the first segment is below, above or at the same place as the second segment.
+fn compare_right_of(a: &Segment, b: &Segment, x: Coordinate) -> Ordering {
+	let a_direction = direction(&a.start, &a.end);
+	let b_direction = direction(&b.start, &b.end);
+	//The heights at X are height / X length of each segment. The X lengths are positive.
+	let a_height = a.start.y as i128 * a_direction.0 + (x as i128 - a.start.x as i128) * a_direction.1;
+	let b_height = b.start.y as i128 * b_direction.0 + (x as i128 - b.start.x as i128) * b_direction.1;
+	(a_height * b_direction.0).cmp(&(b_height * a_direction.0))
+		.then((a_direction.1 * b_direction.0).cmp(&(b_direction.1 * a_direction.0))) //If they meet at X, the steepest is above right of it.
+}
+
+/// Check whether a non-vertical segment is above a point just left of a certain X coordinate.
+///
+/// # Arguments
+/// * `segment` - The segment, which must span the X coordinate.
+/// * `x` - The X coordinate of the point, minus an infinitesimal amount.
+/// * `doubled_y` - Twice the Y coordinate of the point.
+fn is_above_left_of(segment: &Segment, x: Coordinate, doubled_y: i128) -> bool {
+	let segment_direction = direction(&segment.start, &segment.end);
+	let doubled_height = (segment.start.y as i128 * segment_direction.0 + (x as i128 - segment.start.x as i128) * segment_direction.1) * 2;
+	match doubled_height.cmp(&(doubled_y * segment_direction.0)) {
+		Ordering::Greater => true,
+		Ordering::Less => false,
+		Ordering::Equal => segment_direction.1 < 0, //Going through the point, it is above the point just left of it if it goes down.
+	}
+}
+
+/// Link directed edges together into closed contours.
+///
+/// Where multiple contours meet in one vertex, the contours are separated by always taking the
+/// sharpest turn to the left. Collinear vertices are removed from the contours.
+///
+/// # Arguments
+/// * `edges` - The edges, with the inside of the shape on their left. Each vertex must have as many
+/// incoming as outgoing edges.
+///
+/// # Result
+/// The vertices of every contour.
+fn link_contours(mut edges: Vec<(Point2D, Point2D)>) -> Vec<Vec<Point2D>> {
+	edges.sort_unstable();
+	let mut used = vec![false; edges.len()];
+	let mut contours = Vec::new();
+	for first in 0..edges.len() {
+		if used[first] {
+			continue;
+		}
+		let mut contour = Vec::new();
+		let mut current = first;
+		loop {
+			used[current] = true;
+			let (from, to) = edges[current];
+			contour.push(from);
+			let back = direction(&to, &from);
+			let outgoing_start = edges.partition_point(|edge| edge.0 < to);
+			let outgoing_end = edges.partition_point(|edge| edge.0 <= to);
+			let next = (outgoing_start..outgoing_end)
+				.filter(|edge| !used[*edge] || *edge == first)
+				.max_by(|a, b| {
+					//The sharpest turn to the left has the largest angle from the direction back.
+					let a_direction = direction(&edges[*a].0, &edges[*a].1);
+					let b_direction = direction(&edges[*b].0, &edges[*b].1);
+					if angle_from_less(back, a_direction, b_direction) { Ordering::Less } else { Ordering::Greater }
+				});
+			match next {
+				Some(next) if next != first => current = next,
+				_ => break, //Back at the start.
+			}
+		}
+		let contour = without_collinear(contour);
+		if contour.len() >= 3 {
+			contours.push(contour);
+		}
+	}
+	contours
+}
+
+/// Remove the vertices of a closed contour that lie on a straight line between their neighbours.
+fn without_collinear(vertices: Vec<Point2D>) -> Vec<Point2D> {
+	let mut result: Vec<Point2D> = Vec::with_capacity(vertices.len());
+	for vertex in vertices {
+		while result.len() >= 2 && orientation(&result[result.len() - 2], &result[result.len() - 1], &vertex) == 0 {
+			result.pop();
+		}
+		result.push(vertex);
+	}
+	//The seam between the last and the first vertex also needs to be checked.
+	let mut first = 0;
+	loop {
+		let remaining = result.len() - first;
+		if remaining < 3 {
+			break;
+		}
+		if orientation(&result[result.len() - 2], &result[result.len() - 1], &result[first]) == 0 {
+			result.pop();
+		} else if orientation(&result[result.len() - 1], &result[first], &result[first + 1]) == 0 {
+			first += 1;
+		} else {
+			break;
+		}
+	}
+	result.split_off(first)
+}
+
+/// Compare the angles of two directions, measured counter-clockwise from a reference direction.
+///
+/// # Result
+/// Whether the angle of the first direction is smaller than the angle of the second direction.
+fn angle_from_less(reference: (i128, i128), first: (i128, i128), second: (i128, i128)) -> bool {
+	let half = |direction: (i128, i128)| {
+		let cross = reference.0 * direction.1 - reference.1 * direction.0;
+		let dot = reference.0 * direction.0 + reference.1 * direction.1;
+		cross < 0 || (cross == 0 && dot < 0) //In the second half turn, from 180 degrees up to 360 degrees.
+	};
+	let first_half = half(first);
+	let second_half = half(second);
+	if first_half != second_half {
+		return second_half;
+	}
+	first.0 * second.1 - first.1 * second.0 > 0
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+	use crate::Shape2D;
+
+	/// Create a polygon from a list of coordinates.
+	fn polygon(coordinates: &[(Coordinate, Coordinate)]) -> Polygon {
+		Polygon::from_iter(coordinates.iter().map(|(x, y)| Point2D { x: *x, y: *y }))
+	}
+
+	/// Perform an operation with every implementation, and check that they agree.
+	fn all_backends(subject: &Polygon, clip: &Polygon, operation: BooleanOperation, fill_rule: FillRule) -> MultiPolygon {
+		let st = boolean_polygon_st(subject, clip, operation, fill_rule);
+		let mt = boolean_polygon_mt(subject, clip, operation, fill_rule);
+		assert_eq!(st.host_vertices(), mt.host_vertices(), "The single-threaded and multi-threaded implementations must give the same result.");
+		assert_eq!(st.contour_starts(), mt.contour_starts(), "The single-threaded and multi-threaded implementations must give the same contours.");
+		#[cfg(feature = "gpu")]
+		{
+			let gpu = boolean_polygon_gpu(subject, clip, operation, fill_rule);
+			assert_eq!(st.host_vertices(), gpu.host_vertices(), "The GPU implementation must give the same result.");
+		}
+		st
+	}
+
+	/// Test the operations on two overlapping squares.
+	#[test_case(BooleanOperation::UNION, 1, 17500 ; "union")]
+	#[test_case(BooleanOperation::INTERSECTION, 1, 2500 ; "intersection")]
+	#[test_case(BooleanOperation::DIFFERENCE, 1, 7500 ; "difference")]
+	#[test_case(BooleanOperation::XOR, 2, 15000 ; "xor")]
+	fn overlapping_squares(operation: BooleanOperation, num_contours: usize, area: crate::Area) {
+		let square = polygon(&[(0, 0), (100, 0), (100, 100), (0, 100)]);
+		let shifted = polygon(&[(50, 50), (150, 50), (150, 150), (50, 150)]);
+		let result = all_backends(&square, &shifted, operation, FillRule::NON_ZERO);
+		assert_eq!(result.num_contours(), num_contours);
+		assert_eq!(result.area(), area);
+		assert_eq!(boolean_polygon(&square, &shifted, operation, FillRule::NON_ZERO).area(), area, "The automatic choice must give the same result.");
+	}
+
+	/// Test the exact vertices of the union of two overlapping squares.
+	#[test]
+	fn union_vertices() {
+		let square = polygon(&[(0, 0), (100, 0), (100, 100), (0, 100)]);
+		let shifted = polygon(&[(50, 50), (150, 50), (150, 150), (50, 150)]);
+		let result = all_backends(&square, &shifted, BooleanOperation::UNION, FillRule::NON_ZERO);
+		assert_eq!(result.contour(0), &[
+			Point2D { x: 0, y: 0 },
+			Point2D { x: 100, y: 0 },
+			Point2D { x: 100, y: 50 },
+			Point2D { x: 150, y: 50 },
+			Point2D { x: 150, y: 150 },
+			Point2D { x: 50, y: 150 },
+			Point2D { x: 50, y: 100 },
+			Point2D { x: 0, y: 100 },
+		], "The outline goes counter-clockwise, starting at the lowest vertex.");
+	}
+
+	/// Test the operations on two identical squares.
+	#[test_case(BooleanOperation::UNION, 10000 ; "union")]
+	#[test_case(BooleanOperation::INTERSECTION, 10000 ; "intersection")]
+	#[test_case(BooleanOperation::DIFFERENCE, 0 ; "difference")]
+	#[test_case(BooleanOperation::XOR, 0 ; "xor")]
+	fn identical(operation: BooleanOperation, area: crate::Area) {
+		let square = polygon(&[(0, 0), (100, 0), (100, 100), (0, 100)]);
+		let result = all_backends(&square, &square, operation, FillRule::NON_ZERO);
+		assert_eq!(result.area(), area);
+		assert_eq!(result.num_contours(), if area == 0 { 0 } else { 1 }, "The coinciding edges must be merged.");
+	}
+
+	/// Test the operations on two squares that are far apart.
+	#[test_case(BooleanOperation::UNION, 2, 20000 ; "union")]
+	#[test_case(BooleanOperation::INTERSECTION, 0, 0 ; "intersection")]
+	#[test_case(BooleanOperation::DIFFERENCE, 1, 10000 ; "difference")]
+	#[test_case(BooleanOperation::XOR, 2, 20000 ; "xor")]
+	fn disjoint(operation: BooleanOperation, num_contours: usize, area: crate::Area) {
+		let square = polygon(&[(0, 0), (100, 0), (100, 100), (0, 100)]);
+		let far = polygon(&[(500, 0), (600, 0), (600, 100), (500, 100)]);
+		let result = all_backends(&square, &far, operation, FillRule::NON_ZERO);
+		assert_eq!(result.num_contours(), num_contours);
+		assert_eq!(result.area(), area);
+	}
+
+	/// Test merging two squares that share an edge.
+	#[test]
+	fn union_adjacent() {
+		let square = polygon(&[(0, 0), (100, 0), (100, 100), (0, 100)]);
+		let next = polygon(&[(100, 0), (200, 0), (200, 100), (100, 100)]);
+		let result = all_backends(&square, &next, BooleanOperation::UNION, FillRule::NON_ZERO);
+		assert_eq!(result.contour(0), &[
+			Point2D { x: 0, y: 0 },
+			Point2D { x: 200, y: 0 },
+			Point2D { x: 200, y: 100 },
+			Point2D { x: 0, y: 100 },
+		], "The shared edge disappears, and the collinear vertices are removed.");
+	}
+
+	/// Test that squares touching at a corner become separate contours.
+	#[test]
+	fn union_touching_corner() {
+		let square = polygon(&[(0, 0), (100, 0), (100, 100), (0, 100)]);
+		let diagonal = polygon(&[(100, 100), (200, 100), (200, 200), (100, 200)]);
+		let result = all_backends(&square, &diagonal, BooleanOperation::UNION, FillRule::NON_ZERO);
+		assert_eq!(result.num_contours(), 2, "Contours that only touch in a vertex are separated.");
+		assert!(result.contours().all(|contour| contour.len() == 4));
+		assert_eq!(result.area(), 20000);
+	}
+
+	/// Test cutting a hole out of a square, which results in a clockwise contour.
+	#[test]
+	fn difference_hole() {
+		let square = polygon(&[(0, 0), (100, 0), (100, 100), (0, 100)]);
+		let inner = polygon(&[(25, 25), (75, 25), (75, 75), (25, 75)]);
+		let result = all_backends(&square, &inner, BooleanOperation::DIFFERENCE, FillRule::NON_ZERO);
+		assert_eq!(result.num_contours(), 2);
+		assert!(!result.is_hole(0), "The outer contour comes first, since it has the lowest vertex.");
+		assert!(result.is_hole(1), "The inner square became a hole.");
+		assert_eq!(result.area(), 7500);
+	}
+
+	/// Test the operations with a self-intersecting subject, with different fill rules.
+	///
+	/// The bowtie consists of a counter-clockwise triangle on the left and a clockwise triangle on
+	/// the right.
+	#[test_case(FillRule::EVEN_ODD, 2, 500000 ; "even-odd")]
+	#[test_case(FillRule::NON_ZERO, 2, 500000 ; "non-zero")]
+	#[test_case(FillRule::POSITIVE, 1, 250000 ; "positive")]
+	#[test_case(FillRule::NEGATIVE, 1, 250000 ; "negative")]
+	fn self_intersecting(fill_rule: FillRule, num_contours: usize, area: crate::Area) {
+		let bowtie = crate::test::data::polygon::bowtie_1000();
+		let result = all_backends(&bowtie, &Polygon::new(), BooleanOperation::UNION, fill_rule);
+		assert_eq!(result.num_contours(), num_contours);
+		assert_eq!(result.area(), area, "All filled regions become counter-clockwise.");
+	}
+
+	/// Test the difference between the fill rules on a pentagram, which goes around its centre
+	/// twice.
+	#[test]
+	fn pentagram_fill_rules() {
+		let pentagram = crate::test::data::polygon::pentagram_1000();
+		let even_odd = all_backends(&pentagram, &Polygon::new(), BooleanOperation::UNION, FillRule::EVEN_ODD);
+		let non_zero = all_backends(&pentagram, &Polygon::new(), BooleanOperation::UNION, FillRule::NON_ZERO);
+		assert_eq!(even_odd.num_contours(), 5, "With even-odd, the five points are separate, touching triangles.");
+		assert_eq!(non_zero.num_contours(), 1, "With non-zero, the centre is filled, so it's one star.");
+		assert_eq!(non_zero.len(), 10);
+		assert!(non_zero.area() > even_odd.area(), "The centre is only filled with non-zero.");
+	}
+
+	/// Test intersecting shapes whose intersections are not on the grid.
+	#[test]
+	fn rounded_intersections() {
+		let triangle = polygon(&[(0, 0), (7, 0), (0, 3)]);
+		let other = polygon(&[(1, 2), (2, -1), (6, 1)]);
+		let union = all_backends(&triangle, &other, BooleanOperation::UNION, FillRule::NON_ZERO);
+		let intersection = all_backends(&triangle, &other, BooleanOperation::INTERSECTION, FillRule::NON_ZERO);
+		for result in [&union, &intersection] {
+			for contour in result.contours() {
+				assert!(has_no_crossings(contour), "After rounding the intersections, the contours may not cross.");
+			}
+		}
+		let total = 2 * (union.area() + intersection.area());
+		assert!((total - 2 * (triangle.area() + other.area())).abs() <= 10, "Rounding may only change the area slightly.");
+	}
+
+	/// Test a long rectangle across all teeth of a comb, so that its edges are split many times.
+	#[test]
+	fn comb() {
+		let mut teeth = vec![(0, -10), (990, -10)];
+		for tooth in (0..50).rev() {
+			teeth.extend([(tooth * 20 + 10, 0), (tooth * 20 + 10, 1000), (tooth * 20, 1000), (tooth * 20, 0)]);
+		}
+		let comb = polygon(&teeth);
+		let bar = polygon(&[(-5, 400), (1000, 400), (1000, 410), (-5, 410)]);
+		let union = all_backends(&comb, &bar, BooleanOperation::UNION, FillRule::NON_ZERO);
+		assert_eq!(union.area(), comb.area() + bar.area() - 50 * 10 * 10);
+		assert_eq!(union.num_contours(), 50, "The bar closes off a hole between every pair of teeth, and the comb has a single outline.");
+		let intersection = all_backends(&comb, &bar, BooleanOperation::INTERSECTION, FillRule::NON_ZERO);
+		assert_eq!(intersection.area(), 50 * 10 * 10);
+		assert_eq!(intersection.num_contours(), 50, "Every tooth gets its own piece of the bar.");
+	}
+
+	/// Test a difference with multi-polygons that have holes.
+	#[test]
+	fn multipolygon_holes() {
+		let frame = crate::test::data::multi_polygon::square_with_hole_1000();
+		let squares = crate::test::data::multi_polygon::two_squares_1000();
+		let result = boolean_multipolygon_st(&frame, &squares, BooleanOperation::INTERSECTION, FillRule::NON_ZERO);
+		assert_eq!(result.area(), frame.area(), "The frame is entirely inside the first square.");
+		assert_eq!(result.num_contours(), 2);
+		let xor = boolean_multipolygon_mt(&frame, &squares, BooleanOperation::XOR, FillRule::NON_ZERO);
+		assert_eq!(xor.area(), squares.area() - frame.area());
+		assert_eq!(boolean_multipolygon(&frame, &squares, BooleanOperation::XOR, FillRule::NON_ZERO).area(), xor.area(), "The automatic choice must give the same result.");
+	}
+
+	/// Test that the operations on batches give the same results as on each pair of polygons.
+	#[test_case(BooleanOperation::UNION ; "union")]
+	#[test_case(BooleanOperation::INTERSECTION ; "intersection")]
+	#[test_case(BooleanOperation::DIFFERENCE ; "difference")]
+	#[test_case(BooleanOperation::XOR ; "xor")]
+	fn batch_polygons(operation: BooleanOperation) {
+		let subjects = [polygon(&[(0, 0), (100, 0), (100, 100), (0, 100)]), Polygon::new(), crate::test::data::polygon::bowtie_1000()];
+		let clips = [polygon(&[(50, 50), (150, 50), (150, 150), (50, 150)]), polygon(&[(0, 0), (100, 0), (0, 100)]), Polygon::new()];
+		let subject_batch = PolygonBatch::from_iter(subjects.iter().map(|subject| Polygon::from_iter(subject.host_vertices().iter().copied())));
+		let clip_batch = PolygonBatch::from_iter(clips.iter().map(|clip| Polygon::from_iter(clip.host_vertices().iter().copied())));
+		for results in [boolean_batch_st(&subject_batch, &clip_batch, operation, FillRule::NON_ZERO), boolean_batch_mt(&subject_batch, &clip_batch, operation, FillRule::NON_ZERO), boolean_batch(&subject_batch, &clip_batch, operation, FillRule::NON_ZERO)] {
+			assert_eq!(results.len(), subjects.len());
+			for ((subject, clip), result) in subjects.iter().zip(clips.iter()).zip(results.iter()) {
+				let expected = boolean_polygon_st(subject, clip, operation, FillRule::NON_ZERO);
+				assert_eq!(result.host_vertices(), expected.host_vertices());
+				assert_eq!(result.contour_starts(), expected.contour_starts());
+			}
+		}
+	}
+
+	/// Test that the results of the operations fit together, on many different shapes.
+	///
+	/// All operations on the same operands use the same rounded intersections, so their results fit
+	/// together exactly, apart from rounding the areas themselves. Compared to the original operands,
+	/// the rounding of the intersections may change the areas slightly.
+	#[test]
+	fn operations_fit_together() {
+		for seed in 0..20 {
+			let star = |offset: Coordinate| polygon(&(0..12).map(|i| {
+				let radius = if i % 2 == 0 { 1000 } else { 300 + (seed * 37 + i * 91 + offset) % 400 };
+				let angle = i as f64 * std::f64::consts::PI / 6.0;
+				((radius as f64 * angle.cos()).round() as Coordinate + offset, (radius as f64 * angle.sin()).round() as Coordinate)
+			}).collect::<Vec<_>>());
+			let subject = star(0);
+			let clip = star(seed * 40 + 100);
+			let union = all_backends(&subject, &clip, BooleanOperation::UNION, FillRule::NON_ZERO).area();
+			let intersection = all_backends(&subject, &clip, BooleanOperation::INTERSECTION, FillRule::NON_ZERO).area();
+			let difference = all_backends(&subject, &clip, BooleanOperation::DIFFERENCE, FillRule::NON_ZERO).area();
+			let reverse_difference = all_backends(&clip, &subject, BooleanOperation::DIFFERENCE, FillRule::NON_ZERO).area();
+			let xor = all_backends(&subject, &clip, BooleanOperation::XOR, FillRule::NON_ZERO).area();
+			assert!((union - intersection - xor).abs() <= 1, "Seed {seed}: the exclusive or is the union without the intersection.");
+			assert!((xor - difference - reverse_difference).abs() <= 1, "Seed {seed}: the exclusive or consists of both differences.");
+			let operands = subject.area() + clip.area();
+			assert!((union + intersection - operands).abs() <= operands / 100, "Seed {seed}: the intersection is counted twice in the sum of the operands.");
+		}
+	}
+
+	/// Test that snap rounding leaves no crossings between any of the contours of the results.
+	///
+	/// The random polygons on a small grid have many intersections close to each other and to the
+	/// vertices, so rounding one intersection often moves a segment across another.
+	#[test]
+	fn snap_rounding() {
+		let mut state: u64 = 7;
+		let mut random = move || {
+			state ^= state << 13;
+			state ^= state >> 7;
+			state ^= state << 17;
+			(state % 20) as Coordinate
+		};
+		for seed in 0..10 {
+			let subject = polygon(&(0..30).map(|_| (random(), random())).collect::<Vec<_>>());
+			let clip = polygon(&(0..30).map(|_| (random(), random())).collect::<Vec<_>>());
+			for operation in [BooleanOperation::UNION, BooleanOperation::INTERSECTION, BooleanOperation::XOR] {
+				let result = all_backends(&subject, &clip, operation, FillRule::EVEN_ODD);
+				let segments: Vec<Segment> = result.contours().flat_map(|contour| (0..contour.len()).map(|i| Segment { start: contour[i], end: contour[(i + 1) % contour.len()], winding: [1, 0] })).collect();
+				let (_, crossings) = find_splits_st(&normalise(segments, Backend::ST));
+				assert!(crossings.is_empty(), "Seed {seed}, {operation:?}: the contours of the result may touch, but not cross.");
+			}
+		}
+	}
+
+	/// Test which pixels a segment passes through, including where it only touches the sides.
+	///
+	/// The pixel around a coordinate includes its left and bottom sides, but not its right and top
+	/// sides.
+	#[test_case((0, 0), (10, 0), (5, 0), true ; "horizontal through")]
+	#[test_case((0, 0), (10, 0), (5, 1), false ; "horizontal below")]
+	#[test_case((0, 0), (0, 4), (0, 2), true ; "vertical through")]
+	#[test_case((0, 0), (0, 4), (1, 2), false ; "vertical left")]
+	#[test_case((0, 0), (4, 1), (2, 1), true ; "bottom side")]
+	#[test_case((0, 0), (4, 1), (2, 0), true ; "below top side")]
+	#[test_case((0, 0), (1, 1), (1, 0), false ; "diagonal past corner")]
+	#[test_case((0, 0), (1, 1), (0, 1), false ; "diagonal past other corner")]
+	#[test_case((0, 1), (1, 0), (1, 1), true ; "included corner")]
+	#[test_case((0, 1), (1, 0), (0, 0), false ; "excluded corner")]
+	#[test_case((1, 0), (3, 4), (2, 2), true ; "steep through")]
+	#[test_case((1, 0), (3, 4), (3, 2), false ; "steep beside")]
+	fn passes_through_pixel(start: (Coordinate, Coordinate), end: (Coordinate, Coordinate), pixel: (Coordinate, Coordinate), expected: bool) {
+		let segment = Segment { start: Point2D { x: start.0, y: start.1 }, end: Point2D { x: end.0, y: end.1 }, winding: [1, 0] };
+		assert_eq!(super::passes_through_pixel(&segment, &Point2D { x: pixel.0, y: pixel.1 }), expected);
+	}
+
+	/// Check that a closed contour doesn't cross itself, other than in shared vertices.
+	fn has_no_crossings(contour: &[Point2D]) -> bool {
+		let segments: Vec<Segment> = (0..contour.len()).map(|i| Segment { start: contour[i], end: contour[(i + 1) % contour.len()], winding: [1, 0] }).collect();
+		let segments = normalise(segments, Backend::ST);
+		let (splits, crossings) = find_splits_st(&segments);
+		splits.is_empty() && crossings.is_empty()
+	}
+}
\ No newline at end of file
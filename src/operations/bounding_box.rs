@@ -0,0 +1,501 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! This module contains the implementations of operations to find the bounding boxes of geometric
+//! objects.
+//!
+//! The bounding box is found by taking the minimum and maximum of the coordinates of all vertices.
+//! On multiple threads and on the GPU, the vertices are divided in ranges. The box around each
+//! range is found separately, and the boxes of the ranges are then combined.
+
+use cubecl::prelude::*; //For the GPU implementation.
+use rayon::prelude::*; //For multi-threaded implementations.
+
+#[cfg(feature = "gpu")]
+use cubecl::server::Handle; //To find the bounding boxes of vertices on the GPU.
+
+use crate::BoundingBox; //The result of these operations.
+use crate::Coordinate; //The GPU kernel reads coordinates.
+use crate::MultiPolygon; //Find the bounding box of multi-polygons.
+use crate::Point2D; //To find the bounding box of lists of vertices.
+use crate::Polygon; //Find the bounding box of polygons.
+use crate::PolygonBatch; //Find the bounding boxes of batches of polygons at once.
+use crate::cost_model::{self, Backend, Operation}; //To choose the fastest implementation.
+
+/// The number of vertices that each work unit on the GPU combines into one box, when finding the
+/// bounding box of a single shape.
+#[cfg(feature = "gpu")]
+const GPU_RANGE_SIZE: usize = 256;
+
+/// Find the bounding box of a polygon.
+///
+/// This chooses automatically whether to find the bounding box on a single thread, on multiple
+/// threads or on the GPU. The cost model estimates which is fastest, from the number of vertices in
+/// the polygon and from where the most up-to-date copy of the vertices is.
+///
+/// # Examples
+/// ```
+/// use apex::{BoundingBox, Point2D, Polygon};
+/// let poly = Polygon::from_iter([Point2D { x: 0, y: -10 }, Point2D { x: 100, y: 0 }, Point2D { x: 20, y: 50 }]);
+/// assert_eq!(apex::operations::bounding_box::bounding_box_polygon(&poly), BoundingBox { min: Point2D { x: 0, y: -10 }, max: Point2D { x: 100, y: 50 } });
+/// ```
+pub fn bounding_box_polygon(polygon: &Polygon) -> BoundingBox {
+	match cost_model::choose(Operation::BOUNDING_BOX, polygon.len(), polygon.sync_status()) {
+		Backend::ST => bounding_box_polygon_st(polygon),
+		Backend::MT => bounding_box_polygon_mt(polygon),
+		#[cfg(feature = "gpu")]
+		Backend::GPU => bounding_box_polygon_gpu(polygon),
+		#[cfg(not(feature = "gpu"))]
+		Backend::GPU => unreachable!("Without a GPU runtime, the cost model never chooses the GPU."),
+	}
+}
+
+/// Find the bounding box of a polygon.
+///
+/// This implementation is single-threaded and simply includes every vertex in the box one by one.
+///
+/// # Examples
+/// ```
+/// use apex::{BoundingBox, Point2D, Polygon};
+/// let poly = Polygon::from_iter([Point2D { x: 0, y: -10 }, Point2D { x: 100, y: 0 }, Point2D { x: 20, y: 50 }]);
+/// assert_eq!(apex::operations::bounding_box::bounding_box_polygon_st(&poly), BoundingBox { min: Point2D { x: 0, y: -10 }, max: Point2D { x: 100, y: 50 } });
+/// ```
+pub fn bounding_box_polygon_st(polygon: &Polygon) -> BoundingBox {
+	bounding_box_vertices_st(polygon.host_vertices())
+}
+
+/// Find the bounding box of a polygon.
+///
+/// This implementation is multi-threaded. Each thread finds the box around a range of vertices,
+/// after which the boxes are combined.
+///
+/// # Examples
+/// ```
+/// use apex::{BoundingBox, Point2D, Polygon};
+/// let poly = Polygon::from_iter([Point2D { x: 0, y: -10 }, Point2D { x: 100, y: 0 }, Point2D { x: 20, y: 50 }]);
+/// assert_eq!(apex::operations::bounding_box::bounding_box_polygon_mt(&poly), BoundingBox { min: Point2D { x: 0, y: -10 }, max: Point2D { x: 100, y: 50 } });
+/// ```
+pub fn bounding_box_polygon_mt(polygon: &Polygon) -> BoundingBox {
+	bounding_box_vertices_mt(polygon.host_vertices())
+}
+
+/// Find the bounding box of a polygon.
+///
+/// This implementation finds the boxes around ranges of vertices on the GPU. If the vertices are
+/// not on the GPU yet, they are copied there first, and kept there. The boxes of the ranges are
+/// combined on the host.
+///
+/// # Examples
+/// ```
+/// use apex::{BoundingBox, Point2D, Polygon};
+/// let poly = Polygon::from_iter([Point2D { x: 0, y: -10 }, Point2D { x: 100, y: 0 }, Point2D { x: 20, y: 50 }]);
+/// assert_eq!(apex::operations::bounding_box::bounding_box_polygon_gpu(&poly), BoundingBox { min: Point2D { x: 0, y: -10 }, max: Point2D { x: 100, y: 50 } });
+/// ```
+#[cfg(feature = "gpu")]
+pub fn bounding_box_polygon_gpu(polygon: &Polygon) -> BoundingBox {
+	if !crate::detail::gpu::supports_coordinates() {
+		return bounding_box_polygon(polygon);
+	}
+	bounding_box_vertices_gpu(polygon.gpu_vertices())
+}
+
+/// Find the bounding box of a multi-polygon.
+///
+/// This is the box around all of its contours. This chooses automatically whether to find the
+/// bounding box on a single thread, on multiple threads or on the GPU, in the same way as
+/// `bounding_box_polygon`.
+///
+/// # Examples
+/// ```
+/// use apex::{BoundingBox, MultiPolygon, Point2D};
+/// let mut multi = MultiPolygon::new();
+/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+/// multi.push_contour([Point2D { x: 200, y: -50 }, Point2D { x: 300, y: 0 }, Point2D { x: 250, y: 100 }]);
+/// assert_eq!(apex::operations::bounding_box::bounding_box_multipolygon(&multi), BoundingBox { min: Point2D { x: 0, y: -50 }, max: Point2D { x: 300, y: 100 } });
+/// ```
+pub fn bounding_box_multipolygon(multi_polygon: &MultiPolygon) -> BoundingBox {
+	match cost_model::choose(Operation::BOUNDING_BOX, multi_polygon.len(), multi_polygon.sync_status()) {
+		Backend::ST => bounding_box_multipolygon_st(multi_polygon),
+		Backend::MT => bounding_box_multipolygon_mt(multi_polygon),
+		#[cfg(feature = "gpu")]
+		Backend::GPU => bounding_box_multipolygon_gpu(multi_polygon),
+		#[cfg(not(feature = "gpu"))]
+		Backend::GPU => unreachable!("Without a GPU runtime, the cost model never chooses the GPU."),
+	}
+}
+
+/// Find the bounding box of a multi-polygon.
+///
+/// This implementation is single-threaded and simply includes every vertex of every contour in the
+/// box one by one.
+///
+/// # Examples
+/// ```
+/// use apex::{BoundingBox, MultiPolygon, Point2D};
+/// let mut multi = MultiPolygon::new();
+/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+/// assert_eq!(apex::operations::bounding_box::bounding_box_multipolygon_st(&multi), BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 100, y: 100 } });
+/// ```
+pub fn bounding_box_multipolygon_st(multi_polygon: &MultiPolygon) -> BoundingBox {
+	bounding_box_vertices_st(multi_polygon.host_vertices())
+}
+
+/// Find the bounding box of a multi-polygon.
+///
+/// This implementation is multi-threaded. The vertices of all contours are divided over the
+/// threads together, regardless of which contour they belong to.
+///
+/// # Examples
+/// ```
+/// use apex::{BoundingBox, MultiPolygon, Point2D};
+/// let mut multi = MultiPolygon::new();
+/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+/// assert_eq!(apex::operations::bounding_box::bounding_box_multipolygon_mt(&multi), BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 100, y: 100 } });
+/// ```
+pub fn bounding_box_multipolygon_mt(multi_polygon: &MultiPolygon) -> BoundingBox {
+	bounding_box_vertices_mt(multi_polygon.host_vertices())
+}
+
+/// Find the bounding box of a multi-polygon.
+///
+/// This implementation finds the boxes around ranges of vertices of all contours on the GPU, in a
+/// single kernel launch. The boxes of the ranges are combined on the host.
+///
+/// # Examples
+/// ```
+/// use apex::{BoundingBox, MultiPolygon, Point2D};
+/// let mut multi = MultiPolygon::new();
+/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+/// assert_eq!(apex::operations::bounding_box::bounding_box_multipolygon_gpu(&multi), BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 100, y: 100 } });
+/// ```
+#[cfg(feature = "gpu")]
+pub fn bounding_box_multipolygon_gpu(multi_polygon: &MultiPolygon) -> BoundingBox {
+	if !crate::detail::gpu::supports_coordinates() {
+		return bounding_box_multipolygon(multi_polygon);
+	}
+	bounding_box_vertices_gpu(multi_polygon.gpu_vertices())
+}
+
+/// Find the bounding box of every polygon in a batch.
+///
+/// This chooses automatically whether to find the boxes on a single thread, on multiple threads or
+/// on the GPU, in the same way as `bounding_box_polygon`. The cost model considers the total number
+/// of vertices in the batch, since all polygons are processed together.
+///
+/// # Result
+/// The bounding box of each polygon, in the order that they were added to the batch. Polygons
+/// without vertices get an empty box.
+///
+/// # Examples
+/// ```
+/// use apex::{BoundingBox, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 200, y: -50 }, Point2D { x: 300, y: 0 }, Point2D { x: 250, y: 100 }]);
+/// let boxes = apex::operations::bounding_box::bounding_boxes_batch(&batch);
+/// assert_eq!(boxes[1], BoundingBox { min: Point2D { x: 200, y: -50 }, max: Point2D { x: 300, y: 100 } });
+/// ```
+pub fn bounding_boxes_batch(batch: &PolygonBatch) -> Vec<BoundingBox> {
+	match cost_model::choose(Operation::BOUNDING_BOX, batch.len(), batch.sync_status()) {
+		Backend::ST => bounding_boxes_batch_st(batch),
+		Backend::MT => bounding_boxes_batch_mt(batch),
+		#[cfg(feature = "gpu")]
+		Backend::GPU => bounding_boxes_batch_gpu(batch),
+		#[cfg(not(feature = "gpu"))]
+		Backend::GPU => unreachable!("Without a GPU runtime, the cost model never chooses the GPU."),
+	}
+}
+
+/// Find the bounding box of every polygon in a batch.
+///
+/// This implementation is single-threaded. It finds the boxes of the polygons one by one.
+///
+/// # Examples
+/// ```
+/// use apex::{BoundingBox, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+/// assert_eq!(apex::operations::bounding_box::bounding_boxes_batch_st(&batch), vec![BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 100, y: 100 } }]);
+/// ```
+pub fn bounding_boxes_batch_st(batch: &PolygonBatch) -> Vec<BoundingBox> {
+	batch.polygons().map(bounding_box_vertices_st).collect()
+}
+
+/// Find the bounding box of every polygon in a batch.
+///
+/// This implementation is multi-threaded, in a single pass over the vertices of all polygons. The
+/// vertices are divided over the threads regardless of which polygon they belong to. Each thread
+/// produces the boxes around the parts of the polygons that its vertices are in, which are then
+/// combined.
+///
+/// # Examples
+/// ```
+/// use apex::{BoundingBox, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+/// assert_eq!(apex::operations::bounding_box::bounding_boxes_batch_mt(&batch), vec![BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 100, y: 100 } }]);
+/// ```
+pub fn bounding_boxes_batch_mt(batch: &PolygonBatch) -> Vec<BoundingBox> {
+	let vertices = batch.host_vertices();
+	let offsets = batch.offsets();
+	let chunk_size = cost_model::chunk_size(Operation::BOUNDING_BOX, vertices.len()).max(1);
+	let partial_boxes: Vec<(usize, Vec<BoundingBox>)> = vertices.par_chunks(chunk_size).enumerate().map(|(chunk_index, chunk)| {
+		let first = chunk_index * chunk_size;
+		let first_polygon = offsets.partition_point(|offset| *offset <= first) - 1;
+		let mut polygon = first_polygon;
+		let mut boxes = vec![BoundingBox::empty()];
+		for (i, vertex) in (first..).zip(chunk) {
+			while offsets[polygon + 1] <= i { //Skip to the polygon that this vertex is in, which may be after some empty polygons.
+				polygon += 1;
+				boxes.push(BoundingBox::empty());
+			}
+			boxes[polygon - first_polygon].include(vertex);
+		}
+		(first_polygon, boxes)
+	}).collect();
+	let mut boxes = vec![BoundingBox::empty(); batch.num_polygons()];
+	for (first_polygon, partial) in partial_boxes {
+		for (offset, partial_box) in partial.iter().enumerate() {
+			boxes[first_polygon + offset] = boxes[first_polygon + offset].union(partial_box);
+		}
+	}
+	boxes
+}
+
+/// Find the bounding box of every polygon in a batch.
+///
+/// This implementation finds the boxes of all polygons on the GPU, in a single kernel launch. Every
+/// work unit finds the box of one polygon. If the vertices are not on the GPU yet, the whole batch
+/// is copied there at once, and kept there.
+///
+/// # Examples
+/// ```
+/// use apex::{BoundingBox, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+/// assert_eq!(apex::operations::bounding_box::bounding_boxes_batch_gpu(&batch), vec![BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 100, y: 100 } }]);
+/// ```
+#[cfg(feature = "gpu")]
+pub fn bounding_boxes_batch_gpu(batch: &PolygonBatch) -> Vec<BoundingBox> {
+	if !crate::detail::gpu::supports_coordinates() {
+		return bounding_boxes_batch(batch);
+	}
+	if batch.len() == 0 {
+		return vec![BoundingBox::empty(); batch.num_polygons()]; //Can't launch a kernel without any work. Without vertices, all boxes are empty.
+	}
+	bounding_boxes_ranges_gpu(batch.gpu_vertices(), batch.gpu_offsets(), batch.num_polygons())
+}
+
+/// Find the bounding box of a list of vertices on the host, on a single thread.
+///
+/// # Arguments
+/// * `vertices` - The vertices to find the bounding box of.
+pub(crate) fn bounding_box_vertices_st(vertices: &[Point2D]) -> BoundingBox {
+	let mut result = BoundingBox::empty();
+	for vertex in vertices {
+		result.include(vertex);
+	}
+	result
+}
+
+/// Find the bounding box of a list of vertices on the host, on multiple threads.
+///
+/// # Arguments
+/// * `vertices` - The vertices to find the bounding box of.
+fn bounding_box_vertices_mt(vertices: &[Point2D]) -> BoundingBox {
+	let chunk_size = cost_model::chunk_size(Operation::BOUNDING_BOX, vertices.len()).max(1);
+	vertices.par_chunks(chunk_size).map(bounding_box_vertices_st).reduce(BoundingBox::empty, |a, b| a.union(&b))
+}
+
+/// Find the bounding box of a list of vertices on the GPU.
+///
+/// The vertices are divided in ranges of a fixed size. The GPU finds the box around each range, and
+/// those boxes are combined on the host.
+///
+/// # Arguments
+/// * `coordinates_handle` - The vertices, as a flat array of coordinates on the GPU with the X and
+/// Y coordinates interleaved.
+#[cfg(feature = "gpu")]
+fn bounding_box_vertices_gpu(coordinates_handle: &Handle) -> BoundingBox {
+	use crate::detail::gpu;
+
+	let num_vertices = coordinates_handle.size() as usize / (2 * size_of::<Coordinate>());
+	if num_vertices == 0 {
+		return BoundingBox::empty(); //Can't launch a kernel without any work. Without vertices, the box is empty.
+	}
+	let num_ranges = num_vertices.div_ceil(GPU_RANGE_SIZE);
+	let range_starts: Vec<u32> = (0..=num_ranges).map(|range| (range * GPU_RANGE_SIZE).min(num_vertices) as u32).collect();
+	let range_starts_handle = gpu::client().create(u32::as_bytes(&range_starts));
+	bounding_boxes_ranges_gpu(coordinates_handle, &range_starts_handle, num_ranges).iter().fold(BoundingBox::empty(), |a, b| a.union(b))
+}
+
+/// Find the bounding boxes of ranges of vertices on the GPU, in a single kernel launch.
+///
+/// # Arguments
+/// * `coordinates_handle` - The vertices, as a flat array of coordinates on the GPU with the X and
+/// Y coordinates interleaved.
+/// * `range_starts_handle` - The index of the first vertex of each range, followed by the total
+/// number of vertices, as 32-bit unsigned integers on the GPU.
+/// * `num_ranges` - The number of ranges.
+///
+/// # Result
+/// The bounding box of each range. Empty ranges get an empty box.
+#[cfg(feature = "gpu")]
+fn bounding_boxes_ranges_gpu(coordinates_handle: &Handle, range_starts_handle: &Handle, num_ranges: usize) -> Vec<BoundingBox> {
+	use crate::detail::gpu;
+
+	let num_vertices = coordinates_handle.size() as usize / (2 * size_of::<Coordinate>());
+	let client = gpu::client();
+	let boxes_handle = client.empty(num_ranges * 4 * size_of::<Coordinate>());
+	let (cube_count, cube_dim) = gpu::launch_dimensions(num_ranges);
+	unsafe {
+		bounding_boxes_kernel::launch::<gpu::GpuRuntime>(
+			&client,
+			cube_count,
+			cube_dim,
+			ArrayArg::from_raw_parts::<Coordinate>(coordinates_handle, num_vertices * 2, 1),
+			ArrayArg::from_raw_parts::<u32>(range_starts_handle, num_ranges + 1, 1),
+			ArrayArg::from_raw_parts::<Coordinate>(&boxes_handle, num_ranges * 4, 1),
+		);
+	}
+	let bytes = client.read_one(boxes_handle);
+	Coordinate::from_bytes(&bytes).chunks(4).map(|bounds| BoundingBox {
+		min: Point2D { x: bounds[0], y: bounds[1] },
+		max: Point2D { x: bounds[2], y: bounds[3] },
+	}).collect()
+}
+
+/// GPU kernel to find the bounding boxes of ranges of vertices.
+///
+/// Every work unit processes one range, including its vertices in the box one by one.
+///
+/// # Arguments
+/// * `coordinates` - The vertices, with the X and Y coordinates interleaved.
+/// * `range_starts` - The index of the first vertex of each range, followed by the total number of
+/// vertices.
+/// * `boxes` - The output, four coordinates for every range: the minimum X and Y coordinates, then
+/// the maximum X and Y coordinates. Empty ranges get an empty box, with the minimum above the
+/// maximum.
+#[cube(launch)]
+fn bounding_boxes_kernel(coordinates: &Array<Coordinate>, range_starts: &Array<u32>, boxes: &mut Array<Coordinate>) {
+	if ABSOLUTE_POS < range_starts.len() - 1 {
+		let mut min_x = Coordinate::MAX;
+		let mut min_y = Coordinate::MAX;
+		let mut max_x = Coordinate::MIN;
+		let mut max_y = Coordinate::MIN;
+		for vertex in range_starts[ABSOLUTE_POS]..range_starts[ABSOLUTE_POS + 1] {
+			let x = coordinates[vertex * 2];
+			let y = coordinates[vertex * 2 + 1];
+			if x < min_x {
+				min_x = x;
+			}
+			if y < min_y {
+				min_y = y;
+			}
+			if x > max_x {
+				max_x = x;
+			}
+			if y > max_y {
+				max_y = y;
+			}
+		}
+		boxes[ABSOLUTE_POS * 4] = min_x;
+		boxes[ABSOLUTE_POS * 4 + 1] = min_y;
+		boxes[ABSOLUTE_POS * 4 + 2] = max_x;
+		boxes[ABSOLUTE_POS * 4 + 3] = max_y;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+
+	/// The signature of the functions that find the bounding box of polygons.
+	type PolygonBounder = fn(&Polygon) -> BoundingBox;
+
+	/// The signature of the functions that find the bounding boxes of batches of polygons.
+	type BatchBounder = fn(&PolygonBatch) -> Vec<BoundingBox>;
+
+	/// Test the bounding box of polygons.
+	#[test_case(Polygon::new(), BoundingBox::empty() ; "empty")]
+	#[test_case(Polygon::from_iter([Point2D { x: 3, y: -4 }]), BoundingBox { min: Point2D { x: 3, y: -4 }, max: Point2D { x: 3, y: -4 } } ; "point")]
+	#[test_case(crate::test::data::polygon::square_1000(), BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 1000, y: 1000 } } ; "square")]
+	#[test_case(crate::test::data::polygon::pentagram_1000(), BoundingBox { min: Point2D { x: 24, y: 95 }, max: Point2D { x: 976, y: 1000 } } ; "pentagram")]
+	fn bounding_box_polygon_shapes(polygon: Polygon, expected: BoundingBox) {
+		let bounders: [PolygonBounder; 3] = [bounding_box_polygon, bounding_box_polygon_st, bounding_box_polygon_mt];
+		for bounder in bounders {
+			assert_eq!(bounder(&polygon), expected);
+		}
+	}
+
+	/// Test that large polygons get the same bounding box on multiple threads.
+	#[test]
+	fn bounding_box_polygon_large() {
+		let polygon = Polygon::from_iter((0..100000).map(|i| Point2D { x: (i * 7919) % 100003 - 50000, y: (i * 3571) % 99991 }));
+		let expected = bounding_box_polygon_st(&polygon);
+		assert_eq!(bounding_box_polygon_mt(&polygon), expected);
+		assert_eq!(expected.max.y, 99990, "The generated vertices cover the whole range.");
+	}
+
+	/// Test the bounding box of multi-polygons, which is the box around all of their contours.
+	#[test]
+	fn bounding_box_multipolygon_contours() {
+		let multi = crate::test::data::multi_polygon::square_with_hole_1000();
+		let expected = BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 1000, y: 1000 } };
+		assert_eq!(bounding_box_multipolygon(&multi), expected);
+		assert_eq!(bounding_box_multipolygon_st(&multi), expected);
+		assert_eq!(bounding_box_multipolygon_mt(&multi), expected);
+		assert_eq!(bounding_box_multipolygon_st(&MultiPolygon::new()), BoundingBox::empty());
+	}
+
+	/// Test the bounding boxes of the polygons in a batch, which are found separately.
+	#[test]
+	fn bounding_boxes_batch_polygons() {
+		let batch = PolygonBatch::from_iter([crate::test::data::polygon::triangle_1000(), Polygon::new(), Polygon::from_iter([Point2D { x: -5, y: 8 }])]);
+		let expected = vec![
+			bounding_box_polygon_st(&crate::test::data::polygon::triangle_1000()),
+			BoundingBox::empty(),
+			BoundingBox { min: Point2D { x: -5, y: 8 }, max: Point2D { x: -5, y: 8 } },
+		];
+		let bounders: [BatchBounder; 3] = [bounding_boxes_batch, bounding_boxes_batch_st, bounding_boxes_batch_mt];
+		for bounder in bounders {
+			assert_eq!(bounder(&batch), expected);
+			assert_eq!(bounder(&PolygonBatch::new()), vec![]);
+		}
+	}
+
+	/// Test the bounding boxes of a batch with many polygons of different sizes, which get divided
+	/// over the threads in chunks that don't align with the polygons.
+	#[test]
+	fn bounding_boxes_batch_many_polygons() {
+		let mut batch = PolygonBatch::new();
+		for size in 0..100 {
+			batch.push_polygon((0..size * 20).map(|i| Point2D { x: (i * 37) % (size + 1) - size, y: i * size }));
+		}
+		assert_eq!(bounding_boxes_batch_mt(&batch), bounding_boxes_batch_st(&batch));
+	}
+
+	/// Test finding the bounding box of polygons on the GPU.
+	#[cfg(feature = "gpu")]
+	#[test_case(Polygon::new() ; "empty")]
+	#[test_case(crate::test::data::polygon::pentagram_1000() ; "pentagram")]
+	#[test_case(Polygon::from_iter((0..1000).map(|i| Point2D { x: (i * 7919) % 1009, y: -i })) ; "multiple_ranges")]
+	fn bounding_box_polygon_gpu_matches_host(polygon: Polygon) {
+		assert_eq!(bounding_box_polygon_gpu(&polygon), bounding_box_polygon_st(&polygon));
+	}
+
+	/// Test finding the bounding boxes of a batch on the GPU.
+	#[cfg(feature = "gpu")]
+	#[test]
+	fn bounding_boxes_batch_gpu_matches_host() {
+		let batch = PolygonBatch::from_iter([crate::test::data::polygon::l_shape_1000(), Polygon::new(), crate::test::data::polygon::triangle_1000()]);
+		assert_eq!(bounding_boxes_batch_gpu(&batch), bounding_boxes_batch_st(&batch));
+		assert_eq!(bounding_box_multipolygon_gpu(&crate::test::data::multi_polygon::two_squares_1000()), bounding_box_multipolygon_st(&crate::test::data::multi_polygon::two_squares_1000()));
+	}
+}
\ No newline at end of file
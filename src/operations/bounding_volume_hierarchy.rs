@@ -0,0 +1,516 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! This module contains the implementations of building bounding volume hierarchies in bulk.
+//!
+//! All implementations build the same tree, following Karras' method for linear bounding volume
+//! hierarchies. The centre of each bounding box is placed on a Z-order curve, by interleaving the
+//! bits of its quantised X and Y coordinates into a Morton code. The items are sorted by their
+//! codes. Each internal node of the tree then covers a range of the sorted items, which it splits
+//! where the highest bit of the codes changes. Each internal node can find its own range and split
+//! independently of the others, which makes this suitable for the GPU.
+//!
+//! The GPU implementation computes the Morton codes and the splits on the GPU. The codes are sorted
+//! on the host in between. The boxes of the internal nodes are then computed on the host as well,
+//! since the tree is kept there.
+
+use cubecl::prelude::*; //For the GPU implementation.
+use rayon::prelude::*; //For multi-threaded implementations.
+
+use crate::BoundingBox; //The items in the tree are bounding boxes.
+use crate::BoundingVolumeHierarchy; //The result of building.
+use crate::Coordinate; //The GPU kernels read the coordinates of the boxes.
+use crate::cost_model::{self, Backend, Operation}; //To choose the fastest implementation.
+use crate::detail::sync_status::SyncStatus; //The boxes are always on the host.
+
+/// The highest quantised coordinate of the centre of a box, along each dimension.
+///
+/// The X and Y coordinates each get 16 bits, so that their Morton code fits in 32 bits.
+const MORTON_MAX: u32 = 0xFFFF;
+
+/// Build a bounding volume hierarchy around the bounding boxes of a list of items.
+///
+/// This chooses automatically whether to build the tree on a single thread, on multiple threads or
+/// on the GPU. The cost model estimates which is fastest, from the number of items.
+///
+/// # Arguments
+/// * `boxes` - The bounding box of each item.
+///
+/// # Examples
+/// ```
+/// use apex::{BoundingBox, Point2D};
+/// let boxes = [
+/// 	BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 10, y: 10 } },
+/// 	BoundingBox { min: Point2D { x: 20, y: 0 }, max: Point2D { x: 30, y: 10 } },
+/// ];
+/// let hierarchy = apex::operations::bounding_volume_hierarchy::build(&boxes);
+/// assert_eq!(hierarchy.bounds(), BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 30, y: 10 } });
+/// ```
+pub fn build(boxes: &[BoundingBox]) -> BoundingVolumeHierarchy {
+	match cost_model::choose(Operation::SPATIAL_INDEX, boxes.len(), SyncStatus::HOST) {
+		Backend::ST => build_st(boxes),
+		Backend::MT => build_mt(boxes),
+		#[cfg(feature = "gpu")]
+		Backend::GPU => build_gpu(boxes),
+		#[cfg(not(feature = "gpu"))]
+		Backend::GPU => unreachable!("Without a GPU runtime, the cost model never chooses the GPU."),
+	}
+}
+
+/// Build a bounding volume hierarchy around the bounding boxes of a list of items.
+///
+/// This implementation is single-threaded.
+///
+/// # Arguments
+/// * `boxes` - The bounding box of each item.
+///
+/// # Examples
+/// ```
+/// use apex::{BoundingBox, Point2D};
+/// let boxes = [
+/// 	BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 10, y: 10 } },
+/// 	BoundingBox { min: Point2D { x: 20, y: 0 }, max: Point2D { x: 30, y: 10 } },
+/// ];
+/// let hierarchy = apex::operations::bounding_volume_hierarchy::build_st(&boxes);
+/// assert_eq!(hierarchy.intersecting(&BoundingBox { min: Point2D { x: 25, y: 5 }, max: Point2D { x: 25, y: 5 } }), vec![1]);
+/// ```
+pub fn build_st(boxes: &[BoundingBox]) -> BoundingVolumeHierarchy {
+	let scene = boxes.iter().fold(BoundingBox::empty(), |a, b| a.union(b));
+	let mut keys: Vec<(u32, usize)> = boxes.iter().map(|bounds| morton_code(bounds, &scene)).zip(0..).collect();
+	keys.sort_unstable();
+	let codes: Vec<u32> = keys.iter().map(|(code, _)| *code).collect();
+	let children = (0..boxes.len().saturating_sub(1)).map(|node| node_children(&codes, node)).collect();
+	BoundingVolumeHierarchy::from_parts(boxes, children, keys.into_iter().map(|(_, item)| item).collect())
+}
+
+/// Build a bounding volume hierarchy around the bounding boxes of a list of items.
+///
+/// This implementation is multi-threaded. The Morton codes are computed and sorted in parallel, and
+/// the internal nodes each find their children in parallel.
+///
+/// # Arguments
+/// * `boxes` - The bounding box of each item.
+///
+/// # Examples
+/// ```
+/// use apex::{BoundingBox, Point2D};
+/// let boxes = [
+/// 	BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 10, y: 10 } },
+/// 	BoundingBox { min: Point2D { x: 20, y: 0 }, max: Point2D { x: 30, y: 10 } },
+/// ];
+/// let hierarchy = apex::operations::bounding_volume_hierarchy::build_mt(&boxes);
+/// assert_eq!(hierarchy.intersecting(&BoundingBox { min: Point2D { x: 25, y: 5 }, max: Point2D { x: 25, y: 5 } }), vec![1]);
+/// ```
+pub fn build_mt(boxes: &[BoundingBox]) -> BoundingVolumeHierarchy {
+	let chunk_size = cost_model::chunk_size(Operation::SPATIAL_INDEX, boxes.len()).max(1);
+	let scene = boxes.par_iter().with_min_len(chunk_size).fold(BoundingBox::empty, |a, b| a.union(b)).reduce(BoundingBox::empty, |a, b| a.union(&b));
+	let mut keys: Vec<(u32, usize)> = boxes.par_iter().with_min_len(chunk_size).map(|bounds| morton_code(bounds, &scene)).zip(0..boxes.len()).collect();
+	keys.par_sort_unstable();
+	let codes: Vec<u32> = keys.par_iter().map(|(code, _)| *code).collect();
+	let children = (0..boxes.len().saturating_sub(1)).into_par_iter().with_min_len(chunk_size).map(|node| node_children(&codes, node)).collect();
+	BoundingVolumeHierarchy::from_parts(boxes, children, keys.into_par_iter().map(|(_, item)| item).collect())
+}
+
+/// Build a bounding volume hierarchy around the bounding boxes of a list of items.
+///
+/// This implementation computes the Morton codes of the boxes on the GPU, sorts them on the host,
+/// and then finds the children of all internal nodes on the GPU. The resulting tree is the same as
+/// with the other implementations.
+///
+/// # Arguments
+/// * `boxes` - The bounding box of each item.
+///
+/// # Examples
+/// ```
+/// use apex::{BoundingBox, Point2D};
+/// let boxes = [
+/// 	BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 10, y: 10 } },
+/// 	BoundingBox { min: Point2D { x: 20, y: 0 }, max: Point2D { x: 30, y: 10 } },
+/// ];
+/// let hierarchy = apex::operations::bounding_volume_hierarchy::build_gpu(&boxes);
+/// assert_eq!(hierarchy.intersecting(&BoundingBox { min: Point2D { x: 25, y: 5 }, max: Point2D { x: 25, y: 5 } }), vec![1]);
+/// ```
+#[cfg(feature = "gpu")]
+pub fn build_gpu(boxes: &[BoundingBox]) -> BoundingVolumeHierarchy {
+	use crate::detail::gpu;
+
+	if !gpu::supports_coordinates() {
+		return build(boxes);
+	}
+	let num_items = boxes.len();
+	if num_items < 2 {
+		return build_st(boxes); //Without internal nodes, there is nothing to compute.
+	}
+	let scene = boxes.iter().fold(BoundingBox::empty(), |a, b| a.union(b));
+	let client = gpu::client();
+	let coordinates: Vec<Coordinate> = boxes.iter().flat_map(|bounds| [bounds.min.x, bounds.min.y, bounds.max.x, bounds.max.y]).collect();
+	let boxes_handle = gpu::upload_coordinates(&coordinates);
+	let scene_handle = client.create(Coordinate::as_bytes(&[scene.min.x, scene.min.y, scene.max.x, scene.max.y]));
+	let codes_handle = client.empty(num_items * size_of::<u32>());
+	let (cube_count, cube_dim) = gpu::launch_dimensions(num_items);
+	unsafe {
+		morton_codes_kernel::launch::<gpu::GpuRuntime>(
+			&client,
+			cube_count,
+			cube_dim,
+			ArrayArg::from_raw_parts::<Coordinate>(&boxes_handle, num_items * 4, 1),
+			ArrayArg::from_raw_parts::<Coordinate>(&scene_handle, 4, 1),
+			ArrayArg::from_raw_parts::<u32>(&codes_handle, num_items, 1),
+		);
+	}
+	let mut keys: Vec<(u32, usize)> = u32::from_bytes(&client.read_one(codes_handle)).iter().copied().zip(0..).collect();
+	keys.par_sort_unstable();
+
+	let codes: Vec<u32> = keys.iter().map(|(code, _)| *code).collect();
+	let sorted_codes_handle = client.create(u32::as_bytes(&codes));
+	let children_handle = client.empty((num_items - 1) * 2 * size_of::<u32>());
+	let (cube_count, cube_dim) = gpu::launch_dimensions(num_items - 1);
+	unsafe {
+		node_children_kernel::launch::<gpu::GpuRuntime>(
+			&client,
+			cube_count,
+			cube_dim,
+			ArrayArg::from_raw_parts::<u32>(&sorted_codes_handle, num_items, 1),
+			ArrayArg::from_raw_parts::<u32>(&children_handle, (num_items - 1) * 2, 1),
+		);
+	}
+	let children = u32::from_bytes(&client.read_one(children_handle)).chunks(2).map(|pair| [pair[0] as usize, pair[1] as usize]).collect();
+	BoundingVolumeHierarchy::from_parts(boxes, children, keys.into_iter().map(|(_, item)| item).collect())
+}
+
+/// Compute the Morton code of the centre of a bounding box.
+///
+/// # Arguments
+/// * `bounds` - The box to compute the code of.
+/// * `scene` - The box around all boxes. The centres are quantised relative to this box.
+fn morton_code(bounds: &BoundingBox, scene: &BoundingBox) -> u32 {
+	let x = quantise(bounds.min.x as i128 + bounds.max.x as i128, scene.min.x as i128, scene.max.x as i128);
+	let y = quantise(bounds.min.y as i128 + bounds.max.y as i128, scene.min.y as i128, scene.max.y as i128);
+	expand_bits(x) | (expand_bits(y) << 1)
+}
+
+/// Scale a coordinate of the centre of a box to 16 bits.
+///
+/// # Arguments
+/// * `doubled_centre` - Twice the coordinate of the centre, so that it is a whole number.
+/// * `scene_min` - The lowest coordinate of all boxes.
+/// * `scene_max` - The highest coordinate of all boxes.
+fn quantise(doubled_centre: i128, scene_min: i128, scene_max: i128) -> u32 {
+	let range = (2 * (scene_max - scene_min)).max(1); //All boxes could be at the same coordinate.
+	((doubled_centre - 2 * scene_min) * MORTON_MAX as i128 / range).clamp(0, MORTON_MAX as i128) as u32
+}
+
+/// Spread the lowest 16 bits of a number out over 32 bits, with a zero between each two bits.
+///
+/// # Arguments
+/// * `value` - The number to spread out.
+fn expand_bits(value: u32) -> u32 {
+	let mut bits = value & MORTON_MAX;
+	bits = (bits | (bits << 8)) & 0x00FF00FF;
+	bits = (bits | (bits << 4)) & 0x0F0F0F0F;
+	bits = (bits | (bits << 2)) & 0x33333333;
+	(bits | (bits << 1)) & 0x55555555
+}
+
+/// Find the two children of an internal node of the tree.
+///
+/// # Arguments
+/// * `codes` - The sorted Morton codes of all items.
+/// * `node` - The index of the internal node.
+///
+/// # Result
+/// The indices of the two children. Leaves are numbered after the internal nodes.
+fn node_children(codes: &[u32], node: usize) -> [usize; 2] {
+	let i = node as i64;
+	//Find in which direction the range of this node extends, and how far.
+	let direction = if common_prefix(codes, i, i + 1) > common_prefix(codes, i, i - 1) { 1 } else { -1 };
+	let min_prefix = common_prefix(codes, i, i - direction);
+	let mut max_length = 2;
+	while common_prefix(codes, i, i + max_length * direction) > min_prefix {
+		max_length *= 2;
+	}
+	let mut length = 0;
+	let mut step = max_length / 2;
+	while step >= 1 {
+		if common_prefix(codes, i, i + (length + step) * direction) > min_prefix {
+			length += step;
+		}
+		step /= 2;
+	}
+	let j = i + length * direction;
+
+	//Find where the codes in the range start to differ in their highest bit.
+	let node_prefix = common_prefix(codes, i, j);
+	let mut split = 0;
+	step = length;
+	loop {
+		step = (step + 1) / 2;
+		if common_prefix(codes, i, i + (split + step) * direction) > node_prefix {
+			split += step;
+		}
+		if step <= 1 {
+			break;
+		}
+	}
+	let gamma = i + split * direction + std::cmp::min(direction, 0);
+
+	let first_leaf = codes.len() - 1;
+	let left = if std::cmp::min(i, j) == gamma { first_leaf + gamma as usize } else { gamma as usize };
+	let right = if std::cmp::max(i, j) == gamma + 1 { first_leaf + gamma as usize + 1 } else { gamma as usize + 1 };
+	[left, right]
+}
+
+/// Find the length of the common prefix of the Morton codes of two items.
+///
+/// Items with the same code are distinguished by their position, as if that was appended to the
+/// code.
+///
+/// # Arguments
+/// * `codes` - The sorted Morton codes of all items.
+/// * `i` - The position of the first item.
+/// * `j` - The position of the second item, which may be out of range.
+///
+/// # Result
+/// The number of leading bits that the two codes have in common, or -1 if the second position is
+/// out of range.
+fn common_prefix(codes: &[u32], i: i64, j: i64) -> i64 {
+	if j < 0 || j >= codes.len() as i64 {
+		return -1;
+	}
+	let (a, b) = (codes[i as usize], codes[j as usize]);
+	if a == b {
+		32 + ((i ^ j) as u32).leading_zeros() as i64
+	} else {
+		(a ^ b).leading_zeros() as i64
+	}
+}
+
+/// GPU kernel to compute the Morton codes of the centres of bounding boxes.
+///
+/// Every work unit computes the code of one box, in the same way as `morton_code`.
+///
+/// # Arguments
+/// * `boxes` - The boxes, four coordinates each: the minimum X and Y coordinates, then the maximum
+/// X and Y coordinates.
+/// * `scene` - The box around all boxes, in the same layout.
+/// * `codes` - The output, the Morton code of each box.
+#[cube(launch)]
+fn morton_codes_kernel(boxes: &Array<Coordinate>, scene: &Array<Coordinate>, codes: &mut Array<u32>) {
+	if ABSOLUTE_POS < codes.len() {
+		let x = quantise_gpu(boxes[ABSOLUTE_POS * 4], boxes[ABSOLUTE_POS * 4 + 2], scene[0], scene[2]);
+		let y = quantise_gpu(boxes[ABSOLUTE_POS * 4 + 1], boxes[ABSOLUTE_POS * 4 + 3], scene[1], scene[3]);
+		codes[ABSOLUTE_POS] = expand_bits_gpu(x) | (expand_bits_gpu(y) << 1);
+	}
+}
+
+/// Scale a coordinate of the centre of a box to 16 bits on the GPU, in the same way as `quantise`.
+///
+/// With 32-bit coordinates, all intermediate values fit in 64 bits.
+///
+/// # Arguments
+/// * `min` - The lowest coordinate of the box.
+/// * `max` - The highest coordinate of the box.
+/// * `scene_min` - The lowest coordinate of all boxes.
+/// * `scene_max` - The highest coordinate of all boxes.
+#[cube]
+fn quantise_gpu(min: Coordinate, max: Coordinate, scene_min: Coordinate, scene_max: Coordinate) -> u32 {
+	let offset = i64::cast_from(min) + i64::cast_from(max) - i64::cast_from(scene_min) - i64::cast_from(scene_min);
+	let mut range = i64::cast_from(scene_max) - i64::cast_from(scene_min);
+	range += range;
+	if range < i64::new(1) {
+		range = i64::new(1);
+	}
+	let mut quantised = offset * i64::cast_from(MORTON_MAX) / range;
+	if quantised < i64::new(0) {
+		quantised = i64::new(0);
+	}
+	if quantised > i64::cast_from(MORTON_MAX) {
+		quantised = i64::cast_from(MORTON_MAX);
+	}
+	u32::cast_from(quantised)
+}
+
+/// Spread the lowest 16 bits of a number out over 32 bits on the GPU, in the same way as
+/// `expand_bits`.
+///
+/// # Arguments
+/// * `value` - The number to spread out.
+#[cube]
+fn expand_bits_gpu(value: u32) -> u32 {
+	let mut bits = value & MORTON_MAX;
+	bits = (bits | (bits << 8)) & 0x00FF00FF;
+	bits = (bits | (bits << 4)) & 0x0F0F0F0F;
+	bits = (bits | (bits << 2)) & 0x33333333;
+	(bits | (bits << 1)) & 0x55555555
+}
+
+/// GPU kernel to find the children of the internal nodes of the tree.
+///
+/// Every work unit finds the children of one internal node, in the same way as `node_children`.
+///
+/// # Arguments
+/// * `codes` - The sorted Morton codes of all items.
+/// * `children` - The output, the indices of the two children of each internal node.
+#[cube(launch)]
+fn node_children_kernel(codes: &Array<u32>, children: &mut Array<u32>) {
+	let num_leaves = codes.len();
+	if ABSOLUTE_POS < num_leaves - 1 {
+		let i = i32::cast_from(ABSOLUTE_POS);
+		let mut direction = -1;
+		if common_prefix_gpu(codes, i, i + 1) > common_prefix_gpu(codes, i, i - 1) {
+			direction = 1;
+		}
+		let min_prefix = common_prefix_gpu(codes, i, i - direction);
+		let mut max_length = 2;
+		while common_prefix_gpu(codes, i, i + max_length * direction) > min_prefix {
+			max_length *= 2;
+		}
+		let mut length = 0;
+		let mut step = max_length / 2;
+		while step >= 1 {
+			if common_prefix_gpu(codes, i, i + (length + step) * direction) > min_prefix {
+				length += step;
+			}
+			step /= 2;
+		}
+		let j = i + length * direction;
+
+		let node_prefix = common_prefix_gpu(codes, i, j);
+		let mut split = 0;
+		step = length;
+		loop {
+			step = (step + 1) / 2;
+			if common_prefix_gpu(codes, i, i + (split + step) * direction) > node_prefix {
+				split += step;
+			}
+			if step <= 1 {
+				break;
+			}
+		}
+		let mut gamma = i + split * direction;
+		if direction < 0 {
+			gamma -= 1;
+		}
+
+		let mut first = i;
+		let mut last = j;
+		if direction < 0 {
+			first = j;
+			last = i;
+		}
+		let first_leaf = num_leaves - 1;
+		let mut left = u32::cast_from(gamma);
+		if first == gamma {
+			left += first_leaf;
+		}
+		let mut right = u32::cast_from(gamma + 1);
+		if last == gamma + 1 {
+			right += first_leaf;
+		}
+		children[ABSOLUTE_POS * 2] = left;
+		children[ABSOLUTE_POS * 2 + 1] = right;
+	}
+}
+
+/// Find the length of the common prefix of the Morton codes of two items on the GPU, in the same
+/// way as `common_prefix`.
+///
+/// # Arguments
+/// * `codes` - The sorted Morton codes of all items.
+/// * `i` - The position of the first item.
+/// * `j` - The position of the second item, which may be out of range.
+#[cube]
+fn common_prefix_gpu(codes: &Array<u32>, i: i32, j: i32) -> i32 {
+	let mut result = i32::new(-1);
+	if j >= 0 && j < i32::cast_from(codes.len()) {
+		let a = codes[u32::cast_from(i)];
+		let b = codes[u32::cast_from(j)];
+		if a == b {
+			result = 32 + i32::cast_from(u32::leading_zeros(u32::cast_from(i ^ j)));
+		} else {
+			result = i32::cast_from(u32::leading_zeros(a ^ b));
+		}
+	}
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Point2D;
+
+	/// The signature of the functions that build bounding volume hierarchies.
+	type Builder = fn(&[BoundingBox]) -> BoundingVolumeHierarchy;
+
+	/// Create scattered boxes of various sizes, some of which overlap.
+	fn scattered(num_boxes: i32) -> Vec<BoundingBox> {
+		(0..num_boxes).map(|i| {
+			let x = (i * 7919) % 1009;
+			let y = (i * 10007) % 997;
+			BoundingBox { min: Point2D { x: x as _, y: y as _ }, max: Point2D { x: (x + i % 13) as _, y: (y + i % 7) as _ } }
+		}).collect()
+	}
+
+	/// Test that the codes of boxes interleave the bits of their quantised centres.
+	#[test]
+	fn morton_codes() {
+		let scene = BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 65535, y: 65535 } };
+		let point = |x, y| BoundingBox { min: Point2D { x, y }, max: Point2D { x, y } };
+		assert_eq!(morton_code(&point(0, 0), &scene), 0);
+		assert_eq!(morton_code(&point(1, 0), &scene), 0b01);
+		assert_eq!(morton_code(&point(0, 1), &scene), 0b10);
+		assert_eq!(morton_code(&point(3, 1), &scene), 0b0111);
+		assert_eq!(morton_code(&point(65535, 65535), &scene), u32::MAX);
+		assert_eq!(morton_code(&point(7, 7), &point(7, 7)), 0, "If all boxes are at the same place, the scene has no size.");
+	}
+
+	/// Test that every item ends up in exactly one leaf, and that all leaves can be reached from
+	/// the root, with each implementation.
+	#[test]
+	fn build_structure() {
+		let builders: [Builder; 3] = [build, build_st, build_mt];
+		for builder in builders {
+			for num_boxes in [0, 1, 2, 3, 17, 500] {
+				let boxes = scattered(num_boxes);
+				let hierarchy = builder(&boxes);
+				assert_eq!(hierarchy.len(), boxes.len());
+				let everything = BoundingBox { min: Point2D { x: -1, y: -1 }, max: Point2D { x: 2000, y: 2000 } };
+				assert_eq!(hierarchy.intersecting(&everything), (0..boxes.len()).collect::<Vec<usize>>(), "Every item must be found exactly once.");
+				for (item, bounds) in boxes.iter().enumerate() {
+					assert_eq!(hierarchy.item_box(item), *bounds);
+				}
+			}
+		}
+	}
+
+	/// Test building a tree around boxes that all have the same Morton code.
+	#[test]
+	fn build_equal_codes() {
+		let boxes = vec![BoundingBox { min: Point2D { x: 5, y: 5 }, max: Point2D { x: 6, y: 6 } }; 100];
+		let hierarchy = build_st(&boxes);
+		assert_eq!(hierarchy.intersecting(&boxes[0]), (0..100).collect::<Vec<usize>>());
+		assert_eq!(hierarchy.nearest(&Point2D { x: 0, y: 0 }, 3), vec![0, 1, 2]);
+	}
+
+	/// Test that building on multiple threads results in the same tree as on a single thread.
+	#[test]
+	fn build_mt_matches_st() {
+		let boxes = scattered(20000);
+		assert_eq!(build_mt(&boxes), build_st(&boxes));
+	}
+
+	/// Test that building on the GPU results in the same tree as on the host.
+	#[cfg(all(feature = "gpu", not(feature = "coordinate64")))]
+	#[test]
+	fn build_gpu_matches_st() {
+		for num_boxes in [0, 1, 2, 1000] {
+			let boxes = scattered(num_boxes);
+			assert_eq!(build_gpu(&boxes), build_st(&boxes));
+		}
+	}
+}
\ No newline at end of file
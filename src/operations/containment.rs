@@ -0,0 +1,624 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! This module contains the implementations of operations to find whether points are inside
+//! geometric objects.
+//!
+//! The winding number of a shape around a point is found by casting a ray from the point to the
+//! right, and counting the edges that cross it. Edges going up count as +1, edges going down as -1.
+//! To count vertices on the ray only once, each edge includes its lowest endpoint but not its
+//! highest. Which side of the ray an edge crosses on is decided with exact integer arithmetic, so
+//! the result is always correct, also for points very close to an edge. Points that are exactly on
+//! an edge are reported as being on the boundary, regardless of the fill rule.
+//!
+//! To test many points against the same polygon, there are batch implementations. These process
+//! the points in parallel, on multiple threads or on the GPU.
+
+use std::cmp; //To check whether points are within the bounding box of edges.
+use cubecl::prelude::*; //For the GPU implementation.
+use rayon::prelude::*; //For multi-threaded implementations.
+
+use crate::Containment; //To return where the points are.
+use crate::Coordinate; //The GPU kernel reads coordinates.
+use crate::FillRule; //To decide which regions are inside.
+use crate::MultiPolygon; //Test points against multi-polygons.
+use crate::Point2D; //The points to test.
+use crate::Polygon; //Test points against polygons.
+use crate::PolygonBatch; //Test a point against batches of polygons at once.
+use crate::cost_model::{self, Backend, Operation}; //To choose the fastest implementation.
+use crate::predicates; //To find on which side of the edges the points are.
+
+/// Find where a point is relative to a polygon.
+///
+/// This chooses automatically whether to test the edges of the polygon on a single thread or on
+/// multiple threads. The cost model estimates which is fastest, from the number of vertices in the
+/// polygon.
+///
+/// # Arguments
+/// * `polygon` - The polygon to test the point against.
+/// * `point` - The point to test.
+/// * `fill_rule` - Which regions of the polygon are inside, if it intersects itself.
+///
+/// # Result
+/// Whether the point is inside, outside or on the boundary of the polygon.
+///
+/// # Examples
+/// ```
+/// use apex::{Containment, FillRule, Point2D, Polygon};
+/// let square = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// assert_eq!(apex::operations::containment::containment_polygon(&square, &Point2D { x: 50, y: 50 }, FillRule::NON_ZERO), Containment::INSIDE);
+/// assert_eq!(apex::operations::containment::containment_polygon(&square, &Point2D { x: 150, y: 50 }, FillRule::NON_ZERO), Containment::OUTSIDE);
+/// assert_eq!(apex::operations::containment::containment_polygon(&square, &Point2D { x: 100, y: 50 }, FillRule::NON_ZERO), Containment::BOUNDARY);
+/// ```
+pub fn containment_polygon(polygon: &Polygon, point: &Point2D, fill_rule: FillRule) -> Containment {
+	match cost_model::choose(Operation::CONTAINMENT, polygon.len(), polygon.sync_status()) {
+		Backend::ST => containment_polygon_st(polygon, point, fill_rule),
+		Backend::MT => containment_polygon_mt(polygon, point, fill_rule),
+		#[cfg(feature = "gpu")]
+		Backend::GPU => containment_polygon_batch_gpu(polygon, &[*point], fill_rule)[0],
+		#[cfg(not(feature = "gpu"))]
+		Backend::GPU => unreachable!("Without a GPU runtime, the cost model never chooses the GPU."),
+	}
+}
+
+/// Find where a point is relative to a polygon, on a single thread.
+///
+/// # Arguments
+/// * `polygon` - The polygon to test the point against.
+/// * `point` - The point to test.
+/// * `fill_rule` - Which regions of the polygon are inside, if it intersects itself.
+///
+/// # Result
+/// Whether the point is inside, outside or on the boundary of the polygon.
+///
+/// # Examples
+/// ```
+/// use apex::{Containment, FillRule, Point2D, Polygon};
+/// let square = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// assert_eq!(apex::operations::containment::containment_polygon_st(&square, &Point2D { x: 0, y: 0 }, FillRule::EVEN_ODD), Containment::BOUNDARY);
+/// ```
+pub fn containment_polygon_st(polygon: &Polygon, point: &Point2D, fill_rule: FillRule) -> Containment {
+	classify(winding_number_st(polygon.host_vertices(), point), fill_rule)
+}
+
+/// Find where a point is relative to a polygon, on multiple threads.
+///
+/// The edges of the polygon are divided over the threads.
+///
+/// # Arguments
+/// * `polygon` - The polygon to test the point against.
+/// * `point` - The point to test.
+/// * `fill_rule` - Which regions of the polygon are inside, if it intersects itself.
+///
+/// # Result
+/// Whether the point is inside, outside or on the boundary of the polygon.
+///
+/// # Examples
+/// ```
+/// use apex::{Containment, FillRule, Point2D, Polygon};
+/// let square = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// assert_eq!(apex::operations::containment::containment_polygon_mt(&square, &Point2D { x: 1, y: 99 }, FillRule::EVEN_ODD), Containment::INSIDE);
+/// ```
+pub fn containment_polygon_mt(polygon: &Polygon, point: &Point2D, fill_rule: FillRule) -> Containment {
+	classify(winding_number_mt(polygon.host_vertices(), point), fill_rule)
+}
+
+/// Find where a point is relative to a multi-polygon.
+///
+/// The winding numbers of all contours are added up, so holes cancel out the outer contours around
+/// them. This chooses automatically whether to test the edges on a single thread or on multiple
+/// threads, like `containment_polygon`.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to test the point against.
+/// * `point` - The point to test.
+/// * `fill_rule` - Which regions of the multi-polygon are inside, if its contours overlap.
+///
+/// # Result
+/// Whether the point is inside, outside or on the boundary of the multi-polygon.
+///
+/// # Examples
+/// ```
+/// use apex::{Containment, FillRule, MultiPolygon, Point2D};
+/// let mut frame = MultiPolygon::new();
+/// frame.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// frame.push_contour([Point2D { x: 25, y: 25 }, Point2D { x: 25, y: 75 }, Point2D { x: 75, y: 75 }, Point2D { x: 75, y: 25 }]); //A hole.
+/// assert_eq!(apex::operations::containment::containment_multipolygon(&frame, &Point2D { x: 10, y: 50 }, FillRule::NON_ZERO), Containment::INSIDE);
+/// assert_eq!(apex::operations::containment::containment_multipolygon(&frame, &Point2D { x: 50, y: 50 }, FillRule::NON_ZERO), Containment::OUTSIDE);
+/// ```
+pub fn containment_multipolygon(multi_polygon: &MultiPolygon, point: &Point2D, fill_rule: FillRule) -> Containment {
+	match cost_model::choose(Operation::CONTAINMENT, multi_polygon.len(), multi_polygon.sync_status()) {
+		Backend::ST => containment_multipolygon_st(multi_polygon, point, fill_rule),
+		Backend::MT => containment_multipolygon_mt(multi_polygon, point, fill_rule),
+		Backend::GPU => unreachable!("There is no GPU implementation for multi-polygons, so the cost model never chooses the GPU."),
+	}
+}
+
+/// Find where a point is relative to a multi-polygon, on a single thread.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to test the point against.
+/// * `point` - The point to test.
+/// * `fill_rule` - Which regions of the multi-polygon are inside, if its contours overlap.
+///
+/// # Result
+/// Whether the point is inside, outside or on the boundary of the multi-polygon.
+///
+/// # Examples
+/// ```
+/// use apex::{Containment, FillRule, MultiPolygon, Point2D};
+/// let mut frame = MultiPolygon::new();
+/// frame.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// frame.push_contour([Point2D { x: 25, y: 25 }, Point2D { x: 25, y: 75 }, Point2D { x: 75, y: 75 }, Point2D { x: 75, y: 25 }]); //A hole.
+/// assert_eq!(apex::operations::containment::containment_multipolygon_st(&frame, &Point2D { x: 25, y: 50 }, FillRule::NON_ZERO), Containment::BOUNDARY);
+/// ```
+pub fn containment_multipolygon_st(multi_polygon: &MultiPolygon, point: &Point2D, fill_rule: FillRule) -> Containment {
+	let winding_number = multi_polygon.contours().map(|contour| winding_number_st(contour, point)).sum();
+	classify(winding_number, fill_rule)
+}
+
+/// Find where a point is relative to a multi-polygon, on multiple threads.
+///
+/// The edges of each contour are divided over the threads.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to test the point against.
+/// * `point` - The point to test.
+/// * `fill_rule` - Which regions of the multi-polygon are inside, if its contours overlap.
+///
+/// # Result
+/// Whether the point is inside, outside or on the boundary of the multi-polygon.
+///
+/// # Examples
+/// ```
+/// use apex::{Containment, FillRule, MultiPolygon, Point2D};
+/// let mut frame = MultiPolygon::new();
+/// frame.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// frame.push_contour([Point2D { x: 25, y: 25 }, Point2D { x: 25, y: 75 }, Point2D { x: 75, y: 75 }, Point2D { x: 75, y: 25 }]); //A hole.
+/// assert_eq!(apex::operations::containment::containment_multipolygon_mt(&frame, &Point2D { x: 10, y: 10 }, FillRule::EVEN_ODD), Containment::INSIDE);
+/// ```
+pub fn containment_multipolygon_mt(multi_polygon: &MultiPolygon, point: &Point2D, fill_rule: FillRule) -> Containment {
+	let winding_number = multi_polygon.contours().map(|contour| winding_number_mt(contour, point)).sum();
+	classify(winding_number, fill_rule)
+}
+
+/// Find where each of a batch of points is relative to a polygon.
+///
+/// This chooses automatically whether to test the points on a single thread, on multiple threads or
+/// on the GPU. The cost model estimates which is fastest, from the number of tests between a point
+/// and an edge that need to be performed, and from where the most up-to-date copy of the vertices
+/// of the polygon is.
+///
+/// # Arguments
+/// * `polygon` - The polygon to test the points against.
+/// * `points` - The points to test.
+/// * `fill_rule` - Which regions of the polygon are inside, if it intersects itself.
+///
+/// # Result
+/// For each point, in the same order, whether it is inside, outside or on the boundary of the
+/// polygon.
+///
+/// # Examples
+/// ```
+/// use apex::{Containment, FillRule, Point2D, Polygon};
+/// let square = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let points = [Point2D { x: 50, y: 50 }, Point2D { x: 150, y: 50 }, Point2D { x: 100, y: 50 }];
+/// assert_eq!(apex::operations::containment::containment_polygon_batch(&square, &points, FillRule::NON_ZERO), vec![Containment::INSIDE, Containment::OUTSIDE, Containment::BOUNDARY]);
+/// ```
+pub fn containment_polygon_batch(polygon: &Polygon, points: &[Point2D], fill_rule: FillRule) -> Vec<Containment> {
+	match cost_model::choose(Operation::CONTAINMENT, polygon.len() * points.len(), polygon.sync_status()) {
+		Backend::ST => containment_polygon_batch_st(polygon, points, fill_rule),
+		Backend::MT => containment_polygon_batch_mt(polygon, points, fill_rule),
+		#[cfg(feature = "gpu")]
+		Backend::GPU => containment_polygon_batch_gpu(polygon, points, fill_rule),
+		#[cfg(not(feature = "gpu"))]
+		Backend::GPU => unreachable!("Without a GPU runtime, the cost model never chooses the GPU."),
+	}
+}
+
+/// Find where each of a batch of points is relative to a polygon, on a single thread.
+///
+/// # Arguments
+/// * `polygon` - The polygon to test the points against.
+/// * `points` - The points to test.
+/// * `fill_rule` - Which regions of the polygon are inside, if it intersects itself.
+///
+/// # Result
+/// For each point, in the same order, whether it is inside, outside or on the boundary of the
+/// polygon.
+///
+/// # Examples
+/// ```
+/// use apex::{Containment, FillRule, Point2D, Polygon};
+/// let square = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let points = [Point2D { x: 50, y: 50 }, Point2D { x: 150, y: 50 }];
+/// assert_eq!(apex::operations::containment::containment_polygon_batch_st(&square, &points, FillRule::NON_ZERO), vec![Containment::INSIDE, Containment::OUTSIDE]);
+/// ```
+pub fn containment_polygon_batch_st(polygon: &Polygon, points: &[Point2D], fill_rule: FillRule) -> Vec<Containment> {
+	let vertices = polygon.host_vertices();
+	points.iter().map(|point| classify(winding_number_st(vertices, point), fill_rule)).collect()
+}
+
+/// Find where each of a batch of points is relative to a polygon, on multiple threads.
+///
+/// The points are divided over the threads. Each thread tests its points against all edges.
+///
+/// # Arguments
+/// * `polygon` - The polygon to test the points against.
+/// * `points` - The points to test.
+/// * `fill_rule` - Which regions of the polygon are inside, if it intersects itself.
+///
+/// # Result
+/// For each point, in the same order, whether it is inside, outside or on the boundary of the
+/// polygon.
+///
+/// # Examples
+/// ```
+/// use apex::{Containment, FillRule, Point2D, Polygon};
+/// let square = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let points = [Point2D { x: 50, y: 50 }, Point2D { x: 150, y: 50 }];
+/// assert_eq!(apex::operations::containment::containment_polygon_batch_mt(&square, &points, FillRule::NON_ZERO), vec![Containment::INSIDE, Containment::OUTSIDE]);
+/// ```
+pub fn containment_polygon_batch_mt(polygon: &Polygon, points: &[Point2D], fill_rule: FillRule) -> Vec<Containment> {
+	let vertices = polygon.host_vertices();
+	let chunk_size = cmp::max(cost_model::chunk_size(Operation::CONTAINMENT, vertices.len() * points.len()) / cmp::max(vertices.len(), 1), 1);
+	points.par_iter().with_min_len(chunk_size).map(|point| classify(winding_number_st(vertices, point), fill_rule)).collect()
+}
+
+/// Find where each of a batch of points is relative to a polygon, on the GPU.
+///
+/// Every work unit on the GPU tests one point against all edges. The vertices of the polygon stay on
+/// the GPU afterwards, so that further batches don't need to copy them again.
+///
+/// # Arguments
+/// * `polygon` - The polygon to test the points against.
+/// * `points` - The points to test.
+/// * `fill_rule` - Which regions of the polygon are inside, if it intersects itself.
+///
+/// # Result
+/// For each point, in the same order, whether it is inside, outside or on the boundary of the
+/// polygon.
+///
+/// # Examples
+/// ```
+/// use apex::{Containment, FillRule, Point2D, Polygon};
+/// let square = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let points = [Point2D { x: 50, y: 50 }, Point2D { x: 150, y: 50 }];
+/// assert_eq!(apex::operations::containment::containment_polygon_batch_gpu(&square, &points, FillRule::NON_ZERO), vec![Containment::INSIDE, Containment::OUTSIDE]);
+/// ```
+#[cfg(feature = "gpu")]
+pub fn containment_polygon_batch_gpu(polygon: &Polygon, points: &[Point2D], fill_rule: FillRule) -> Vec<Containment> {
+	use crate::detail::gpu;
+
+	if !gpu::supports_coordinates() {
+		return containment_polygon_batch(polygon, points, fill_rule);
+	}
+	if points.is_empty() || polygon.len() == 0 {
+		return vec![Containment::OUTSIDE; points.len()]; //Can't launch a kernel without any work. Without edges, nothing is inside.
+	}
+	let coordinates_handle = polygon.gpu_vertices();
+	let num_vertices = coordinates_handle.size() as usize / (2 * size_of::<Coordinate>());
+	let client = gpu::client();
+	let points_handle = gpu::upload(points);
+	let windings_handle = client.empty(points.len() * size_of::<i32>());
+	let boundary_handle = client.empty(points.len() * size_of::<u32>());
+	let (cube_count, cube_dim) = gpu::launch_dimensions(points.len());
+	unsafe {
+		winding_numbers_kernel::launch::<gpu::GpuRuntime>(
+			&client,
+			cube_count,
+			cube_dim,
+			ArrayArg::from_raw_parts::<Coordinate>(coordinates_handle, num_vertices * 2, 1),
+			ArrayArg::from_raw_parts::<Coordinate>(&points_handle, points.len() * 2, 1),
+			ArrayArg::from_raw_parts::<i32>(&windings_handle, points.len(), 1),
+			ArrayArg::from_raw_parts::<u32>(&boundary_handle, points.len(), 1),
+		);
+	}
+	let windings = client.read_one(windings_handle);
+	let boundary = client.read_one(boundary_handle);
+	i32::from_bytes(&windings).iter().zip(u32::from_bytes(&boundary)).map(|(winding, on_boundary)| {
+		classify(if *on_boundary != 0 { None } else { Some(*winding) }, fill_rule)
+	}).collect()
+}
+
+/// Find where a point is relative to every polygon in a batch.
+///
+/// Unlike `containment_polygon_batch`, which tests many points against one polygon, this tests one
+/// point against many polygons. This chooses automatically whether to test the polygons on a single
+/// thread or on multiple threads. The cost model considers the total number of vertices in the
+/// batch.
+///
+/// # Arguments
+/// * `batch` - The polygons to test the point against.
+/// * `point` - The point to test.
+/// * `fill_rule` - Which regions of the polygons are inside, if they intersect themselves.
+///
+/// # Result
+/// For each polygon, in the order that they were added to the batch, whether the point is inside,
+/// outside or on the boundary of it.
+///
+/// # Examples
+/// ```
+/// use apex::{Containment, FillRule, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 200, y: 0 }, Point2D { x: 300, y: 0 }, Point2D { x: 300, y: 100 }, Point2D { x: 200, y: 100 }]);
+/// assert_eq!(apex::operations::containment::containment_batch(&batch, &Point2D { x: 50, y: 50 }, FillRule::NON_ZERO), vec![Containment::INSIDE, Containment::OUTSIDE]);
+/// ```
+pub fn containment_batch(batch: &PolygonBatch, point: &Point2D, fill_rule: FillRule) -> Vec<Containment> {
+	match cost_model::choose(Operation::CONTAINMENT, batch.len(), batch.sync_status()) {
+		Backend::ST => containment_batch_st(batch, point, fill_rule),
+		Backend::MT => containment_batch_mt(batch, point, fill_rule),
+		Backend::GPU => containment_batch_mt(batch, point, fill_rule), //There is no GPU implementation for batches of polygons. The GPU is only chosen for a lot of work, which suits multiple threads best.
+	}
+}
+
+/// Find where a point is relative to every polygon in a batch, on a single thread.
+///
+/// # Arguments
+/// * `batch` - The polygons to test the point against.
+/// * `point` - The point to test.
+/// * `fill_rule` - Which regions of the polygons are inside, if they intersect themselves.
+///
+/// # Result
+/// For each polygon, in the order that they were added to the batch, whether the point is inside,
+/// outside or on the boundary of it.
+///
+/// # Examples
+/// ```
+/// use apex::{Containment, FillRule, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// assert_eq!(apex::operations::containment::containment_batch_st(&batch, &Point2D { x: 50, y: 50 }, FillRule::NON_ZERO), vec![Containment::INSIDE, Containment::BOUNDARY]);
+/// ```
+pub fn containment_batch_st(batch: &PolygonBatch, point: &Point2D, fill_rule: FillRule) -> Vec<Containment> {
+	batch.polygons().map(|vertices| classify(winding_number_st(vertices, point), fill_rule)).collect()
+}
+
+/// Find where a point is relative to every polygon in a batch, on multiple threads.
+///
+/// The polygons are divided over the threads. Each thread tests the point against all edges of its
+/// polygons.
+///
+/// # Arguments
+/// * `batch` - The polygons to test the point against.
+/// * `point` - The point to test.
+/// * `fill_rule` - Which regions of the polygons are inside, if they intersect themselves.
+///
+/// # Result
+/// For each polygon, in the order that they were added to the batch, whether the point is inside,
+/// outside or on the boundary of it.
+///
+/// # Examples
+/// ```
+/// use apex::{Containment, FillRule, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// assert_eq!(apex::operations::containment::containment_batch_mt(&batch, &Point2D { x: 50, y: 50 }, FillRule::NON_ZERO), vec![Containment::INSIDE, Containment::BOUNDARY]);
+/// ```
+pub fn containment_batch_mt(batch: &PolygonBatch, point: &Point2D, fill_rule: FillRule) -> Vec<Containment> {
+	let polygons: Vec<&[Point2D]> = batch.polygons().collect();
+	polygons.into_par_iter().map(|vertices| classify(winding_number_st(vertices, point), fill_rule)).collect()
+}
+
+/// GPU kernel to calculate the winding number of a polygon around each of a batch of points.
+///
+/// Every work unit processes one point, testing it against every edge of the polygon.
+///
+/// # Arguments
+/// * `coordinates` - The vertices of the polygon, with the X and Y coordinates interleaved.
+/// * `points` - The points to test, with the X and Y coordinates interleaved.
+/// * `windings` - The output, the winding number around each point.
+/// * `boundary` - The output, 1 for each point that is on an edge of the polygon, or 0 otherwise.
+#[cube(launch)]
+fn winding_numbers_kernel(coordinates: &Array<Coordinate>, points: &Array<Coordinate>, windings: &mut Array<i32>, boundary: &mut Array<u32>) {
+	let num_points = points.len() / 2;
+	if ABSOLUTE_POS < num_points {
+		let num_vertices = coordinates.len() / 2;
+		let x = i64::cast_from(points[ABSOLUTE_POS * 2]);
+		let y = i64::cast_from(points[ABSOLUTE_POS * 2 + 1]);
+		let mut winding: i32 = 0;
+		let mut on_boundary: u32 = 0;
+		let mut vertex = 0;
+		while vertex < num_vertices {
+			let next = (vertex + 1) % num_vertices;
+			let start_x = i64::cast_from(coordinates[vertex * 2]);
+			let start_y = i64::cast_from(coordinates[vertex * 2 + 1]);
+			let end_x = i64::cast_from(coordinates[next * 2]);
+			let end_y = i64::cast_from(coordinates[next * 2 + 1]);
+			let side = predicates::orientation_gpu(start_x, start_y, end_x, end_y, x, y);
+			let within_x = (start_x <= x && x <= end_x) || (end_x <= x && x <= start_x);
+			let within_y = (start_y <= y && y <= end_y) || (end_y <= y && y <= start_y);
+			if side == 0 && within_x && within_y {
+				on_boundary = 1;
+			}
+			if start_y <= y && end_y > y && side > 0 {
+				winding += 1; //Upward crossing, with the point on the left.
+			}
+			if end_y <= y && start_y > y && side < 0 {
+				winding -= 1; //Downward crossing, with the point on the right.
+			}
+			vertex += 1;
+		}
+		windings[ABSOLUTE_POS] = winding;
+		boundary[ABSOLUTE_POS] = on_boundary;
+	}
+}
+
+
+/// Decide where a point is, from the winding number around it.
+///
+/// # Arguments
+/// * `winding_number` - The winding number around the point, or `None` if it is on the boundary.
+/// * `fill_rule` - Which winding numbers are inside.
+fn classify(winding_number: Option<i32>, fill_rule: FillRule) -> Containment {
+	match winding_number {
+		None => Containment::BOUNDARY,
+		Some(winding_number) if fill_rule.is_filled(winding_number) => Containment::INSIDE,
+		Some(_) => Containment::OUTSIDE,
+	}
+}
+
+/// Calculate the winding number of a closed contour around a point, single-threaded.
+///
+/// # Result
+/// The winding number, or `None` if the point is on an edge of the contour.
+pub(crate) fn winding_number_st(vertices: &[Point2D], point: &Point2D) -> Option<i32> {
+	let mut winding_number = 0;
+	for i in 0..vertices.len() {
+		winding_number += edge_winding(&vertices[i], &vertices[(i + 1) % vertices.len()], point)?;
+	}
+	Some(winding_number)
+}
+
+/// Calculate the winding number of a closed contour around a point, multi-threaded.
+///
+/// # Result
+/// The winding number, or `None` if the point is on an edge of the contour.
+fn winding_number_mt(vertices: &[Point2D], point: &Point2D) -> Option<i32> {
+	let chunk_size = cost_model::chunk_size(Operation::CONTAINMENT, vertices.len());
+	(0..vertices.len()).into_par_iter().with_min_len(chunk_size).map(
+		|i| edge_winding(&vertices[i], &vertices[(i + 1) % vertices.len()], point)
+	).try_reduce(|| 0, |first, second| Some(first + second))
+}
+
+/// Calculate how much an edge contributes to the winding number around a point.
+///
+/// # Result
+/// 1 if the edge crosses the ray to the right of the point upwards, -1 if it crosses it downwards,
+/// or 0 if it doesn't cross it. If the point is on the edge, `None`.
+fn edge_winding(start: &Point2D, end: &Point2D, point: &Point2D) -> Option<i32> {
+	let side = predicates::orientation(start, end, point);
+	if side == 0
+			&& cmp::min(start.x, end.x) <= point.x && point.x <= cmp::max(start.x, end.x)
+			&& cmp::min(start.y, end.y) <= point.y && point.y <= cmp::max(start.y, end.y) {
+		return None;
+	}
+	if start.y <= point.y && end.y > point.y && side > 0 {
+		Some(1) //Upward crossing, with the point on the left.
+	} else if end.y <= point.y && start.y > point.y && side < 0 {
+		Some(-1) //Downward crossing, with the point on the right.
+	} else {
+		Some(0)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+
+	/// Test a point against a polygon with every implementation, and check that they agree.
+	fn all_backends(polygon: &Polygon, point: Point2D, fill_rule: FillRule) -> Containment {
+		let st = containment_polygon_st(polygon, &point, fill_rule);
+		assert_eq!(containment_polygon_mt(polygon, &point, fill_rule), st, "The multi-threaded implementation must give the same result.");
+		assert_eq!(containment_polygon(polygon, &point, fill_rule), st, "The automatic choice must give the same result.");
+		assert_eq!(containment_polygon_batch_st(polygon, &[point], fill_rule), vec![st], "The batch implementation must give the same result.");
+		assert_eq!(containment_polygon_batch_mt(polygon, &[point], fill_rule), vec![st], "The multi-threaded batch implementation must give the same result.");
+		#[cfg(feature = "gpu")]
+		assert_eq!(containment_polygon_batch_gpu(polygon, &[point], fill_rule), vec![st], "The GPU implementation must give the same result.");
+		st
+	}
+
+	/// Test points against an empty polygon, which contains nothing.
+	#[test]
+	fn empty() {
+		assert_eq!(all_backends(&Polygon::new(), Point2D { x: 0, y: 0 }, FillRule::NON_ZERO), Containment::OUTSIDE);
+	}
+
+	/// Test points inside, outside and on the boundary of a square.
+	#[test_case(500, 500, Containment::INSIDE ; "centre")]
+	#[test_case(1, 999, Containment::INSIDE ; "near corner")]
+	#[test_case(-1, 500, Containment::OUTSIDE ; "left")]
+	#[test_case(1001, 500, Containment::OUTSIDE ; "right")]
+	#[test_case(500, 1001, Containment::OUTSIDE ; "above")]
+	#[test_case(2000, 0, Containment::OUTSIDE ; "in line with edge")]
+	#[test_case(2000, 1000, Containment::OUTSIDE ; "in line with top edge")]
+	#[test_case(0, 500, Containment::BOUNDARY ; "on edge")]
+	#[test_case(1000, 1000, Containment::BOUNDARY ; "on vertex")]
+	fn square(x: Coordinate, y: Coordinate, expected: Containment) {
+		let square = crate::test::data::polygon::square_1000();
+		for fill_rule in [FillRule::EVEN_ODD, FillRule::NON_ZERO, FillRule::POSITIVE] {
+			assert_eq!(all_backends(&square, Point2D { x, y }, fill_rule), expected);
+		}
+	}
+
+	/// Test that the inside of a clockwise polygon has a negative winding number.
+	#[test]
+	fn clockwise() {
+		let square = crate::test::data::polygon::square_1000();
+		let reversed = Polygon::from_iter(square.host_vertices().iter().rev().copied());
+		let centre = Point2D { x: 500, y: 500 };
+		assert_eq!(all_backends(&reversed, centre, FillRule::NON_ZERO), Containment::INSIDE);
+		assert_eq!(all_backends(&reversed, centre, FillRule::POSITIVE), Containment::OUTSIDE);
+		assert_eq!(all_backends(&reversed, centre, FillRule::NEGATIVE), Containment::INSIDE);
+	}
+
+	/// Test the centre of a pentagram, which the polygon goes around twice.
+	#[test_case(FillRule::EVEN_ODD, Containment::OUTSIDE ; "even-odd")]
+	#[test_case(FillRule::NON_ZERO, Containment::INSIDE ; "non-zero")]
+	fn pentagram_centre(fill_rule: FillRule, expected: Containment) {
+		let pentagram = crate::test::data::polygon::pentagram_1000();
+		assert_eq!(all_backends(&pentagram, Point2D { x: 500, y: 500 }, fill_rule), expected);
+		assert_eq!(all_backends(&pentagram, Point2D { x: 500, y: 900 }, fill_rule), Containment::INSIDE, "The points are inside regardless of the fill rule.");
+	}
+
+	/// Test points very close to a long, diagonal edge, where rounding errors would give the wrong
+	/// answer.
+	#[test]
+	fn exact() {
+		let triangle = Polygon::from_iter([Point2D { x: -2_000_000_000, y: -2_000_000_001 }, Point2D { x: 2_000_000_000, y: 1_999_999_999 }, Point2D { x: -2_000_000_000, y: 2_000_000_000 }]);
+		//The diagonal edge goes through (1, 0) with slope 1.
+		assert_eq!(all_backends(&triangle, Point2D { x: 1, y: 0 }, FillRule::NON_ZERO), Containment::BOUNDARY);
+		assert_eq!(all_backends(&triangle, Point2D { x: 1, y: 1 }, FillRule::NON_ZERO), Containment::INSIDE);
+		assert_eq!(all_backends(&triangle, Point2D { x: 2, y: 0 }, FillRule::NON_ZERO), Containment::OUTSIDE);
+	}
+
+	/// Test a large batch of points against a polygon, comparing to testing the points one by one.
+	#[test]
+	fn batch() {
+		let pentagram = crate::test::data::polygon::pentagram_1000();
+		let points: Vec<Point2D> = (-10..=110).flat_map(|x| (-10..=110).map(move |y| Point2D { x: x * 10, y: y * 10 })).collect();
+		let expected: Vec<Containment> = points.iter().map(|point| containment_polygon_st(&pentagram, point, FillRule::EVEN_ODD)).collect();
+		assert_eq!(containment_polygon_batch_st(&pentagram, &points, FillRule::EVEN_ODD), expected);
+		assert_eq!(containment_polygon_batch_mt(&pentagram, &points, FillRule::EVEN_ODD), expected);
+		assert_eq!(containment_polygon_batch(&pentagram, &points, FillRule::EVEN_ODD), expected);
+		#[cfg(feature = "gpu")]
+		assert_eq!(containment_polygon_batch_gpu(&pentagram, &points, FillRule::EVEN_ODD), expected);
+		assert!(expected.contains(&Containment::INSIDE) && expected.contains(&Containment::OUTSIDE) && expected.contains(&Containment::BOUNDARY), "The grid covers all cases.");
+	}
+
+	/// Test points against a multi-polygon with a hole.
+	#[test_case(100, 500, Containment::INSIDE ; "in frame")]
+	#[test_case(500, 500, Containment::OUTSIDE ; "in hole")]
+	#[test_case(250, 500, Containment::BOUNDARY ; "on hole")]
+	#[test_case(1500, 500, Containment::OUTSIDE ; "outside")]
+	fn multipolygon_hole(x: Coordinate, y: Coordinate, expected: Containment) {
+		let frame = crate::test::data::multi_polygon::square_with_hole_1000();
+		let point = Point2D { x, y };
+		assert_eq!(containment_multipolygon_st(&frame, &point, FillRule::NON_ZERO), expected);
+		assert_eq!(containment_multipolygon_mt(&frame, &point, FillRule::NON_ZERO), expected);
+		assert_eq!(containment_multipolygon(&frame, &point, FillRule::NON_ZERO), expected);
+	}
+
+	/// Test a point against the polygons of a batch, which are tested separately.
+	#[test]
+	fn batch_polygons() {
+		let batch = PolygonBatch::from_iter([crate::test::data::polygon::square_1000(), Polygon::new(), crate::test::data::polygon::pentagram_1000(), crate::test::data::polygon::triangle_1000()]);
+		for point in [Point2D { x: 500, y: 500 }, Point2D { x: 0, y: 500 }, Point2D { x: 2000, y: 0 }] {
+			for fill_rule in [FillRule::EVEN_ODD, FillRule::NON_ZERO] {
+				let expected: Vec<Containment> = batch.polygons().map(|vertices| containment_polygon_st(&Polygon::from_iter(vertices.iter().copied()), &point, fill_rule)).collect();
+				assert_eq!(containment_batch_st(&batch, &point, fill_rule), expected);
+				assert_eq!(containment_batch_mt(&batch, &point, fill_rule), expected);
+				assert_eq!(containment_batch(&batch, &point, fill_rule), expected);
+			}
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,861 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! This module contains the implementations of operations to find the convex hull of geometric
+//! objects and of sets of points.
+//!
+//! The convex hull is the smallest convex polygon that contains all of the points. It is returned
+//! as a counter-clockwise polygon, starting with its lowest vertex in the ordering of `Point2D`,
+//! which is the leftmost vertex, and of those the bottom one.
+//!
+//! Only the corners of the hull are vertices of the hull. Points that are on an edge of the hull
+//! between two corners, collinear with them, are left out, and so are duplicate points. If all
+//! points are collinear, the hull is degenerate and has only the two extreme points as vertices. A
+//! single distinct point results in a hull with that one vertex, and no points result in an empty
+//! hull.
+//!
+//! The single-threaded implementation sorts the points and then uses Andrew's monotone chain
+//! algorithm. The multi-threaded implementation sorts the points in parallel, finds the hull of
+//! ranges of them in parallel, and merges the hulls of neighbouring ranges. The GPU implementation
+//! first discards all points that are strictly inside an octagon formed by the extreme points in
+//! eight directions. For large point clouds, that leaves only a small fraction of the points to
+//! sort on the host.
+
+use cubecl::prelude::*; //For the GPU implementation.
+use rayon::prelude::*; //For multi-threaded implementations.
+
+use crate::Coordinate; //The GPU kernels read coordinates.
+use crate::MultiPolygon; //Find the convex hull of multi-polygons.
+use crate::Point2D; //The points to find the hull of.
+use crate::Polygon; //Find the convex hull of polygons, and return the hull as a polygon.
+use crate::PolygonBatch; //Find the convex hulls of batches of polygons at once.
+use crate::cost_model::{self, Backend, Operation}; //To choose the fastest implementation.
+use crate::detail::sync_status::SyncStatus; //Loose points are always on the host.
+use crate::predicates::{orientation, orientation_gpu}; //To find which points are on the hull, and which are inside the octagon on the GPU.
+
+/// The number of points that each work unit processes in the GPU implementation.
+///
+/// Each work unit finds extremes and counts points sequentially, so that the number of
+/// intermediate results stays small.
+#[cfg(feature = "gpu")]
+const GPU_CHUNK_SIZE: usize = 256;
+
+/// Find the convex hull of a polygon.
+///
+/// This chooses automatically whether to find the hull on a single thread, on multiple threads or
+/// on the GPU. The cost model estimates which is fastest, from the number of vertices in the polygon
+/// and from where the most up-to-date copy of the vertices is.
+///
+/// # Arguments
+/// * `polygon` - The polygon to find the convex hull of.
+///
+/// # Result
+/// The convex hull, counter-clockwise.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// let l_shape = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 50 }, Point2D { x: 50, y: 50 }, Point2D { x: 50, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let hull = apex::operations::convex_hull::convex_hull_polygon(&l_shape);
+/// assert_eq!(hull, Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 50 }, Point2D { x: 50, y: 100 }, Point2D { x: 0, y: 100 }]));
+/// ```
+pub fn convex_hull_polygon(polygon: &Polygon) -> Polygon {
+	match cost_model::choose(Operation::CONVEX_HULL, polygon.len(), polygon.sync_status()) {
+		Backend::ST => convex_hull_polygon_st(polygon),
+		Backend::MT => convex_hull_polygon_mt(polygon),
+		#[cfg(feature = "gpu")]
+		Backend::GPU => convex_hull_polygon_gpu(polygon),
+		#[cfg(not(feature = "gpu"))]
+		Backend::GPU => unreachable!("Without a GPU runtime, the cost model never chooses the GPU."),
+	}
+}
+
+/// Find the convex hull of a polygon, on a single thread.
+///
+/// # Arguments
+/// * `polygon` - The polygon to find the convex hull of.
+///
+/// # Result
+/// The convex hull, counter-clockwise.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// let triangle = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 50, y: 100 }, Point2D { x: 100, y: 0 }]); //Clockwise.
+/// let hull = apex::operations::convex_hull::convex_hull_polygon_st(&triangle);
+/// assert_eq!(hull, Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]));
+/// ```
+pub fn convex_hull_polygon_st(polygon: &Polygon) -> Polygon {
+	convex_hull_points_st(polygon.host_vertices())
+}
+
+/// Find the convex hull of a polygon, on multiple threads.
+///
+/// # Arguments
+/// * `polygon` - The polygon to find the convex hull of.
+///
+/// # Result
+/// The convex hull, counter-clockwise.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// let triangle = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 50, y: 100 }, Point2D { x: 100, y: 0 }]); //Clockwise.
+/// let hull = apex::operations::convex_hull::convex_hull_polygon_mt(&triangle);
+/// assert_eq!(hull, Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]));
+/// ```
+pub fn convex_hull_polygon_mt(polygon: &Polygon) -> Polygon {
+	convex_hull_points_mt(polygon.host_vertices())
+}
+
+/// Find the convex hull of a polygon, on the GPU.
+///
+/// The vertices stay on the GPU. Only the vertices that may be on the hull are copied back to the
+/// host.
+///
+/// # Arguments
+/// * `polygon` - The polygon to find the convex hull of.
+///
+/// # Result
+/// The convex hull, counter-clockwise.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// let triangle = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 50, y: 100 }, Point2D { x: 100, y: 0 }]); //Clockwise.
+/// let hull = apex::operations::convex_hull::convex_hull_polygon_gpu(&triangle);
+/// assert_eq!(hull, Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]));
+/// ```
+#[cfg(feature = "gpu")]
+pub fn convex_hull_polygon_gpu(polygon: &Polygon) -> Polygon {
+	if !crate::detail::gpu::supports_coordinates() {
+		return convex_hull_polygon(polygon);
+	}
+	if polygon.len() == 0 {
+		return Polygon::new(); //Can't launch a kernel without any work.
+	}
+	convex_hull_gpu(polygon.gpu_vertices(), polygon.len())
+}
+
+/// Find the convex hull of a multi-polygon.
+///
+/// This is the convex hull of the vertices of all of its contours. Holes are inside the outer
+/// contours, so they don't affect the hull. This chooses automatically whether to find the hull on
+/// a single thread, on multiple threads or on the GPU, like `convex_hull_polygon`.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to find the convex hull of.
+///
+/// # Result
+/// The convex hull, counter-clockwise.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, Point2D, Polygon};
+/// let mut squares = MultiPolygon::new();
+/// squares.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 10, y: 10 }, Point2D { x: 0, y: 10 }]);
+/// squares.push_contour([Point2D { x: 90, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 10 }, Point2D { x: 90, y: 10 }]);
+/// let hull = apex::operations::convex_hull::convex_hull_multipolygon(&squares);
+/// assert_eq!(hull, Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 10 }, Point2D { x: 0, y: 10 }]));
+/// ```
+pub fn convex_hull_multipolygon(multi_polygon: &MultiPolygon) -> Polygon {
+	match cost_model::choose(Operation::CONVEX_HULL, multi_polygon.len(), multi_polygon.sync_status()) {
+		Backend::ST => convex_hull_multipolygon_st(multi_polygon),
+		Backend::MT => convex_hull_multipolygon_mt(multi_polygon),
+		#[cfg(feature = "gpu")]
+		Backend::GPU => convex_hull_multipolygon_gpu(multi_polygon),
+		#[cfg(not(feature = "gpu"))]
+		Backend::GPU => unreachable!("Without a GPU runtime, the cost model never chooses the GPU."),
+	}
+}
+
+/// Find the convex hull of a multi-polygon, on a single thread.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to find the convex hull of.
+///
+/// # Result
+/// The convex hull, counter-clockwise.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, Point2D, Polygon};
+/// let mut squares = MultiPolygon::new();
+/// squares.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 10, y: 10 }, Point2D { x: 0, y: 10 }]);
+/// squares.push_contour([Point2D { x: 90, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 10 }, Point2D { x: 90, y: 10 }]);
+/// let hull = apex::operations::convex_hull::convex_hull_multipolygon_st(&squares);
+/// assert_eq!(hull, Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 10 }, Point2D { x: 0, y: 10 }]));
+/// ```
+pub fn convex_hull_multipolygon_st(multi_polygon: &MultiPolygon) -> Polygon {
+	convex_hull_points_st(multi_polygon.host_vertices())
+}
+
+/// Find the convex hull of a multi-polygon, on multiple threads.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to find the convex hull of.
+///
+/// # Result
+/// The convex hull, counter-clockwise.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, Point2D, Polygon};
+/// let mut squares = MultiPolygon::new();
+/// squares.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 10, y: 10 }, Point2D { x: 0, y: 10 }]);
+/// squares.push_contour([Point2D { x: 90, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 10 }, Point2D { x: 90, y: 10 }]);
+/// let hull = apex::operations::convex_hull::convex_hull_multipolygon_mt(&squares);
+/// assert_eq!(hull, Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 10 }, Point2D { x: 0, y: 10 }]));
+/// ```
+pub fn convex_hull_multipolygon_mt(multi_polygon: &MultiPolygon) -> Polygon {
+	convex_hull_points_mt(multi_polygon.host_vertices())
+}
+
+/// Find the convex hull of a multi-polygon, on the GPU.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to find the convex hull of.
+///
+/// # Result
+/// The convex hull, counter-clockwise.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, Point2D, Polygon};
+/// let mut squares = MultiPolygon::new();
+/// squares.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 10, y: 10 }, Point2D { x: 0, y: 10 }]);
+/// squares.push_contour([Point2D { x: 90, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 10 }, Point2D { x: 90, y: 10 }]);
+/// let hull = apex::operations::convex_hull::convex_hull_multipolygon_gpu(&squares);
+/// assert_eq!(hull, Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 10 }, Point2D { x: 0, y: 10 }]));
+/// ```
+#[cfg(feature = "gpu")]
+pub fn convex_hull_multipolygon_gpu(multi_polygon: &MultiPolygon) -> Polygon {
+	if !crate::detail::gpu::supports_coordinates() {
+		return convex_hull_multipolygon(multi_polygon);
+	}
+	if multi_polygon.len() == 0 {
+		return Polygon::new(); //Can't launch a kernel without any work.
+	}
+	convex_hull_gpu(multi_polygon.gpu_vertices(), multi_polygon.len())
+}
+
+/// Find the convex hull of a set of points.
+///
+/// This chooses automatically whether to find the hull on a single thread, on multiple threads or
+/// on the GPU. The cost model estimates which is fastest, from the number of points.
+///
+/// # Arguments
+/// * `points` - The points to find the convex hull of, in any order.
+///
+/// # Result
+/// The convex hull, counter-clockwise.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// let points = [Point2D { x: 0, y: 0 }, Point2D { x: 30, y: 40 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 0 }, Point2D { x: 50, y: 100 }];
+/// let hull = apex::operations::convex_hull::convex_hull_points(&points);
+/// assert_eq!(hull, Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]));
+/// ```
+pub fn convex_hull_points(points: &[Point2D]) -> Polygon {
+	match cost_model::choose(Operation::CONVEX_HULL, points.len(), SyncStatus::HOST) {
+		Backend::ST => convex_hull_points_st(points),
+		Backend::MT => convex_hull_points_mt(points),
+		#[cfg(feature = "gpu")]
+		Backend::GPU => convex_hull_points_gpu(points),
+		#[cfg(not(feature = "gpu"))]
+		Backend::GPU => unreachable!("Without a GPU runtime, the cost model never chooses the GPU."),
+	}
+}
+
+/// Find the convex hull of a set of points, on a single thread.
+///
+/// # Arguments
+/// * `points` - The points to find the convex hull of, in any order.
+///
+/// # Result
+/// The convex hull, counter-clockwise.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// let points = [Point2D { x: 0, y: 0 }, Point2D { x: 30, y: 40 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 0 }, Point2D { x: 50, y: 100 }];
+/// let hull = apex::operations::convex_hull::convex_hull_points_st(&points);
+/// assert_eq!(hull, Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]));
+/// ```
+pub fn convex_hull_points_st(points: &[Point2D]) -> Polygon {
+	let mut sorted = points.to_vec();
+	sorted.sort_unstable();
+	Polygon::from_iter(monotone_chain(&sorted))
+}
+
+/// Find the convex hull of a set of points, on multiple threads.
+///
+/// The points are sorted in parallel. Then the sorted points are divided into ranges, and the hull
+/// of each range is found in parallel. The ranges don't overlap, so the hulls of neighbouring ranges
+/// can be merged by running the monotone chain algorithm again on only their vertices.
+///
+/// # Arguments
+/// * `points` - The points to find the convex hull of, in any order.
+///
+/// # Result
+/// The convex hull, counter-clockwise.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// let points = [Point2D { x: 0, y: 0 }, Point2D { x: 30, y: 40 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 0 }, Point2D { x: 50, y: 100 }];
+/// let hull = apex::operations::convex_hull::convex_hull_points_mt(&points);
+/// assert_eq!(hull, Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]));
+/// ```
+pub fn convex_hull_points_mt(points: &[Point2D]) -> Polygon {
+	let mut sorted = points.to_vec();
+	sorted.par_sort_unstable();
+	let chunk_size = cost_model::chunk_size(Operation::CONVEX_HULL, sorted.len());
+	let merged = sorted.par_chunks(chunk_size).map(sorted_hull_vertices).reduce(Vec::new, |mut left, right| {
+		left.extend(right); //All of the left range comes before the right range, so this is still sorted.
+		sorted_hull_vertices(&left)
+	});
+	Polygon::from_iter(monotone_chain(&merged))
+}
+
+/// Find the convex hull of a set of points, on the GPU.
+///
+/// The points are copied to the GPU, where the points that can't be on the hull are discarded.
+///
+/// # Arguments
+/// * `points` - The points to find the convex hull of, in any order.
+///
+/// # Result
+/// The convex hull, counter-clockwise.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// let points = [Point2D { x: 0, y: 0 }, Point2D { x: 30, y: 40 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 0 }, Point2D { x: 50, y: 100 }];
+/// let hull = apex::operations::convex_hull::convex_hull_points_gpu(&points);
+/// assert_eq!(hull, Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]));
+/// ```
+#[cfg(feature = "gpu")]
+pub fn convex_hull_points_gpu(points: &[Point2D]) -> Polygon {
+	use crate::detail::gpu;
+
+	if !gpu::supports_coordinates() {
+		return convex_hull_points(points);
+	}
+	if points.is_empty() {
+		return Polygon::new(); //Can't launch a kernel without any work.
+	}
+	convex_hull_gpu(&gpu::upload(points), points.len())
+}
+
+/// Find the convex hull of every polygon in a batch.
+///
+/// This chooses automatically whether to find the hulls on a single thread or on multiple threads.
+/// The cost model considers the total number of vertices in the batch.
+///
+/// # Arguments
+/// * `batch` - The polygons to find the convex hulls of.
+///
+/// # Result
+/// A batch with the convex hull of each polygon, counter-clockwise, in the same order.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 50 }, Point2D { x: 50, y: 50 }, Point2D { x: 50, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 50, y: 100 }, Point2D { x: 100, y: 0 }]);
+/// let hulls = apex::operations::convex_hull::convex_hull_batch(&batch);
+/// assert_eq!(hulls.polygon(0), &[Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 50 }, Point2D { x: 50, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// assert_eq!(hulls.polygon(1), &[Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+/// ```
+pub fn convex_hull_batch(batch: &PolygonBatch) -> PolygonBatch {
+	match cost_model::choose(Operation::CONVEX_HULL, batch.len(), batch.sync_status()) {
+		Backend::ST => convex_hull_batch_st(batch),
+		Backend::MT => convex_hull_batch_mt(batch),
+		Backend::GPU => convex_hull_batch_mt(batch), //There is no GPU implementation for batches. The GPU is only chosen for a lot of work, which suits multiple threads best.
+	}
+}
+
+/// Find the convex hull of every polygon in a batch, on a single thread.
+///
+/// # Arguments
+/// * `batch` - The polygons to find the convex hulls of.
+///
+/// # Result
+/// A batch with the convex hull of each polygon, counter-clockwise, in the same order.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 50, y: 100 }, Point2D { x: 100, y: 0 }]); //Clockwise.
+/// let hulls = apex::operations::convex_hull::convex_hull_batch_st(&batch);
+/// assert_eq!(hulls.polygon(0), &[Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+/// ```
+pub fn convex_hull_batch_st(batch: &PolygonBatch) -> PolygonBatch {
+	batch.polygons().map(convex_hull_points_st).collect()
+}
+
+/// Find the convex hull of every polygon in a batch, on multiple threads.
+///
+/// The polygons are divided over the threads, and the hull of each polygon is found on a single
+/// thread.
+///
+/// # Arguments
+/// * `batch` - The polygons to find the convex hulls of.
+///
+/// # Result
+/// A batch with the convex hull of each polygon, counter-clockwise, in the same order.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 50, y: 100 }, Point2D { x: 100, y: 0 }]); //Clockwise.
+/// let hulls = apex::operations::convex_hull::convex_hull_batch_mt(&batch);
+/// assert_eq!(hulls.polygon(0), &[Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+/// ```
+pub fn convex_hull_batch_mt(batch: &PolygonBatch) -> PolygonBatch {
+	let polygons: Vec<&[Point2D]> = batch.polygons().collect();
+	let hulls: Vec<Polygon> = polygons.into_par_iter().map(convex_hull_points_st).collect();
+	PolygonBatch::from_iter(hulls)
+}
+
+/// Find the convex hull of points that are on the GPU.
+///
+/// This runs three kernels. The first finds the extreme points of each chunk of points in eight
+/// directions, from which the host picks the global extremes. They form a convex octagon inside the
+/// hull. The second kernel counts the points in each chunk that are not strictly inside the octagon,
+/// and the third copies those points to the output, at offsets computed from the counts. Only those
+/// points are copied to the host and sorted there.
+///
+/// # Arguments
+/// * `coordinates_handle` - The points on the GPU, with the X and Y coordinates interleaved.
+/// * `num_points` - The number of points. This must not be zero.
+///
+/// # Result
+/// The convex hull, counter-clockwise.
+#[cfg(feature = "gpu")]
+fn convex_hull_gpu(coordinates_handle: &cubecl::server::Handle, num_points: usize) -> Polygon {
+	use crate::detail::gpu;
+
+	let client = gpu::client();
+	let num_chunks = num_points.div_ceil(GPU_CHUNK_SIZE);
+	let extremes_handle = client.empty(num_chunks * 16 * size_of::<Coordinate>());
+	let (cube_count, cube_dim) = gpu::launch_dimensions(num_chunks);
+	unsafe {
+		extremes_kernel::launch::<gpu::GpuRuntime>(
+			&client,
+			cube_count,
+			cube_dim,
+			ArrayArg::from_raw_parts::<Coordinate>(coordinates_handle, num_points * 2, 1),
+			ArrayArg::from_raw_parts::<Coordinate>(&extremes_handle, num_chunks * 16, 1),
+			ScalarArg::new(GPU_CHUNK_SIZE as u32),
+		);
+	}
+	let chunk_extremes = Coordinate::from_bytes(&client.read_one(extremes_handle)).to_vec();
+	let mut octagon = [0; 16];
+	for direction in 0..8 {
+		let extreme = (0..num_chunks).map(|chunk| Point2D { x: chunk_extremes[chunk * 16 + direction * 2], y: chunk_extremes[chunk * 16 + direction * 2 + 1] })
+			.max_by_key(|point| extremeness(point, direction)).unwrap(); //There is at least one chunk.
+		octagon[direction * 2] = extreme.x;
+		octagon[direction * 2 + 1] = extreme.y;
+	}
+
+	let octagon_handle = client.create(Coordinate::as_bytes(&octagon));
+	let counts_handle = client.empty(num_chunks * size_of::<u32>());
+	let (cube_count, cube_dim) = gpu::launch_dimensions(num_chunks);
+	unsafe {
+		count_outside_octagon_kernel::launch::<gpu::GpuRuntime>(
+			&client,
+			cube_count,
+			cube_dim,
+			ArrayArg::from_raw_parts::<Coordinate>(coordinates_handle, num_points * 2, 1),
+			ArrayArg::from_raw_parts::<Coordinate>(&octagon_handle, 16, 1),
+			ArrayArg::from_raw_parts::<u32>(&counts_handle, num_chunks, 1),
+			ScalarArg::new(GPU_CHUNK_SIZE as u32),
+		);
+	}
+	let counts = u32::from_bytes(&client.read_one(counts_handle)).to_vec();
+	let mut offsets = Vec::with_capacity(num_chunks + 1);
+	offsets.push(0u32);
+	for count in &counts {
+		offsets.push(offsets.last().unwrap() + count);
+	}
+	let num_candidates = *offsets.last().unwrap() as usize; //Never zero, since the corners of the octagon are not strictly inside it.
+
+	let offsets_handle = client.create(u32::as_bytes(&offsets));
+	let candidates_handle = client.empty(num_candidates * 2 * size_of::<Coordinate>());
+	let (cube_count, cube_dim) = gpu::launch_dimensions(num_chunks);
+	unsafe {
+		list_outside_octagon_kernel::launch::<gpu::GpuRuntime>(
+			&client,
+			cube_count,
+			cube_dim,
+			ArrayArg::from_raw_parts::<Coordinate>(coordinates_handle, num_points * 2, 1),
+			ArrayArg::from_raw_parts::<Coordinate>(&octagon_handle, 16, 1),
+			ArrayArg::from_raw_parts::<u32>(&offsets_handle, offsets.len(), 1),
+			ArrayArg::from_raw_parts::<Coordinate>(&candidates_handle, num_candidates * 2, 1),
+			ScalarArg::new(GPU_CHUNK_SIZE as u32),
+		);
+	}
+	let mut candidates = gpu::download(&candidates_handle);
+	candidates.par_sort_unstable();
+	Polygon::from_iter(monotone_chain(&candidates))
+}
+
+/// Measure how far a point is in one of the eight directions that the GPU implementation finds
+/// extremes in.
+///
+/// # Arguments
+/// * `point` - The point to measure.
+/// * `direction` - Which direction to measure in, from 0 to 8. The directions are 45 degrees apart,
+/// counter-clockwise, starting at the positive X direction.
+///
+/// # Result
+/// The dot product of the point with the direction. Larger values are further in that direction.
+#[cfg(feature = "gpu")]
+#[allow(clippy::unnecessary_cast)] //With 64-bit coordinates, the coordinates are already i64.
+fn extremeness(point: &Point2D, direction: usize) -> i64 {
+	const DIRECTIONS: [(i64, i64); 8] = [(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)];
+	let (x, y) = DIRECTIONS[direction];
+	point.x as i64 * x + point.y as i64 * y
+}
+
+/// GPU kernel to find the extreme points of each chunk of points, in eight directions.
+///
+/// Every work unit processes one chunk of points.
+///
+/// # Arguments
+/// * `coordinates` - The points, with the X and Y coordinates interleaved.
+/// * `extremes` - The output, for each chunk the eight extreme points, with the X and Y coordinates
+/// interleaved. The directions are in the same order as in `extremeness`.
+/// * `chunk_size` - The number of points in each chunk.
+#[cube(launch)]
+fn extremes_kernel(coordinates: &Array<Coordinate>, extremes: &mut Array<Coordinate>, chunk_size: u32) {
+	let num_points = coordinates.len() / 2;
+	let start = ABSOLUTE_POS * chunk_size;
+	if start < num_points {
+		let mut end = start + chunk_size;
+		if end > num_points {
+			end = num_points;
+		}
+		let mut direction: u32 = 0;
+		while direction < 8 {
+			let direction_x = direction_x(direction);
+			let direction_y = direction_y(direction);
+			let mut best = start;
+			let mut best_extremeness = i64::cast_from(coordinates[start * 2]) * direction_x + i64::cast_from(coordinates[start * 2 + 1]) * direction_y;
+			let mut point = start + 1;
+			while point < end {
+				let point_extremeness = i64::cast_from(coordinates[point * 2]) * direction_x + i64::cast_from(coordinates[point * 2 + 1]) * direction_y;
+				if point_extremeness > best_extremeness {
+					best = point;
+					best_extremeness = point_extremeness;
+				}
+				point += 1;
+			}
+			extremes[ABSOLUTE_POS * 16 + direction * 2] = coordinates[best * 2];
+			extremes[ABSOLUTE_POS * 16 + direction * 2 + 1] = coordinates[best * 2 + 1];
+			direction += 1;
+		}
+	}
+}
+
+/// GPU kernel to count the points in each chunk that are not strictly inside an octagon.
+///
+/// # Arguments
+/// * `coordinates` - The points, with the X and Y coordinates interleaved.
+/// * `octagon` - The vertices of the octagon, counter-clockwise. Some vertices may coincide.
+/// * `counts` - The output, the number of points of each chunk that are not strictly inside.
+/// * `chunk_size` - The number of points in each chunk.
+#[cube(launch)]
+fn count_outside_octagon_kernel(coordinates: &Array<Coordinate>, octagon: &Array<Coordinate>, counts: &mut Array<u32>, chunk_size: u32) {
+	let num_points = coordinates.len() / 2;
+	let start = ABSOLUTE_POS * chunk_size;
+	if start < num_points {
+		let mut end = start + chunk_size;
+		if end > num_points {
+			end = num_points;
+		}
+		let mut count: u32 = 0;
+		let mut point = start;
+		while point < end {
+			if !strictly_inside_octagon(octagon, i64::cast_from(coordinates[point * 2]), i64::cast_from(coordinates[point * 2 + 1])) {
+				count += 1;
+			}
+			point += 1;
+		}
+		counts[ABSOLUTE_POS] = count;
+	}
+}
+
+/// GPU kernel to copy the points in each chunk that are not strictly inside an octagon to the
+/// output.
+///
+/// # Arguments
+/// * `coordinates` - The points, with the X and Y coordinates interleaved.
+/// * `octagon` - The vertices of the octagon, counter-clockwise. Some vertices may coincide.
+/// * `offsets` - Where the points of each chunk start in the output, followed by the total number
+/// of points in the output.
+/// * `candidates` - The output, the points that are not strictly inside, with the X and Y
+/// coordinates interleaved.
+/// * `chunk_size` - The number of points in each chunk.
+#[cube(launch)]
+fn list_outside_octagon_kernel(coordinates: &Array<Coordinate>, octagon: &Array<Coordinate>, offsets: &Array<u32>, candidates: &mut Array<Coordinate>, chunk_size: u32) {
+	let num_points = coordinates.len() / 2;
+	let start = ABSOLUTE_POS * chunk_size;
+	if start < num_points {
+		let mut end = start + chunk_size;
+		if end > num_points {
+			end = num_points;
+		}
+		let mut position = offsets[ABSOLUTE_POS];
+		let mut point = start;
+		while point < end {
+			if !strictly_inside_octagon(octagon, i64::cast_from(coordinates[point * 2]), i64::cast_from(coordinates[point * 2 + 1])) {
+				candidates[position * 2] = coordinates[point * 2];
+				candidates[position * 2 + 1] = coordinates[point * 2 + 1];
+				position += 1;
+			}
+			point += 1;
+		}
+	}
+}
+
+/// Test whether a point is strictly inside a convex octagon, on the GPU.
+///
+/// Edges between coinciding vertices are skipped. If the octagon has no area, no point is strictly
+/// inside it, since no point can be left of an edge in both directions.
+///
+/// # Arguments
+/// * `octagon` - The vertices of the octagon, counter-clockwise, with the X and Y coordinates
+/// interleaved.
+/// * `x` - The X coordinate of the point.
+/// * `y` - The Y coordinate of the point.
+#[cube]
+fn strictly_inside_octagon(octagon: &Array<Coordinate>, x: i64, y: i64) -> bool {
+	let mut inside = true;
+	let mut vertex: u32 = 0;
+	while vertex < 8 {
+		let next = (vertex + 1) % 8;
+		let start_x = i64::cast_from(octagon[vertex * 2]);
+		let start_y = i64::cast_from(octagon[vertex * 2 + 1]);
+		let end_x = i64::cast_from(octagon[next * 2]);
+		let end_y = i64::cast_from(octagon[next * 2 + 1]);
+		if (start_x != end_x || start_y != end_y) && orientation_gpu(start_x, start_y, end_x, end_y, x, y) <= 0 {
+			inside = false;
+		}
+		vertex += 1;
+	}
+	inside
+}
+
+/// Get the X component of one of the eight directions that extremes are found in, on the GPU.
+#[cube]
+fn direction_x(direction: u32) -> i64 {
+	let mut result: i64 = 0;
+	if direction == 0 || direction == 1 || direction == 7 {
+		result = 1;
+	} else if direction == 3 || direction == 4 || direction == 5 {
+		result = -1;
+	}
+	result
+}
+
+/// Get the Y component of one of the eight directions that extremes are found in, on the GPU.
+#[cube]
+fn direction_y(direction: u32) -> i64 {
+	let mut result: i64 = 0;
+	if direction == 1 || direction == 2 || direction == 3 {
+		result = 1;
+	} else if direction == 5 || direction == 6 || direction == 7 {
+		result = -1;
+	}
+	result
+}
+
+/// Find the vertices of the convex hull of sorted points, in sorted order rather than around the
+/// hull.
+///
+/// This is the intermediate result of the multi-threaded implementation. Keeping the vertices
+/// sorted allows the hulls of neighbouring ranges to be merged with the monotone chain algorithm.
+///
+/// # Arguments
+/// * `sorted` - The points, sorted.
+fn sorted_hull_vertices(sorted: &[Point2D]) -> Vec<Point2D> {
+	let mut hull = monotone_chain(sorted);
+	hull.sort_unstable();
+	hull
+}
+
+/// Find the convex hull of sorted points with Andrew's monotone chain algorithm.
+///
+/// The lower half of the hull is found by walking through the points from left to right, and the
+/// upper half by walking back. Points where the walk doesn't turn strictly left are removed.
+///
+/// # Arguments
+/// * `sorted` - The points, sorted. They may contain duplicates.
+///
+/// # Result
+/// The vertices of the hull, counter-clockwise, starting with the first point.
+fn monotone_chain(sorted: &[Point2D]) -> Vec<Point2D> {
+	let mut lower = Vec::new();
+	for point in sorted {
+		push_turning_left(&mut lower, *point);
+	}
+	if lower.len() < 2 {
+		return lower; //There are no distinct points to make a hull with.
+	}
+	let mut upper = Vec::new();
+	for point in sorted.iter().rev() {
+		push_turning_left(&mut upper, *point);
+	}
+	lower.pop(); //The last point of each half is the first point of the other.
+	upper.pop();
+	lower.extend(upper);
+	lower
+}
+
+/// Add a point to a chain of points, removing the last points of the chain until it turns strictly
+/// left towards the new point.
+///
+/// # Arguments
+/// * `chain` - The chain of points to add the point to.
+/// * `point` - The point to add. If it is equal to the last point of the chain, it is skipped.
+fn push_turning_left(chain: &mut Vec<Point2D>, point: Point2D) {
+	if chain.last() == Some(&point) {
+		return; //Duplicates are adjacent after sorting.
+	}
+	while chain.len() >= 2 && orientation(&chain[chain.len() - 2], &chain[chain.len() - 1], &point) <= 0 {
+		chain.pop();
+	}
+	chain.push(point);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+
+	/// Find the convex hull of points with every implementation, and check that they agree.
+	fn all_backends(points: &[Point2D]) -> Polygon {
+		let st = convex_hull_points_st(points);
+		assert_eq!(convex_hull_points_mt(points), st, "The multi-threaded implementation must give the same hull.");
+		assert_eq!(convex_hull_points(points), st, "The automatic choice must give the same hull.");
+		#[cfg(feature = "gpu")]
+		assert_eq!(convex_hull_points_gpu(points), st, "The GPU implementation must give the same hull.");
+		st
+	}
+
+	/// Generate pseudo-random points in a square, without depending on a random number generator.
+	fn scattered_points(num_points: usize, size: Coordinate) -> Vec<Point2D> {
+		let mut state: u64 = 12345;
+		let mut next = || {
+			state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+			((state >> 33) % (2 * size as u64 + 1)) as Coordinate - size
+		};
+		(0..num_points).map(|_| Point2D { x: next(), y: next() }).collect()
+	}
+
+	/// Test the hulls of degenerate sets of points.
+	#[test_case(&[], &[] ; "empty")]
+	#[test_case(&[Point2D { x: 5, y: 5 }], &[Point2D { x: 5, y: 5 }] ; "single")]
+	#[test_case(&[Point2D { x: 5, y: 5 }, Point2D { x: 5, y: 5 }, Point2D { x: 5, y: 5 }], &[Point2D { x: 5, y: 5 }] ; "duplicates")]
+	#[test_case(&[Point2D { x: 0, y: 0 }, Point2D { x: 30, y: 30 }, Point2D { x: 10, y: 10 }, Point2D { x: 20, y: 20 }], &[Point2D { x: 0, y: 0 }, Point2D { x: 30, y: 30 }] ; "collinear")]
+	#[test_case(&[Point2D { x: 0, y: 10 }, Point2D { x: 0, y: 0 }, Point2D { x: 0, y: 5 }], &[Point2D { x: 0, y: 0 }, Point2D { x: 0, y: 10 }] ; "vertical")]
+	fn degenerate(points: &[Point2D], expected: &[Point2D]) {
+		assert_eq!(all_backends(points), Polygon::from_iter(expected.iter().copied()));
+	}
+
+	/// Test that points on the edges of the hull are left out.
+	#[test]
+	fn collinear_on_edges() {
+		let points: Vec<Point2D> = (0..=10).flat_map(|i| [Point2D { x: i * 10, y: 0 }, Point2D { x: 100, y: i * 10 }, Point2D { x: i * 10, y: 100 }, Point2D { x: 0, y: i * 10 }]).collect();
+		let expected = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+		assert_eq!(all_backends(&points), expected);
+	}
+
+	/// Test the hulls of polygons.
+	#[test]
+	fn polygons() {
+		let square = crate::test::data::polygon::square_1000();
+		assert_eq!(convex_hull_polygon_st(&square), square, "A convex polygon is its own hull.");
+		let l_shape = crate::test::data::polygon::l_shape_1000();
+		let l_hull = convex_hull_polygon_st(&l_shape);
+		assert_eq!(l_hull.len(), 5, "The concave corner of the L is left out.");
+		let pentagram = crate::test::data::polygon::pentagram_1000();
+		let pentagram_hull = convex_hull_polygon_st(&pentagram);
+		assert_eq!(pentagram_hull.len(), 5, "The hull of a pentagram is a pentagon.");
+		for (polygon, hull) in [(&square, &square), (&l_shape, &l_hull), (&pentagram, &pentagram_hull)] {
+			assert_eq!(&convex_hull_polygon_mt(polygon), hull, "The multi-threaded implementation must give the same hull.");
+			assert_eq!(&convex_hull_polygon(polygon), hull, "The automatic choice must give the same hull.");
+			#[cfg(feature = "gpu")]
+			assert_eq!(&convex_hull_polygon_gpu(polygon), hull, "The GPU implementation must give the same hull.");
+		}
+	}
+
+	/// Test the hull of a multi-polygon, which includes all contours.
+	#[test]
+	fn multipolygon() {
+		let squares = crate::test::data::multi_polygon::two_squares_1000();
+		let hull = convex_hull_multipolygon_st(&squares);
+		assert_eq!(hull, convex_hull_points_st(squares.host_vertices()));
+		assert_eq!(convex_hull_multipolygon_mt(&squares), hull);
+		assert_eq!(convex_hull_multipolygon(&squares), hull);
+		#[cfg(feature = "gpu")]
+		assert_eq!(convex_hull_multipolygon_gpu(&squares), hull);
+	}
+
+	/// Test the hull of many scattered points, which the multi-threaded implementation divides into
+	/// many ranges.
+	#[test]
+	fn scattered() {
+		let points = scattered_points(100_000, 1_000_000);
+		let hull = all_backends(&points);
+		let vertices = hull.host_vertices();
+		assert!(vertices.len() >= 3, "Scattered points have a hull with area.");
+		for i in 0..vertices.len() {
+			let start = &vertices[i];
+			let end = &vertices[(i + 1) % vertices.len()];
+			for point in &points {
+				assert!(orientation(start, end, point) >= 0, "All points must be inside the hull, which is counter-clockwise.");
+			}
+			assert!(orientation(start, end, &vertices[(i + 2) % vertices.len()]) > 0, "The hull must turn strictly left at every vertex.");
+		}
+	}
+
+	/// Test that the hull works with extreme coordinates.
+	///
+	/// These are the extremes of the range in which the operations are exact, which is the whole range
+	/// of 32-bit coordinates.
+	#[test]
+	fn extreme_coordinates() {
+		let max = crate::MAX_EXACT_COORDINATE;
+		let min = -max - 1;
+		let points = [Point2D { x: min, y: min }, Point2D { x: max, y: min }, Point2D { x: 0, y: 0 }, Point2D { x: max, y: max }, Point2D { x: min, y: max }, Point2D { x: min + 1, y: max - 1 }];
+		let expected = Polygon::from_iter([Point2D { x: min, y: min }, Point2D { x: max, y: min }, Point2D { x: max, y: max }, Point2D { x: min, y: max }]);
+		assert_eq!(all_backends(&points), expected);
+	}
+
+	/// Test the convex hulls of the polygons in a batch, which are found separately.
+	#[test]
+	fn batch_polygons() {
+		let polygons = [crate::test::data::polygon::l_shape_1000(), Polygon::new(), crate::test::data::polygon::pentagram_1000(), crate::test::data::polygon::triangle_1000()];
+		let batch = PolygonBatch::from_iter(polygons.iter().map(|polygon| Polygon::from_iter(polygon.host_vertices().iter().copied())));
+		let expected: Vec<Polygon> = polygons.iter().map(convex_hull_polygon_st).collect();
+		for hulls in [convex_hull_batch_st(&batch), convex_hull_batch_mt(&batch), convex_hull_batch(&batch)] {
+			assert_eq!(hulls.num_polygons(), expected.len());
+			for (hull, expected) in hulls.polygons().zip(&expected) {
+				assert_eq!(hull, expected.host_vertices());
+			}
+		}
+	}
+}
\ No newline at end of file
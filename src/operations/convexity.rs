@@ -0,0 +1,717 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! This module contains the implementations of operations to determine the convexity of geometric
+//! objects.
+//!
+//! Polygons are classified as follows:
+//! * Duplicate consecutive vertices are ignored, as are vertices in the middle of a straight line.
+//! * If the polygon has fewer than 3 vertices left, or it has no area, it is degenerate.
+//! * If the polygon reverses direction somewhere, forming a spike of zero width, it is degenerate.
+//! * If the polygon turns the same way at every vertex and goes around exactly once, it is convex.
+//! * If the polygon turns the same way at every vertex but goes around multiple times, like a
+//! pentagram, it is concave.
+//! * Otherwise, if the polygon is simple, it is concave.
+//! * Self-intersecting polygons are degenerate if two of their edges overlap in opposite
+//! directions, creating a part with zero width, or if part of the polygon is wound the opposite
+//! way of the polygon as a whole, creating a negative part. Otherwise they are concave.
+//!
+//! This means that the convexity is relative to the orientation of the polygon. A clockwise square
+//! is a convex negative shape. It is not considered to be degenerate.
+//!
+//! Finding negative parts requires computing the winding number next to every edge of the
+//! polygon, which takes quadratic time. This is only done for polygons that turn both ways and
+//! intersect themselves.
+
+use std::cmp;
+use rayon::prelude::*; //For multi-threaded implementations.
+
+use crate::Convexity; //To return the convexity of the polygon.
+use crate::MultiPolygon; //Determine the convexity of multi-polygons.
+use crate::Point2D; //To analyse the vertices of the polygon.
+use crate::Polygon; //Determine the convexity of polygons.
+use crate::PolygonBatch; //Determine the convexity of batches of polygons at once.
+use crate::cost_model::{self, Backend, Operation}; //To choose the fastest implementation.
+use crate::operations::area; //Polygons without area are degenerate.
+use crate::predicates::{self, SegmentIntersection, direction}; //To find how the edges meet.
+
+/// Determine the convexity of a polygon.
+///
+/// This chooses automatically whether to determine the convexity on a single thread or on multiple
+/// threads. The cost model estimates which is fastest, from the number of vertices in the polygon.
+///
+/// # Examples
+/// ```
+/// use apex::{Convexity, Point2D, Polygon};
+/// let triangle = Polygon::from_iter([
+/// 	Point2D { x: 0, y: 0 },
+/// 	Point2D { x: 100, y: 0 },
+/// 	Point2D { x: 50, y: 100 }
+/// ]);
+/// assert_eq!(apex::operations::convexity::convexity_polygon(&triangle), Convexity::CONVEX);
+/// ```
+pub fn convexity_polygon(polygon: &Polygon) -> Convexity {
+	match cost_model::choose(Operation::CONVEXITY, polygon.len(), polygon.sync_status()) {
+		Backend::ST => convexity_polygon_st(polygon),
+		Backend::MT => convexity_polygon_mt(polygon),
+		Backend::GPU => unreachable!("There is no GPU implementation, so the cost model never chooses the GPU."),
+	}
+}
+
+/// Determine the convexity of a polygon.
+///
+/// This implementation is single-threaded.
+///
+/// # Examples
+/// ```
+/// use apex::{Convexity, Point2D, Polygon};
+/// let triangle = Polygon::from_iter([
+/// 	Point2D { x: 0, y: 0 },
+/// 	Point2D { x: 100, y: 0 },
+/// 	Point2D { x: 50, y: 100 }
+/// ]);
+/// assert_eq!(apex::operations::convexity::convexity_polygon_st(&triangle), Convexity::CONVEX);
+/// let arrow = Polygon::from_iter([
+/// 	Point2D { x: 0, y: 0 },
+/// 	Point2D { x: 50, y: 20 },
+/// 	Point2D { x: 100, y: 0 },
+/// 	Point2D { x: 50, y: 100 }
+/// ]);
+/// assert_eq!(apex::operations::convexity::convexity_polygon_st(&arrow), Convexity::CONCAVE);
+/// ```
+pub fn convexity_polygon_st(polygon: &Polygon) -> Convexity {
+	convexity_contour_st(polygon.host_vertices())
+}
+
+/// Determine the convexity of a polygon.
+///
+/// This implementation is multi-threaded. The turns at the vertices, the self-intersections and the
+/// winding numbers around the edges are each computed in parallel.
+///
+/// # Examples
+/// ```
+/// use apex::{Convexity, Point2D, Polygon};
+/// let triangle = Polygon::from_iter([
+/// 	Point2D { x: 0, y: 0 },
+/// 	Point2D { x: 100, y: 0 },
+/// 	Point2D { x: 50, y: 100 }
+/// ]);
+/// assert_eq!(apex::operations::convexity::convexity_polygon_mt(&triangle), Convexity::CONVEX);
+/// let arrow = Polygon::from_iter([
+/// 	Point2D { x: 0, y: 0 },
+/// 	Point2D { x: 50, y: 20 },
+/// 	Point2D { x: 100, y: 0 },
+/// 	Point2D { x: 50, y: 100 }
+/// ]);
+/// assert_eq!(apex::operations::convexity::convexity_polygon_mt(&arrow), Convexity::CONCAVE);
+/// ```
+pub fn convexity_polygon_mt(polygon: &Polygon) -> Convexity {
+	convexity_contour_mt(polygon.host_vertices())
+}
+
+/// Determine the convexity of a multi-polygon.
+///
+/// A multi-polygon with a single contour has the convexity of that contour. A multi-polygon with
+/// multiple contours has either multiple disjoint parts or holes, so it is concave. If any of the
+/// contours is degenerate, or if there are no contours at all, the multi-polygon is degenerate.
+/// Empty contours are ignored.
+///
+/// This chooses automatically whether to determine the convexity on a single thread or on multiple
+/// threads, in the same way as `convexity_polygon`.
+///
+/// # Examples
+/// ```
+/// use apex::{Convexity, MultiPolygon, Point2D};
+/// let mut multi = MultiPolygon::new();
+/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+/// assert_eq!(apex::operations::convexity::convexity_multipolygon(&multi), Convexity::CONVEX);
+/// multi.push_contour([Point2D { x: 200, y: 0 }, Point2D { x: 300, y: 0 }, Point2D { x: 250, y: 100 }]);
+/// assert_eq!(apex::operations::convexity::convexity_multipolygon(&multi), Convexity::CONCAVE);
+/// ```
+pub fn convexity_multipolygon(multi_polygon: &MultiPolygon) -> Convexity {
+	match cost_model::choose(Operation::CONVEXITY, multi_polygon.len(), multi_polygon.sync_status()) {
+		Backend::ST => convexity_multipolygon_st(multi_polygon),
+		Backend::MT => convexity_multipolygon_mt(multi_polygon),
+		Backend::GPU => unreachable!("There is no GPU implementation, so the cost model never chooses the GPU."),
+	}
+}
+
+/// Determine the convexity of a multi-polygon.
+///
+/// This implementation is single-threaded. It determines the convexity of each contour one by one.
+///
+/// # Examples
+/// ```
+/// use apex::{Convexity, MultiPolygon, Point2D};
+/// let mut multi = MultiPolygon::new();
+/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+/// assert_eq!(apex::operations::convexity::convexity_multipolygon_st(&multi), Convexity::CONVEX);
+/// ```
+pub fn convexity_multipolygon_st(multi_polygon: &MultiPolygon) -> Convexity {
+	combine_contours(multi_polygon.contours().filter(|contour| !contour.is_empty()).map(convexity_contour_st).collect())
+}
+
+/// Determine the convexity of a multi-polygon.
+///
+/// This implementation is multi-threaded. The contours are processed in parallel, and each contour
+/// is processed with multiple threads as well.
+///
+/// # Examples
+/// ```
+/// use apex::{Convexity, MultiPolygon, Point2D};
+/// let mut multi = MultiPolygon::new();
+/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+/// assert_eq!(apex::operations::convexity::convexity_multipolygon_mt(&multi), Convexity::CONVEX);
+/// ```
+pub fn convexity_multipolygon_mt(multi_polygon: &MultiPolygon) -> Convexity {
+	let contours: Vec<&[Point2D]> = multi_polygon.contours().filter(|contour| !contour.is_empty()).collect();
+	combine_contours(contours.into_par_iter().map(convexity_contour_mt).collect())
+}
+
+/// Determine the convexity of every polygon in a batch.
+///
+/// This chooses automatically whether to determine the convexities on a single thread or on
+/// multiple threads, in the same way as `convexity_polygon`. The cost model considers the total
+/// number of vertices in the batch.
+///
+/// # Result
+/// The convexity of each polygon, in the order that they were added to the batch.
+///
+/// # Examples
+/// ```
+/// use apex::{Convexity, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 50, y: 20 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+/// assert_eq!(apex::operations::convexity::convexity_batch(&batch), vec![Convexity::CONVEX, Convexity::CONCAVE]);
+/// ```
+pub fn convexity_batch(batch: &PolygonBatch) -> Vec<Convexity> {
+	match cost_model::choose(Operation::CONVEXITY, batch.len(), batch.sync_status()) {
+		Backend::ST => convexity_batch_st(batch),
+		Backend::MT => convexity_batch_mt(batch),
+		Backend::GPU => unreachable!("There is no GPU implementation, so the cost model never chooses the GPU."),
+	}
+}
+
+/// Determine the convexity of every polygon in a batch.
+///
+/// This implementation is single-threaded. It determines the convexities of the polygons one by
+/// one.
+///
+/// # Examples
+/// ```
+/// use apex::{Convexity, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }]);
+/// assert_eq!(apex::operations::convexity::convexity_batch_st(&batch), vec![Convexity::CONVEX, Convexity::DEGENERATE]);
+/// ```
+pub fn convexity_batch_st(batch: &PolygonBatch) -> Vec<Convexity> {
+	batch.polygons().map(convexity_contour_st).collect()
+}
+
+/// Determine the convexity of every polygon in a batch.
+///
+/// This implementation is multi-threaded. The polygons are divided over the threads, and each
+/// polygon is processed on a single thread.
+///
+/// # Examples
+/// ```
+/// use apex::{Convexity, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }]);
+/// assert_eq!(apex::operations::convexity::convexity_batch_mt(&batch), vec![Convexity::CONVEX, Convexity::DEGENERATE]);
+/// ```
+pub fn convexity_batch_mt(batch: &PolygonBatch) -> Vec<Convexity> {
+	let polygons: Vec<&[Point2D]> = batch.polygons().collect();
+	polygons.into_par_iter().map(convexity_contour_st).collect()
+}
+
+/// Combine the convexities of the contours of a multi-polygon into the convexity of the whole.
+///
+/// # Arguments
+/// * `convexities` - The convexity of each of the non-empty contours of the multi-polygon.
+fn combine_contours(convexities: Vec<Convexity>) -> Convexity {
+	if convexities.is_empty() || convexities.contains(&Convexity::DEGENERATE) {
+		return Convexity::DEGENERATE;
+	}
+	if convexities.len() == 1 {
+		return convexities[0];
+	}
+	Convexity::CONCAVE //Multiple parts or holes.
+}
+
+/// Determine the convexity of a single closed contour, single-threaded.
+///
+/// # Arguments
+/// * `vertices` - The vertices of the contour.
+fn convexity_contour_st(vertices: &[Point2D]) -> Convexity {
+	let vertices = distinct_vertices(vertices);
+	if vertices.len() < 3 {
+		return Convexity::DEGENERATE;
+	}
+	let doubled_area = area::doubled_area_st(&vertices);
+	if doubled_area == 0 {
+		return Convexity::DEGENERATE;
+	}
+
+	let turns = (0..vertices.len()).map(|vertex| Turns::at(&vertices, vertex)).fold(Turns::default(), Turns::combine);
+	if let Some(convexity) = turns.classify() {
+		return convexity;
+	}
+
+	let mut edges_by_x: Vec<usize> = (0..vertices.len()).collect();
+	edges_by_x.sort_unstable_by_key(|edge| cmp::min(vertices[*edge].x, vertices[(*edge + 1) % vertices.len()].x));
+	let contact = (0..edges_by_x.len()).map(|position| contact_with_later_edges(&vertices, &edges_by_x, position)).max().unwrap_or(Contact::NONE);
+	match contact {
+		Contact::NONE => Convexity::CONCAVE, //Simple polygons can't have negative parts.
+		Contact::OVERLAP => Convexity::DEGENERATE,
+		Contact::CROSSING => {
+			if turns.is_one_way() { //Polygons that only turn one way can't have negative parts.
+				return Convexity::CONCAVE;
+			}
+			if (0..vertices.len()).any(|edge| has_negative_side(&vertices, edge, doubled_area)) {
+				Convexity::DEGENERATE
+			} else {
+				Convexity::CONCAVE
+			}
+		}
+	}
+}
+
+/// Determine the convexity of a single closed contour, multi-threaded.
+///
+/// # Arguments
+/// * `vertices` - The vertices of the contour.
+fn convexity_contour_mt(vertices: &[Point2D]) -> Convexity {
+	let vertices = distinct_vertices(vertices);
+	if vertices.len() < 3 {
+		return Convexity::DEGENERATE;
+	}
+	let doubled_area = area::doubled_area_mt(&vertices);
+	if doubled_area == 0 {
+		return Convexity::DEGENERATE;
+	}
+
+	let chunk_size = cost_model::chunk_size(Operation::CONVEXITY, vertices.len());
+	let turns = (0..vertices.len()).into_par_iter().with_min_len(chunk_size).map(|vertex| Turns::at(&vertices, vertex)).reduce(Turns::default, Turns::combine);
+	if let Some(convexity) = turns.classify() {
+		return convexity;
+	}
+
+	let mut edges_by_x: Vec<usize> = (0..vertices.len()).collect();
+	edges_by_x.par_sort_unstable_by_key(|edge| cmp::min(vertices[*edge].x, vertices[(*edge + 1) % vertices.len()].x));
+	let contact = (0..edges_by_x.len()).into_par_iter().map(|position| contact_with_later_edges(&vertices, &edges_by_x, position)).max().unwrap_or(Contact::NONE);
+	match contact {
+		Contact::NONE => Convexity::CONCAVE, //Simple polygons can't have negative parts.
+		Contact::OVERLAP => Convexity::DEGENERATE,
+		Contact::CROSSING => {
+			if turns.is_one_way() { //Polygons that only turn one way can't have negative parts.
+				return Convexity::CONCAVE;
+			}
+			if (0..vertices.len()).into_par_iter().any(|edge| has_negative_side(&vertices, edge, doubled_area)) {
+				Convexity::DEGENERATE
+			} else {
+				Convexity::CONCAVE
+			}
+		}
+	}
+}
+
+/// A tally of the turns that a polygon makes at its vertices.
+#[derive(Clone, Copy, Default)]
+struct Turns {
+	/// The number of vertices where the polygon turns left (counter-clockwise).
+	left: usize,
+
+	/// The number of vertices where the polygon turns right (clockwise).
+	right: usize,
+
+	/// The number of vertices where the polygon reverses its direction.
+	spikes: usize,
+
+	/// The number of left turns where the direction of the polygon passes the positive X axis.
+	///
+	/// After turning left all the way around, this counts how many times the polygon went around.
+	left_wraps: usize,
+
+	/// The number of right turns where the direction of the polygon passes the positive X axis.
+	///
+	/// After turning right all the way around, this counts how many times the polygon went around.
+	right_wraps: usize,
+}
+
+impl Turns {
+	/// Find the turn that a polygon makes at one of its vertices.
+	///
+	/// # Arguments
+	/// * `vertices` - The vertices of the polygon, without consecutive duplicates.
+	/// * `vertex` - The index of the vertex to find the turn at.
+	fn at(vertices: &[Point2D], vertex: usize) -> Turns {
+		let previous = &vertices[(vertex + vertices.len() - 1) % vertices.len()];
+		let current = &vertices[vertex];
+		let next = &vertices[(vertex + 1) % vertices.len()];
+		let incoming = direction(previous, current);
+		let outgoing = direction(current, next);
+		let cross = incoming.0 * outgoing.1 - incoming.1 * outgoing.0;
+		let mut turns = Turns::default();
+		if cross > 0 {
+			turns.left = 1;
+			turns.left_wraps = angle_less(outgoing, incoming) as usize;
+		} else if cross < 0 {
+			turns.right = 1;
+			turns.right_wraps = angle_less(incoming, outgoing) as usize;
+		} else if incoming.0 * outgoing.0 + incoming.1 * outgoing.1 < 0 {
+			turns.spikes = 1;
+		}
+		turns
+	}
+
+	/// Add up the turns of two parts of a polygon.
+	fn combine(self, other: Turns) -> Turns {
+		Turns {
+			left: self.left + other.left,
+			right: self.right + other.right,
+			spikes: self.spikes + other.spikes,
+			left_wraps: self.left_wraps + other.left_wraps,
+			right_wraps: self.right_wraps + other.right_wraps,
+		}
+	}
+
+	/// Whether the polygon only ever turns one way, or goes straight on.
+	fn is_one_way(&self) -> bool {
+		self.left == 0 || self.right == 0
+	}
+
+	/// Determine the convexity of the polygon from its turns alone, if possible.
+	///
+	/// # Result
+	/// The convexity of the polygon, or `None` if the turns are not enough to determine it.
+	fn classify(&self) -> Option<Convexity> {
+		if self.spikes > 0 || (self.left == 0 && self.right == 0) {
+			return Some(Convexity::DEGENERATE);
+		}
+		if self.right == 0 && self.left_wraps == 1 {
+			return Some(Convexity::CONVEX);
+		}
+		if self.left == 0 && self.right_wraps == 1 {
+			return Some(Convexity::CONVEX);
+		}
+		None
+	}
+}
+
+/// The worst kind of contact found between non-adjacent edges of a polygon.
+///
+/// The variants are ordered from least to most severe.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Contact {
+	/// The edges don't touch.
+	NONE,
+
+	/// The edges cross or touch each other, or overlap in the same direction.
+	CROSSING,
+
+	/// The edges overlap in opposite directions, enclosing a part with zero width.
+	OVERLAP,
+}
+
+/// Find the worst contact between an edge and the edges that come after it in X order.
+///
+/// Only the edges whose X range overlaps with the X range of the edge are checked.
+///
+/// # Arguments
+/// * `vertices` - The vertices of the polygon, without consecutive duplicates.
+/// * `edges_by_x` - The indices of the edges of the polygon, sorted by their lowest X coordinate.
+/// * `position` - The position in `edges_by_x` of the edge to check.
+fn contact_with_later_edges(vertices: &[Point2D], edges_by_x: &[usize], position: usize) -> Contact {
+	let edge = edges_by_x[position];
+	let start = &vertices[edge];
+	let end = &vertices[(edge + 1) % vertices.len()];
+	let max_x = cmp::max(start.x, end.x);
+	let mut worst = Contact::NONE;
+	for other in edges_by_x[position + 1..].iter().copied() {
+		let other_start = &vertices[other];
+		let other_end = &vertices[(other + 1) % vertices.len()];
+		if cmp::min(other_start.x, other_end.x) > max_x {
+			break; //This and all further edges are completely to the right of this edge.
+		}
+		let distance = edge.abs_diff(other);
+		if distance == 1 || distance == vertices.len() - 1 {
+			continue; //Adjacent edges share a vertex. They can only overlap as a spike, which has been checked already.
+		}
+		worst = cmp::max(worst, contact(start, end, other_start, other_end));
+		if worst == Contact::OVERLAP {
+			break; //Can't get any worse.
+		}
+	}
+	worst
+}
+
+/// Find the contact between two line segments.
+fn contact(start: &Point2D, end: &Point2D, other_start: &Point2D, other_end: &Point2D) -> Contact {
+	match predicates::segment_intersection(start, end, other_start, other_end) {
+		SegmentIntersection::DISJOINT => Contact::NONE,
+		SegmentIntersection::OVERLAPPING => {
+			let this_direction = direction(start, end);
+			let other_direction = direction(other_start, other_end);
+			if this_direction.0 * other_direction.0 + this_direction.1 * other_direction.1 < 0 {
+				Contact::OVERLAP
+			} else {
+				Contact::CROSSING
+			}
+		},
+		SegmentIntersection::CROSSING | SegmentIntersection::TOUCHING => Contact::CROSSING,
+	}
+}
+
+/// Check whether an edge of a polygon borders on a part of the polygon that is wound the opposite
+/// way of the polygon as a whole.
+///
+/// This computes the winding number of the polygon around a point infinitesimally to the left of
+/// the middle of the edge. The winding number on the right of the edge is one less than that.
+///
+/// The point is perturbed symbolically. It is the middle of the edge, plus an infinitesimal amount
+/// of the left normal of the edge, plus an even smaller amount up, plus an even smaller amount to
+/// the right. This way, the point never lies exactly on another edge or at the same height as
+/// another vertex, while all computations remain exact.
+///
+/// # Arguments
+/// * `vertices` - The vertices of the polygon, without consecutive duplicates.
+/// * `edge` - The index of the edge to check.
+/// * `doubled_area` - Twice the area of the polygon, which indicates its orientation.
+fn has_negative_side(vertices: &[Point2D], edge: usize, doubled_area: i128) -> bool {
+	let start = &vertices[edge];
+	let end = &vertices[(edge + 1) % vertices.len()];
+	//Work in doubled coordinates, so that the middle of the edge is on the grid.
+	let middle = (start.x as i128 + end.x as i128, start.y as i128 + end.y as i128);
+	let normal = (start.y as i128 - end.y as i128, end.x as i128 - start.x as i128);
+	let is_above = |point: &Point2D| {
+		let y = point.y as i128 * 2;
+		if y != middle.1 {
+			y > middle.1
+		} else {
+			normal.1 < 0 //Else it's below, due to the perturbation upwards.
+		}
+	};
+
+	let mut winding = 0;
+	for i in 0..vertices.len() {
+		let segment_start = &vertices[i];
+		let segment_end = &vertices[(i + 1) % vertices.len()];
+		let start_above = is_above(segment_start);
+		let end_above = is_above(segment_end);
+		if start_above == end_above {
+			continue; //Doesn't cross the horizontal ray through the point.
+		}
+		//Which side of the segment is the point on? Take the first term of the perturbation that is not zero.
+		let segment = direction(segment_start, segment_end);
+		let relative = (middle.0 - segment_start.x as i128 * 2, middle.1 - segment_start.y as i128 * 2);
+		let terms = [
+			segment.0 * relative.1 - segment.1 * relative.0,
+			segment.0 * normal.1 - segment.1 * normal.0,
+			segment.0,
+			-segment.1,
+		];
+		let side = terms.into_iter().find(|term| *term != 0).unwrap_or(0);
+		if !start_above && side > 0 {
+			winding += 1; //Upward crossing with the point on the left.
+		} else if start_above && side < 0 {
+			winding -= 1; //Downward crossing with the point on the right.
+		}
+	}
+
+	if doubled_area > 0 {
+		winding - 1 < 0 //The right side is negative.
+	} else {
+		winding > 0 //The left side is positive, while the polygon as a whole is negative.
+	}
+}
+
+/// Copy the vertices of a polygon, leaving out consecutive duplicates.
+///
+/// The polygon is closed, so the last vertex is also compared to the first.
+fn distinct_vertices(vertices: &[Point2D]) -> Vec<Point2D> {
+	let mut result: Vec<Point2D> = Vec::with_capacity(vertices.len());
+	for vertex in vertices {
+		if result.last() != Some(vertex) {
+			result.push(*vertex);
+		}
+	}
+	while result.len() > 1 && result.last() == result.first() {
+		result.pop();
+	}
+	result
+}
+
+/// Compare the angles of two directions, measured counter-clockwise from the positive X axis.
+///
+/// # Result
+/// Whether the angle of the first direction is smaller than the angle of the second direction.
+fn angle_less(first: (i128, i128), second: (i128, i128)) -> bool {
+	let half = |direction: (i128, i128)| direction.1 < 0 || (direction.1 == 0 && direction.0 < 0);
+	let first_half = half(first);
+	let second_half = half(second);
+	if first_half != second_half {
+		return second_half;
+	}
+	first.0 * second.1 - first.1 * second.0 > 0
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test::data::polygon;
+	use test_case::test_case;
+
+	/// Test the convexity of polygons with too few vertices to enclose anything.
+	#[test_case(&[] ; "empty")]
+	#[test_case(&[Point2D { x: 40, y: 70 }] ; "point")]
+	#[test_case(&[Point2D { x: 40, y: 70 }, Point2D { x: 1000, y: -50 }] ; "line")]
+	#[test_case(&[Point2D { x: 0, y: 0 }, Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 0 }] ; "line_with_duplicates")]
+	fn convexity_polygon_too_few_vertices(vertices: &[Point2D]) {
+		let poly = Polygon::from_iter(vertices.iter().copied());
+		assert_eq!(convexity_polygon_st(&poly), Convexity::DEGENERATE, "The polygon can't enclose any area with fewer than 3 vertices.");
+		assert_eq!(convexity_polygon_mt(&poly), Convexity::DEGENERATE, "The polygon can't enclose any area with fewer than 3 vertices.");
+	}
+
+	/// Test the convexity of polygons that are convex.
+	#[test_case(polygon::square_1000() ; "square")]
+	#[test_case(polygon::triangle_1000() ; "triangle")]
+	#[test_case(Polygon::from_iter(polygon::square_1000().host_vertices().iter().rev().copied()) ; "clockwise_square")]
+	#[test_case(Polygon::from_iter([
+		Point2D { x: 0, y: 0 },
+		Point2D { x: 500, y: 0 },
+		Point2D { x: 1000, y: 0 },
+		Point2D { x: 1000, y: 1000 },
+		Point2D { x: 0, y: 1000 },
+		Point2D { x: 0, y: 500 }
+	]) ; "collinear_vertices")]
+	#[test_case(Polygon::from_iter([
+		Point2D { x: 0, y: 0 },
+		Point2D { x: 0, y: 0 },
+		Point2D { x: 1000, y: 0 },
+		Point2D { x: 1000, y: 1000 },
+		Point2D { x: 1000, y: 1000 },
+		Point2D { x: 0, y: 1000 },
+		Point2D { x: 0, y: 0 }
+	]) ; "duplicate_vertices")]
+	fn convexity_polygon_convex(poly: Polygon) {
+		assert_eq!(convexity_polygon_st(&poly), Convexity::CONVEX);
+		assert_eq!(convexity_polygon_mt(&poly), Convexity::CONVEX);
+		assert_eq!(convexity_polygon(&poly), Convexity::CONVEX);
+	}
+
+	/// Test the convexity of polygons that are concave.
+	#[test_case(polygon::l_shape_1000() ; "l_shape")]
+	#[test_case(polygon::pentagram_1000() ; "pentagram")]
+	#[test_case(Polygon::from_iter([
+		Point2D { x: 500, y: 1000 },
+		Point2D { x: 344, y: 550 }, //A notch in the pentagram, which makes it turn both ways.
+		Point2D { x: 206, y: 95 },
+		Point2D { x: 976, y: 655 },
+		Point2D { x: 24, y: 655 },
+		Point2D { x: 794, y: 95 }
+	]) ; "notched_pentagram")]
+	#[test_case(Polygon::from_iter([
+		Point2D { x: 0, y: 0 },
+		Point2D { x: 1000, y: 0 },
+		Point2D { x: 1000, y: 1000 }, //Touches the second square here.
+		Point2D { x: 2000, y: 1000 },
+		Point2D { x: 2000, y: 2000 },
+		Point2D { x: 1000, y: 2000 },
+		Point2D { x: 1000, y: 1000 },
+		Point2D { x: 0, y: 1000 }
+	]) ; "touching_squares")]
+	fn convexity_polygon_concave(poly: Polygon) {
+		assert_eq!(convexity_polygon_st(&poly), Convexity::CONCAVE);
+		assert_eq!(convexity_polygon_mt(&poly), Convexity::CONCAVE);
+		assert_eq!(convexity_polygon(&poly), Convexity::CONCAVE);
+	}
+
+	/// Test the convexity of polygons that are degenerate.
+	#[test_case(polygon::bowtie_1000() ; "bowtie")]
+	#[test_case(Polygon::from_iter([
+		Point2D { x: 0, y: 0 },
+		Point2D { x: 1000, y: 1000 },
+		Point2D { x: 1000, y: 0 },
+		Point2D { x: 0, y: 2000 }
+	]) ; "uneven_bowtie")]
+	#[test_case(Polygon::from_iter([
+		Point2D { x: 0, y: 0 },
+		Point2D { x: 1000, y: 0 },
+		Point2D { x: 2000, y: 0 }
+	]) ; "collinear")]
+	#[test_case(Polygon::from_iter([
+		Point2D { x: 0, y: 0 },
+		Point2D { x: 1000, y: 0 },
+		Point2D { x: 1000, y: 1000 },
+		Point2D { x: 500, y: 1000 },
+		Point2D { x: 500, y: 1500 }, //Spike of zero width.
+		Point2D { x: 500, y: 1000 },
+		Point2D { x: 0, y: 1000 }
+	]) ; "spike")]
+	#[test_case(Polygon::from_iter([
+		Point2D { x: 0, y: 0 },
+		Point2D { x: 3000, y: 0 },
+		Point2D { x: 3000, y: 3000 },
+		Point2D { x: 1500, y: 3000 },
+		Point2D { x: 1500, y: 2000 }, //A bridge into a hole, and later back along the same line.
+		Point2D { x: 2000, y: 2000 },
+		Point2D { x: 2000, y: 1000 },
+		Point2D { x: 1000, y: 1000 },
+		Point2D { x: 1000, y: 2000 },
+		Point2D { x: 1500, y: 2000 },
+		Point2D { x: 1500, y: 3000 },
+		Point2D { x: 0, y: 3000 }
+	]) ; "keyhole")]
+	fn convexity_polygon_degenerate(poly: Polygon) {
+		assert_eq!(convexity_polygon_st(&poly), Convexity::DEGENERATE);
+		assert_eq!(convexity_polygon_mt(&poly), Convexity::DEGENERATE);
+		assert_eq!(convexity_polygon(&poly), Convexity::DEGENERATE);
+	}
+
+	/// Test that the convexity doesn't depend on where the seam of the polygon is.
+	#[test]
+	fn convexity_polygon_seam() {
+		for original in [polygon::square_1000(), polygon::l_shape_1000(), polygon::pentagram_1000(), polygon::bowtie_1000()] {
+			let expected = convexity_polygon_st(&original);
+			for seam in 1..original.len() {
+				let vertices = original.host_vertices();
+				let poly = Polygon::from_iter(vertices[seam..].iter().chain(vertices[..seam].iter()).copied());
+				assert_eq!(convexity_polygon_st(&poly), expected, "Moving the seam must not change the convexity.");
+				assert_eq!(convexity_polygon_mt(&poly), expected, "Moving the seam must not change the convexity.");
+			}
+		}
+	}
+
+	/// Test the convexity of multi-polygons.
+	#[test_case(MultiPolygon::new(), Convexity::DEGENERATE ; "empty")]
+	#[test_case(MultiPolygon::from_iter([polygon::square_1000()]), Convexity::CONVEX ; "single_convex")]
+	#[test_case(MultiPolygon::from_iter([polygon::l_shape_1000()]), Convexity::CONCAVE ; "single_concave")]
+	#[test_case(MultiPolygon::from_iter([Polygon::new(), polygon::triangle_1000()]), Convexity::CONVEX ; "empty_contour_ignored")]
+	#[test_case(crate::test::data::multi_polygon::square_with_hole_1000(), Convexity::CONCAVE ; "square_with_hole")]
+	#[test_case(crate::test::data::multi_polygon::two_squares_1000(), Convexity::CONCAVE ; "two_squares")]
+	#[test_case(MultiPolygon::from_iter([polygon::square_1000(), polygon::bowtie_1000()]), Convexity::DEGENERATE ; "degenerate_contour")]
+	fn convexity_multipolygon_contours(multi: MultiPolygon, expected: Convexity) {
+		assert_eq!(convexity_multipolygon_st(&multi), expected);
+		assert_eq!(convexity_multipolygon_mt(&multi), expected);
+		assert_eq!(convexity_multipolygon(&multi), expected);
+	}
+
+	/// Test the convexities of the polygons in a batch, which are determined separately.
+	#[test_case(PolygonBatch::new(), vec![] ; "empty")]
+	#[test_case(PolygonBatch::from_iter([polygon::square_1000(), polygon::l_shape_1000(), polygon::bowtie_1000()]), vec![Convexity::CONVEX, Convexity::CONCAVE, Convexity::DEGENERATE] ; "mixed")]
+	#[test_case(PolygonBatch::from_iter([Polygon::new(), polygon::triangle_1000()]), vec![Convexity::DEGENERATE, Convexity::CONVEX] ; "empty_polygon")]
+	fn convexity_batch_polygons(batch: PolygonBatch, expected: Vec<Convexity>) {
+		assert_eq!(convexity_batch_st(&batch), expected);
+		assert_eq!(convexity_batch_mt(&batch), expected);
+		assert_eq!(convexity_batch(&batch), expected);
+	}
+}
\ No newline at end of file
@@ -17,4 +17,16 @@
 //! to read. The methods in the geometric objects will simply call these free functions to implement
 //! them.
 
+pub mod area;
+pub mod boolean;
+pub mod bounding_box;
+pub mod bounding_volume_hierarchy;
+pub mod containment;
+pub mod convex_hull;
+pub mod convexity;
+pub mod offset;
+pub mod self_intersection;
+pub mod simplify;
+pub mod transform;
+pub mod triangulate;
 pub mod translate;
\ No newline at end of file
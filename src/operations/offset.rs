@@ -0,0 +1,685 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! This module contains the implementations of operations to offset geometric objects, making them
+//! grow or shrink by a certain distance.
+//!
+//! Every edge is moved outwards by the offset distance, along its normal. Where the moved edges of
+//! two consecutive edges diverge, the gap between them is filled with a join: a sharp corner (miter),
+//! an arc (round) or a corner that is cut off (square). Where they converge, they are connected via
+//! the original vertex, which creates a small loop. These loops, and any other places where the
+//! moved contours intersect themselves or each other, are then resolved with a union. Contours that
+//! got turned inside out by shrinking them too much disappear in this union.
+//!
+//! The vertices of the joins are computed in floating point, and rounded to the nearest coordinate.
+//!
+//! Outer contours are expected to be counter-clockwise and holes to be clockwise, so that the holes
+//! shrink when the outer contours grow. If the total area of a shape is negative, all of its
+//! orientations are assumed to be reversed.
+
+use rayon::prelude::*; //For multi-threaded implementations.
+use std::f64::consts::PI; //To compute the angles of arcs.
+
+use crate::Coordinate; //The offset distance.
+use crate::FillRule; //To resolve the self-intersections of the offset contours.
+use crate::MultiPolygon; //The result of offsetting.
+use crate::Point2D; //To compute the vertices of the offset contours.
+use crate::Polygon; //Offset polygons.
+use crate::PolygonBatch; //Offset batches of polygons at once.
+use crate::cost_model::{self, Backend, Operation}; //To choose the fastest implementation.
+use crate::operations::area; //To find the orientation of the shapes.
+use crate::operations::boolean::{self, BooleanOperation}; //To resolve the self-intersections of the offset contours.
+use crate::predicates; //To find the exact direction of the turns.
+
+/// The smallest arc tolerance, as a fraction of the offset distance.
+///
+/// This limits the number of vertices that are generated for each round join.
+const MIN_ARC_TOLERANCE_FRACTION: f64 = 1e-4;
+
+/// The ways in which the edges around a convex vertex can be joined, after moving them apart.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JoinType {
+	/// Extend both edges until they meet in a sharp corner.
+	///
+	/// For very sharp corners, this point lies very far from the original vertex. If the distance
+	/// from the original vertex would become more than `limit` times the offset distance, the corner
+	/// is cut off like a square join instead. The limit is at least 1.
+	MITER {
+		/// The maximum distance of the corner from the original vertex, as a multiple of the offset
+		/// distance.
+		limit: f64,
+	},
+
+	/// Connect the edges with a circular arc around the original vertex.
+	ROUND {
+		/// The maximum distance between the arc and the straight edges that approximate it, in
+		/// coordinate units.
+		tolerance: f64,
+	},
+
+	/// Cut the corner off at the offset distance from the original vertex, perpendicular to the
+	/// bisector of the corner.
+	SQUARE,
+}
+
+/// Offset a polygon by a certain distance.
+///
+/// This chooses automatically whether to offset on a single thread or on multiple threads. The cost
+/// model estimates which is fastest, from the number of vertices in the polygon.
+///
+/// # Arguments
+/// * `polygon` - The polygon to offset.
+/// * `distance` - How far to move the edges outwards. Use a negative number to shrink the polygon.
+/// * `join_type` - How to connect edges around convex vertices.
+///
+/// # Result
+/// The contours of the offset polygon. Shrinking a polygon can split it into multiple parts, or
+/// make it disappear entirely.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon, Shape2D};
+/// use apex::operations::offset::JoinType;
+/// let square = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let grown = apex::operations::offset::offset_polygon(&square, 10, JoinType::MITER { limit: 2.0 });
+/// assert_eq!(grown.contour(0), &[Point2D { x: -10, y: -10 }, Point2D { x: 110, y: -10 }, Point2D { x: 110, y: 110 }, Point2D { x: -10, y: 110 }]);
+/// let shrunk = apex::operations::offset::offset_polygon(&square, -10, JoinType::MITER { limit: 2.0 });
+/// assert_eq!(shrunk.area(), 80 * 80);
+/// ```
+pub fn offset_polygon(polygon: &Polygon, distance: Coordinate, join_type: JoinType) -> MultiPolygon {
+	match cost_model::choose(Operation::OFFSET, polygon.len(), polygon.sync_status()) {
+		Backend::ST => offset_polygon_st(polygon, distance, join_type),
+		Backend::MT => offset_polygon_mt(polygon, distance, join_type),
+		Backend::GPU => unreachable!("There is no GPU implementation, so the cost model never chooses the GPU."),
+	}
+}
+
+/// Offset a polygon by a certain distance, on a single thread.
+///
+/// # Arguments
+/// * `polygon` - The polygon to offset.
+/// * `distance` - How far to move the edges outwards. Use a negative number to shrink the polygon.
+/// * `join_type` - How to connect edges around convex vertices.
+///
+/// # Result
+/// The contours of the offset polygon.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon, Shape2D};
+/// use apex::operations::offset::JoinType;
+/// let square = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let grown = apex::operations::offset::offset_polygon_st(&square, 10, JoinType::SQUARE);
+/// assert_eq!(grown.len(), 8, "Each corner is cut off.");
+/// ```
+pub fn offset_polygon_st(polygon: &Polygon, distance: Coordinate, join_type: JoinType) -> MultiPolygon {
+	offset_vertices_st(polygon.host_vertices(), distance, join_type)
+}
+
+/// Offset a polygon by a certain distance, on multiple threads.
+///
+/// The joins of the vertices are computed in parallel, as is the union that resolves the
+/// self-intersections afterwards.
+///
+/// # Arguments
+/// * `polygon` - The polygon to offset.
+/// * `distance` - How far to move the edges outwards. Use a negative number to shrink the polygon.
+/// * `join_type` - How to connect edges around convex vertices.
+///
+/// # Result
+/// The contours of the offset polygon.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon, Shape2D};
+/// use apex::operations::offset::JoinType;
+/// let square = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let grown = apex::operations::offset::offset_polygon_mt(&square, 10, JoinType::ROUND { tolerance: 0.25 });
+/// assert!((grown.area() - 14314).abs() < 20, "The corners are quarter circles.");
+/// ```
+pub fn offset_polygon_mt(polygon: &Polygon, distance: Coordinate, join_type: JoinType) -> MultiPolygon {
+	let vertices = polygon.host_vertices();
+	let clockwise = area::doubled_area_mt(vertices) < 0;
+	let mut raw = MultiPolygon::new();
+	raw.push_contour(offset_contour_mt(vertices, right_distance(distance, clockwise), join_type));
+	resolve(&raw, clockwise, Backend::MT)
+}
+
+/// Offset all contours of a multi-polygon by a certain distance.
+///
+/// Outer contours grow while holes shrink, and the other way around. This chooses automatically
+/// whether to offset on a single thread or on multiple threads, like `offset_polygon`.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to offset.
+/// * `distance` - How far to move the edges outwards. Use a negative number to shrink the shape.
+/// * `join_type` - How to connect edges around convex vertices.
+///
+/// # Result
+/// The contours of the offset shape. Growing a shape can merge contours, and close holes.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, Point2D, Shape2D};
+/// use apex::operations::offset::JoinType;
+/// let mut frame = MultiPolygon::new();
+/// frame.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// frame.push_contour([Point2D { x: 25, y: 25 }, Point2D { x: 25, y: 75 }, Point2D { x: 75, y: 75 }, Point2D { x: 75, y: 25 }]); //A hole.
+/// let grown = apex::operations::offset::offset_multipolygon(&frame, 10, JoinType::MITER { limit: 2.0 });
+/// assert_eq!(grown.area(), 120 * 120 - 30 * 30);
+/// let closed = apex::operations::offset::offset_multipolygon(&frame, 30, JoinType::MITER { limit: 2.0 });
+/// assert_eq!(closed.num_contours(), 1, "The hole closed.");
+/// ```
+pub fn offset_multipolygon(multi_polygon: &MultiPolygon, distance: Coordinate, join_type: JoinType) -> MultiPolygon {
+	match cost_model::choose(Operation::OFFSET, multi_polygon.len(), multi_polygon.sync_status()) {
+		Backend::ST => offset_multipolygon_st(multi_polygon, distance, join_type),
+		Backend::MT => offset_multipolygon_mt(multi_polygon, distance, join_type),
+		Backend::GPU => unreachable!("There is no GPU implementation, so the cost model never chooses the GPU."),
+	}
+}
+
+/// Offset all contours of a multi-polygon by a certain distance, on a single thread.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to offset.
+/// * `distance` - How far to move the edges outwards. Use a negative number to shrink the shape.
+/// * `join_type` - How to connect edges around convex vertices.
+///
+/// # Result
+/// The contours of the offset shape.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, Point2D, Shape2D};
+/// use apex::operations::offset::JoinType;
+/// let mut squares = MultiPolygon::new();
+/// squares.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// squares.push_contour([Point2D { x: 110, y: 0 }, Point2D { x: 210, y: 0 }, Point2D { x: 210, y: 100 }, Point2D { x: 110, y: 100 }]);
+/// let grown = apex::operations::offset::offset_multipolygon_st(&squares, 10, JoinType::MITER { limit: 2.0 });
+/// assert_eq!(grown.num_contours(), 1, "The squares grew together.");
+/// assert_eq!(grown.area(), 230 * 120);
+/// ```
+pub fn offset_multipolygon_st(multi_polygon: &MultiPolygon, distance: Coordinate, join_type: JoinType) -> MultiPolygon {
+	let clockwise = multi_polygon.contours().map(area::doubled_area_st).sum::<i128>() < 0;
+	let distance = right_distance(distance, clockwise);
+	let mut raw = MultiPolygon::new();
+	for contour in multi_polygon.contours() {
+		raw.push_contour(offset_contour_st(contour, distance, join_type));
+	}
+	resolve(&raw, clockwise, Backend::ST)
+}
+
+/// Offset all contours of a multi-polygon by a certain distance, on multiple threads.
+///
+/// The contours are divided over the threads, and the joins of the vertices of each contour are
+/// computed in parallel as well.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to offset.
+/// * `distance` - How far to move the edges outwards. Use a negative number to shrink the shape.
+/// * `join_type` - How to connect edges around convex vertices.
+///
+/// # Result
+/// The contours of the offset shape.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, Point2D, Shape2D};
+/// use apex::operations::offset::JoinType;
+/// let mut squares = MultiPolygon::new();
+/// squares.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// squares.push_contour([Point2D { x: 110, y: 0 }, Point2D { x: 210, y: 0 }, Point2D { x: 210, y: 100 }, Point2D { x: 110, y: 100 }]);
+/// let shrunk = apex::operations::offset::offset_multipolygon_mt(&squares, -10, JoinType::MITER { limit: 2.0 });
+/// assert_eq!(shrunk.num_contours(), 2);
+/// assert_eq!(shrunk.area(), 2 * 80 * 80);
+/// ```
+pub fn offset_multipolygon_mt(multi_polygon: &MultiPolygon, distance: Coordinate, join_type: JoinType) -> MultiPolygon {
+	let clockwise = area::doubled_area_contours_mt(multi_polygon.host_vertices(), multi_polygon.contour_starts()) < 0;
+	let distance = right_distance(distance, clockwise);
+	let contours: Vec<&[Point2D]> = multi_polygon.contours().collect();
+	let offset_contours: Vec<Vec<Point2D>> = contours.into_par_iter().map(|contour| offset_contour_mt(contour, distance, join_type)).collect();
+	let raw = MultiPolygon::from_iter(offset_contours.into_iter().map(Polygon::from_iter));
+	resolve(&raw, clockwise, Backend::MT)
+}
+
+/// Offset every polygon in a batch by a certain distance.
+///
+/// This chooses automatically whether to offset the polygons on a single thread or on multiple
+/// threads. The cost model considers the total number of vertices in the batch.
+///
+/// # Arguments
+/// * `batch` - The polygons to offset.
+/// * `distance` - How far to move the edges outwards. Use a negative number to shrink the polygons.
+/// * `join_type` - How to connect edges around convex vertices.
+///
+/// # Result
+/// The contours of each offset polygon, in the order that they were added to the batch.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, PolygonBatch, Shape2D};
+/// use apex::operations::offset::JoinType;
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 200, y: 0 }, Point2D { x: 300, y: 0 }, Point2D { x: 300, y: 100 }, Point2D { x: 200, y: 100 }]);
+/// let grown = apex::operations::offset::offset_batch(&batch, 10, JoinType::MITER { limit: 2.0 });
+/// assert_eq!(grown[0].area(), 120 * 120);
+/// assert_eq!(grown[1].area(), 120 * 120);
+/// ```
+pub fn offset_batch(batch: &PolygonBatch, distance: Coordinate, join_type: JoinType) -> Vec<MultiPolygon> {
+	match cost_model::choose(Operation::OFFSET, batch.len(), batch.sync_status()) {
+		Backend::ST => offset_batch_st(batch, distance, join_type),
+		Backend::MT => offset_batch_mt(batch, distance, join_type),
+		Backend::GPU => unreachable!("There is no GPU implementation, so the cost model never chooses the GPU."),
+	}
+}
+
+/// Offset every polygon in a batch by a certain distance, on a single thread.
+///
+/// # Arguments
+/// * `batch` - The polygons to offset.
+/// * `distance` - How far to move the edges outwards. Use a negative number to shrink the polygons.
+/// * `join_type` - How to connect edges around convex vertices.
+///
+/// # Result
+/// The contours of each offset polygon, in the order that they were added to the batch.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, PolygonBatch, Shape2D};
+/// use apex::operations::offset::JoinType;
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let grown = apex::operations::offset::offset_batch_st(&batch, 10, JoinType::SQUARE);
+/// assert_eq!(grown[0].len(), 8, "Each corner is cut off.");
+/// ```
+pub fn offset_batch_st(batch: &PolygonBatch, distance: Coordinate, join_type: JoinType) -> Vec<MultiPolygon> {
+	batch.polygons().map(|vertices| offset_vertices_st(vertices, distance, join_type)).collect()
+}
+
+/// Offset every polygon in a batch by a certain distance, on multiple threads.
+///
+/// The polygons are divided over the threads, and each polygon is offset on a single thread.
+///
+/// # Arguments
+/// * `batch` - The polygons to offset.
+/// * `distance` - How far to move the edges outwards. Use a negative number to shrink the polygons.
+/// * `join_type` - How to connect edges around convex vertices.
+///
+/// # Result
+/// The contours of each offset polygon, in the order that they were added to the batch.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, PolygonBatch, Shape2D};
+/// use apex::operations::offset::JoinType;
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let grown = apex::operations::offset::offset_batch_mt(&batch, 10, JoinType::SQUARE);
+/// assert_eq!(grown[0].len(), 8, "Each corner is cut off.");
+/// ```
+pub fn offset_batch_mt(batch: &PolygonBatch, distance: Coordinate, join_type: JoinType) -> Vec<MultiPolygon> {
+	let polygons: Vec<&[Point2D]> = batch.polygons().collect();
+	polygons.into_par_iter().map(|vertices| offset_vertices_st(vertices, distance, join_type)).collect()
+}
+
+/// Offset the contour of a polygon by a certain distance, on a single thread.
+///
+/// # Arguments
+/// * `vertices` - The vertices of the polygon.
+/// * `distance` - How far to move the edges outwards. Use a negative number to shrink the polygon.
+/// * `join_type` - How to connect edges around convex vertices.
+fn offset_vertices_st(vertices: &[Point2D], distance: Coordinate, join_type: JoinType) -> MultiPolygon {
+	let clockwise = area::doubled_area_st(vertices) < 0;
+	let mut raw = MultiPolygon::new();
+	raw.push_contour(offset_contour_st(vertices, right_distance(distance, clockwise), join_type));
+	resolve(&raw, clockwise, Backend::ST)
+}
+
+/// Get how far to move the edges of a shape to their right, to offset it by a certain distance.
+///
+/// For counter-clockwise shapes, the right of the edges is outwards. For clockwise shapes, it is
+/// inwards, so the distance is negated.
+///
+/// # Arguments
+/// * `distance` - The requested offset distance.
+/// * `clockwise` - Whether the shape is clockwise as a whole.
+fn right_distance(distance: Coordinate, clockwise: bool) -> f64 {
+	if clockwise { -(distance as f64) } else { distance as f64 }
+}
+
+/// Resolve the self-intersections of the offset contours, keeping the regions that are covered by
+/// the offset shape.
+///
+/// The regions inside the offset shape have the same winding number as the inside of the original
+/// shape. The loops around concave vertices, and contours that got turned inside out by shrinking,
+/// have a different winding number, so they disappear.
+///
+/// # Arguments
+/// * `raw` - The offset contours, which may intersect themselves and each other.
+/// * `clockwise` - Whether the original shape was clockwise as a whole.
+/// * `backend` - Whether to resolve the intersections on a single thread or on multiple threads.
+fn resolve(raw: &MultiPolygon, clockwise: bool, backend: Backend) -> MultiPolygon {
+	let fill_rule = if clockwise { FillRule::NEGATIVE } else { FillRule::POSITIVE };
+	let empty = MultiPolygon::new();
+	match backend {
+		Backend::ST => boolean::boolean_multipolygon_st(raw, &empty, BooleanOperation::UNION, fill_rule),
+		_ => boolean::boolean_multipolygon_mt(raw, &empty, BooleanOperation::UNION, fill_rule),
+	}
+}
+
+/// Move the edges of a closed contour to their right, and join them, on a single thread.
+///
+/// # Arguments
+/// * `vertices` - The vertices of the contour.
+/// * `distance` - How far to move the edges to their right.
+/// * `join_type` - How to connect edges around convex vertices.
+///
+/// # Result
+/// The vertices of the offset contour, which may intersect itself.
+fn offset_contour_st(vertices: &[Point2D], distance: f64, join_type: JoinType) -> Vec<Point2D> {
+	let vertices = distinct_vertices(vertices);
+	if vertices.len() < 3 {
+		return Vec::new(); //Doesn't enclose anything, so there is nothing to offset.
+	}
+	let mut result = Vec::with_capacity(vertices.len() * 2);
+	for i in 0..vertices.len() {
+		join(&vertices, i, distance, join_type, &mut result);
+	}
+	result
+}
+
+/// Move the edges of a closed contour to their right, and join them, on multiple threads.
+///
+/// The vertices are divided over the threads, which compute the joins around them.
+///
+/// # Arguments
+/// * `vertices` - The vertices of the contour.
+/// * `distance` - How far to move the edges to their right.
+/// * `join_type` - How to connect edges around convex vertices.
+///
+/// # Result
+/// The vertices of the offset contour, which may intersect itself.
+fn offset_contour_mt(vertices: &[Point2D], distance: f64, join_type: JoinType) -> Vec<Point2D> {
+	let vertices = distinct_vertices(vertices);
+	if vertices.len() < 3 {
+		return Vec::new(); //Doesn't enclose anything, so there is nothing to offset.
+	}
+	let chunk_size = cost_model::chunk_size(Operation::OFFSET, vertices.len());
+	(0..vertices.len()).into_par_iter().with_min_len(chunk_size).flat_map_iter(|i| {
+		let mut points = Vec::new();
+		join(&vertices, i, distance, join_type, &mut points);
+		points
+	}).collect()
+}
+
+/// Compute the vertices of the offset contour around one vertex of the original contour.
+///
+/// These are the end of the moved edge before the vertex, the join, and the start of the moved edge
+/// after the vertex.
+///
+/// # Arguments
+/// * `vertices` - The vertices of the contour, without consecutive duplicates.
+/// * `index` - The index of the vertex to compute the join of.
+/// * `distance` - How far to move the edges to their right.
+/// * `join_type` - How to connect edges around convex vertices.
+/// * `result` - The list of vertices to add the vertices of the join to.
+fn join(vertices: &[Point2D], index: usize, distance: f64, join_type: JoinType, result: &mut Vec<Point2D>) {
+	let previous = &vertices[(index + vertices.len() - 1) % vertices.len()];
+	let vertex = &vertices[index];
+	let next = &vertices[(index + 1) % vertices.len()];
+	let incoming = unit_direction(previous, vertex);
+	let outgoing = unit_direction(vertex, next);
+	let incoming_normal = (incoming.1, -incoming.0); //To the right.
+	let outgoing_normal = (outgoing.1, -outgoing.0);
+	let sine = incoming.0 * outgoing.1 - incoming.1 * outgoing.0; //Positive for a turn to the left.
+	let cosine = incoming.0 * outgoing.0 + incoming.1 * outgoing.1;
+	//The exact turn direction, to decide robustly whether the moved edges diverge.
+	let turn = predicates::orientation(previous, vertex, next);
+	let at = |direction: (f64, f64), length: f64| point(vertex, (direction.0 * length, direction.1 * length));
+
+	if turn == 0 && cosine > 0.0 { //Straight on. The moved edges connect seamlessly.
+		result.push(at(incoming_normal, distance));
+		return;
+	}
+	let diverging = turn == 0 || (turn > 0) == (distance > 0.0); //Spikes always diverge.
+	if !diverging {
+		//Connect via the original vertex. The loop this creates is removed when resolving the self-intersections.
+		result.push(at(incoming_normal, distance));
+		result.push(*vertex);
+		result.push(at(outgoing_normal, distance));
+		return;
+	}
+
+	let join_type = match join_type {
+		JoinType::MITER { limit } if 1.0 + cosine < 2.0 / (limit.max(1.0) * limit.max(1.0)) => JoinType::SQUARE, //Too sharp.
+		join_type => join_type,
+	};
+	match join_type {
+		JoinType::MITER { .. } => {
+			let bisector = (incoming_normal.0 + outgoing_normal.0, incoming_normal.1 + outgoing_normal.1);
+			result.push(at(bisector, distance / (1.0 + cosine)));
+		}
+		JoinType::SQUARE => {
+			//Cut the corner off perpendicular to the bisector. For spikes, the bisector continues straight on.
+			let bisector = (incoming_normal.0 + outgoing_normal.0, incoming_normal.1 + outgoing_normal.1);
+			let bisector_length = (bisector.0 * bisector.0 + bisector.1 * bisector.1).sqrt();
+			let bisector = if bisector_length > 1e-9 {
+				(bisector.0 / bisector_length, bisector.1 / bisector_length)
+			} else {
+				(incoming.0 * distance.signum(), incoming.1 * distance.signum())
+			};
+			let dot = |a: (f64, f64), b: (f64, f64)| a.0 * b.0 + a.1 * b.1;
+			let along_incoming = distance * (1.0 - dot(incoming_normal, bisector)) / dot(incoming, bisector);
+			let along_outgoing = distance * (1.0 - dot(outgoing_normal, bisector)) / dot(outgoing, bisector);
+			result.push(point(vertex, (incoming_normal.0 * distance + incoming.0 * along_incoming, incoming_normal.1 * distance + incoming.1 * along_incoming)));
+			result.push(point(vertex, (outgoing_normal.0 * distance + outgoing.0 * along_outgoing, outgoing_normal.1 * distance + outgoing.1 * along_outgoing)));
+		}
+		JoinType::ROUND { tolerance } => {
+			let radius = distance.abs();
+			let tolerance = tolerance.clamp(radius * MIN_ARC_TOLERANCE_FRACTION, radius);
+			let angle = if turn == 0 { PI * distance.signum() } else { sine.atan2(cosine) };
+			let max_step = 2.0 * (1.0 - tolerance / radius).acos();
+			let steps = (angle.abs() / max_step).ceil().max(1.0) as usize;
+			for step in 0..=steps {
+				let (sin, cos) = (angle * step as f64 / steps as f64).sin_cos();
+				let normal = (incoming_normal.0 * cos - incoming_normal.1 * sin, incoming_normal.0 * sin + incoming_normal.1 * cos);
+				result.push(at(normal, distance));
+			}
+		}
+	}
+}
+
+/// Get the direction from one point to another, with a length of 1.
+fn unit_direction(from: &Point2D, to: &Point2D) -> (f64, f64) {
+	let dx = to.x as f64 - from.x as f64;
+	let dy = to.y as f64 - from.y as f64;
+	let length = (dx * dx + dy * dy).sqrt();
+	(dx / length, dy / length)
+}
+
+/// Move a point by a vector, rounding to the nearest coordinate.
+///
+/// The result is clamped to the range of coordinates.
+fn point(origin: &Point2D, offset: (f64, f64)) -> Point2D {
+	let round = |coordinate: Coordinate, delta: f64| (coordinate as f64 + delta).round().clamp(Coordinate::MIN as f64, Coordinate::MAX as f64) as Coordinate;
+	Point2D { x: round(origin.x, offset.0), y: round(origin.y, offset.1) }
+}
+
+/// Copy the vertices of a contour, leaving out consecutive duplicates.
+///
+/// The contour is closed, so the last vertex is also compared to the first.
+fn distinct_vertices(vertices: &[Point2D]) -> Vec<Point2D> {
+	let mut result: Vec<Point2D> = Vec::with_capacity(vertices.len());
+	for vertex in vertices {
+		if result.last() != Some(vertex) {
+			result.push(*vertex);
+		}
+	}
+	while result.len() > 1 && result.last() == result.first() {
+		result.pop();
+	}
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+	use crate::Shape2D;
+
+	/// The default miter join that is used in these tests.
+	const MITER: JoinType = JoinType::MITER { limit: 2.0 };
+
+	/// Offset a polygon with every implementation, and check that they agree.
+	fn all_backends(polygon: &Polygon, distance: Coordinate, join_type: JoinType) -> MultiPolygon {
+		let st = offset_polygon_st(polygon, distance, join_type);
+		let mt = offset_polygon_mt(polygon, distance, join_type);
+		assert_eq!(st.host_vertices(), mt.host_vertices(), "The single-threaded and multi-threaded implementations must give the same result.");
+		assert_eq!(offset_polygon(polygon, distance, join_type).host_vertices(), st.host_vertices(), "The automatic choice must give the same result.");
+		st
+	}
+
+	/// Test growing and shrinking a square with miter joins, which keeps it a square.
+	#[test_case(100, 1200 * 1200 ; "grow")]
+	#[test_case(0, 1000 * 1000 ; "zero")]
+	#[test_case(-100, 800 * 800 ; "shrink")]
+	#[test_case(-499, 2 * 2 ; "almost gone")]
+	#[test_case(-500, 0 ; "gone")]
+	#[test_case(-600, 0 ; "inverted")]
+	fn square_miter(distance: Coordinate, area: crate::Area) {
+		let result = all_backends(&crate::test::data::polygon::square_1000(), distance, MITER);
+		assert_eq!(result.area(), area);
+		assert!(result.num_contours() <= 1);
+		assert!(result.contours().all(|contour| contour.len() == 4), "The corners stay sharp.");
+	}
+
+	/// Test growing a square with square joins, which cuts off the corners.
+	#[test]
+	fn square_square() {
+		let result = all_backends(&crate::test::data::polygon::square_1000(), 100, JoinType::SQUARE);
+		assert_eq!(result.len(), 8, "Each corner is cut off.");
+		let cut_off = (100.0 * 2f64.sqrt() - 100.0) * (100.0 * 2f64.sqrt() - 100.0); //At each corner, a right triangle with this height.
+		let expected = 1200.0 * 1200.0 - 4.0 * cut_off;
+		assert!((result.area() as f64 - expected).abs() < 400.0, "The area must be close to {expected}, but was {}.", result.area());
+	}
+
+	/// Test growing a square with round joins, which makes the corners quarter circles.
+	#[test_case(0.25 ; "fine")]
+	#[test_case(5.0 ; "coarse")]
+	fn square_round(tolerance: f64) {
+		let result = all_backends(&crate::test::data::polygon::square_1000(), 100, JoinType::ROUND { tolerance });
+		let expected = 1000.0 * 1000.0 + 4.0 * 1000.0 * 100.0 + PI * 100.0 * 100.0;
+		assert!((result.area() as f64 - expected).abs() < 4.0 * 100.0 * tolerance + 400.0, "The area must be close to {expected}, but was {}.", result.area());
+		for vertex in result.host_vertices() {
+			let dx = (vertex.x.clamp(0, 1000) - vertex.x) as f64;
+			let dy = (vertex.y.clamp(0, 1000) - vertex.y) as f64;
+			let distance = (dx * dx + dy * dy).sqrt();
+			assert!((distance - 100.0).abs() <= 1.0, "All vertices are on the offset distance from the square, up to rounding.");
+		}
+	}
+
+	/// Test that a finer arc tolerance gives more vertices.
+	#[test]
+	fn round_tolerance() {
+		let square = crate::test::data::polygon::square_1000();
+		let fine = all_backends(&square, 100, JoinType::ROUND { tolerance: 0.1 });
+		let coarse = all_backends(&square, 100, JoinType::ROUND { tolerance: 10.0 });
+		assert!(fine.len() > coarse.len());
+	}
+
+	/// Test that the miter limit cuts off sharp corners.
+	#[test]
+	fn miter_limit() {
+		let sharp = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 1000, y: 0 }, Point2D { x: 0, y: 100 }]);
+		let limited = all_backends(&sharp, 10, JoinType::MITER { limit: 2.0 });
+		let unlimited = all_backends(&sharp, 10, JoinType::MITER { limit: 1000.0 });
+		assert_eq!(unlimited.len(), 3, "All corners are sharp.");
+		assert_eq!(limited.len(), 4, "The sharp corner is cut off.");
+		assert!(limited.area() < unlimited.area());
+	}
+
+	/// Test growing a concave polygon, where the moved edges overlap at the concave vertex.
+	#[test]
+	fn concave_grow() {
+		let result = all_backends(&crate::test::data::polygon::l_shape_1000(), 100, MITER);
+		assert_eq!(result.num_contours(), 1);
+		assert_eq!(result.len(), 6, "The loop at the concave vertex must be removed.");
+		assert_eq!(result.area(), 1200 * 1200 - 500 * 500);
+	}
+
+	/// Test shrinking a shape until it falls apart in two parts.
+	#[test]
+	fn shrink_splits() {
+		let dumbbell = Polygon::from_iter([
+			Point2D { x: 0, y: 0 }, Point2D { x: 400, y: 0 }, Point2D { x: 400, y: 150 }, Point2D { x: 600, y: 150 },
+			Point2D { x: 600, y: 0 }, Point2D { x: 1000, y: 0 }, Point2D { x: 1000, y: 400 }, Point2D { x: 600, y: 400 },
+			Point2D { x: 600, y: 250 }, Point2D { x: 400, y: 250 }, Point2D { x: 400, y: 400 }, Point2D { x: 0, y: 400 },
+		]);
+		assert_eq!(all_backends(&dumbbell, -40, MITER).num_contours(), 1, "The bar between the parts is still 20 wide.");
+		let result = all_backends(&dumbbell, -60, MITER);
+		assert_eq!(result.num_contours(), 2, "The bar between the parts disappeared.");
+		assert_eq!(result.area(), 2 * 280 * 280);
+	}
+
+	/// Test that clockwise polygons also grow with a positive distance.
+	#[test]
+	fn clockwise() {
+		let square = crate::test::data::polygon::square_1000();
+		let reversed = Polygon::from_iter(square.host_vertices().iter().rev().copied());
+		let result = all_backends(&reversed, 100, MITER);
+		assert_eq!(result.area(), 1200 * 1200, "The result is counter-clockwise.");
+	}
+
+	/// Test offsetting polygons that have too few vertices to enclose anything.
+	#[test]
+	fn degenerate() {
+		let line = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 0 }]);
+		assert_eq!(all_backends(&line, 10, MITER).num_contours(), 0);
+		assert_eq!(all_backends(&Polygon::new(), 10, MITER).num_contours(), 0);
+	}
+
+	/// Test offsetting a multi-polygon with a hole, which shrinks while the outside grows.
+	#[test_case(100, 2, 1200 * 1200 - 300 * 300 ; "grow")]
+	#[test_case(250, 1, 1500 * 1500 ; "hole closes")]
+	#[test_case(-100, 2, 800 * 800 - 700 * 700 ; "shrink")]
+	#[test_case(-200, 0, 0 ; "frame disappears")]
+	fn multipolygon_hole(distance: Coordinate, num_contours: usize, area: crate::Area) {
+		let frame = crate::test::data::multi_polygon::square_with_hole_1000();
+		let st = offset_multipolygon_st(&frame, distance, MITER);
+		let mt = offset_multipolygon_mt(&frame, distance, MITER);
+		assert_eq!(st.host_vertices(), mt.host_vertices(), "The single-threaded and multi-threaded implementations must give the same result.");
+		assert_eq!(offset_multipolygon(&frame, distance, MITER).host_vertices(), st.host_vertices(), "The automatic choice must give the same result.");
+		assert_eq!(st.num_contours(), num_contours);
+		assert_eq!(st.area(), area);
+	}
+
+	/// Test offsetting a self-intersecting polygon, which is resolved with its orientation.
+	#[test]
+	fn self_intersecting() {
+		let bowtie = crate::test::data::polygon::bowtie_1000();
+		let result = all_backends(&bowtie, 0, MITER);
+		assert_eq!(result.area(), 250000, "The halves have opposite areas, so the shape counts as counter-clockwise, and only the counter-clockwise half is kept.");
+	}
+
+	/// Test offsetting the polygons of a batch, which are offset separately.
+	#[test_case(10 ; "grow")]
+	#[test_case(-100 ; "shrink")]
+	fn batch_polygons(distance: Coordinate) {
+		let polygons = [crate::test::data::polygon::square_1000(), Polygon::new(), crate::test::data::polygon::l_shape_1000(), crate::test::data::polygon::bowtie_1000()];
+		let batch = PolygonBatch::from_iter(polygons.iter().map(|polygon| Polygon::from_iter(polygon.host_vertices().iter().copied())));
+		let expected: Vec<MultiPolygon> = polygons.iter().map(|polygon| offset_polygon_st(polygon, distance, MITER)).collect();
+		for results in [offset_batch_st(&batch, distance, MITER), offset_batch_mt(&batch, distance, MITER), offset_batch(&batch, distance, MITER)] {
+			assert_eq!(results.len(), expected.len());
+			for (result, expected) in results.iter().zip(&expected) {
+				assert_eq!(result.host_vertices(), expected.host_vertices());
+				assert_eq!(result.contour_starts(), expected.contour_starts());
+			}
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,964 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! This module contains the implementations of operations to find and resolve the places where the
+//! boundary of a shape intersects itself.
+//!
+//! The crossings are found with a Bentley-Ottmann sweep. A vertical sweep line moves from left to
+//! right, stopping at every vertex and at every crossing that it has found so far. It keeps track of
+//! the edges that it currently crosses, sorted from bottom to top. Two edges can only meet after
+//! they have become neighbours in that order, so only neighbouring edges are tested for crossings.
+//! The crossings that are found that way are added as new stops for the sweep line, where the edges
+//! swap places. The number of tests grows with the number of edges plus the number of crossings,
+//! rather than with the square of the number of edges.
+//!
+//! The positions of the crossings are fractions. They are computed and compared exactly, so that
+//! the sweep line never loses track of the order of the edges. Only in the result are they rounded
+//! to the grid.
+//!
+//! The multi-threaded variant divides the plane into vertical slabs, each with about the same
+//! number of edges, and runs a separate sweep line through each slab. Edges that start left of a
+//! slab enter its sweep line at the left side of the slab.
+//!
+//! To resolve the crossings, the shape is taken apart into the regions that are filled according to
+//! a fill rule, as with a boolean operation.
+
+use std::cmp; //To find the lowest index of two edges.
+use std::cmp::Ordering; //To order the stops and the edges along the sweep line.
+use std::collections::BTreeMap; //The stops of the sweep line, in order.
+use rayon::prelude::*; //For multi-threaded implementations.
+
+use crate::Coordinate; //To bound the slabs.
+use crate::Crossing; //The result of searching for crossings.
+use crate::FillRule; //To choose which regions are kept when resolving crossings.
+use crate::MultiPolygon; //To find crossings in multi-polygons, and the result of resolving crossings.
+use crate::Point2D; //The endpoints of the edges.
+use crate::Polygon; //To find crossings in polygons.
+use crate::PolygonBatch; //To find and resolve crossings in batches of polygons at once.
+use crate::cost_model::{self, Backend, Operation}; //To choose the fastest implementation.
+use crate::operations::boolean::{self, BooleanOperation}; //To resolve crossings.
+use crate::predicates::{self, RationalPoint, compare_products, direction}; //To find and order the crossings exactly.
+
+/// Find all places where the boundary of a polygon meets itself.
+///
+/// This chooses automatically whether to search on a single thread or on multiple threads. The
+/// cost model estimates which is fastest, from the number of vertices in the polygon.
+///
+/// Consecutive edges always meet at the vertex between them, which is not reported as a crossing.
+/// Duplicate vertices are skipped, so edges without length never cross anything.
+///
+/// # Arguments
+/// * `polygon` - The polygon to search for crossings.
+///
+/// # Result
+/// The crossings, sorted by their edges. Each pair of edges is reported at most once.
+///
+/// # Examples
+/// ```
+/// use apex::{Crossing, Point2D, Polygon};
+/// let bowtie = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// let crossings = apex::operations::self_intersection::crossings_polygon(&bowtie);
+/// assert_eq!(crossings, vec![Crossing { first_edge: 0, second_edge: 2, point: Point2D { x: 50, y: 50 } }]);
+/// ```
+pub fn crossings_polygon(polygon: &Polygon) -> Vec<Crossing> {
+	match cost_model::choose(Operation::SELF_INTERSECTION, polygon.len(), polygon.sync_status()) {
+		Backend::ST => crossings_polygon_st(polygon),
+		Backend::MT => crossings_polygon_mt(polygon),
+		Backend::GPU => unreachable!("There is no GPU implementation, so the cost model never chooses the GPU."),
+	}
+}
+
+/// Find all places where the boundary of a polygon meets itself, on a single thread.
+///
+/// # Arguments
+/// * `polygon` - The polygon to search for crossings.
+///
+/// # Result
+/// The crossings, sorted by their edges. Each pair of edges is reported at most once.
+///
+/// # Examples
+/// ```
+/// use apex::{Crossing, Point2D, Polygon};
+/// let bowtie = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// let crossings = apex::operations::self_intersection::crossings_polygon_st(&bowtie);
+/// assert_eq!(crossings, vec![Crossing { first_edge: 0, second_edge: 2, point: Point2D { x: 50, y: 50 } }]);
+/// ```
+pub fn crossings_polygon_st(polygon: &Polygon) -> Vec<Crossing> {
+	crossings(&contour_segments(polygon.host_vertices(), 0), 1)
+}
+
+/// Find all places where the boundary of a polygon meets itself, on multiple threads.
+///
+/// The plane is divided into vertical slabs, which are searched on separate threads.
+///
+/// # Arguments
+/// * `polygon` - The polygon to search for crossings.
+///
+/// # Result
+/// The crossings, sorted by their edges. Each pair of edges is reported at most once.
+///
+/// # Examples
+/// ```
+/// use apex::{Crossing, Point2D, Polygon};
+/// let bowtie = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// let crossings = apex::operations::self_intersection::crossings_polygon_mt(&bowtie);
+/// assert_eq!(crossings, vec![Crossing { first_edge: 0, second_edge: 2, point: Point2D { x: 50, y: 50 } }]);
+/// ```
+pub fn crossings_polygon_mt(polygon: &Polygon) -> Vec<Crossing> {
+	let segments = contour_segments(polygon.host_vertices(), 0);
+	crossings(&segments, num_slabs(segments.len()))
+}
+
+/// Find all places where the contours of a multi-polygon meet themselves or each other.
+///
+/// This chooses automatically whether to search on a single thread or on multiple threads. The
+/// cost model estimates which is fastest, from the number of vertices in the multi-polygon.
+///
+/// Consecutive edges of the same contour always meet at the vertex between them, which is not
+/// reported as a crossing. Duplicate vertices are skipped, so edges without length never cross
+/// anything.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to search for crossings.
+///
+/// # Result
+/// The crossings, sorted by their edges. Each pair of edges is reported at most once.
+///
+/// # Examples
+/// ```
+/// use apex::{Crossing, MultiPolygon, Point2D};
+/// let mut squares = MultiPolygon::new();
+/// squares.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// squares.push_contour([Point2D { x: 50, y: 50 }, Point2D { x: 150, y: 50 }, Point2D { x: 150, y: 150 }, Point2D { x: 50, y: 150 }]);
+/// let crossings = apex::operations::self_intersection::crossings_multipolygon(&squares);
+/// assert_eq!(crossings, vec![
+/// 	Crossing { first_edge: 1, second_edge: 4, point: Point2D { x: 100, y: 50 } },
+/// 	Crossing { first_edge: 2, second_edge: 7, point: Point2D { x: 50, y: 100 } },
+/// ]);
+/// ```
+pub fn crossings_multipolygon(multi_polygon: &MultiPolygon) -> Vec<Crossing> {
+	match cost_model::choose(Operation::SELF_INTERSECTION, multi_polygon.len(), multi_polygon.sync_status()) {
+		Backend::ST => crossings_multipolygon_st(multi_polygon),
+		Backend::MT => crossings_multipolygon_mt(multi_polygon),
+		Backend::GPU => unreachable!("There is no GPU implementation, so the cost model never chooses the GPU."),
+	}
+}
+
+/// Find all places where the contours of a multi-polygon meet themselves or each other, on a single
+/// thread.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to search for crossings.
+///
+/// # Result
+/// The crossings, sorted by their edges. Each pair of edges is reported at most once.
+///
+/// # Examples
+/// ```
+/// use apex::{Crossing, MultiPolygon, Point2D};
+/// let mut squares = MultiPolygon::new();
+/// squares.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// squares.push_contour([Point2D { x: 50, y: 50 }, Point2D { x: 150, y: 50 }, Point2D { x: 150, y: 150 }, Point2D { x: 50, y: 150 }]);
+/// let crossings = apex::operations::self_intersection::crossings_multipolygon_st(&squares);
+/// assert_eq!(crossings.len(), 2);
+/// ```
+pub fn crossings_multipolygon_st(multi_polygon: &MultiPolygon) -> Vec<Crossing> {
+	crossings(&multipolygon_segments(multi_polygon), 1)
+}
+
+/// Find all places where the contours of a multi-polygon meet themselves or each other, on multiple
+/// threads.
+///
+/// The plane is divided into vertical slabs, which are searched on separate threads.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to search for crossings.
+///
+/// # Result
+/// The crossings, sorted by their edges. Each pair of edges is reported at most once.
+///
+/// # Examples
+/// ```
+/// use apex::{Crossing, MultiPolygon, Point2D};
+/// let mut squares = MultiPolygon::new();
+/// squares.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// squares.push_contour([Point2D { x: 50, y: 50 }, Point2D { x: 150, y: 50 }, Point2D { x: 150, y: 150 }, Point2D { x: 50, y: 150 }]);
+/// let crossings = apex::operations::self_intersection::crossings_multipolygon_mt(&squares);
+/// assert_eq!(crossings.len(), 2);
+/// ```
+pub fn crossings_multipolygon_mt(multi_polygon: &MultiPolygon) -> Vec<Crossing> {
+	let segments = multipolygon_segments(multi_polygon);
+	crossings(&segments, num_slabs(segments.len()))
+}
+
+/// Divide a polygon into simple contours, resolving the places where it intersects itself.
+///
+/// The regions that are filled according to the fill rule are kept. Their boundaries become the
+/// contours of the result: counter-clockwise outer contours and clockwise holes, which don't
+/// intersect themselves or each other. Intersection points are rounded to the grid.
+///
+/// This chooses automatically whether to perform the operation on a single thread, on multiple
+/// threads or with help of the GPU, like a boolean operation.
+///
+/// # Arguments
+/// * `polygon` - The polygon to resolve.
+/// * `fill_rule` - Which regions of the polygon are considered to be inside the polygon.
+///
+/// # Result
+/// The filled regions of the polygon, as simple contours.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, Point2D, Polygon, Shape2D};
+/// let bowtie = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// let simple = apex::operations::self_intersection::make_simple_polygon(&bowtie, FillRule::NON_ZERO);
+/// assert_eq!(simple.num_contours(), 2, "The two lobes of the bowtie are separated.");
+/// assert_eq!(simple.area(), 5000, "Both lobes are counter-clockwise now.");
+/// ```
+pub fn make_simple_polygon(polygon: &Polygon, fill_rule: FillRule) -> MultiPolygon {
+	boolean::boolean_polygon(polygon, &Polygon::new(), BooleanOperation::UNION, fill_rule)
+}
+
+/// Divide a polygon into simple contours, resolving the places where it intersects itself, on a
+/// single thread.
+///
+/// # Arguments
+/// * `polygon` - The polygon to resolve.
+/// * `fill_rule` - Which regions of the polygon are considered to be inside the polygon.
+///
+/// # Result
+/// The filled regions of the polygon, as simple contours.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, Point2D, Polygon, Shape2D};
+/// let bowtie = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// let simple = apex::operations::self_intersection::make_simple_polygon_st(&bowtie, FillRule::NON_ZERO);
+/// assert_eq!(simple.num_contours(), 2, "The two lobes of the bowtie are separated.");
+/// ```
+pub fn make_simple_polygon_st(polygon: &Polygon, fill_rule: FillRule) -> MultiPolygon {
+	boolean::boolean_polygon_st(polygon, &Polygon::new(), BooleanOperation::UNION, fill_rule)
+}
+
+/// Divide a polygon into simple contours, resolving the places where it intersects itself, on
+/// multiple threads.
+///
+/// # Arguments
+/// * `polygon` - The polygon to resolve.
+/// * `fill_rule` - Which regions of the polygon are considered to be inside the polygon.
+///
+/// # Result
+/// The filled regions of the polygon, as simple contours.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, Point2D, Polygon, Shape2D};
+/// let bowtie = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// let simple = apex::operations::self_intersection::make_simple_polygon_mt(&bowtie, FillRule::NON_ZERO);
+/// assert_eq!(simple.num_contours(), 2, "The two lobes of the bowtie are separated.");
+/// ```
+pub fn make_simple_polygon_mt(polygon: &Polygon, fill_rule: FillRule) -> MultiPolygon {
+	boolean::boolean_polygon_mt(polygon, &Polygon::new(), BooleanOperation::UNION, fill_rule)
+}
+
+/// Divide a multi-polygon into simple contours, resolving the places where its contours intersect
+/// themselves or each other.
+///
+/// All contours together form one shape. The regions of that shape that are filled according to the
+/// fill rule are kept, like in `make_simple_polygon`.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to resolve.
+/// * `fill_rule` - Which regions of the multi-polygon are considered to be inside of it.
+///
+/// # Result
+/// The filled regions of the multi-polygon, as simple contours.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, MultiPolygon, Point2D, Shape2D};
+/// let mut squares = MultiPolygon::new();
+/// squares.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// squares.push_contour([Point2D { x: 50, y: 50 }, Point2D { x: 150, y: 50 }, Point2D { x: 150, y: 150 }, Point2D { x: 50, y: 150 }]);
+/// let simple = apex::operations::self_intersection::make_simple_multipolygon(&squares, FillRule::EVEN_ODD);
+/// assert_eq!(simple.num_contours(), 2, "The overlap is left out, so only the corners remain.");
+/// assert_eq!(simple.area(), 15000);
+/// ```
+pub fn make_simple_multipolygon(multi_polygon: &MultiPolygon, fill_rule: FillRule) -> MultiPolygon {
+	boolean::boolean_multipolygon(multi_polygon, &MultiPolygon::new(), BooleanOperation::UNION, fill_rule)
+}
+
+/// Divide a multi-polygon into simple contours, resolving the places where its contours intersect
+/// themselves or each other, on a single thread.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to resolve.
+/// * `fill_rule` - Which regions of the multi-polygon are considered to be inside of it.
+///
+/// # Result
+/// The filled regions of the multi-polygon, as simple contours.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, MultiPolygon, Point2D, Shape2D};
+/// let mut squares = MultiPolygon::new();
+/// squares.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// squares.push_contour([Point2D { x: 50, y: 50 }, Point2D { x: 150, y: 50 }, Point2D { x: 150, y: 150 }, Point2D { x: 50, y: 150 }]);
+/// let simple = apex::operations::self_intersection::make_simple_multipolygon_st(&squares, FillRule::NON_ZERO);
+/// assert_eq!(simple.num_contours(), 1, "The squares are merged.");
+/// assert_eq!(simple.area(), 17500);
+/// ```
+pub fn make_simple_multipolygon_st(multi_polygon: &MultiPolygon, fill_rule: FillRule) -> MultiPolygon {
+	boolean::boolean_multipolygon_st(multi_polygon, &MultiPolygon::new(), BooleanOperation::UNION, fill_rule)
+}
+
+/// Divide a multi-polygon into simple contours, resolving the places where its contours intersect
+/// themselves or each other, on multiple threads.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to resolve.
+/// * `fill_rule` - Which regions of the multi-polygon are considered to be inside of it.
+///
+/// # Result
+/// The filled regions of the multi-polygon, as simple contours.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, MultiPolygon, Point2D, Shape2D};
+/// let mut squares = MultiPolygon::new();
+/// squares.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// squares.push_contour([Point2D { x: 50, y: 50 }, Point2D { x: 150, y: 50 }, Point2D { x: 150, y: 150 }, Point2D { x: 50, y: 150 }]);
+/// let simple = apex::operations::self_intersection::make_simple_multipolygon_mt(&squares, FillRule::NON_ZERO);
+/// assert_eq!(simple.num_contours(), 1, "The squares are merged.");
+/// assert_eq!(simple.area(), 17500);
+/// ```
+pub fn make_simple_multipolygon_mt(multi_polygon: &MultiPolygon, fill_rule: FillRule) -> MultiPolygon {
+	boolean::boolean_multipolygon_mt(multi_polygon, &MultiPolygon::new(), BooleanOperation::UNION, fill_rule)
+}
+
+/// Find all places where the boundaries of the polygons in a batch meet themselves.
+///
+/// Each polygon is searched separately, so places where two different polygons meet each other are
+/// not reported. This chooses automatically whether to search on a single thread or on multiple
+/// threads. The cost model estimates which is fastest, from the total number of vertices in the
+/// batch.
+///
+/// # Arguments
+/// * `batch` - The polygons to search for crossings.
+///
+/// # Result
+/// For each polygon, its crossings, sorted by their edges, like `crossings_polygon`.
+///
+/// # Examples
+/// ```
+/// use apex::{Crossing, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let crossings = apex::operations::self_intersection::crossings_batch(&batch);
+/// assert_eq!(crossings[0], vec![Crossing { first_edge: 0, second_edge: 2, point: Point2D { x: 50, y: 50 } }]);
+/// assert!(crossings[1].is_empty(), "A square doesn't intersect itself.");
+/// ```
+pub fn crossings_batch(batch: &PolygonBatch) -> Vec<Vec<Crossing>> {
+	match cost_model::choose(Operation::SELF_INTERSECTION, batch.len(), batch.sync_status()) {
+		Backend::ST => crossings_batch_st(batch),
+		Backend::MT => crossings_batch_mt(batch),
+		Backend::GPU => unreachable!("There is no GPU implementation, so the cost model never chooses the GPU."),
+	}
+}
+
+/// Find all places where the boundaries of the polygons in a batch meet themselves, on a single
+/// thread.
+///
+/// # Arguments
+/// * `batch` - The polygons to search for crossings.
+///
+/// # Result
+/// For each polygon, its crossings, sorted by their edges, like `crossings_polygon`.
+///
+/// # Examples
+/// ```
+/// use apex::{Crossing, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// let crossings = apex::operations::self_intersection::crossings_batch_st(&batch);
+/// assert_eq!(crossings[0], vec![Crossing { first_edge: 0, second_edge: 2, point: Point2D { x: 50, y: 50 } }]);
+/// ```
+pub fn crossings_batch_st(batch: &PolygonBatch) -> Vec<Vec<Crossing>> {
+	batch.polygons().map(|vertices| crossings(&contour_segments(vertices, 0), 1)).collect()
+}
+
+/// Find all places where the boundaries of the polygons in a batch meet themselves, on multiple
+/// threads.
+///
+/// The polygons are divided over the threads, and each polygon is searched on a single thread.
+///
+/// # Arguments
+/// * `batch` - The polygons to search for crossings.
+///
+/// # Result
+/// For each polygon, its crossings, sorted by their edges, like `crossings_polygon`.
+///
+/// # Examples
+/// ```
+/// use apex::{Crossing, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// let crossings = apex::operations::self_intersection::crossings_batch_mt(&batch);
+/// assert_eq!(crossings[0], vec![Crossing { first_edge: 0, second_edge: 2, point: Point2D { x: 50, y: 50 } }]);
+/// ```
+pub fn crossings_batch_mt(batch: &PolygonBatch) -> Vec<Vec<Crossing>> {
+	let polygons: Vec<&[Point2D]> = batch.polygons().collect();
+	polygons.into_par_iter().map(|vertices| crossings(&contour_segments(vertices, 0), 1)).collect()
+}
+
+/// Divide each polygon of a batch into simple contours, resolving the places where it intersects
+/// itself.
+///
+/// Each polygon is resolved separately, like in `make_simple_polygon`. This chooses automatically
+/// whether to perform the operations on a single thread or on multiple threads, like a boolean
+/// operation on a batch.
+///
+/// # Arguments
+/// * `batch` - The polygons to resolve.
+/// * `fill_rule` - Which regions of each polygon are considered to be inside the polygon.
+///
+/// # Result
+/// For each polygon, its filled regions, as simple contours.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, Point2D, PolygonBatch, Shape2D};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let simple = apex::operations::self_intersection::make_simple_batch(&batch, FillRule::NON_ZERO);
+/// assert_eq!(simple[0].num_contours(), 2, "The two lobes of the bowtie are separated.");
+/// assert_eq!(simple[1].num_contours(), 1, "A square is already simple.");
+/// ```
+pub fn make_simple_batch(batch: &PolygonBatch, fill_rule: FillRule) -> Vec<MultiPolygon> {
+	match cost_model::choose(Operation::BOOLEAN, batch.len(), batch.sync_status()) {
+		Backend::ST => make_simple_batch_st(batch, fill_rule),
+		Backend::MT => make_simple_batch_mt(batch, fill_rule),
+		Backend::GPU => make_simple_batch_mt(batch, fill_rule), //There is no GPU implementation for batches. The GPU is only chosen for a lot of work, which suits multiple threads best.
+	}
+}
+
+/// Divide each polygon of a batch into simple contours, resolving the places where it intersects
+/// itself, on a single thread.
+///
+/// # Arguments
+/// * `batch` - The polygons to resolve.
+/// * `fill_rule` - Which regions of each polygon are considered to be inside the polygon.
+///
+/// # Result
+/// For each polygon, its filled regions, as simple contours.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, Point2D, PolygonBatch, Shape2D};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// let simple = apex::operations::self_intersection::make_simple_batch_st(&batch, FillRule::NON_ZERO);
+/// assert_eq!(simple[0].num_contours(), 2, "The two lobes of the bowtie are separated.");
+/// ```
+pub fn make_simple_batch_st(batch: &PolygonBatch, fill_rule: FillRule) -> Vec<MultiPolygon> {
+	boolean::make_simple_batch_st(batch, fill_rule)
+}
+
+/// Divide each polygon of a batch into simple contours, resolving the places where it intersects
+/// itself, on multiple threads.
+///
+/// The polygons are divided over the threads, and each polygon is resolved on a single thread.
+///
+/// # Arguments
+/// * `batch` - The polygons to resolve.
+/// * `fill_rule` - Which regions of each polygon are considered to be inside the polygon.
+///
+/// # Result
+/// For each polygon, its filled regions, as simple contours.
+///
+/// # Examples
+/// ```
+/// use apex::{FillRule, Point2D, PolygonBatch, Shape2D};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// let simple = apex::operations::self_intersection::make_simple_batch_mt(&batch, FillRule::NON_ZERO);
+/// assert_eq!(simple[0].num_contours(), 2, "The two lobes of the bowtie are separated.");
+/// ```
+pub fn make_simple_batch_mt(batch: &PolygonBatch, fill_rule: FillRule) -> Vec<MultiPolygon> {
+	boolean::make_simple_batch_mt(batch, fill_rule)
+}
+
+/// An edge of a shape, for the sweep line.
+#[derive(Clone, Copy, Debug)]
+struct Segment {
+	/// The lowest endpoint of the edge, in lexicographic order.
+	start: Point2D,
+
+	/// The highest endpoint of the edge, in lexicographic order.
+	end: Point2D,
+
+	/// The endpoint where the edge starts, in the direction of its contour.
+	from: Point2D,
+
+	/// The index of the first vertex of the edge.
+	edge: usize,
+
+	/// The edge before this one in its contour, skipping edges without length.
+	prev_edge: usize,
+
+	/// The edge after this one in its contour, skipping edges without length.
+	next_edge: usize,
+}
+
+/// A place where the sweep line stops, which is a vertex or a crossing.
+///
+/// Crossings are generally not on the grid, so stops are represented exactly as rational points.
+type Stop = RationalPoint;
+
+/// Collect the edges of a contour as segments, skipping edges without length.
+///
+/// # Arguments
+/// * `vertices` - The vertices of the contour.
+/// * `first_index` - The index of the first vertex of the contour, in the whole shape.
+fn contour_segments(vertices: &[Point2D], first_index: usize) -> Vec<Segment> {
+	let edges: Vec<usize> = (0..vertices.len()).filter(|i| vertices[*i] != vertices[(i + 1) % vertices.len()]).collect();
+	(0..edges.len()).map(|i| {
+		let from = vertices[edges[i]];
+		let to = vertices[(edges[i] + 1) % vertices.len()];
+		Segment {
+			start: cmp::min(from, to),
+			end: cmp::max(from, to),
+			from,
+			edge: first_index + edges[i],
+			prev_edge: first_index + edges[(i + edges.len() - 1) % edges.len()],
+			next_edge: first_index + edges[(i + 1) % edges.len()],
+		}
+	}).collect()
+}
+
+/// Collect the edges of all contours of a multi-polygon as segments.
+fn multipolygon_segments(multi_polygon: &MultiPolygon) -> Vec<Segment> {
+	let contour_starts = multi_polygon.contour_starts();
+	multi_polygon.contours().enumerate().flat_map(|(contour, vertices)| contour_segments(vertices, contour_starts[contour])).collect()
+}
+
+/// Find all pairs of loose segments that meet, including where they only touch.
+///
+/// Boolean operations use this to find where their segments need to be split. Unlike the edges of a
+/// contour, the segments have no neighbours, so every pair of segments that meets is reported, even
+/// if they only share an endpoint.
+///
+/// # Arguments
+/// * `ends` - The two endpoints of every segment. The segments must have length.
+/// * `num_slabs` - How many slabs to divide the plane into, to search them in parallel.
+///
+/// # Result
+/// For every pair of segments that meet, their indices, the lowest first. The pairs are sorted, and
+/// each pair is reported once.
+pub(crate) fn meeting_segments(ends: &[(Point2D, Point2D)], num_slabs: usize) -> Vec<(usize, usize)> {
+	let segments: Vec<Segment> = ends.iter().enumerate().map(|(index, (from, to))| Segment {
+		start: cmp::min(*from, *to),
+		end: cmp::max(*from, *to),
+		from: *from,
+		edge: index,
+		prev_edge: index, //Not part of a contour, so no other segment is next to it.
+		next_edge: index,
+	}).collect();
+	crossings(&segments, num_slabs).into_iter().map(|crossing| (crossing.first_edge, crossing.second_edge)).collect()
+}
+
+/// Choose how many slabs to divide the segments over, for the multi-threaded search.
+fn num_slabs(num_segments: usize) -> usize {
+	cmp::max(1, num_segments / cost_model::chunk_size(Operation::SELF_INTERSECTION, num_segments))
+}
+
+/// Find all crossings between segments.
+///
+/// The plane is divided into vertical slabs, each with about the same number of segments starting
+/// in it. If there is more than one slab, they are searched in parallel.
+///
+/// # Arguments
+/// * `segments` - The segments to search for crossings.
+/// * `num_slabs` - How many slabs to divide the plane into.
+///
+/// # Result
+/// The crossings, sorted by their edges, with only the lowest point for each pair of edges.
+fn crossings(segments: &[Segment], num_slabs: usize) -> Vec<Crossing> {
+	let mut found = if num_slabs <= 1 {
+		sweep(segments, Coordinate::MIN, None)
+	} else {
+		let mut starts: Vec<Coordinate> = segments.iter().map(|segment| segment.start.x).collect();
+		starts.par_sort_unstable();
+		let mut bounds: Vec<Coordinate> = (1..num_slabs).map(|slab| starts[slab * starts.len() / num_slabs]).collect();
+		bounds.dedup();
+		let lefts = std::iter::once(Coordinate::MIN).chain(bounds.iter().copied());
+		let rights = bounds.iter().copied().map(Some).chain(std::iter::once(None));
+		let slabs: Vec<(Coordinate, Option<Coordinate>)> = lefts.zip(rights).collect();
+		slabs.into_par_iter().flat_map_iter(|(left, right)| sweep(segments, left, right)).collect()
+	};
+	found.par_sort_unstable_by(|(stop_a, a), (stop_b, b)| (a.first_edge, a.second_edge).cmp(&(b.first_edge, b.second_edge)).then_with(|| stop_a.cmp(stop_b)));
+	found.dedup_by_key(|(_, crossing)| (crossing.first_edge, crossing.second_edge)); //Keeps the lowest point of overlapping edges, before rounding.
+	found.into_iter().map(|(_, crossing)| crossing).collect()
+}
+
+/// Sweep a line through a vertical slab of the plane, to find the crossings in it.
+///
+/// The sweep line stops at every vertex and crossing in the slab, from left to right, and for equal
+/// X coordinates from bottom to top. At each stop, it reports all pairs of segments that go through
+/// that point, then reorders those segments by how they continue to the right. Only segments that
+/// became neighbours need to be tested for new crossings.
+///
+/// # Arguments
+/// * `segments` - All segments of the shape.
+/// * `left` - The X coordinate where the slab starts. Crossings at this coordinate are included.
+/// Segments that start further left enter the sweep line here.
+/// * `right` - The X coordinate where the slab ends, if any. Crossings at this coordinate are not
+/// included.
+///
+/// # Result
+/// The crossings in the slab, each with the exact stop where it was found. The same pair of
+/// segments may be reported multiple times if they overlap.
+fn sweep(segments: &[Segment], left: Coordinate, right: Option<Coordinate>) -> Vec<(Stop, Crossing)> {
+	let before_right = |x: Coordinate| right.is_none_or(|right| x < right);
+	let mut stops: BTreeMap<Stop, Vec<usize>> = BTreeMap::new(); //For each stop, the segments that start there.
+	for (index, segment) in segments.iter().enumerate() {
+		if segment.end.x < left || !before_right(segment.start.x) {
+			continue; //Not in this slab.
+		}
+		let entry = if segment.start.x >= left {
+			Stop::from_point(&segment.start)
+		} else {
+			//Enter the slab at the left side. This is not vertical, since it spans multiple X coordinates.
+			let (dx, dy) = direction(&segment.start, &segment.end);
+			Stop { x: left as i128 * dx, y: segment.start.y as i128 * dx + dy * (left as i128 - segment.start.x as i128), denominator: dx }
+		};
+		stops.entry(entry).or_default().push(index);
+		if before_right(segment.end.x) {
+			stops.entry(Stop::from_point(&segment.end)).or_default(); //So that it leaves the sweep line.
+		}
+	}
+
+	let mut crossings = Vec::new();
+	let mut status: Vec<usize> = Vec::new(); //The segments that the sweep line crosses, from bottom to top.
+	while let Some((stop, starting)) = stops.pop_first() {
+		let first = status.partition_point(|index| side(&segments[*index], &stop) == Ordering::Less);
+		let mut last = first;
+		while last < status.len() && side(&segments[status[last]], &stop) == Ordering::Equal {
+			last += 1;
+		}
+		let through: Vec<usize> = status.drain(first..last).collect();
+		let meeting: Vec<usize> = starting.iter().chain(through.iter()).copied().collect();
+		for (i, a) in meeting.iter().enumerate() {
+			for b in &meeting[i + 1..] {
+				if is_reported_at(&segments[*a], &segments[*b], &stop) {
+					let (first_edge, second_edge) = (cmp::min(segments[*a].edge, segments[*b].edge), cmp::max(segments[*a].edge, segments[*b].edge));
+					crossings.push((stop, Crossing { first_edge, second_edge, point: stop.rounded() }));
+				}
+			}
+		}
+
+		let mut continuing: Vec<usize> = meeting.into_iter().filter(|index| Stop::from_point(&segments[*index].end) != stop).collect();
+		continuing.sort_by(|a, b| compare_slopes(&segments[*a], &segments[*b]));
+		let num_continuing = continuing.len();
+		status.splice(first..first, continuing);
+		let mut test = |below: usize, above: usize| {
+			if let Some(crossing) = intersection(&segments[status[below]], &segments[status[above]]) && crossing > stop && before_right(crossing_x_floor(&crossing)) {
+				stops.entry(crossing).or_default(); //Crossings that the sweep line already passed have been found before.
+			}
+		};
+		if num_continuing == 0 {
+			if first > 0 && first < status.len() {
+				test(first - 1, first); //The segments around the point became neighbours.
+			}
+		} else {
+			if first > 0 {
+				test(first - 1, first);
+			}
+			if first + num_continuing < status.len() {
+				test(first + num_continuing - 1, first + num_continuing);
+			}
+		}
+	}
+	crossings
+}
+
+/// Get the largest X coordinate that is not to the right of a stop.
+fn crossing_x_floor(stop: &Stop) -> Coordinate {
+	stop.x.div_euclid(stop.denominator) as Coordinate
+}
+
+/// Check whether two segments that meet in a point should be reported as crossing there.
+///
+/// Consecutive edges of the same contour always meet at their shared vertex, so they only cross if
+/// they overlap. That is reported only at the other end of the overlap, which is an endpoint of one
+/// of them.
+fn is_reported_at(a: &Segment, b: &Segment, stop: &Stop) -> bool {
+	let shares_to = a.next_edge == b.edge;
+	let shares_from = a.prev_edge == b.edge;
+	if !shares_to && !shares_from {
+		return true;
+	}
+	let is_at = |point: &Point2D| Stop::from_point(point) == *stop;
+	let to = if a.from == a.start { a.end } else { a.start };
+	if (shares_to && is_at(&to)) || (shares_from && is_at(&a.from)) {
+		return false;
+	}
+	is_at(&a.start) || is_at(&a.end) || is_at(&b.start) || is_at(&b.end)
+}
+
+/// Find where a segment is relative to a point, at the X coordinate of the point.
+///
+/// The segment must span the X coordinate of the point. Vertical segments in the sweep line always
+/// span the point too, since the sweep line stops at their endpoints.
+///
+/// # Result
+/// Whether the segment passes below the point, through the point or above the point.
+fn side(segment: &Segment, point: &Stop) -> Ordering {
+	let (dx, dy) = direction(&segment.start, &segment.end);
+	if dx == 0 {
+		return Ordering::Equal;
+	}
+	let relative_x = point.x - segment.start.x as i128 * point.denominator;
+	let relative_y = point.y - segment.start.y as i128 * point.denominator;
+	//The point is above the segment if it is left of it, so if dx * relative_y > dy * relative_x.
+	compare_products(dy, relative_x, dx, relative_y)
+}
+
+/// Compare the directions of two segments that go through the same point, from bottom to top just
+/// right of that point.
+///
+/// Vertical segments are ordered last, since they continue upwards from the point where the sweep
+/// line is.
+fn compare_slopes(a: &Segment, b: &Segment) -> Ordering {
+	let (a_dx, a_dy) = direction(&a.start, &a.end);
+	let (b_dx, b_dy) = direction(&b.start, &b.end);
+	match (a_dx == 0, b_dx == 0) {
+		(true, true) => Ordering::Equal,
+		(true, false) => Ordering::Greater,
+		(false, true) => Ordering::Less,
+		(false, false) => (a_dy * b_dx).cmp(&(b_dy * a_dx)),
+	}
+}
+
+/// Find where two segments meet, if they meet in a single point.
+///
+/// Parallel segments are not tested, since their overlaps start and end at vertices, where the
+/// sweep line stops anyway.
+fn intersection(a: &Segment, b: &Segment) -> Option<Stop> {
+	predicates::intersection(&a.start, &a.end, &b.start, &b.end)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+	use crate::Shape2D;
+
+	/// Find all crossings by testing every pair of segments, to compare the sweep line against.
+	fn brute_force(segments: &[Segment]) -> Vec<Crossing> {
+		let orientation = |start: &Point2D, end: &Point2D, point: &Point2D| {
+			let line = direction(start, end);
+			let relative = direction(start, point);
+			line.0 * relative.1 - line.1 * relative.0
+		};
+		let mut result = Vec::new();
+		for (i, a) in segments.iter().enumerate() {
+			for b in &segments[i + 1..] {
+				let collinear = orientation(&a.start, &a.end, &b.start) == 0 && orientation(&a.start, &a.end, &b.end) == 0;
+				let point = if collinear {
+					let overlap_start = cmp::max(a.start, b.start);
+					let overlap_end = cmp::min(a.end, b.end);
+					let consecutive = a.next_edge == b.edge || a.prev_edge == b.edge;
+					if overlap_start > overlap_end || (consecutive && overlap_start == overlap_end) {
+						None
+					} else if consecutive {
+						let shared: Vec<Point2D> = [(a.next_edge == b.edge, b.from), (a.prev_edge == b.edge, a.from)].iter().filter(|(shares, _)| *shares).map(|(_, point)| *point).collect();
+						[overlap_start, overlap_end].into_iter().find(|point| !shared.contains(point))
+					} else {
+						Some(overlap_start)
+					}
+				} else {
+					intersection(a, b).map(|stop| stop.rounded()).filter(|point| {
+						let is_shared = (a.next_edge == b.edge && *point == b.from) || (a.prev_edge == b.edge && *point == a.from);
+						!is_shared
+					})
+				};
+				if let Some(point) = point {
+					result.push(Crossing { first_edge: cmp::min(a.edge, b.edge), second_edge: cmp::max(a.edge, b.edge), point });
+				}
+			}
+		}
+		result.sort_unstable();
+		result
+	}
+
+	/// Generate a pseudo-random polygon with many degenerate cases, on a small grid.
+	fn random_polygon(seed: u64, num_vertices: usize, size: u64) -> Vec<Point2D> {
+		let mut state = seed;
+		let mut random = move || {
+			state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+			((state >> 33) % size) as Coordinate
+		};
+		(0..num_vertices).map(|_| Point2D { x: random(), y: random() }).collect()
+	}
+
+	/// Test polygons without crossings.
+	#[test_case(crate::test::data::polygon::square_1000() ; "square")]
+	#[test_case(crate::test::data::polygon::l_shape_1000() ; "L-shape")]
+	#[test_case(Polygon::new() ; "empty")]
+	#[test_case(Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 100, y: 100 }]) ; "duplicate vertices")]
+	fn simple(polygon: Polygon) {
+		assert_eq!(crossings_polygon_st(&polygon), Vec::new());
+		assert_eq!(crossings_polygon_mt(&polygon), Vec::new());
+		assert_eq!(polygon.crossings(), Vec::new());
+	}
+
+	/// Test the crossing in the middle of a bowtie.
+	#[test]
+	fn bowtie() {
+		let bowtie = crate::test::data::polygon::bowtie_1000();
+		let expected = vec![Crossing { first_edge: 0, second_edge: 2, point: Point2D { x: 500, y: 500 } }];
+		assert_eq!(crossings_polygon_st(&bowtie), expected);
+		assert_eq!(crossings_polygon_mt(&bowtie), expected);
+	}
+
+	/// Test the five crossings of a pentagram, where every edge crosses the two edges that are not
+	/// next to it.
+	#[test]
+	fn pentagram() {
+		let pentagram = crate::test::data::polygon::pentagram_1000();
+		let crossings = crossings_polygon_st(&pentagram);
+		let pairs: Vec<(usize, usize)> = crossings.iter().map(|crossing| (crossing.first_edge, crossing.second_edge)).collect();
+		assert_eq!(pairs, vec![(0, 2), (0, 3), (1, 3), (1, 4), (2, 4)]);
+		assert_eq!(crossings_polygon_mt(&pentagram), crossings, "The multi-threaded search must find the same crossings.");
+	}
+
+	/// Test that crossings that are not on the grid are rounded to the nearest coordinates.
+	#[test]
+	fn rounded() {
+		let polygon = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 3, y: 1 }, Point2D { x: 3, y: 0 }, Point2D { x: 0, y: 1 }]);
+		assert_eq!(crossings_polygon_st(&polygon), vec![Crossing { first_edge: 0, second_edge: 2, point: Point2D { x: 2, y: 1 } }], "Halves of the crossing at (1.5, 0.5) are rounded up.");
+	}
+
+	/// Test a vertex that touches an edge that is not next to it.
+	#[test]
+	fn touching() {
+		let polygon = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 50, y: 0 }, Point2D { x: 0, y: 100 }]);
+		assert_eq!(crossings_polygon_st(&polygon), vec![
+			Crossing { first_edge: 0, second_edge: 2, point: Point2D { x: 50, y: 0 } },
+			Crossing { first_edge: 0, second_edge: 3, point: Point2D { x: 50, y: 0 } },
+		]);
+	}
+
+	/// Test consecutive edges that go back along the same line, which overlap.
+	#[test]
+	fn spike() {
+		let polygon = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 100, y: 50 }, Point2D { x: 0, y: 100 }]);
+		assert_eq!(crossings_polygon_st(&polygon), vec![
+			Crossing { first_edge: 1, second_edge: 2, point: Point2D { x: 100, y: 50 } }, //The overlap is reported at its end, away from the shared vertex.
+			Crossing { first_edge: 1, second_edge: 3, point: Point2D { x: 100, y: 50 } }, //The next edge starts on the overlap.
+		]);
+	}
+
+	/// Test non-consecutive edges that overlap along a stretch.
+	#[test]
+	fn overlapping() {
+		let polygon = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 50, y: 100 }, Point2D { x: 50, y: 0 }, Point2D { x: 30, y: 0 }, Point2D { x: 30, y: 100 }, Point2D { x: 0, y: 100 }]);
+		assert_eq!(crossings_polygon_st(&polygon), vec![
+			Crossing { first_edge: 0, second_edge: 3, point: Point2D { x: 50, y: 0 } }, //Ends on the edge.
+			Crossing { first_edge: 0, second_edge: 4, point: Point2D { x: 30, y: 0 } }, //The overlap is reported at its lowest point.
+			Crossing { first_edge: 0, second_edge: 5, point: Point2D { x: 30, y: 0 } }, //Starts on the edge.
+		]);
+	}
+
+	/// Test that crossings between contours of a multi-polygon are reported with the indices of
+	/// their vertices in the whole multi-polygon.
+	#[test]
+	fn multipolygon() {
+		let mut squares = MultiPolygon::new();
+		squares.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+		squares.push_contour([Point2D { x: 50, y: 50 }, Point2D { x: 150, y: 50 }, Point2D { x: 150, y: 150 }, Point2D { x: 50, y: 150 }]);
+		let expected = vec![
+			Crossing { first_edge: 1, second_edge: 4, point: Point2D { x: 100, y: 50 } },
+			Crossing { first_edge: 2, second_edge: 7, point: Point2D { x: 50, y: 100 } },
+		];
+		assert_eq!(crossings_multipolygon_st(&squares), expected);
+		assert_eq!(crossings_multipolygon_mt(&squares), expected);
+		assert_eq!(squares.crossings(), expected);
+		assert_eq!(crossings_multipolygon_st(&crate::test::data::multi_polygon::square_with_hole_1000()), Vec::new(), "The hole doesn't touch the outer contour.");
+	}
+
+	/// Test the sweep line against testing every pair of edges, on random polygons with many
+	/// collinear edges, vertical edges, touching vertices and crossings through the same point.
+	#[test_case(1, 10, 8 ; "small")]
+	#[test_case(2, 40, 12 ; "medium")]
+	#[test_case(3, 100, 16 ; "large")]
+	#[test_case(4, 200, 1000 ; "sparse")]
+	#[test_case(5, 50, Coordinate::MAX as u64 ; "huge coordinates")]
+	fn random(seed: u64, num_vertices: usize, size: u64) {
+		for attempt in 0..20 {
+			let vertices = random_polygon(seed * 1000 + attempt, num_vertices, size);
+			let segments = contour_segments(&vertices, 0);
+			let expected = brute_force(&segments);
+			assert_eq!(crossings(&segments, 1), expected, "The sweep line must find exactly the crossings of every pair of edges.");
+			for num_slabs in [2, 3, 7] {
+				assert_eq!(crossings(&segments, num_slabs), expected, "Dividing the plane into {num_slabs} slabs must find the same crossings.");
+			}
+		}
+	}
+
+	/// Test that crossings in a random multi-polygon are found in all contours.
+	#[test]
+	fn random_multipolygon() {
+		let mut multi_polygon = MultiPolygon::new();
+		for contour in 0..5 {
+			multi_polygon.push_contour(random_polygon(contour, 3 + contour as usize * 5, 20));
+		}
+		let expected = brute_force(&multipolygon_segments(&multi_polygon));
+		assert_eq!(crossings_multipolygon_st(&multi_polygon), expected);
+		assert_eq!(crossings_multipolygon_mt(&multi_polygon), expected);
+	}
+
+	/// Test resolving the crossings of self-intersecting polygons with different fill rules.
+	#[test_case(crate::test::data::polygon::bowtie_1000(), FillRule::NON_ZERO, 2, 500000 ; "bowtie non-zero")]
+	#[test_case(crate::test::data::polygon::bowtie_1000(), FillRule::EVEN_ODD, 2, 500000 ; "bowtie even-odd")]
+	#[test_case(crate::test::data::polygon::pentagram_1000(), FillRule::NON_ZERO, 1, 0 ; "pentagram non-zero")]
+	#[test_case(crate::test::data::polygon::pentagram_1000(), FillRule::EVEN_ODD, 5, 0 ; "pentagram even-odd")]
+	fn make_simple(polygon: Polygon, fill_rule: FillRule, num_contours: usize, area: crate::Area) {
+		for simple in [make_simple_polygon_st(&polygon, fill_rule), make_simple_polygon_mt(&polygon, fill_rule), make_simple_polygon(&polygon, fill_rule)] {
+			assert_eq!(simple.num_contours(), num_contours);
+			for contour in simple.contours() {
+				assert_eq!(crossings_polygon_st(&Polygon::from_iter(contour.iter().copied())), Vec::new(), "The contours must not intersect themselves.");
+			}
+			if area != 0 {
+				assert_eq!(simple.area(), area);
+			}
+		}
+	}
+
+	/// Test that searching and resolving batches gives the same results as for each polygon.
+	#[test]
+	fn batch_polygons() {
+		let polygons = [crate::test::data::polygon::bowtie_1000(), crate::test::data::polygon::square_1000(), Polygon::new(), crate::test::data::polygon::pentagram_1000()];
+		let batch = PolygonBatch::from_iter(polygons.iter().map(|polygon| Polygon::from_iter(polygon.host_vertices().iter().copied())));
+		let expected: Vec<Vec<Crossing>> = polygons.iter().map(crossings_polygon_st).collect();
+		assert_eq!(crossings_batch_st(&batch), expected);
+		assert_eq!(crossings_batch_mt(&batch), expected);
+		assert_eq!(crossings_batch(&batch), expected);
+		for simple in [make_simple_batch_st(&batch, FillRule::EVEN_ODD), make_simple_batch_mt(&batch, FillRule::EVEN_ODD), make_simple_batch(&batch, FillRule::EVEN_ODD)] {
+			assert_eq!(simple.len(), polygons.len());
+			for (polygon, result) in polygons.iter().zip(simple.iter()) {
+				let expected = make_simple_polygon_st(polygon, FillRule::EVEN_ODD);
+				assert_eq!(result.host_vertices(), expected.host_vertices());
+				assert_eq!(result.contour_starts(), expected.contour_starts());
+			}
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,874 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! This module contains the implementations of operations to reduce the number of vertices of
+//! geometric objects, while keeping their shape approximately the same.
+//!
+//! Simplification only removes vertices. The remaining vertices are not moved, and keep their
+//! order. The first vertex of every contour is always kept, so that the seam stays in place, and
+//! every contour keeps at least three vertices. Contours with three vertices or fewer are not
+//! changed.
+//!
+//! There are two methods:
+//! * Douglas-Peucker keeps the vertices that are needed to stay within a maximum distance from the
+//! original contour. It starts with the first vertex and the vertex furthest away from it. Between
+//! each pair of consecutive kept vertices, the vertex furthest from the line between them is added,
+//! until all removed vertices are close enough to that line.
+//! * Visvalingam-Whyatt repeatedly removes the vertex that changes the area the least, which is the
+//! area of the triangle that it forms with its neighbours. It stops when removing the next vertex
+//! would exceed a budget for the total area of those triangles.
+//!
+//! Removing vertices can make a contour intersect itself or other contours, for instance where a
+//! narrow gap gets closed. Optionally the topology can be preserved, so that this never happens if
+//! the input didn't intersect itself. For Visvalingam-Whyatt, a vertex is then only removed if the
+//! triangle that it forms with its neighbours contains no other vertices. Douglas-Peucker adds back
+//! the furthest vertex of every simplified edge that intersects another edge, until nothing
+//! intersects any more.
+
+use std::cmp::Reverse; //To remove the vertices with the smallest area first.
+use std::collections::BinaryHeap; //To find the vertex with the smallest area.
+use rayon::prelude::*; //For multi-threaded implementations.
+
+use crate::Area; //The area budget for Visvalingam-Whyatt.
+use crate::MultiPolygon; //Simplify multi-polygons.
+use crate::Point2D; //To compute distances and areas.
+use crate::Polygon; //Simplify polygons.
+use crate::PolygonBatch; //Simplify batches of polygons at once.
+use crate::cost_model::{self, Backend, Operation}; //To choose the fastest implementation.
+use crate::detail::sync_status::SyncStatus; //A batch of polygons has no single place where its data is.
+use crate::operations::self_intersection; //To find simplified edges that intersect other edges.
+use crate::predicates::orientation; //To find which side of an edge the removed vertices are on.
+
+/// The methods to decide which vertices to remove.
+#[allow(non_camel_case_types)] //The variants are capitalised like those of the other enums, with underscores between words.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SimplificationMethod {
+	/// Keep the vertices that are needed to stay within a maximum distance of the original contour.
+	DOUGLAS_PEUCKER {
+		/// The maximum distance between the removed vertices and the simplified contour, in
+		/// coordinate units.
+		tolerance: f64,
+	},
+
+	/// Remove the vertices that change the area the least, within a budget.
+	VISVALINGAM {
+		/// The maximum total area of the triangles that are cut off or filled in by removing
+		/// vertices, for each contour.
+		area_budget: Area,
+	},
+}
+
+/// Reduce the number of vertices of a polygon.
+///
+/// This chooses automatically whether to simplify on a single thread or on multiple threads. The
+/// cost model estimates which is fastest, from the number of vertices in the polygon.
+///
+/// # Arguments
+/// * `polygon` - The polygon to simplify.
+/// * `method` - How to decide which vertices to remove.
+/// * `preserve_topology` - Whether to keep vertices that are needed to prevent the polygon from
+/// intersecting itself.
+///
+/// # Result
+/// A polygon with a subset of the vertices of the original polygon.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// use apex::operations::simplify::SimplificationMethod;
+/// let rough_square = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 50, y: 1 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let square = apex::operations::simplify::simplify_polygon(&rough_square, SimplificationMethod::DOUGLAS_PEUCKER { tolerance: 2.0 }, false);
+/// assert_eq!(square.len(), 4, "The vertex that is only 1 unit off is removed.");
+/// ```
+pub fn simplify_polygon(polygon: &Polygon, method: SimplificationMethod, preserve_topology: bool) -> Polygon {
+	match cost_model::choose(Operation::SIMPLIFY, polygon.len(), polygon.sync_status()) {
+		Backend::ST => simplify_polygon_st(polygon, method, preserve_topology),
+		Backend::MT => simplify_polygon_mt(polygon, method, preserve_topology),
+		Backend::GPU => unreachable!("There is no GPU implementation, so the cost model never chooses the GPU."),
+	}
+}
+
+/// Reduce the number of vertices of a polygon, on a single thread.
+///
+/// # Arguments
+/// * `polygon` - The polygon to simplify.
+/// * `method` - How to decide which vertices to remove.
+/// * `preserve_topology` - Whether to keep vertices that are needed to prevent the polygon from
+/// intersecting itself.
+///
+/// # Result
+/// A polygon with a subset of the vertices of the original polygon.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// use apex::operations::simplify::SimplificationMethod;
+/// let rough_square = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 50, y: 1 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let square = apex::operations::simplify::simplify_polygon_st(&rough_square, SimplificationMethod::VISVALINGAM { area_budget: 100 }, false);
+/// assert_eq!(square.len(), 4, "The vertex only adds an area of 50.");
+/// ```
+pub fn simplify_polygon_st(polygon: &Polygon, method: SimplificationMethod, preserve_topology: bool) -> Polygon {
+	Polygon::from_iter(simplify_contours(&[polygon.host_vertices()], method, preserve_topology, Backend::ST).remove(0))
+}
+
+/// Reduce the number of vertices of a polygon, on multiple threads.
+///
+/// For Douglas-Peucker, the parts of the contour between the vertices that were kept so far are
+/// processed in parallel. Visvalingam-Whyatt removes one vertex at a time, so only the initial
+/// areas are computed in parallel.
+///
+/// # Arguments
+/// * `polygon` - The polygon to simplify.
+/// * `method` - How to decide which vertices to remove.
+/// * `preserve_topology` - Whether to keep vertices that are needed to prevent the polygon from
+/// intersecting itself.
+///
+/// # Result
+/// A polygon with a subset of the vertices of the original polygon.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// use apex::operations::simplify::SimplificationMethod;
+/// let rough_square = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 50, y: 1 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let square = apex::operations::simplify::simplify_polygon_mt(&rough_square, SimplificationMethod::DOUGLAS_PEUCKER { tolerance: 0.5 }, false);
+/// assert_eq!(square.len(), 5, "The vertex is too far off to remove it.");
+/// ```
+pub fn simplify_polygon_mt(polygon: &Polygon, method: SimplificationMethod, preserve_topology: bool) -> Polygon {
+	Polygon::from_iter(simplify_contours(&[polygon.host_vertices()], method, preserve_topology, Backend::MT).remove(0))
+}
+
+/// Reduce the number of vertices of all contours of a multi-polygon.
+///
+/// This chooses automatically whether to simplify on a single thread or on multiple threads, like
+/// `simplify_polygon`. If the topology is preserved, the contours are also kept from intersecting
+/// each other.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to simplify.
+/// * `method` - How to decide which vertices to remove.
+/// * `preserve_topology` - Whether to keep vertices that are needed to prevent the contours from
+/// intersecting themselves or each other.
+///
+/// # Result
+/// A multi-polygon with a subset of the vertices of each contour of the original.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, Point2D};
+/// use apex::operations::simplify::SimplificationMethod;
+/// let mut frame = MultiPolygon::new();
+/// frame.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// frame.push_contour([Point2D { x: 25, y: 25 }, Point2D { x: 25, y: 75 }, Point2D { x: 50, y: 76 }, Point2D { x: 75, y: 75 }, Point2D { x: 75, y: 25 }]); //A hole.
+/// let simplified = apex::operations::simplify::simplify_multipolygon(&frame, SimplificationMethod::DOUGLAS_PEUCKER { tolerance: 2.0 }, true);
+/// assert_eq!(simplified.contour(1).len(), 4);
+/// ```
+pub fn simplify_multipolygon(multi_polygon: &MultiPolygon, method: SimplificationMethod, preserve_topology: bool) -> MultiPolygon {
+	match cost_model::choose(Operation::SIMPLIFY, multi_polygon.len(), multi_polygon.sync_status()) {
+		Backend::ST => simplify_multipolygon_st(multi_polygon, method, preserve_topology),
+		Backend::MT => simplify_multipolygon_mt(multi_polygon, method, preserve_topology),
+		Backend::GPU => unreachable!("There is no GPU implementation, so the cost model never chooses the GPU."),
+	}
+}
+
+/// Reduce the number of vertices of all contours of a multi-polygon, on a single thread.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to simplify.
+/// * `method` - How to decide which vertices to remove.
+/// * `preserve_topology` - Whether to keep vertices that are needed to prevent the contours from
+/// intersecting themselves or each other.
+///
+/// # Result
+/// A multi-polygon with a subset of the vertices of each contour of the original.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, Point2D};
+/// use apex::operations::simplify::SimplificationMethod;
+/// let mut frame = MultiPolygon::new();
+/// frame.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// frame.push_contour([Point2D { x: 25, y: 25 }, Point2D { x: 25, y: 75 }, Point2D { x: 50, y: 76 }, Point2D { x: 75, y: 75 }, Point2D { x: 75, y: 25 }]); //A hole.
+/// let simplified = apex::operations::simplify::simplify_multipolygon_st(&frame, SimplificationMethod::VISVALINGAM { area_budget: 24 }, true);
+/// assert_eq!(simplified.contour(1).len(), 5, "Removing the vertex would change the area by 25, which exceeds the budget.");
+/// ```
+pub fn simplify_multipolygon_st(multi_polygon: &MultiPolygon, method: SimplificationMethod, preserve_topology: bool) -> MultiPolygon {
+	let contours: Vec<&[Point2D]> = multi_polygon.contours().collect();
+	let mut result = MultiPolygon::new();
+	for contour in simplify_contours(&contours, method, preserve_topology, Backend::ST) {
+		result.push_contour(contour);
+	}
+	result
+}
+
+/// Reduce the number of vertices of all contours of a multi-polygon, on multiple threads.
+///
+/// The contours are divided over the threads.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to simplify.
+/// * `method` - How to decide which vertices to remove.
+/// * `preserve_topology` - Whether to keep vertices that are needed to prevent the contours from
+/// intersecting themselves or each other.
+///
+/// # Result
+/// A multi-polygon with a subset of the vertices of each contour of the original.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, Point2D};
+/// use apex::operations::simplify::SimplificationMethod;
+/// let mut frame = MultiPolygon::new();
+/// frame.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// frame.push_contour([Point2D { x: 25, y: 25 }, Point2D { x: 25, y: 75 }, Point2D { x: 50, y: 76 }, Point2D { x: 75, y: 75 }, Point2D { x: 75, y: 25 }]); //A hole.
+/// let simplified = apex::operations::simplify::simplify_multipolygon_mt(&frame, SimplificationMethod::VISVALINGAM { area_budget: 26 }, true);
+/// assert_eq!(simplified.contour(1).len(), 4);
+/// ```
+pub fn simplify_multipolygon_mt(multi_polygon: &MultiPolygon, method: SimplificationMethod, preserve_topology: bool) -> MultiPolygon {
+	let contours: Vec<&[Point2D]> = multi_polygon.contours().collect();
+	let mut result = MultiPolygon::new();
+	for contour in simplify_contours(&contours, method, preserve_topology, Backend::MT) {
+		result.push_contour(contour);
+	}
+	result
+}
+
+/// Reduce the number of vertices of many polygons.
+///
+/// Each polygon is simplified separately. If the topology is preserved, each polygon is kept from
+/// intersecting itself, but different polygons may intersect each other. This chooses
+/// automatically whether to simplify on a single thread or on multiple threads, from the total
+/// number of vertices.
+///
+/// # Arguments
+/// * `polygons` - The polygons to simplify.
+/// * `method` - How to decide which vertices to remove.
+/// * `preserve_topology` - Whether to keep vertices that are needed to prevent the polygons from
+/// intersecting themselves.
+///
+/// # Result
+/// For each polygon, a polygon with a subset of its vertices.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// use apex::operations::simplify::SimplificationMethod;
+/// let polygons = vec![
+/// 	Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 50, y: 1 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]),
+/// 	Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]),
+/// ];
+/// let simplified = apex::operations::simplify::simplify_polygons(&polygons, SimplificationMethod::DOUGLAS_PEUCKER { tolerance: 2.0 }, false);
+/// assert_eq!(simplified[0].len(), 4);
+/// assert_eq!(simplified[1].len(), 3, "A triangle can't be simplified.");
+/// ```
+pub fn simplify_polygons(polygons: &[Polygon], method: SimplificationMethod, preserve_topology: bool) -> Vec<Polygon> {
+	let num_vertices = polygons.iter().map(Polygon::len).sum();
+	match cost_model::choose(Operation::SIMPLIFY, num_vertices, SyncStatus::HOST) {
+		Backend::ST => simplify_polygons_st(polygons, method, preserve_topology),
+		Backend::MT => simplify_polygons_mt(polygons, method, preserve_topology),
+		Backend::GPU => unreachable!("There is no GPU implementation, so the cost model never chooses the GPU."),
+	}
+}
+
+/// Reduce the number of vertices of many polygons, on a single thread.
+///
+/// # Arguments
+/// * `polygons` - The polygons to simplify.
+/// * `method` - How to decide which vertices to remove.
+/// * `preserve_topology` - Whether to keep vertices that are needed to prevent the polygons from
+/// intersecting themselves.
+///
+/// # Result
+/// For each polygon, a polygon with a subset of its vertices.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// use apex::operations::simplify::SimplificationMethod;
+/// let polygons = vec![
+/// 	Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 50, y: 1 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]),
+/// 	Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]),
+/// ];
+/// let simplified = apex::operations::simplify::simplify_polygons_st(&polygons, SimplificationMethod::VISVALINGAM { area_budget: 100 }, true);
+/// assert_eq!(simplified[0].len(), 4);
+/// ```
+pub fn simplify_polygons_st(polygons: &[Polygon], method: SimplificationMethod, preserve_topology: bool) -> Vec<Polygon> {
+	polygons.iter().map(|polygon| simplify_polygon_st(polygon, method, preserve_topology)).collect()
+}
+
+/// Reduce the number of vertices of many polygons, on multiple threads.
+///
+/// The polygons are divided over the threads. Each polygon is simplified on a single thread.
+///
+/// # Arguments
+/// * `polygons` - The polygons to simplify.
+/// * `method` - How to decide which vertices to remove.
+/// * `preserve_topology` - Whether to keep vertices that are needed to prevent the polygons from
+/// intersecting themselves.
+///
+/// # Result
+/// For each polygon, a polygon with a subset of its vertices.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// use apex::operations::simplify::SimplificationMethod;
+/// let polygons = vec![
+/// 	Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 50, y: 1 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]),
+/// 	Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]),
+/// ];
+/// let simplified = apex::operations::simplify::simplify_polygons_mt(&polygons, SimplificationMethod::VISVALINGAM { area_budget: 100 }, true);
+/// assert_eq!(simplified[0].len(), 4);
+/// ```
+pub fn simplify_polygons_mt(polygons: &[Polygon], method: SimplificationMethod, preserve_topology: bool) -> Vec<Polygon> {
+	polygons.par_iter().map(|polygon| simplify_polygon_st(polygon, method, preserve_topology)).collect()
+}
+
+/// Reduce the number of vertices of every polygon in a batch.
+///
+/// Like in `simplify_polygons`, each polygon is simplified separately. This chooses automatically
+/// whether to simplify on a single thread or on multiple threads. The cost model considers the
+/// total number of vertices in the batch.
+///
+/// # Arguments
+/// * `batch` - The polygons to simplify.
+/// * `method` - How to decide which vertices to remove.
+/// * `preserve_topology` - Whether to keep vertices that are needed to prevent the polygons from
+/// intersecting themselves.
+///
+/// # Result
+/// A batch with, for each polygon, a polygon with a subset of its vertices.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, PolygonBatch};
+/// use apex::operations::simplify::SimplificationMethod;
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 50, y: 1 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+/// let simplified = apex::operations::simplify::simplify_batch(&batch, SimplificationMethod::DOUGLAS_PEUCKER { tolerance: 2.0 }, false);
+/// assert_eq!(simplified.polygon(0).len(), 4);
+/// assert_eq!(simplified.polygon(1).len(), 3, "A triangle can't be simplified.");
+/// ```
+pub fn simplify_batch(batch: &PolygonBatch, method: SimplificationMethod, preserve_topology: bool) -> PolygonBatch {
+	match cost_model::choose(Operation::SIMPLIFY, batch.len(), batch.sync_status()) {
+		Backend::ST => simplify_batch_st(batch, method, preserve_topology),
+		Backend::MT => simplify_batch_mt(batch, method, preserve_topology),
+		Backend::GPU => unreachable!("There is no GPU implementation, so the cost model never chooses the GPU."),
+	}
+}
+
+/// Reduce the number of vertices of every polygon in a batch, on a single thread.
+///
+/// # Arguments
+/// * `batch` - The polygons to simplify.
+/// * `method` - How to decide which vertices to remove.
+/// * `preserve_topology` - Whether to keep vertices that are needed to prevent the polygons from
+/// intersecting themselves.
+///
+/// # Result
+/// A batch with, for each polygon, a polygon with a subset of its vertices.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, PolygonBatch};
+/// use apex::operations::simplify::SimplificationMethod;
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 50, y: 1 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let simplified = apex::operations::simplify::simplify_batch_st(&batch, SimplificationMethod::VISVALINGAM { area_budget: 100 }, true);
+/// assert_eq!(simplified.polygon(0).len(), 4);
+/// ```
+pub fn simplify_batch_st(batch: &PolygonBatch, method: SimplificationMethod, preserve_topology: bool) -> PolygonBatch {
+	let mut result = PolygonBatch::new();
+	for vertices in batch.polygons() {
+		result.push_polygon(simplify_contours(&[vertices], method, preserve_topology, Backend::ST).remove(0));
+	}
+	result
+}
+
+/// Reduce the number of vertices of every polygon in a batch, on multiple threads.
+///
+/// The polygons are divided over the threads. Each polygon is simplified on a single thread.
+///
+/// # Arguments
+/// * `batch` - The polygons to simplify.
+/// * `method` - How to decide which vertices to remove.
+/// * `preserve_topology` - Whether to keep vertices that are needed to prevent the polygons from
+/// intersecting themselves.
+///
+/// # Result
+/// A batch with, for each polygon, a polygon with a subset of its vertices.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, PolygonBatch};
+/// use apex::operations::simplify::SimplificationMethod;
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 50, y: 1 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let simplified = apex::operations::simplify::simplify_batch_mt(&batch, SimplificationMethod::VISVALINGAM { area_budget: 100 }, true);
+/// assert_eq!(simplified.polygon(0).len(), 4);
+/// ```
+pub fn simplify_batch_mt(batch: &PolygonBatch, method: SimplificationMethod, preserve_topology: bool) -> PolygonBatch {
+	let polygons: Vec<&[Point2D]> = batch.polygons().collect();
+	let simplified: Vec<Vec<Point2D>> = polygons.into_par_iter().map(|vertices| simplify_contours(&[vertices], method, preserve_topology, Backend::ST).remove(0)).collect();
+	let mut result = PolygonBatch::new();
+	for vertices in simplified {
+		result.push_polygon(vertices);
+	}
+	result
+}
+
+/// A vertex that could block the removal of another vertex, when preserving the topology.
+#[derive(Clone, Copy, Debug)]
+struct Obstacle {
+	/// The position of the vertex.
+	point: Point2D,
+
+	/// The contour that the vertex is in.
+	contour: usize,
+
+	/// The index of the vertex in its contour.
+	index: usize,
+}
+
+/// Simplify a set of contours that belong to the same shape.
+///
+/// # Arguments
+/// * `contours` - The vertices of each contour.
+/// * `method` - How to decide which vertices to remove.
+/// * `preserve_topology` - Whether to keep the contours from intersecting themselves or each other.
+/// * `backend` - Whether to simplify on a single thread or on multiple threads.
+///
+/// # Result
+/// The remaining vertices of each contour.
+fn simplify_contours(contours: &[&[Point2D]], method: SimplificationMethod, preserve_topology: bool, backend: Backend) -> Vec<Vec<Point2D>> {
+	let keep: Vec<Vec<bool>> = match method {
+		SimplificationMethod::DOUGLAS_PEUCKER { tolerance } => {
+			let mut keep: Vec<Vec<bool>> = match backend {
+				Backend::ST => contours.iter().map(|contour| douglas_peucker(contour, tolerance, backend)).collect(),
+				_ => contours.par_iter().map(|contour| douglas_peucker(contour, tolerance, backend)).collect(),
+			};
+			if preserve_topology {
+				restore_crossed(contours, &mut keep, backend);
+			}
+			keep
+		}
+		SimplificationMethod::VISVALINGAM { area_budget } => {
+			let obstacles = if preserve_topology { Some(obstacles(contours, backend)) } else { None };
+			#[allow(clippy::unnecessary_cast)] //With 64-bit coordinates, the area is already an i128.
+			let doubled_budget = area_budget as i128 * 2;
+			match backend {
+				Backend::ST => contours.iter().enumerate().map(|(contour, vertices)| visvalingam(vertices, contour, doubled_budget, obstacles.as_deref(), backend)).collect(),
+				_ => contours.par_iter().enumerate().map(|(contour, vertices)| visvalingam(vertices, contour, doubled_budget, obstacles.as_deref(), backend)).collect(),
+			}
+		}
+	};
+	contours.iter().zip(keep).map(|(contour, keep)| contour.iter().zip(keep).filter(|(_, keep)| *keep).map(|(vertex, _)| *vertex).collect()).collect()
+}
+
+/// Choose which vertices of a contour to keep with Douglas-Peucker.
+///
+/// The contour is first divided into two chains: from the first vertex to the vertex furthest away
+/// from it, and back. Then in each round, every chain whose furthest vertex from the line between
+/// its endpoints is further than the tolerance is divided at that vertex. The chains of one round are
+/// processed in parallel, for the multi-threaded implementation.
+///
+/// # Arguments
+/// * `vertices` - The vertices of the contour.
+/// * `tolerance` - The maximum distance between a removed vertex and the simplified contour.
+/// * `backend` - Whether to process the chains on a single thread or on multiple threads.
+///
+/// # Result
+/// For each vertex, whether to keep it.
+fn douglas_peucker(vertices: &[Point2D], tolerance: f64, backend: Backend) -> Vec<bool> {
+	let len = vertices.len();
+	if len <= 3 {
+		return vec![true; len];
+	}
+	let furthest = (1..len).max_by_key(|index| (squared_distance(&vertices[0], &vertices[*index]), Reverse(*index))).unwrap();
+	if vertices[furthest] == vertices[0] {
+		return vec![true; len]; //All vertices are in the same place. There is no line to simplify towards.
+	}
+	let mut keep = vec![false; len];
+	keep[0] = true;
+	keep[furthest] = true;
+	let mut chains = vec![(0, furthest), (furthest, len)]; //The end of the last chain is the first vertex again.
+	while !chains.is_empty() {
+		let splits: Vec<Option<(usize, f64)>> = match backend {
+			Backend::ST => chains.iter().map(|(start, end)| furthest_from_chord(vertices, *start, *end)).collect(),
+			_ => chains.par_iter().map(|(start, end)| furthest_from_chord(vertices, *start, *end)).collect(),
+		};
+		let mut next_chains = Vec::new();
+		for ((start, end), split) in chains.into_iter().zip(splits) {
+			if let Some((index, distance)) = split && distance > tolerance {
+				keep[index] = true;
+				next_chains.push((start, index));
+				next_chains.push((index, end));
+			}
+		}
+		chains = next_chains;
+	}
+	if keep.iter().filter(|keep| **keep).count() < 3 {
+		//The contour must keep some area, so keep the vertex furthest from the line through the other two.
+		if let Some((index, _)) = furthest_from_chord(vertices, 0, furthest).into_iter().chain(furthest_from_chord(vertices, furthest, len)).max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0))) {
+			keep[index] = true;
+		}
+	}
+	keep
+}
+
+/// Find the vertex of a chain that is furthest from the line segment between its endpoints.
+///
+/// # Arguments
+/// * `vertices` - The vertices of the contour.
+/// * `start` - The index of the first vertex of the chain.
+/// * `end` - The index of the last vertex of the chain. This may be the length of the contour, to
+/// indicate the first vertex again.
+///
+/// # Result
+/// The index of the furthest vertex and its distance, or `None` if the chain has no vertices
+/// between its endpoints. Of equally far vertices, the first is chosen.
+fn furthest_from_chord(vertices: &[Point2D], start: usize, end: usize) -> Option<(usize, f64)> {
+	let chord_start = &vertices[start];
+	let chord_end = &vertices[end % vertices.len()];
+	(start + 1..end).map(|index| (index, distance_to_segment(&vertices[index], chord_start, chord_end))).max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))
+}
+
+/// Add back vertices of simplified edges that intersect other edges, until no edges intersect.
+///
+/// For each simplified edge that is involved in a crossing, the vertex of the original chain that
+/// is furthest from it is kept again. If the original contours didn't intersect, this always ends
+/// before all vertices are back.
+///
+/// # Arguments
+/// * `contours` - The vertices of each original contour.
+/// * `keep` - For each vertex of each contour, whether it is kept. This is modified in place.
+/// * `backend` - Whether to search for crossings on a single thread or on multiple threads.
+fn restore_crossed(contours: &[&[Point2D]], keep: &mut [Vec<bool>], backend: Backend) {
+	loop {
+		let kept: Vec<Vec<usize>> = keep.iter().map(|keep| (0..keep.len()).filter(|index| keep[*index]).collect()).collect();
+		let mut simplified = MultiPolygon::new();
+		let mut first_edges = Vec::with_capacity(kept.len() + 1);
+		first_edges.push(0);
+		for (contour, kept) in contours.iter().zip(&kept) {
+			simplified.push_contour(kept.iter().map(|index| contour[*index]));
+			first_edges.push(first_edges.last().unwrap() + kept.len());
+		}
+		let crossings = match backend {
+			Backend::ST => self_intersection::crossings_multipolygon_st(&simplified),
+			_ => self_intersection::crossings_multipolygon_mt(&simplified),
+		};
+		let mut changed = false;
+		for edge in crossings.iter().flat_map(|crossing| [crossing.first_edge, crossing.second_edge]) {
+			let contour = first_edges.partition_point(|first_edge| *first_edge <= edge) - 1;
+			let position = edge - first_edges[contour];
+			let start = kept[contour][position];
+			let end = if position + 1 < kept[contour].len() { kept[contour][position + 1] } else { contours[contour].len() };
+			if let Some((index, _)) = furthest_from_chord(contours[contour], start, end) && !keep[contour][index] {
+				keep[contour][index] = true;
+				changed = true;
+			}
+		}
+		if !changed {
+			return; //No crossings, or only crossings between original edges.
+		}
+	}
+}
+
+/// List the vertices of all contours as obstacles, sorted by their X coordinate.
+///
+/// # Arguments
+/// * `contours` - The vertices of each contour.
+/// * `backend` - Whether to sort on a single thread or on multiple threads.
+fn obstacles(contours: &[&[Point2D]], backend: Backend) -> Vec<Obstacle> {
+	let mut obstacles: Vec<Obstacle> = contours.iter().enumerate().flat_map(|(contour, vertices)| {
+		vertices.iter().enumerate().map(move |(index, point)| Obstacle { point: *point, contour, index })
+	}).collect();
+	let key = |obstacle: &Obstacle| (obstacle.point.x, obstacle.contour, obstacle.index);
+	match backend {
+		Backend::ST => obstacles.sort_unstable_by_key(key),
+		_ => obstacles.par_sort_unstable_by_key(key),
+	}
+	obstacles
+}
+
+/// Choose which vertices of a contour to keep with Visvalingam-Whyatt.
+///
+/// The vertex whose triangle with its neighbours has the smallest area is removed first, which
+/// changes the triangles of its neighbours. This repeats until the next removal would exceed the
+/// area budget, or only three vertices remain.
+///
+/// When preserving the topology, vertices are only removed if their triangle contains no other
+/// vertex. Vertices of other contours are considered even if they are removed from their own
+/// contour, so that the contours can be simplified independently, in parallel.
+///
+/// # Arguments
+/// * `vertices` - The vertices of the contour.
+/// * `contour` - The index of the contour in its shape.
+/// * `doubled_budget` - Twice the maximum total area of the removed triangles.
+/// * `obstacles` - The vertices of all contours of the shape, sorted by their X coordinate, if the
+/// topology must be preserved.
+/// * `backend` - Whether to compute the initial areas on a single thread or on multiple threads.
+///
+/// # Result
+/// For each vertex, whether to keep it.
+fn visvalingam(vertices: &[Point2D], contour: usize, doubled_budget: i128, obstacles: Option<&[Obstacle]>, backend: Backend) -> Vec<bool> {
+	let len = vertices.len();
+	if len <= 3 {
+		return vec![true; len];
+	}
+	let mut prev: Vec<usize> = (0..len).map(|index| (index + len - 1) % len).collect();
+	let mut next: Vec<usize> = (0..len).map(|index| (index + 1) % len).collect();
+	let doubled_area = |prev: usize, index: usize, next: usize| orientation(&vertices[prev], &vertices[index], &vertices[next]).abs();
+	let initial: Vec<Reverse<(i128, usize)>> = match backend {
+		Backend::ST => (1..len).map(|index| Reverse((doubled_area(prev[index], index, next[index]), index))).collect(),
+		_ => (1..len).into_par_iter().map(|index| Reverse((doubled_area(prev[index], index, next[index]), index))).collect(),
+	};
+	let mut queue = BinaryHeap::from(initial); //The first vertex is never removed.
+	let mut current_area: Vec<i128> = (0..len).map(|index| doubled_area(prev[index], index, next[index])).collect();
+	let mut keep = vec![true; len];
+	let mut remaining = len;
+	let mut spent = 0;
+	while let Some(Reverse((area, index))) = queue.pop() {
+		if !keep[index] || area != current_area[index] {
+			continue; //Removed already, or its area changed since.
+		}
+		if remaining <= 3 || spent + area > doubled_budget {
+			break; //All other vertices have a larger area, so they don't fit in the budget either.
+		}
+		if let Some(obstacles) = obstacles && is_blocked(vertices, contour, &keep, prev[index], index, next[index], obstacles) {
+			continue; //It may become removable when its neighbours change.
+		}
+		keep[index] = false;
+		spent += area;
+		remaining -= 1;
+		let (before, after) = (prev[index], next[index]);
+		next[before] = after;
+		prev[after] = before;
+		for neighbour in [before, after] {
+			if neighbour != 0 {
+				current_area[neighbour] = doubled_area(prev[neighbour], neighbour, next[neighbour]);
+				queue.push(Reverse((current_area[neighbour], neighbour)));
+			}
+		}
+	}
+	keep
+}
+
+/// Check whether the triangle of a vertex and its neighbours contains any other vertex.
+///
+/// # Arguments
+/// * `vertices` - The vertices of the contour.
+/// * `contour` - The index of the contour in its shape.
+/// * `keep` - Which vertices of the contour are still there.
+/// * `prev` - The index of the vertex before it.
+/// * `index` - The index of the vertex to remove.
+/// * `next` - The index of the vertex after it.
+/// * `obstacles` - The vertices of all contours of the shape, sorted by their X coordinate.
+fn is_blocked(vertices: &[Point2D], contour: usize, keep: &[bool], prev: usize, index: usize, next: usize, obstacles: &[Obstacle]) -> bool {
+	let (a, b, c) = (&vertices[prev], &vertices[index], &vertices[next]);
+	let min_x = a.x.min(b.x).min(c.x);
+	let max_x = a.x.max(b.x).max(c.x);
+	let min_y = a.y.min(b.y).min(c.y);
+	let max_y = a.y.max(b.y).max(c.y);
+	let first = obstacles.partition_point(|obstacle| obstacle.point.x < min_x);
+	obstacles[first..].iter().take_while(|obstacle| obstacle.point.x <= max_x).any(|obstacle| {
+		if obstacle.point.y < min_y || obstacle.point.y > max_y {
+			return false;
+		}
+		if obstacle.contour == contour && (!keep[obstacle.index] || obstacle.index == prev || obstacle.index == index || obstacle.index == next) {
+			return false; //Not there any more, or part of the triangle itself.
+		}
+		in_closed_triangle(a, b, c, &obstacle.point)
+	})
+}
+
+/// Check whether a point is inside a triangle or on its boundary.
+///
+/// The triangle may have any orientation, and may be degenerate.
+fn in_closed_triangle(a: &Point2D, b: &Point2D, c: &Point2D, point: &Point2D) -> bool {
+	let sides = [orientation(a, b, point), orientation(b, c, point), orientation(c, a, point)];
+	let orientation = orientation(a, b, c);
+	if orientation != 0 {
+		return sides.iter().all(|side| side.signum() != -orientation.signum());
+	}
+	//A degenerate triangle is a line segment, from its lowest to its highest corner.
+	let low = a.min(b).min(c);
+	let high = a.max(b).max(c);
+	sides[0] == 0 && sides[1] == 0 && sides[2] == 0 && low <= point && point <= high
+}
+
+/// Compute the squared distance between two points.
+fn squared_distance(a: &Point2D, b: &Point2D) -> i128 {
+	let dx = a.x as i128 - b.x as i128;
+	let dy = a.y as i128 - b.y as i128;
+	dx * dx + dy * dy
+}
+
+/// Compute the distance from a point to a line segment.
+fn distance_to_segment(point: &Point2D, start: &Point2D, end: &Point2D) -> f64 {
+	let length_squared = squared_distance(start, end);
+	if length_squared == 0 {
+		return (squared_distance(point, start) as f64).sqrt();
+	}
+	let dot = (point.x as i128 - start.x as i128) * (end.x as i128 - start.x as i128) + (point.y as i128 - start.y as i128) * (end.y as i128 - start.y as i128);
+	if dot <= 0 {
+		return (squared_distance(point, start) as f64).sqrt();
+	}
+	if dot >= length_squared {
+		return (squared_distance(point, end) as f64).sqrt();
+	}
+	orientation(start, end, point).abs() as f64 / (length_squared as f64).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+	use crate::Coordinate;
+	use crate::operations::self_intersection::{crossings_multipolygon_st, crossings_polygon_st};
+
+	/// Generate a pseudo-random thin strip that wiggles up and down.
+	///
+	/// The vertices of the top side are halfway between those of the bottom side, a few units above
+	/// the highest of the two, so the strip doesn't intersect itself. Simplifying both sides
+	/// separately easily makes them cross though.
+	fn random_strip(seed: u64, num_vertices: usize) -> Polygon {
+		let mut state = seed;
+		let mut random = move || {
+			state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+			((state >> 33) % 200) as Coordinate - 100
+		};
+		let mut bottom = vec![Point2D { x: 0, y: 0 }];
+		for index in 1..num_vertices / 2 {
+			bottom.push(Point2D { x: index as Coordinate * 10, y: bottom[index - 1].y + random() });
+		}
+		let top: Vec<Point2D> = bottom.windows(2).rev().map(|pair| Point2D { x: pair[0].x + 5, y: pair[0].y.max(pair[1].y) + 3 }).collect();
+		Polygon::from_iter(bottom.into_iter().chain(top))
+	}
+
+	/// A square with an extra vertex in the middle of every side, and one slightly off.
+	fn rough_square() -> Polygon {
+		Polygon::from_iter([
+			Point2D { x: 0, y: 0 },
+			Point2D { x: 500, y: 0 },
+			Point2D { x: 1000, y: 0 },
+			Point2D { x: 1000, y: 500 },
+			Point2D { x: 1000, y: 1000 },
+			Point2D { x: 500, y: 1003 },
+			Point2D { x: 0, y: 1000 },
+			Point2D { x: 0, y: 500 },
+		])
+	}
+
+	/// A square with a bump in the top side, and a hole that only fits inside because of the bump.
+	fn bump_with_hole() -> MultiPolygon {
+		let mut multi_polygon = MultiPolygon::new();
+		multi_polygon.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 50, y: 103 }, Point2D { x: 0, y: 100 }]);
+		multi_polygon.push_contour([Point2D { x: 45, y: 99 }, Point2D { x: 50, y: 102 }, Point2D { x: 55, y: 99 }]);
+		multi_polygon
+	}
+
+	/// Test removing collinear vertices and vertices within the tolerance.
+	#[test_case(SimplificationMethod::DOUGLAS_PEUCKER { tolerance: 0.0 }, 5 ; "Douglas-Peucker only collinear")]
+	#[test_case(SimplificationMethod::DOUGLAS_PEUCKER { tolerance: 2.9 }, 5 ; "Douglas-Peucker below the bump")]
+	#[test_case(SimplificationMethod::DOUGLAS_PEUCKER { tolerance: 3.0 }, 4 ; "Douglas-Peucker at the bump")]
+	#[test_case(SimplificationMethod::VISVALINGAM { area_budget: 0 }, 5 ; "Visvalingam only collinear")]
+	#[test_case(SimplificationMethod::VISVALINGAM { area_budget: 1499 }, 5 ; "Visvalingam below the bump")]
+	#[test_case(SimplificationMethod::VISVALINGAM { area_budget: 1500 }, 4 ; "Visvalingam at the bump")]
+	fn rough_square_tolerance(method: SimplificationMethod, expected_len: usize) {
+		let polygon = rough_square();
+		let simplified = simplify_polygon_st(&polygon, method, false);
+		assert_eq!(simplified.len(), expected_len);
+		assert_eq!(simplified.host_vertices()[0], Point2D { x: 0, y: 0 }, "The first vertex is always kept.");
+		assert!(simplified.host_vertices().iter().all(|vertex| polygon.host_vertices().contains(vertex)), "Simplification only removes vertices.");
+		assert_eq!(simplify_polygon_mt(&polygon, method, false), simplified, "The multi-threaded implementation must give the same result.");
+		assert_eq!(simplify_polygon(&polygon, method, true), simplified, "Nothing intersects, so preserving the topology makes no difference.");
+	}
+
+	/// Test that contours never get fewer than three vertices, and small contours are not changed.
+	#[test_case(SimplificationMethod::DOUGLAS_PEUCKER { tolerance: 1e9 } ; "Douglas-Peucker")]
+	#[test_case(SimplificationMethod::VISVALINGAM { area_budget: Area::MAX / 4 } ; "Visvalingam")]
+	fn minimum_vertices(method: SimplificationMethod) {
+		assert_eq!(simplify_polygon_st(&rough_square(), method, false).len(), 3);
+		assert_eq!(simplify_polygon_mt(&rough_square(), method, false).len(), 3);
+		let triangle = crate::test::data::polygon::triangle_1000();
+		assert_eq!(simplify_polygon_st(&triangle, method, false), triangle);
+		assert_eq!(simplify_polygon_st(&Polygon::new(), method, false), Polygon::new());
+		let line = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }]);
+		assert_eq!(simplify_polygon_st(&line, method, false), line);
+	}
+
+	/// Test that a polygon whose vertices are all in the same place is kept.
+	#[test]
+	fn coincident() {
+		let polygon = Polygon::from_iter([Point2D { x: 5, y: 5 }; 6]);
+		assert_eq!(simplify_polygon_st(&polygon, SimplificationMethod::DOUGLAS_PEUCKER { tolerance: 1.0 }, false).len(), 6);
+	}
+
+	/// Test that contours are kept from intersecting each other, if requested.
+	#[test_case(SimplificationMethod::DOUGLAS_PEUCKER { tolerance: 5.0 } ; "Douglas-Peucker")]
+	#[test_case(SimplificationMethod::VISVALINGAM { area_budget: 200 } ; "Visvalingam")]
+	fn preserve_topology_hole(method: SimplificationMethod) {
+		let multi_polygon = bump_with_hole();
+		let naive = simplify_multipolygon_st(&multi_polygon, method, false);
+		assert_eq!(naive.contour(0).len(), 4, "Without preserving the topology, the bump is removed.");
+		assert!(!crossings_multipolygon_st(&naive).is_empty(), "Without the bump, the hole sticks out of the outer contour.");
+		let preserved = simplify_multipolygon_st(&multi_polygon, method, true);
+		assert_eq!(preserved.contour(0).len(), 5, "The bump must be kept to keep the hole inside.");
+		assert_eq!(crossings_multipolygon_st(&preserved), Vec::new());
+		let expected: Vec<&[Point2D]> = preserved.contours().collect();
+		assert_eq!(simplify_multipolygon_mt(&multi_polygon, method, true).contours().collect::<Vec<_>>(), expected);
+		assert_eq!(simplify_multipolygon(&multi_polygon, method, true).contours().collect::<Vec<_>>(), expected);
+	}
+
+	/// Test that random polygons that don't intersect themselves never do after simplification, if the
+	/// topology is preserved.
+	#[test_case(SimplificationMethod::DOUGLAS_PEUCKER { tolerance: 30.0 } ; "Douglas-Peucker")]
+	#[test_case(SimplificationMethod::VISVALINGAM { area_budget: 3000 } ; "Visvalingam")]
+	fn preserve_topology_random(method: SimplificationMethod) {
+		let mut num_naive_crossings = 0;
+		for seed in 0..100 {
+			let polygon = random_strip(seed, 40);
+			assert_eq!(crossings_polygon_st(&polygon), Vec::new(), "The input of seed {seed} must not intersect itself.");
+			num_naive_crossings += crossings_polygon_st(&simplify_polygon_st(&polygon, method, false)).len();
+			let preserved = simplify_polygon_st(&polygon, method, true);
+			assert_eq!(crossings_polygon_st(&preserved), Vec::new(), "Seed {seed} must not intersect itself after simplification.");
+			assert!(preserved.len() < polygon.len(), "Seed {seed} must still be simplified.");
+			assert_eq!(simplify_polygon_mt(&polygon, method, true), preserved, "The multi-threaded implementation must give the same result for seed {seed}.");
+		}
+		assert!(num_naive_crossings > 0, "Some of the polygons must intersect themselves if the topology is not preserved, or this test tests nothing.");
+	}
+
+	/// Test simplifying many polygons at once.
+	#[test]
+	fn polygons() {
+		let polygons: Vec<Polygon> = (0..20).map(|seed| random_strip(seed, 30)).collect();
+		let method = SimplificationMethod::DOUGLAS_PEUCKER { tolerance: 50.0 };
+		let expected: Vec<Polygon> = polygons.iter().map(|polygon| simplify_polygon_st(polygon, method, true)).collect();
+		assert_eq!(simplify_polygons_st(&polygons, method, true), expected);
+		assert_eq!(simplify_polygons_mt(&polygons, method, true), expected);
+		assert_eq!(simplify_polygons(&polygons, method, true), expected);
+		assert_eq!(simplify_polygons(&[], method, true), Vec::new());
+	}
+
+	/// Test simplifying the polygons of a batch, which are simplified separately.
+	#[test]
+	fn batch_polygons() {
+		let polygons: Vec<Polygon> = (0..20).map(|seed| random_strip(seed, 30)).chain([Polygon::new()]).collect();
+		let batch = PolygonBatch::from_iter(polygons.iter().map(|polygon| Polygon::from_iter(polygon.host_vertices().iter().copied())));
+		let method = SimplificationMethod::DOUGLAS_PEUCKER { tolerance: 50.0 };
+		let expected: Vec<Polygon> = polygons.iter().map(|polygon| simplify_polygon_st(polygon, method, true)).collect();
+		for simplified in [simplify_batch_st(&batch, method, true), simplify_batch_mt(&batch, method, true), simplify_batch(&batch, method, true)] {
+			assert_eq!(simplified.num_polygons(), expected.len());
+			for (result, expected) in simplified.polygons().zip(&expected) {
+				assert_eq!(result, expected.host_vertices());
+			}
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,666 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! This module contains the implementations of operations to rotate, scale, mirror or otherwise
+//! transform geometric objects with an affine transformation.
+//!
+//! There are implementations for the host, single-threaded and multi-threaded, and for the GPU.
+//! All of them compute and round the coordinates the same way, as described in `AffineTransform`.
+//! Only the GPU may round differently in rare cases, where it fuses a multiplication and an
+//! addition. If any coordinate goes out of range, they return an `OverflowError` and leave the
+//! object unchanged.
+
+use cubecl::prelude::*; //For the GPU implementation.
+use rayon::prelude::*; //For multi-threaded implementations.
+
+#[cfg(feature = "gpu")]
+use cubecl::server::Handle; //To transform vertices on the GPU.
+
+use crate::AffineTransform; //The transformation to apply.
+use crate::Coordinate; //The vertices are stored as coordinates on the GPU.
+use crate::MultiPolygon; //Transform multi-polygons.
+use crate::OverflowError; //If a transformed coordinate is out of range.
+use crate::Point2D; //To transform lists of vertices.
+use crate::Polygon; //Transform polygons.
+use crate::PolygonBatch; //Transform batches of polygons.
+use crate::cost_model::{self, Backend, Operation}; //To choose the fastest implementation.
+
+/// Transform a polygon with an affine transformation.
+///
+/// This chooses automatically whether to transform the polygon on a single thread, on multiple
+/// threads or on the GPU. The cost model estimates which is fastest, from the number of vertices in
+/// the polygon and from where the most up-to-date copy of the vertices is.
+///
+/// # Arguments
+/// * `polygon` - The polygon to transform.
+/// * `transform` - The transformation to apply to every vertex.
+///
+/// # Result
+/// An error if any transformed coordinate would be out of range. The polygon is then unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{AffineTransform, Point2D, Polygon};
+/// let mut poly = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// apex::operations::transform::transform_polygon(&mut poly, &AffineTransform::rotation(&Point2D { x: 0, y: 0 }, std::f64::consts::PI)).unwrap();
+/// assert_eq!(*poly.vertex(1), Point2D { x: -100, y: 0 });
+/// assert_eq!(*poly.vertex(2), Point2D { x: 0, y: -100 });
+/// ```
+pub fn transform_polygon(polygon: &mut Polygon, transform: &AffineTransform) -> Result<(), OverflowError> {
+	match cost_model::choose(Operation::TRANSFORM, polygon.len(), polygon.sync_status()) {
+		Backend::ST => transform_polygon_st(polygon, transform),
+		Backend::MT => transform_polygon_mt(polygon, transform),
+		#[cfg(feature = "gpu")]
+		Backend::GPU => transform_polygon_gpu(polygon, transform),
+		#[cfg(not(feature = "gpu"))]
+		Backend::GPU => unreachable!("Without a GPU runtime, the cost model never chooses the GPU."),
+	}
+}
+
+/// Transform a polygon with an affine transformation, on a single thread.
+///
+/// # Arguments
+/// * `polygon` - The polygon to transform.
+/// * `transform` - The transformation to apply to every vertex.
+///
+/// # Result
+/// An error if any transformed coordinate would be out of range. The polygon is then unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{AffineTransform, OverflowError, Point2D, Polygon};
+/// let mut poly = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// apex::operations::transform::transform_polygon_st(&mut poly, &AffineTransform::scaling(&Point2D { x: 0, y: 0 }, 2.0, 3.0)).unwrap();
+/// assert_eq!(*poly.vertex(2), Point2D { x: 0, y: 300 });
+/// let too_far = AffineTransform::scaling(&Point2D { x: 0, y: 0 }, 1e30, 1e30);
+/// assert_eq!(apex::operations::transform::transform_polygon_st(&mut poly, &too_far), Err(OverflowError));
+/// assert_eq!(*poly.vertex(2), Point2D { x: 0, y: 300 }, "The polygon is unchanged.");
+/// ```
+pub fn transform_polygon_st(polygon: &mut Polygon, transform: &AffineTransform) -> Result<(), OverflowError> {
+	transform_vertices_st(polygon.host_vertices_mut(), transform)
+}
+
+/// Transform a polygon with an affine transformation, on multiple threads.
+///
+/// # Arguments
+/// * `polygon` - The polygon to transform.
+/// * `transform` - The transformation to apply to every vertex.
+///
+/// # Result
+/// An error if any transformed coordinate would be out of range. The polygon is then unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{AffineTransform, Point2D, Polygon};
+/// let mut poly = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// apex::operations::transform::transform_polygon_mt(&mut poly, &AffineTransform::mirror(&Point2D { x: 0, y: 0 }, &Point2D { x: 1, y: 1 })).unwrap();
+/// assert_eq!(*poly.vertex(1), Point2D { x: 0, y: 100 });
+/// ```
+pub fn transform_polygon_mt(polygon: &mut Polygon, transform: &AffineTransform) -> Result<(), OverflowError> {
+	transform_vertices_mt(polygon.host_vertices_mut(), transform)
+}
+
+/// Transform a polygon with an affine transformation, on the GPU.
+///
+/// Each vertex is transformed in a separate work unit. If the vertices are not on the GPU yet,
+/// they are copied there first. Afterwards, the copy on the GPU is leading.
+///
+/// # Arguments
+/// * `polygon` - The polygon to transform.
+/// * `transform` - The transformation to apply to every vertex.
+///
+/// # Result
+/// An error if any transformed coordinate would be out of range. The polygon is then unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{AffineTransform, Point2D, Polygon};
+/// let mut poly = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// apex::operations::transform::transform_polygon_gpu(&mut poly, &AffineTransform::scaling(&Point2D { x: 0, y: 0 }, -1.0, 1.0)).unwrap();
+/// assert_eq!(*poly.vertex(1), Point2D { x: -100, y: 0 });
+/// ```
+#[cfg(feature = "gpu")]
+pub fn transform_polygon_gpu(polygon: &mut Polygon, transform: &AffineTransform) -> Result<(), OverflowError> {
+	use crate::detail::gpu;
+
+	if !gpu::supports_coordinates() || !gpu::supports_float64() {
+		return transform_polygon(polygon, transform);
+	}
+	transform_vertices_gpu(polygon.gpu_vertices_mut(), transform)
+}
+
+/// Transform all contours of a multi-polygon with an affine transformation.
+///
+/// This chooses automatically whether to transform the multi-polygon on a single thread, on
+/// multiple threads or on the GPU, in the same way as `transform_polygon`.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to transform.
+/// * `transform` - The transformation to apply to every vertex.
+///
+/// # Result
+/// An error if any transformed coordinate would be out of range. The multi-polygon is then
+/// unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{AffineTransform, MultiPolygon, Point2D};
+/// let mut multi = MultiPolygon::new();
+/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 67, y: 100 }]);
+/// multi.push_contour([Point2D { x: 200, y: 0 }, Point2D { x: 300, y: 0 }, Point2D { x: 267, y: 100 }]);
+/// apex::operations::transform::transform_multipolygon(&mut multi, &AffineTransform::scaling(&Point2D { x: 0, y: 0 }, 0.5, 0.5)).unwrap();
+/// assert_eq!(multi.contour(1)[2], Point2D { x: 134, y: 50 });
+/// ```
+pub fn transform_multipolygon(multi_polygon: &mut MultiPolygon, transform: &AffineTransform) -> Result<(), OverflowError> {
+	match cost_model::choose(Operation::TRANSFORM, multi_polygon.len(), multi_polygon.sync_status()) {
+		Backend::ST => transform_multipolygon_st(multi_polygon, transform),
+		Backend::MT => transform_multipolygon_mt(multi_polygon, transform),
+		#[cfg(feature = "gpu")]
+		Backend::GPU => transform_multipolygon_gpu(multi_polygon, transform),
+		#[cfg(not(feature = "gpu"))]
+		Backend::GPU => unreachable!("Without a GPU runtime, the cost model never chooses the GPU."),
+	}
+}
+
+/// Transform all contours of a multi-polygon with an affine transformation, on a single thread.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to transform.
+/// * `transform` - The transformation to apply to every vertex.
+///
+/// # Result
+/// An error if any transformed coordinate would be out of range. The multi-polygon is then
+/// unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{AffineTransform, MultiPolygon, Point2D};
+/// let mut multi = MultiPolygon::new();
+/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 67, y: 100 }]);
+/// apex::operations::transform::transform_multipolygon_st(&mut multi, &AffineTransform::scaling(&Point2D { x: 0, y: 0 }, 0.5, 0.5)).unwrap();
+/// assert_eq!(multi.contour(0)[2], Point2D { x: 34, y: 50 });
+/// ```
+pub fn transform_multipolygon_st(multi_polygon: &mut MultiPolygon, transform: &AffineTransform) -> Result<(), OverflowError> {
+	transform_vertices_st(multi_polygon.host_vertices_mut(), transform)
+}
+
+/// Transform all contours of a multi-polygon with an affine transformation, on multiple threads.
+///
+/// The vertices of all contours are divided over the threads together, regardless of which
+/// contour they belong to.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to transform.
+/// * `transform` - The transformation to apply to every vertex.
+///
+/// # Result
+/// An error if any transformed coordinate would be out of range. The multi-polygon is then
+/// unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{AffineTransform, MultiPolygon, Point2D};
+/// let mut multi = MultiPolygon::new();
+/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 67, y: 100 }]);
+/// apex::operations::transform::transform_multipolygon_mt(&mut multi, &AffineTransform::scaling(&Point2D { x: 0, y: 0 }, 0.5, 0.5)).unwrap();
+/// assert_eq!(multi.contour(0)[2], Point2D { x: 34, y: 50 });
+/// ```
+pub fn transform_multipolygon_mt(multi_polygon: &mut MultiPolygon, transform: &AffineTransform) -> Result<(), OverflowError> {
+	transform_vertices_mt(multi_polygon.host_vertices_mut(), transform)
+}
+
+/// Transform all contours of a multi-polygon with an affine transformation, on the GPU.
+///
+/// The vertices of all contours are transformed in a single kernel launch. Afterwards, the copy on
+/// the GPU is leading.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to transform.
+/// * `transform` - The transformation to apply to every vertex.
+///
+/// # Result
+/// An error if any transformed coordinate would be out of range. The multi-polygon is then
+/// unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{AffineTransform, MultiPolygon, Point2D};
+/// let mut multi = MultiPolygon::new();
+/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 67, y: 100 }]);
+/// apex::operations::transform::transform_multipolygon_gpu(&mut multi, &AffineTransform::scaling(&Point2D { x: 0, y: 0 }, 0.5, 0.5)).unwrap();
+/// assert_eq!(multi.contour(0)[2], Point2D { x: 34, y: 50 });
+/// ```
+#[cfg(feature = "gpu")]
+pub fn transform_multipolygon_gpu(multi_polygon: &mut MultiPolygon, transform: &AffineTransform) -> Result<(), OverflowError> {
+	use crate::detail::gpu;
+
+	if !gpu::supports_coordinates() || !gpu::supports_float64() {
+		return transform_multipolygon(multi_polygon, transform);
+	}
+	transform_vertices_gpu(multi_polygon.gpu_vertices_mut(), transform)
+}
+
+/// Transform all polygons in a batch with an affine transformation.
+///
+/// This chooses automatically whether to transform the batch on a single thread, on multiple
+/// threads or on the GPU, in the same way as `transform_polygon`. The vertices of all polygons are
+/// transformed together.
+///
+/// # Arguments
+/// * `batch` - The batch of polygons to transform.
+/// * `transform` - The transformation to apply to every vertex.
+///
+/// # Result
+/// An error if any transformed coordinate would be out of range. All polygons in the batch are then
+/// unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{AffineTransform, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 67, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 200, y: 0 }, Point2D { x: 300, y: 0 }, Point2D { x: 267, y: 100 }]);
+/// apex::operations::transform::transform_batch(&mut batch, &AffineTransform::scaling(&Point2D { x: 0, y: 0 }, 0.5, 0.5)).unwrap();
+/// assert_eq!(batch.polygon(1)[2], Point2D { x: 134, y: 50 });
+/// ```
+pub fn transform_batch(batch: &mut PolygonBatch, transform: &AffineTransform) -> Result<(), OverflowError> {
+	match cost_model::choose(Operation::TRANSFORM, batch.len(), batch.sync_status()) {
+		Backend::ST => transform_batch_st(batch, transform),
+		Backend::MT => transform_batch_mt(batch, transform),
+		#[cfg(feature = "gpu")]
+		Backend::GPU => transform_batch_gpu(batch, transform),
+		#[cfg(not(feature = "gpu"))]
+		Backend::GPU => unreachable!("Without a GPU runtime, the cost model never chooses the GPU."),
+	}
+}
+
+/// Transform all polygons in a batch with an affine transformation, on a single thread.
+///
+/// # Arguments
+/// * `batch` - The batch of polygons to transform.
+/// * `transform` - The transformation to apply to every vertex.
+///
+/// # Result
+/// An error if any transformed coordinate would be out of range. All polygons in the batch are then
+/// unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{AffineTransform, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 67, y: 100 }]);
+/// apex::operations::transform::transform_batch_st(&mut batch, &AffineTransform::scaling(&Point2D { x: 0, y: 0 }, 0.5, 0.5)).unwrap();
+/// assert_eq!(batch.polygon(0)[2], Point2D { x: 34, y: 50 });
+/// ```
+pub fn transform_batch_st(batch: &mut PolygonBatch, transform: &AffineTransform) -> Result<(), OverflowError> {
+	transform_vertices_st(batch.host_vertices_mut(), transform)
+}
+
+/// Transform all polygons in a batch with an affine transformation, on multiple threads.
+///
+/// The vertices of all polygons are divided over the threads together, in a single pass,
+/// regardless of which polygon they belong to.
+///
+/// # Arguments
+/// * `batch` - The batch of polygons to transform.
+/// * `transform` - The transformation to apply to every vertex.
+///
+/// # Result
+/// An error if any transformed coordinate would be out of range. All polygons in the batch are then
+/// unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{AffineTransform, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 67, y: 100 }]);
+/// apex::operations::transform::transform_batch_mt(&mut batch, &AffineTransform::scaling(&Point2D { x: 0, y: 0 }, 0.5, 0.5)).unwrap();
+/// assert_eq!(batch.polygon(0)[2], Point2D { x: 34, y: 50 });
+/// ```
+pub fn transform_batch_mt(batch: &mut PolygonBatch, transform: &AffineTransform) -> Result<(), OverflowError> {
+	transform_vertices_mt(batch.host_vertices_mut(), transform)
+}
+
+/// Transform all polygons in a batch with an affine transformation, on the GPU.
+///
+/// The vertices of all polygons are transformed in a single kernel launch. Afterwards, the copy on
+/// the GPU is leading.
+///
+/// # Arguments
+/// * `batch` - The batch of polygons to transform.
+/// * `transform` - The transformation to apply to every vertex.
+///
+/// # Result
+/// An error if any transformed coordinate would be out of range. All polygons in the batch are then
+/// unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{AffineTransform, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 67, y: 100 }]);
+/// apex::operations::transform::transform_batch_gpu(&mut batch, &AffineTransform::scaling(&Point2D { x: 0, y: 0 }, 0.5, 0.5)).unwrap();
+/// assert_eq!(batch.polygon(0)[2], Point2D { x: 34, y: 50 });
+/// ```
+#[cfg(feature = "gpu")]
+pub fn transform_batch_gpu(batch: &mut PolygonBatch, transform: &AffineTransform) -> Result<(), OverflowError> {
+	use crate::detail::gpu;
+
+	if !gpu::supports_coordinates() || !gpu::supports_float64() {
+		return transform_batch(batch, transform);
+	}
+	transform_vertices_gpu(batch.gpu_vertices_mut(), transform)
+}
+
+/// Transform a list of vertices on the host, on a single thread.
+///
+/// The transformed vertices are computed in a separate list first, so that the original vertices
+/// stay unchanged if any of them overflows.
+///
+/// # Arguments
+/// * `vertices` - The vertices to transform.
+/// * `transform` - The transformation to apply to every vertex.
+fn transform_vertices_st(vertices: &mut [Point2D], transform: &AffineTransform) -> Result<(), OverflowError> {
+	let transformed = vertices.iter().map(|vertex| transform.apply(vertex)).collect::<Result<Vec<Point2D>, OverflowError>>()?;
+	vertices.copy_from_slice(&transformed);
+	Ok(())
+}
+
+/// Transform a list of vertices on the host, on multiple threads.
+///
+/// # Arguments
+/// * `vertices` - The vertices to transform.
+/// * `transform` - The transformation to apply to every vertex.
+fn transform_vertices_mt(vertices: &mut [Point2D], transform: &AffineTransform) -> Result<(), OverflowError> {
+	let chunk_size = cost_model::chunk_size(Operation::TRANSFORM, vertices.len());
+	let mut transformed = vertices.to_vec();
+	transformed.par_chunks_mut(chunk_size).try_for_each(
+		|slice| slice.iter_mut().try_for_each(
+			|vertex| {
+				*vertex = transform.apply(vertex)?;
+				Ok(())
+			}
+		)
+	)?;
+	vertices.copy_from_slice(&transformed);
+	Ok(())
+}
+
+/// Transform a list of vertices on the GPU.
+///
+/// The kernel writes the transformed vertices to a new buffer, and raises a flag if any of them
+/// overflows. Only if none did, the new buffer replaces the original one.
+///
+/// # Arguments
+/// * `coordinates_handle` - The vertices to transform, as a flat array of coordinates on the GPU
+/// with the X and Y coordinates interleaved.
+/// * `transform` - The transformation to apply to every vertex.
+#[cfg(feature = "gpu")]
+fn transform_vertices_gpu(coordinates_handle: &mut Handle, transform: &AffineTransform) -> Result<(), OverflowError> {
+	use crate::detail::gpu;
+
+	let num_vertices = coordinates_handle.size() as usize / (2 * size_of::<Coordinate>());
+	if num_vertices == 0 {
+		return Ok(()); //Can't launch a kernel without any work. There is nothing to transform.
+	}
+	let client = gpu::client();
+	let transformed_handle = client.empty(num_vertices * 2 * size_of::<Coordinate>());
+	let overflow_handle = client.create(u32::as_bytes(&[0]));
+	let (cube_count, cube_dim) = gpu::launch_dimensions(num_vertices);
+	unsafe {
+		transform_vertices_kernel::launch::<gpu::GpuRuntime>(
+			&client,
+			cube_count,
+			cube_dim,
+			ArrayArg::from_raw_parts::<Coordinate>(coordinates_handle, num_vertices * 2, 1),
+			ArrayArg::from_raw_parts::<Coordinate>(&transformed_handle, num_vertices * 2, 1),
+			ArrayArg::from_raw_parts::<u32>(&overflow_handle, 1, 1),
+			ScalarArg::new(transform.xx),
+			ScalarArg::new(transform.xy),
+			ScalarArg::new(transform.dx),
+			ScalarArg::new(transform.yx),
+			ScalarArg::new(transform.yy),
+			ScalarArg::new(transform.dy),
+		);
+	}
+	if u32::from_bytes(&client.read_one(overflow_handle))[0] != 0 {
+		return Err(OverflowError);
+	}
+	*coordinates_handle = transformed_handle;
+	Ok(())
+}
+
+/// GPU kernel to transform every vertex in a list with an affine transformation.
+///
+/// Every work unit transforms one vertex. The coordinates are computed in the same order and rounded
+/// in the same way as `AffineTransform::apply`, but the compiler of the GPU may fuse multiplications
+/// and additions, as described in `AffineTransform`.
+///
+/// # Arguments
+/// * `coordinates` - The vertices to transform, with the X and Y coordinates interleaved.
+/// * `transformed` - The output, the transformed vertices in the same layout.
+/// * `overflow` - A single flag, which is set to 1 if any coordinate is out of range.
+/// * `xx`, `xy`, `dx`, `yx`, `yy`, `dy` - The matrix of the transformation.
+#[cube(launch)]
+#[allow(clippy::too_many_arguments)] //The matrix of the transformation is passed as separate scalars.
+fn transform_vertices_kernel(coordinates: &Array<Coordinate>, transformed: &mut Array<Coordinate>, overflow: &mut Array<u32>, xx: f64, xy: f64, dx: f64, yx: f64, yy: f64, dy: f64) {
+	let num_vertices = coordinates.len() / 2;
+	if ABSOLUTE_POS < num_vertices {
+		let x = f64::cast_from(coordinates[ABSOLUTE_POS * 2]);
+		let y = f64::cast_from(coordinates[ABSOLUTE_POS * 2 + 1]);
+		let new_x = f64::floor(xx * x + xy * y + dx + 0.5);
+		let new_y = f64::floor(yx * x + yy * y + dy + 0.5);
+		let min = f64::cast_from(Coordinate::MIN);
+		let max = f64::cast_from(Coordinate::MAX);
+		if new_x >= min && new_x <= max && new_y >= min && new_y <= max { //Also false for NaN.
+			transformed[ABSOLUTE_POS * 2] = Coordinate::cast_from(new_x);
+			transformed[ABSOLUTE_POS * 2 + 1] = Coordinate::cast_from(new_y);
+		} else {
+			overflow[0] = 1;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TwoDimensional;
+	use crate::detail::sync_status::SyncStatus;
+	use test_case::test_case;
+
+	/// The signature of the functions that transform polygons.
+	type PolygonTransformer = fn(&mut Polygon, &AffineTransform) -> Result<(), OverflowError>;
+
+	/// The signature of the functions that transform multi-polygons.
+	type MultiPolygonTransformer = fn(&mut MultiPolygon, &AffineTransform) -> Result<(), OverflowError>;
+
+	/// The signature of the functions that transform batches of polygons.
+	type BatchTransformer = fn(&mut PolygonBatch, &AffineTransform) -> Result<(), OverflowError>;
+
+	/// The transformations to test with.
+	fn transforms() -> [AffineTransform; 4] {
+		let pivot = Point2D { x: 300, y: -200 };
+		[
+			AffineTransform::rotation(&pivot, 0.3),
+			AffineTransform::scaling(&pivot, 1.5, -0.25),
+			AffineTransform::mirror(&pivot, &Point2D { x: 1000, y: 700 }),
+			AffineTransform { xx: 0.5, xy: 2.0, dx: 13.5, yx: -1.0, yy: 0.75, dy: -7.25 },
+		]
+	}
+
+	/// Test that all implementations transform every vertex like `AffineTransform::apply`.
+	#[test_case(0 ; "rotation")]
+	#[test_case(1 ; "scaling")]
+	#[test_case(2 ; "mirror")]
+	#[test_case(3 ; "general")]
+	fn transform_polygon_matches_apply(index: usize) {
+		let transform = transforms()[index];
+		let original = crate::test::data::polygon::pentagram_1000();
+		let expected: Vec<Point2D> = original.host_vertices().iter().map(|vertex| transform.apply(vertex).unwrap()).collect();
+		let transformers: [PolygonTransformer; 3] = [transform_polygon, transform_polygon_st, transform_polygon_mt];
+		for transformer in transformers {
+			let mut poly = crate::test::data::polygon::pentagram_1000();
+			assert_eq!(transformer(&mut poly, &transform), Ok(()));
+			assert_eq!(*poly.host_vertices(), expected);
+		}
+	}
+
+	/// Test that large polygons are transformed the same way on multiple threads.
+	#[test]
+	fn transform_polygon_large() {
+		let transform = transforms()[3];
+		let mut st = Polygon::from_iter((0..100000).map(|i| Point2D { x: i, y: -3 * i }));
+		let mut mt = Polygon::from_iter((0..100000).map(|i| Point2D { x: i, y: -3 * i }));
+		transform_polygon_st(&mut st, &transform).unwrap();
+		transform_polygon_mt(&mut mt, &transform).unwrap();
+		assert_eq!(st, mt);
+	}
+
+	/// Test transforming an empty polygon.
+	///
+	/// This mainly just tests that it won't panic on that.
+	#[test]
+	fn transform_polygon_empty() {
+		let mut poly = Polygon::new();
+		assert_eq!(transform_polygon_st(&mut poly, &transforms()[0]), Ok(()));
+		assert_eq!(transform_polygon_mt(&mut poly, &transforms()[0]), Ok(()));
+		assert_eq!(poly.len(), 0, "The polygon must still be unchanged.");
+	}
+
+	/// Test that the polygon is unchanged if only one of its vertices overflows.
+	#[test_case(10 ; "small")]
+	#[test_case(100000 ; "large")]
+	fn transform_polygon_overflow(num_vertices: Coordinate) {
+		let original = Polygon::from_iter((0..num_vertices).map(|i| Point2D { x: i, y: 0 }).chain([Point2D { x: 0, y: Coordinate::MAX - 5 }]));
+		let upwards = AffineTransform { dy: 10.0, ..AffineTransform::identity() };
+		let transformers: [PolygonTransformer; 3] = [transform_polygon, transform_polygon_st, transform_polygon_mt];
+		for transformer in transformers {
+			let mut poly = Polygon::from_iter(original.iter().copied());
+			assert_eq!(transformer(&mut poly, &upwards), Err(OverflowError));
+			assert_eq!(poly, original, "The polygon must be unchanged after an overflow.");
+		}
+	}
+
+	/// Test the convenience functions of `TwoDimensional` on a polygon.
+	#[test]
+	fn two_dimensional() {
+		let mut poly = crate::test::data::polygon::square_1000();
+		let centre = Point2D { x: 500, y: 500 };
+		poly.rotate(&centre, std::f64::consts::FRAC_PI_2).unwrap();
+		assert_eq!(poly.host_vertices().len(), 4);
+		assert_eq!(*poly.vertex(0), Point2D { x: 1000, y: 0 }, "The corners of the square move to the next corner.");
+		poly.scale(&centre, 0.5).unwrap();
+		assert_eq!(*poly.vertex(0), Point2D { x: 750, y: 250 });
+		poly.scale_non_uniform(&centre, 2.0, 1.0).unwrap();
+		assert_eq!(*poly.vertex(0), Point2D { x: 1000, y: 250 });
+		poly.mirror(&centre, &Point2D { x: 500, y: 0 }).unwrap();
+		assert_eq!(*poly.vertex(0), Point2D { x: 0, y: 250 });
+		assert_eq!(poly.scale(&centre, 1e20), Err(OverflowError));
+		assert_eq!(*poly.vertex(0), Point2D { x: 0, y: 250 }, "The polygon must be unchanged after an overflow.");
+	}
+
+	/// Test transforming all contours of a multi-polygon.
+	#[test_case(0 ; "rotation")]
+	#[test_case(3 ; "general")]
+	fn transform_multipolygon_matches_apply(index: usize) {
+		let transform = transforms()[index];
+		let original = crate::test::data::multi_polygon::square_with_hole_1000();
+		let transformers: [MultiPolygonTransformer; 3] = [transform_multipolygon, transform_multipolygon_st, transform_multipolygon_mt];
+		for transformer in transformers {
+			let mut multi = crate::test::data::multi_polygon::square_with_hole_1000();
+			assert_eq!(transformer(&mut multi, &transform), Ok(()));
+			assert_eq!(multi.num_contours(), original.num_contours(), "Transforming doesn't change the contours.");
+			for contour in 0..multi.num_contours() {
+				for i in 0..multi.contour(contour).len() {
+					assert_eq!(multi.contour(contour)[i], transform.apply(&original.contour(contour)[i]).unwrap());
+				}
+			}
+		}
+	}
+
+	/// Test that small polygons on the host are not copied to the GPU to transform them.
+	#[test]
+	fn transform_polygon_dispatch_stays_on_host() {
+		let mut poly = crate::test::data::polygon::square_1000();
+		transform_polygon(&mut poly, &transforms()[0]).unwrap();
+		assert_eq!(poly.sync_status(), SyncStatus::HOST, "Copying the vertices to the GPU is not worth it for a small polygon.");
+	}
+
+	/// Test transforming a polygon on the GPU gives the same result as on the host.
+	#[cfg(all(feature = "gpu", not(feature = "coordinate64")))] //With 64-bit coordinates, the vertices are transformed on the host.
+	#[test_case(0 ; "rotation")]
+	#[test_case(1 ; "scaling")]
+	#[test_case(2 ; "mirror")]
+	#[test_case(3 ; "general")]
+	fn transform_polygon_gpu_matches_host(index: usize) {
+		let transform = transforms()[index];
+		let mut host = crate::test::data::polygon::pentagram_1000();
+		let mut gpu = crate::test::data::polygon::pentagram_1000();
+		transform_polygon_st(&mut host, &transform).unwrap();
+		assert_eq!(transform_polygon_gpu(&mut gpu, &transform), Ok(()));
+		assert_eq!(gpu.sync_status(), SyncStatus::GPU, "The transformed vertices are only on the GPU until they are needed on the host.");
+		assert_eq!(gpu, host);
+	}
+
+	/// Test that the polygon is unchanged if a vertex overflows on the GPU.
+	#[cfg(feature = "gpu")]
+	#[test]
+	fn transform_polygon_gpu_overflow() {
+		let original = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 0, y: Coordinate::MAX - 5 }]);
+		let mut poly = Polygon::from_iter(original.iter().copied());
+		assert_eq!(transform_polygon_gpu(&mut poly, &AffineTransform { dy: 10.0, ..AffineTransform::identity() }), Err(OverflowError));
+		assert_eq!(poly, original, "The polygon must be unchanged after an overflow.");
+	}
+
+	/// Test transforming all contours of a multi-polygon on the GPU.
+	#[cfg(feature = "gpu")]
+	#[test]
+	fn transform_multipolygon_gpu_matches_host() {
+		let transform = transforms()[3];
+		let mut host = crate::test::data::multi_polygon::two_squares_1000();
+		let mut gpu = crate::test::data::multi_polygon::two_squares_1000();
+		transform_multipolygon_st(&mut host, &transform).unwrap();
+		assert_eq!(transform_multipolygon_gpu(&mut gpu, &transform), Ok(()));
+		for contour in 0..host.num_contours() {
+			assert_eq!(gpu.contour(contour), host.contour(contour));
+		}
+	}
+
+	/// Test transforming all polygons of a batch.
+	#[test_case(0 ; "rotation")]
+	#[test_case(3 ; "general")]
+	fn transform_batch_matches_apply(index: usize) {
+		let transform = transforms()[index];
+		let original = PolygonBatch::from_iter([crate::test::data::polygon::pentagram_1000(), Polygon::new(), crate::test::data::polygon::triangle_1000()]);
+		let transformers: [BatchTransformer; 3] = [transform_batch, transform_batch_st, transform_batch_mt];
+		for transformer in transformers {
+			let mut batch = PolygonBatch::from_iter([crate::test::data::polygon::pentagram_1000(), Polygon::new(), crate::test::data::polygon::triangle_1000()]);
+			assert_eq!(transformer(&mut batch, &transform), Ok(()));
+			assert_eq!(batch.num_polygons(), original.num_polygons(), "Transforming doesn't change the polygons.");
+			for polygon in 0..batch.num_polygons() {
+				for i in 0..batch.polygon(polygon).len() {
+					assert_eq!(batch.polygon(polygon)[i], transform.apply(&original.polygon(polygon)[i]).unwrap());
+				}
+			}
+		}
+	}
+
+	/// Test transforming all polygons of a batch on the GPU.
+	#[cfg(feature = "gpu")]
+	#[test]
+	fn transform_batch_gpu_matches_host() {
+		let transform = transforms()[3];
+		let mut host = PolygonBatch::from_iter([crate::test::data::polygon::square_1000(), crate::test::data::polygon::triangle_1000()]);
+		let mut gpu = PolygonBatch::from_iter([crate::test::data::polygon::square_1000(), crate::test::data::polygon::triangle_1000()]);
+		transform_batch_st(&mut host, &transform).unwrap();
+		assert_eq!(transform_batch_gpu(&mut gpu, &transform), Ok(()));
+		for polygon in 0..host.num_polygons() {
+			assert_eq!(gpu.polygon(polygon), host.polygon(polygon));
+		}
+	}
+}
\ No newline at end of file
@@ -7,27 +7,52 @@
  */
 
 //! This module contains the implementations of operations to translate (move) geometric objects.
+//!
+//! There are implementations for the host, single-threaded and multi-threaded, and for the GPU.
+//! The `translate_polygon` function chooses between them automatically.
+//!
+//! Every implementation takes an `OverflowPolicy` that decides what happens to coordinates that
+//! would go out of the range of `Coordinate`. The policies behave the same on the host and on the
+//! GPU.
 
-use std::cmp;
+use cubecl::prelude::*; //For the GPU implementation.
 use rayon::prelude::*; //For multi-threaded implementations.
 
+#[cfg(feature = "gpu")]
+use cubecl::server::Handle; //To translate vertices on the GPU.
+
 use crate::Coordinate; //As parameter for how far to translate.
+use crate::MultiPolygon; //Translate multi-polygons.
+use crate::OverflowError; //If a coordinate would go out of range with the checked policy.
+use crate::OverflowPolicy; //To choose what happens if a coordinate would go out of range.
+use crate::Point2D; //To translate lists of vertices.
 use crate::Polygon; //Translate polygons.
-use crate::TwoDimensional; //The translate function is part of TwoDimensional.
+use crate::PolygonBatch; //Translate batches of polygons.
+use crate::TwoDimensional; //To translate the individual vertices with an overflow policy.
+use crate::cost_model::{self, Backend, Operation}; //To choose the fastest implementation.
 
 /// Move a polygon by a certain delta coordinate.
 ///
-/// This implementation is single-threaded and simply translates every vertex one by one.
+/// This chooses automatically whether to translate the polygon on a single thread, on multiple
+/// threads or on the GPU. The cost model estimates which is fastest, from the number of vertices in
+/// the polygon and from where the most up-to-date copy of the vertices is. For instance, if the
+/// vertices were modified on the GPU, translating them there prevents copying them back to the
+/// host.
 ///
 /// # Arguments
 /// * `dx` - How far to move the object in the X direction. Use a positive number to increase the X
 /// position, or a negative number to reduce the X position.
 /// * `dy` - How far to move the object in the Y direction. Use a positive number to increase the Y
 /// position, or a negative number to reduce the Y position.
+/// * `policy` - What to do with coordinates that would go out of the range of `Coordinate`.
+///
+/// # Result
+/// An error if any coordinate would go out of range and the policy is `CHECKED`. The object is then
+/// unchanged.
 ///
 /// # Examples
 /// ```
-/// use apex::{Point2D, Polygon, TwoDimensional};
+/// use apex::{OverflowPolicy, Point2D, Polygon};
 /// //Create a triangular polygon.
 /// let mut poly = Polygon::from_iter([
 /// 	Point2D { x: 0, y: 0 },
@@ -35,32 +60,77 @@ use crate::TwoDimensional; //The translate function is part of TwoDimensional.
 /// 	Point2D { x: 67, y: 100 }
 /// ]);
 /// //Move the polygon.
-/// apex::operations::translate::translate_polygon_st(&mut poly, 100, -150);
+/// apex::operations::translate::translate_polygon(&mut poly, 100, -150, OverflowPolicy::WRAPPING).unwrap();
 /// //Now, all of the vertices will have moved.
-/// assert_eq!(poly[0], Point2D { x: 100, y: -150 });
-/// assert_eq!(poly[1], Point2D { x: 200, y: -150 });
-/// assert_eq!(poly[2], Point2D { x: 167, y: -50 });
+/// assert_eq!(*poly.vertex(0), Point2D { x: 100, y: -150 });
+/// assert_eq!(*poly.vertex(1), Point2D { x: 200, y: -150 });
+/// assert_eq!(*poly.vertex(2), Point2D { x: 167, y: -50 });
 /// ```
-pub fn translate_polygon_st(polygon: &mut Polygon, dx: Coordinate, dy: Coordinate) {
-	for vertex in polygon.host_vertices_mut().iter_mut() {
-		vertex.translate(dx, dy);
+pub fn translate_polygon(polygon: &mut Polygon, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+	match cost_model::choose(Operation::TRANSLATE, polygon.len(), polygon.sync_status()) {
+		Backend::ST => translate_polygon_st(polygon, dx, dy, policy),
+		Backend::MT => translate_polygon_mt(polygon, dx, dy, policy),
+		#[cfg(feature = "gpu")]
+		Backend::GPU => translate_polygon_gpu(polygon, dx, dy, policy),
+		#[cfg(not(feature = "gpu"))]
+		Backend::GPU => unreachable!("Without a GPU runtime, the cost model never chooses the GPU."),
 	}
 }
 
+/// Move a polygon by a certain delta coordinate.
+///
+/// This implementation is single-threaded and simply translates every vertex one by one.
+///
+/// # Arguments
+/// * `dx` - How far to move the object in the X direction. Use a positive number to increase the X
+/// position, or a negative number to reduce the X position.
+/// * `dy` - How far to move the object in the Y direction. Use a positive number to increase the Y
+/// position, or a negative number to reduce the Y position.
+/// * `policy` - What to do with coordinates that would go out of the range of `Coordinate`.
+///
+/// # Result
+/// An error if any coordinate would go out of range and the policy is `CHECKED`. The object is then
+/// unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{OverflowPolicy, Point2D, Polygon};
+/// //Create a triangular polygon.
+/// let mut poly = Polygon::from_iter([
+/// 	Point2D { x: 0, y: 0 },
+/// 	Point2D { x: 100, y: 0 },
+/// 	Point2D { x: 67, y: 100 }
+/// ]);
+/// //Move the polygon.
+/// apex::operations::translate::translate_polygon_st(&mut poly, 100, -150, OverflowPolicy::WRAPPING).unwrap();
+/// //Now, all of the vertices will have moved.
+/// assert_eq!(*poly.vertex(0), Point2D { x: 100, y: -150 });
+/// assert_eq!(*poly.vertex(1), Point2D { x: 200, y: -150 });
+/// assert_eq!(*poly.vertex(2), Point2D { x: 167, y: -50 });
+/// ```
+pub fn translate_polygon_st(polygon: &mut Polygon, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+	translate_vertices_st(polygon.host_vertices_mut(), dx, dy, policy)
+}
+
 /// Move a polygon by a certain delta coordinate.
 ///
 /// This implementation is multi-threaded and will apply multiple threads to move the polygon
 /// quickly.
 ///
-/// # Aruments
+/// # Arguments
 /// * `dx` - How far to move the object in the X direction. Use a positive number to increase the X
 /// position, or a negative number to reduce the X position.
 /// * `dy` - How far to move the object in the Y direction. Use a positive number to increase the Y
 /// position, or a negative number to reduce the Y position.
+/// * `policy` - What to do with coordinates that would go out of the range of `Coordinate`.
+///
+/// # Result
+/// An error if any coordinate would go out of range and the policy is `CHECKED`. The object is then
+/// unchanged.
 ///
 /// # Examples
 /// ```
-/// use apex::{Point2D, Polygon, TwoDimensional};
+/// use apex::{OverflowPolicy, Point2D, Polygon};
 /// //Create a triangular polygon.
 /// let mut poly = Polygon::from_iter([
 /// 	Point2D { x: 0, y: 0 },
@@ -68,35 +138,509 @@ pub fn translate_polygon_st(polygon: &mut Polygon, dx: Coordinate, dy: Coordinat
 /// 	Point2D { x: 67, y: 100 }
 /// ]);
 /// //Move the polygon.
-/// apex::operations::translate::translate_polygon_mt(&mut poly, 100, -150);
+/// apex::operations::translate::translate_polygon_mt(&mut poly, 100, -150, OverflowPolicy::WRAPPING).unwrap();
 /// //Now, all of the vertices will have moved.
-/// assert_eq!(poly[0], Point2D { x: 100, y: -150 });
-/// assert_eq!(poly[1], Point2D { x: 200, y: -150 });
-/// assert_eq!(poly[2], Point2D { x: 167, y: -50 });
-/// ```
-pub fn translate_polygon_mt(polygon: &mut Polygon, dx: Coordinate, dy: Coordinate) {
-	let chunk_size = cmp::max(10000, polygon.host_vertices().len() / rayon::current_num_threads());
-	polygon.host_vertices_mut().par_chunks_mut(chunk_size).for_each(
-		|slice| slice.iter_mut().for_each(
-			|vertex| vertex.translate(dx, dy)
+/// assert_eq!(*poly.vertex(0), Point2D { x: 100, y: -150 });
+/// assert_eq!(*poly.vertex(1), Point2D { x: 200, y: -150 });
+/// assert_eq!(*poly.vertex(2), Point2D { x: 167, y: -50 });
+/// ```
+pub fn translate_polygon_mt(polygon: &mut Polygon, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+	translate_vertices_mt(polygon.host_vertices_mut(), dx, dy, policy)
+}
+
+/// Move a polygon by a certain delta coordinate.
+///
+/// This implementation translates the vertices on the GPU, each vertex in a separate work unit. If
+/// the vertices are not on the GPU yet, they are copied there first. Afterwards, the copy on the
+/// GPU is leading. The vertices are only copied back to the host when they are needed there.
+///
+/// # Arguments
+/// * `dx` - How far to move the object in the X direction. Use a positive number to increase the X
+/// position, or a negative number to reduce the X position.
+/// * `dy` - How far to move the object in the Y direction. Use a positive number to increase the Y
+/// position, or a negative number to reduce the Y position.
+/// * `policy` - What to do with coordinates that would go out of the range of `Coordinate`.
+///
+/// # Result
+/// An error if any coordinate would go out of range and the policy is `CHECKED`. The object is then
+/// unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{OverflowPolicy, Point2D, Polygon};
+/// //Create a triangular polygon.
+/// let mut poly = Polygon::from_iter([
+/// 	Point2D { x: 0, y: 0 },
+/// 	Point2D { x: 100, y: 0 },
+/// 	Point2D { x: 67, y: 100 }
+/// ]);
+/// //Move the polygon.
+/// apex::operations::translate::translate_polygon_gpu(&mut poly, 100, -150, OverflowPolicy::WRAPPING).unwrap();
+/// //Now, all of the vertices will have moved.
+/// assert_eq!(*poly.vertex(0), Point2D { x: 100, y: -150 });
+/// assert_eq!(*poly.vertex(1), Point2D { x: 200, y: -150 });
+/// assert_eq!(*poly.vertex(2), Point2D { x: 167, y: -50 });
+/// ```
+#[cfg(feature = "gpu")]
+pub fn translate_polygon_gpu(polygon: &mut Polygon, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+	if !crate::detail::gpu::supports_coordinates() {
+		return translate_polygon(polygon, dx, dy, policy);
+	}
+	translate_vertices_gpu(polygon.gpu_vertices_mut(), dx, dy, policy)
+}
+
+/// Move a multi-polygon by a certain delta coordinate.
+///
+/// This chooses automatically whether to translate the multi-polygon on a single thread, on
+/// multiple threads or on the GPU, in the same way as `translate_polygon`. The vertices of all
+/// contours are translated together.
+///
+/// # Arguments
+/// * `dx` - How far to move the object in the X direction. Use a positive number to increase the X
+/// position, or a negative number to reduce the X position.
+/// * `dy` - How far to move the object in the Y direction. Use a positive number to increase the Y
+/// position, or a negative number to reduce the Y position.
+/// * `policy` - What to do with coordinates that would go out of the range of `Coordinate`.
+///
+/// # Result
+/// An error if any coordinate would go out of range and the policy is `CHECKED`. The object is then
+/// unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, OverflowPolicy, Point2D};
+/// let mut multi = MultiPolygon::new();
+/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 67, y: 100 }]);
+/// multi.push_contour([Point2D { x: 200, y: 0 }, Point2D { x: 300, y: 0 }, Point2D { x: 267, y: 100 }]);
+/// apex::operations::translate::translate_multipolygon(&mut multi, 100, -150, OverflowPolicy::WRAPPING).unwrap();
+/// assert_eq!(multi.contour(0)[0], Point2D { x: 100, y: -150 });
+/// assert_eq!(multi.contour(1)[2], Point2D { x: 367, y: -50 });
+/// ```
+pub fn translate_multipolygon(multi_polygon: &mut MultiPolygon, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+	match cost_model::choose(Operation::TRANSLATE, multi_polygon.len(), multi_polygon.sync_status()) {
+		Backend::ST => translate_multipolygon_st(multi_polygon, dx, dy, policy),
+		Backend::MT => translate_multipolygon_mt(multi_polygon, dx, dy, policy),
+		#[cfg(feature = "gpu")]
+		Backend::GPU => translate_multipolygon_gpu(multi_polygon, dx, dy, policy),
+		#[cfg(not(feature = "gpu"))]
+		Backend::GPU => unreachable!("Without a GPU runtime, the cost model never chooses the GPU."),
+	}
+}
+
+/// Move a multi-polygon by a certain delta coordinate.
+///
+/// This implementation is single-threaded and simply translates every vertex of every contour one
+/// by one.
+///
+/// # Arguments
+/// * `dx` - How far to move the object in the X direction. Use a positive number to increase the X
+/// position, or a negative number to reduce the X position.
+/// * `dy` - How far to move the object in the Y direction. Use a positive number to increase the Y
+/// position, or a negative number to reduce the Y position.
+/// * `policy` - What to do with coordinates that would go out of the range of `Coordinate`.
+///
+/// # Result
+/// An error if any coordinate would go out of range and the policy is `CHECKED`. The object is then
+/// unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, OverflowPolicy, Point2D};
+/// let mut multi = MultiPolygon::new();
+/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 67, y: 100 }]);
+/// apex::operations::translate::translate_multipolygon_st(&mut multi, 100, -150, OverflowPolicy::WRAPPING).unwrap();
+/// assert_eq!(multi.contour(0)[2], Point2D { x: 167, y: -50 });
+/// ```
+pub fn translate_multipolygon_st(multi_polygon: &mut MultiPolygon, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+	translate_vertices_st(multi_polygon.host_vertices_mut(), dx, dy, policy)
+}
+
+/// Move a multi-polygon by a certain delta coordinate.
+///
+/// This implementation is multi-threaded. The vertices of all contours are divided over the
+/// threads together, regardless of which contour they belong to.
+///
+/// # Arguments
+/// * `dx` - How far to move the object in the X direction. Use a positive number to increase the X
+/// position, or a negative number to reduce the X position.
+/// * `dy` - How far to move the object in the Y direction. Use a positive number to increase the Y
+/// position, or a negative number to reduce the Y position.
+/// * `policy` - What to do with coordinates that would go out of the range of `Coordinate`.
+///
+/// # Result
+/// An error if any coordinate would go out of range and the policy is `CHECKED`. The object is then
+/// unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, OverflowPolicy, Point2D};
+/// let mut multi = MultiPolygon::new();
+/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 67, y: 100 }]);
+/// apex::operations::translate::translate_multipolygon_mt(&mut multi, 100, -150, OverflowPolicy::WRAPPING).unwrap();
+/// assert_eq!(multi.contour(0)[2], Point2D { x: 167, y: -50 });
+/// ```
+pub fn translate_multipolygon_mt(multi_polygon: &mut MultiPolygon, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+	translate_vertices_mt(multi_polygon.host_vertices_mut(), dx, dy, policy)
+}
+
+/// Move a multi-polygon by a certain delta coordinate.
+///
+/// This implementation translates the vertices of all contours on the GPU, in a single kernel
+/// launch. If the vertices are not on the GPU yet, they are all copied there at once. Afterwards,
+/// the copy on the GPU is leading.
+///
+/// # Arguments
+/// * `dx` - How far to move the object in the X direction. Use a positive number to increase the X
+/// position, or a negative number to reduce the X position.
+/// * `dy` - How far to move the object in the Y direction. Use a positive number to increase the Y
+/// position, or a negative number to reduce the Y position.
+/// * `policy` - What to do with coordinates that would go out of the range of `Coordinate`.
+///
+/// # Result
+/// An error if any coordinate would go out of range and the policy is `CHECKED`. The object is then
+/// unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, OverflowPolicy, Point2D};
+/// let mut multi = MultiPolygon::new();
+/// multi.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 67, y: 100 }]);
+/// apex::operations::translate::translate_multipolygon_gpu(&mut multi, 100, -150, OverflowPolicy::WRAPPING).unwrap();
+/// assert_eq!(multi.contour(0)[2], Point2D { x: 167, y: -50 });
+/// ```
+#[cfg(feature = "gpu")]
+pub fn translate_multipolygon_gpu(multi_polygon: &mut MultiPolygon, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+	if !crate::detail::gpu::supports_coordinates() {
+		return translate_multipolygon(multi_polygon, dx, dy, policy);
+	}
+	translate_vertices_gpu(multi_polygon.gpu_vertices_mut(), dx, dy, policy)
+}
+
+/// Move all polygons in a batch by a certain delta coordinate.
+///
+/// This chooses automatically whether to translate the batch on a single thread, on multiple
+/// threads or on the GPU, in the same way as `translate_polygon`. The vertices of all polygons are
+/// translated together.
+///
+/// # Arguments
+/// * `dx` - How far to move the polygons in the X direction. Use a positive number to increase the
+/// X position, or a negative number to reduce the X position.
+/// * `dy` - How far to move the polygons in the Y direction. Use a positive number to increase the
+/// Y position, or a negative number to reduce the Y position.
+/// * `policy` - What to do with coordinates that would go out of the range of `Coordinate`.
+///
+/// # Result
+/// An error if any coordinate would go out of range and the policy is `CHECKED`. All polygons in
+/// the batch are then unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{OverflowPolicy, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 67, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 200, y: 0 }, Point2D { x: 300, y: 0 }, Point2D { x: 267, y: 100 }]);
+/// apex::operations::translate::translate_batch(&mut batch, 100, -150, OverflowPolicy::WRAPPING).unwrap();
+/// assert_eq!(batch.polygon(0)[0], Point2D { x: 100, y: -150 });
+/// assert_eq!(batch.polygon(1)[2], Point2D { x: 367, y: -50 });
+/// ```
+pub fn translate_batch(batch: &mut PolygonBatch, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+	match cost_model::choose(Operation::TRANSLATE, batch.len(), batch.sync_status()) {
+		Backend::ST => translate_batch_st(batch, dx, dy, policy),
+		Backend::MT => translate_batch_mt(batch, dx, dy, policy),
+		#[cfg(feature = "gpu")]
+		Backend::GPU => translate_batch_gpu(batch, dx, dy, policy),
+		#[cfg(not(feature = "gpu"))]
+		Backend::GPU => unreachable!("Without a GPU runtime, the cost model never chooses the GPU."),
+	}
+}
+
+/// Move all polygons in a batch by a certain delta coordinate.
+///
+/// This implementation is single-threaded and simply translates every vertex of every polygon one
+/// by one.
+///
+/// # Arguments
+/// * `dx` - How far to move the polygons in the X direction. Use a positive number to increase the
+/// X position, or a negative number to reduce the X position.
+/// * `dy` - How far to move the polygons in the Y direction. Use a positive number to increase the
+/// Y position, or a negative number to reduce the Y position.
+/// * `policy` - What to do with coordinates that would go out of the range of `Coordinate`.
+///
+/// # Result
+/// An error if any coordinate would go out of range and the policy is `CHECKED`. All polygons in
+/// the batch are then unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{OverflowPolicy, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 67, y: 100 }]);
+/// apex::operations::translate::translate_batch_st(&mut batch, 100, -150, OverflowPolicy::WRAPPING).unwrap();
+/// assert_eq!(batch.polygon(0)[2], Point2D { x: 167, y: -50 });
+/// ```
+pub fn translate_batch_st(batch: &mut PolygonBatch, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+	translate_vertices_st(batch.host_vertices_mut(), dx, dy, policy)
+}
+
+/// Move all polygons in a batch by a certain delta coordinate.
+///
+/// This implementation is multi-threaded. The vertices of all polygons are divided over the threads
+/// together, in a single pass, regardless of which polygon they belong to.
+///
+/// # Arguments
+/// * `dx` - How far to move the polygons in the X direction. Use a positive number to increase the
+/// X position, or a negative number to reduce the X position.
+/// * `dy` - How far to move the polygons in the Y direction. Use a positive number to increase the
+/// Y position, or a negative number to reduce the Y position.
+/// * `policy` - What to do with coordinates that would go out of the range of `Coordinate`.
+///
+/// # Result
+/// An error if any coordinate would go out of range and the policy is `CHECKED`. All polygons in
+/// the batch are then unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{OverflowPolicy, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 67, y: 100 }]);
+/// apex::operations::translate::translate_batch_mt(&mut batch, 100, -150, OverflowPolicy::WRAPPING).unwrap();
+/// assert_eq!(batch.polygon(0)[2], Point2D { x: 167, y: -50 });
+/// ```
+pub fn translate_batch_mt(batch: &mut PolygonBatch, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+	translate_vertices_mt(batch.host_vertices_mut(), dx, dy, policy)
+}
+
+/// Move all polygons in a batch by a certain delta coordinate.
+///
+/// This implementation translates the vertices of all polygons on the GPU, in a single kernel
+/// launch. If the vertices are not on the GPU yet, the whole batch is copied there at once.
+/// Afterwards, the copy on the GPU is leading.
+///
+/// # Arguments
+/// * `dx` - How far to move the polygons in the X direction. Use a positive number to increase the
+/// X position, or a negative number to reduce the X position.
+/// * `dy` - How far to move the polygons in the Y direction. Use a positive number to increase the
+/// Y position, or a negative number to reduce the Y position.
+/// * `policy` - What to do with coordinates that would go out of the range of `Coordinate`.
+///
+/// # Result
+/// An error if any coordinate would go out of range and the policy is `CHECKED`. All polygons in
+/// the batch are then unchanged.
+///
+/// # Examples
+/// ```
+/// use apex::{OverflowPolicy, Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 67, y: 100 }]);
+/// apex::operations::translate::translate_batch_gpu(&mut batch, 100, -150, OverflowPolicy::WRAPPING).unwrap();
+/// assert_eq!(batch.polygon(0)[2], Point2D { x: 167, y: -50 });
+/// ```
+#[cfg(feature = "gpu")]
+pub fn translate_batch_gpu(batch: &mut PolygonBatch, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+	if !crate::detail::gpu::supports_coordinates() {
+		return translate_batch(batch, dx, dy, policy);
+	}
+	translate_vertices_gpu(batch.gpu_vertices_mut(), dx, dy, policy)
+}
+
+/// Move a list of vertices on the host by a certain delta coordinate, on a single thread.
+///
+/// With the checked policy, all vertices are checked before any of them is moved, so that they
+/// stay unchanged if any of them would overflow.
+///
+/// # Arguments
+/// * `vertices` - The vertices to move.
+/// * `dx` - How far to move the vertices in the X direction.
+/// * `dy` - How far to move the vertices in the Y direction.
+/// * `policy` - What to do with coordinates that would go out of range.
+fn translate_vertices_st(vertices: &mut [Point2D], dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+	if policy == OverflowPolicy::CHECKED && !vertices.iter().all(|vertex| fits(vertex, dx, dy)) {
+		return Err(OverflowError);
+	}
+	for vertex in vertices.iter_mut() {
+		vertex.translate_with_policy(dx, dy, policy)?;
+	}
+	Ok(())
+}
+
+/// Move a list of vertices on the host by a certain delta coordinate, on multiple threads.
+///
+/// # Arguments
+/// * `vertices` - The vertices to move.
+/// * `dx` - How far to move the vertices in the X direction.
+/// * `dy` - How far to move the vertices in the Y direction.
+/// * `policy` - What to do with coordinates that would go out of range.
+fn translate_vertices_mt(vertices: &mut [Point2D], dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+	let chunk_size = cost_model::chunk_size(Operation::TRANSLATE, vertices.len());
+	if policy == OverflowPolicy::CHECKED && !vertices.par_chunks(chunk_size).all(|slice| slice.iter().all(|vertex| fits(vertex, dx, dy))) {
+		return Err(OverflowError);
+	}
+	vertices.par_chunks_mut(chunk_size).try_for_each(
+		|slice| slice.iter_mut().try_for_each(
+			|vertex| vertex.translate_with_policy(dx, dy, policy)
 		)
-	);
+	)
+}
+
+/// Check whether a vertex can be moved by a certain delta coordinate without going out of range.
+///
+/// # Arguments
+/// * `vertex` - The vertex to move.
+/// * `dx` - How far to move the vertex in the X direction.
+/// * `dy` - How far to move the vertex in the Y direction.
+fn fits(vertex: &Point2D, dx: Coordinate, dy: Coordinate) -> bool {
+	vertex.x.checked_add(dx).is_some() && vertex.y.checked_add(dy).is_some()
+}
+
+/// Move a list of vertices on the GPU by a certain delta coordinate.
+///
+/// With the checked policy, a first kernel checks whether any vertex would overflow. Only if none
+/// would, the vertices are moved.
+///
+/// # Arguments
+/// * `coordinates_handle` - The vertices to move, as a flat array of coordinates on the GPU with
+/// the X and Y coordinates interleaved.
+/// * `dx` - How far to move the vertices in the X direction.
+/// * `dy` - How far to move the vertices in the Y direction.
+/// * `policy` - What to do with coordinates that would go out of range.
+#[cfg(feature = "gpu")]
+fn translate_vertices_gpu(coordinates_handle: &Handle, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+	use crate::detail::gpu;
+
+	let num_vertices = coordinates_handle.size() as usize / (2 * size_of::<Coordinate>());
+	if num_vertices == 0 {
+		return Ok(()); //Can't launch a kernel without any work. There is nothing to move.
+	}
+	let client = gpu::client();
+	if policy == OverflowPolicy::CHECKED {
+		let overflow_handle = client.create(u32::as_bytes(&[0]));
+		let (cube_count, cube_dim) = gpu::launch_dimensions(num_vertices);
+		unsafe {
+			check_translation_kernel::launch::<gpu::GpuRuntime>(
+				&client,
+				cube_count,
+				cube_dim,
+				ArrayArg::from_raw_parts::<Coordinate>(coordinates_handle, num_vertices * 2, 1),
+				ArrayArg::from_raw_parts::<u32>(&overflow_handle, 1, 1),
+				ScalarArg::new(dx),
+				ScalarArg::new(dy),
+			);
+		}
+		if u32::from_bytes(&client.read_one(overflow_handle))[0] != 0 {
+			return Err(OverflowError);
+		}
+	}
+	let (cube_count, cube_dim) = gpu::launch_dimensions(num_vertices);
+	unsafe {
+		translate_vertices_kernel::launch::<gpu::GpuRuntime>(
+			&client,
+			cube_count,
+			cube_dim,
+			ArrayArg::from_raw_parts::<Coordinate>(coordinates_handle, num_vertices * 2, 1),
+			ScalarArg::new(dx),
+			ScalarArg::new(dy),
+			ScalarArg::new(if policy == OverflowPolicy::SATURATING { 1u32 } else { 0u32 }),
+		);
+	}
+	Ok(())
+}
+
+/// GPU kernel to check whether any vertex in a list would go out of range when moved.
+///
+/// Every work unit checks one vertex.
+///
+/// # Arguments
+/// * `coordinates` - The vertices to move, with the X and Y coordinates interleaved.
+/// * `overflow` - A single flag, which is set to 1 if any coordinate would go out of range.
+/// * `dx` - How far to move the vertices in the X direction.
+/// * `dy` - How far to move the vertices in the Y direction.
+#[cube(launch)]
+fn check_translation_kernel(coordinates: &Array<Coordinate>, overflow: &mut Array<u32>, dx: Coordinate, dy: Coordinate) {
+	let num_vertices = coordinates.len() / 2;
+	if ABSOLUTE_POS < num_vertices {
+		let x = i64::cast_from(coordinates[ABSOLUTE_POS * 2]) + i64::cast_from(dx);
+		let y = i64::cast_from(coordinates[ABSOLUTE_POS * 2 + 1]) + i64::cast_from(dy);
+		let min = i64::cast_from(Coordinate::MIN);
+		let max = i64::cast_from(Coordinate::MAX);
+		if x < min || x > max || y < min || y > max {
+			overflow[0] = 1;
+		}
+	}
+}
+
+/// GPU kernel to move every vertex in a list by a certain delta coordinate.
+///
+/// Every work unit moves one vertex, in place. Coordinates that go out of range wrap around, or
+/// are clamped if `saturate` is set.
+///
+/// # Arguments
+/// * `coordinates` - The vertices to move, with the X and Y coordinates interleaved.
+/// * `dx` - How far to move the vertices in the X direction.
+/// * `dy` - How far to move the vertices in the Y direction.
+/// * `saturate` - 1 to clamp coordinates that go out of range, or 0 to let them wrap around.
+#[cube(launch)]
+fn translate_vertices_kernel(coordinates: &mut Array<Coordinate>, dx: Coordinate, dy: Coordinate, saturate: u32) {
+	let num_vertices = coordinates.len() / 2;
+	if ABSOLUTE_POS < num_vertices {
+		if saturate != 0 {
+			coordinates[ABSOLUTE_POS * 2] = saturating_add(coordinates[ABSOLUTE_POS * 2], dx);
+			coordinates[ABSOLUTE_POS * 2 + 1] = saturating_add(coordinates[ABSOLUTE_POS * 2 + 1], dy);
+		} else {
+			coordinates[ABSOLUTE_POS * 2] += dx;
+			coordinates[ABSOLUTE_POS * 2 + 1] += dy;
+		}
+	}
+}
+
+/// Add two coordinates on the GPU, clamping the sum to the range of `Coordinate`.
+///
+/// # Arguments
+/// * `coordinate` - The original coordinate.
+/// * `delta` - How much to add to it.
+#[cube]
+fn saturating_add(coordinate: Coordinate, delta: Coordinate) -> Coordinate {
+	let mut sum = i64::cast_from(coordinate) + i64::cast_from(delta);
+	let min = i64::cast_from(Coordinate::MIN);
+	let max = i64::cast_from(Coordinate::MAX);
+	if sum < min {
+		sum = min;
+	}
+	if sum > max {
+		sum = max;
+	}
+	Coordinate::cast_from(sum)
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::detail::sync_status::SyncStatus;
 	use test_case::test_case;
 
+	/// The signature of the functions that translate polygons.
+	type PolygonTranslator = fn(&mut Polygon, Coordinate, Coordinate, OverflowPolicy) -> Result<(), OverflowError>;
+
+	/// The signature of the functions that translate multi-polygons.
+	type MultiPolygonTranslator = fn(&mut MultiPolygon, Coordinate, Coordinate, OverflowPolicy) -> Result<(), OverflowError>;
+
+	/// The signature of the functions that translate batches of polygons.
+	type BatchTranslator = fn(&mut PolygonBatch, Coordinate, Coordinate, OverflowPolicy) -> Result<(), OverflowError>;
+
+	/// A polygon with one vertex close to the maximum X coordinate and one close to the minimum Y
+	/// coordinate.
+	fn near_edges(num_vertices: Coordinate) -> Polygon {
+		Polygon::from_iter((0..num_vertices).map(|i| Point2D { x: i, y: i }).chain([Point2D { x: Coordinate::MAX - 5, y: 0 }, Point2D { x: 0, y: Coordinate::MIN + 5 }]))
+	}
+
 	/// Test moving an empty polygon.
 	///
 	/// This mainly just tests that it won't panic on that.
 	#[test]
 	fn translate_polygon_empty() {
 		let mut poly = Polygon::new();
-		translate_polygon_st(&mut poly, 100, 100);
+		translate_polygon_st(&mut poly, 100, 100, OverflowPolicy::WRAPPING).unwrap();
 		assert_eq!(poly.len(), 0, "The polygon must still be unchanged.");
-		translate_polygon_mt(&mut poly, 100, 100);
+		translate_polygon_mt(&mut poly, 100, 100, OverflowPolicy::WRAPPING).unwrap();
 		assert_eq!(poly.len(), 0, "The polygon must still be unchanged.");
 	}
 
@@ -105,29 +649,251 @@ mod tests {
 	fn translate_polygon_zero() {
 		let original = crate::test::data::polygon::square_1000(); //An original to compare to.
 		let mut poly = crate::test::data::polygon::square_1000(); //A copy that we can translate.
-		translate_polygon_st(&mut poly, 0, 0); //Translate by 0,0.
-		assert_eq!(poly.host_vertices(), original.host_vertices(), "The polygon's vertices may not have changed by moving 0,0.");
-		translate_polygon_mt(&mut poly, 0, 0);
-		assert_eq!(poly.host_vertices(), original.host_vertices(), "The polygon's vertices may not have changed by moving 0,0.");
+		translate_polygon_st(&mut poly, 0, 0, OverflowPolicy::WRAPPING).unwrap(); //Translate by 0,0.
+		assert_eq!(*poly.host_vertices(), *original.host_vertices(), "The polygon's vertices may not have changed by moving 0,0.");
+		translate_polygon_mt(&mut poly, 0, 0, OverflowPolicy::WRAPPING).unwrap();
+		assert_eq!(*poly.host_vertices(), *original.host_vertices(), "The polygon's vertices may not have changed by moving 0,0.");
 	}
 
 	/// Test moving a polygon by a certain offset.
 	#[test_case(250, 0    ; "x_positive")]
 	#[test_case(0,   -300 ; "y_negative")]
 	#[test_case(-40, 70   ; "mixed")]
-	fn translate_polygon_vector(x: i32, y: i32) {
+	fn translate_polygon_vector(x: Coordinate, y: Coordinate) {
 		let original = crate::test::data::polygon::square_1000(); //An original to compare to.
 		let mut poly = crate::test::data::polygon::square_1000(); //A copy that we can translate.
 
-		translate_polygon_st(&mut poly, x, y);
+		translate_polygon_st(&mut poly, x, y, OverflowPolicy::WRAPPING).unwrap();
 		for i in 0..poly.len() {
-			assert_eq!(*(&poly)[i], *(&original)[i] + crate::Point2D { x, y });
+			assert_eq!(*poly.vertex(i), *original.vertex(i) + crate::Point2D { x, y });
 		}
 
 		poly = crate::test::data::polygon::square_1000(); //Reset to original.
-		translate_polygon_mt(&mut poly, x, y);
+		translate_polygon_mt(&mut poly, x, y, OverflowPolicy::WRAPPING).unwrap();
+		for i in 0..poly.len() {
+			assert_eq!(*poly.vertex(i), *original.vertex(i) + crate::Point2D { x, y });
+		}
+	}
+
+	/// Test that the automatic choice of device translates small and large polygons correctly.
+	#[test_case(3      ; "small")]
+	#[test_case(100000 ; "large")]
+	fn translate_polygon_dispatch(num_vertices: Coordinate) {
+		let mut poly = Polygon::from_iter((0..num_vertices).map(|i| crate::Point2D { x: i, y: -i }));
+		translate_polygon(&mut poly, 40, 70, OverflowPolicy::WRAPPING).unwrap();
+		for i in 0..num_vertices {
+			assert_eq!(*poly.vertex(i as usize), crate::Point2D { x: i + 40, y: -i + 70 }, "Every vertex must be moved, regardless of the device that was chosen.");
+		}
+	}
+
+	/// Test that small polygons on the host are not copied to the GPU to translate them.
+	#[test]
+	fn translate_polygon_dispatch_stays_on_host() {
+		let mut poly = crate::test::data::polygon::square_1000();
+		translate_polygon(&mut poly, 40, 70, OverflowPolicy::WRAPPING).unwrap();
+		assert_eq!(poly.sync_status(), SyncStatus::HOST, "Copying the vertices to the GPU is not worth it for a small polygon.");
+	}
+
+	/// Test translating a polygon on the GPU.
+	#[cfg(all(feature = "gpu", not(feature = "coordinate64")))] //With 64-bit coordinates, the vertices are translated on the host.
+	#[test_case(250, 0    ; "x_positive")]
+	#[test_case(0,   -300 ; "y_negative")]
+	#[test_case(-40, 70   ; "mixed")]
+	fn translate_polygon_gpu_vector(x: Coordinate, y: Coordinate) {
+		let original = crate::test::data::polygon::square_1000(); //An original to compare to.
+		let mut poly = crate::test::data::polygon::square_1000(); //A copy that we can translate.
+		translate_polygon_gpu(&mut poly, x, y, OverflowPolicy::WRAPPING).unwrap();
+		assert_eq!(poly.sync_status(), SyncStatus::GPU, "The translated vertices are only on the GPU until they are needed on the host.");
 		for i in 0..poly.len() {
-			assert_eq!(*(&poly)[i], *(&original)[i] + crate::Point2D { x, y });
+			assert_eq!(*poly.vertex(i), *original.vertex(i) + crate::Point2D { x, y });
+		}
+	}
+
+	/// Test moving an empty polygon on the GPU.
+	#[cfg(feature = "gpu")]
+	#[test]
+	fn translate_polygon_gpu_empty() {
+		let mut poly = Polygon::new();
+		translate_polygon_gpu(&mut poly, 100, 100, OverflowPolicy::WRAPPING).unwrap();
+		assert_eq!(poly.len(), 0, "The polygon must still be unchanged.");
+	}
+
+	/// Test that polygons that were modified on the GPU are translated there too, even if small.
+	#[cfg(all(feature = "gpu", not(feature = "coordinate64")))] //With 64-bit coordinates, the vertices are translated on the host.
+	#[test]
+	fn translate_polygon_dispatch_stays_on_gpu() {
+		let mut poly = crate::test::data::polygon::square_1000();
+		translate_polygon_gpu(&mut poly, 10, 10, OverflowPolicy::WRAPPING).unwrap();
+		translate_polygon(&mut poly, 30, 60, OverflowPolicy::WRAPPING).unwrap();
+		assert_eq!(poly.sync_status(), SyncStatus::GPU, "Copying the vertices back to the host is not worth it to translate them.");
+		assert_eq!(*poly.vertex(0), *crate::test::data::polygon::square_1000().vertex(0) + crate::Point2D { x: 40, y: 70 });
+	}
+
+	/// Test moving all contours of a multi-polygon by a certain offset.
+	#[test_case(250, 0    ; "x_positive")]
+	#[test_case(0,   -300 ; "y_negative")]
+	#[test_case(-40, 70   ; "mixed")]
+	fn translate_multipolygon_vector(x: Coordinate, y: Coordinate) {
+		let original = crate::test::data::multi_polygon::square_with_hole_1000(); //An original to compare to.
+		let translators: [MultiPolygonTranslator; 3] = [translate_multipolygon, translate_multipolygon_st, translate_multipolygon_mt];
+		for translator in translators {
+			let mut multi = crate::test::data::multi_polygon::square_with_hole_1000(); //A copy that we can translate.
+			translator(&mut multi, x, y, OverflowPolicy::WRAPPING).unwrap();
+			assert_eq!(multi.num_contours(), original.num_contours(), "Translating doesn't change the contours.");
+			for contour in 0..multi.num_contours() {
+				for i in 0..multi.contour(contour).len() {
+					assert_eq!(multi.contour(contour)[i], original.contour(contour)[i] + crate::Point2D { x, y });
+				}
+			}
+		}
+	}
+
+	/// Test moving all contours of a multi-polygon on the GPU.
+	#[cfg(all(feature = "gpu", not(feature = "coordinate64")))] //With 64-bit coordinates, the vertices are translated on the host.
+	#[test]
+	fn translate_multipolygon_gpu_vector() {
+		let original = crate::test::data::multi_polygon::two_squares_1000(); //An original to compare to.
+		let mut multi = crate::test::data::multi_polygon::two_squares_1000(); //A copy that we can translate.
+		translate_multipolygon_gpu(&mut multi, -40, 70, OverflowPolicy::WRAPPING).unwrap();
+		assert_eq!(multi.sync_status(), SyncStatus::GPU, "The translated vertices are only on the GPU until they are needed on the host.");
+		for contour in 0..multi.num_contours() {
+			for i in 0..multi.contour(contour).len() {
+				assert_eq!(multi.contour(contour)[i], original.contour(contour)[i] + crate::Point2D { x: -40, y: 70 });
+			}
+		}
+	}
+
+	/// Test that the checked policy leaves the polygon unchanged if only one of its vertices would
+	/// go out of range.
+	#[test_case(10 ; "small")]
+	#[test_case(100000 ; "large")]
+	fn translate_polygon_checked_overflow(num_vertices: Coordinate) {
+		let translators: [PolygonTranslator; 3] = [translate_polygon, translate_polygon_st, translate_polygon_mt];
+		for translator in translators {
+			for (dx, dy) in [(10, 0), (0, -10)] {
+				let mut poly = near_edges(num_vertices);
+				assert_eq!(translator(&mut poly, dx, dy, OverflowPolicy::CHECKED), Err(OverflowError));
+				assert_eq!(poly, near_edges(num_vertices), "The polygon must be unchanged after an overflow.");
+			}
+			let mut poly = near_edges(num_vertices);
+			assert_eq!(translator(&mut poly, 5, -5, OverflowPolicy::CHECKED), Ok(()), "Exactly reaching the edges of the range is allowed.");
+			assert_eq!(*poly.vertex(num_vertices as usize), Point2D { x: Coordinate::MAX, y: -5 });
+		}
+	}
+
+	/// Test that the saturating and wrapping policies treat the vertices that go out of range
+	/// differently, but move the other vertices the same way.
+	#[test_case(OverflowPolicy::SATURATING, Point2D { x: Coordinate::MAX, y: -10 }, Point2D { x: 10, y: Coordinate::MIN } ; "saturating")]
+	#[test_case(OverflowPolicy::WRAPPING, Point2D { x: Coordinate::MIN + 4, y: -10 }, Point2D { x: 10, y: Coordinate::MAX - 4 } ; "wrapping")]
+	fn translate_polygon_policy(policy: OverflowPolicy, expected_right: Point2D, expected_bottom: Point2D) {
+		let translators: [PolygonTranslator; 3] = [translate_polygon, translate_polygon_st, translate_polygon_mt];
+		for translator in translators {
+			let mut poly = near_edges(100000);
+			assert_eq!(translator(&mut poly, 10, -10, policy), Ok(()));
+			assert_eq!(*poly.vertex(3), Point2D { x: 13, y: -7 }, "Vertices that stay in range are moved normally.");
+			assert_eq!(*poly.vertex(100000), expected_right);
+			assert_eq!(*poly.vertex(100001), expected_bottom);
+		}
+	}
+
+	/// Test the overflow policies on multi-polygons.
+	#[test_case(OverflowPolicy::CHECKED ; "checked")]
+	#[test_case(OverflowPolicy::SATURATING ; "saturating")]
+	#[test_case(OverflowPolicy::WRAPPING ; "wrapping")]
+	fn translate_multipolygon_policy(policy: OverflowPolicy) {
+		let translators: [MultiPolygonTranslator; 3] = [translate_multipolygon, translate_multipolygon_st, translate_multipolygon_mt];
+		for translator in translators {
+			let mut multi = crate::test::data::multi_polygon::square_with_hole_1000();
+			let result = translator(&mut multi, Coordinate::MAX - 500, 0, policy);
+			let corner = multi.contour(0).iter().copied().max().unwrap();
+			match policy {
+				OverflowPolicy::CHECKED => {
+					assert_eq!(result, Err(OverflowError));
+					assert_eq!(corner, Point2D { x: 1000, y: 1000 }, "The multi-polygon must be unchanged after an overflow.");
+				}
+				OverflowPolicy::SATURATING => {
+					assert_eq!(result, Ok(()));
+					assert_eq!(corner, Point2D { x: Coordinate::MAX, y: 1000 });
+				}
+				OverflowPolicy::WRAPPING => {
+					assert_eq!(result, Ok(()));
+					assert_eq!(corner.x, Coordinate::MAX - 500, "The corners at X=1000 wrapped around to negative coordinates, so the highest are the ones that were at X=0.");
+				}
+			}
+		}
+	}
+
+	/// Test the convenience function of `TwoDimensional` to translate with a policy.
+	#[test]
+	fn translate_with_policy() {
+		let mut poly = near_edges(3);
+		assert_eq!(poly.translate_with_policy(6, 0, OverflowPolicy::CHECKED), Err(OverflowError));
+		assert_eq!(poly, near_edges(3));
+		poly.translate_with_policy(6, 0, OverflowPolicy::SATURATING).unwrap();
+		assert_eq!(*poly.vertex(3), Point2D { x: Coordinate::MAX, y: 0 });
+		poly.translate(1, 0);
+		assert_eq!(*poly.vertex(3), Point2D { x: Coordinate::MIN, y: 0 }, "Plain translation wraps around.");
+	}
+
+	/// Test that the overflow policies behave the same on the GPU as on the host.
+	#[cfg(feature = "gpu")]
+	#[test_case(OverflowPolicy::CHECKED ; "checked")]
+	#[test_case(OverflowPolicy::SATURATING ; "saturating")]
+	#[test_case(OverflowPolicy::WRAPPING ; "wrapping")]
+	fn translate_polygon_gpu_policy(policy: OverflowPolicy) {
+		for (dx, dy) in [(10, -10), (5, -5), (-3, 3)] {
+			let mut host = near_edges(10);
+			let mut gpu = near_edges(10);
+			let host_result = translate_polygon_st(&mut host, dx, dy, policy);
+			assert_eq!(translate_polygon_gpu(&mut gpu, dx, dy, policy), host_result);
+			assert_eq!(gpu, host);
+		}
+	}
+
+	/// Test moving all polygons of a batch together.
+	#[test_case(250, 0    ; "x_positive")]
+	#[test_case(0,   -300 ; "y_negative")]
+	#[test_case(-40, 70   ; "mixed")]
+	fn translate_batch_vector(x: Coordinate, y: Coordinate) {
+		let original = PolygonBatch::from_iter([crate::test::data::polygon::square_1000(), Polygon::new(), crate::test::data::polygon::l_shape_1000()]); //An original to compare to.
+		let translators: [BatchTranslator; 3] = [translate_batch, translate_batch_st, translate_batch_mt];
+		for translator in translators {
+			let mut batch = PolygonBatch::from_iter([crate::test::data::polygon::square_1000(), Polygon::new(), crate::test::data::polygon::l_shape_1000()]); //A copy that we can translate.
+			translator(&mut batch, x, y, OverflowPolicy::WRAPPING).unwrap();
+			assert_eq!(batch.num_polygons(), original.num_polygons(), "Translating doesn't change the polygons.");
+			for polygon in 0..batch.num_polygons() {
+				for i in 0..batch.polygon(polygon).len() {
+					assert_eq!(batch.polygon(polygon)[i], original.polygon(polygon)[i] + crate::Point2D { x, y });
+				}
+			}
+		}
+	}
+
+	/// Test that the checked policy leaves all polygons of a batch unchanged if only one of them
+	/// would go out of range.
+	#[test]
+	fn translate_batch_checked_overflow() {
+		let translators: [BatchTranslator; 3] = [translate_batch, translate_batch_st, translate_batch_mt];
+		for translator in translators {
+			let mut batch = PolygonBatch::from_iter([crate::test::data::polygon::square_1000(), near_edges(10)]);
+			assert_eq!(translator(&mut batch, 10, 0, OverflowPolicy::CHECKED), Err(OverflowError));
+			assert_eq!(batch.polygon(0), crate::test::data::polygon::square_1000().host_vertices().as_slice(), "The polygon that would stay in range must be unchanged too.");
+			assert_eq!(batch.polygon(1), near_edges(10).host_vertices().as_slice(), "The polygon that would go out of range must be unchanged.");
+		}
+	}
+
+	/// Test moving all polygons of a batch on the GPU.
+	#[cfg(all(feature = "gpu", not(feature = "coordinate64")))] //With 64-bit coordinates, the vertices are translated on the host.
+	#[test]
+	fn translate_batch_gpu_vector() {
+		let original = PolygonBatch::from_iter([crate::test::data::polygon::square_1000(), crate::test::data::polygon::triangle_1000()]); //An original to compare to.
+		let mut batch = PolygonBatch::from_iter([crate::test::data::polygon::square_1000(), crate::test::data::polygon::triangle_1000()]); //A copy that we can translate.
+		translate_batch_gpu(&mut batch, -40, 70, OverflowPolicy::WRAPPING).unwrap();
+		assert_eq!(batch.sync_status(), SyncStatus::GPU, "The translated vertices are only on the GPU until they are needed on the host.");
+		for polygon in 0..batch.num_polygons() {
+			for i in 0..batch.polygon(polygon).len() {
+				assert_eq!(batch.polygon(polygon)[i], original.polygon(polygon)[i] + crate::Point2D { x: -40, y: 70 });
+			}
 		}
 	}
 }
\ No newline at end of file
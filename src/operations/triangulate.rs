@@ -0,0 +1,1477 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! This module contains the implementations of operations to divide geometric objects into
+//! triangles.
+//!
+//! The triangles are returned as an index buffer: a list of indices into the vertices of the
+//! original object, where every three consecutive indices form a triangle. For multi-polygons, the
+//! indices refer to the vertices of all contours together, one contour after another, in the order
+//! that they are stored in. No new vertices are created. The triangles have the same orientation as
+//! the contours they fill, so counter-clockwise contours produce counter-clockwise triangles. For
+//! valid input, the signed areas of the triangles add up to the area of the object.
+//!
+//! Vertices that are duplicates of the vertex before them, or that are collinear with their
+//! neighbours, don't contribute to the shape. They are removed before triangulating, so they don't
+//! appear in any triangle. The contours must not intersect themselves or each other, but they may
+//! touch at vertices, as the results of boolean operations often do. Contours that touch themselves
+//! are divided into loops there, which are triangulated as separate contours. To triangulate
+//! self-intersecting shapes, resolve the intersections first.
+//!
+//! There are two algorithms. Small shapes are triangulated with ear clipping, which repeatedly cuts
+//! off a triangle at a convex vertex. Holes are first connected to the outer contour with bridges,
+//! so that the contour winds around them. This takes quadratic time, but is fast for small shapes.
+//! Large shapes are triangulated by first dividing them into monotone pieces with a sweep line,
+//! which are then triangulated in linear time. This takes `O(n log n)` time and handles holes
+//! directly. The sweep line is more sensitive to invalid input, such as a vertex that touches the
+//! middle of an edge. Its triangles are checked against the area of the shape, and if they don't
+//! match, the shape is triangulated with ear clipping instead.
+
+use std::cmp::{Ordering, Reverse}; //To sort vertices in the order of the sweep line, and around each vertex.
+use std::collections::{BTreeMap, VecDeque}; //To find where contours touch themselves, and to clean up both ends of a contour.
+use rayon::prelude::*; //For multi-threaded implementations.
+
+use crate::MultiPolygon; //Triangulate multi-polygons.
+use crate::Point2D; //To analyse the vertices.
+use crate::Polygon; //Triangulate polygons.
+use crate::PolygonBatch; //Triangulate batches of polygons at once.
+use crate::cost_model::{self, Backend, Operation}; //To choose the fastest implementation.
+use crate::operations::area; //To check that the triangles cover the whole shape, and to find holes.
+use crate::operations::containment; //To find which outer contour each hole is in.
+use crate::predicates::{compare_products, direction, orientation, sum_of_products_sign}; //To classify the vertices and to check which diagonals are valid.
+
+/// The maximum number of vertices for which ear clipping is used. Larger shapes are triangulated
+/// with a sweep line.
+const EAR_CLIPPING_LIMIT: usize = 64;
+
+/// Divide a polygon into triangles.
+///
+/// This chooses automatically whether to use ear clipping or a sweep line, depending on the number
+/// of vertices of the polygon.
+///
+/// # Arguments
+/// * `polygon` - The polygon to triangulate. It must not intersect itself.
+///
+/// # Result
+/// The indices of the vertices of the triangles, three for each triangle.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// let square = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let triangles = apex::operations::triangulate::triangulate_polygon(&square);
+/// assert_eq!(triangles.len(), 6, "Two triangles.");
+/// ```
+pub fn triangulate_polygon(polygon: &Polygon) -> Vec<u32> {
+	triangulate_polygon_with(polygon.host_vertices(), Algorithm::for_size(polygon.len()))
+}
+
+/// Divide a polygon into triangles with ear clipping.
+///
+/// This takes quadratic time, so it is only suitable for small polygons.
+///
+/// # Arguments
+/// * `polygon` - The polygon to triangulate. It must not intersect itself.
+///
+/// # Result
+/// The indices of the vertices of the triangles, three for each triangle.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// let l_shape = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 50 }, Point2D { x: 50, y: 50 }, Point2D { x: 50, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let triangles = apex::operations::triangulate::triangulate_polygon_ear_clipping(&l_shape);
+/// assert_eq!(triangles.len(), 12, "Four triangles.");
+/// ```
+pub fn triangulate_polygon_ear_clipping(polygon: &Polygon) -> Vec<u32> {
+	triangulate_polygon_with(polygon.host_vertices(), Algorithm::EAR_CLIPPING)
+}
+
+/// Divide a polygon into triangles with a sweep line.
+///
+/// The polygon is divided into pieces that are monotone in the Y direction, which are each
+/// triangulated separately.
+///
+/// # Arguments
+/// * `polygon` - The polygon to triangulate. It must not intersect itself.
+///
+/// # Result
+/// The indices of the vertices of the triangles, three for each triangle.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, Polygon};
+/// let l_shape = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 50 }, Point2D { x: 50, y: 50 }, Point2D { x: 50, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let triangles = apex::operations::triangulate::triangulate_polygon_sweep(&l_shape);
+/// assert_eq!(triangles.len(), 12, "Four triangles.");
+/// ```
+pub fn triangulate_polygon_sweep(polygon: &Polygon) -> Vec<u32> {
+	triangulate_polygon_with(polygon.host_vertices(), Algorithm::SWEEP)
+}
+
+/// Divide a multi-polygon into triangles.
+///
+/// Each outer contour is triangulated together with the holes inside of it. This chooses
+/// automatically whether to triangulate them on a single thread or on multiple threads. The cost
+/// model estimates which is fastest, from the number of vertices. For each outer contour, the
+/// algorithm is chosen like in `triangulate_polygon`.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to triangulate. Its contours must not intersect.
+///
+/// # Result
+/// The indices of the vertices of the triangles, three for each triangle.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, Point2D};
+/// let mut frame = MultiPolygon::new();
+/// frame.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// frame.push_contour([Point2D { x: 25, y: 25 }, Point2D { x: 25, y: 75 }, Point2D { x: 75, y: 75 }, Point2D { x: 75, y: 25 }]); //A hole.
+/// let triangles = apex::operations::triangulate::triangulate_multipolygon(&frame);
+/// assert_eq!(triangles.len(), 24, "Eight triangles around the hole.");
+/// ```
+pub fn triangulate_multipolygon(multi_polygon: &MultiPolygon) -> Vec<u32> {
+	match cost_model::choose(Operation::TRIANGULATE, multi_polygon.len(), multi_polygon.sync_status()) {
+		Backend::ST => triangulate_multipolygon_st(multi_polygon),
+		Backend::MT => triangulate_multipolygon_mt(multi_polygon),
+		Backend::GPU => unreachable!("There is no GPU implementation, so the cost model never chooses the GPU."),
+	}
+}
+
+/// Divide a multi-polygon into triangles, on a single thread.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to triangulate. Its contours must not intersect.
+///
+/// # Result
+/// The indices of the vertices of the triangles, three for each triangle.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, Point2D};
+/// let mut frame = MultiPolygon::new();
+/// frame.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// frame.push_contour([Point2D { x: 25, y: 25 }, Point2D { x: 25, y: 75 }, Point2D { x: 75, y: 75 }, Point2D { x: 75, y: 25 }]); //A hole.
+/// let triangles = apex::operations::triangulate::triangulate_multipolygon_st(&frame);
+/// assert_eq!(triangles.len(), 24, "Eight triangles around the hole.");
+/// ```
+pub fn triangulate_multipolygon_st(multi_polygon: &MultiPolygon) -> Vec<u32> {
+	let vertices = multi_polygon.host_vertices();
+	groups(vertices, contours(multi_polygon), Backend::ST).iter().flat_map(|group| {
+		triangulate_group(vertices, group, Algorithm::for_size(group.len()))
+	}).collect()
+}
+
+/// Divide a multi-polygon into triangles, on multiple threads.
+///
+/// The outer contours are divided over the threads, each with the holes inside of them.
+///
+/// # Arguments
+/// * `multi_polygon` - The multi-polygon to triangulate. Its contours must not intersect.
+///
+/// # Result
+/// The indices of the vertices of the triangles, three for each triangle.
+///
+/// # Examples
+/// ```
+/// use apex::{MultiPolygon, Point2D};
+/// let mut frame = MultiPolygon::new();
+/// frame.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// frame.push_contour([Point2D { x: 25, y: 25 }, Point2D { x: 25, y: 75 }, Point2D { x: 75, y: 75 }, Point2D { x: 75, y: 25 }]); //A hole.
+/// let triangles = apex::operations::triangulate::triangulate_multipolygon_mt(&frame);
+/// assert_eq!(triangles.len(), 24, "Eight triangles around the hole.");
+/// ```
+pub fn triangulate_multipolygon_mt(multi_polygon: &MultiPolygon) -> Vec<u32> {
+	let vertices = multi_polygon.host_vertices();
+	groups(vertices, contours(multi_polygon), Backend::MT).par_iter().flat_map_iter(|group| {
+		triangulate_group(vertices, group, Algorithm::for_size(group.len()))
+	}).collect()
+}
+
+/// Divide each polygon of a batch into triangles.
+///
+/// This chooses automatically whether to triangulate on a single thread or on multiple threads.
+/// The cost model estimates which is fastest, from the total number of vertices in the batch. For
+/// each polygon, the algorithm is chosen like in `triangulate_polygon`.
+///
+/// # Arguments
+/// * `batch` - The polygons to triangulate. They must not intersect themselves.
+///
+/// # Result
+/// For each polygon, the indices of the vertices of its triangles, three for each triangle. The
+/// indices refer to the vertices of that polygon, not to the vertices of the whole batch.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// let triangles = apex::operations::triangulate::triangulate_batch(&batch);
+/// assert_eq!(triangles[0].len(), 6, "Two triangles.");
+/// assert_eq!(triangles[1].len(), 3, "Just one triangle.");
+/// ```
+pub fn triangulate_batch(batch: &PolygonBatch) -> Vec<Vec<u32>> {
+	match cost_model::choose(Operation::TRIANGULATE, batch.len(), batch.sync_status()) {
+		Backend::ST => triangulate_batch_st(batch),
+		Backend::MT => triangulate_batch_mt(batch),
+		Backend::GPU => unreachable!("There is no GPU implementation, so the cost model never chooses the GPU."),
+	}
+}
+
+/// Divide each polygon of a batch into triangles, on a single thread.
+///
+/// # Arguments
+/// * `batch` - The polygons to triangulate. They must not intersect themselves.
+///
+/// # Result
+/// For each polygon, the indices of the vertices of its triangles, three for each triangle. The
+/// indices refer to the vertices of that polygon, not to the vertices of the whole batch.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let triangles = apex::operations::triangulate::triangulate_batch_st(&batch);
+/// assert_eq!(triangles[0].len(), 6, "Two triangles.");
+/// ```
+pub fn triangulate_batch_st(batch: &PolygonBatch) -> Vec<Vec<u32>> {
+	batch.polygons().map(|vertices| triangulate_polygon_with(vertices, Algorithm::for_size(vertices.len()))).collect()
+}
+
+/// Divide each polygon of a batch into triangles, on multiple threads.
+///
+/// The polygons are divided over the threads, and each polygon is triangulated on a single thread.
+///
+/// # Arguments
+/// * `batch` - The polygons to triangulate. They must not intersect themselves.
+///
+/// # Result
+/// For each polygon, the indices of the vertices of its triangles, three for each triangle. The
+/// indices refer to the vertices of that polygon, not to the vertices of the whole batch.
+///
+/// # Examples
+/// ```
+/// use apex::{Point2D, PolygonBatch};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+/// let triangles = apex::operations::triangulate::triangulate_batch_mt(&batch);
+/// assert_eq!(triangles[0].len(), 6, "Two triangles.");
+/// ```
+pub fn triangulate_batch_mt(batch: &PolygonBatch) -> Vec<Vec<u32>> {
+	let polygons: Vec<&[Point2D]> = batch.polygons().collect();
+	polygons.into_par_iter().map(|vertices| triangulate_polygon_with(vertices, Algorithm::for_size(vertices.len()))).collect()
+}
+
+/// The algorithms to triangulate an outer contour with its holes.
+#[allow(non_camel_case_types)] //The variants are capitalised like those of the other enums, with underscores between words.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Algorithm {
+	/// Cut off triangles at convex vertices, after bridging the holes to the outer contour.
+	EAR_CLIPPING,
+
+	/// Divide the shape into monotone pieces with a sweep line, and triangulate those.
+	SWEEP,
+}
+
+impl Algorithm {
+	/// Choose the fastest algorithm for a shape with a certain number of vertices.
+	fn for_size(num_vertices: usize) -> Algorithm {
+		if num_vertices <= EAR_CLIPPING_LIMIT { Algorithm::EAR_CLIPPING } else { Algorithm::SWEEP }
+	}
+}
+
+/// An outer contour together with the holes inside of it, which are triangulated together.
+struct Group {
+	/// The indices of the vertices of the outer contour.
+	outer: Vec<u32>,
+
+	/// The indices of the vertices of each hole.
+	holes: Vec<Vec<u32>>,
+
+	/// Whether the outer contour is clockwise, and the holes counter-clockwise. This is the case
+	/// for polygons with negative area, and for holes that aren't inside any outer contour.
+	clockwise: bool,
+}
+
+impl Group {
+	/// Get the total number of vertices in the group.
+	fn len(&self) -> usize {
+		self.outer.len() + self.holes.iter().map(Vec::len).sum::<usize>()
+	}
+}
+
+/// A vertex in a doubly linked list of vertices, for the triangulation algorithms.
+#[derive(Clone, Copy, Debug)]
+struct Node {
+	/// The index of the vertex in the original shape.
+	index: u32,
+
+	/// The position of the vertex.
+	point: Point2D,
+
+	/// The node before this one in its contour.
+	prev: usize,
+
+	/// The node after this one in its contour.
+	next: usize,
+
+	/// The direction in which the sweep line treats the vertex as being moved by an infinitely small
+	/// distance. This separates vertices at the same position, by moving each into its own part of
+	/// the interior. For all other vertices, this is zero.
+	nudge: (i128, i128),
+}
+
+/// How a vertex relates to its neighbours, from the point of view of a sweep line going down.
+#[allow(non_camel_case_types)] //The variants are capitalised like those of the other enums, with underscores between words.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum VertexKind {
+	/// Both neighbours are below, and the interior is between them.
+	START,
+
+	/// Both neighbours are below, and the interior is around them. The part of the shape above is
+	/// split in two here.
+	SPLIT,
+
+	/// Both neighbours are above, and the interior is between them.
+	END,
+
+	/// Both neighbours are above, and the interior is around them. Two parts of the shape merge
+	/// here.
+	MERGE,
+
+	/// One neighbour is above, and the other below.
+	REGULAR,
+}
+
+/// Triangulate a polygon with a specific algorithm.
+///
+/// # Arguments
+/// * `vertices` - The vertices of the polygon to triangulate.
+/// * `algorithm` - The algorithm to use.
+fn triangulate_polygon_with(vertices: &[Point2D], algorithm: Algorithm) -> Vec<u32> {
+	groups(vertices, vec![(0..vertices.len() as u32).collect()], Backend::ST).iter().flat_map(|group| {
+		triangulate_group(vertices, group, algorithm)
+	}).collect()
+}
+
+/// List the indices of the vertices of each contour of a multi-polygon.
+fn contours(multi_polygon: &MultiPolygon) -> Vec<Vec<u32>> {
+	multi_polygon.contour_starts().windows(2).map(|range| (range[0] as u32..range[1] as u32).collect()).collect()
+}
+
+/// Divide a contour into loops where it touches itself.
+///
+/// Wherever the contour comes back to a position where it was before, the part in between is split
+/// off as a separate loop. The loops together have the same area as the contour, but each loop may
+/// have a different orientation. Loops without area are left out.
+///
+/// # Arguments
+/// * `vertices` - The vertices that the indices refer to.
+/// * `contour` - The indices of the vertices of the contour.
+///
+/// # Result
+/// The indices of the vertices of each loop.
+fn loops(vertices: &[Point2D], contour: Vec<u32>) -> Vec<Vec<u32>> {
+	let mut sorted: Vec<Point2D> = contour.iter().map(|index| vertices[*index as usize]).collect();
+	sorted.sort_unstable();
+	if sorted.windows(2).all(|pair| pair[0] != pair[1]) {
+		return vec![contour]; //The contour doesn't touch itself.
+	}
+	let mut loops = Vec::new();
+	let mut current: Vec<u32> = Vec::with_capacity(contour.len());
+	let mut positions: BTreeMap<Point2D, usize> = BTreeMap::new(); //Where each position is in the current loop.
+	for index in contour {
+		let point = vertices[index as usize];
+		match positions.get(&point) {
+			Some(position) => {
+				let position = *position;
+				for removed in &current[position + 1..] {
+					positions.remove(&vertices[*removed as usize]);
+				}
+				loops.push(current[position..].to_vec());
+				current.truncate(position + 1);
+			}
+			None => {
+				positions.insert(point, current.len());
+				current.push(index);
+			}
+		}
+	}
+	loops.push(current);
+	loops.retain(|contour_loop| contour_loop.len() >= 3);
+	loops
+}
+
+/// Divide contours into groups of an outer contour with the holes inside of it.
+///
+/// Contours that touch themselves are first divided into loops, which are grouped separately. Each
+/// hole is assigned to the smallest outer contour that contains it. Holes that are not inside any
+/// outer contour form a group of their own, which is filled with clockwise triangles. Contours
+/// without area are left out.
+///
+/// # Arguments
+/// * `vertices` - The vertices that the indices refer to.
+/// * `contours` - The indices of the vertices of each contour.
+/// * `backend` - Whether to divide the contours and find the outer contour of the holes in
+/// parallel.
+fn groups(vertices: &[Point2D], contours: Vec<Vec<u32>>, backend: Backend) -> Vec<Group> {
+	let mut contours: Vec<Vec<u32>> = match backend {
+		Backend::ST => contours.into_iter().flat_map(|contour| loops(vertices, contour)).collect(),
+		_ => contours.into_par_iter().flat_map_iter(|contour| loops(vertices, contour)).collect(),
+	};
+	let points: Vec<Vec<Point2D>> = contours.iter().map(|contour| contour.iter().map(|index| vertices[*index as usize]).collect()).collect();
+	let areas: Vec<i128> = points.iter().map(|contour| area::doubled_area_st(contour)).collect();
+	let outers: Vec<usize> = (0..areas.len()).filter(|contour| areas[*contour] > 0).collect();
+	let holes: Vec<usize> = (0..areas.len()).filter(|contour| areas[*contour] < 0).collect();
+	let owner = |hole: &usize| -> Option<usize> {
+		let point = points[*hole].iter().find_map(|vertex| {
+			let inside = outers.iter().map(|outer| containment::winding_number_st(&points[*outer], vertex)).collect::<Option<Vec<i32>>>()?;
+			Some(inside) //Use the first vertex that is not on the boundary of any outer contour.
+		})?;
+		outers.iter().zip(point).filter(|(_, winding_number)| *winding_number != 0).min_by_key(|(outer, _)| areas[**outer]).map(|(outer, _)| *outer)
+	};
+	let owners: Vec<Option<usize>> = match backend {
+		Backend::ST => holes.iter().map(owner).collect(),
+		_ => holes.par_iter().map(owner).collect(),
+	};
+
+	let mut groups: Vec<Group> = outers.iter().map(|outer| Group { outer: std::mem::take(&mut contours[*outer]), holes: Vec::new(), clockwise: false }).collect();
+	for (hole, owner) in holes.iter().zip(owners) {
+		let hole_indices = std::mem::take(&mut contours[*hole]);
+		match owner {
+			Some(outer) => groups[outers.binary_search(&outer).unwrap()].holes.push(hole_indices),
+			None => groups.push(Group { outer: hole_indices, holes: Vec::new(), clockwise: true }),
+		}
+	}
+	groups
+}
+
+/// Triangulate an outer contour together with its holes.
+///
+/// Triangles of clockwise groups are turned clockwise again at the end. If the sweep line gives
+/// triangles that don't cover the shape, the group is triangulated with ear clipping instead.
+///
+/// # Arguments
+/// * `vertices` - The vertices that the indices in the group refer to.
+/// * `group` - The contours to triangulate.
+/// * `algorithm` - The algorithm to use.
+fn triangulate_group(vertices: &[Point2D], group: &Group, algorithm: Algorithm) -> Vec<u32> {
+	let (outer, holes) = clean_group(vertices, group);
+	if outer.len() < 3 {
+		return Vec::new(); //Nothing left to fill.
+	}
+	let triangles = match algorithm {
+		Algorithm::EAR_CLIPPING => ear_clipping(vertices, &outer, &holes),
+		Algorithm::SWEEP => checked_sweep(vertices, &outer, &holes).unwrap_or_else(|| ear_clipping(vertices, &outer, &holes)),
+	};
+	triangles.into_iter().flat_map(|[a, b, c]| if group.clockwise { [a, c, b] } else { [a, b, c] }).collect()
+}
+
+/// Clean up the contours of a group, and orient them so that the interior is to the left of every
+/// edge.
+///
+/// # Arguments
+/// * `vertices` - The vertices that the indices in the group refer to.
+/// * `group` - The contours to clean up.
+///
+/// # Result
+/// The outer contour and the holes. Holes without area are left out.
+fn clean_group(vertices: &[Point2D], group: &Group) -> (Vec<u32>, Vec<Vec<u32>>) {
+	let orient = |contour: &Vec<u32>| -> Vec<u32> {
+		if group.clockwise { contour.iter().rev().copied().collect() } else { contour.clone() }
+	};
+	let mut touching: Vec<Point2D> = [&group.outer].into_iter().chain(&group.holes).flatten().map(|index| vertices[*index as usize]).collect();
+	touching.sort_unstable();
+	touching = touching.windows(2).filter(|pair| pair[0] == pair[1]).map(|pair| pair[0]).collect();
+	touching.dedup();
+	let outer = clean_contour(vertices, &orient(&group.outer), &touching);
+	let holes: Vec<Vec<u32>> = group.holes.iter().map(|hole| clean_contour(vertices, &orient(hole), &touching)).filter(|hole| hole.len() >= 3).collect();
+	(outer, holes)
+}
+
+/// Triangulate a contour with holes with a sweep line, and check that the triangles cover it.
+///
+/// The signed areas of the triangles must add up to the area of the shape. The sweep line relies on
+/// the contours meeting only at their vertices. Where a vertex touches the middle of another edge,
+/// its triangles can overlap or leave gaps.
+///
+/// # Arguments
+/// * `vertices` - The vertices that the indices refer to.
+/// * `outer` - The outer contour, counter-clockwise.
+/// * `holes` - The holes, clockwise.
+///
+/// # Result
+/// The triangles, counter-clockwise, or `None` if they don't cover the shape.
+fn checked_sweep(vertices: &[Point2D], outer: &[u32], holes: &[Vec<u32>]) -> Option<Vec<[u32; 3]>> {
+	let triangles = sweep(vertices, outer, holes);
+	let doubled_area: i128 = [outer].into_iter().chain(holes.iter().map(Vec::as_slice)).map(|contour| {
+		area::doubled_area_st(&contour.iter().map(|index| vertices[*index as usize]).collect::<Vec<Point2D>>())
+	}).sum();
+	(doubled_triangles_area(vertices, triangles.as_flattened()) == doubled_area).then_some(triangles)
+}
+
+/// Remove the vertices of a contour that don't contribute to its shape.
+///
+/// These are vertices that are equal to the vertex before them, and vertices that are collinear with
+/// the vertices before and after them. The latter includes the tips of spikes that go out and back
+/// along the same line. Vertices that lie straight between their neighbours are kept where another
+/// contour touches them, since removing them would leave that contour touching the middle of an
+/// edge.
+///
+/// # Arguments
+/// * `vertices` - The vertices that the indices refer to.
+/// * `contour` - The indices of the vertices of the contour.
+/// * `touching` - The positions where contours touch each other, sorted.
+///
+/// # Result
+/// The indices of the remaining vertices. If fewer than three remain, the contour has no area.
+fn clean_contour(vertices: &[Point2D], contour: &[u32], touching: &[Point2D]) -> Vec<u32> {
+	let point = |index: u32| &vertices[index as usize];
+	let redundant = |before: u32, index: u32, after: u32| -> bool {
+		if orientation(point(before), point(index), point(after)) != 0 {
+			return false;
+		}
+		let incoming = direction(point(before), point(index));
+		let outgoing = direction(point(index), point(after));
+		let straight = compare_products(incoming.0, outgoing.0, -incoming.1, outgoing.1) == Ordering::Greater;
+		!straight || touching.binary_search(point(index)).is_err()
+	};
+	let mut cleaned: VecDeque<u32> = VecDeque::with_capacity(contour.len());
+	for index in contour {
+		loop {
+			let len = cleaned.len();
+			if len >= 1 && point(cleaned[len - 1]) == point(*index) {
+				break; //A duplicate. Keep the earlier one.
+			}
+			if len >= 2 && redundant(cleaned[len - 2], cleaned[len - 1], *index) {
+				cleaned.pop_back();
+				continue;
+			}
+			cleaned.push_back(*index);
+			break;
+		}
+	}
+	//The start and end of the contour may still be duplicate or collinear with each other.
+	while cleaned.len() >= 3 {
+		let len = cleaned.len();
+		if point(cleaned[len - 1]) == point(cleaned[0]) || redundant(cleaned[len - 2], cleaned[len - 1], cleaned[0]) {
+			cleaned.pop_back();
+		} else if redundant(cleaned[len - 1], cleaned[0], cleaned[1]) {
+			cleaned.pop_front();
+		} else {
+			break;
+		}
+	}
+	cleaned.into()
+}
+
+/// Add the nodes of a contour to a list of nodes, as a closed ring.
+///
+/// # Arguments
+/// * `nodes` - The list of nodes to add the ring to.
+/// * `vertices` - The vertices that the indices refer to.
+/// * `contour` - The indices of the vertices of the contour.
+///
+/// # Result
+/// The first node of the ring.
+fn add_ring(nodes: &mut Vec<Node>, vertices: &[Point2D], contour: &[u32]) -> usize {
+	let start = nodes.len();
+	let len = contour.len();
+	for (i, index) in contour.iter().enumerate() {
+		nodes.push(Node {
+			index: *index,
+			point: vertices[*index as usize],
+			prev: start + (i + len - 1) % len,
+			next: start + (i + 1) % len,
+			nudge: (0, 0),
+		});
+	}
+	start
+}
+
+/// Triangulate a contour with holes with ear clipping.
+///
+/// The holes are processed from right to left. Each is connected to the outer contour with a bridge
+/// from its rightmost vertex to the nearest vertex of the outer contour that it can see. The bridge
+/// is traversed in both directions, so that the outer contour becomes a single contour that winds
+/// around the hole. Holes that touch the outer contour or another hole at a vertex are joined to it
+/// there instead, without a bridge.
+///
+/// # Arguments
+/// * `vertices` - The vertices that the indices refer to.
+/// * `outer` - The outer contour, counter-clockwise.
+/// * `holes` - The holes, clockwise.
+///
+/// # Result
+/// The triangles, counter-clockwise.
+fn ear_clipping(vertices: &[Point2D], outer: &[u32], holes: &[Vec<u32>]) -> Vec<[u32; 3]> {
+	let mut nodes = Vec::with_capacity(outer.len() + holes.iter().map(|hole| hole.len() + 2).sum::<usize>());
+	let start = add_ring(&mut nodes, vertices, outer);
+	let mut hole_starts: Vec<usize> = holes.iter().map(|hole| {
+		let hole_start = add_ring(&mut nodes, vertices, hole);
+		(hole_start..hole_start + hole.len()).max_by_key(|node| (nodes[*node].point.x, nodes[*node].point.y)).unwrap()
+	}).collect();
+	hole_starts.sort_by_key(|node| Reverse((nodes[*node].point.x, nodes[*node].point.y)));
+	let mut order: Vec<usize> = (0..nodes.len()).collect();
+	order.sort_unstable_by_key(|node| nodes[*node].point);
+	let pinches: Vec<Vec<usize>> = order.chunk_by(|a, b| nodes[*a].point == nodes[*b].point).filter(|pinch| pinch.len() > 1).map(|pinch| pinch.to_vec()).collect();
+	for pinch in pinches {
+		reconnect(&mut nodes, &pinch);
+	}
+	for (i, hole) in hole_starts.iter().enumerate() {
+		let mut obstacles = vec![start, *hole];
+		obstacles.extend_from_slice(&hole_starts[i + 1..]);
+		let mut candidates = ring(&nodes, start);
+		if candidates.contains(hole) {
+			continue; //Already joined to the outer contour where they touch.
+		}
+		let target = nodes[*hole].point;
+		candidates.sort_by_key(|node| squared_distance(&nodes[*node].point, &target));
+		let bridge = candidates.iter().copied().find(|node| can_bridge(&nodes, *node, *hole, &obstacles)).unwrap_or(candidates[0]); //Only for invalid input is there no visible vertex.
+		split(&mut nodes, bridge, *hole);
+	}
+	clip_ears(&mut nodes, start)
+}
+
+/// List the nodes of a ring, starting at a certain node.
+fn ring(nodes: &[Node], start: usize) -> Vec<usize> {
+	let mut result = vec![start];
+	let mut node = nodes[start].next;
+	while node != start {
+		result.push(node);
+		node = nodes[node].next;
+	}
+	result
+}
+
+/// Test whether a hole can be connected to a vertex of the outer contour with a bridge.
+///
+/// The bridge must leave both vertices towards the interior, and must not touch any edge, except
+/// the edges that end at the same vertices.
+///
+/// # Arguments
+/// * `nodes` - The nodes of all contours.
+/// * `outer` - The node on the outer contour to connect to.
+/// * `hole` - The node on the hole to connect.
+/// * `obstacles` - A node on each of the rings whose edges could block the bridge.
+fn can_bridge(nodes: &[Node], outer: usize, hole: usize, obstacles: &[usize]) -> bool {
+	let start = nodes[outer].point;
+	let end = nodes[hole].point;
+	if start == end || !locally_inside(nodes, outer, &end) || !locally_inside(nodes, hole, &start) {
+		return false;
+	}
+	obstacles.iter().all(|obstacle| ring(nodes, *obstacle).iter().all(|node| {
+		!blocks(&start, &end, &nodes[*node].point, &nodes[nodes[*node].next].point)
+	}))
+}
+
+/// Connect a node of a hole to a node of the outer contour with a bridge.
+///
+/// Both nodes are duplicated, so that the contour can go to the hole, around the hole and back
+/// along the bridge.
+///
+/// # Arguments
+/// * `nodes` - The nodes of all contours.
+/// * `outer` - The node on the outer contour to connect to.
+/// * `hole` - The node on the hole to connect.
+fn split(nodes: &mut Vec<Node>, outer: usize, hole: usize) {
+	let outer_copy = nodes.len();
+	nodes.push(nodes[outer]);
+	let hole_copy = nodes.len();
+	nodes.push(nodes[hole]);
+	let outer_next = nodes[outer].next;
+	let hole_prev = nodes[hole].prev;
+	nodes[outer].next = hole;
+	nodes[hole].prev = outer;
+	nodes[outer_copy].next = outer_next;
+	nodes[outer_next].prev = outer_copy;
+	nodes[hole_copy].next = outer_copy;
+	nodes[outer_copy].prev = hole_copy;
+	nodes[hole_prev].next = hole_copy;
+	nodes[hole_copy].prev = hole_prev;
+}
+
+/// Cut off ears from a ring of nodes until only a triangle remains.
+///
+/// Vertices that are collinear with their neighbours are removed without creating a triangle. If a
+/// full round finds no ears, which only happens for invalid input, a convex vertex is cut off
+/// anyway so that the algorithm finishes.
+///
+/// # Arguments
+/// * `nodes` - The nodes of the ring.
+/// * `start` - The node to start at.
+///
+/// # Result
+/// The triangles, counter-clockwise.
+fn clip_ears(nodes: &mut [Node], start: usize) -> Vec<[u32; 3]> {
+	let mut triangles = Vec::new();
+	let mut remaining = ring(nodes, start).len();
+	let mut current = start;
+	let mut stalled = 0;
+	while remaining > 3 {
+		let prev = nodes[current].prev;
+		let next = nodes[current].next;
+		let turn = orientation(&nodes[prev].point, &nodes[current].point, &nodes[next].point);
+		if turn == 0 || (turn > 0 && (is_ear(nodes, current) || stalled > remaining)) {
+			if turn > 0 {
+				triangles.push([nodes[prev].index, nodes[current].index, nodes[next].index]);
+			}
+			nodes[prev].next = next;
+			nodes[next].prev = prev;
+			remaining -= 1;
+			current = prev; //The previous vertex may have become an ear.
+			stalled = 0;
+		} else {
+			current = next;
+			stalled += 1;
+			if stalled > 2 * remaining {
+				break; //No convex vertices at all. The input must be invalid.
+			}
+		}
+	}
+	let prev = nodes[current].prev;
+	let next = nodes[current].next;
+	if remaining == 3 && orientation(&nodes[prev].point, &nodes[current].point, &nodes[next].point) > 0 {
+		triangles.push([nodes[prev].index, nodes[current].index, nodes[next].index]);
+	}
+	triangles
+}
+
+/// Test whether a convex vertex is an ear, which can be cut off.
+///
+/// The diagonal between its neighbours must be inside the contour at both ends, and there may not
+/// be any other vertices inside or on the boundary of the triangle. Vertices at the same position as
+/// the corners of the triangle are copies made by bridges, and are allowed.
+fn is_ear(nodes: &[Node], current: usize) -> bool {
+	let prev = nodes[current].prev;
+	let next = nodes[current].next;
+	let (a, b, c) = (&nodes[prev].point, &nodes[current].point, &nodes[next].point);
+	if !locally_inside(nodes, prev, c) || !locally_inside(nodes, next, a) {
+		return false;
+	}
+	let mut node = nodes[next].next;
+	while node != prev {
+		let point = &nodes[node].point;
+		if point != a && point != b && point != c && orientation(a, b, point) >= 0 && orientation(b, c, point) >= 0 && orientation(c, a, point) >= 0 {
+			return false;
+		}
+		node = nodes[node].next;
+	}
+	true
+}
+
+/// Test whether the direction from a node towards a point goes into the interior of the contour.
+///
+/// The interior is to the left of the edges of the contour. At a convex vertex, the direction must
+/// be left of both edges. At a reflex vertex, it must be left of either one.
+fn locally_inside(nodes: &[Node], node: usize, target: &Point2D) -> bool {
+	let prev = &nodes[nodes[node].prev].point;
+	let point = &nodes[node].point;
+	let next = &nodes[nodes[node].next].point;
+	if orientation(prev, point, next) >= 0 {
+		orientation(prev, point, target) > 0 && orientation(point, next, target) > 0
+	} else {
+		orientation(prev, point, target) > 0 || orientation(point, next, target) > 0
+	}
+}
+
+/// Test whether an edge blocks a bridge.
+///
+/// Edges that touch the bridge only at its endpoints don't block it, unless they overlap with it.
+///
+/// # Arguments
+/// * `start` - The start of the bridge.
+/// * `end` - The end of the bridge.
+/// * `edge_start` - The start of the edge.
+/// * `edge_end` - The end of the edge.
+fn blocks(start: &Point2D, end: &Point2D, edge_start: &Point2D, edge_end: &Point2D) -> bool {
+	if edge_start == start || edge_start == end || edge_end == start || edge_end == end {
+		return on_open_segment(edge_start, start, end) || on_open_segment(edge_end, start, end) || on_open_segment(start, edge_start, edge_end) || on_open_segment(end, edge_start, edge_end);
+	}
+	let side_start = orientation(start, end, edge_start).signum();
+	let side_end = orientation(start, end, edge_end).signum();
+	let side_bridge_start = orientation(edge_start, edge_end, start).signum();
+	let side_bridge_end = orientation(edge_start, edge_end, end).signum();
+	if side_start * side_end < 0 && side_bridge_start * side_bridge_end < 0 {
+		return true; //A proper crossing.
+	}
+	on_open_segment(edge_start, start, end) || on_open_segment(edge_end, start, end) || on_open_segment(start, edge_start, edge_end) || on_open_segment(end, edge_start, edge_end)
+}
+
+/// Test whether a point is on a line segment, excluding its endpoints.
+fn on_open_segment(point: &Point2D, start: &Point2D, end: &Point2D) -> bool {
+	orientation(start, end, point) == 0
+		&& (point.x as i128 - start.x as i128) * (end.x as i128 - point.x as i128) + (point.y as i128 - start.y as i128) * (end.y as i128 - point.y as i128) > 0
+}
+
+/// Compute the squared distance between two points.
+fn squared_distance(a: &Point2D, b: &Point2D) -> i128 {
+	let dx = a.x as i128 - b.x as i128;
+	let dy = a.y as i128 - b.y as i128;
+	dx * dx + dy * dy
+}
+
+/// Triangulate a contour with holes with a sweep line.
+///
+/// A sweep line goes down over the vertices, finding diagonals that divide the shape into pieces
+/// that are monotone in the Y direction. Vertices with the same Y coordinate are processed from left
+/// to right, as if the sweep line were rotated very slightly. The edges that the sweep line
+/// currently crosses with the interior on their right are kept in a sorted list, with for each edge
+/// the lowest vertex above the sweep line that can see it, its helper. Wherever the shape splits or
+/// merges, a diagonal to a helper is added. The pieces are then found by walking along the edges and
+/// diagonals, and triangulated one by one.
+///
+/// Vertices at the same position, where contours touch, are first separated from each other.
+///
+/// # Arguments
+/// * `vertices` - The vertices that the indices refer to.
+/// * `outer` - The outer contour, counter-clockwise.
+/// * `holes` - The holes, clockwise.
+///
+/// # Result
+/// The triangles, counter-clockwise.
+fn sweep(vertices: &[Point2D], outer: &[u32], holes: &[Vec<u32>]) -> Vec<[u32; 3]> {
+	let mut nodes = Vec::with_capacity(outer.len() + holes.iter().map(Vec::len).sum::<usize>());
+	add_ring(&mut nodes, vertices, outer);
+	for hole in holes {
+		add_ring(&mut nodes, vertices, hole);
+	}
+	let mut order: Vec<usize> = (0..nodes.len()).collect();
+	order.par_sort_unstable_by_key(|node| (Reverse(nodes[*node].point.y), nodes[*node].point.x, *node));
+	separate_pinches(&mut nodes, &mut order);
+	let mut rank = vec![0; nodes.len()];
+	for (position, node) in order.iter().enumerate() {
+		rank[*node] = position;
+	}
+	let kinds: Vec<VertexKind> = (0..nodes.len()).map(|node| {
+		let prev = nodes[node].prev;
+		let next = nodes[node].next;
+		let convex = nudged_orientation(&nodes[prev], &nodes[node], &nodes[next]) == Ordering::Greater;
+		if rank[node] < rank[prev] && rank[node] < rank[next] {
+			if convex { VertexKind::START } else { VertexKind::SPLIT }
+		} else if rank[node] > rank[prev] && rank[node] > rank[next] {
+			if convex { VertexKind::END } else { VertexKind::MERGE }
+		} else {
+			VertexKind::REGULAR
+		}
+	}).collect();
+
+	let mut status: Vec<(usize, usize)> = Vec::new(); //For each edge, the node it starts at, and its helper.
+	let mut diagonals: Vec<(usize, usize)> = Vec::new();
+	for node in order {
+		let left_of = |status: &Vec<(usize, usize)>| status.partition_point(|(edge, _)| edge_left_of(&nodes, *edge, node)).checked_sub(1);
+		match kinds[node] {
+			VertexKind::START => insert_edge(&nodes, &mut status, node),
+			VertexKind::END => end_edge(&nodes, &kinds, &mut status, &mut diagonals, node),
+			VertexKind::SPLIT => {
+				if let Some(left) = left_of(&status) {
+					diagonals.push((node, status[left].1));
+					status[left].1 = node;
+				}
+				insert_edge(&nodes, &mut status, node);
+			}
+			VertexKind::MERGE => {
+				end_edge(&nodes, &kinds, &mut status, &mut diagonals, node);
+				if let Some(left) = left_of(&status) {
+					if kinds[status[left].1] == VertexKind::MERGE {
+						diagonals.push((node, status[left].1));
+					}
+					status[left].1 = node;
+				}
+			}
+			VertexKind::REGULAR => {
+				if rank[nodes[node].prev] < rank[node] { //Going down, so the interior is to the right.
+					end_edge(&nodes, &kinds, &mut status, &mut diagonals, node);
+					insert_edge(&nodes, &mut status, node);
+				} else if let Some(left) = left_of(&status) {
+					if kinds[status[left].1] == VertexKind::MERGE {
+						diagonals.push((node, status[left].1));
+					}
+					status[left].1 = node;
+				}
+			}
+		}
+	}
+
+	let mut triangles = Vec::with_capacity(nodes.len());
+	for piece in monotone_pieces(&nodes, diagonals) {
+		triangulate_monotone(&nodes, &rank, &piece, &mut triangles);
+	}
+	triangles
+}
+
+/// Test whether the edge starting at a node is to the left of another node, at the height of that
+/// node.
+///
+/// The edge must cross the height of the other node. The nodes are nudged first. If the other node
+/// is still on the line through the edge, the edge is only considered to be left of it if it ends
+/// left of it.
+fn edge_left_of(nodes: &[Node], edge: usize, node: usize) -> bool {
+	let upper = &nodes[edge];
+	let lower = &nodes[upper.next];
+	let point = &nodes[node].point;
+	match nudged_orientation(lower, upper, &nodes[node]) {
+		Ordering::Less => true, //The node is right of the edge, going up.
+		Ordering::Greater => false,
+		Ordering::Equal => upper.point.x < point.x && lower.point.x < point.x,
+	}
+}
+
+/// Insert the edge starting at a node into the status of the sweep line, with that node as its
+/// helper.
+fn insert_edge(nodes: &[Node], status: &mut Vec<(usize, usize)>, node: usize) {
+	let position = status.partition_point(|(edge, _)| edge_left_of(nodes, *edge, node));
+	status.insert(position, (node, node));
+}
+
+/// Remove the edge ending at a node from the status of the sweep line.
+///
+/// If the helper of the edge was a merge vertex, a diagonal is added from the node to it, since the
+/// merge vertex has no connection downwards yet.
+fn end_edge(nodes: &[Node], kinds: &[VertexKind], status: &mut Vec<(usize, usize)>, diagonals: &mut Vec<(usize, usize)>, node: usize) {
+	let edge = nodes[node].prev;
+	let near = status.partition_point(|(other, _)| edge_left_of(nodes, *other, node));
+	let position = status[near..].iter().position(|(other, _)| *other == edge).map(|offset| near + offset)
+		.or_else(|| status.iter().position(|(other, _)| *other == edge)); //Only for invalid input is the edge elsewhere.
+	if let Some(position) = position {
+		let helper = status[position].1;
+		if kinds[helper] == VertexKind::MERGE {
+			diagonals.push((node, helper));
+		}
+		status.remove(position);
+	}
+}
+
+/// Separate the vertices of a shape that are at the same position, where the shape touches itself.
+///
+/// The sweep line needs every vertex to be at a different position. Where multiple vertices are at
+/// the same position, the edges there are first reconnected, so that each vertex connects the two
+/// edges around one part of the interior. This may merge a hole with the contour that it touches,
+/// or divide a contour into loops, but it doesn't change the shape. Each of these vertices is then
+/// nudged into its own part of the interior, by an infinitely small distance. This separates the
+/// vertices without making any edges cross.
+///
+/// # Arguments
+/// * `nodes` - The nodes of all contours of the shape.
+/// * `order` - The nodes in the order of the sweep line. Nodes at the same position are reordered to
+/// where they are nudged to.
+fn separate_pinches(nodes: &mut [Node], order: &mut [usize]) {
+	let mut start = 0;
+	while start < order.len() {
+		let point = nodes[order[start]].point;
+		let end = order[start..].iter().position(|node| nodes[*node].point != point).map_or(order.len(), |length| start + length);
+		if end - start > 1 {
+			let pinch = &mut order[start..end];
+			reconnect(nodes, pinch);
+			for node in pinch.iter() {
+				nodes[*node].nudge = interior_direction(nodes, *node);
+			}
+			pinch.sort_unstable_by_key(|node| (Reverse(nodes[*node].nudge.1), nodes[*node].nudge.0, *node));
+		}
+		start = end;
+	}
+}
+
+/// Reconnect the edges at a position where multiple vertices are, so that each vertex connects the
+/// two edges around one part of the interior.
+///
+/// Going counter-clockwise around the position, the interior is between each outgoing edge and the
+/// incoming edge after it. If the edges don't alternate between outgoing and incoming, the contours
+/// overlap there, and the edges are left as they are.
+///
+/// # Arguments
+/// * `nodes` - The nodes of all contours of the shape.
+/// * `pinch` - The nodes at the same position.
+fn reconnect(nodes: &mut [Node], pinch: &[usize]) {
+	let centre = nodes[pinch[0]];
+	let far_end = |(node, outgoing): &(usize, bool)| if *outgoing { nodes[*node].next } else { nodes[*node].prev };
+	let mut edges: Vec<(usize, bool)> = pinch.iter().flat_map(|node| [(*node, true), (*node, false)]).collect(); //Each node, and whether it's its outgoing edge.
+	edges.sort_by(|a, b| angle_order(&centre, &nodes[far_end(a)], &nodes[far_end(b)]));
+	let first_outgoing = edges.iter().position(|(_, outgoing)| *outgoing).unwrap();
+	edges.rotate_left(first_outgoing);
+	if edges.chunks_exact(2).any(|pair| !pair[0].1 || pair[1].1) {
+		return; //Only invalid input has overlapping contours.
+	}
+	let prevs: Vec<usize> = edges.chunks_exact(2).map(|pair| nodes[pair[1].0].prev).collect();
+	for (pair, prev) in edges.chunks_exact(2).zip(prevs) {
+		nodes[pair[0].0].prev = prev;
+		nodes[prev].next = pair[0].0;
+	}
+}
+
+/// Find a direction from a vertex into the interior next to it.
+///
+/// The direction is to the left of both edges at the vertex. Moving the vertex in this direction
+/// keeps its edges in the same part of the interior.
+fn interior_direction(nodes: &[Node], node: usize) -> (i128, i128) {
+	let prev = &nodes[nodes[node].prev].point;
+	let point = &nodes[node].point;
+	let next = &nodes[nodes[node].next].point;
+	let incoming = direction(prev, point);
+	let outgoing = direction(point, next);
+	match orientation(prev, point, next).signum() {
+		1 => (outgoing.0 - incoming.0, outgoing.1 - incoming.1),
+		-1 => (incoming.0 - outgoing.0, incoming.1 - outgoing.1),
+		_ => (-outgoing.1, outgoing.0), //Straight on, so perpendicular to the edges.
+	}
+}
+
+/// Compute on which side of a line through two nodes a third node is, after nudging them.
+///
+/// If the positions of the nodes are not collinear, this is the sign of `orientation`. Otherwise, it
+/// depends on the directions in which they are nudged.
+///
+/// # Result
+/// `Greater` if the third node is left of the line, `Less` if it is right of the line, or `Equal` if
+/// it is on the line even after nudging.
+fn nudged_orientation(start: &Node, end: &Node, node: &Node) -> Ordering {
+	let exact = orientation(&start.point, &end.point, &node.point);
+	if exact != 0 {
+		return exact.cmp(&0);
+	}
+	let line = direction(&start.point, &end.point);
+	let relative = direction(&start.point, &node.point);
+	let line_nudge = (end.nudge.0 - start.nudge.0, end.nudge.1 - start.nudge.1);
+	let relative_nudge = (node.nudge.0 - start.nudge.0, node.nudge.1 - start.nudge.1);
+	//The orientation after nudging is a polynomial in the nudged distance. Its first term that isn't zero determines its sign.
+	sum_of_products_sign(&[(line.0, relative_nudge.1), (-line.1, relative_nudge.0), (line_nudge.0, relative.1), (-line_nudge.1, relative.0)])
+		.then_with(|| compare_products(line_nudge.0, relative_nudge.1, line_nudge.1, relative_nudge.0))
+}
+
+/// Divide a shape into pieces along diagonals.
+///
+/// The pieces are found by walking along the edges, always turning as far right as possible. The
+/// interior is to the left of every edge, so this walks around the piece to its left.
+///
+/// # Arguments
+/// * `nodes` - The nodes of all contours of the shape.
+/// * `diagonals` - Pairs of nodes that are connected by diagonals.
+///
+/// # Result
+/// The nodes around each piece, counter-clockwise.
+fn monotone_pieces(nodes: &[Node], mut diagonals: Vec<(usize, usize)>) -> Vec<Vec<usize>> {
+	for diagonal in diagonals.iter_mut() {
+		*diagonal = (diagonal.0.min(diagonal.1), diagonal.0.max(diagonal.1));
+	}
+	diagonals.sort_unstable();
+	diagonals.dedup();
+	diagonals.retain(|(a, b)| a != b && nodes[*a].next != *b && nodes[*a].prev != *b);
+
+	let mut neighbours: Vec<Vec<usize>> = nodes.iter().map(|node| vec![node.next, node.prev]).collect();
+	for (a, b) in diagonals {
+		neighbours[a].push(b);
+		neighbours[b].push(a);
+	}
+	for (node, list) in neighbours.iter_mut().enumerate() {
+		if list.len() > 2 {
+			let centre = nodes[node];
+			list.sort_by(|a, b| angle_order(&centre, &nodes[*a], &nodes[*b]));
+		}
+	}
+	let mut visited: Vec<Vec<bool>> = neighbours.iter().map(|list| vec![false; list.len()]).collect();
+	let mut pieces = Vec::new();
+	for start in 0..nodes.len() {
+		for start_position in 0..neighbours[start].len() {
+			if visited[start][start_position] || neighbours[start][start_position] == nodes[start].prev {
+				continue; //Edges towards the previous vertex have the exterior on their left.
+			}
+			let mut piece = Vec::new();
+			let (mut from, mut position) = (start, start_position);
+			while !visited[from][position] {
+				visited[from][position] = true;
+				piece.push(from);
+				let to = neighbours[from][position];
+				let back = neighbours[to].iter().position(|other| *other == from).unwrap();
+				position = (back + neighbours[to].len() - 1) % neighbours[to].len(); //The next edge clockwise, which turns furthest right.
+				from = to;
+			}
+			pieces.push(piece);
+		}
+	}
+	pieces
+}
+
+/// Compare the directions from a centre node towards two other nodes, counter-clockwise starting
+/// from the positive X direction.
+///
+/// The nodes are nudged first, so nodes at the same position as the centre have a direction too.
+fn angle_order(centre: &Node, a: &Node, b: &Node) -> Ordering {
+	let position = |node: &Node| ((node.point.y, node.nudge.1), (node.point.x, node.nudge.0)); //Compare Y first, like the sweep line does.
+	let half = |node: &Node| if position(node) > position(centre) { 0 } else { 1 };
+	half(a).cmp(&half(b)).then_with(|| nudged_orientation(centre, b, a))
+}
+
+/// Triangulate a piece of a shape that is monotone in the Y direction.
+///
+/// The vertices are processed from top to bottom. A stack holds the vertices that still need to be
+/// connected to lower vertices, which form a reflex chain on one side. A vertex on the other side
+/// can connect to all of them. A vertex on the same side connects to as many as it can see.
+///
+/// # Arguments
+/// * `nodes` - The nodes of all contours of the shape.
+/// * `rank` - The order in which the sweep line passes each node.
+/// * `piece` - The nodes around the piece, counter-clockwise.
+/// * `triangles` - The list to add the triangles to.
+fn triangulate_monotone(nodes: &[Node], rank: &[usize], piece: &[usize], triangles: &mut Vec<[u32; 3]>) {
+	let len = piece.len();
+	if len < 3 {
+		return;
+	}
+	let top = (0..len).min_by_key(|position| rank[piece[*position]]).unwrap();
+	let bottom = (0..len).max_by_key(|position| rank[piece[*position]]).unwrap();
+	let mut sorted: Vec<(usize, bool)> = Vec::with_capacity(len); //Each node, and whether it is on the left chain.
+	sorted.push((piece[top], true));
+	let mut left = (top + 1) % len; //Counter-clockwise from the top goes down along the left side.
+	let mut right = (top + len - 1) % len;
+	while left != bottom || right != bottom {
+		if left != bottom && (right == bottom || rank[piece[left]] < rank[piece[right]]) {
+			sorted.push((piece[left], true));
+			left = (left + 1) % len;
+		} else {
+			sorted.push((piece[right], false));
+			right = (right + len - 1) % len;
+		}
+	}
+	sorted.push((piece[bottom], false));
+
+	let mut stack = vec![sorted[0], sorted[1]];
+	for j in 2..len - 1 {
+		let (node, on_left) = sorted[j];
+		if on_left != stack.last().unwrap().1 {
+			for pair in stack.windows(2) {
+				push_triangle(nodes, node, pair[0].0, pair[1].0, triangles);
+			}
+			stack.clear();
+			stack.push(sorted[j - 1]);
+			stack.push(sorted[j]);
+		} else {
+			let mut last = stack.pop().unwrap();
+			while let Some(top) = stack.last() {
+				let (a, b, c) = (&nodes[top.0], &nodes[last.0], &nodes[node]);
+				let visible = if on_left { nudged_orientation(a, b, c) } else { nudged_orientation(c, b, a) } == Ordering::Greater;
+				if !visible {
+					break;
+				}
+				push_triangle(nodes, node, last.0, top.0, triangles);
+				last = stack.pop().unwrap();
+			}
+			stack.push(last);
+			stack.push(sorted[j]);
+		}
+	}
+	let bottom = sorted[len - 1].0;
+	for pair in stack.windows(2) {
+		push_triangle(nodes, bottom, pair[0].0, pair[1].0, triangles);
+	}
+}
+
+/// Add a triangle to a list of triangles, counter-clockwise.
+///
+/// Triangles without area are left out.
+fn push_triangle(nodes: &[Node], a: usize, b: usize, c: usize, triangles: &mut Vec<[u32; 3]>) {
+	let turn = orientation(&nodes[a].point, &nodes[b].point, &nodes[c].point);
+	if turn > 0 {
+		triangles.push([nodes[a].index, nodes[b].index, nodes[c].index]);
+	} else if turn < 0 {
+		triangles.push([nodes[a].index, nodes[c].index, nodes[b].index]);
+	}
+}
+
+/// Calculate twice the total signed area of the triangles in an index buffer.
+///
+/// # Arguments
+/// * `vertices` - The vertices that the indices refer to.
+/// * `triangles` - The indices of the vertices of the triangles, three for each triangle.
+fn doubled_triangles_area(vertices: &[Point2D], triangles: &[u32]) -> i128 {
+	triangles.chunks_exact(3).map(|triangle| {
+		orientation(&vertices[triangle[0] as usize], &vertices[triangle[1] as usize], &vertices[triangle[2] as usize])
+	}).sum()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+	use crate::Containment;
+	use crate::FillRule;
+	use crate::operations::boolean::{boolean_polygon_st, BooleanOperation};
+
+	/// Check that triangles cover a shape exactly.
+	///
+	/// The signed areas of the triangles must add up to the area of the shape, and sample points
+	/// inside the shape must be inside exactly one triangle. Sample points that are on the boundary
+	/// of a triangle are skipped.
+	fn assert_covers(multi_polygon: &MultiPolygon, triangles: &[u32]) {
+		let vertices = multi_polygon.host_vertices();
+		assert_eq!(triangles.len() % 3, 0, "Every triangle has three vertices.");
+		assert!(triangles.iter().all(|index| (*index as usize) < vertices.len()), "The indices must refer to existing vertices.");
+		let doubled_area: i128 = multi_polygon.contours().map(area::doubled_area_st).sum();
+		assert_eq!(doubled_triangles_area(vertices, triangles), doubled_area, "The triangles must cover exactly the area of the shape.");
+		let corners = |triangle: &[u32]| (vertices[triangle[0] as usize], vertices[triangle[1] as usize], vertices[triangle[2] as usize]);
+
+		let min_x = vertices.iter().map(|vertex| vertex.x).min().unwrap();
+		let max_x = vertices.iter().map(|vertex| vertex.x).max().unwrap();
+		let min_y = vertices.iter().map(|vertex| vertex.y).min().unwrap();
+		let max_y = vertices.iter().map(|vertex| vertex.y).max().unwrap();
+		for x in (min_x..=max_x).step_by(((max_x - min_x) / 37).max(1) as usize) {
+			for y in (min_y..=max_y).step_by(((max_y - min_y) / 41).max(1) as usize) {
+				let point = Point2D { x, y };
+				let containment = containment::containment_multipolygon_st(multi_polygon, &point, FillRule::NON_ZERO);
+				if containment == Containment::BOUNDARY {
+					continue;
+				}
+				let mut on_boundary = false;
+				let mut count = 0;
+				for triangle in triangles.chunks_exact(3) {
+					let (a, b, c) = corners(triangle);
+					let sides = [orientation(&a, &b, &point), orientation(&b, &c, &point), orientation(&c, &a, &point)].map(|side| side * orientation(&a, &b, &c).signum());
+					if sides.iter().all(|side| *side > 0) {
+						count += 1;
+					} else if sides.iter().all(|side| *side >= 0) {
+						on_boundary = true;
+					}
+				}
+				if !on_boundary {
+					assert_eq!(count, if containment == Containment::INSIDE { 1 } else { 0 }, "The point {point:?} must be covered by exactly one triangle if it is inside.");
+				}
+			}
+		}
+	}
+
+	/// Check that a polygon is triangulated correctly by both algorithms, with triangles that have
+	/// the same orientation as the polygon.
+	fn assert_polygon(polygon: &Polygon) {
+		let as_multi_polygon = MultiPolygon::from_iter([Polygon::from_iter(polygon.iter().copied())]);
+		let vertices = polygon.host_vertices();
+		let orientation_sign = area::doubled_area_st(vertices).signum();
+		assert_sweep(&as_multi_polygon);
+		for triangles in [triangulate_polygon_ear_clipping(polygon), triangulate_polygon_sweep(polygon), triangulate_polygon(polygon)] {
+			assert_covers(&as_multi_polygon, &triangles);
+			for triangle in triangles.chunks_exact(3) {
+				let (a, b, c) = (&vertices[triangle[0] as usize], &vertices[triangle[1] as usize], &vertices[triangle[2] as usize]);
+				assert_eq!(orientation(a, b, c).signum(), orientation_sign, "The triangles must have the same orientation as the polygon.");
+			}
+		}
+	}
+
+	/// Check that a multi-polygon is triangulated correctly by both algorithms and all
+	/// implementations.
+	fn assert_multipolygon(multi_polygon: &MultiPolygon) {
+		let vertices = multi_polygon.host_vertices();
+		assert_sweep(multi_polygon);
+		for algorithm in [Algorithm::EAR_CLIPPING, Algorithm::SWEEP] {
+			let triangles: Vec<u32> = groups(vertices, contours(multi_polygon), Backend::ST).iter().flat_map(|group| triangulate_group(vertices, group, algorithm)).collect();
+			assert_covers(multi_polygon, &triangles);
+		}
+		assert_covers(multi_polygon, &triangulate_multipolygon_st(multi_polygon));
+		assert_covers(multi_polygon, &triangulate_multipolygon_mt(multi_polygon));
+		assert_covers(multi_polygon, &triangulate_multipolygon(multi_polygon));
+	}
+
+	/// Check that the sweep line covers a shape by itself, without falling back to ear clipping.
+	fn assert_sweep(multi_polygon: &MultiPolygon) {
+		let vertices = multi_polygon.host_vertices();
+		for group in groups(vertices, contours(multi_polygon), Backend::ST) {
+			let (outer, holes) = clean_group(vertices, &group);
+			if outer.len() >= 3 {
+				assert!(checked_sweep(vertices, &outer, &holes).is_some(), "The sweep line must cover the shape by itself.");
+			}
+		}
+	}
+
+	/// Create a polygon from a list of coordinates.
+	fn polygon(coordinates: &[(crate::Coordinate, crate::Coordinate)]) -> Polygon {
+		Polygon::from_iter(coordinates.iter().map(|(x, y)| Point2D { x: *x, y: *y }))
+	}
+
+	/// Create a star with a random radius at each vertex.
+	///
+	/// Boolean operations on two of these give many contours that touch themselves or each other.
+	fn jagged_star(seed: u64, centre: Point2D) -> Polygon {
+		let mut state = seed;
+		Polygon::from_iter((0..100).map(|i| {
+			state ^= state << 13; //Xorshift, to get the same star every time.
+			state ^= state >> 7;
+			state ^= state << 17;
+			let angle = i as f64 / 100.0 * std::f64::consts::TAU;
+			let radius = 5.0 + (state % 60) as f64;
+			Point2D { x: centre.x + (angle.cos() * radius).round() as crate::Coordinate, y: centre.y + (angle.sin() * radius).round() as crate::Coordinate }
+		}))
+	}
+
+	/// Create a star with many points, which has many vertices where the shape splits or merges.
+	fn star(num_points: i32) -> Polygon {
+		Polygon::from_iter((0..num_points * 2).map(|i| {
+			let angle = i as f64 * std::f64::consts::PI / num_points as f64;
+			let radius = if i % 2 == 0 { 10000.0 } else { 3000.0 };
+			Point2D { x: (angle.cos() * radius).round() as crate::Coordinate, y: (angle.sin() * radius).round() as crate::Coordinate }
+		}))
+	}
+
+	/// Create a comb, with teeth pointing down, and with vertices on the same height.
+	fn comb(num_teeth: crate::Coordinate) -> Polygon {
+		let mut vertices = vec![Point2D { x: num_teeth * 20, y: 100 }, Point2D { x: 0, y: 100 }];
+		for tooth in 0..num_teeth {
+			vertices.extend([Point2D { x: tooth * 20, y: 0 }, Point2D { x: tooth * 20 + 10, y: 0 }, Point2D { x: tooth * 20 + 10, y: 50 }, Point2D { x: tooth * 20 + 20, y: 50 }]);
+		}
+		Polygon::from_iter(vertices)
+	}
+
+	/// Test triangulating simple polygons from the test data.
+	#[test_case(crate::test::data::polygon::square_1000() ; "square")]
+	#[test_case(crate::test::data::polygon::triangle_1000() ; "triangle")]
+	#[test_case(crate::test::data::polygon::l_shape_1000() ; "L-shape")]
+	#[test_case(star(5) ; "star")]
+	#[test_case(star(200) ; "large star")]
+	#[test_case(comb(5) ; "comb")]
+	#[test_case(comb(50) ; "large comb")]
+	fn simple(polygon: Polygon) {
+		assert_polygon(&polygon);
+	}
+
+	/// Test that a square is divided into two triangles.
+	#[test]
+	fn square_two_triangles() {
+		let square = crate::test::data::polygon::square_1000();
+		assert_eq!(triangulate_polygon_ear_clipping(&square).len(), 6);
+		assert_eq!(triangulate_polygon_sweep(&square).len(), 6);
+	}
+
+	/// Test triangulating a clockwise polygon, which gives clockwise triangles.
+	#[test]
+	fn clockwise() {
+		let l_shape = crate::test::data::polygon::l_shape_1000();
+		assert_polygon(&Polygon::from_iter(l_shape.host_vertices().iter().rev().copied()));
+		assert_polygon(&Polygon::from_iter(star(100).host_vertices().iter().rev().copied()));
+	}
+
+	/// Test that collinear and duplicate vertices are left out.
+	#[test]
+	fn collinear_and_duplicate() {
+		let square = Polygon::from_iter([
+			Point2D { x: 0, y: 0 }, Point2D { x: 0, y: 0 }, Point2D { x: 50, y: 0 }, Point2D { x: 100, y: 0 },
+			Point2D { x: 100, y: 50 }, Point2D { x: 100, y: 100 }, Point2D { x: 100, y: 100 }, Point2D { x: 50, y: 100 },
+			Point2D { x: 0, y: 100 }, Point2D { x: 0, y: 50 }, Point2D { x: 0, y: 0 },
+		]);
+		assert_polygon(&square);
+		assert_eq!(triangulate_polygon_ear_clipping(&square).len(), 6, "Only the corners are needed.");
+		assert_eq!(triangulate_polygon_sweep(&square).len(), 6, "Only the corners are needed.");
+	}
+
+	/// Test that a spike going out and back along the same line is left out.
+	#[test]
+	fn spike() {
+		let polygon = Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 200, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+		assert_polygon(&polygon);
+	}
+
+	/// Test triangulating polygons without area, which gives no triangles.
+	#[test_case(Polygon::new() ; "empty")]
+	#[test_case(Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 10 }]) ; "line")]
+	#[test_case(Polygon::from_iter([Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 10 }, Point2D { x: 20, y: 20 }]) ; "collinear")]
+	fn degenerate(polygon: Polygon) {
+		assert_eq!(triangulate_polygon_ear_clipping(&polygon), Vec::<u32>::new());
+		assert_eq!(triangulate_polygon_sweep(&polygon), Vec::<u32>::new());
+	}
+
+	/// Test triangulating multi-polygons from the test data.
+	#[test_case(crate::test::data::multi_polygon::square_with_hole_1000() ; "square with hole")]
+	#[test_case(crate::test::data::multi_polygon::two_squares_1000() ; "two squares")]
+	fn multipolygon(multi_polygon: MultiPolygon) {
+		assert_multipolygon(&multi_polygon);
+	}
+
+	/// Test triangulating a square with a grid of holes, including holes on the same height.
+	#[test]
+	fn many_holes() {
+		let mut grid = MultiPolygon::new();
+		grid.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 1000, y: 0 }, Point2D { x: 1000, y: 1000 }, Point2D { x: 0, y: 1000 }]);
+		for x in 0..6 {
+			for y in 0..6 {
+				let (left, bottom) = (x * 160 + 40, y * 160 + 40);
+				grid.push_contour([Point2D { x: left, y: bottom }, Point2D { x: left, y: bottom + 80 }, Point2D { x: left + 80 + y * 3, y: bottom + 80 }, Point2D { x: left + 80, y: bottom }]);
+			}
+		}
+		assert_multipolygon(&grid);
+	}
+
+	/// Test triangulating a hole that touches the middle of an edge of the outer contour.
+	#[test]
+	fn touching_hole() {
+		let mut touching = MultiPolygon::new();
+		touching.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 100, y: 100 }, Point2D { x: 0, y: 100 }]);
+		touching.push_contour([Point2D { x: 100, y: 50 }, Point2D { x: 50, y: 25 }, Point2D { x: 50, y: 75 }]);
+		let vertices = touching.host_vertices();
+		for algorithm in [Algorithm::EAR_CLIPPING, Algorithm::SWEEP] {
+			let triangles: Vec<u32> = groups(vertices, contours(&touching), Backend::ST).iter().flat_map(|group| triangulate_group(vertices, group, algorithm)).collect();
+			assert_covers(&touching, &triangles);
+		}
+	}
+
+	/// Test triangulating holes that touch the outer contour or each other at a vertex.
+	#[test_case(&[(0, 0), (100, 0), (100, 50), (100, 100), (0, 100)], &[&[(100, 50), (50, 25), (50, 75)]] ; "straight vertex")]
+	#[test_case(&[(0, 0), (100, 0), (100, 100), (0, 100)], &[&[(100, 100), (80, 50), (50, 80)]] ; "corner")]
+	#[test_case(&[(0, 0), (100, 0), (100, 100), (0, 100)], &[&[(50, 50), (70, 20), (30, 20)], &[(50, 50), (30, 80), (70, 80)]] ; "two holes")]
+	#[test_case(&[(0, 0), (100, 0), (100, 100), (50, 100), (0, 100)], &[&[(50, 100), (60, 50), (50, 50), (40, 50)], &[(50, 50), (70, 20), (30, 20)]] ; "chain")]
+	fn touching_vertex(outer: &[(crate::Coordinate, crate::Coordinate)], holes: &[&[(crate::Coordinate, crate::Coordinate)]]) {
+		let mut touching = MultiPolygon::new();
+		for contour in [outer].iter().chain(holes) {
+			touching.push_contour(polygon(contour).iter().copied());
+		}
+		assert_multipolygon(&touching);
+	}
+
+	/// Test triangulating contours that touch themselves at a vertex.
+	///
+	/// These contours come from boolean operations. Where a contour touches itself, it consists of
+	/// loops that may be oriented differently, such as a hole attached to the outside of a shape.
+	#[test_case(&[(-44, 34), (-40, 33), (-37, 30), (-36, 30), (-39, 33), (-37, 33), (-34, 30), (-33, 28), (-35, 30), (-36, 30), (-34, 28), (-36, 28)] ; "two lobes")]
+	#[test_case(&[(7, -5), (16, -10), (19, -11), (8, -5), (12, -3), (20, -6), (21, -5), (17, -4), (20, -3), (16, -2), (15, -3), (19, -3), (16, -4), (13, -3), (15, -3), (13, -2)] ; "hole at straight vertex")]
+	#[test_case(&[(15, -4), (20, -6), (23, -7), (49, -7), (45, -6), (57, -4), (27, -5), (43, -3), (27, -2), (23, -3), (25, -3), (27, -4), (20, -3), (23, -3), (19, -2), (15, -3), (18, -4), (19, -3), (24, -5), (18, -4)] ; "two holes")]
+	fn pinched(coordinates: &[(crate::Coordinate, crate::Coordinate)]) {
+		assert_polygon(&polygon(coordinates));
+	}
+
+	/// Test triangulating the results of boolean operations, which have many contours that touch
+	/// themselves or each other.
+	#[test]
+	fn boolean_results() {
+		let subject = jagged_star(7, Point2D { x: 0, y: 0 });
+		let clip = jagged_star(1007, Point2D { x: 7, y: 3 });
+		for operation in [BooleanOperation::UNION, BooleanOperation::INTERSECTION, BooleanOperation::DIFFERENCE, BooleanOperation::XOR] {
+			let result = boolean_polygon_st(&subject, &clip, operation, FillRule::NON_ZERO);
+			assert_multipolygon(&result);
+		}
+	}
+
+	/// Test triangulating an island inside a hole, and a hole that isn't inside anything.
+	#[test]
+	fn nesting() {
+		let mut nested = MultiPolygon::new();
+		nested.push_contour([Point2D { x: 0, y: 0 }, Point2D { x: 1000, y: 0 }, Point2D { x: 1000, y: 1000 }, Point2D { x: 0, y: 1000 }]);
+		nested.push_contour([Point2D { x: 100, y: 100 }, Point2D { x: 100, y: 900 }, Point2D { x: 900, y: 900 }, Point2D { x: 900, y: 100 }]); //Hole.
+		nested.push_contour([Point2D { x: 200, y: 200 }, Point2D { x: 800, y: 200 }, Point2D { x: 800, y: 800 }, Point2D { x: 200, y: 800 }]); //Island.
+		nested.push_contour([Point2D { x: 300, y: 300 }, Point2D { x: 300, y: 700 }, Point2D { x: 700, y: 700 }, Point2D { x: 700, y: 300 }]); //Hole in the island.
+		nested.push_contour([Point2D { x: 2000, y: 0 }, Point2D { x: 2000, y: 100 }, Point2D { x: 2100, y: 100 }, Point2D { x: 2100, y: 0 }]); //Loose hole.
+		assert_multipolygon(&nested);
+	}
+
+	/// Test that triangulating a batch gives the same triangles as triangulating each polygon.
+	#[test]
+	fn batch_polygons() {
+		let polygons = [crate::test::data::polygon::square_1000(), comb(50), Polygon::new(), crate::test::data::polygon::l_shape_1000()];
+		let batch = PolygonBatch::from_iter(polygons.iter().map(|polygon| Polygon::from_iter(polygon.host_vertices().iter().copied())));
+		let expected: Vec<Vec<u32>> = polygons.iter().map(triangulate_polygon).collect();
+		assert_eq!(triangulate_batch_st(&batch), expected);
+		assert_eq!(triangulate_batch_mt(&batch), expected);
+		assert_eq!(triangulate_batch(&batch), expected);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,26 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! Defines the error that is returned when a coordinate goes out of the range of `Coordinate`.
+
+use std::error::Error; //This is an error that can be propagated with other errors.
+use std::fmt; //To describe the error as text.
+
+/// An operation would move a coordinate outside of the range that a `Coordinate` can represent.
+///
+/// Operations that return this error leave the object that they were applied to unchanged.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct OverflowError;
+
+impl fmt::Display for OverflowError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "A coordinate went out of the range of the coordinate type.")
+	}
+}
+
+impl Error for OverflowError {}
\ No newline at end of file
@@ -0,0 +1,80 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! Defines an enum for what to do when a coordinate goes out of the range of `Coordinate`.
+
+use crate::Coordinate; //The type whose range is exceeded.
+use crate::OverflowError; //To report overflow with the checked policy.
+
+/// What to do if an operation would move a coordinate outside of the range of `Coordinate`.
+///
+/// The policies behave the same on the host and on the GPU.
+#[allow(non_camel_case_types)] //The variants are capitalised like those of the other enums, with underscores between words.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OverflowPolicy {
+	/// Return an `OverflowError`, and leave the whole object unchanged.
+	CHECKED,
+
+	/// Clamp the coordinate to the nearest coordinate that is in range. This may deform the object.
+	SATURATING,
+
+	/// Wrap the coordinate around to the other end of the range, like two's complement arithmetic
+	/// does. This is the fastest.
+	WRAPPING,
+}
+
+impl OverflowPolicy {
+	/// Add two coordinates according to this policy.
+	///
+	/// # Arguments
+	/// * `coordinate` - The original coordinate.
+	/// * `delta` - How much to add to it.
+	///
+	/// # Result
+	/// The sum, or an error if it is out of range and this policy is `CHECKED`.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{Coordinate, OverflowError, OverflowPolicy};
+	/// assert_eq!(OverflowPolicy::CHECKED.add(Coordinate::MAX, 1), Err(OverflowError));
+	/// assert_eq!(OverflowPolicy::SATURATING.add(Coordinate::MAX, 1), Ok(Coordinate::MAX));
+	/// assert_eq!(OverflowPolicy::WRAPPING.add(Coordinate::MAX, 1), Ok(Coordinate::MIN));
+	/// ```
+	pub fn add(&self, coordinate: Coordinate, delta: Coordinate) -> Result<Coordinate, OverflowError> {
+		match self {
+			OverflowPolicy::CHECKED => coordinate.checked_add(delta).ok_or(OverflowError),
+			OverflowPolicy::SATURATING => Ok(coordinate.saturating_add(delta)),
+			OverflowPolicy::WRAPPING => Ok(coordinate.wrapping_add(delta)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+
+	/// Test adding coordinates that stay in range, which is the same for every policy.
+	#[test_case(OverflowPolicy::CHECKED ; "checked")]
+	#[test_case(OverflowPolicy::SATURATING ; "saturating")]
+	#[test_case(OverflowPolicy::WRAPPING ; "wrapping")]
+	fn add_in_range(policy: OverflowPolicy) {
+		assert_eq!(policy.add(100, -250), Ok(-150));
+		assert_eq!(policy.add(Coordinate::MAX - 1, 1), Ok(Coordinate::MAX), "Exactly reaching the maximum is not an overflow.");
+		assert_eq!(policy.add(Coordinate::MIN + 1, -1), Ok(Coordinate::MIN), "Exactly reaching the minimum is not an overflow.");
+	}
+
+	/// Test adding coordinates that go out of range in either direction.
+	#[test_case(OverflowPolicy::CHECKED, Err(OverflowError), Err(OverflowError) ; "checked")]
+	#[test_case(OverflowPolicy::SATURATING, Ok(Coordinate::MAX), Ok(Coordinate::MIN) ; "saturating")]
+	#[test_case(OverflowPolicy::WRAPPING, Ok(Coordinate::MIN + 9), Ok(Coordinate::MAX - 9) ; "wrapping")]
+	fn add_out_of_range(policy: OverflowPolicy, expected_up: Result<Coordinate, OverflowError>, expected_down: Result<Coordinate, OverflowError>) {
+		assert_eq!(policy.add(Coordinate::MAX - 10, 20), expected_up);
+		assert_eq!(policy.add(Coordinate::MIN + 10, -20), expected_down);
+	}
+}
\ No newline at end of file
@@ -8,9 +8,17 @@
 
 //! Defines a struct that represents single points in a 2-dimensional space.
 
+use crate::AffineTransform; //To implement TwoDimensional.
 use crate::Area; //To implement Shape2D.
+use crate::BoundingBox; //To implement Shape2D.
+use crate::Containment; //To implement Shape2D.
 use crate::Convexity; //To implement Shape2D.
 use crate::Coordinate; //The position of the point is stored with coordinates.
+use crate::Crossing; //To implement Shape2D.
+use crate::FillRule; //To implement Shape2D.
+use crate::OverflowError; //To implement TwoDimensional.
+use crate::OverflowPolicy; //To implement TwoDimensional.
+use crate::Polygon; //To implement Shape2D.
 use crate::TwoDimensional; //This point is in two-dimensional space.
 use crate::Shape2D; //A point is a shape, with a bounded (zero) area.
 
@@ -26,6 +34,7 @@ use crate::Shape2D; //A point is a shape, with a bounded (zero) area.
 /// considered lower. If points have the same X coordinate, points with lower Y coordinates will be
 /// considered lower. Thus the points are compared lexicographically with X before Y.
 #[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point2D {
 	/// The projection of this point on the X dimension.
 	pub x: Coordinate,
@@ -45,15 +54,46 @@ impl Shape2D for Point2D {
 		return 0; //A point has no area.
 	}
 
+	fn bounding_box(&self) -> BoundingBox {
+		return BoundingBox { min: *self, max: *self }; //A point is its own bounding box, with no width or height.
+	}
+
+	fn containment(&self, point: &Point2D, _fill_rule: FillRule) -> Containment {
+		if self == point {
+			return Containment::BOUNDARY; //A point has no interior, only a boundary.
+		}
+		return Containment::OUTSIDE;
+	}
+
+	fn convex_hull(&self) -> Polygon {
+		return Polygon::from_iter([*self]); //The hull of a point is degenerate, with just the point itself.
+	}
+
 	fn convexity(&self) -> Convexity {
 		return Convexity::DEGENERATE; //Points are degenerate shapes.
 	}
+
+	fn crossings(&self) -> Vec<Crossing> {
+		return Vec::new(); //Points have no edges to cross.
+	}
+
+	fn triangulate(&self) -> Vec<u32> {
+		return Vec::new(); //Points have no area to cover.
+	}
 }
 
 impl TwoDimensional for Point2D {
-	fn translate(&mut self, dx: Coordinate, dy: Coordinate) {
-		self.x += dx;
-		self.y += dy;
+	fn translate_with_policy(&mut self, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+		let x = policy.add(self.x, dx)?;
+		let y = policy.add(self.y, dy)?; //Only modify the point once both coordinates are known to be in range.
+		self.x = x;
+		self.y = y;
+		return Ok(());
+	}
+
+	fn transform(&mut self, transform: &AffineTransform) -> Result<(), OverflowError> {
+		*self = transform.apply(self)?; //Only modified if both coordinates are in range.
+		return Ok(());
 	}
 }
 
@@ -110,6 +150,23 @@ mod tests {
 		assert_eq!(point.y, -10000 - 200 + 1000, "We further moved the Y coordinate into the positive direction by 1000.");
 	}
 
+	#[test]
+	/// Test moving a point out of range with the checked policy. Neither coordinate may change.
+	fn point2d_translate_checked_overflow() {
+		let mut point = Point2D { x: 10, y: Coordinate::MIN };
+		assert_eq!(point.translate_with_policy(5, -1, OverflowPolicy::CHECKED), Err(OverflowError), "The Y coordinate would go below the minimum.");
+		assert_eq!(point, Point2D { x: 10, y: Coordinate::MIN }, "The X coordinate may not be moved if the Y coordinate can't be.");
+	}
+
+	#[test]
+	/// Test moving a point out of range with the wrapping policy, which is also what plain translation
+	/// does.
+	fn point2d_translate_wrapping() {
+		let mut point = Point2D { x: Coordinate::MAX, y: 0 };
+		point.translate(1, 0);
+		assert_eq!(point, Point2D { x: Coordinate::MIN, y: 0 }, "Going beyond the maximum wraps around to the minimum, also in debug builds.");
+	}
+
 	#[test]
 	/// Test the equality operator on Point2D.
 	fn point2d_equality() {
@@ -194,4 +251,15 @@ mod tests {
 		let point = Point2D { x: 100, y: 200 };
 		assert_eq!(point.convexity(), Convexity::DEGENERATE, "Points are always degenerate convexity.");
 	}
+
+	/// Test serialising a point and deserialising it again.
+	#[cfg(feature = "serde")]
+	#[test]
+	fn point2d_serde() {
+		let point = Point2D { x: -100, y: 200 };
+		let json = serde_json::to_string(&point).unwrap();
+		assert_eq!(json, r#"{"x":-100,"y":200}"#);
+		assert_eq!(serde_json::from_str::<Point2D>(&json).unwrap(), point);
+		assert!(serde_json::from_str::<Point2D>(r#"{"x":1}"#).is_err(), "Both coordinates are required.");
+	}
 }
\ No newline at end of file
@@ -8,20 +8,40 @@
 
 //! Defines the Polygon struct.
 
-use std::cell::{Ref, RefCell, RefMut}; //For interior mutability to keep CPU and GPU in sync.
 use std::fmt; //You can print polygons as text.
 use std::iter::FromIterator; //Constructing polygons from iterable lists of vertices.
-use std::rc::Rc; //For interior mutability to keep CPU and GPU in sync.
-use cubecl::prelude::Array;  //GPU processing.
+use std::slice; //To iterate over the vertices.
+#[cfg(feature = "gpu")]
+use cubecl::server::Handle; //To refer to the copy of the vertices on the GPU.
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer}; //To serialise polygons as sequences of points.
 
+use crate::AffineTransform; //To transform the polygon.
 use crate::Area; //To return the polygon's surface area.
+use crate::BoundingBox; //To return the bounding box of the polygon.
+use crate::Containment; //To return whether points are inside the polygon.
 use crate::Convexity; //To return the polygon's convexity.
-use crate::Coordinate;
+use crate::Coordinate; //To translate the polygon.
+use crate::Crossing; //To return where the boundary of the polygon intersects itself.
+use crate::FillRule; //To decide which regions of the polygon are inside.
+use crate::OverflowError; //If transforming the polygon would move it out of range.
+use crate::OverflowPolicy; //To choose what happens if translating the polygon moves it out of range.
 use crate::Point2D; //The vertices of the Polygon are Point2D.
 use crate::Shape2D; //This is a 2D shape.
 use crate::TwoDimensional; //This is a two-dimensional object.
+#[cfg(feature = "gpu")]
+use crate::detail::gpu; //To create polygons on the GPU directly.
 use crate::detail::sync_status; //To track whether the GPU or CPU copies are up-to-date.
+use crate::detail::vertex_buffer::VertexBuffer; //To store the vertices on the host and the GPU.
+use crate::operations::area; //To calculate the area of the polygons.
+use crate::operations::bounding_box; //To find the bounding box of the polygons.
+use crate::operations::containment; //To find whether points are inside the polygons.
+use crate::operations::convex_hull; //To find the convex hull of the polygons.
+use crate::operations::convexity; //To determine the convexity of the polygons.
+use crate::operations::self_intersection; //To find where the polygons intersect themselves.
+use crate::operations::transform; //To transform the polygons.
 use crate::operations::translate; //To translate the polygons.
+use crate::operations::triangulate; //To divide the polygons into triangles.
 
 /// A plane figure consisting of a single contour of straight line segments.
 ///
@@ -71,30 +91,18 @@ use crate::operations::translate; //To translate the polygons.
 ///
 /// Transfer time between the host and the CPU is significant, but this is taken into account in
 /// order to decide where an operation should be calculated.
+///
+/// # Thread safety
+/// Polygons are `Send` and `Sync`, so they can be moved to other threads, and read from multiple
+/// threads at the same time. If the vertices need to be copied between the host and the GPU while
+/// multiple threads are reading them, only one thread copies them, and the others wait for it.
 pub struct Polygon {
 	/// The vertices that form the closed polygonal chain around this polygon.
 	///
-	/// This is the copy of the vertices that is in the host CPU's RAM. These vertices are not
-	/// publicly accessible, since access to the most up-to-date version may require a sync from the
-	/// GPU to the CPU.
-	vertices: Rc<RefCell<Vec<Point2D>>>,
-
-	/// The vertices that form the closed polygonal chain around this polygon.
-	///
-	/// This is the copy of the vertices that is on the GPU slave. These vertices are not publicly
-	/// accessible, since access to the most up-to-date version may require a sync from the CPU to
-	/// the GPU.
-	///
-	/// Before the first time that the polygon gets synced to the GPU, this will be `None`.
-	gpu_vertices: Rc<RefCell<Array<Coordinate>>>,
-
-	/// The up-to-date-ness of the vertex data on the CPU (host) or the GPU.
-	///
-	/// This tracks whether the CPU version is the most up-to-date version of the vertex data, or
-	/// the GPU is, or whether both are in sync (so both are the most up-to-date version).
-	///
-	/// If the CPU version is the most up-to-date,
-	sync_status: Rc<RefCell<sync_status::SyncStatus>>,
+	/// There is a copy of these vertices on the host, on the GPU, or both. These vertices are not
+	/// publicly accessible, since access to the most up-to-date version may require a sync between
+	/// the CPU and the GPU.
+	vertices: VertexBuffer,
 }
 
 impl Polygon {
@@ -103,9 +111,7 @@ impl Polygon {
 	/// The polygon will be degenerate, since it has no vertices.
 	pub fn new() -> Self {
 		Polygon {
-			vertices: Rc::new(RefCell::new(vec!())),
-			gpu_vertices: Rc::new(RefCell::new(Array::new(0))),
-			sync_status: Rc::new(RefCell::new(sync_status::SyncStatus::SYNCED)),
+			vertices: VertexBuffer::new(vec!()),
 		}
 	}
 
@@ -136,9 +142,21 @@ impl Polygon {
 	/// ```
 	pub fn with_capacity(capacity: usize) -> Self {
 		Polygon {
-			vertices: Rc::new(RefCell::new(Vec::with_capacity(capacity))),
-			gpu_vertices: Rc::new(RefCell::new(Array::new(0))),
-			sync_status: Rc::new(RefCell::new(sync_status::SyncStatus::HOST)),
+			vertices: VertexBuffer::new(Vec::with_capacity(capacity)),
+		}
+	}
+
+	/// Create a polygon whose vertices are only on the GPU, from coordinates on the host.
+	///
+	/// The coordinates are copied to the GPU as they are, without being stored on the host.
+	///
+	/// # Arguments
+	/// * `coordinates` - The coordinates of the vertices, with the X and Y coordinates of each vertex
+	/// interleaved, the way they are stored on the GPU.
+	#[cfg(feature = "gpu")]
+	pub(crate) fn from_gpu_coordinates(coordinates: &[Coordinate]) -> Self {
+		Polygon {
+			vertices: VertexBuffer::from_gpu(gpu::upload_coordinates(coordinates)),
 		}
 	}
 
@@ -222,17 +240,15 @@ impl Polygon {
 	/// assert_eq!(pentagon.len(), 5, "A pentagon has 5 sides.");
 	/// ```
 	pub fn len(&self) -> usize {
-		self.host_vertices().len()
+		self.vertices.len()
 	}
 
-	pub fn vertex(&self, index: usize) -> Ref<Point2D> {
-		self.sync_gpu_to_host();
-		Ref::map(self.vertices.borrow(), |verts| &verts[index])
+	pub fn vertex(&self, index: usize) -> &Point2D {
+		&self.host_vertices()[index]
 	}
 
-	pub fn vertex_mut(&mut self, index: usize) -> RefMut<Point2D> {
-		self.sync_gpu_to_host();
-		RefMut::map(self.vertices.borrow_mut(), |verts| &mut verts[index])
+	pub fn vertex_mut(&mut self, index: usize) -> &mut Point2D {
+		&mut self.host_vertices_mut()[index]
 	}
 
 	/// Add an extra vertex to this polygon.
@@ -315,13 +331,13 @@ impl Polygon {
 	/// //Insert a new vertex halfway.
 	/// poly.insert(3, Point2D { x: 500, y: 500 });
 	/// //The first 3 vertices are not moved.
-	/// assert_eq!(poly[0], Point2D { x: 0, y: 0 });
-	/// assert_eq!(poly[1], Point2D { x: 1000, y: 0 });
-	/// assert_eq!(poly[2], Point2D { x: 1000, y: 1000 });
+	/// assert_eq!(*poly.vertex(0), Point2D { x: 0, y: 0 });
+	/// assert_eq!(*poly.vertex(1), Point2D { x: 1000, y: 0 });
+	/// assert_eq!(*poly.vertex(2), Point2D { x: 1000, y: 1000 });
 	/// //Here is where the new vertex was inserted.
-	/// assert_eq!(poly[3], Point2D { x: 500, y: 500 });
+	/// assert_eq!(*poly.vertex(3), Point2D { x: 500, y: 500 });
 	/// //The remaining vertices were shifted.
-	/// assert_eq!(poly[4], Point2D { x: 0, y: 1000 });
+	/// assert_eq!(*poly.vertex(4), Point2D { x: 0, y: 1000 });
 	/// ```
 	pub fn insert(&mut self, index: usize, vertex: Point2D) {
 		self.host_vertices_mut().insert(index, vertex);
@@ -347,7 +363,7 @@ impl Polygon {
 	/// //Remove one of the vertices.
 	/// let removed_vertex = poly.remove(2);
 	/// assert_eq!(removed_vertex, Point2D { x: 1000, y: 1000 }); //This is the removed vertex.
-	/// assert_eq!(poly[2], Point2D { x: 0, y: 1000 }); //The last vertex has shifted in its place.
+	/// assert_eq!(*poly.vertex(2), Point2D { x: 0, y: 1000 }); //The last vertex has shifted in its place.
 	/// ```
 	pub fn remove(&mut self, index: usize) -> Point2D {
 		self.host_vertices_mut().remove(index)
@@ -386,45 +402,47 @@ impl Polygon {
 	/// 	Point2D { x: 333, y: 1000 }
 	/// ]);
 	/// let mut iter = poly.iter();
-	/// assert_eq!(iter.next(), Some(&Point2D { x: 0, y: 0 }));
-	/// assert_eq!(iter.next(), Some(&Point2D { x: 667, y: 0 })); //Counter-clockwise along the polygon's boundary.
-	/// assert_eq!(iter.next(), Some(&Point2D { x: 333, y: 1000 }));
-	/// assert_eq!(iter.next(), None); //It ran out of vertices, so it stops iterating here.
+	/// assert_eq!(iter.next().as_deref(), Some(&Point2D { x: 0, y: 0 }));
+	/// assert_eq!(iter.next().as_deref(), Some(&Point2D { x: 667, y: 0 })); //Counter-clockwise along the polygon's boundary.
+	/// assert_eq!(iter.next().as_deref(), Some(&Point2D { x: 333, y: 1000 }));
+	/// assert_eq!(iter.next().as_deref(), None); //It ran out of vertices, so it stops iterating here.
 	/// ```
-	pub fn iter(&self) -> PolygonIterator {
+	pub fn iter(&self) -> PolygonIterator<'_> {
 		PolygonIterator {
-			vertices_ref: Some(Ref::map(self.vertices.borrow(), |v| &v[..])),
+			vertices: self.host_vertices().iter(),
 		}
 	}
 
-	pub fn iter_mut(&mut self) -> PolygonIteratorMut {
+	pub fn iter_mut(&mut self) -> PolygonIteratorMut<'_> {
 		PolygonIteratorMut {
-			vertices_ref: Some(RefMut::map(self.vertices.borrow_mut(), |v| &mut v[..])),
+			vertices: self.host_vertices_mut().iter_mut(),
 		}
 	}
 
+	/// Get which copy of the vertices is currently the most up-to-date.
+	///
+	/// Operations use this to decide whether to compute on the host or on the GPU, since it
+	/// indicates whether the vertices would need to be copied first.
+	pub(crate) fn sync_status(&self) -> sync_status::SyncStatus {
+		self.vertices.sync_status()
+	}
+
 	/// Obtain the vertices of this polygon on the host.
 	///
 	/// If the latest version of the vertices is in the GPU rather than the host, it will be copied
 	/// to the host's RAM. If the latest version of the vertices is on the CPU (or they are in
 	/// sync), it will simply give a reference to those.
-	pub(crate) fn host_vertices<'a>(&'a self) -> Ref<'a, Vec<Point2D>> {
-		if self.sync_status.borrow().eq(&sync_status::SyncStatus::GPU) { //Host is outdated.
-			self.sync_gpu_to_host();
-		}
-		self.vertices.borrow()
+	pub(crate) fn host_vertices(&self) -> &Vec<Point2D> {
+		self.vertices.host()
 	}
 
 	/// Obtain the vertices of this polygon on the host, allowing their modification.
 	///
 	/// If the latest version of the vertices is in the GPU rather than the host, it will be copied
-	/// to the host's RAM. If the latest version of the vertices is on the CPU (or they are in
-	/// sync), it will simply give a reference to those.
-	pub(crate) fn host_vertices_mut<'a>(&'a mut self) -> RefMut<'a, Vec<Point2D>> {
-		if self.sync_status.borrow().eq(&sync_status::SyncStatus::GPU) { //Host is outdated.
-			self.sync_gpu_to_host();
-		}
-		self.vertices.borrow_mut()
+	/// to the host's RAM first. Since the vertices may be modified, the host becomes the leading
+	/// copy.
+	pub(crate) fn host_vertices_mut(&mut self) -> &mut Vec<Point2D> {
+		self.vertices.host_mut()
 	}
 
 	/// Obtain the vertices of this polygon on the GPU.
@@ -433,103 +451,60 @@ impl Polygon {
 	/// to the GPU first. If the latest version of the vertices is in the GPU (or they are in sync),
 	/// it will simply give a reference to those.
 	///
-	/// While this returns an ``Option`` due to the internal data structure in this polygon, the
-	/// resulting ``Option`` is guaranteed to be ``Some``.
-	pub(crate) fn gpu_vertices(&self) -> Ref<Array<Coordinate>> {
-		if self.sync_status.borrow().eq(&sync_status::SyncStatus::HOST) { //GPU is outdated.
-			self.sync_host_to_gpu();
-		}
-		self.gpu_vertices.borrow()
+	/// On the GPU, the vertices are stored as a flat array of coordinates, with the X and Y
+	/// coordinates of each vertex interleaved.
+	#[cfg(feature = "gpu")]
+	pub(crate) fn gpu_vertices(&self) -> &Handle {
+		self.vertices.gpu()
 	}
 
 	/// Obtain the vertices of this polygon on the GPU, allowing their modification.
 	///
 	/// If the latest version of the vertices is in the host rather than the GPU, it will be copied
-	/// to the GPU first. If the latest version of the vertices is in the GPU (or they are in sync),
-	/// it will simply give a reference to those.
-	///
-	/// While this returns an ``Option`` due to the internal data structure in this polygon, the
-	/// resulting ``Option`` is guaranteed to be ``Some``.
-	pub(crate) fn gpu_vertices_mut(&mut self) -> RefMut<Array<Coordinate>> {
-		if self.sync_status.borrow().eq(&sync_status::SyncStatus::HOST) { //GPU is outdated.
-			//self.sync_host_to_gpu();
-		}
-		self.gpu_vertices.borrow_mut()
+	/// to the GPU first. Since the vertices may be modified, the GPU becomes the leading copy.
+	#[cfg(feature = "gpu")]
+	pub(crate) fn gpu_vertices_mut(&mut self) -> &mut Handle {
+		self.vertices.gpu_mut()
 	}
+}
 
-	fn sync_host_to_gpu(&self) {
-		//TODO.
-	}
-	fn sync_gpu_to_host(&self) {
-		//TODO.
+impl TwoDimensional for Polygon {
+	fn translate_with_policy(&mut self, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+		translate::translate_polygon(self, dx, dy, policy)
 	}
 
-	/*/// Copy the vertex data from the host to the GPU, to prepare for processing it there.
-	///
-	/// This will allocate space for the data on the GPU if there is not enough space there yet. If
-	/// there is enough space, the existing space will be re-used.
-	///
-	/// This function assumes that the GPU data is outdated. If the host and GPU are already synced,
-	/// this will cause an unnecessary copy to the GPU. If the GPU was leading, then this will
-	/// overwrite the leading GPU data with the data on the host, effectively reversing the latest
-	/// changes to the data. So it is important to check first what the sync status is of these
-	/// vertices between the different devices.
-	fn sync_host_to_gpu(&mut self) {
-		//TODO: If the GPU memory already has enough space, copy without allocating new memory.
-		let num_vertices = self.len();
-		let mut coordinates = Vec::<Coordinate>::with_capacity(num_vertices * 2);
-		for vertex in self.vertices.borrow().iter() {
-			coordinates.push(vertex.x);
-			coordinates.push(vertex.y);
-		}
+	fn transform(&mut self, transform: &AffineTransform) -> Result<(), OverflowError> {
+		transform::transform_polygon(self, transform)
+	}
+}
 
-		self.gpu_vertices.borrow_mut().replace(
-			Array::<Coordinate>::new(coordinates.as_slice(), arrayfire::Dim4::new(&[num_vertices as u64, 2, 1, 1]))
-		);
-		self.sync_status.replace(sync_status::SyncStatus::SYNCED); //They are now in sync.
+impl Shape2D for Polygon {
+	fn area(&self) -> Area {
+		area::area_polygon(self)
 	}
 
-	/// Copy the vertex data from the GPU to the host, to prepare for processing it there.
-	///
-	/// This function assumes that the host data is outdated. If the host and GPU are already
-	/// synced, this will cause an unnecessary copy from the GPU to the host. If the host was
-	/// leading, then this will override the leading host data with the data on the GU, effectively
-	/// reversing the latest changes to the data. So it is important to check first what the sync
-	/// status is of these vertices between the different devices.
-	fn sync_gpu_to_host(&mut self) {
-		let mut host_verts = self.vertices.borrow_mut();
-		let gpu_verts = self.gpu_vertices.borrow();
-		let num_vertices = gpu_verts.as_ref().unwrap().dims()[0] as usize;
+	fn bounding_box(&self) -> BoundingBox {
+		bounding_box::bounding_box_polygon(self)
+	}
 
-		//Copy the coordinates into a 1D array.
-		let mut coordinates = Vec::<Coordinate>::with_capacity(num_vertices * 2);
-		gpu_verts.as_ref().unwrap().host(&mut coordinates);
+	fn containment(&self, point: &Point2D, fill_rule: FillRule) -> Containment {
+		containment::containment_polygon(self, point, fill_rule)
+	}
 
-		//Unwrap the 1D array as vertices.
-		let num_host_verts = host_verts.len();
-		if num_vertices > num_host_verts {
-			host_verts.reserve(num_vertices - num_host_verts);
-		}
-		for i in 0..num_vertices {
-			host_verts[i] = Point2D { x: coordinates[i * 2], y: coordinates[i * 2 + 1] };
-		}
-		self.sync_status.replace(sync_status::SyncStatus::SYNCED); //They are now in sync.
-	}*/
-}
+	fn convex_hull(&self) -> Polygon {
+		convex_hull::convex_hull_polygon(self)
+	}
 
-impl TwoDimensional for Polygon {
-	fn translate(&mut self, dx: Coordinate, dy: Coordinate) {
-		translate::translate_polygon_st(self, dx, dy);
+	fn convexity(&self) -> Convexity {
+		convexity::convexity_polygon(self)
 	}
-}
 
-impl Shape2D for Polygon {
-	fn area(&self) -> Area {
-		return 0; //TODO: Implement.
+	fn crossings(&self) -> Vec<Crossing> {
+		self_intersection::crossings_polygon(self)
 	}
 
-	fn convexity(&self) -> Convexity {
-		return Convexity::UNKNOWN; //TODO: Implement.
+	fn triangulate(&self) -> Vec<u32> {
+		triangulate::triangulate_polygon(self)
 	}
 }
 
@@ -552,16 +527,14 @@ impl FromIterator<Point2D> for Polygon {
 	/// 	Point2D { x: 100, y: 0 },
 	/// 	Point2D { x: 50, y: 100 }
 	/// ]);
-	/// assert_eq!(poly[0], Point2D { x: 0, y: 0 });
-	/// assert_eq!(poly[1], Point2D { x: 100, y: 0 });
-	/// assert_eq!(poly[2], Point2D { x: 50, y: 100 });
+	/// assert_eq!(*poly.vertex(0), Point2D { x: 0, y: 0 });
+	/// assert_eq!(*poly.vertex(1), Point2D { x: 100, y: 0 });
+	/// assert_eq!(*poly.vertex(2), Point2D { x: 50, y: 100 });
 	/// ```
 	fn from_iter<T>(iter: T) -> Self
 			where T: IntoIterator<Item = Point2D> {
 		Polygon {
-			vertices: Rc::new(RefCell::new(Vec::from_iter(iter))),
-			gpu_vertices: Rc::new(RefCell::new(Array::new(0))),
-			sync_status: Rc::new(RefCell::new(sync_status::SyncStatus::HOST)),
+			vertices: VertexBuffer::new(Vec::from_iter(iter)),
 		}
 	}
 }
@@ -580,6 +553,18 @@ impl AsMut<Polygon> for Polygon {
 	}
 }
 
+impl PartialEq for Polygon {
+	/// Test whether two polygons have the same vertices, in the same order.
+	///
+	/// The polygons must also start at the same vertex. A polygon with its vertices rotated
+	/// describes the same shape, but is not considered equal.
+	fn eq(&self, other: &Polygon) -> bool {
+		self.host_vertices() == other.host_vertices()
+	}
+}
+
+impl Eq for Polygon {}
+
 impl fmt::Debug for Polygon {
 	/// A reference string representing this polygon, for debugging the polygon in a log or CLI
 	/// output.
@@ -590,65 +575,65 @@ impl fmt::Debug for Polygon {
 	}
 }
 
+#[cfg(feature = "serde")]
+impl Serialize for Polygon {
+	/// Serialise the vertices of this polygon, as a sequence of points.
+	///
+	/// If the latest version of the vertices is on the GPU, they are copied to the host first.
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.host_vertices().serialize(serializer)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Polygon {
+	/// Deserialise a polygon from a sequence of points.
+	///
+	/// The vertices of the new polygon are on the host.
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Polygon, D::Error> {
+		Ok(Polygon::from_iter(Vec::<Point2D>::deserialize(deserializer)?))
+	}
+}
+
 /// An iterator over the vertices of a polygon.
 ///
-/// This iterator holds a reference to the vertex data in the polygon. The reference is a guard to
-/// borrow the polygon's data. While the iterator is in use, the reference will be kept alive so
-/// that iteration can continue safely.
-///
-/// If the polygon is modified during iteration, the reference will panic due to the RefCell being
-/// obtained mutably while also being referenced to immutably. This is slightly different to the
-/// behaviour of a normal iterator.
-///
-/// This class is not an actual iterator but merely implements `IntoIterator`, consuming it to
-/// become an actual iterator but keeping the reference alive.
-struct PolygonIterator<'a> {
-	vertices_ref: Option<Ref<'a, [Point2D]>>,
+/// This iterator borrows the vertex data in the polygon, so the polygon can't be modified while
+/// iterating over it.
+pub struct PolygonIterator<'a> {
+	/// An iterator over the vertices on the host.
+	vertices: slice::Iter<'a, Point2D>,
 }
 
 impl<'a> Iterator for PolygonIterator<'a> {
-	type Item = Ref<'a, Point2D>;
+	type Item = &'a Point2D;
 
 	fn next(&mut self) -> Option<Self::Item> {
-		if self.vertices_ref.is_none() {
-			return None;
-		}
-		if let Some(borrow) = self.vertices_ref.take() {
-			if borrow.is_empty() {
-				return None;
-			}
-			let (head, tail) = Ref::map_split(borrow, |slice| {
-				slice.split_at(1)
-			});
-			self.vertices_ref.replace(tail);
-			return Some(Ref::map(head, |slice| &slice[0]));
-		}
-		None
+		self.vertices.next()
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.vertices.size_hint()
 	}
 }
 
-struct PolygonIteratorMut<'a> {
-	vertices_ref: Option<RefMut<'a, [Point2D]>>,
+/// An iterator over the vertices of a polygon, allowing them to be modified.
+///
+/// This iterator borrows the vertex data in the polygon mutably. Since the vertices may be
+/// modified, the copy on the host becomes the leading copy.
+pub struct PolygonIteratorMut<'a> {
+	/// An iterator over the vertices on the host.
+	vertices: slice::IterMut<'a, Point2D>,
 }
 
 impl<'a> Iterator for PolygonIteratorMut<'a> {
-	type Item = RefMut<'a, Point2D>;
+	type Item = &'a mut Point2D;
 
 	fn next(&mut self) -> Option<Self::Item> {
-		if self.vertices_ref.is_none() {
-			return None;
-		}
-		if let Some(borrow) = self.vertices_ref.take() {
-			if borrow.is_empty() {
-				return None;
-			}
-			let (head, tail) = RefMut::map_split(borrow, |slice| {
-				slice.split_at_mut(1)
-			});
-			self.vertices_ref.replace(tail);
-			return Some(RefMut::map(head, |slice| &mut slice[0]));
-		}
-		None
+		self.vertices.next()
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.vertices.size_hint()
 	}
 }
 
@@ -726,6 +711,16 @@ mod tests {
 		assert_eq!(poly.len(), 11, "After adding 10 more vertices, the length is now 11.");
 	}
 
+	/// Test comparing polygons, which are equal if their vertices are the same, in the same order.
+	#[test]
+	fn eq() {
+		assert_eq!(polygon::square_1000(), polygon::square_1000(), "The same vertices make the same polygon.");
+		assert_ne!(polygon::square_1000(), polygon::triangle_1000(), "Different vertices make different polygons.");
+		let square = polygon::square_1000();
+		let rotated = Polygon::from_iter(square.iter().skip(1).chain(square.iter().take(1)).copied());
+		assert_ne!(rotated, polygon::square_1000(), "Starting at a different vertex makes a different polygon.");
+	}
+
 	/// Test adding new vertices to a polygon.
 	#[test]
 	fn push() {
@@ -846,7 +841,7 @@ mod tests {
 		let mut poly = polygon::square_1000();
 		let copy = polygon::square_1000();
 		let mut i = 0;
-		for mut vertex in poly.iter_mut() {
+		for vertex in poly.iter_mut() {
 			assert_eq!(*vertex, *copy.vertex(i), "We must iterate over the polygon in index order.");
 			i += 1;
 			vertex.x += 33;
@@ -940,4 +935,133 @@ mod tests {
 		assert_eq!(*poly.vertex(1), Point2D { x: 200, y: 400 }, "The second vertex was modified.");
 		assert_eq!(*poly.vertex(2), Point2D { x: 10, y: 100 }, "The third vertex was not modified.");
 	}
+
+	/// Test that new polygons start out with their vertices on the host.
+	#[test]
+	fn sync_status_new() {
+		assert_eq!(Polygon::new().sync_status(), sync_status::SyncStatus::HOST, "New polygons only exist on the host.");
+		assert_eq!(Polygon::with_capacity(10).sync_status(), sync_status::SyncStatus::HOST, "New polygons only exist on the host.");
+		assert_eq!(polygon::square_1000().sync_status(), sync_status::SyncStatus::HOST, "New polygons only exist on the host.");
+	}
+
+	/// Test that reading and modifying vertices on the host keeps the host leading.
+	#[test]
+	fn sync_status_host_access() {
+		let mut poly = polygon::square_1000();
+		poly.push(Point2D { x: 500, y: 500 });
+		*poly.vertex_mut(0) = Point2D { x: 10, y: 10 };
+		assert_eq!(*poly.vertex(0), Point2D { x: 10, y: 10 });
+		assert_eq!(poly.sync_status(), sync_status::SyncStatus::HOST, "The vertices were never needed on the GPU.");
+	}
+
+	/// Test copying the vertices to the GPU and reading them there.
+	#[cfg(feature = "gpu")]
+	#[test]
+	fn sync_host_to_gpu() {
+		let poly = polygon::square_1000();
+		let handle = poly.gpu_vertices().clone();
+		assert_eq!(poly.sync_status(), sync_status::SyncStatus::SYNCED, "After copying to the GPU, both copies are up-to-date.");
+		assert_eq!(gpu::download(&handle), *poly.host_vertices(), "The GPU must hold the same vertices, in the same order.");
+		assert_eq!(poly.sync_status(), sync_status::SyncStatus::SYNCED, "Reading on the host doesn't change anything.");
+	}
+
+	/// Test that modifying the vertices on the host after syncing makes the host leading again.
+	#[cfg(feature = "gpu")]
+	#[test]
+	fn sync_host_modified_after_sync() {
+		let mut poly = polygon::square_1000();
+		poly.gpu_vertices();
+		poly.push(Point2D { x: 500, y: 500 });
+		assert_eq!(poly.sync_status(), sync_status::SyncStatus::HOST, "The host was modified, so it is leading.");
+		assert_eq!(gpu::download(poly.gpu_vertices()).len(), 5, "The modified vertices must be copied to the GPU again.");
+	}
+
+	/// Test modifying the vertices on the GPU and copying them back to the host.
+	#[cfg(feature = "gpu")]
+	#[test]
+	fn sync_gpu_to_host() {
+		let mut poly = polygon::square_1000();
+		let triangle = polygon::triangle_1000();
+		*poly.gpu_vertices_mut() = gpu::upload(triangle.host_vertices()); //Replace the data on the GPU.
+		assert_eq!(poly.sync_status(), sync_status::SyncStatus::GPU, "The GPU copy may have been modified, so it is leading.");
+		assert_eq!(poly.len(), 3, "The length is taken from the GPU copy.");
+		assert_eq!(poly.sync_status(), sync_status::SyncStatus::GPU, "Getting the length doesn't need to copy the vertices to the host.");
+		assert_eq!(*poly.host_vertices(), *triangle.host_vertices(), "The modified vertices must be copied back to the host.");
+		assert_eq!(poly.sync_status(), sync_status::SyncStatus::SYNCED, "After copying back to the host, both copies are up-to-date.");
+	}
+
+	/// Test copying an empty polygon to the GPU and back.
+	#[cfg(feature = "gpu")]
+	#[test]
+	fn sync_empty() {
+		let mut poly = Polygon::new();
+		poly.gpu_vertices_mut();
+		assert_eq!(poly.len(), 0, "There are still no vertices.");
+		assert!(poly.host_vertices().is_empty(), "There are still no vertices after copying them back.");
+	}
+
+	/// Test that polygons can be shared between threads.
+	///
+	/// This is mostly checked at compile time.
+	#[test]
+	fn send_sync() {
+		fn assert_send_sync<T: Send + Sync>() {}
+		assert_send_sync::<Polygon>();
+	}
+
+	/// Test moving a polygon to another thread and modifying it there.
+	#[test]
+	fn move_to_thread() {
+		let mut poly = polygon::square_1000();
+		poly = std::thread::spawn(move || {
+			poly.push(Point2D { x: 500, y: 500 });
+			poly
+		}).join().unwrap();
+		assert_eq!(poly.len(), 5, "The vertex was added on the other thread.");
+		assert_eq!(*poly.vertex(4), Point2D { x: 500, y: 500 });
+	}
+
+	/// Test reading a polygon from multiple threads at the same time.
+	#[test]
+	fn read_from_threads() {
+		use rayon::prelude::*;
+		let poly = polygon::square_1000();
+		let sum: Coordinate = (0..100).into_par_iter().map(|i| poly.vertex(i % 4).x).sum();
+		assert_eq!(sum, 50 * 1000, "Half of the vertices of the square have X coordinate 1000.");
+	}
+
+	/// Test copying the vertices back to the host from multiple threads at the same time.
+	///
+	/// Only one of the threads should copy them, while the others wait for the result.
+	#[cfg(feature = "gpu")]
+	#[test]
+	fn sync_gpu_to_host_from_threads() {
+		use rayon::prelude::*;
+		let mut poly = polygon::square_1000();
+		poly.gpu_vertices_mut();
+		let sum: Coordinate = (0..100).into_par_iter().map(|i| poly.vertex(i % 4).x).sum();
+		assert_eq!(sum, 50 * 1000, "All threads must see the vertices that were copied back.");
+		assert_eq!(poly.sync_status(), sync_status::SyncStatus::SYNCED, "The vertices were copied back to the host.");
+	}
+
+	/// Test serialising a polygon and deserialising it again.
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serde_round_trip() {
+		let json = serde_json::to_string(&polygon::triangle_1000()).unwrap();
+		assert_eq!(json, r#"[{"x":24,"y":24},{"x":1024,"y":24},{"x":524,"y":1024}]"#, "The polygon is serialised as a sequence of points.");
+		let poly: Polygon = serde_json::from_str(&json).unwrap();
+		assert_eq!(poly, polygon::triangle_1000());
+		assert_eq!(poly.sync_status(), sync_status::SyncStatus::HOST, "Deserialised polygons only exist on the host.");
+	}
+
+	/// Test serialising a polygon whose latest vertices are on the GPU.
+	#[cfg(all(feature = "serde", feature = "gpu"))]
+	#[test]
+	fn serde_from_gpu() {
+		let mut poly = polygon::square_1000();
+		*poly.gpu_vertices_mut() = gpu::upload(polygon::triangle_1000().host_vertices()); //Replace the data on the GPU.
+		let json = serde_json::to_string(&poly).unwrap();
+		assert_eq!(serde_json::from_str::<Polygon>(&json).unwrap(), polygon::triangle_1000(), "The vertices on the GPU are serialised.");
+	}
 }
\ No newline at end of file
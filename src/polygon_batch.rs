@@ -0,0 +1,517 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! Defines the PolygonBatch struct.
+
+use std::fmt; //You can print batches as text.
+use std::iter::FromIterator; //Constructing batches from iterable lists of polygons.
+#[cfg(feature = "gpu")]
+use std::sync::OnceLock; //To lazily copy the offsets to the GPU.
+#[cfg(feature = "gpu")]
+use cubecl::server::Handle; //To refer to the copy of the vertices on the GPU.
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer}; //To serialise batches as sequences of polygons.
+
+use crate::AffineTransform; //To transform the whole batch.
+use crate::Area; //To return the surface area of each polygon.
+use crate::BoundingBox; //To return the bounding box of each polygon.
+use crate::Coordinate; //To translate the whole batch.
+use crate::OverflowError; //If transforming the batch would move it out of range.
+use crate::OverflowPolicy; //To choose what happens if translating the batch moves it out of range.
+use crate::Point2D; //The vertices of the polygons are Point2D.
+use crate::Polygon; //The batch contains polygons.
+use crate::TwoDimensional; //The batch can be moved like any two-dimensional object.
+use crate::detail::sync_status; //To track whether the GPU or CPU copies are up-to-date.
+use crate::detail::vertex_buffer::VertexBuffer; //To store the vertices on the host and the GPU.
+use crate::operations::area; //To calculate the area of all polygons at once.
+use crate::operations::bounding_box; //To find the bounding boxes of all polygons at once.
+use crate::operations::transform; //To transform all polygons at once.
+use crate::operations::translate; //To translate all polygons at once.
+
+/// A collection of many separate polygons, which are processed together.
+///
+/// Processing many small polygons one by one is slow, especially on the GPU, where every polygon
+/// would need to be uploaded separately and every operation would need a kernel launch for each
+/// polygon. A batch stores the vertices of all of its polygons in a single buffer, with a table of
+/// offsets indicating where each polygon starts. Operations on the batch then process all of the
+/// polygons at once, with a single kernel launch or a single pass over the threads.
+///
+/// Unlike the contours of a `MultiPolygon`, the polygons in a batch are independent shapes. Their
+/// winding order doesn't make them holes, and operations like the area give a separate result for
+/// each polygon.
+///
+/// # Basic usage
+/// ```
+/// use apex::{Point2D, Polygon, PolygonBatch, TwoDimensional};
+/// let mut batch = PolygonBatch::new();
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 10, y: 10 }, Point2D { x: 0, y: 10 }]);
+/// batch.translate(5, 5);
+/// assert_eq!(batch.polygon(1)[0], Point2D { x: 5, y: 5 });
+/// assert_eq!(batch.areas(), vec![5000, 100]);
+/// ```
+///
+/// # Host vs. GPU
+/// The vertices of all polygons are stored together, in a single buffer, with a single state of
+/// synchronisation for the whole batch. When the batch is copied to the GPU, all polygons are
+/// copied at once. Like with `Polygon`, Apex decides for itself whether to process the batch on the
+/// host or on the GPU.
+pub struct PolygonBatch {
+	/// The vertices of all polygons, one polygon after another.
+	///
+	/// There is a copy of these vertices on the host, on the GPU, or both. These vertices are not
+	/// publicly accessible, since access to the most up-to-date version may require a sync between
+	/// the CPU and the GPU.
+	vertices: VertexBuffer,
+
+	/// The index of the first vertex of each polygon in the vertex buffer.
+	///
+	/// This has one more element than there are polygons. The last element is the total number of
+	/// vertices. So the vertices of polygon `i` are in the range from `offsets[i]` to
+	/// `offsets[i + 1]`. The polygons never change on the GPU, so this is only kept on the host.
+	offsets: Vec<usize>,
+
+	/// A copy of the offsets on the GPU, for the kernels that need to know where each polygon ends.
+	///
+	/// This is only copied to the GPU when a kernel needs it, and is released when the polygons
+	/// change.
+	#[cfg(feature = "gpu")]
+	gpu_offsets: OnceLock<Handle>,
+}
+
+impl PolygonBatch {
+	/// Create a new, empty batch, without any polygons.
+	pub fn new() -> Self {
+		PolygonBatch {
+			vertices: VertexBuffer::new(vec!()),
+			offsets: vec![0],
+			#[cfg(feature = "gpu")]
+			gpu_offsets: OnceLock::new(),
+		}
+	}
+
+	/// Get the number of polygons in this batch.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{Point2D, PolygonBatch};
+	/// let mut batch = PolygonBatch::new();
+	/// assert_eq!(batch.num_polygons(), 0);
+	/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+	/// assert_eq!(batch.num_polygons(), 1);
+	/// ```
+	pub fn num_polygons(&self) -> usize {
+		self.offsets.len() - 1
+	}
+
+	/// Get the total number of vertices in all polygons of this batch.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{Point2D, PolygonBatch};
+	/// let mut batch = PolygonBatch::new();
+	/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+	/// batch.push_polygon([Point2D { x: 200, y: 0 }, Point2D { x: 300, y: 0 }, Point2D { x: 250, y: 100 }]);
+	/// assert_eq!(batch.len(), 6);
+	/// ```
+	pub fn len(&self) -> usize {
+		self.vertices.len()
+	}
+
+	/// Get the vertices of one of the polygons.
+	///
+	/// # Arguments
+	/// * `index` - The index of the polygon, in the order that they were added.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{Point2D, PolygonBatch};
+	/// let mut batch = PolygonBatch::new();
+	/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+	/// assert_eq!(batch.polygon(0)[2], Point2D { x: 50, y: 100 });
+	/// ```
+	pub fn polygon(&self, index: usize) -> &[Point2D] {
+		&self.host_vertices()[self.offsets[index]..self.offsets[index + 1]]
+	}
+
+	/// Get the vertices of one of the polygons, allowing their modification.
+	///
+	/// The vertices can be moved, but no vertices can be added or removed this way.
+	///
+	/// # Arguments
+	/// * `index` - The index of the polygon, in the order that they were added.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{Point2D, PolygonBatch};
+	/// let mut batch = PolygonBatch::new();
+	/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+	/// batch.polygon_mut(0)[2].y = 200;
+	/// assert_eq!(batch.polygon(0)[2], Point2D { x: 50, y: 200 });
+	/// ```
+	pub fn polygon_mut(&mut self, index: usize) -> &mut [Point2D] {
+		let (start, end) = (self.offsets[index], self.offsets[index + 1]);
+		&mut self.host_vertices_mut()[start..end]
+	}
+
+	/// Create an iterator over the polygons of this batch.
+	///
+	/// The polygons are given in the order that they were added, each as a slice of vertices.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{Point2D, PolygonBatch};
+	/// let mut batch = PolygonBatch::new();
+	/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+	/// batch.push_polygon([Point2D { x: 200, y: 0 }, Point2D { x: 300, y: 0 }, Point2D { x: 300, y: 100 }, Point2D { x: 200, y: 100 }]);
+	/// let sizes: Vec<usize> = batch.polygons().map(|polygon| polygon.len()).collect();
+	/// assert_eq!(sizes, vec![3, 4]);
+	/// ```
+	pub fn polygons(&self) -> impl Iterator<Item = &[Point2D]> {
+		let vertices = self.host_vertices();
+		self.offsets.windows(2).map(move |range| &vertices[range[0]..range[1]])
+	}
+
+	/// Add a polygon to the end of this batch.
+	///
+	/// # Arguments
+	/// * `polygon` - The vertices of the new polygon.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{Point2D, PolygonBatch};
+	/// let mut batch = PolygonBatch::new();
+	/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+	/// assert_eq!(batch.areas(), vec![5000]);
+	/// ```
+	pub fn push_polygon<I>(&mut self, polygon: I)
+			where I: IntoIterator<Item = Point2D> {
+		let vertices = self.host_vertices_mut();
+		vertices.extend(polygon);
+		let end = vertices.len();
+		self.offsets.push(end);
+		self.polygons_changed();
+	}
+
+	/// Remove the last polygon from this batch and return it.
+	///
+	/// If there are no polygons, return `None`.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{Point2D, PolygonBatch};
+	/// let mut batch = PolygonBatch::new();
+	/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+	/// let removed = batch.pop_polygon().unwrap();
+	/// assert_eq!(removed.len(), 3);
+	/// assert_eq!(batch.num_polygons(), 0);
+	/// assert!(batch.pop_polygon().is_none());
+	/// ```
+	pub fn pop_polygon(&mut self) -> Option<Polygon> {
+		if self.num_polygons() == 0 {
+			return None;
+		}
+		self.offsets.pop();
+		let start = *self.offsets.last().expect("The start of the first polygon is always there.");
+		let removed = self.host_vertices_mut().split_off(start);
+		self.polygons_changed();
+		Some(Polygon::from_iter(removed))
+	}
+
+	/// Removes all polygons from this batch, leaving it empty.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{Point2D, PolygonBatch};
+	/// let mut batch = PolygonBatch::new();
+	/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 50, y: 100 }]);
+	/// batch.clear();
+	/// assert_eq!(batch.num_polygons(), 0);
+	/// assert_eq!(batch.len(), 0);
+	/// ```
+	pub fn clear(&mut self) {
+		self.host_vertices_mut().clear();
+		self.offsets.truncate(1);
+		self.polygons_changed();
+	}
+
+	/// Calculate the signed surface area of every polygon in this batch.
+	///
+	/// The areas of all polygons are calculated together, on a single thread, on multiple threads or
+	/// on the GPU. Polygons that wind counter-clockwise get a positive area, and polygons that wind
+	/// clockwise a negative area.
+	///
+	/// # Result
+	/// The area of each polygon, in the order that they were added.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{Point2D, PolygonBatch};
+	/// let mut batch = PolygonBatch::new();
+	/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+	/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 0, y: 100 }, Point2D { x: 100, y: 0 }]);
+	/// assert_eq!(batch.areas(), vec![5000, -5000]);
+	/// ```
+	pub fn areas(&self) -> Vec<Area> {
+		area::area_batch(self)
+	}
+
+	/// Find the bounding box of every polygon in this batch.
+	///
+	/// The boxes of all polygons are found together, on a single thread, on multiple threads or on
+	/// the GPU.
+	///
+	/// # Result
+	/// The bounding box of each polygon, in the order that they were added. Polygons without
+	/// vertices get an empty box.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{BoundingBox, Point2D, PolygonBatch};
+	/// let mut batch = PolygonBatch::new();
+	/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 100, y: 0 }, Point2D { x: 0, y: 100 }]);
+	/// batch.push_polygon([]);
+	/// assert_eq!(batch.bounding_boxes(), vec![BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 100, y: 100 } }, BoundingBox::empty()]);
+	/// ```
+	pub fn bounding_boxes(&self) -> Vec<BoundingBox> {
+		bounding_box::bounding_boxes_batch(self)
+	}
+
+	/// Get the index of the first vertex of each polygon in the vertex buffer, followed by the total
+	/// number of vertices.
+	pub(crate) fn offsets(&self) -> &[usize] {
+		&self.offsets
+	}
+
+	/// Get a copy of the offsets on the GPU, as 32-bit unsigned integers.
+	///
+	/// If they are not on the GPU yet, they are copied there first.
+	#[cfg(feature = "gpu")]
+	pub(crate) fn gpu_offsets(&self) -> &Handle {
+		use cubecl::prelude::CubeElement;
+		self.gpu_offsets.get_or_init(|| {
+			let offsets: Vec<u32> = self.offsets.iter().map(|offset| *offset as u32).collect();
+			crate::detail::gpu::client().create(u32::as_bytes(&offsets))
+		})
+	}
+
+	/// Get which copy of the vertices is currently the most up-to-date.
+	///
+	/// This is the same for all polygons in the batch, since they are all stored in the same
+	/// buffer.
+	pub(crate) fn sync_status(&self) -> sync_status::SyncStatus {
+		self.vertices.sync_status()
+	}
+
+	/// Obtain the vertices of all polygons on the host.
+	///
+	/// If the latest version of the vertices is in the GPU rather than the host, it will be copied
+	/// to the host's RAM. If the latest version of the vertices is on the CPU (or they are in
+	/// sync), it will simply give a reference to those.
+	pub(crate) fn host_vertices(&self) -> &Vec<Point2D> {
+		self.vertices.host()
+	}
+
+	/// Obtain the vertices of all polygons on the host, allowing their modification.
+	///
+	/// If the latest version of the vertices is in the GPU rather than the host, it will be copied
+	/// to the host's RAM first. Since the vertices may be modified, the host becomes the leading
+	/// copy.
+	pub(crate) fn host_vertices_mut(&mut self) -> &mut Vec<Point2D> {
+		self.vertices.host_mut()
+	}
+
+	/// Obtain the vertices of all polygons on the GPU.
+	///
+	/// If the latest version of the vertices is in the host rather than the GPU, it will be copied
+	/// to the GPU first, all polygons at once. On the GPU, the vertices are stored as a flat array of
+	/// coordinates, with the X and Y coordinates of each vertex interleaved.
+	#[cfg(feature = "gpu")]
+	pub(crate) fn gpu_vertices(&self) -> &Handle {
+		self.vertices.gpu()
+	}
+
+	/// Obtain the vertices of all polygons on the GPU, allowing their modification.
+	///
+	/// If the latest version of the vertices is in the host rather than the GPU, it will be copied
+	/// to the GPU first. Since the vertices may be modified, the GPU becomes the leading copy.
+	#[cfg(feature = "gpu")]
+	pub(crate) fn gpu_vertices_mut(&mut self) -> &mut Handle {
+		self.vertices.gpu_mut()
+	}
+
+	/// Release the copy of the offsets on the GPU, after polygons were added or removed.
+	fn polygons_changed(&mut self) {
+		#[cfg(feature = "gpu")]
+		self.gpu_offsets.take();
+	}
+}
+
+impl TwoDimensional for PolygonBatch {
+	fn translate_with_policy(&mut self, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+		translate::translate_batch(self, dx, dy, policy)
+	}
+
+	fn transform(&mut self, transform: &AffineTransform) -> Result<(), OverflowError> {
+		transform::transform_batch(self, transform)
+	}
+}
+
+impl FromIterator<Polygon> for PolygonBatch {
+	/// Construct a new batch from a collection of polygons.
+	///
+	/// The vertices of all polygons are copied into the single buffer of the batch.
+	///
+	/// # Arguments
+	/// * `iter` - An object that can be converted into an iterator, yielding the polygons to put in
+	/// the new batch.
+	fn from_iter<T>(iter: T) -> Self
+			where T: IntoIterator<Item = Polygon> {
+		let mut vertices = vec!();
+		let mut offsets = vec![0];
+		for polygon in iter {
+			vertices.extend_from_slice(polygon.host_vertices());
+			offsets.push(vertices.len());
+		}
+		PolygonBatch {
+			vertices: VertexBuffer::new(vertices),
+			offsets,
+			#[cfg(feature = "gpu")]
+			gpu_offsets: OnceLock::new(),
+		}
+	}
+}
+
+impl fmt::Debug for PolygonBatch {
+	/// A reference string representing this batch, for debugging the batch in a log or CLI output.
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("PolygonBatch")
+			.field("polygons", &self.polygons().collect::<Vec<&[Point2D]>>())
+			.finish()
+	}
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for PolygonBatch {
+	/// Serialise the polygons of this batch, as a sequence of sequences of points.
+	///
+	/// If the latest version of the vertices is on the GPU, they are copied to the host first.
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_seq(self.polygons())
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for PolygonBatch {
+	/// Deserialise a batch from a sequence of polygons, each a sequence of points.
+	///
+	/// The vertices of the new batch are on the host.
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<PolygonBatch, D::Error> {
+		let mut result = PolygonBatch::new();
+		for polygon in Vec::<Vec<Point2D>>::deserialize(deserializer)? {
+			result.push_polygon(polygon);
+		}
+		Ok(result)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test::data::polygon;
+
+	/// Create a batch with a square, an empty polygon and a triangle.
+	///
+	/// The empty polygon in between tests that the offsets are followed correctly.
+	fn square_empty_triangle() -> PolygonBatch {
+		PolygonBatch::from_iter([polygon::square_1000(), Polygon::new(), polygon::triangle_1000()])
+	}
+
+	/// Test creating a new, empty batch.
+	#[test]
+	fn new() {
+		let batch = PolygonBatch::new();
+		assert_eq!(batch.num_polygons(), 0, "The new batch has no polygons.");
+		assert_eq!(batch.len(), 0, "The new batch has no vertices.");
+		assert_eq!(batch.sync_status(), sync_status::SyncStatus::HOST, "New batches only exist on the host.");
+	}
+
+	/// Test creating a batch from polygons.
+	#[test]
+	fn from_iter() {
+		let batch = square_empty_triangle();
+		assert_eq!(batch.num_polygons(), 3, "Each polygon is in the batch, including the empty one.");
+		assert_eq!(batch.len(), 7, "The square has 4 vertices, the triangle 3.");
+		assert_eq!(batch.offsets(), &[0, 4, 4, 7], "The offsets point to the start of each polygon, followed by the total.");
+		assert_eq!(batch.polygon(0), polygon::square_1000().host_vertices().as_slice(), "The first polygon is the square.");
+		assert_eq!(batch.polygon(1).len(), 0, "The second polygon is empty.");
+		assert_eq!(batch.polygon(2), polygon::triangle_1000().host_vertices().as_slice(), "The third polygon is the triangle.");
+	}
+
+	/// Test adding and removing polygons.
+	#[test]
+	fn push_pop_polygon() {
+		let mut batch = PolygonBatch::new();
+		batch.push_polygon(polygon::square_1000().iter().copied());
+		batch.push_polygon(polygon::triangle_1000().iter().copied());
+		assert_eq!(batch.num_polygons(), 2);
+
+		let triangle = batch.pop_polygon().expect("There were 2 polygons.");
+		assert_eq!(triangle, polygon::triangle_1000(), "The last polygon was the triangle.");
+		assert_eq!(batch.len(), 4, "Only the square is left.");
+		assert_eq!(batch.offsets(), &[0, 4], "The offset of the triangle was removed.");
+	}
+
+	/// Test modifying the vertices of one polygon.
+	#[test]
+	fn polygon_mut() {
+		let mut batch = square_empty_triangle();
+		batch.polygon_mut(2)[0] = Point2D { x: -10, y: -10 };
+		assert_eq!(batch.polygon(2)[0], Point2D { x: -10, y: -10 }, "The vertex was modified.");
+		assert_eq!(batch.polygon(0), polygon::square_1000().host_vertices().as_slice(), "The other polygons were not modified.");
+	}
+
+	/// Test removing all polygons.
+	#[test]
+	fn clear() {
+		let mut batch = square_empty_triangle();
+		batch.clear();
+		assert_eq!(batch.num_polygons(), 0, "After clearing, there are no polygons.");
+		assert_eq!(batch.len(), 0, "After clearing, there are no vertices.");
+	}
+
+	/// Test that batches can be shared between threads.
+	#[test]
+	fn send_sync() {
+		fn assert_send_sync<T: Send + Sync>() {}
+		assert_send_sync::<PolygonBatch>();
+	}
+
+	/// Test copying all polygons to the GPU at once, and back.
+	#[cfg(feature = "gpu")]
+	#[test]
+	fn sync_gpu() {
+		let mut batch = square_empty_triangle();
+		batch.gpu_vertices_mut();
+		assert_eq!(batch.sync_status(), sync_status::SyncStatus::GPU, "The GPU copy may have been modified, so it is leading.");
+		assert_eq!(batch.len(), 7, "All polygons are on the GPU.");
+		assert_eq!(batch.polygon(2), polygon::triangle_1000().host_vertices().as_slice(), "The polygons must be copied back unchanged.");
+		assert_eq!(batch.sync_status(), sync_status::SyncStatus::SYNCED, "After copying back to the host, both copies are up-to-date.");
+	}
+
+	/// Test serialising a batch and deserialising it again.
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serde_round_trip() {
+		let json = serde_json::to_string(&square_empty_triangle()).unwrap();
+		assert!(json.contains(r#"}],[],[{"#), "The empty polygon is serialised as an empty sequence.");
+		let batch: PolygonBatch = serde_json::from_str(&json).unwrap();
+		assert_eq!(batch.polygons().collect::<Vec<&[Point2D]>>(), square_empty_triangle().polygons().collect::<Vec<&[Point2D]>>());
+		assert_eq!(batch.sync_status(), sync_status::SyncStatus::HOST, "Deserialised batches only exist on the host.");
+	}
+}
\ No newline at end of file
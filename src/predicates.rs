@@ -0,0 +1,1037 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! Exact geometric predicates on points, which the operations are built on.
+//!
+//! The predicates are computed with integers that are wide enough to hold every intermediate
+//! result, so they never suffer from rounding errors. Where a result is not on the grid, like the
+//! intersection point of two segments, it is represented exactly as a fraction, and rounding it to
+//! coordinates is a separate, documented step. With the `coordinate64` feature, the predicates are
+//! only exact for coordinates up to `MAX_EXACT_COORDINATE`.
+//!
+//! Each predicate also has a GPU version, to use in kernels. The GPU lacks 128-bit integers, so
+//! these compute with several 32-bit limbs instead. They take the coordinates as `i64`, and give
+//! exactly the same results as the host versions for 32-bit coordinates.
+
+use std::cmp::{self, Ordering}; //To compare points and exact products.
+use cubecl::prelude::*; //For the GPU versions.
+
+use crate::Containment; //The result of the in-circle test.
+use crate::Coordinate; //To round intersection points to coordinates.
+use crate::Point2D; //The predicates are on points.
+
+/// The ways in which two line segments can meet.
+///
+/// The discriminants are what the GPU version of `segment_intersection` returns.
+#[allow(non_camel_case_types)] //The variants are capitalised like those of the other enums, with underscores between words.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SegmentIntersection {
+	/// The segments don't have any point in common.
+	DISJOINT = 0,
+
+	/// The segments have exactly one point in common, which is not an endpoint of either of them.
+	CROSSING = 1,
+
+	/// The segments have exactly one point in common, which is an endpoint of at least one of them.
+	TOUCHING = 2,
+
+	/// The segments are collinear and have more than one point in common.
+	OVERLAPPING = 3,
+}
+
+/// A point with rational coordinates, which can represent intersection points exactly.
+///
+/// Both coordinates share the same denominator, which is always positive. Rational points are
+/// ordered lexicographically, like points.
+#[derive(Clone, Copy, Debug)]
+pub struct RationalPoint {
+	/// The X coordinate, times the denominator.
+	pub x: i128,
+
+	/// The Y coordinate, times the denominator.
+	pub y: i128,
+
+	/// The denominator of both coordinates.
+	pub denominator: i128,
+}
+
+impl RationalPoint {
+	/// Create a rational point at the same position as a point on the grid.
+	///
+	/// # Arguments
+	/// * `point` - The point to represent.
+	pub fn from_point(point: &Point2D) -> RationalPoint {
+		RationalPoint { x: point.x as i128, y: point.y as i128, denominator: 1 }
+	}
+
+	/// Round this point to the nearest coordinates.
+	///
+	/// Each coordinate is rounded to the nearest integer, with halves rounded up, towards positive
+	/// infinity. This is the same rounding that `AffineTransform` uses. Since it doesn't depend on
+	/// where the origin is, translating the segments translates their rounded intersection exactly
+	/// as much.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::Point2D;
+	/// use apex::predicates::RationalPoint;
+	/// assert_eq!(RationalPoint { x: 5, y: -5, denominator: 2 }.rounded(), Point2D { x: 3, y: -2 });
+	/// assert_eq!(RationalPoint { x: 7, y: -7, denominator: 3 }.rounded(), Point2D { x: 2, y: -2 });
+	/// ```
+	pub fn rounded(&self) -> Point2D {
+		Point2D {
+			x: divide_rounded(self.x, self.denominator) as Coordinate,
+			y: divide_rounded(self.y, self.denominator) as Coordinate,
+		}
+	}
+}
+
+impl Ord for RationalPoint {
+	fn cmp(&self, other: &RationalPoint) -> Ordering {
+		compare_products(self.x, other.denominator, other.x, self.denominator)
+			.then_with(|| compare_products(self.y, other.denominator, other.y, self.denominator))
+	}
+}
+
+impl PartialOrd for RationalPoint {
+	fn partial_cmp(&self, other: &RationalPoint) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl PartialEq for RationalPoint {
+	fn eq(&self, other: &RationalPoint) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl Eq for RationalPoint {}
+
+/// Compute on which side of a line through two points a third point is, exactly.
+///
+/// This is the cross product of the line with the vector from its start to the point. For 32-bit
+/// coordinates it needs 66 bits, more than `Area` has, so it is computed in 128 bits.
+///
+/// # Arguments
+/// * `start` - A point on the line.
+/// * `end` - Another point on the line, in the direction of the line.
+/// * `point` - The point to test.
+///
+/// # Result
+/// Twice the signed area of the triangle between the three points. This is positive if the third
+/// point is left of the line, negative if it is right of it, or zero if it is on the line.
+///
+/// # Examples
+/// ```
+/// use apex::Point2D;
+/// use apex::predicates::orientation;
+/// let start = Point2D { x: 0, y: 0 };
+/// let end = Point2D { x: 10, y: 0 };
+/// assert_eq!(orientation(&start, &end, &Point2D { x: 3, y: 5 }), 50);
+/// assert_eq!(orientation(&start, &end, &Point2D { x: 3, y: -5 }), -50);
+/// assert_eq!(orientation(&start, &end, &Point2D { x: 30, y: 0 }), 0);
+/// ```
+pub fn orientation(start: &Point2D, end: &Point2D, point: &Point2D) -> i128 {
+	let line = direction(start, end);
+	let relative = direction(start, point);
+	line.0 * relative.1 - line.1 * relative.0
+}
+
+/// Find where a point is relative to the circle through three other points, exactly.
+///
+/// The three points may be in either orientation. If they are collinear, the circle degenerates into
+/// the line through them. Points on that line are then on the boundary, and all other points are
+/// outside.
+///
+/// The determinant that decides this needs about 135 bits for 32-bit coordinates, so it is computed
+/// in 256 bits.
+///
+/// # Arguments
+/// * `a` - The first point on the circle.
+/// * `b` - The second point on the circle.
+/// * `c` - The third point on the circle.
+/// * `point` - The point to test.
+///
+/// # Result
+/// Whether the point is inside, outside or on the circle.
+///
+/// # Examples
+/// ```
+/// use apex::{Containment, Point2D};
+/// use apex::predicates::in_circle;
+/// let a = Point2D { x: 10, y: 0 };
+/// let b = Point2D { x: 0, y: 10 };
+/// let c = Point2D { x: -10, y: 0 };
+/// assert_eq!(in_circle(&a, &b, &c, &Point2D { x: 3, y: 3 }), Containment::INSIDE);
+/// assert_eq!(in_circle(&a, &b, &c, &Point2D { x: 0, y: -10 }), Containment::BOUNDARY);
+/// assert_eq!(in_circle(&a, &b, &c, &Point2D { x: 8, y: 8 }), Containment::OUTSIDE);
+/// ```
+pub fn in_circle(a: &Point2D, b: &Point2D, c: &Point2D, point: &Point2D) -> Containment {
+	let lifted = |vertex: &Point2D| {
+		let (dx, dy) = direction(point, vertex);
+		(dx, dy, dx * dx + dy * dy)
+	};
+	let (a_dx, a_dy, a_lift) = lifted(a);
+	let (b_dx, b_dy, b_lift) = lifted(b);
+	let (c_dx, c_dy, c_lift) = lifted(c);
+	let terms = [
+		(a_lift, b_dx * c_dy - b_dy * c_dx),
+		(b_lift, c_dx * a_dy - c_dy * a_dx),
+		(c_lift, a_dx * b_dy - a_dy * b_dx),
+	];
+	//The determinant is the sum of the terms. Sum the positive and negative terms separately, to compare them.
+	let mut positive = (0, 0);
+	let mut negative = (0, 0);
+	for (lift, minor) in terms {
+		let product = wide_product(lift as u128, minor.unsigned_abs());
+		if minor > 0 {
+			positive = wide_sum(positive, product);
+		} else {
+			negative = wide_sum(negative, product);
+		}
+	}
+	let determinant = positive.cmp(&negative);
+	let circle_orientation = orientation(a, b, c).signum();
+	if determinant == Ordering::Equal {
+		Containment::BOUNDARY
+	} else if circle_orientation != 0 && (circle_orientation > 0) == (determinant == Ordering::Greater) {
+		Containment::INSIDE
+	} else {
+		Containment::OUTSIDE
+	}
+}
+
+/// Find how two line segments meet, exactly.
+///
+/// Segments whose endpoints coincide are treated as single points.
+///
+/// # Arguments
+/// * `a_start` - One endpoint of the first segment.
+/// * `a_end` - The other endpoint of the first segment.
+/// * `b_start` - One endpoint of the second segment.
+/// * `b_end` - The other endpoint of the second segment.
+///
+/// # Result
+/// Whether the segments cross, touch, overlap or are disjoint.
+///
+/// # Examples
+/// ```
+/// use apex::Point2D;
+/// use apex::predicates::{segment_intersection, SegmentIntersection};
+/// let start = Point2D { x: 0, y: 0 };
+/// let end = Point2D { x: 10, y: 10 };
+/// assert_eq!(segment_intersection(&start, &end, &Point2D { x: 0, y: 10 }, &Point2D { x: 10, y: 0 }), SegmentIntersection::CROSSING);
+/// assert_eq!(segment_intersection(&start, &end, &Point2D { x: 5, y: 5 }, &Point2D { x: 10, y: 0 }), SegmentIntersection::TOUCHING);
+/// assert_eq!(segment_intersection(&start, &end, &Point2D { x: 5, y: 5 }, &Point2D { x: 20, y: 20 }), SegmentIntersection::OVERLAPPING);
+/// assert_eq!(segment_intersection(&start, &end, &Point2D { x: 20, y: 0 }, &Point2D { x: 30, y: 0 }), SegmentIntersection::DISJOINT);
+/// ```
+pub fn segment_intersection(a_start: &Point2D, a_end: &Point2D, b_start: &Point2D, b_end: &Point2D) -> SegmentIntersection {
+	let side_b_start = orientation(a_start, a_end, b_start).signum();
+	let side_b_end = orientation(a_start, a_end, b_end).signum();
+	let side_a_start = orientation(b_start, b_end, a_start).signum();
+	let side_a_end = orientation(b_start, b_end, a_end).signum();
+	//Collinear points are ordered along their line when compared lexicographically.
+	let within = |point: &Point2D, start: &Point2D, end: &Point2D| cmp::min(start, end) <= point && point <= cmp::max(start, end);
+	let touching_if = |condition: bool| if condition { SegmentIntersection::TOUCHING } else { SegmentIntersection::DISJOINT };
+	if a_start == a_end && b_start == b_end {
+		touching_if(a_start == b_start)
+	} else if a_start == a_end {
+		touching_if(side_a_start == 0 && within(a_start, b_start, b_end))
+	} else if b_start == b_end {
+		touching_if(side_b_start == 0 && within(b_start, a_start, a_end))
+	} else if side_b_start == 0 && side_b_end == 0 {
+		let overlap_start = cmp::max(cmp::min(a_start, a_end), cmp::min(b_start, b_end));
+		let overlap_end = cmp::min(cmp::max(a_start, a_end), cmp::max(b_start, b_end));
+		match overlap_start.cmp(overlap_end) {
+			Ordering::Less => SegmentIntersection::OVERLAPPING,
+			Ordering::Equal => SegmentIntersection::TOUCHING,
+			Ordering::Greater => SegmentIntersection::DISJOINT,
+		}
+	} else if side_b_start * side_b_end < 0 && side_a_start * side_a_end < 0 {
+		SegmentIntersection::CROSSING
+	} else {
+		touching_if(side_b_start * side_b_end <= 0 && side_a_start * side_a_end <= 0)
+	}
+}
+
+/// Find the point where two line segments meet, exactly.
+///
+/// # Arguments
+/// * `a_start` - One endpoint of the first segment.
+/// * `a_end` - The other endpoint of the first segment.
+/// * `b_start` - One endpoint of the second segment.
+/// * `b_end` - The other endpoint of the second segment.
+///
+/// # Result
+/// The point where the segments meet, if they meet in a single point and are not parallel. Parallel
+/// segments that touch at an endpoint are not reported, since the endpoint is already on the grid.
+///
+/// # Examples
+/// ```
+/// use apex::Point2D;
+/// use apex::predicates::intersection;
+/// let point = intersection(&Point2D { x: 0, y: 0 }, &Point2D { x: 3, y: 3 }, &Point2D { x: 0, y: 2 }, &Point2D { x: 2, y: 0 }).unwrap();
+/// assert_eq!((point.x / point.denominator, point.x % point.denominator), (1, 0), "They cross at 1,1.");
+/// let point = intersection(&Point2D { x: 0, y: 0 }, &Point2D { x: 3, y: 0 }, &Point2D { x: 0, y: -1 }, &Point2D { x: 3, y: 1 }).unwrap();
+/// assert_eq!(point.rounded(), Point2D { x: 2, y: 0 }, "They cross at 1.5,0, which is rounded up.");
+/// assert!(intersection(&Point2D { x: 0, y: 0 }, &Point2D { x: 3, y: 0 }, &Point2D { x: 0, y: 1 }, &Point2D { x: 3, y: 1 }).is_none(), "Parallel segments.");
+/// ```
+pub fn intersection(a_start: &Point2D, a_end: &Point2D, b_start: &Point2D, b_end: &Point2D) -> Option<RationalPoint> {
+	let a_direction = direction(a_start, a_end);
+	let b_direction = direction(b_start, b_end);
+	let to_b = direction(a_start, b_start);
+	//They meet at a_start + a_direction * a_numerator / denominator = b_start + b_direction * b_numerator / denominator.
+	let mut denominator = a_direction.0 * b_direction.1 - a_direction.1 * b_direction.0;
+	let mut a_numerator = to_b.0 * b_direction.1 - to_b.1 * b_direction.0;
+	let mut b_numerator = to_b.0 * a_direction.1 - to_b.1 * a_direction.0;
+	if denominator == 0 {
+		return None;
+	}
+	if denominator < 0 {
+		denominator = -denominator;
+		a_numerator = -a_numerator;
+		b_numerator = -b_numerator;
+	}
+	if a_numerator < 0 || a_numerator > denominator || b_numerator < 0 || b_numerator > denominator {
+		return None;
+	}
+	Some(RationalPoint {
+		x: a_start.x as i128 * denominator + a_direction.0 * a_numerator,
+		y: a_start.y as i128 * denominator + a_direction.1 * a_numerator,
+		denominator,
+	})
+}
+
+/// Get the direction vector from one point to another.
+///
+/// The vector is widened, so that cross products of such vectors can be computed without overflow.
+pub(crate) fn direction(from: &Point2D, to: &Point2D) -> (i128, i128) {
+	(to.x as i128 - from.x as i128, to.y as i128 - from.y as i128)
+}
+
+/// Divide two integers, rounding to the nearest integer, with halves rounded up.
+///
+/// # Arguments
+/// * `numerator` - The number to divide.
+/// * `denominator` - The number to divide by. This must not be zero.
+pub(crate) fn divide_rounded(numerator: i128, denominator: i128) -> i128 {
+	let (numerator, denominator) = if denominator < 0 { (-numerator, -denominator) } else { (numerator, denominator) };
+	(numerator * 2 + denominator).div_euclid(denominator * 2)
+}
+
+/// Compare two products of integers exactly, without overflow.
+///
+/// The positions of crossings need about 100 bits, and multiplying them needs about 200 bits. The
+/// products are computed in 256 bits, as pairs of a high and a low half.
+///
+/// # Result
+/// How `a * b` compares to `c * d`.
+pub(crate) fn compare_products(a: i128, b: i128, c: i128, d: i128) -> Ordering {
+	let sign_ab = a.signum() * b.signum();
+	let sign_cd = c.signum() * d.signum();
+	if sign_ab != sign_cd || sign_ab == 0 {
+		return sign_ab.cmp(&sign_cd);
+	}
+	let magnitude_ab = wide_product(a.unsigned_abs(), b.unsigned_abs());
+	let magnitude_cd = wide_product(c.unsigned_abs(), d.unsigned_abs());
+	if sign_ab > 0 { magnitude_ab.cmp(&magnitude_cd) } else { magnitude_cd.cmp(&magnitude_ab) }
+}
+
+/// Find the sign of a sum of products of integers exactly, without overflow.
+///
+/// Like in `compare_products`, the products are computed in 256 bits. The positive and negative
+/// products are summed separately, and then compared.
+///
+/// # Arguments
+/// * `products` - The pairs of factors of the products to sum.
+///
+/// # Result
+/// How the sum of the products compares to zero.
+pub(crate) fn sum_of_products_sign(products: &[(i128, i128)]) -> Ordering {
+	let mut positive = (0, 0);
+	let mut negative = (0, 0);
+	for (a, b) in products {
+		let product = wide_product(a.unsigned_abs(), b.unsigned_abs());
+		if a.signum() * b.signum() > 0 {
+			positive = wide_sum(positive, product);
+		} else {
+			negative = wide_sum(negative, product);
+		}
+	}
+	positive.cmp(&negative)
+}
+
+/// Multiply two unsigned integers into a 256-bit result.
+///
+/// # Result
+/// The high and low 128 bits of the product.
+fn wide_product(a: u128, b: u128) -> (u128, u128) {
+	const LOW: u128 = u64::MAX as u128;
+	let (a_high, a_low) = (a >> 64, a & LOW);
+	let (b_high, b_low) = (b >> 64, b & LOW);
+	let low = a_low * b_low;
+	let cross_a = a_high * b_low;
+	let cross_b = a_low * b_high;
+	let middle = (low >> 64) + (cross_a & LOW) + (cross_b & LOW);
+	let high = a_high * b_high + (cross_a >> 64) + (cross_b >> 64) + (middle >> 64);
+	(high, (low & LOW) | (middle << 64))
+}
+
+/// Add two 256-bit unsigned integers, given as pairs of a high and a low half.
+fn wide_sum(a: (u128, u128), b: (u128, u128)) -> (u128, u128) {
+	let (low, carry) = a.1.overflowing_add(b.1);
+	(a.0 + b.0 + carry as u128, low)
+}
+
+/// Compute on which side of a line through two points a third point is, on the GPU.
+///
+/// The cross product that determines this needs 66 bits, which the GPU doesn't have. But the
+/// magnitudes of its two terms fit in unsigned 64-bit integers, so their signs and magnitudes are
+/// compared separately.
+///
+/// # Result
+/// The sign of `orientation`: 1 if the third point is left of the line, -1 if it is right of the
+/// line, or 0 if it is on the line.
+#[cube]
+pub fn orientation_gpu(start_x: i64, start_y: i64, end_x: i64, end_y: i64, point_x: i64, point_y: i64) -> i32 {
+	let line_x = end_x - start_x;
+	let line_y = end_y - start_y;
+	let relative_x = point_x - start_x;
+	let relative_y = point_y - start_y;
+	//The cross product is line_x * relative_y - line_y * relative_x.
+	let first_sign = sign(line_x) * sign(relative_y);
+	let second_sign = sign(line_y) * sign(relative_x);
+	let first = magnitude(line_x) * magnitude(relative_y);
+	let second = magnitude(line_y) * magnitude(relative_x);
+	let mut result: i32 = 0;
+	if first_sign == second_sign {
+		if first > second {
+			result = first_sign;
+		} else if first < second {
+			result = -first_sign;
+		}
+	} else if first_sign != 0 {
+		result = first_sign; //The terms have opposite signs, so they add up.
+	} else {
+		result = -second_sign;
+	}
+	result
+}
+
+/// Find where a point is relative to the circle through three other points, on the GPU.
+///
+/// # Result
+/// 1 if the point is inside the circle, 0 if it is on the circle or -1 if it is outside, exactly
+/// like `in_circle`.
+#[cube]
+#[allow(clippy::too_many_arguments)] //Kernels pass points as separate coordinates.
+pub fn in_circle_gpu(a_x: i64, a_y: i64, b_x: i64, b_y: i64, c_x: i64, c_y: i64, point_x: i64, point_y: i64) -> i32 {
+	let a_dx = a_x - point_x;
+	let a_dy = a_y - point_y;
+	let b_dx = b_x - point_x;
+	let b_dy = b_y - point_y;
+	let c_dx = c_x - point_x;
+	let c_dy = c_y - point_y;
+	//Sum the positive and negative terms of the determinant separately, to compare them.
+	let mut positive = Array::<u64>::new(6);
+	let mut negative = Array::<u64>::new(6);
+	limbs_set(&mut positive, u64::new(0));
+	limbs_set(&mut negative, u64::new(0));
+	add_in_circle_term(&mut positive, &mut negative, a_dx, a_dy, b_dx, b_dy, c_dx, c_dy);
+	add_in_circle_term(&mut positive, &mut negative, b_dx, b_dy, c_dx, c_dy, a_dx, a_dy);
+	add_in_circle_term(&mut positive, &mut negative, c_dx, c_dy, a_dx, a_dy, b_dx, b_dy);
+	let determinant = limbs_compare(&positive, &negative);
+	let circle_orientation = orientation_gpu(a_x, a_y, b_x, b_y, c_x, c_y);
+	let mut result: i32 = 0;
+	if determinant != 0 {
+		if circle_orientation == 0 {
+			result = -1;
+		} else {
+			result = determinant * circle_orientation;
+		}
+	}
+	result
+}
+
+/// Find how two line segments meet, on the GPU.
+///
+/// # Result
+/// The discriminant of the `SegmentIntersection` that `segment_intersection` gives.
+#[cube]
+#[allow(clippy::too_many_arguments)] //Kernels pass points as separate coordinates.
+pub fn segment_intersection_gpu(a_start_x: i64, a_start_y: i64, a_end_x: i64, a_end_y: i64, b_start_x: i64, b_start_y: i64, b_end_x: i64, b_end_y: i64) -> u32 {
+	let side_b_start = orientation_gpu(a_start_x, a_start_y, a_end_x, a_end_y, b_start_x, b_start_y);
+	let side_b_end = orientation_gpu(a_start_x, a_start_y, a_end_x, a_end_y, b_end_x, b_end_y);
+	let side_a_start = orientation_gpu(b_start_x, b_start_y, b_end_x, b_end_y, a_start_x, a_start_y);
+	let side_a_end = orientation_gpu(b_start_x, b_start_y, b_end_x, b_end_y, a_end_x, a_end_y);
+	let a_is_point = a_start_x == a_end_x && a_start_y == a_end_y;
+	let b_is_point = b_start_x == b_end_x && b_start_y == b_end_y;
+	//Order the endpoints of both segments lexicographically.
+	let mut a_low_x = a_start_x;
+	let mut a_low_y = a_start_y;
+	let mut a_high_x = a_end_x;
+	let mut a_high_y = a_end_y;
+	if is_before(a_end_x, a_end_y, a_start_x, a_start_y) {
+		a_low_x = a_end_x;
+		a_low_y = a_end_y;
+		a_high_x = a_start_x;
+		a_high_y = a_start_y;
+	}
+	let mut b_low_x = b_start_x;
+	let mut b_low_y = b_start_y;
+	let mut b_high_x = b_end_x;
+	let mut b_high_y = b_end_y;
+	if is_before(b_end_x, b_end_y, b_start_x, b_start_y) {
+		b_low_x = b_end_x;
+		b_low_y = b_end_y;
+		b_high_x = b_start_x;
+		b_high_y = b_start_y;
+	}
+
+	let mut result: u32 = 0; //Disjoint.
+	if a_is_point && b_is_point {
+		if a_start_x == b_start_x && a_start_y == b_start_y {
+			result = 2; //Touching.
+		}
+	} else if a_is_point {
+		if side_a_start == 0 && !is_before(a_start_x, a_start_y, b_low_x, b_low_y) && !is_before(b_high_x, b_high_y, a_start_x, a_start_y) {
+			result = 2; //Touching.
+		}
+	} else if b_is_point {
+		if side_b_start == 0 && !is_before(b_start_x, b_start_y, a_low_x, a_low_y) && !is_before(a_high_x, a_high_y, b_start_x, b_start_y) {
+			result = 2; //Touching.
+		}
+	} else if side_b_start == 0 && side_b_end == 0 {
+		//Collinear. The overlap is from the highest low endpoint to the lowest high endpoint.
+		let mut overlap_start_x = a_low_x;
+		let mut overlap_start_y = a_low_y;
+		if is_before(a_low_x, a_low_y, b_low_x, b_low_y) {
+			overlap_start_x = b_low_x;
+			overlap_start_y = b_low_y;
+		}
+		let mut overlap_end_x = a_high_x;
+		let mut overlap_end_y = a_high_y;
+		if is_before(b_high_x, b_high_y, a_high_x, a_high_y) {
+			overlap_end_x = b_high_x;
+			overlap_end_y = b_high_y;
+		}
+		if is_before(overlap_start_x, overlap_start_y, overlap_end_x, overlap_end_y) {
+			result = 3; //Overlapping.
+		} else if overlap_start_x == overlap_end_x && overlap_start_y == overlap_end_y {
+			result = 2; //Touching.
+		}
+	} else if side_b_start * side_b_end < 0 && side_a_start * side_a_end < 0 {
+		result = 1; //Crossing.
+	} else if side_b_start * side_b_end <= 0 && side_a_start * side_a_end <= 0 {
+		result = 2; //Touching.
+	}
+	result
+}
+
+/// Find the point where two line segments meet, rounded to the nearest coordinates, on the GPU.
+///
+/// # Arguments
+/// * `point` - The output. If the segments meet, the X and Y coordinates of the point where they
+/// meet are stored in its first two elements, rounded like `RationalPoint::rounded` does.
+///
+/// # Result
+/// Whether the segments meet in a single point and are not parallel, like `intersection`.
+#[cube]
+#[allow(clippy::too_many_arguments)] //Kernels pass points as separate coordinates.
+pub fn intersection_gpu(a_start_x: i64, a_start_y: i64, a_end_x: i64, a_end_y: i64, b_start_x: i64, b_start_y: i64, b_end_x: i64, b_end_y: i64, point: &mut Array<i64>) -> bool {
+	let a_dx = a_end_x - a_start_x;
+	let a_dy = a_end_y - a_start_y;
+	let b_dx = b_end_x - b_start_x;
+	let b_dy = b_end_y - b_start_y;
+	let to_b_x = b_start_x - a_start_x;
+	let to_b_y = b_start_y - a_start_y;
+	let mut denominator = Array::<u64>::new(6);
+	let mut a_numerator = Array::<u64>::new(6);
+	let mut b_numerator = Array::<u64>::new(6);
+	let denominator_sign = cross(a_dx, a_dy, b_dx, b_dy, &mut denominator);
+	//Flip the signs of the numerators along with the denominator, to make the denominator positive.
+	let a_sign = cross(to_b_x, to_b_y, b_dx, b_dy, &mut a_numerator) * denominator_sign;
+	let b_sign = cross(to_b_x, to_b_y, a_dx, a_dy, &mut b_numerator) * denominator_sign;
+	let a_within = a_sign >= 0 && limbs_compare(&a_numerator, &denominator) <= 0;
+	let b_within = b_sign >= 0 && limbs_compare(&b_numerator, &denominator) <= 0;
+	let meets = denominator_sign != 0 && a_within && b_within;
+	if meets {
+		point[0] = a_start_x + rounded_fraction(a_dx, &a_numerator, &denominator);
+		point[1] = a_start_y + rounded_fraction(a_dy, &a_numerator, &denominator);
+	}
+	meets
+}
+
+/// Get the sign of an integer, on the GPU.
+///
+/// # Result
+/// 1 if it is positive, -1 if it is negative, or 0 if it is zero.
+#[cube]
+fn sign(value: i64) -> i32 {
+	let mut result: i32 = 0;
+	if value > 0 {
+		result = 1;
+	} else if value < 0 {
+		result = -1;
+	}
+	result
+}
+
+/// Get the absolute value of an integer as an unsigned integer, on the GPU.
+#[cube]
+fn magnitude(value: i64) -> u64 {
+	let mut result = value;
+	if value < 0 {
+		result = -value;
+	}
+	u64::cast_from(result)
+}
+
+/// Test whether a point comes before another point lexicographically, on the GPU.
+#[cube]
+fn is_before(x: i64, y: i64, other_x: i64, other_y: i64) -> bool {
+	x < other_x || (x == other_x && y < other_y)
+}
+
+/// Compute the cross product of two vectors exactly, on the GPU.
+///
+/// # Arguments
+/// * `limbs` - The output, the magnitude of the cross product.
+///
+/// # Result
+/// The sign of the cross product.
+#[cube]
+fn cross(a_x: i64, a_y: i64, b_x: i64, b_y: i64, limbs: &mut Array<u64>) -> i32 {
+	//The cross product is a_x * b_y - a_y * b_x.
+	let first_sign = sign(a_x) * sign(b_y);
+	let second_sign = sign(a_y) * sign(b_x);
+	let first = magnitude(a_x) * magnitude(b_y);
+	let second = magnitude(a_y) * magnitude(b_x);
+	let mut result: i32 = 0;
+	if first_sign == second_sign {
+		if first >= second {
+			limbs_set(limbs, first - second);
+			if first > second {
+				result = first_sign;
+			}
+		} else {
+			limbs_set(limbs, second - first);
+			result = -first_sign;
+		}
+	} else {
+		//The terms have opposite signs, so their magnitudes add up, possibly beyond 64 bits.
+		let mut addend = Array::<u64>::new(6);
+		limbs_set(limbs, first);
+		limbs_set(&mut addend, second);
+		limbs_add(limbs, &addend);
+		if first_sign != 0 {
+			result = first_sign;
+		} else {
+			result = -second_sign;
+		}
+	}
+	result
+}
+
+/// Add one of the terms of the in-circle determinant, on the GPU.
+///
+/// The term is the squared distance to the first vector, times the cross product of the other two.
+///
+/// # Arguments
+/// * `positive` - The sum of the positive terms, to add the term to if it is positive.
+/// * `negative` - The sum of the magnitudes of the negative terms, to add the term to if it is
+/// negative.
+#[cube]
+#[allow(clippy::too_many_arguments)] //Kernels pass points as separate coordinates.
+fn add_in_circle_term(positive: &mut Array<u64>, negative: &mut Array<u64>, lift_x: i64, lift_y: i64, first_x: i64, first_y: i64, second_x: i64, second_y: i64) {
+	let mut lift = Array::<u64>::new(6);
+	let mut lift_y_squared = Array::<u64>::new(6);
+	limbs_set(&mut lift, magnitude(lift_x) * magnitude(lift_x));
+	limbs_set(&mut lift_y_squared, magnitude(lift_y) * magnitude(lift_y));
+	limbs_add(&mut lift, &lift_y_squared);
+	let mut minor = Array::<u64>::new(6);
+	let minor_sign = cross(first_x, first_y, second_x, second_y, &mut minor);
+	let mut term = Array::<u64>::new(6);
+	limbs_multiply(&mut term, &lift, &minor);
+	if minor_sign > 0 {
+		limbs_add(positive, &term);
+	} else {
+		limbs_add(negative, &term);
+	}
+}
+
+/// Compute `factor * numerator / denominator`, rounded like `RationalPoint::rounded` does, on the
+/// GPU.
+///
+/// The numerator must be at most the denominator, so the result is at most the factor.
+///
+/// # Arguments
+/// * `factor` - A signed integer of at most 32 bits.
+/// * `numerator` - The magnitude of the numerator.
+/// * `denominator` - The magnitude of the denominator, which must not be zero.
+#[cube]
+fn rounded_fraction(factor: i64, numerator: &Array<u64>, denominator: &Array<u64>) -> i64 {
+	//For a positive factor, the result is floor((2 * factor * numerator + denominator) / (2 * denominator)).
+	//For a negative factor, it is -floor((2 * |factor| * numerator + denominator - 1) / (2 * denominator)).
+	let mut doubled_factor = Array::<u64>::new(6);
+	limbs_set(&mut doubled_factor, magnitude(factor) * 2);
+	let mut dividend = Array::<u64>::new(6);
+	limbs_multiply(&mut dividend, &doubled_factor, numerator);
+	limbs_add(&mut dividend, denominator);
+	if factor < 0 {
+		let mut one = Array::<u64>::new(6);
+		limbs_set(&mut one, u64::new(1));
+		limbs_subtract(&mut dividend, &one);
+	}
+	let mut divisor = Array::<u64>::new(6);
+	limbs_set(&mut divisor, u64::new(0));
+	limbs_add(&mut divisor, denominator);
+	limbs_add(&mut divisor, denominator);
+
+	//The quotient is at most |factor|, the difference between two 32-bit coordinates, so it fits in 33 bits. Find it bit by bit.
+	let mut quotient: u64 = 0;
+	let mut bit_value: u64 = 0x1_0000_0000u64;
+	let mut candidate_limbs = Array::<u64>::new(6);
+	let mut product = Array::<u64>::new(6);
+	for _ in 0..33 {
+		let candidate = quotient | bit_value;
+		limbs_set(&mut candidate_limbs, candidate);
+		limbs_multiply(&mut product, &candidate_limbs, &divisor);
+		if limbs_compare(&product, &dividend) <= 0 {
+			quotient = candidate;
+		}
+		bit_value >>= 1;
+	}
+	let mut result = i64::cast_from(quotient);
+	if factor < 0 {
+		result = -result;
+	}
+	result
+}
+
+/// Store an unsigned 64-bit integer in six 32-bit limbs, on the GPU.
+///
+/// The GPU versions of the predicates represent unsigned integers of up to 192 bits this way. Each
+/// limb is stored in a 64-bit integer, so that limbs can be multiplied without overflow. The least
+/// significant limb comes first.
+#[cube]
+fn limbs_set(limbs: &mut Array<u64>, value: u64) {
+	limbs[0] = value & 0xFFFF_FFFFu64;
+	limbs[1] = value >> 32;
+	for i in 2..6 {
+		limbs[i] = 0;
+	}
+}
+
+/// Carry the overflow of every limb into the next limb, on the GPU.
+#[cube]
+fn limbs_carry(limbs: &mut Array<u64>) {
+	for i in 0..5 {
+		limbs[i + 1] += limbs[i] >> 32;
+		limbs[i] &= 0xFFFF_FFFFu64;
+	}
+}
+
+/// Add an integer in limbs to another, on the GPU.
+#[cube]
+fn limbs_add(sum: &mut Array<u64>, addend: &Array<u64>) {
+	for i in 0..6 {
+		sum[i] += addend[i];
+	}
+	limbs_carry(sum);
+}
+
+/// Subtract an integer in limbs from a greater or equal one, on the GPU.
+#[cube]
+fn limbs_subtract(difference: &mut Array<u64>, subtrahend: &Array<u64>) {
+	let mut borrow: u64 = 0;
+	for i in 0..6 {
+		let take = subtrahend[i] + borrow;
+		if difference[i] >= take {
+			difference[i] -= take;
+			borrow = 0;
+		} else {
+			difference[i] = difference[i] + 0x1_0000_0000u64 - take;
+			borrow = 1;
+		}
+	}
+}
+
+/// Multiply two integers in limbs, on the GPU.
+///
+/// The product must fit in the limbs.
+#[cube]
+fn limbs_multiply(product: &mut Array<u64>, a: &Array<u64>, b: &Array<u64>) {
+	limbs_set(product, u64::new(0));
+	for i in 0..6 {
+		for j in 0..6 {
+			if i + j < 6 {
+				let partial = a[i] * b[j];
+				product[i + j] += partial & 0xFFFF_FFFFu64;
+				if i + j < 5 {
+					product[i + j + 1] += partial >> 32;
+				}
+			}
+		}
+	}
+	limbs_carry(product);
+}
+
+/// Compare two integers in limbs, on the GPU.
+///
+/// # Result
+/// 1 if the first is greater, -1 if the second is greater, or 0 if they are equal.
+#[cube]
+fn limbs_compare(a: &Array<u64>, b: &Array<u64>) -> i32 {
+	let mut result: i32 = 0;
+	for i in 0..6 {
+		let limb = 5 - i;
+		if result == 0 {
+			if a[limb] > b[limb] {
+				result = 1;
+			} else if a[limb] < b[limb] {
+				result = -1;
+			}
+		}
+	}
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+
+	/// Test the orientation of points on either side of a line, and on it.
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 5, y: 5 }, 50 ; "left")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 5, y: -5 }, -50 ; "right")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: -5, y: 0 }, 0 ; "on the line")]
+	#[test_case(Point2D { x: 3, y: 3 }, Point2D { x: 3, y: 3 }, Point2D { x: 5, y: 5 }, 0 ; "degenerate line")]
+	fn orientation_sides(start: Point2D, end: Point2D, point: Point2D, expected: i128) {
+		assert_eq!(orientation(&start, &end, &point), expected);
+	}
+
+	/// Test that the orientation is exact at the extremes of the coordinate range.
+	#[test]
+	fn orientation_extremes() {
+		let max = crate::MAX_EXACT_COORDINATE;
+		let min = -max - 1;
+		let start = Point2D { x: min, y: min };
+		let end = Point2D { x: max, y: max };
+		assert_eq!(orientation(&start, &end, &Point2D { x: max - 1, y: max }), (max as i128 - min as i128), "Just left of the diagonal.");
+		assert_eq!(orientation(&start, &end, &Point2D { x: max, y: max - 1 }), -(max as i128 - min as i128), "Just right of the diagonal.");
+	}
+
+	/// Test the in-circle test with the circle in both orientations.
+	#[test_case(Point2D { x: 0, y: 0 }, Containment::INSIDE ; "centre")]
+	#[test_case(Point2D { x: 9, y: 4 }, Containment::INSIDE ; "near the boundary")]
+	#[test_case(Point2D { x: 6, y: 8 }, Containment::BOUNDARY ; "boundary")]
+	#[test_case(Point2D { x: 10, y: 0 }, Containment::BOUNDARY ; "on a vertex")]
+	#[test_case(Point2D { x: 8, y: 7 }, Containment::OUTSIDE ; "just outside")]
+	#[test_case(Point2D { x: -100, y: 50 }, Containment::OUTSIDE ; "far outside")]
+	fn in_circle_points(point: Point2D, expected: Containment) {
+		let a = Point2D { x: 10, y: 0 };
+		let b = Point2D { x: 0, y: 10 };
+		let c = Point2D { x: -10, y: 0 };
+		assert_eq!(in_circle(&a, &b, &c, &point), expected, "Counter-clockwise circle.");
+		assert_eq!(in_circle(&c, &b, &a, &point), expected, "Clockwise circle.");
+	}
+
+	/// Test the in-circle test with three collinear points, where the circle degenerates into a line.
+	#[test_case(Point2D { x: 5, y: 5 }, Containment::BOUNDARY ; "on the line")]
+	#[test_case(Point2D { x: -50, y: -50 }, Containment::BOUNDARY ; "on the line beyond the points")]
+	#[test_case(Point2D { x: 5, y: 6 }, Containment::OUTSIDE ; "beside the line")]
+	fn in_circle_collinear(point: Point2D, expected: Containment) {
+		let a = Point2D { x: 0, y: 0 };
+		let b = Point2D { x: 10, y: 10 };
+		let c = Point2D { x: 20, y: 20 };
+		assert_eq!(in_circle(&a, &b, &c, &point), expected);
+	}
+
+	/// Test the in-circle test at the extremes of the coordinate range, where the determinant needs
+	/// more than 128 bits.
+	#[test]
+	fn in_circle_extremes() {
+		let max = crate::MAX_EXACT_COORDINATE;
+		let min = -max - 1;
+		let a = Point2D { x: min, y: min };
+		let b = Point2D { x: max, y: min };
+		let c = Point2D { x: max, y: max };
+		assert_eq!(in_circle(&a, &b, &c, &Point2D { x: min, y: max }), Containment::BOUNDARY, "The fourth corner of the square is on the circle.");
+		assert_eq!(in_circle(&a, &b, &c, &Point2D { x: min + 1, y: max }), Containment::INSIDE, "Just inside the fourth corner.");
+		assert_eq!(in_circle(&a, &b, &c, &Point2D { x: 0, y: 0 }), Containment::INSIDE, "The centre.");
+	}
+
+	/// Test the classification of pairs of segments.
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 10 }, Point2D { x: 0, y: 10 }, Point2D { x: 10, y: 0 }, SegmentIntersection::CROSSING ; "crossing")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 10 }, Point2D { x: 0, y: 10 }, Point2D { x: 4, y: 6 }, SegmentIntersection::DISJOINT ; "would cross if extended")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 10 }, Point2D { x: 0, y: 10 }, Point2D { x: 5, y: 5 }, SegmentIntersection::TOUCHING ; "endpoint on segment")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 10 }, Point2D { x: 10, y: 10 }, Point2D { x: 20, y: 0 }, SegmentIntersection::TOUCHING ; "shared endpoint")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 10 }, Point2D { x: 10, y: 10 }, Point2D { x: 20, y: 20 }, SegmentIntersection::TOUCHING ; "collinear shared endpoint")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 10 }, Point2D { x: 15, y: 15 }, Point2D { x: 5, y: 5 }, SegmentIntersection::OVERLAPPING ; "collinear overlapping")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 10 }, Point2D { x: 2, y: 2 }, Point2D { x: 4, y: 4 }, SegmentIntersection::OVERLAPPING ; "collinear contained")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 10 }, Point2D { x: 11, y: 11 }, Point2D { x: 20, y: 20 }, SegmentIntersection::DISJOINT ; "collinear apart")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 0, y: 1 }, Point2D { x: 10, y: 1 }, SegmentIntersection::DISJOINT ; "parallel")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 10 }, Point2D { x: 5, y: 5 }, Point2D { x: 5, y: 5 }, SegmentIntersection::TOUCHING ; "point on segment")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 10 }, Point2D { x: 15, y: 15 }, Point2D { x: 15, y: 15 }, SegmentIntersection::DISJOINT ; "point beyond segment")]
+	#[test_case(Point2D { x: 3, y: 3 }, Point2D { x: 3, y: 3 }, Point2D { x: 3, y: 3 }, Point2D { x: 3, y: 3 }, SegmentIntersection::TOUCHING ; "coincident points")]
+	fn segment_classes(a_start: Point2D, a_end: Point2D, b_start: Point2D, b_end: Point2D, expected: SegmentIntersection) {
+		assert_eq!(segment_intersection(&a_start, &a_end, &b_start, &b_end), expected);
+		assert_eq!(segment_intersection(&b_end, &b_start, &a_end, &a_start), expected, "The order of the segments and their endpoints must not matter.");
+	}
+
+	/// Test the exact intersection point of two segments, and how it is rounded.
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 4, y: 4 }, Point2D { x: 0, y: 4 }, Point2D { x: 4, y: 0 }, Some(Point2D { x: 2, y: 2 }) ; "on the grid")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 3, y: 1 }, Point2D { x: 3, y: 0 }, Point2D { x: 0, y: 1 }, Some(Point2D { x: 2, y: 1 }) ; "halves rounded up")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: -3, y: -1 }, Point2D { x: -3, y: 0 }, Point2D { x: 0, y: -1 }, Some(Point2D { x: -1, y: 0 }) ; "negative halves rounded up")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 3, y: 0 }, Point2D { x: 1, y: -1 }, Point2D { x: 2, y: 2 }, Some(Point2D { x: 1, y: 0 }) ; "thirds rounded to nearest")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 5, y: 0 }, Point2D { x: 5, y: 5 }, Some(Point2D { x: 5, y: 0 }) ; "touching")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 5, y: 1 }, Point2D { x: 5, y: 5 }, None ; "disjoint")]
+	#[test_case(Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 5, y: 0 }, Point2D { x: 15, y: 0 }, None ; "overlapping")]
+	fn intersection_points(a_start: Point2D, a_end: Point2D, b_start: Point2D, b_end: Point2D, expected: Option<Point2D>) {
+		assert_eq!(intersection(&a_start, &a_end, &b_start, &b_end).map(|point| point.rounded()), expected);
+		assert_eq!(intersection(&b_start, &b_end, &a_start, &a_end).map(|point| point.rounded()), expected, "The order of the segments must not matter.");
+	}
+
+	/// Test that rounding is translation invariant, so that translated segments meet at the translated
+	/// point.
+	#[test]
+	fn intersection_translated() {
+		let a_start = Point2D { x: 0, y: 0 };
+		let a_end = Point2D { x: 7, y: 3 };
+		let b_start = Point2D { x: 0, y: 3 };
+		let b_end = Point2D { x: 5, y: -2 };
+		let original = intersection(&a_start, &a_end, &b_start, &b_end).unwrap().rounded();
+		for offset in [-1000, -7, 13, 1000] {
+			let moved = |point: &Point2D| Point2D { x: point.x + offset, y: point.y - offset };
+			let translated = intersection(&moved(&a_start), &moved(&a_end), &moved(&b_start), &moved(&b_end)).unwrap().rounded();
+			assert_eq!(translated, moved(&original));
+		}
+	}
+
+	/// Test that rational points are compared exactly, regardless of their denominators.
+	#[test]
+	fn rational_order() {
+		let half = RationalPoint { x: 1, y: 1, denominator: 2 };
+		assert_eq!(half, RationalPoint { x: 3, y: 3, denominator: 6 }, "Equal fractions with different denominators.");
+		assert!(half < RationalPoint { x: 2, y: 3, denominator: 4 }, "Y breaks the tie.");
+		assert!(half > RationalPoint::from_point(&Point2D { x: 0, y: 5 }), "X is compared first.");
+	}
+
+	/// GPU kernel to evaluate every predicate on a batch of pairs of segments.
+	///
+	/// The first three endpoints form the circle for the in-circle test, and the fourth is tested
+	/// against it. Each pair gives six results: the orientation of the third point relative to the
+	/// first segment, the in-circle test, the segment classification, whether the segments meet, and
+	/// the rounded intersection point.
+	#[cfg(all(feature = "gpu", not(feature = "coordinate64")))]
+	#[cube(launch)]
+	fn predicates_kernel(coordinates: &Array<Coordinate>, results: &mut Array<i64>) {
+		let num_pairs = coordinates.len() / 8;
+		if ABSOLUTE_POS < num_pairs {
+			let input = ABSOLUTE_POS * 8;
+			let a_start_x = i64::cast_from(coordinates[input]);
+			let a_start_y = i64::cast_from(coordinates[input + 1]);
+			let a_end_x = i64::cast_from(coordinates[input + 2]);
+			let a_end_y = i64::cast_from(coordinates[input + 3]);
+			let b_start_x = i64::cast_from(coordinates[input + 4]);
+			let b_start_y = i64::cast_from(coordinates[input + 5]);
+			let b_end_x = i64::cast_from(coordinates[input + 6]);
+			let b_end_y = i64::cast_from(coordinates[input + 7]);
+			let output = ABSOLUTE_POS * 6;
+			results[output] = i64::cast_from(orientation_gpu(a_start_x, a_start_y, a_end_x, a_end_y, b_start_x, b_start_y));
+			results[output + 1] = i64::cast_from(in_circle_gpu(a_start_x, a_start_y, a_end_x, a_end_y, b_start_x, b_start_y, b_end_x, b_end_y));
+			results[output + 2] = i64::cast_from(segment_intersection_gpu(a_start_x, a_start_y, a_end_x, a_end_y, b_start_x, b_start_y, b_end_x, b_end_y));
+			let mut point = Array::<i64>::new(2);
+			point[0] = 0;
+			point[1] = 0;
+			let mut meets: i64 = 0;
+			if intersection_gpu(a_start_x, a_start_y, a_end_x, a_end_y, b_start_x, b_start_y, b_end_x, b_end_y, &mut point) {
+				meets = 1;
+			}
+			results[output + 3] = meets;
+			results[output + 4] = point[0];
+			results[output + 5] = point[1];
+		}
+	}
+
+	/// Test that the GPU versions of the predicates give exactly the same results as the host
+	/// versions, on many degenerate cases and on coordinates that span the whole range.
+	#[cfg(all(feature = "gpu", not(feature = "coordinate64")))]
+	#[test]
+	fn gpu_identical() {
+		use crate::detail::gpu;
+
+		let mut state: u64 = 42;
+		let mut random = move |size: u64| {
+			state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+			((state >> 16) % size) as i64 - (size / 2) as i64
+		};
+		let mut points = Vec::new();
+		for size in [4, 16, 1 << 20, 1 << 32] {
+			for _ in 0..2000 {
+				points.push(Point2D { x: random(size) as Coordinate, y: random(size) as Coordinate });
+			}
+		}
+		let (min, max) = (Coordinate::MIN, Coordinate::MAX);
+		points.extend([Point2D { x: min, y: min }, Point2D { x: max, y: max }, Point2D { x: min, y: max }, Point2D { x: max, y: min }]);
+		points.extend([Point2D { x: min, y: min }, Point2D { x: max, y: min }, Point2D { x: max, y: max }, Point2D { x: min + 1, y: max }]);
+		points.extend([Point2D { x: min, y: 0 }, Point2D { x: max, y: 1 }, Point2D { x: max, y: min }, Point2D { x: min, y: max }]);
+
+		let num_pairs = points.len() / 4;
+		let client = gpu::client();
+		let coordinates_handle = gpu::upload(&points);
+		let results_handle = client.empty(num_pairs * 6 * size_of::<i64>());
+		let (cube_count, cube_dim) = gpu::launch_dimensions(num_pairs);
+		unsafe {
+			predicates_kernel::launch::<gpu::GpuRuntime>(
+				&client,
+				cube_count,
+				cube_dim,
+				ArrayArg::from_raw_parts::<Coordinate>(&coordinates_handle, num_pairs * 8, 1),
+				ArrayArg::from_raw_parts::<i64>(&results_handle, num_pairs * 6, 1),
+			);
+		}
+		let results = i64::from_bytes(&client.read_one(results_handle)).to_vec();
+		for (pair, result) in points.chunks(4).zip(results.chunks(6)) {
+			let [a_start, a_end, b_start, b_end] = [&pair[0], &pair[1], &pair[2], &pair[3]];
+			assert_eq!(result[0], orientation(a_start, a_end, b_start).signum() as i64, "Orientation of {:?}.", pair);
+			let containment = match result[1] {
+				1 => Containment::INSIDE,
+				0 => Containment::BOUNDARY,
+				_ => Containment::OUTSIDE,
+			};
+			assert_eq!(containment, in_circle(a_start, a_end, b_start, b_end), "In-circle test of {:?}.", pair);
+			assert_eq!(result[2], segment_intersection(a_start, a_end, b_start, b_end) as i64, "Segment classification of {:?}.", pair);
+			let point = intersection(a_start, a_end, b_start, b_end).map(|point| point.rounded());
+			let gpu_point = if result[3] != 0 { Some(Point2D { x: result[4] as Coordinate, y: result[5] as Coordinate }) } else { None };
+			assert_eq!(gpu_point, point, "Intersection of {:?}.", pair);
+		}
+	}
+
+	/// Test that exact products are compared correctly, also beyond 128 bits.
+	#[test_case(3, 4, 2, 6, Ordering::Equal ; "equal")]
+	#[test_case(-3, 4, 2, 6, Ordering::Less ; "negative")]
+	#[test_case(-3, -4, 2, 5, Ordering::Greater ; "two negatives")]
+	#[test_case(0, 4, -2, 6, Ordering::Greater ; "zero")]
+	#[test_case(1 << 100, 1 << 100, (1 << 100) + 1, (1 << 100) - 1, Ordering::Greater ; "large")]
+	#[test_case(-(1 << 100), 1 << 100, -((1 << 100) + 1), (1 << 100) - 1, Ordering::Less ; "large negative")]
+	fn products(a: i128, b: i128, c: i128, d: i128, expected: Ordering) {
+		assert_eq!(compare_products(a, b, c, d), expected);
+	}
+
+	/// Test that the sign of a sum of products is found exactly, also beyond 128 bits.
+	#[test_case(&[], Ordering::Equal ; "empty")]
+	#[test_case(&[(3, 4), (-2, 6)], Ordering::Equal ; "cancelling")]
+	#[test_case(&[(3, 4), (-2, 5), (0, 7)], Ordering::Greater ; "positive")]
+	#[test_case(&[(-3, -4), (2, -7)], Ordering::Less ; "negative")]
+	#[test_case(&[(1 << 100, 1 << 100), (1 << 100, 1 << 100), (-((1 << 100) + 1), (1 << 101) - 1)], Ordering::Less ; "large")]
+	fn products_sum(products: &[(i128, i128)], expected: Ordering) {
+		assert_eq!(sum_of_products_sign(products), expected);
+	}
+}
\ No newline at end of file
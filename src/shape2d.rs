@@ -7,7 +7,13 @@
  */
 
 use crate::Area; //To return the area of the shape.
+use crate::BoundingBox; //To return the bounding box of the shape.
+use crate::Containment; //To return whether points are inside the shape.
 use crate::Convexity; //To return the convexity of the shape.
+use crate::Crossing; //To return where the boundary of the shape intersects itself.
+use crate::FillRule; //To decide which regions of the shape are inside.
+use crate::Point2D; //To test whether points are inside the shape.
+use crate::Polygon; //To return the convex hull of the shape.
 use crate::TwoDimensional; //All Shape2Ds are two-dimensional.
 
 /// A trait for finitely-bounded shapes in a 2D space.
@@ -21,6 +27,37 @@ pub trait Shape2D : TwoDimensional {
 	/// The surface area of this shape.
 	fn area(&self) -> Area;
 
+	/// Get the bounding box of this shape.
+	///
+	/// This is the smallest axis-aligned rectangle that contains the whole shape. Shapes without any
+	/// vertices have an empty bounding box.
+	///
+	/// # Result
+	/// The bounding box of the shape.
+	fn bounding_box(&self) -> BoundingBox;
+
+	/// Find whether a point is inside this shape.
+	///
+	/// Points exactly on the boundary of the shape are neither inside nor outside. This is decided
+	/// exactly, without rounding errors.
+	///
+	/// # Arguments
+	/// * `point` - The point to test.
+	/// * `fill_rule` - Which regions of the shape are inside, if parts of it overlap.
+	///
+	/// # Result
+	/// Whether the point is inside, outside or on the boundary of the shape.
+	fn containment(&self, point: &Point2D, fill_rule: FillRule) -> Containment;
+
+	/// Get the convex hull of this shape.
+	///
+	/// This is the smallest convex polygon that contains the whole shape. It is counter-clockwise,
+	/// and only has vertices at its corners.
+	///
+	/// # Result
+	/// The convex hull of the shape.
+	fn convex_hull(&self) -> Polygon;
+
 	/// Get the convexity of this shape.
 	///
 	/// A shape is convex if and only if all line segments starting and ending inside of the shape
@@ -33,4 +70,23 @@ pub trait Shape2D : TwoDimensional {
 	/// # Result
 	/// The convexity of the shape.
 	fn convexity(&self) -> Convexity;
+
+	/// Find all places where the boundary of this shape meets itself.
+	///
+	/// Consecutive edges always meet at the vertex between them, which is not reported as a
+	/// crossing.
+	///
+	/// # Result
+	/// The crossings, sorted by their edges.
+	fn crossings(&self) -> Vec<Crossing>;
+
+	/// Divide this shape into triangles.
+	///
+	/// The triangles are given as indices into the vertices of the shape, three for each triangle.
+	/// Together they cover the shape exactly, with the same orientation as the shape. The shape must
+	/// not intersect itself.
+	///
+	/// # Result
+	/// The indices of the vertices of the triangles.
+	fn triangulate(&self) -> Vec<u32>;
 }
\ No newline at end of file
@@ -0,0 +1,421 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+//! Defines a collection of shapes that can quickly find which of its shapes are near a certain
+//! place.
+
+use crate::AffineTransform; //To transform all shapes in the index.
+use crate::BoundingBox; //The index searches through the bounding boxes of the shapes.
+use crate::BoundingVolumeHierarchy; //The tree that the index searches through.
+use crate::Coordinate; //To translate the shapes in the index.
+use crate::OverflowError; //If transforming the shapes would move them out of range.
+use crate::OverflowPolicy; //To choose what happens if translating the shapes moves them out of range.
+use crate::Point2D; //To search for the shapes nearest to a point.
+use crate::Polygon; //Lists of polygons can be indexed.
+use crate::PolygonBatch; //Batches of polygons can be indexed.
+use crate::Shape2D; //To find the bounding boxes of polygons.
+use crate::TwoDimensional; //The shapes in the index can be moved like any two-dimensional object.
+use crate::operations::bounding_box; //To find the bounding boxes of polygons in a batch.
+
+/// A collection of shapes that a spatial index can be built over.
+///
+/// The shapes are identified by their index in the collection. The collection must not add, remove
+/// or reorder its shapes while it is indexed. Moving them is only allowed through these functions,
+/// so that the index can update the bounding boxes of the shapes that moved.
+pub trait Indexable {
+	/// Get the number of shapes in this collection.
+	fn num_items(&self) -> usize;
+
+	/// Get the bounding box of every shape in this collection.
+	///
+	/// # Result
+	/// The bounding box of each shape, in the order of the collection.
+	fn bounding_boxes(&self) -> Vec<BoundingBox>;
+
+	/// Get the bounding box of one shape in this collection.
+	///
+	/// # Arguments
+	/// * `item` - The index of the shape.
+	fn item_bounding_box(&self, item: usize) -> BoundingBox;
+
+	/// Move one shape in this collection.
+	///
+	/// If the shape would move out of the range of coordinates with the checked policy, it is left
+	/// where it was.
+	///
+	/// # Arguments
+	/// * `item` - The index of the shape to move.
+	/// * `dx` - How far to move the shape in the X direction.
+	/// * `dy` - How far to move the shape in the Y direction.
+	/// * `policy` - What to do if the shape would move out of the range of coordinates.
+	fn translate_item(&mut self, item: usize, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError>;
+
+	/// Move all shapes in this collection.
+	///
+	/// If any shape would move out of the range of coordinates with the checked policy, all shapes
+	/// are left where they were.
+	///
+	/// # Arguments
+	/// * `dx` - How far to move the shapes in the X direction.
+	/// * `dy` - How far to move the shapes in the Y direction.
+	/// * `policy` - What to do if a shape would move out of the range of coordinates.
+	fn translate_items(&mut self, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError>;
+
+	/// Transform all shapes in this collection with an affine transformation.
+	///
+	/// If any shape would move out of the range of coordinates, all shapes are left as they were.
+	///
+	/// # Arguments
+	/// * `transform` - The transformation to apply.
+	fn transform_items(&mut self, transform: &AffineTransform) -> Result<(), OverflowError>;
+}
+
+impl Indexable for Vec<Polygon> {
+	fn num_items(&self) -> usize {
+		self.len()
+	}
+
+	fn bounding_boxes(&self) -> Vec<BoundingBox> {
+		self.iter().map(Shape2D::bounding_box).collect()
+	}
+
+	fn item_bounding_box(&self, item: usize) -> BoundingBox {
+		self[item].bounding_box()
+	}
+
+	fn translate_item(&mut self, item: usize, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+		self[item].translate_with_policy(dx, dy, policy)
+	}
+
+	fn translate_items(&mut self, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+		if policy == OverflowPolicy::CHECKED { //Check all polygons before moving any of them.
+			check_translation(&self.bounding_boxes().iter().fold(BoundingBox::empty(), |a, b| a.union(b)), dx, dy)?;
+		}
+		for polygon in self.iter_mut() {
+			polygon.translate_with_policy(dx, dy, policy)?;
+		}
+		Ok(())
+	}
+
+	fn transform_items(&mut self, transform: &AffineTransform) -> Result<(), OverflowError> {
+		let mut transformed = Vec::with_capacity(self.len());
+		for polygon in self.iter() { //Transform copies, so that the originals stay intact if any of them fails.
+			let mut copy = Polygon::from_iter(polygon.iter().copied());
+			copy.transform(transform)?;
+			transformed.push(copy);
+		}
+		*self = transformed;
+		Ok(())
+	}
+}
+
+impl Indexable for PolygonBatch {
+	fn num_items(&self) -> usize {
+		self.num_polygons()
+	}
+
+	fn bounding_boxes(&self) -> Vec<BoundingBox> {
+		PolygonBatch::bounding_boxes(self)
+	}
+
+	fn item_bounding_box(&self, item: usize) -> BoundingBox {
+		bounding_box::bounding_box_vertices_st(self.polygon(item))
+	}
+
+	fn translate_item(&mut self, item: usize, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+		if policy == OverflowPolicy::CHECKED { //Check all vertices before moving any of them.
+			check_translation(&self.item_bounding_box(item), dx, dy)?;
+		}
+		for vertex in self.polygon_mut(item) {
+			vertex.translate_with_policy(dx, dy, policy)?;
+		}
+		Ok(())
+	}
+
+	fn translate_items(&mut self, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+		self.translate_with_policy(dx, dy, policy)
+	}
+
+	fn transform_items(&mut self, transform: &AffineTransform) -> Result<(), OverflowError> {
+		self.transform(transform)
+	}
+}
+
+/// Check whether everything inside a bounding box can be moved without going out of the range of
+/// coordinates.
+///
+/// # Arguments
+/// * `bounds` - The box around everything that is to be moved.
+/// * `dx` - How far to move in the X direction.
+/// * `dy` - How far to move in the Y direction.
+fn check_translation(bounds: &BoundingBox, dx: Coordinate, dy: Coordinate) -> Result<(), OverflowError> {
+	if bounds.is_empty() {
+		return Ok(()); //Nothing to move.
+	}
+	for corner in [bounds.min, bounds.max] {
+		OverflowPolicy::CHECKED.add(corner.x, dx)?;
+		OverflowPolicy::CHECKED.add(corner.y, dy)?;
+	}
+	Ok(())
+}
+
+/// A collection of shapes with a spatial index over them, to quickly find which shapes are near a
+/// certain place.
+///
+/// The index is a bounding volume hierarchy over the bounding boxes of the shapes, which is built
+/// in bulk when the index is created. The queries find shapes by their bounding boxes. They are
+/// meant to quickly narrow down which shapes need to be tested precisely.
+///
+/// The shapes can be moved through the index. This updates the bounding boxes in the hierarchy
+/// without rebuilding it, so the index stays valid.
+///
+/// # Examples
+/// ```
+/// use apex::{BoundingBox, Point2D, Polygon, SpatialIndex, TwoDimensional};
+/// let square = |x, y| Polygon::from_iter([Point2D { x, y }, Point2D { x: x + 10, y }, Point2D { x: x + 10, y: y + 10 }, Point2D { x, y: y + 10 }]);
+/// let mut index = SpatialIndex::new(vec![square(0, 0), square(5, 5), square(100, 0)]);
+/// assert_eq!(index.neighbours(0), vec![1]);
+/// assert_eq!(index.nearest(&Point2D { x: 80, y: 0 }, 1), vec![2]);
+///
+/// index.translate(1000, 0);
+/// assert_eq!(index.intersecting(&BoundingBox { min: Point2D { x: 1000, y: 0 }, max: Point2D { x: 1000, y: 0 } }), vec![0]);
+/// ```
+#[derive(Debug)]
+pub struct SpatialIndex<C: Indexable> {
+	/// The shapes in the index.
+	items: C,
+
+	/// The tree of bounding boxes of the shapes.
+	hierarchy: BoundingVolumeHierarchy,
+}
+
+impl<C: Indexable> SpatialIndex<C> {
+	/// Build a spatial index over a collection of shapes.
+	///
+	/// The bounding boxes of the shapes are found and the hierarchy is built in bulk, on a single
+	/// thread, on multiple threads or on the GPU, whichever is estimated to be fastest.
+	///
+	/// # Arguments
+	/// * `items` - The shapes to index. The index takes ownership of them, so that they can't be
+	/// moved without updating the index.
+	pub fn new(items: C) -> Self {
+		let hierarchy = BoundingVolumeHierarchy::new(&items.bounding_boxes());
+		SpatialIndex { items, hierarchy }
+	}
+
+	/// Get the shapes in this index.
+	pub fn items(&self) -> &C {
+		&self.items
+	}
+
+	/// Take the shapes out of this index, discarding the index.
+	pub fn into_items(self) -> C {
+		self.items
+	}
+
+	/// Get the bounding volume hierarchy over the shapes.
+	pub fn hierarchy(&self) -> &BoundingVolumeHierarchy {
+		&self.hierarchy
+	}
+
+	/// Find all shapes whose bounding boxes intersect a certain region.
+	///
+	/// # Arguments
+	/// * `region` - The region to search in.
+	///
+	/// # Result
+	/// The indices of the shapes that intersect the region, in ascending order.
+	pub fn intersecting(&self, region: &BoundingBox) -> Vec<usize> {
+		self.hierarchy.intersecting(region)
+	}
+
+	/// Find all shapes whose bounding boxes lie completely inside of a certain region.
+	///
+	/// # Arguments
+	/// * `region` - The region to search in.
+	///
+	/// # Result
+	/// The indices of the shapes that are inside the region, in ascending order.
+	pub fn within(&self, region: &BoundingBox) -> Vec<usize> {
+		self.hierarchy.within(region)
+	}
+
+	/// Find the shapes whose bounding boxes are nearest to a point.
+	///
+	/// # Arguments
+	/// * `point` - The point to search around.
+	/// * `count` - How many shapes to find.
+	///
+	/// # Result
+	/// The indices of the nearest shapes, from nearest to farthest. Shapes at the same distance are
+	/// ordered by their index.
+	pub fn nearest(&self, point: &Point2D, count: usize) -> Vec<usize> {
+		self.hierarchy.nearest(point, count)
+	}
+
+	/// Find the shapes whose bounding boxes intersect the bounding box of a certain shape in the
+	/// index.
+	///
+	/// # Arguments
+	/// * `item` - The index of the shape to find the neighbours of.
+	///
+	/// # Result
+	/// The indices of the neighbouring shapes, in ascending order. The shape itself is not included.
+	pub fn neighbours(&self, item: usize) -> Vec<usize> {
+		let mut result = self.hierarchy.intersecting(&self.hierarchy.item_box(item));
+		result.retain(|neighbour| *neighbour != item);
+		result
+	}
+
+	/// Move one shape in the index.
+	///
+	/// The bounding boxes along the path from the shape to the root of the hierarchy are updated.
+	///
+	/// # Arguments
+	/// * `item` - The index of the shape to move.
+	/// * `dx` - How far to move the shape in the X direction.
+	/// * `dy` - How far to move the shape in the Y direction.
+	/// * `policy` - What to do if the shape would move out of the range of coordinates.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{BoundingBox, OverflowPolicy, Point2D, PolygonBatch, SpatialIndex};
+	/// let mut batch = PolygonBatch::new();
+	/// batch.push_polygon([Point2D { x: 0, y: 0 }, Point2D { x: 10, y: 0 }, Point2D { x: 0, y: 10 }]);
+	/// batch.push_polygon([Point2D { x: 50, y: 0 }, Point2D { x: 60, y: 0 }, Point2D { x: 50, y: 10 }]);
+	/// let mut index = SpatialIndex::new(batch);
+	/// index.translate_item(1, -45, 0, OverflowPolicy::CHECKED).unwrap();
+	/// assert_eq!(index.neighbours(0), vec![1]);
+	/// ```
+	pub fn translate_item(&mut self, item: usize, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+		self.items.translate_item(item, dx, dy, policy)?;
+		self.hierarchy.update(item, self.items.item_bounding_box(item));
+		Ok(())
+	}
+}
+
+impl<C: Indexable> TwoDimensional for SpatialIndex<C> {
+	/// Move all shapes in the index.
+	///
+	/// The bounding boxes in the hierarchy are all updated, without rebuilding it.
+	fn translate_with_policy(&mut self, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError> {
+		self.items.translate_items(dx, dy, policy)?;
+		self.hierarchy.refit(&self.items.bounding_boxes());
+		Ok(())
+	}
+
+	/// Transform all shapes in the index.
+	///
+	/// The bounding boxes in the hierarchy are all updated, without rebuilding it. Transformations
+	/// that rotate or mirror the shapes keep the index valid, but may make the queries slower.
+	fn transform(&mut self, transform: &AffineTransform) -> Result<(), OverflowError> {
+		self.items.transform_items(transform)?;
+		self.hierarchy.refit(&self.items.bounding_boxes());
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Create a square of 10 by 10 with its lowest corner at a certain position.
+	fn square(x: Coordinate, y: Coordinate) -> Polygon {
+		Polygon::from_iter([Point2D { x, y }, Point2D { x: x + 10, y }, Point2D { x: x + 10, y: y + 10 }, Point2D { x, y: y + 10 }])
+	}
+
+	/// Create a row of squares that each overlap with their neighbours.
+	fn row() -> Vec<Polygon> {
+		(0..50).map(|i| square(i * 8, 0)).collect()
+	}
+
+	/// Test finding shapes in an index over a list of polygons.
+	#[test]
+	fn queries_polygons() {
+		let index = SpatialIndex::new(row());
+		assert_eq!(index.neighbours(0), vec![1]);
+		assert_eq!(index.neighbours(10), vec![9, 11]);
+		assert_eq!(index.intersecting(&BoundingBox { min: Point2D { x: 17, y: 5 }, max: Point2D { x: 23, y: 5 } }), vec![1, 2]);
+		assert_eq!(index.within(&BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 30, y: 10 } }), vec![0, 1, 2]);
+		assert_eq!(index.nearest(&Point2D { x: 1000, y: 5 }, 2), vec![49, 48]);
+	}
+
+	/// Test that the index over a batch finds the same shapes as over a list.
+	#[test]
+	fn queries_batch() {
+		let list = SpatialIndex::new(row());
+		let batch = SpatialIndex::new(PolygonBatch::from_iter(row()));
+		assert_eq!(batch.items().num_items(), 50);
+		assert_eq!(batch.hierarchy(), list.hierarchy());
+		for item in [0, 25, 49] {
+			assert_eq!(batch.neighbours(item), list.neighbours(item));
+		}
+	}
+
+	/// Test that moving all shapes of a batch keeps the index valid.
+	#[test]
+	fn translate_all_batch() {
+		let mut index = SpatialIndex::new(PolygonBatch::from_iter(row()));
+		index.translate(0, 1000);
+		assert_eq!(index.intersecting(&BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 1000, y: 10 } }), Vec::<usize>::new());
+		assert_eq!(index.intersecting(&BoundingBox { min: Point2D { x: 17, y: 1005 }, max: Point2D { x: 23, y: 1005 } }), vec![1, 2]);
+		assert_eq!(index.hierarchy().bounds(), BoundingBox { min: Point2D { x: 0, y: 1000 }, max: Point2D { x: 402, y: 1010 } });
+	}
+
+	/// Test that moving all shapes of a list keeps the index valid.
+	#[test]
+	fn translate_all_polygons() {
+		let mut index = SpatialIndex::new(row());
+		index.translate(0, 1000);
+		assert_eq!(index.intersecting(&BoundingBox { min: Point2D { x: 17, y: 1005 }, max: Point2D { x: 23, y: 1005 } }), vec![1, 2]);
+		assert_eq!(index.hierarchy().bounds(), BoundingBox { min: Point2D { x: 0, y: 1000 }, max: Point2D { x: 402, y: 1010 } });
+	}
+
+	/// Test that moving all shapes out of range with the checked policy leaves all of them in place.
+	#[test]
+	fn translate_all_checked_overflow() {
+		let mut polygons = row();
+		polygons.push(square(Coordinate::MAX - 20, 0));
+		let mut index = SpatialIndex::new(polygons);
+		assert_eq!(index.translate_with_policy(15, 0, OverflowPolicy::CHECKED), Err(OverflowError));
+		assert_eq!(index.items()[0], square(0, 0), "The first square fits, but must not be moved if the last one doesn't.");
+		assert_eq!(index.neighbours(1), vec![0, 2]);
+	}
+
+	/// Test that moving a single shape of a batch updates its neighbours.
+	#[test]
+	fn translate_item_batch() {
+		let mut index = SpatialIndex::new(PolygonBatch::from_iter(row()));
+		index.translate_item(0, 200, 100, OverflowPolicy::CHECKED).unwrap();
+		assert_eq!(index.neighbours(0), Vec::<usize>::new());
+		assert_eq!(index.neighbours(1), vec![2]);
+		index.translate_item(0, -3, -100, OverflowPolicy::CHECKED).unwrap();
+		assert_eq!(index.neighbours(0), vec![24, 25]);
+		assert_eq!(index.translate_item(0, Coordinate::MAX, 0, OverflowPolicy::CHECKED), Err(OverflowError));
+		assert_eq!(index.items().polygon(0)[0], Point2D { x: 197, y: 0 }, "The polygon was not moved when it would overflow.");
+	}
+
+	/// Test that moving a single shape of a list updates its neighbours.
+	#[test]
+	fn translate_item_polygons() {
+		let mut index = SpatialIndex::new(row());
+		index.translate_item(0, 200, 100, OverflowPolicy::CHECKED).unwrap();
+		assert_eq!(index.neighbours(0), Vec::<usize>::new());
+		assert_eq!(index.neighbours(1), vec![2]);
+		assert_eq!(index.into_items()[0], square(200, 100));
+	}
+
+	/// Test that transforming all shapes keeps the index valid.
+	#[test]
+	fn transform_all() {
+		let mut index = SpatialIndex::new(row());
+		index.transform(&AffineTransform::scaling(&Point2D { x: 0, y: 0 }, 2.0, 2.0)).unwrap();
+		assert_eq!(index.hierarchy().bounds(), BoundingBox { min: Point2D { x: 0, y: 0 }, max: Point2D { x: 804, y: 20 } });
+		assert_eq!(index.intersecting(&BoundingBox { min: Point2D { x: 780, y: 0 }, max: Point2D { x: 790, y: 0 } }), vec![48, 49]);
+	}
+}
\ No newline at end of file
@@ -10,4 +10,5 @@
 //!
 //! It supplies a bunch of fixtures which make it easy to test with existing geometric objects.
 
+pub mod multi_polygon;
 pub mod polygon;
\ No newline at end of file
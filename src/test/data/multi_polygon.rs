@@ -0,0 +1,35 @@
+/*
+ * Library for performing massively parallel computations on polygons.
+ * Copyright (C) 2026 Ghostkeeper
+ * This library is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version.
+ * This library is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for details.
+ * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
+ */
+
+/// A 1000x1000 square with a 500x500 square hole in the centre.
+///
+/// The outer contour is counter-clockwise and starts at the coordinate origin with the seam. The
+/// hole is clockwise and starts at 250,250.
+pub fn square_with_hole_1000() -> crate::MultiPolygon {
+	load_multi_polygon(include_str!("multi_polygon/square_with_hole_1000.svg"))
+}
+
+/// Two separate 1000x1000 squares, 1000 apart along the X axis.
+///
+/// The first square starts at the coordinate origin with the seam. The second starts at 2000,0.
+pub fn two_squares_1000() -> crate::MultiPolygon {
+	load_multi_polygon(include_str!("multi_polygon/two_squares_1000.svg"))
+}
+
+/// Parse an SVG file to load a multi-polygon from it.
+///
+/// Every `<polygon>` tag in the file becomes a contour of the multi-polygon, in the order in which
+/// they appear in the file. Like with the polygons, groups and transforms are ignored completely.
+///
+/// # Arguments
+/// * `svg` - An SVG document, the contents of an SVG file, as text mark-up.
+fn load_multi_polygon(svg: &str) -> crate::MultiPolygon {
+	svg.match_indices("<polygon ")
+		.map(|(tag_start, _)| super::polygon::load_polygon(&svg[tag_start..])) //Load each polygon from the position of its tag onwards.
+		.collect()
+}
\ No newline at end of file
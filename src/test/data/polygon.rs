@@ -21,6 +21,30 @@ pub fn triangle_1000() -> crate::Polygon {
 	load_polygon(include_str!("polygon/triangle_1000.svg"))
 }
 
+/// An L-shaped polygon of 1000x1000, with a 500x500 square cut out of one corner.
+///
+/// The polygon starts at the coordinate origin with the seam. The cut-out corner is at 1000,1000,
+/// which makes the polygon concave.
+pub fn l_shape_1000() -> crate::Polygon {
+	load_polygon(include_str!("polygon/l_shape_1000.svg"))
+}
+
+/// A five-pointed star of 1000x1000, drawn as a single self-intersecting pentagram.
+///
+/// The star starts at its top point, 500,1000, with the seam. Every vertex turns the same way, but
+/// the contour goes around the centre twice.
+pub fn pentagram_1000() -> crate::Polygon {
+	load_polygon(include_str!("polygon/pentagram_1000.svg"))
+}
+
+/// A self-intersecting bowtie of 1000x1000, crossing itself in the centre.
+///
+/// The bowtie starts at the coordinate origin with the seam. Its two lobes have opposite
+/// orientations, so one of them is negative.
+pub fn bowtie_1000() -> crate::Polygon {
+	load_polygon(include_str!("polygon/bowtie_1000.svg"))
+}
+
 /// Parse an SVG file to load a polygon from it.
 ///
 /// This will find the first `<polygon>` tag in the file, and take the `points` attribute from it to
@@ -40,7 +64,7 @@ pub fn triangle_1000() -> crate::Polygon {
 /// let poly = load_polygon(include_str!("polygon/square_1000.svg")); //Statically load this polygon.
 /// assert_eq!(poly.area(), 1000000);
 /// ```
-fn load_polygon(svg: &str) -> crate::Polygon {
+pub(super) fn load_polygon(svg: &str) -> crate::Polygon {
 	let tag_start = svg.find("<polygon ").expect("The <polygon> tag is missing.") + 9;
 	let points_start = tag_start + svg[tag_start..].find("points=\"").expect("The points attribute is missing.") + 8;
 	let points_end = points_start + svg[points_start..].find("\"").expect("The points attribute never closes.");
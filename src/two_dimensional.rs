@@ -6,17 +6,28 @@
  * You should have received a copy of the GNU Affero General Public License along with this library. If not, see <https://gnu.org/licenses/>.
  */
 
+use crate::AffineTransform; //To rotate, scale, mirror and otherwise transform objects.
 use crate::Coordinate; //To transform objects across the two-dimensional space.
+use crate::OverflowError; //Transformations can move objects out of the range of coordinates.
+use crate::OverflowPolicy; //To choose what happens if translating moves objects out of the range of coordinates.
+use crate::Point2D; //Pivot points and axes for the transformations.
 
 /// This trait is for geometrical objects that are in a two-dimensional space.
 ///
 /// The traits provides operations that are available for all two-dimensional geometries.
+///
+/// The transformations other than translation round the transformed coordinates as described in
+/// `AffineTransform`. If any coordinate would go out of the range of `Coordinate`, they return an
+/// `OverflowError` and leave the object unchanged.
 pub trait TwoDimensional {
 	/// Move the object across the two-dimensional space.
 	///
 	/// This causes the position of the object to change, but doesn't otherwise transform it. The
 	/// object is not rotated, scaled or deformed in any way.
 	///
+	/// If a coordinate would go out of the range of `Coordinate`, it wraps around, as with
+	/// `OverflowPolicy::WRAPPING`. Use `translate_with_policy` to detect or prevent that.
+	///
 	/// # Arguments
 	/// * `dx` - How far to move the object in the X direction. Use a positive number to increase
 	/// the X position, or a negative number to reduce the X position.
@@ -29,5 +40,129 @@ pub trait TwoDimensional {
 	/// let mut point = Point2D{ x: 100, y: 500 }; //Create a two-dimensional object, such as Point2D.
 	/// point.translate(50, -130); //The point's position is now [150, 370].
 	/// ```
-	fn translate(&mut self, dx: Coordinate, dy: Coordinate);
+	fn translate(&mut self, dx: Coordinate, dy: Coordinate) {
+		self.translate_with_policy(dx, dy, OverflowPolicy::WRAPPING).expect("Wrapping around never fails.");
+	}
+
+	/// Move the object across the two-dimensional space, with a choice of what to do if it moves
+	/// out of the range of `Coordinate`.
+	///
+	/// # Arguments
+	/// * `dx` - How far to move the object in the X direction.
+	/// * `dy` - How far to move the object in the Y direction.
+	/// * `policy` - What to do with coordinates that would go out of range.
+	///
+	/// # Result
+	/// An error if any coordinate would go out of range and the policy is `CHECKED`. The object is
+	/// then unchanged. With the other policies, this never fails.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{Coordinate, OverflowError, OverflowPolicy, Point2D, TwoDimensional};
+	/// let mut point = Point2D { x: Coordinate::MAX - 10, y: 0 };
+	/// assert_eq!(point.translate_with_policy(20, 5, OverflowPolicy::CHECKED), Err(OverflowError));
+	/// assert_eq!(point, Point2D { x: Coordinate::MAX - 10, y: 0 }, "The point is unchanged.");
+	/// point.translate_with_policy(20, 5, OverflowPolicy::SATURATING).unwrap();
+	/// assert_eq!(point, Point2D { x: Coordinate::MAX, y: 5 });
+	/// ```
+	fn translate_with_policy(&mut self, dx: Coordinate, dy: Coordinate, policy: OverflowPolicy) -> Result<(), OverflowError>;
+
+	/// Transform the object with an affine transformation.
+	///
+	/// # Arguments
+	/// * `transform` - The transformation to apply to every point of the object.
+	///
+	/// # Result
+	/// An error if any transformed coordinate would be out of range. The object is then unchanged.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{AffineTransform, Point2D, TwoDimensional};
+	/// let mut point = Point2D { x: 10, y: 20 };
+	/// let shear = AffineTransform { xy: 1.0, ..AffineTransform::identity() };
+	/// point.transform(&shear).unwrap();
+	/// assert_eq!(point, Point2D { x: 30, y: 20 });
+	/// ```
+	fn transform(&mut self, transform: &AffineTransform) -> Result<(), OverflowError>;
+
+	/// Rotate the object around a pivot point.
+	///
+	/// # Arguments
+	/// * `pivot` - The point to rotate around.
+	/// * `angle` - How far to rotate, in radians. Positive angles rotate counter-clockwise.
+	///
+	/// # Result
+	/// An error if any rotated coordinate would be out of range. The object is then unchanged.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{Point2D, TwoDimensional};
+	/// let mut point = Point2D { x: 100, y: 0 };
+	/// point.rotate(&Point2D { x: 0, y: 0 }, std::f64::consts::FRAC_PI_2).unwrap();
+	/// assert_eq!(point, Point2D { x: 0, y: 100 });
+	/// ```
+	fn rotate(&mut self, pivot: &Point2D, angle: f64) -> Result<(), OverflowError> {
+		self.transform(&AffineTransform::rotation(pivot, angle))
+	}
+
+	/// Scale the object by the same factor in all directions, relative to a pivot point.
+	///
+	/// # Arguments
+	/// * `pivot` - The point that stays in place.
+	/// * `factor` - How much to scale the object. Negative factors mirror it through the pivot.
+	///
+	/// # Result
+	/// An error if any scaled coordinate would be out of range. The object is then unchanged.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{Point2D, TwoDimensional};
+	/// let mut point = Point2D { x: 30, y: 40 };
+	/// point.scale(&Point2D { x: 10, y: 10 }, 2.0).unwrap();
+	/// assert_eq!(point, Point2D { x: 50, y: 70 });
+	/// ```
+	fn scale(&mut self, pivot: &Point2D, factor: f64) -> Result<(), OverflowError> {
+		self.transform(&AffineTransform::scaling(pivot, factor, factor))
+	}
+
+	/// Scale the object by different factors in the X and Y directions, relative to a pivot point.
+	///
+	/// # Arguments
+	/// * `pivot` - The point that stays in place.
+	/// * `factor_x` - How much to scale the object in the X direction.
+	/// * `factor_y` - How much to scale the object in the Y direction.
+	///
+	/// # Result
+	/// An error if any scaled coordinate would be out of range. The object is then unchanged.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{Point2D, TwoDimensional};
+	/// let mut point = Point2D { x: 30, y: 40 };
+	/// point.scale_non_uniform(&Point2D { x: 0, y: 0 }, 0.5, 3.0).unwrap();
+	/// assert_eq!(point, Point2D { x: 15, y: 120 });
+	/// ```
+	fn scale_non_uniform(&mut self, pivot: &Point2D, factor_x: f64, factor_y: f64) -> Result<(), OverflowError> {
+		self.transform(&AffineTransform::scaling(pivot, factor_x, factor_y))
+	}
+
+	/// Mirror the object across a line.
+	///
+	/// # Arguments
+	/// * `axis_start` - A point on the line to mirror across.
+	/// * `axis_end` - Another point on the line to mirror across.
+	///
+	/// # Result
+	/// An error if any mirrored coordinate would be out of range. The object is then unchanged.
+	///
+	/// # Examples
+	/// ```
+	/// use apex::{Point2D, TwoDimensional};
+	/// let mut point = Point2D { x: 30, y: 40 };
+	/// point.mirror(&Point2D { x: 10, y: 0 }, &Point2D { x: 10, y: 100 }).unwrap(); //Across the vertical line at X=10.
+	/// assert_eq!(point, Point2D { x: -10, y: 40 });
+	/// ```
+	fn mirror(&mut self, axis_start: &Point2D, axis_end: &Point2D) -> Result<(), OverflowError> {
+		self.transform(&AffineTransform::mirror(axis_start, axis_end))
+	}
 }
\ No newline at end of file